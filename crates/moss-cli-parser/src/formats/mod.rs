@@ -36,6 +36,9 @@ pub use self::yargs::YargsFormat;
 use crate::CliSpec;
 use std::sync::{OnceLock, RwLock};
 
+/// Score a format must exceed to be picked without further checks.
+pub const MIN_DETECT_SCORE: f64 = 0.5;
+
 /// Global registry of CLI format parsers.
 static FORMATS: RwLock<Vec<&'static dyn CliFormat>> = RwLock::new(Vec::new());
 static INITIALIZED: OnceLock<()> = OnceLock::new();
@@ -92,7 +95,7 @@ pub fn detect_format(help_text: &str) -> Option<&'static dyn CliFormat> {
         .unwrap()
         .iter()
         .map(|f| (*f, f.detect(help_text)))
-        .filter(|(_, score)| *score > 0.5)
+        .filter(|(_, score)| *score > MIN_DETECT_SCORE)
         .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
         .map(|(f, _)| f)
 }
@@ -103,6 +106,13 @@ pub fn list_formats() -> Vec<&'static str> {
     FORMATS.read().unwrap().iter().map(|f| f.name()).collect()
 }
 
+/// A format that could have produced some help text, with its detection score.
+pub struct FormatCandidate<'a> {
+    pub format: &'a dyn CliFormat,
+    /// Confidence from [`CliFormat::detect`], above 0.0
+    pub score: f64,
+}
+
 /// Registry of CLI format parsers.
 ///
 /// For most use cases, prefer the global registry via [`register()`],
@@ -146,14 +156,57 @@ impl FormatRegistry {
             .map(|f| f.as_ref())
     }
 
-    /// Auto-detect format from help text.
-    pub fn detect(&self, help_text: &str) -> Option<&dyn CliFormat> {
-        self.formats
+    /// Rank the formats that could have produced the help text.
+    ///
+    /// Returns every format with a non-zero score, highest first. Ties go to the
+    /// most recently registered format, so custom formats win over built-ins.
+    pub fn detect(&self, help_text: &str) -> Vec<FormatCandidate<'_>> {
+        let mut candidates: Vec<FormatCandidate<'_>> = self
+            .formats
             .iter()
-            .map(|f| (f, f.detect(help_text)))
-            .filter(|(_, score)| *score > 0.5)
-            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
-            .map(|(f, _)| f.as_ref())
+            .rev()
+            .map(|f| FormatCandidate {
+                format: f.as_ref(),
+                score: f.detect(help_text),
+            })
+            .filter(|c| c.score > 0.0)
+            .collect();
+        // Stable sort keeps the reversed registration order among ties
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+        candidates
+    }
+
+    /// The best-scoring format, if it is confident enough (score above
+    /// [`MIN_DETECT_SCORE`]).
+    pub fn detect_best(&self, help_text: &str) -> Option<&dyn CliFormat> {
+        self.detect(help_text)
+            .into_iter()
+            .next()
+            .filter(|c| c.score > MIN_DETECT_SCORE)
+            .map(|c| c.format)
+    }
+
+    /// Parse help text with the detected formats.
+    ///
+    /// Help text can look like more than one format (click and clap both print
+    /// `Usage:`, `Options:` and `Commands:`), so the candidates from
+    /// [`detect`](Self::detect) are tried best first, and the first that finds
+    /// options or commands wins. If none does, the top candidate's result is
+    /// returned when its score is above [`MIN_DETECT_SCORE`].
+    pub fn parse(&self, help_text: &str) -> Result<CliSpec, String> {
+        let candidates = self.detect(help_text);
+        for candidate in &candidates {
+            if let Ok(spec) = candidate.format.parse(help_text)
+                && (!spec.options.is_empty() || !spec.commands.is_empty())
+            {
+                return Ok(spec);
+            }
+        }
+
+        match candidates.first() {
+            Some(best) if best.score > MIN_DETECT_SCORE => best.format.parse(help_text),
+            _ => Err("Could not detect CLI help format".to_string()),
+        }
     }
 
     /// List all available format names.
@@ -167,3 +220,106 @@ impl Default for FormatRegistry {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Format with a fixed score that parses to a fixed number of options.
+    struct Fixed(&'static str, f64, usize);
+
+    impl CliFormat for Fixed {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        fn detect(&self, _help_text: &str) -> f64 {
+            self.1
+        }
+
+        fn parse(&self, _help_text: &str) -> Result<CliSpec, String> {
+            let option = crate::CliOption {
+                short: None,
+                long: Some(format!("--{}", self.0)),
+                value: None,
+                description: None,
+                default: None,
+                required: false,
+                env: None,
+            };
+            Ok(CliSpec {
+                name: Some(self.0.to_string()),
+                options: vec![option; self.2],
+                ..Default::default()
+            })
+        }
+    }
+
+    fn registry_of(formats: Vec<Fixed>) -> FormatRegistry {
+        let mut registry = FormatRegistry::empty();
+        for format in formats {
+            registry.register(Box::new(format));
+        }
+        registry
+    }
+
+    #[test]
+    fn test_detect_ranks_ambiguous_help() {
+        // Click help also has everything clap detection looks for
+        let help = "Usage: tool [OPTIONS] COMMAND [ARGS]...
+
+  A tool.
+
+Options:
+  -v, --verbose  Be loud.
+  --version      Show the version and exit.
+  --help         Show this message and exit.
+
+Commands:
+  run   Run it.
+  stop  Stop it.
+";
+        let registry = FormatRegistry::new();
+        let ranked: Vec<&str> = registry
+            .detect(help)
+            .iter()
+            .filter(|c| c.score > MIN_DETECT_SCORE)
+            .map(|c| c.format.name())
+            .collect();
+        assert_eq!(ranked, vec!["click", "clap"]);
+        assert_eq!(registry.detect_best(help).map(|f| f.name()), Some("click"));
+
+        let spec = registry.parse(help).unwrap();
+        assert_eq!(spec.commands.len(), 2);
+    }
+
+    #[test]
+    fn test_detect_ties_prefer_later_registration() {
+        let registry = registry_of(vec![
+            Fixed("a", 0.8, 1),
+            Fixed("b", 0.8, 1),
+            Fixed("c", 0.0, 1),
+        ]);
+        let ranked: Vec<&str> = registry
+            .detect("")
+            .iter()
+            .map(|c| c.format.name())
+            .collect();
+        assert_eq!(ranked, vec!["b", "a"]);
+    }
+
+    #[test]
+    fn test_parse_falls_through_to_next_candidate() {
+        // The best match finds nothing; the runner-up finds an option
+        let registry = registry_of(vec![Fixed("empty", 0.9, 0), Fixed("real", 0.4, 1)]);
+        assert_eq!(registry.parse("").unwrap().name.as_deref(), Some("real"));
+
+        // Nothing finds anything: keep a confident top candidate's result
+        let registry = registry_of(vec![Fixed("empty", 0.9, 0), Fixed("other", 0.6, 0)]);
+        assert_eq!(registry.parse("").unwrap().name.as_deref(), Some("empty"));
+
+        let registry = registry_of(vec![Fixed("weak", 0.4, 0)]);
+        assert!(registry.parse("").is_err());
+        assert!(registry.detect_best("").is_none());
+    }
+}
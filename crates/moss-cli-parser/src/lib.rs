@@ -27,7 +27,10 @@
 
 mod formats;
 
-pub use formats::{CliFormat, FormatRegistry, detect_format, get_format, list_formats, register};
+pub use formats::{
+    CliFormat, FormatCandidate, FormatRegistry, MIN_DETECT_SCORE, detect_format, get_format,
+    list_formats, register,
+};
 
 use serde::{Deserialize, Serialize};
 
@@ -83,15 +86,10 @@ pub struct CliCommand {
 }
 
 /// Parse help text, auto-detecting the format.
+///
+/// See [`FormatRegistry::parse`] for how ambiguous help text is handled.
 pub fn parse_help(help_text: &str) -> Result<CliSpec, String> {
-    let registry = FormatRegistry::new();
-
-    // Try to detect format
-    if let Some(format) = registry.detect(help_text) {
-        format.parse(help_text)
-    } else {
-        Err("Could not detect CLI help format".to_string())
-    }
+    FormatRegistry::new().parse(help_text)
 }
 
 /// Parse help text with a specific format.
@@ -0,0 +1,22 @@
+//! Errors from code generators.
+//!
+//! Built-in generators always succeed; generators backed by an external
+//! command can fail, and report it to the caller instead of exiting.
+
+use std::fmt;
+
+/// A generator failed to produce code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenerateError {
+    /// Name of the generator that failed
+    pub generator: String,
+    pub message: String,
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "generator {}: {}", self.generator, self.message)
+    }
+}
+
+impl std::error::Error for GenerateError {}
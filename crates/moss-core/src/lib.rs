@@ -1,7 +1,17 @@
 //! Core traits for moss.
 //!
-//! This crate provides foundational traits used across moss sub-crates.
+//! This crate provides foundational traits used across moss sub-crates, plus
+//! spec loading shared by every consumer of OpenAPI specs and JSON Schemas and
+//! the preamble options and error type shared by their generators.
 
+mod generate;
 mod merge;
+mod preamble;
+mod spec;
 
-pub use merge::Merge;
+pub use generate::GenerateError;
+pub use merge::{Merge, MergeAppend, MergeExtend};
+pub use preamble::GeneratorPreamble;
+pub use spec::{
+    LoadError, SpecFormat, load_spec_file, object_entries, object_entries_mut, parse_spec,
+};
@@ -137,27 +137,39 @@ impl<T: Merge> Merge for Option<T> {
     }
 }
 
-// === Collections: extend/merge ===
+// === Collections: replace/merge ===
 
 impl<T> Merge for Vec<T> {
-    /// Vectors: other replaces self entirely (not appended)
+    /// Vectors: other replaces self entirely; use `#[merge(strategy = "append")]` to concatenate
     fn merge(self, other: Self) -> Self {
         other
     }
 }
 
-impl<K: Eq + std::hash::Hash, V> Merge for HashMap<K, V> {
-    /// HashMaps: other's keys override self's
+impl<K: Eq + std::hash::Hash, V: Merge> Merge for HashMap<K, V> {
+    /// HashMaps: key-wise, values present on both sides are merged recursively
     fn merge(mut self, other: Self) -> Self {
-        self.extend(other);
+        for (key, value) in other {
+            let merged = match self.remove(&key) {
+                Some(existing) => existing.merge(value),
+                None => value,
+            };
+            self.insert(key, merged);
+        }
         self
     }
 }
 
-impl<K: Ord, V> Merge for BTreeMap<K, V> {
-    /// BTreeMaps: other's keys override self's
+impl<K: Ord, V: Merge> Merge for BTreeMap<K, V> {
+    /// BTreeMaps: key-wise, values present on both sides are merged recursively
     fn merge(mut self, other: Self) -> Self {
-        self.extend(other);
+        for (key, value) in other {
+            let merged = match self.remove(&key) {
+                Some(existing) => existing.merge(value),
+                None => value,
+            };
+            self.insert(key, merged);
+        }
         self
     }
 }
@@ -178,6 +190,82 @@ impl<T: Ord> Merge for BTreeSet<T> {
     }
 }
 
+// === Field strategies: #[merge(strategy = "...")] ===
+
+/// Concatenation, selected with `#[merge(strategy = "append")]`.
+pub trait MergeAppend {
+    fn merge_append(self, other: Self) -> Self;
+}
+
+impl<T> MergeAppend for Vec<T> {
+    fn merge_append(mut self, other: Self) -> Self {
+        self.extend(other);
+        self
+    }
+}
+
+impl<T: MergeAppend> MergeAppend for Option<T> {
+    fn merge_append(self, other: Self) -> Self {
+        match (self, other) {
+            (Some(a), Some(b)) => Some(a.merge_append(b)),
+            (None, b) => b,
+            (a, None) => a,
+        }
+    }
+}
+
+/// Shallow `Extend`, selected with `#[merge(strategy = "extend")]`.
+///
+/// For maps, other's keys override self's without merging values.
+pub trait MergeExtend {
+    fn merge_extend(self, other: Self) -> Self;
+}
+
+impl<T> MergeExtend for Vec<T> {
+    fn merge_extend(mut self, other: Self) -> Self {
+        self.extend(other);
+        self
+    }
+}
+
+impl<K: Eq + std::hash::Hash, V> MergeExtend for HashMap<K, V> {
+    fn merge_extend(mut self, other: Self) -> Self {
+        self.extend(other);
+        self
+    }
+}
+
+impl<K: Ord, V> MergeExtend for BTreeMap<K, V> {
+    fn merge_extend(mut self, other: Self) -> Self {
+        self.extend(other);
+        self
+    }
+}
+
+impl<T: Eq + std::hash::Hash> MergeExtend for HashSet<T> {
+    fn merge_extend(mut self, other: Self) -> Self {
+        self.extend(other);
+        self
+    }
+}
+
+impl<T: Ord> MergeExtend for BTreeSet<T> {
+    fn merge_extend(mut self, other: Self) -> Self {
+        self.extend(other);
+        self
+    }
+}
+
+impl<T: MergeExtend> MergeExtend for Option<T> {
+    fn merge_extend(self, other: Self) -> Self {
+        match (self, other) {
+            (Some(a), Some(b)) => Some(a.merge_extend(b)),
+            (None, b) => b,
+            (a, None) => a,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -222,4 +310,30 @@ mod tests {
         assert_eq!(merged.get("b"), Some(&3)); // b wins
         assert_eq!(merged.get("c"), Some(&4));
     }
+
+    #[test]
+    fn test_vec_merge_replaces() {
+        assert_eq!(vec![1, 2].merge(vec![3]), vec![3]);
+        assert_eq!(vec![1, 2].merge_append(vec![3]), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_hashmap_merge_recursive() {
+        let a: HashMap<&str, Option<i32>> = [("x", Some(1)), ("y", Some(2))].into_iter().collect();
+        let b: HashMap<&str, Option<i32>> = [("y", None)].into_iter().collect();
+
+        let merged = a.merge(b);
+        assert_eq!(merged["x"], Some(1));
+        assert_eq!(merged["y"], Some(2)); // values merged, not replaced
+    }
+
+    #[test]
+    fn test_hashmap_merge_extend() {
+        let a: HashMap<&str, Vec<i32>> = [("x", vec![1]), ("y", vec![2])].into_iter().collect();
+        let b: HashMap<&str, Vec<i32>> = [("y", vec![3])].into_iter().collect();
+
+        let merged = a.merge_extend(b);
+        assert_eq!(merged["x"], vec![1]);
+        assert_eq!(merged["y"], vec![3]); // b wins
+    }
 }
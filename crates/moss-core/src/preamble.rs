@@ -0,0 +1,93 @@
+//! Custom preambles for generated code.
+//!
+//! Generators start their output with a banner comment such as
+//! `// Auto-generated from OpenAPI spec`. A preamble can put a header ahead of
+//! it (an SPDX license line, a `# pylint: disable=...` directive) and can drop
+//! the banner altogether.
+
+/// Text placed at the top of generated code.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GeneratorPreamble {
+    /// Prepended verbatim; a trailing newline is added if missing
+    pub header: Option<String>,
+    /// Leave out the generator's banner
+    pub no_banner: bool,
+}
+
+impl GeneratorPreamble {
+    /// Whether applying this preamble leaves code unchanged.
+    pub fn is_empty(&self) -> bool {
+        self.header.is_none() && !self.no_banner
+    }
+
+    /// Apply to generated `code`: with `no_banner`, drop its leading comment
+    /// block (lines starting with `//` or `#`) and the blank lines after it,
+    /// then prepend the header.
+    pub fn apply(&self, code: &str) -> String {
+        let body = if self.no_banner {
+            strip_banner(code)
+        } else {
+            code
+        };
+        let mut out = String::new();
+        if let Some(header) = &self.header {
+            out.push_str(header);
+            if !header.ends_with('\n') {
+                out.push('\n');
+            }
+        }
+        out.push_str(body);
+        out
+    }
+}
+
+/// `code` without its leading comment block and the blank lines following it.
+fn strip_banner(code: &str) -> &str {
+    let mut rest = code;
+    let mut in_banner = true;
+    while !rest.is_empty() {
+        let (line, next) = match rest.find('\n') {
+            Some(i) => (&rest[..i], &rest[i + 1..]),
+            None => (rest, ""),
+        };
+        let line = line.trim();
+        let comment = line.starts_with("//") || line.starts_with('#');
+        if !(line.is_empty() || (in_banner && comment)) {
+            break;
+        }
+        in_banner &= comment;
+        rest = next;
+    }
+    rest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_preamble() {
+        let code =
+            "//! Auto-generated from OpenAPI spec\n//! Uses ureq\n\nuse serde::Deserialize;\n";
+        assert_eq!(GeneratorPreamble::default().apply(code), code);
+
+        let header = GeneratorPreamble {
+            header: Some("// SPDX-License-Identifier: MIT".to_string()),
+            no_banner: false,
+        };
+        assert_eq!(
+            header.apply(code),
+            format!("// SPDX-License-Identifier: MIT\n{}", code)
+        );
+
+        let no_banner = GeneratorPreamble {
+            header: Some("# pylint: disable=all\n\n".to_string()),
+            no_banner: true,
+        };
+        assert_eq!(
+            no_banner
+                .apply("# Auto-generated from JSON Schema\n\nfrom typing import Any\n\n# Types\n"),
+            "# pylint: disable=all\n\nfrom typing import Any\n\n# Types\n"
+        );
+    }
+}
@@ -0,0 +1,291 @@
+//! Loading API specs and schemas written in JSON or YAML.
+//!
+//! OpenAPI specs and JSON Schemas are commonly authored in either format. Input
+//! is detected by file extension, then by content: a leading `{` or `[` is JSON,
+//! anything else is YAML. Either way the result is a `serde_json::Value`.
+//!
+//! Generators walk objects with [`object_entries`] so that output doesn't
+//! depend on how `serde_json` was built: its maps keep insertion order only
+//! with its `preserve_order` feature, which any crate in a build can enable.
+
+use std::fmt;
+use std::path::Path;
+
+/// Serialization format of a spec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecFormat {
+    Json,
+    Yaml,
+}
+
+impl SpecFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpecFormat::Json => "JSON",
+            SpecFormat::Yaml => "YAML",
+        }
+    }
+
+    /// Format implied by a file extension, if any.
+    pub fn from_path(path: &Path) -> Option<SpecFormat> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "json" => Some(SpecFormat::Json),
+            "yaml" | "yml" => Some(SpecFormat::Yaml),
+            _ => None,
+        }
+    }
+
+    /// Format implied by content: JSON documents start with `{` or `[`.
+    pub fn sniff(content: &str) -> SpecFormat {
+        match content
+            .trim_start_matches('\u{feff}')
+            .trim_start()
+            .chars()
+            .next()
+        {
+            Some('{') | Some('[') => SpecFormat::Json,
+            _ => SpecFormat::Yaml,
+        }
+    }
+}
+
+/// Error loading a spec.
+#[derive(Debug)]
+pub enum LoadError {
+    /// The spec file could not be read
+    Io { path: String, message: String },
+    /// The spec is not valid in its detected format
+    Parse {
+        /// File path, or `<input>` for inline content
+        origin: String,
+        format: SpecFormat,
+        line: Option<usize>,
+        column: Option<usize>,
+        message: String,
+    },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io { path, message } => write!(f, "failed to read {}: {}", path, message),
+            LoadError::Parse {
+                origin,
+                format,
+                line,
+                column,
+                message,
+            } => {
+                write!(f, "{}", origin)?;
+                if let Some(line) = line {
+                    write!(f, ":{}", line)?;
+                    if let Some(column) = column {
+                        write!(f, ":{}", column)?;
+                    }
+                }
+                write!(f, ": invalid {}: {}", format.as_str(), message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// Load a spec from a file. The format is detected by extension (`.json`,
+/// `.yaml`, `.yml`) and falls back to content sniffing.
+pub fn load_spec_file(path: &Path) -> Result<serde_json::Value, LoadError> {
+    let origin = path.to_string_lossy();
+    let content = std::fs::read_to_string(path).map_err(|e| LoadError::Io {
+        path: origin.to_string(),
+        message: e.to_string(),
+    })?;
+    let format = SpecFormat::from_path(path).unwrap_or_else(|| SpecFormat::sniff(&content));
+    parse_spec(&content, format, &origin)
+}
+
+/// Parse spec content in a known format. `origin` names the input in errors.
+pub fn parse_spec(
+    content: &str,
+    format: SpecFormat,
+    origin: &str,
+) -> Result<serde_json::Value, LoadError> {
+    let parse_error = |line, column, message: String| LoadError::Parse {
+        origin: origin.to_string(),
+        format,
+        line,
+        column,
+        message,
+    };
+    match format {
+        SpecFormat::Json => serde_json::from_str(content).map_err(|e| {
+            // serde_json appends " at line X column Y"; location is reported separately
+            let message = e.to_string();
+            let message = match message.rfind(" at line ") {
+                Some(i) => message[..i].to_string(),
+                None => message,
+            };
+            parse_error(Some(e.line()), Some(e.column()), message)
+        }),
+        SpecFormat::Yaml => {
+            let value: serde_yaml::Value = serde_yaml::from_str(content).map_err(|e| {
+                let location = e.location();
+                let message = e.to_string();
+                let message = match message.rfind(" at line ") {
+                    Some(i) => message[..i].to_string(),
+                    None => message,
+                };
+                parse_error(
+                    location.as_ref().map(|l| l.line()),
+                    location.as_ref().map(|l| l.column()),
+                    message,
+                )
+            })?;
+            yaml_to_json(value).map_err(|message| parse_error(None, None, message))
+        }
+    }
+}
+
+/// Iterate over the entries of a JSON object in a fixed order: as written in
+/// the spec with the `preserve_order` feature, alphabetically by key otherwise.
+pub fn object_entries(
+    map: &serde_json::Map<String, serde_json::Value>,
+) -> impl Iterator<Item = (&String, &serde_json::Value)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    if !cfg!(feature = "preserve_order") {
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+    }
+    entries.into_iter()
+}
+
+/// [`object_entries`] with mutable values.
+pub fn object_entries_mut(
+    map: &mut serde_json::Map<String, serde_json::Value>,
+) -> impl Iterator<Item = (&String, &mut serde_json::Value)> {
+    let mut entries: Vec<_> = map.iter_mut().collect();
+    if !cfg!(feature = "preserve_order") {
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+    }
+    entries.into_iter()
+}
+
+/// Convert YAML to JSON. YAML allows non-string keys (OpenAPI response codes
+/// are often written `200:`); scalar keys are stringified.
+fn yaml_to_json(value: serde_yaml::Value) -> Result<serde_json::Value, String> {
+    use serde_json::Value as Json;
+    use serde_yaml::Value as Yaml;
+
+    Ok(match value {
+        Yaml::Null => Json::Null,
+        Yaml::Bool(b) => Json::Bool(b),
+        Yaml::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Json::from(i)
+            } else if let Some(u) = n.as_u64() {
+                Json::from(u)
+            } else {
+                let f = n.as_f64().unwrap_or(f64::NAN);
+                serde_json::Number::from_f64(f)
+                    .map(Json::Number)
+                    .ok_or_else(|| format!("number {} has no JSON representation", f))?
+            }
+        }
+        Yaml::String(s) => Json::String(s),
+        Yaml::Sequence(items) => Json::Array(
+            items
+                .into_iter()
+                .map(yaml_to_json)
+                .collect::<Result<_, _>>()?,
+        ),
+        Yaml::Mapping(map) => {
+            let mut object = serde_json::Map::new();
+            for (key, value) in map {
+                let key = match key {
+                    Yaml::String(s) => s,
+                    Yaml::Number(n) => n.to_string(),
+                    Yaml::Bool(b) => b.to_string(),
+                    Yaml::Null => "null".to_string(),
+                    other => return Err(format!("unsupported mapping key: {:?}", other)),
+                };
+                object.insert(key, yaml_to_json(value)?);
+            }
+            Json::Object(object)
+        }
+        Yaml::Tagged(tagged) => yaml_to_json(tagged.value)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_sniff() {
+        assert_eq!(SpecFormat::sniff("  {\"a\": 1}"), SpecFormat::Json);
+        assert_eq!(SpecFormat::sniff("[1]"), SpecFormat::Json);
+        assert_eq!(SpecFormat::sniff("openapi: 3.0.0"), SpecFormat::Yaml);
+        assert_eq!(
+            SpecFormat::from_path(Path::new("api.YML")),
+            Some(SpecFormat::Yaml)
+        );
+        assert_eq!(SpecFormat::from_path(Path::new("api.txt")), None);
+    }
+
+    #[test]
+    fn test_parse_yaml_content() {
+        let spec = parse_spec("openapi: 3.0.0\npaths:\n  /users:\n    get:\n      responses:\n        200:\n          description: ok\n", SpecFormat::Yaml, "<input>").unwrap();
+        assert_eq!(spec["openapi"], json!("3.0.0"));
+        assert_eq!(
+            spec["paths"]["/users"]["get"]["responses"]["200"]["description"],
+            json!("ok")
+        );
+    }
+
+    #[test]
+    fn test_parse_json_content() {
+        let spec = parse_spec(r#"{"openapi": "3.1.0"}"#, SpecFormat::Json, "<input>").unwrap();
+        assert_eq!(spec, json!({"openapi": "3.1.0"}));
+    }
+
+    #[test]
+    fn test_errors_are_precise() {
+        let err = parse_spec("{\n  \"a\": 1,\n  oops\n}", SpecFormat::Json, "<input>").unwrap_err();
+        let message = err.to_string();
+        assert!(message.starts_with("<input>:3:"), "{}", message);
+        assert!(message.contains("invalid JSON"), "{}", message);
+
+        let err = parse_spec("a: 1\n b: [\n", SpecFormat::Yaml, "<input>").unwrap_err();
+        assert!(err.to_string().contains("invalid YAML"), "{}", err);
+
+        let err = load_spec_file(Path::new("does/not/exist.yaml")).unwrap_err();
+        assert!(matches!(err, LoadError::Io { .. }), "{}", err);
+    }
+
+    #[test]
+    fn test_load_spec_file() {
+        // No extension: the format is sniffed from the content
+        let path = std::env::temp_dir().join(format!("spec-{}", std::process::id()));
+        std::fs::write(&path, "openapi: 3.0.0\n").unwrap();
+        let spec = load_spec_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(spec.unwrap(), json!({"openapi": "3.0.0"}));
+    }
+
+    #[test]
+    fn test_object_entries() {
+        let spec = parse_spec(
+            "zebra: 1\napple: 2\nmango: 3\n",
+            SpecFormat::Yaml,
+            "<input>",
+        )
+        .unwrap();
+        let keys: Vec<&str> = object_entries(spec.as_object().unwrap())
+            .map(|(key, _)| key.as_str())
+            .collect();
+        if cfg!(feature = "preserve_order") {
+            assert_eq!(keys, ["zebra", "apple", "mango"]);
+        } else {
+            assert_eq!(keys, ["apple", "mango", "zebra"]);
+        }
+    }
+}
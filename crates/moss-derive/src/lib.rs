@@ -2,12 +2,16 @@
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{Data, DeriveInput, Fields, parse_macro_input};
+use syn::{Data, DeriveInput, Field, Fields, LitStr, parse_macro_input};
 
 /// Derive the `Merge` trait for a struct.
 ///
 /// Generates an implementation that calls `.merge()` on each field.
-/// All fields must implement `Merge`.
+/// All fields must implement `Merge`, unless a strategy overrides it:
+///
+/// - `#[merge(strategy = "replace")]` - other's value wins wholesale
+/// - `#[merge(strategy = "append")]` - concatenate (`MergeAppend`)
+/// - `#[merge(strategy = "extend")]` - shallow extend, other's keys win (`MergeExtend`)
 ///
 /// # Example
 ///
@@ -18,9 +22,11 @@ use syn::{Data, DeriveInput, Fields, parse_macro_input};
 /// struct Config {
 ///     enabled: bool,
 ///     name: Option<String>,
+///     #[merge(strategy = "extend")]
+///     aliases: HashMap<String, Vec<String>>,
 /// }
 /// ```
-#[proc_macro_derive(Merge)]
+#[proc_macro_derive(Merge, attributes(merge))]
 pub fn derive_merge(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
@@ -30,12 +36,15 @@ pub fn derive_merge(input: TokenStream) -> TokenStream {
     let merge_impl = match &input.data {
         Data::Struct(data) => match &data.fields {
             Fields::Named(fields) => {
-                let field_merges = fields.named.iter().map(|f| {
+                let mut field_merges = Vec::new();
+                for f in &fields.named {
                     let field_name = &f.ident;
-                    quote! {
-                        #field_name: ::rhizome_moss_core::Merge::merge(self.#field_name, other.#field_name)
-                    }
-                });
+                    let merge = match field_merge(f, quote!(#field_name)) {
+                        Ok(m) => m,
+                        Err(e) => return e.to_compile_error().into(),
+                    };
+                    field_merges.push(quote! { #field_name: #merge });
+                }
                 quote! {
                     Self {
                         #(#field_merges),*
@@ -43,12 +52,14 @@ pub fn derive_merge(input: TokenStream) -> TokenStream {
                 }
             }
             Fields::Unnamed(fields) => {
-                let field_merges = (0..fields.unnamed.len()).map(|i| {
+                let mut field_merges = Vec::new();
+                for (i, f) in fields.unnamed.iter().enumerate() {
                     let index = syn::Index::from(i);
-                    quote! {
-                        ::rhizome_moss_core::Merge::merge(self.#index, other.#index)
+                    match field_merge(f, quote!(#index)) {
+                        Ok(m) => field_merges.push(m),
+                        Err(e) => return e.to_compile_error().into(),
                     }
-                });
+                }
                 quote! {
                     Self(#(#field_merges),*)
                 }
@@ -77,3 +88,44 @@ pub fn derive_merge(input: TokenStream) -> TokenStream {
 
     TokenStream::from(expanded)
 }
+
+/// Build the merge expression for one field, honoring `#[merge(strategy = "...")]`.
+fn field_merge(
+    field: &Field,
+    access: proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut strategy: Option<LitStr> = None;
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("merge")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("strategy") {
+                strategy = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `strategy = \"...\"`"))
+            }
+        })?;
+    }
+
+    let Some(strategy) = strategy else {
+        return Ok(quote! {
+            ::rhizome_moss_core::Merge::merge(self.#access, other.#access)
+        });
+    };
+
+    match strategy.value().as_str() {
+        "replace" => Ok(quote! { other.#access }),
+        "append" => Ok(quote! {
+            ::rhizome_moss_core::MergeAppend::merge_append(self.#access, other.#access)
+        }),
+        "extend" => Ok(quote! {
+            ::rhizome_moss_core::MergeExtend::merge_extend(self.#access, other.#access)
+        }),
+        other => Err(syn::Error::new_spanned(
+            &strategy,
+            format!(
+                "unknown merge strategy `{}` (expected \"replace\", \"append\", or \"extend\")",
+                other
+            ),
+        )),
+    }
+}
@@ -7,21 +7,28 @@
 //! Users can register custom generators via [`register()`]:
 //!
 //! ```ignore
-//! use rhizome_moss_jsonschema::{JsonSchemaGenerator, register};
+//! use rhizome_moss_jsonschema::{GenerateError, JsonSchemaGenerator, register};
 //! use serde_json::Value;
 //!
 //! struct MyGenerator;
 //!
 //! impl JsonSchemaGenerator for MyGenerator {
 //!     fn language(&self) -> &'static str { "mylang" }
-//!     fn generate(&self, schema: &Value, root_name: &str) -> String { /* ... */ }
+//!     fn generate(&self, schema: &Value, root_name: &str) -> Result<String, GenerateError> { /* ... */ }
 //! }
 //!
 //! // Register before first use
 //! register(&MyGenerator);
 //! ```
+//!
+//! Output can start with a custom header, or without the banner, through
+//! [`apply_preamble`].
+
+pub use rhizome_moss_core::{GenerateError, GeneratorPreamble};
 
+use rhizome_moss_core::{object_entries, object_entries_mut};
 use serde_json::Value;
+use std::collections::{HashMap, HashSet};
 use std::sync::{OnceLock, RwLock};
 
 /// A type generator for a specific language.
@@ -30,7 +37,21 @@ pub trait JsonSchemaGenerator: Send + Sync {
     fn language(&self) -> &'static str;
 
     /// Generate type definitions from JSON Schema.
-    fn generate(&self, schema: &Value, root_name: &str) -> String;
+    ///
+    /// Fails only for generators that delegate to something that can fail,
+    /// such as an external command.
+    fn generate(&self, schema: &Value, root_name: &str) -> Result<String, GenerateError>;
+}
+
+/// Generate type definitions under a custom preamble, applied to the
+/// generator's output.
+pub fn apply_preamble(
+    generator: &dyn JsonSchemaGenerator,
+    schema: &Value,
+    root_name: &str,
+    preamble: &GeneratorPreamble,
+) -> Result<String, GenerateError> {
+    Ok(preamble.apply(&generator.generate(schema, root_name)?))
 }
 
 /// Global registry of generator plugins.
@@ -99,7 +120,7 @@ impl JsonSchemaGenerator for GeneratorWrapper {
         self.0.language()
     }
 
-    fn generate(&self, schema: &Value, root_name: &str) -> String {
+    fn generate(&self, schema: &Value, root_name: &str) -> Result<String, GenerateError> {
         self.0.generate(schema, root_name)
     }
 }
@@ -124,7 +145,8 @@ impl JsonSchemaGenerator for TypeScriptGenerator {
         "typescript"
     }
 
-    fn generate(&self, schema: &Value, root_name: &str) -> String {
+    fn generate(&self, schema: &Value, root_name: &str) -> Result<String, GenerateError> {
+        let schema = &promote_inline_enums(schema, root_name);
         let mut out = String::new();
         out.push_str("// Auto-generated from JSON Schema\n\n");
 
@@ -134,7 +156,7 @@ impl JsonSchemaGenerator for TypeScriptGenerator {
             .or_else(|| schema.get("$defs"))
             .and_then(|d| d.as_object())
         {
-            for (name, def_schema) in defs {
+            for (name, def_schema) in object_entries(defs) {
                 out.push_str(&generate_ts_type(name, def_schema, 0));
                 out.push('\n');
             }
@@ -142,7 +164,7 @@ impl JsonSchemaGenerator for TypeScriptGenerator {
 
         // Generate root type
         out.push_str(&generate_ts_type(root_name, schema, 0));
-        out
+        Ok(out)
     }
 }
 
@@ -189,6 +211,11 @@ fn generate_ts_type(name: &str, schema: &Value, depth: usize) -> String {
     // Handle object type
     let type_str = schema.get("type").and_then(|t| t.as_str());
     if type_str == Some("object") || schema.get("properties").is_some() {
+        let mut nested = String::new();
+        if is_strict_object(schema) {
+            // Structural typing accepts extra properties; say so for readers
+            out.push_str("/** Strict: unknown properties are not allowed (additionalProperties: false). */\n");
+        }
         out.push_str(&format!("export interface {} {{\n", name));
         if let Some(props) = schema.get("properties").and_then(|p| p.as_object()) {
             let required: Vec<&str> = schema
@@ -197,8 +224,16 @@ fn generate_ts_type(name: &str, schema: &Value, depth: usize) -> String {
                 .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
                 .unwrap_or_default();
 
-            for (prop_name, prop_schema) in props {
-                let ts_type = schema_to_ts(prop_schema);
+            for (prop_name, prop_schema) in object_entries(props) {
+                let ts_type = match nullable_object(prop_schema) {
+                    Some(inner) => {
+                        let nested_name = nested_type_name(name, prop_name);
+                        nested.push_str(&generate_ts_type(&nested_name, &inner, 0));
+                        nested.push('\n');
+                        format!("{} | null", nested_name)
+                    }
+                    None => schema_to_ts(prop_schema),
+                };
                 let opt = if required.contains(&prop_name.as_str()) {
                     ""
                 } else {
@@ -209,7 +244,7 @@ fn generate_ts_type(name: &str, schema: &Value, depth: usize) -> String {
             }
         }
         out.push_str("}\n");
-        return out;
+        return nested + &out;
     }
 
     // Simple type alias
@@ -224,14 +259,9 @@ fn schema_to_ts(schema: &Value) -> String {
         return ref_path.split('/').last().unwrap_or("unknown").to_string();
     }
 
-    // Handle type array (nullable)
-    if let Some(arr) = schema.get("type").and_then(|t| t.as_array()) {
-        let types: Vec<&str> = arr.iter().filter_map(|v| v.as_str()).collect();
-        let non_null: Vec<_> = types.iter().filter(|t| **t != "null").collect();
-        if non_null.len() == 1 {
-            let base = type_to_ts(non_null[0]);
-            return format!("{} | null", base);
-        }
+    // Handle nullable
+    if let Some(inner) = non_null_schema(schema) {
+        return format!("{} | null", schema_to_ts(&inner));
     }
 
     let type_str = schema.get("type").and_then(|t| t.as_str());
@@ -254,6 +284,30 @@ fn schema_to_ts(schema: &Value) -> String {
         };
     }
 
+    // Inline object (such as an inlined `$ref`): an object literal type
+    if let Some(props) = schema
+        .get("properties")
+        .and_then(|p| p.as_object())
+        .filter(|p| !p.is_empty())
+    {
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(|r| r.as_array())
+            .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+        let fields: Vec<String> = object_entries(props)
+            .map(|(name, prop)| {
+                let opt = if required.contains(&name.as_str()) {
+                    ""
+                } else {
+                    "?"
+                };
+                format!("{}{}: {}", name, opt, schema_to_ts(prop))
+            })
+            .collect();
+        return format!("{{ {} }}", fields.join("; "));
+    }
+
     type_str
         .map(type_to_ts)
         .unwrap_or_else(|| "unknown".to_string())
@@ -279,11 +333,12 @@ impl JsonSchemaGenerator for PythonGenerator {
         "python"
     }
 
-    fn generate(&self, schema: &Value, root_name: &str) -> String {
-        let mut out = String::new();
-        out.push_str("# Auto-generated from JSON Schema\n\n");
-        out.push_str("from dataclasses import dataclass\n");
-        out.push_str("from typing import Any, Literal, Optional, Union\n\n");
+    fn generate(&self, schema: &Value, root_name: &str) -> Result<String, GenerateError> {
+        let schema = &promote_inline_enums(schema, root_name);
+        let mut types = String::new();
+        let mut parsers = String::new();
+        // Dataclasses whose parse helpers reject unknown keys
+        let mut strict = Vec::new();
 
         // Handle definitions/$defs first
         if let Some(defs) = schema
@@ -291,18 +346,205 @@ impl JsonSchemaGenerator for PythonGenerator {
             .or_else(|| schema.get("$defs"))
             .and_then(|d| d.as_object())
         {
+            // Enum aliases go first, so the dataclass annotations naming them resolve
+            let mut defs: Vec<_> = object_entries(defs).collect();
+            defs.sort_by_key(|(_, def)| def.get("enum").is_none());
             for (name, def_schema) in defs {
-                out.push_str(&generate_py_type(name, def_schema));
-                out.push('\n');
+                types.push_str(&generate_py_type(name, def_schema));
+                types.push('\n');
+                if is_strict_object(def_schema) {
+                    strict.push(name.as_str());
+                }
+                if let Some(parser) = generate_py_union_parser(name, def_schema, schema) {
+                    parsers.push('\n');
+                    parsers.push_str(&parser);
+                }
             }
         }
 
         // Generate root type
-        out.push_str(&generate_py_type(root_name, schema));
-        out
+        types.push_str(&generate_py_type(root_name, schema));
+        if let Some(parser) = generate_py_union_parser(root_name, schema, schema) {
+            parsers.push('\n');
+            parsers.push_str(&parser);
+        }
+
+        let mut out = String::new();
+        out.push_str("# Auto-generated from JSON Schema\n\n");
+        if parsers.is_empty() {
+            out.push_str("from dataclasses import dataclass\n");
+        } else {
+            out.push_str("from dataclasses import dataclass, fields\n");
+        }
+        out.push_str("from typing import Any, Literal, Optional, Union\n\n");
+        out.push_str(&types);
+        if is_strict_object(schema) {
+            strict.push(root_name);
+        }
+        if !parsers.is_empty() && strict.is_empty() {
+            out.push_str("\n\ndef _construct(cls, data: dict):\n");
+            out.push_str(
+                "    return cls(**{f.name: data[f.name] for f in fields(cls) if f.name in data})\n",
+            );
+            out.push_str(&parsers);
+        } else if !parsers.is_empty() {
+            // additionalProperties: false
+            out.push_str(&format!("\n_STRICT = {{{}}}\n", strict.join(", ")));
+            out.push_str("\n\ndef _construct(cls, data: dict):\n");
+            out.push_str("    names = {f.name for f in fields(cls)}\n");
+            out.push_str("    unknown = sorted(set(data) - names)\n");
+            out.push_str("    if unknown and cls in _STRICT:\n");
+            out.push_str(
+                "        raise ValueError(f\"unknown keys for {cls.__name__}: {unknown}\")\n",
+            );
+            out.push_str("    return cls(**{key: data[key] for key in names if key in data})\n");
+            out.push_str(&parsers);
+        }
+        Ok(out)
     }
 }
 
+/// `parse_<name>(data)` for a `oneOf`/`anyOf` union of object types, which builds
+/// the dataclass of the variant `data` is.
+///
+/// With a `discriminator` (`propertyName` and optional `mapping`, as in OpenAPI),
+/// the variant is picked by that property's value; without `mapping`, each
+/// variant's value is its `const` (or single-value `enum`) for the property, or
+/// else its type name. Otherwise variants are told apart by their required keys:
+/// for `oneOf`, exactly one variant's required keys must all be present, for
+/// `anyOf` the first such variant wins. Only variants that `$ref` an object type
+/// can be constructed; other variants are left out. Returns None if no variant
+/// can be constructed.
+fn generate_py_union_parser(name: &str, schema: &Value, root: &Value) -> Option<String> {
+    let (variants, one_of) = match (schema.get("oneOf"), schema.get("anyOf")) {
+        (Some(v), _) => (v.as_array()?, true),
+        (None, Some(v)) => (v.as_array()?, false),
+        (None, None) => return None,
+    };
+    // (class name, variant schema)
+    let classes: Vec<(&str, &Value)> = variants
+        .iter()
+        .filter_map(|variant| {
+            let ref_path = variant.get("$ref")?.as_str()?;
+            let target = resolve_local_ref(root, ref_path)?;
+            let is_object = target.get("type").and_then(|t| t.as_str()) == Some("object")
+                || target.get("properties").is_some();
+            is_object.then(|| (ref_path.rsplit('/').next().unwrap_or(ref_path), target))
+        })
+        .collect();
+    if classes.is_empty() {
+        return None;
+    }
+
+    let func = format!("parse_{}", to_snake_case(name));
+    let mut out = String::new();
+    out.push_str(&format!("\ndef {}(data: dict) -> {}:\n", func, name));
+
+    if let Some(property) = schema
+        .get("discriminator")
+        .and_then(|d| d.get("propertyName"))
+        .and_then(|p| p.as_str())
+    {
+        let mapping = schema
+            .get("discriminator")
+            .and_then(|d| d.get("mapping"))
+            .and_then(|m| m.as_object());
+        let entries: Vec<String> = match mapping {
+            Some(mapping) => object_entries(mapping)
+                .filter_map(|(value, ref_path)| {
+                    let class = ref_path.as_str()?.rsplit('/').next()?;
+                    classes
+                        .iter()
+                        .any(|(c, _)| *c == class)
+                        .then(|| format!("{}: {}", py_str(value), class))
+                })
+                .collect(),
+            None => classes
+                .iter()
+                .map(|(class, target)| {
+                    let prop = target.get("properties").and_then(|p| p.get(property));
+                    let value = prop
+                        .and_then(|p| p.get("const"))
+                        .or_else(|| {
+                            prop.and_then(|p| p.get("enum"))
+                                .and_then(|e| e.as_array())
+                                .filter(|e| e.len() == 1)
+                                .map(|e| &e[0])
+                        })
+                        .and_then(|v| v.as_str())
+                        .unwrap_or(class);
+                    format!("{}: {}", py_str(value), class)
+                })
+                .collect(),
+        };
+        out.push_str(&format!(
+            "    \"\"\"Construct the variant of `{}` named by `data[{}]`.\"\"\"\n",
+            name,
+            py_str(property)
+        ));
+        out.push_str(&format!("    tag = data.get({})\n", py_str(property)));
+        out.push_str(&format!(
+            "    variant = {{{}}}.get(tag)\n",
+            entries.join(", ")
+        ));
+        out.push_str("    if variant is None:\n");
+        out.push_str(&format!(
+            "        raise ValueError(f\"unknown {} {{tag!r}} for {}\")\n",
+            property, name
+        ));
+        out.push_str("    return _construct(variant, data)\n");
+        return Some(out);
+    }
+
+    let entries: Vec<String> = classes
+        .iter()
+        .map(|(class, target)| {
+            let required: Vec<String> = target
+                .get("required")
+                .and_then(|r| r.as_array())
+                .map(|r| r.iter().filter_map(|k| k.as_str()).map(py_str).collect())
+                .unwrap_or_default();
+            format!("({}, [{}])", class, required.join(", "))
+        })
+        .collect();
+    out.push_str(&format!(
+        "    \"\"\"Construct the variant of `{}` whose required keys are all in `data`.\"\"\"\n",
+        name
+    ));
+    out.push_str(&format!("    variants = [{}]\n", entries.join(", ")));
+    if one_of {
+        out.push_str(
+            "    matches = [cls for cls, keys in variants if all(key in data for key in keys)]\n",
+        );
+        out.push_str("    if not matches:\n");
+        out.push_str(&format!(
+            "        raise ValueError(f\"no variant of {} has all its required keys in {{sorted(data)}}\")\n",
+            name
+        ));
+        out.push_str("    if len(matches) > 1:\n");
+        out.push_str("        names = \", \".join(cls.__name__ for cls in matches)\n");
+        out.push_str(&format!(
+            "        raise ValueError(f\"ambiguous {}: keys {{sorted(data)}} match {{names}}\")\n",
+            name
+        ));
+        out.push_str("    return _construct(matches[0], data)\n");
+    } else {
+        out.push_str("    for cls, keys in variants:\n");
+        out.push_str("        if all(key in data for key in keys):\n");
+        out.push_str("            return _construct(cls, data)\n");
+        out.push_str(&format!(
+            "    raise ValueError(f\"no variant of {} has all its required keys in {{sorted(data)}}\")\n",
+            name
+        ));
+    }
+    Some(out)
+}
+
+/// A Python string literal (JSON's escaping is valid Python).
+fn py_str(s: &str) -> String {
+    serde_json::to_string(s).unwrap_or_default()
+}
+
 fn generate_py_type(name: &str, schema: &Value) -> String {
     let mut out = String::new();
 
@@ -343,6 +585,7 @@ fn generate_py_type(name: &str, schema: &Value) -> String {
     // Handle object type
     let type_str = schema.get("type").and_then(|t| t.as_str());
     if type_str == Some("object") || schema.get("properties").is_some() {
+        let mut nested = String::new();
         out.push_str("@dataclass\n");
         out.push_str(&format!("class {}:\n", name));
         if let Some(props) = schema.get("properties").and_then(|p| p.as_object()) {
@@ -352,17 +595,28 @@ fn generate_py_type(name: &str, schema: &Value) -> String {
                 .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
                 .unwrap_or_default();
 
+            let mut py_type =
+                |prop_name: &str, prop_schema: &Value| match nullable_object(prop_schema) {
+                    Some(inner) => {
+                        let nested_name = nested_type_name(name, prop_name);
+                        nested.push_str(&generate_py_type(&nested_name, &inner));
+                        nested.push('\n');
+                        format!("Optional[{}]", nested_name)
+                    }
+                    None => schema_to_py(prop_schema),
+                };
+
             // Required fields first
-            for (prop_name, prop_schema) in props {
+            for (prop_name, prop_schema) in object_entries(props) {
                 if required.contains(&prop_name.as_str()) {
-                    let py_type = schema_to_py(prop_schema);
+                    let py_type = py_type(prop_name, prop_schema);
                     out.push_str(&format!("    {}: {}\n", prop_name, py_type));
                 }
             }
             // Optional fields
-            for (prop_name, prop_schema) in props {
+            for (prop_name, prop_schema) in object_entries(props) {
                 if !required.contains(&prop_name.as_str()) {
-                    let py_type = schema_to_py(prop_schema);
+                    let py_type = py_type(prop_name, prop_schema);
                     out.push_str(&format!(
                         "    {}: Optional[{}] = None\n",
                         prop_name, py_type
@@ -375,7 +629,8 @@ fn generate_py_type(name: &str, schema: &Value) -> String {
         } else {
             out.push_str("    pass\n");
         }
-        return out;
+        // Nested classes must be defined before the annotations that name them
+        return nested + &out;
     }
 
     // Simple type alias
@@ -390,14 +645,9 @@ fn schema_to_py(schema: &Value) -> String {
         return ref_path.split('/').last().unwrap_or("Any").to_string();
     }
 
-    // Handle type array (nullable)
-    if let Some(arr) = schema.get("type").and_then(|t| t.as_array()) {
-        let types: Vec<&str> = arr.iter().filter_map(|v| v.as_str()).collect();
-        let non_null: Vec<_> = types.iter().filter(|t| **t != "null").collect();
-        if non_null.len() == 1 {
-            let base = type_to_py(non_null[0]);
-            return format!("Optional[{}]", base);
-        }
+    // Handle nullable
+    if let Some(inner) = non_null_schema(schema) {
+        return format!("Optional[{}]", schema_to_py(&inner));
     }
 
     let type_str = schema.get("type").and_then(|t| t.as_str());
@@ -446,7 +696,8 @@ impl JsonSchemaGenerator for RustGenerator {
         "rust"
     }
 
-    fn generate(&self, schema: &Value, root_name: &str) -> String {
+    fn generate(&self, schema: &Value, root_name: &str) -> Result<String, GenerateError> {
+        let schema = &promote_inline_enums(schema, root_name);
         let mut out = String::new();
         out.push_str("//! Auto-generated from JSON Schema\n\n");
         out.push_str("use serde::{Deserialize, Serialize};\n\n");
@@ -457,7 +708,7 @@ impl JsonSchemaGenerator for RustGenerator {
             .or_else(|| schema.get("$defs"))
             .and_then(|d| d.as_object())
         {
-            for (name, def_schema) in defs {
+            for (name, def_schema) in object_entries(defs) {
                 out.push_str(&generate_rust_type(name, def_schema));
                 out.push('\n');
             }
@@ -465,7 +716,7 @@ impl JsonSchemaGenerator for RustGenerator {
 
         // Generate root type
         out.push_str(&generate_rust_type(root_name, schema));
-        out
+        Ok(out)
     }
 }
 
@@ -490,6 +741,22 @@ fn generate_rust_type(name: &str, schema: &Value) -> String {
             out.push_str("}\n");
             return out;
         }
+        if !enum_vals.is_empty() && enum_vals.iter().all(|v| v.is_i64()) {
+            return generate_rust_int_enum(name, schema, enum_vals);
+        }
+
+        // Mixed values can't be one Rust enum: keep the base type, document the values
+        let allowed: Vec<String> = enum_vals.iter().map(|v| v.to_string()).collect();
+        out.push_str(&format!("/// Allowed values: {}\n", allowed.join(", ")));
+        let base_type = if schema.get("type").is_some() {
+            schema_to_rust(schema)
+        } else if enum_vals.iter().all(|v| v.is_number()) {
+            "f64".to_string()
+        } else {
+            "serde_json::Value".to_string()
+        };
+        out.push_str(&format!("pub type {} = {};\n", name, base_type));
+        return out;
     }
 
     // Handle oneOf/anyOf (tagged union)
@@ -512,7 +779,11 @@ fn generate_rust_type(name: &str, schema: &Value) -> String {
     // Handle object type
     let type_str = schema.get("type").and_then(|t| t.as_str());
     if type_str == Some("object") || schema.get("properties").is_some() {
+        let mut nested = String::new();
         out.push_str("#[derive(Debug, Clone, Serialize, Deserialize)]\n");
+        if is_strict_object(schema) {
+            out.push_str("#[serde(deny_unknown_fields)]\n");
+        }
         out.push_str(&format!("pub struct {} {{\n", name));
         if let Some(props) = schema.get("properties").and_then(|p| p.as_object()) {
             let required: Vec<&str> = schema
@@ -521,8 +792,16 @@ fn generate_rust_type(name: &str, schema: &Value) -> String {
                 .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
                 .unwrap_or_default();
 
-            for (prop_name, prop_schema) in props {
-                let rust_type = schema_to_rust(prop_schema);
+            for (prop_name, prop_schema) in object_entries(props) {
+                let rust_type = match nullable_object(prop_schema) {
+                    Some(inner) => {
+                        let nested_name = nested_type_name(name, prop_name);
+                        nested.push_str(&generate_rust_type(&nested_name, &inner));
+                        nested.push('\n');
+                        format!("Option<{}>", nested_name)
+                    }
+                    None => schema_to_rust(prop_schema),
+                };
                 let field_name = to_snake_case(prop_name);
                 let field_type = if required.contains(&prop_name.as_str()) {
                     rust_type
@@ -536,7 +815,7 @@ fn generate_rust_type(name: &str, schema: &Value) -> String {
             }
         }
         out.push_str("}\n");
-        return out;
+        return nested + &out;
     }
 
     // Simple type alias
@@ -545,6 +824,66 @@ fn generate_rust_type(name: &str, schema: &Value) -> String {
     out
 }
 
+/// Integer enum: a fieldless enum with the values as discriminants, (de)serialized
+/// through `i64` so the JSON stays numeric. Variants are named from
+/// `x-enum-varnames` when given, otherwise after the value (`V2`, `VNeg1`).
+fn generate_rust_int_enum(name: &str, schema: &Value, values: &[Value]) -> String {
+    let values: Vec<i64> = values.iter().filter_map(|v| v.as_i64()).collect();
+    let varnames: Vec<&str> = schema
+        .get("x-enum-varnames")
+        .and_then(|v| v.as_array())
+        .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
+        .filter(|names: &Vec<&str>| names.len() == values.len())
+        .unwrap_or_default();
+    let variants: Vec<(String, i64)> = values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let variant = match varnames.get(i) {
+                Some(varname) => to_pascal_case(varname),
+                None if *value < 0 => format!("VNeg{}", value.unsigned_abs()),
+                None => format!("V{}", value),
+            };
+            (variant, *value)
+        })
+        .collect();
+
+    let mut out = String::new();
+    out.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]\n");
+    out.push_str("#[serde(try_from = \"i64\", into = \"i64\")]\n");
+    out.push_str("#[repr(i64)]\n");
+    out.push_str(&format!("pub enum {} {{\n", name));
+    for (variant, value) in &variants {
+        out.push_str(&format!("    {} = {},\n", variant, value));
+    }
+    out.push_str("}\n\n");
+
+    out.push_str(&format!("impl TryFrom<i64> for {} {{\n", name));
+    out.push_str("    type Error = String;\n\n");
+    out.push_str("    fn try_from(value: i64) -> Result<Self, Self::Error> {\n");
+    out.push_str("        match value {\n");
+    for (variant, value) in &variants {
+        out.push_str(&format!(
+            "            {} => Ok(Self::{}),\n",
+            value, variant
+        ));
+    }
+    out.push_str(&format!(
+        "            _ => Err(format!(\"invalid {} value: {{}}\", value)),\n",
+        name
+    ));
+    out.push_str("        }\n");
+    out.push_str("    }\n");
+    out.push_str("}\n\n");
+
+    out.push_str(&format!("impl From<{}> for i64 {{\n", name));
+    out.push_str(&format!("    fn from(value: {}) -> Self {{\n", name));
+    out.push_str("        value as i64\n");
+    out.push_str("    }\n");
+    out.push_str("}\n");
+    out
+}
+
 fn schema_to_rust(schema: &Value) -> String {
     // Handle $ref
     if let Some(ref_path) = schema.get("$ref").and_then(|r| r.as_str()) {
@@ -555,14 +894,9 @@ fn schema_to_rust(schema: &Value) -> String {
             .to_string();
     }
 
-    // Handle type array (nullable)
-    if let Some(arr) = schema.get("type").and_then(|t| t.as_array()) {
-        let types: Vec<&str> = arr.iter().filter_map(|v| v.as_str()).collect();
-        let non_null: Vec<_> = types.iter().filter(|t| **t != "null").collect();
-        if non_null.len() == 1 {
-            let base = type_to_rust(non_null[0]);
-            return format!("Option<{}>", base);
-        }
+    // Handle nullable
+    if let Some(inner) = non_null_schema(schema) {
+        return format!("Option<{}>", schema_to_rust(&inner));
     }
 
     let type_str = schema.get("type").and_then(|t| t.as_str());
@@ -598,6 +932,558 @@ fn type_to_rust(t: &str) -> String {
     }
 }
 
+// --- Samples ---
+
+/// A sample instance of one type in a schema.
+#[derive(Debug, Clone)]
+pub struct Sample {
+    /// Definition name, or the root name for the schema itself
+    pub type_name: String,
+    pub value: Value,
+}
+
+/// Sample instances of each type in a schema: its `definitions`/`$defs`, then
+/// the root (named `root_name`). See [`sample()`].
+pub fn samples(schema: &Value, root_name: &str) -> Vec<Sample> {
+    let mut out = Vec::new();
+    if let Some(defs) = schema
+        .get("definitions")
+        .or_else(|| schema.get("$defs"))
+        .and_then(|d| d.as_object())
+    {
+        for (name, def_schema) in object_entries(defs) {
+            out.push(Sample {
+                type_name: name.clone(),
+                value: sample(def_schema, schema),
+            });
+        }
+    }
+    out.push(Sample {
+        type_name: root_name.to_string(),
+        value: sample(schema, schema),
+    });
+    out
+}
+
+/// A sample instance of `schema`, for fixtures and docs.
+///
+/// Declared values win: `example`, the first of `examples`, `const`, `default`,
+/// then the first `enum` value. Otherwise the value is synthesized from the
+/// type: `"string"`, `0`, `false`, `null`, arrays of one sample item, and
+/// objects with a sample of every property. Local `$ref`s are resolved against
+/// `root`; a reference back to a type being built becomes `null`.
+pub fn sample(schema: &Value, root: &Value) -> Value {
+    sample_inner(schema, root, &mut Vec::new())
+}
+
+fn sample_inner<'a>(schema: &'a Value, root: &'a Value, refs: &mut Vec<&'a str>) -> Value {
+    if let Some(value) = declared_example(schema) {
+        return value.clone();
+    }
+    for key in ["const", "default"] {
+        if let Some(value) = schema.get(key) {
+            return value.clone();
+        }
+    }
+    if let Some(first) = schema
+        .get("enum")
+        .and_then(|e| e.as_array())
+        .and_then(|e| e.first())
+    {
+        return first.clone();
+    }
+
+    if let Some(ref_path) = schema.get("$ref").and_then(|r| r.as_str()) {
+        let Some(target) = resolve_local_ref(root, ref_path) else {
+            return Value::Null;
+        };
+        if refs.contains(&ref_path) {
+            return Value::Null;
+        }
+        refs.push(ref_path);
+        let value = sample_inner(target, root, refs);
+        refs.pop();
+        return value;
+    }
+
+    if let Some(all_of) = schema.get("allOf").and_then(|a| a.as_array()) {
+        let mut merged = serde_json::Map::new();
+        for member in all_of {
+            match sample_inner(member, root, refs) {
+                Value::Object(fields) => merged.extend(fields),
+                other if all_of.len() == 1 => return other,
+                _ => {}
+            }
+        }
+        return Value::Object(merged);
+    }
+    if let Some(members) = schema
+        .get("oneOf")
+        .or_else(|| schema.get("anyOf"))
+        .and_then(|a| a.as_array())
+    {
+        let is_null = |s: &&Value| s.get("type").and_then(|t| t.as_str()) == Some("null");
+        return match members.iter().find(|s| !is_null(s)).or(members.first()) {
+            Some(member) => sample_inner(member, root, refs),
+            None => Value::Null,
+        };
+    }
+
+    let type_str = match schema.get("type") {
+        Some(Value::String(t)) => Some(t.as_str()),
+        // First non-null type of a union
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(|t| t.as_str())
+            .find(|t| *t != "null")
+            .or(Some("null")),
+        _ => None,
+    };
+    match type_str {
+        Some("string") => Value::String("string".to_string()),
+        Some("integer") => Value::from(0),
+        Some("number") => Value::from(0.0),
+        Some("boolean") => Value::Bool(false),
+        Some("array") => match schema.get("items") {
+            Some(items) => Value::Array(vec![sample_inner(items, root, refs)]),
+            None => Value::Array(Vec::new()),
+        },
+        Some("object") => sample_object(schema, root, refs),
+        None if schema.get("properties").is_some() => sample_object(schema, root, refs),
+        _ => Value::Null,
+    }
+}
+
+fn sample_object<'a>(schema: &'a Value, root: &'a Value, refs: &mut Vec<&'a str>) -> Value {
+    let mut fields = serde_json::Map::new();
+    if let Some(props) = schema.get("properties").and_then(|p| p.as_object()) {
+        for (name, prop_schema) in object_entries(props) {
+            fields.insert(name.clone(), sample_inner(prop_schema, root, refs));
+        }
+    }
+    Value::Object(fields)
+}
+
+/// `example`, or the first of `examples` (a list in JSON Schema, a map of
+/// `{value}` objects in OpenAPI).
+fn declared_example(schema: &Value) -> Option<&Value> {
+    if let Some(example) = schema.get("example") {
+        return Some(example);
+    }
+    match schema.get("examples")? {
+        Value::Array(examples) => examples.first(),
+        Value::Object(examples) => examples.values().find_map(|e| e.get("value")),
+        _ => None,
+    }
+}
+
+/// Resolve a `#/...` JSON Pointer reference within `root`.
+fn resolve_local_ref<'a>(root: &'a Value, ref_path: &str) -> Option<&'a Value> {
+    let pointer = ref_path.strip_prefix('#')?;
+    root.pointer(pointer)
+}
+
+// --- Inference ---
+
+/// Infer a JSON Schema from a sample instance, named `root_name`.
+///
+/// Objects get their keys as `properties`, all `required`; arrays get `items`
+/// merged from every element. Merging widens types: a key missing from some
+/// elements is no longer required, `null` makes a type nullable, integers and
+/// numbers become `number`, and other mixed scalars a list of types. Nested
+/// objects are moved to `$defs` and referenced, named after their parent and
+/// property (`RootAddress`; array elements get an `Item` suffix), so every
+/// generator produces a named type for them.
+pub fn infer_schema(sample: &Value, root_name: &str) -> Value {
+    let mut schema = infer_inline(sample);
+    let mut defs = serde_json::Map::new();
+    hoist_children(&mut schema, root_name, &mut defs);
+    if !defs.is_empty()
+        && let Some(obj) = schema.as_object_mut()
+    {
+        obj.insert("$defs".to_string(), Value::Object(defs));
+    }
+    schema
+}
+
+/// Schema of one value, with nested objects inline.
+fn infer_inline(value: &Value) -> Value {
+    match value {
+        Value::Null => serde_json::json!({"type": "null"}),
+        Value::Bool(_) => serde_json::json!({"type": "boolean"}),
+        Value::Number(n) if n.is_f64() => serde_json::json!({"type": "number"}),
+        Value::Number(_) => serde_json::json!({"type": "integer"}),
+        Value::String(_) => serde_json::json!({"type": "string"}),
+        Value::Array(items) => {
+            let mut schema = serde_json::json!({"type": "array"});
+            if let Some(merged) = items.iter().map(infer_inline).reduce(merge_schemas) {
+                schema["items"] = merged;
+            }
+            schema
+        }
+        Value::Object(map) => {
+            let properties: serde_json::Map<String, Value> = map
+                .iter()
+                .map(|(key, value)| (key.clone(), infer_inline(value)))
+                .collect();
+            let required: Vec<Value> = map.keys().cloned().map(Value::String).collect();
+            serde_json::json!({"type": "object", "properties": properties, "required": required})
+        }
+    }
+}
+
+/// Widen two inferred schemas into one that accepts both.
+fn merge_schemas(a: Value, b: Value) -> Value {
+    let a = split_null(a);
+    let b = split_null(b);
+    let merged = match (a.schema, b.schema) {
+        (Some(a), Some(b)) => merge_non_null(a, b),
+        (Some(s), None) | (None, Some(s)) => s,
+        (None, None) => return serde_json::json!({"type": "null"}),
+    };
+    if a.nullable || b.nullable {
+        add_null(merged)
+    } else {
+        merged
+    }
+}
+
+fn merge_non_null(mut a: Value, b: Value) -> Value {
+    if a == b {
+        return a;
+    }
+    let type_of = |s: &Value| s.get("type").and_then(|t| t.as_str()).map(str::to_string);
+    match (type_of(&a).as_deref(), type_of(&b).as_deref()) {
+        (Some("object"), Some("object")) => {
+            let required = |s: &Value| -> Vec<Value> {
+                s.get("required")
+                    .and_then(|r| r.as_array())
+                    .cloned()
+                    .unwrap_or_default()
+            };
+            let b_required = required(&b);
+            let both_required: Vec<Value> = required(&a)
+                .into_iter()
+                .filter(|key| b_required.contains(key))
+                .collect();
+            let mut properties = match a.get_mut("properties").map(Value::take) {
+                Some(Value::Object(props)) => props,
+                _ => serde_json::Map::new(),
+            };
+            if let Some(b_props) = b.get("properties").and_then(|p| p.as_object()) {
+                for (key, schema) in b_props {
+                    let merged = match properties.remove(key) {
+                        Some(existing) => merge_schemas(existing, schema.clone()),
+                        None => schema.clone(),
+                    };
+                    properties.insert(key.clone(), merged);
+                }
+            }
+            serde_json::json!({"type": "object", "properties": properties, "required": both_required})
+        }
+        (Some("array"), Some("array")) => {
+            let items = match (a.get_mut("items").map(Value::take), b.get("items")) {
+                (Some(a_items), Some(b_items)) => Some(merge_schemas(a_items, b_items.clone())),
+                (Some(items), None) => Some(items),
+                (None, items) => items.cloned(),
+            };
+            let mut schema = serde_json::json!({"type": "array"});
+            if let Some(items) = items {
+                schema["items"] = items;
+            }
+            schema
+        }
+        (Some("integer"), Some("number")) | (Some("number"), Some("integer")) => {
+            serde_json::json!({"type": "number"})
+        }
+        _ => {
+            let scalar = |s: &Value| {
+                let types: Vec<Value> = match s.get("type") {
+                    Some(Value::Array(types)) => types.clone(),
+                    Some(t @ Value::String(_)) => vec![t.clone()],
+                    _ => return None,
+                };
+                types
+                    .iter()
+                    .all(|t| !matches!(t.as_str(), Some("object" | "array")))
+                    .then_some(types)
+            };
+            match (scalar(&a), scalar(&b)) {
+                (Some(mut types), Some(b_types)) => {
+                    for t in b_types {
+                        if !types.contains(&t) {
+                            types.push(t);
+                        }
+                    }
+                    serde_json::json!({"type": types})
+                }
+                // Structures mixed with other types: accept anything
+                _ => serde_json::json!({}),
+            }
+        }
+    }
+}
+
+/// An inferred schema with its `null` type split off.
+struct NullSplit {
+    /// Whether the schema allows `null`
+    nullable: bool,
+    /// The schema without `null` (None if it only allows `null`)
+    schema: Option<Value>,
+}
+
+/// Split `null` off an inferred schema's type. See [`NullSplit`].
+fn split_null(mut schema: Value) -> NullSplit {
+    match schema.get_mut("type") {
+        Some(Value::String(t)) if t == "null" => NullSplit {
+            nullable: true,
+            schema: None,
+        },
+        Some(Value::Array(types)) if types.iter().any(|t| t == "null") => {
+            types.retain(|t| t != "null");
+            if types.len() == 1 {
+                schema["type"] = types[0].clone();
+            }
+            NullSplit {
+                nullable: true,
+                schema: Some(schema),
+            }
+        }
+        _ => NullSplit {
+            nullable: false,
+            schema: Some(schema),
+        },
+    }
+}
+
+fn add_null(mut schema: Value) -> Value {
+    match schema.get_mut("type") {
+        Some(Value::Array(types)) => types.push("null".into()),
+        Some(t) => *t = serde_json::json!([t.take(), "null"]),
+        // No type accepts null already
+        None => {}
+    }
+    schema
+}
+
+/// Move the object schemas nested in `schema` (named `name`) to `defs`.
+fn hoist_children(schema: &mut Value, name: &str, defs: &mut serde_json::Map<String, Value>) {
+    if let Some(props) = schema.get_mut("properties").and_then(|p| p.as_object_mut()) {
+        for (key, prop) in object_entries_mut(props) {
+            hoist(prop, &nested_type_name(name, key), defs);
+        }
+    }
+    if let Some(items) = schema.get_mut("items") {
+        hoist(items, &format!("{}Item", name), defs);
+    }
+}
+
+/// Replace an object `schema` with a `$ref` to it in `defs`, after hoisting its
+/// own nested objects. A nullable object becomes `anyOf` the reference and null.
+fn hoist(schema: &mut Value, name: &str, defs: &mut serde_json::Map<String, Value>) {
+    let NullSplit {
+        nullable,
+        schema: inner,
+    } = split_null(schema.take());
+    let Some(mut inner) = inner else {
+        *schema = serde_json::json!({"type": "null"});
+        return;
+    };
+    if inner.get("type").and_then(|t| t.as_str()) != Some("object") {
+        hoist_children(&mut inner, name, defs);
+        *schema = if nullable { add_null(inner) } else { inner };
+        return;
+    }
+
+    let def_name = unique_def_name(name, |n| defs.contains_key(n));
+    // Reserve the name before children take theirs
+    defs.insert(def_name.clone(), Value::Null);
+    hoist_children(&mut inner, &def_name, defs);
+    defs.insert(def_name.clone(), inner);
+
+    let reference = serde_json::json!({"$ref": format!("#/$defs/{}", def_name)});
+    *schema = if nullable {
+        serde_json::json!({"anyOf": [reference, {"type": "null"}]})
+    } else {
+        reference
+    };
+}
+
+// --- Enum promotion ---
+
+/// Inline enums moved out of a schema, and the definition names in use.
+struct EnumPromotion {
+    /// `#/$defs/` or `#/definitions/`, matching the schema
+    ref_prefix: String,
+    taken: HashSet<String>,
+    defs: serde_json::Map<String, Value>,
+}
+
+/// Copy of `schema` with its inline enums (of more than one value, in
+/// properties and array items at any depth) moved to its definitions and
+/// referenced, so every generator gives them a named type. They are named like
+/// hoisted objects: after the parent type and property (`PostStatus`), with an
+/// `Item` suffix for array elements (`PostTagsItem`), and a number suffix when
+/// the name is taken. A nullable enum becomes `anyOf` the reference and null.
+fn promote_inline_enums(schema: &Value, root_name: &str) -> Value {
+    let mut schema = schema.clone();
+    let defs_key = if schema.get("definitions").is_some() {
+        "definitions"
+    } else {
+        "$defs"
+    };
+    let mut defs = match schema.as_object_mut().and_then(|o| o.remove(defs_key)) {
+        Some(Value::Object(defs)) => defs,
+        _ => serde_json::Map::new(),
+    };
+    let mut promotion = EnumPromotion {
+        ref_prefix: format!("#/{}/", defs_key),
+        taken: defs.keys().cloned().collect(),
+        defs: serde_json::Map::new(),
+    };
+    promotion.taken.insert(root_name.to_string());
+
+    for (name, def) in object_entries_mut(&mut defs) {
+        promote_enum_children(def, name, &mut promotion);
+    }
+    promote_enum_children(&mut schema, root_name, &mut promotion);
+
+    defs.extend(promotion.defs);
+    if !defs.is_empty()
+        && let Some(obj) = schema.as_object_mut()
+    {
+        obj.insert(defs_key.to_string(), Value::Object(defs));
+    }
+    schema
+}
+
+/// Promote the enums in the properties and items of `schema` (named `name`).
+fn promote_enum_children(schema: &mut Value, name: &str, promotion: &mut EnumPromotion) {
+    if let Some(props) = schema.get_mut("properties").and_then(|p| p.as_object_mut()) {
+        for (key, prop) in object_entries_mut(props) {
+            promote_enum(prop, &nested_type_name(name, key), promotion);
+        }
+    }
+    if let Some(items) = schema.get_mut("items") {
+        promote_enum(items, &format!("{}Item", name), promotion);
+    }
+}
+
+fn promote_enum(schema: &mut Value, name: &str, promotion: &mut EnumPromotion) {
+    if schema.get("$ref").is_some() {
+        return;
+    }
+    let values: Vec<Value> = match schema.get("enum").and_then(|e| e.as_array()) {
+        Some(values) => values.iter().filter(|v| !v.is_null()).cloned().collect(),
+        None => {
+            // The non-null member of `anyOf: [X, null]` keeps the property's name
+            let key = if schema.get("anyOf").is_some() {
+                "anyOf"
+            } else {
+                "oneOf"
+            };
+            if let Some(members) = schema
+                .get_mut(key)
+                .and_then(|a| a.as_array_mut())
+                .filter(|members| members.len() == 2 && members.iter().any(|m| m["type"] == "null"))
+            {
+                for member in members.iter_mut() {
+                    promote_enum(member, name, promotion);
+                }
+            }
+            promote_enum_children(schema, name, promotion);
+            return;
+        }
+    };
+    if values.len() < 2 {
+        // A single value is a tag (like `const`), not a type of its own
+        return;
+    }
+
+    let had_null = values.len() < schema["enum"].as_array().map_or(0, |e| e.len());
+    let NullSplit {
+        nullable,
+        schema: inner,
+    } = split_null(schema.take());
+    let mut inner = inner.unwrap_or_default();
+    inner["enum"] = Value::Array(values);
+
+    let def_name = unique_def_name(name, |n| promotion.taken.contains(n));
+    promotion.taken.insert(def_name.clone());
+    promotion.defs.insert(def_name.clone(), inner);
+
+    let reference = serde_json::json!({"$ref": format!("{}{}", promotion.ref_prefix, def_name)});
+    *schema = if nullable || had_null {
+        serde_json::json!({"anyOf": [reference, {"type": "null"}]})
+    } else {
+        reference
+    };
+}
+
+// --- Helpers ---
+
+/// `name`, or `name` with the lowest number suffix from 2 that isn't `taken`.
+fn unique_def_name(name: &str, taken: impl Fn(&str) -> bool) -> String {
+    let mut def_name = name.to_string();
+    let mut n = 2;
+    while taken(&def_name) {
+        def_name = format!("{}{}", name, n);
+        n += 1;
+    }
+    def_name
+}
+
+/// The non-null member of a nullable schema, or None if `schema` isn't nullable.
+///
+/// Recognizes `type: [T, "null"]` (returned as the same schema with `type: T`)
+/// and `anyOf`/`oneOf` of exactly one schema plus `{"type": "null"}`.
+fn non_null_schema(schema: &Value) -> Option<Value> {
+    if let Some(arr) = schema.get("type").and_then(|t| t.as_array()) {
+        let non_null: Vec<&Value> = arr.iter().filter(|t| t.as_str() != Some("null")).collect();
+        if non_null.len() == 1 && non_null.len() < arr.len() {
+            let mut inner = schema.clone();
+            inner["type"] = non_null[0].clone();
+            return Some(inner);
+        }
+        return None;
+    }
+
+    let members = schema
+        .get("anyOf")
+        .or_else(|| schema.get("oneOf"))
+        .and_then(|a| a.as_array())?;
+    let is_null = |s: &Value| s.get("type").and_then(|t| t.as_str()) == Some("null");
+    if members.len() == 2 && members.iter().any(is_null) {
+        members.iter().find(|s| !is_null(s)).cloned()
+    } else {
+        None
+    }
+}
+
+/// The non-null member of a nullable property when it is an inline object with
+/// properties. Such properties get their own generated type.
+fn nullable_object(schema: &Value) -> Option<Value> {
+    non_null_schema(schema)
+        .filter(|inner| inner.get("$ref").is_none() && inner.get("properties").is_some())
+}
+
+/// Whether `schema` is an object with explicit `properties` that forbids others
+/// (`additionalProperties: false`).
+fn is_strict_object(schema: &Value) -> bool {
+    schema.get("additionalProperties") == Some(&Value::Bool(false))
+        && schema.get("properties").is_some_and(|p| p.is_object())
+}
+
+/// Name for a type generated from a property: `Person.home_address` -> `PersonHomeAddress`.
+fn nested_type_name(parent: &str, prop_name: &str) -> String {
+    let prop = to_pascal_case(prop_name);
+    let mut chars = prop.chars();
+    match chars.next() {
+        Some(first) => format!("{}{}{}", parent, first.to_uppercase(), chars.as_str()),
+        None => parent.to_string(),
+    }
+}
+
 fn to_snake_case(s: &str) -> String {
     let mut result = String::new();
     for (i, c) in s.chars().enumerate() {
@@ -627,6 +1513,172 @@ fn to_pascal_case(s: &str) -> String {
         .collect()
 }
 
+// --- Ref inlining ---
+
+/// Copy of `schema` with every local `$ref` replaced by the schema it points
+/// to, recursively, so the generators emit the structure at each use site
+/// instead of a named type. References to recursive types (a tree `Node`
+/// containing `Node`s) stay references; of the definitions, only those are
+/// kept. Keywords next to a `$ref` override the referenced schema's.
+pub fn inline_refs(schema: &Value) -> Value {
+    let defs_key = if schema.get("definitions").is_some() {
+        "definitions"
+    } else {
+        "$defs"
+    };
+    let prefix = format!("#/{}/", defs_key);
+
+    let mut inlined = schema.clone();
+    if let Some(obj) = inlined.as_object_mut() {
+        obj.remove(defs_key);
+    }
+    let recursive = recursive_refs(schema, &inlined);
+    let mut remaining = Vec::new();
+    inline_refs_in(&mut inlined, schema, &recursive, &mut remaining);
+
+    // Definitions of recursive types, inlined themselves; they may reference more
+    let mut defs = serde_json::Map::new();
+    let mut next = 0;
+    while let Some(reference) = remaining.get(next).cloned() {
+        next += 1;
+        let Some(name) = reference.strip_prefix(&prefix) else {
+            continue;
+        };
+        if defs.contains_key(name) {
+            continue;
+        }
+        let Some(mut def) = local_ref_target(schema, &reference).cloned() else {
+            continue;
+        };
+        inline_refs_in(&mut def, schema, &recursive, &mut remaining);
+        defs.insert(name.to_string(), def);
+    }
+    if !defs.is_empty()
+        && let Some(obj) = inlined.as_object_mut()
+    {
+        obj.insert(defs_key.to_string(), Value::Object(defs));
+    }
+    inlined
+}
+
+fn local_ref_target<'a>(root: &'a Value, reference: &str) -> Option<&'a Value> {
+    root.pointer(reference.strip_prefix('#')?)
+}
+
+/// Keywords holding instance data rather than schemas.
+fn is_instance_keyword(key: &str) -> bool {
+    matches!(key, "enum" | "const" | "default" | "examples")
+}
+
+/// Local references in `value`, not looking into definitions.
+fn collect_local_refs(value: &Value, refs: &mut Vec<String>) {
+    match value {
+        Value::Object(obj) => {
+            if let Some(reference) = obj.get("$ref").and_then(|r| r.as_str())
+                && reference.starts_with('#')
+            {
+                refs.push(reference.to_string());
+            }
+            for (key, value) in obj {
+                if !is_instance_keyword(key) && key != "$defs" && key != "definitions" {
+                    collect_local_refs(value, refs);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_local_refs(item, refs);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Local references whose schema leads back to themselves. `body` is the
+/// root schema without its definitions.
+fn recursive_refs(root: &Value, body: &Value) -> HashSet<String> {
+    // References directly inside each referenced schema ("#" is the root)
+    let mut edges: HashMap<String, Vec<String>> = HashMap::new();
+    let mut queue = Vec::new();
+    collect_local_refs(body, &mut queue);
+    edges.insert("#".to_string(), queue.clone());
+    while let Some(reference) = queue.pop() {
+        if edges.contains_key(&reference) {
+            continue;
+        }
+        let mut refs = Vec::new();
+        if let Some(target) = local_ref_target(root, &reference) {
+            collect_local_refs(target, &mut refs);
+        }
+        queue.extend(refs.iter().cloned());
+        edges.insert(reference, refs);
+    }
+
+    let reaches_itself = |start: &String| {
+        let mut seen = HashSet::new();
+        let mut stack: Vec<&String> = edges[start].iter().collect();
+        while let Some(reference) = stack.pop() {
+            if reference == start {
+                return true;
+            }
+            if seen.insert(reference) {
+                stack.extend(edges.get(reference).into_iter().flatten());
+            }
+        }
+        false
+    };
+    edges
+        .keys()
+        .filter(|r| reaches_itself(r))
+        .cloned()
+        .collect()
+}
+
+/// Inline the non-recursive references in `node`; the references left in
+/// place are added to `remaining`.
+fn inline_refs_in(
+    node: &mut Value,
+    root: &Value,
+    recursive: &HashSet<String>,
+    remaining: &mut Vec<String>,
+) {
+    match node {
+        Value::Object(obj) => {
+            let Some(reference) = obj.get("$ref").and_then(|r| r.as_str()) else {
+                for (key, value) in obj.iter_mut() {
+                    if !is_instance_keyword(key) {
+                        inline_refs_in(value, root, recursive, remaining);
+                    }
+                }
+                return;
+            };
+            let reference = reference.to_string();
+            // Not local, or dangling
+            let Some(target) = local_ref_target(root, &reference) else {
+                return;
+            };
+            if recursive.contains(&reference) {
+                remaining.push(reference);
+                return;
+            }
+
+            let mut expanded = target.clone();
+            obj.remove("$ref");
+            if let Value::Object(fields) = &mut expanded {
+                fields.extend(std::mem::take(obj));
+            }
+            inline_refs_in(&mut expanded, root, recursive, remaining);
+            *node = expanded;
+        }
+        Value::Array(items) => {
+            for item in items {
+                inline_refs_in(item, root, recursive, remaining);
+            }
+        }
+        _ => {}
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -656,7 +1708,7 @@ mod tests {
         )
         .unwrap();
 
-        let output = TypeScriptGenerator.generate(&schema, "Person");
+        let output = TypeScriptGenerator.generate(&schema, "Person").unwrap();
         assert!(output.contains("export interface Person"));
         assert!(output.contains("name: string"));
         assert!(output.contains("age?: number"));
@@ -671,7 +1723,580 @@ mod tests {
         )
         .unwrap();
 
-        let output = TypeScriptGenerator.generate(&schema, "Color");
+        let output = TypeScriptGenerator.generate(&schema, "Color").unwrap();
         assert!(output.contains("export type Color = \"red\" | \"green\" | \"blue\""));
     }
+
+    fn nullable_schema() -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "address": {
+                    "type": ["object", "null"],
+                    "properties": {"city": {"type": "string"}},
+                    "required": ["city"]
+                },
+                "manager": {"anyOf": [{"$ref": "#/$defs/Person"}, {"type": "null"}]},
+                "nickname": {"type": ["string", "null"]}
+            },
+            "required": ["address", "manager", "nickname"]
+        })
+    }
+
+    #[test]
+    fn test_nullable_object_ts() {
+        let output = TypeScriptGenerator
+            .generate(&nullable_schema(), "Person")
+            .unwrap();
+        assert!(output.contains("export interface PersonAddress {\n  city: string;"));
+        assert!(output.contains("address: PersonAddress | null;"));
+        assert!(output.contains("manager: Person | null;"));
+        assert!(output.contains("nickname: string | null;"));
+    }
+
+    #[test]
+    fn test_nullable_object_py() {
+        let output = PythonGenerator
+            .generate(&nullable_schema(), "Person")
+            .unwrap();
+        let nested = output.find("class PersonAddress:").unwrap();
+        assert!(nested < output.find("class Person:").unwrap());
+        assert!(output.contains("    address: Optional[PersonAddress]\n"));
+        assert!(output.contains("    manager: Optional[Person]\n"));
+    }
+
+    #[test]
+    fn test_nullable_object_rust() {
+        let output = RustGenerator
+            .generate(&nullable_schema(), "Person")
+            .unwrap();
+        assert!(output.contains("pub struct PersonAddress {\n    pub city: String,"));
+        assert!(output.contains("pub address: Option<PersonAddress>,"));
+        assert!(output.contains("pub manager: Option<Person>,"));
+        assert!(output.contains("pub nickname: Option<String>,"));
+    }
+
+    fn enum_schema() -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "tags": {"type": "array", "items": {"enum": ["news", "tech"]}},
+                "status": {"type": ["string", "null"], "enum": ["draft", "published", null]},
+                "author": {
+                    "anyOf": [
+                        {
+                            "type": "object",
+                            "properties": {"role": {"enum": ["editor", "guest"]}},
+                            "required": ["role"]
+                        },
+                        {"type": "null"}
+                    ]
+                }
+            },
+            "required": ["tags", "status", "author"],
+            "$defs": {"PostTagsItem": {"type": "string"}}
+        })
+    }
+
+    #[test]
+    fn test_promote_inline_enums() {
+        let schema = promote_inline_enums(&enum_schema(), "Post");
+        let defs = schema["$defs"].as_object().unwrap();
+        // The item name is taken, so the promoted enum gets a number suffix
+        assert_eq!(
+            defs["PostTagsItem2"],
+            serde_json::json!({"enum": ["news", "tech"]})
+        );
+        assert_eq!(
+            defs["PostStatus"],
+            serde_json::json!({"type": "string", "enum": ["draft", "published"]})
+        );
+        assert_eq!(
+            schema["properties"]["status"],
+            serde_json::json!({"anyOf": [{"$ref": "#/$defs/PostStatus"}, {"type": "null"}]})
+        );
+        assert!(defs.contains_key("PostAuthorRole"));
+    }
+
+    #[test]
+    fn test_array_of_enum_ts() {
+        let output = TypeScriptGenerator
+            .generate(&enum_schema(), "Post")
+            .unwrap();
+        assert!(output.contains("export type PostTagsItem2 = \"news\" | \"tech\""));
+        assert!(output.contains("tags: PostTagsItem2[];"));
+        assert!(output.contains("status: PostStatus | null;"));
+        assert!(output.contains("role: PostAuthorRole;"));
+    }
+
+    #[test]
+    fn test_array_of_enum_py() {
+        let output = PythonGenerator.generate(&enum_schema(), "Post").unwrap();
+        let alias = output
+            .find("PostTagsItem2 = Literal[\"news\", \"tech\"]")
+            .unwrap();
+        assert!(alias < output.find("class Post:").unwrap());
+        assert!(output.contains("    tags: list[PostTagsItem2]\n"));
+        assert!(output.contains("    status: Optional[PostStatus]\n"));
+        assert!(output.contains("    role: PostAuthorRole\n"));
+    }
+
+    #[test]
+    fn test_array_of_enum_rust() {
+        let output = RustGenerator.generate(&enum_schema(), "Post").unwrap();
+        assert!(output.contains("pub enum PostTagsItem2 {"));
+        assert!(output.contains("pub tags: Vec<PostTagsItem2>,"));
+        assert!(output.contains("pub status: Option<PostStatus>,"));
+        assert!(output.contains("pub enum PostAuthorRole {"));
+    }
+
+    fn strict_schema() -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "strict": {"$ref": "#/$defs/Strict"},
+                "open": {"$ref": "#/$defs/Open"}
+            },
+            "$defs": {
+                "Strict": {
+                    "type": "object",
+                    "properties": {"id": {"type": "integer"}},
+                    "additionalProperties": false
+                },
+                "Open": {
+                    "type": "object",
+                    "properties": {"id": {"type": "integer"}},
+                    "additionalProperties": true
+                },
+                // Without properties there are no fields to restrict to
+                "Empty": {"type": "object", "additionalProperties": false}
+            }
+        })
+    }
+
+    #[test]
+    fn test_strict_object_rust() {
+        let output = RustGenerator.generate(&strict_schema(), "Config").unwrap();
+        assert!(output.contains(
+            "#[derive(Debug, Clone, Serialize, Deserialize)]\n#[serde(deny_unknown_fields)]\npub struct Strict {"
+        ));
+        assert_eq!(output.matches("deny_unknown_fields").count(), 1);
+    }
+
+    #[test]
+    fn test_strict_object_ts_py() {
+        let output = TypeScriptGenerator
+            .generate(&strict_schema(), "Config")
+            .unwrap();
+        assert!(output.contains("(additionalProperties: false). */\nexport interface Strict {"));
+        assert_eq!(output.matches("Strict: unknown properties").count(), 1);
+
+        let mut schema = strict_schema();
+        schema["$defs"]["Either"] =
+            serde_json::json!({"oneOf": [{"$ref": "#/$defs/Strict"}, {"$ref": "#/$defs/Open"}]});
+        let output = PythonGenerator.generate(&schema, "Config").unwrap();
+        assert!(output.contains("_STRICT = {Strict}\n"));
+        assert!(output.contains("    if unknown and cls in _STRICT:\n"));
+        // Without strict types the helper stays as it was
+        let output = PythonGenerator.generate(
+            &pets_schema(
+                serde_json::json!({"oneOf": [{"$ref": "#/$defs/Cat"}, {"$ref": "#/$defs/Dog"}]}),
+            ),
+            "Pet",
+        ).unwrap();
+        assert!(!output.contains("_STRICT"));
+    }
+
+    fn pets_schema(union: Value) -> Value {
+        let mut schema = serde_json::json!({
+            "$defs": {
+                "Cat": {
+                    "type": "object",
+                    "properties": {"kind": {"const": "cat"}, "name": {"type": "string"}, "meows": {"type": "boolean"}},
+                    "required": ["kind", "name", "meows"]
+                },
+                "Dog": {
+                    "type": "object",
+                    "properties": {"kind": {"enum": ["dog"]}, "name": {"type": "string"}, "barks": {"type": "boolean"}},
+                    "required": ["kind", "name", "barks"]
+                }
+            }
+        });
+        schema
+            .as_object_mut()
+            .unwrap()
+            .extend(union.as_object().unwrap().clone());
+        schema
+    }
+
+    #[test]
+    fn test_union_parser_py() {
+        let refs =
+            serde_json::json!([{"$ref": "#/$defs/Cat"}, {"$ref": "#/$defs/Dog"}, {"type": "null"}]);
+
+        let schema = pets_schema(
+            serde_json::json!({"oneOf": refs, "discriminator": {"propertyName": "kind"}}),
+        );
+        let output = get_generator("python")
+            .unwrap()
+            .generate(&schema, "Pet")
+            .unwrap();
+        assert!(output.contains("from dataclasses import dataclass, fields\n"));
+        assert!(output.contains("def _construct(cls, data: dict):\n"));
+        assert!(output.contains("def parse_pet(data: dict) -> Pet:\n"));
+        assert!(output.contains(
+            "    tag = data.get(\"kind\")\n    variant = {\"cat\": Cat, \"dog\": Dog}.get(tag)\n"
+        ));
+
+        let schema = pets_schema(serde_json::json!({
+            "oneOf": refs,
+            "discriminator": {"propertyName": "kind", "mapping": {"c": "#/$defs/Cat", "d": "#/$defs/Dog"}}
+        }));
+        let output = get_generator("python")
+            .unwrap()
+            .generate(&schema, "Pet")
+            .unwrap();
+        assert!(output.contains("variant = {\"c\": Cat, \"d\": Dog}.get(tag)"));
+
+        let schema = pets_schema(serde_json::json!({"oneOf": refs}));
+        let output = get_generator("python")
+            .unwrap()
+            .generate(&schema, "Pet")
+            .unwrap();
+        assert!(output.contains(
+            "    variants = [(Cat, [\"kind\", \"name\", \"meows\"]), (Dog, [\"kind\", \"name\", \"barks\"])]\n"
+        ));
+        assert!(output.contains("if len(matches) > 1:"));
+
+        let schema = pets_schema(serde_json::json!({"anyOf": refs}));
+        let output = get_generator("python")
+            .unwrap()
+            .generate(&schema, "Pet")
+            .unwrap();
+        assert!(output.contains("    for cls, keys in variants:\n"));
+        assert!(!output.contains("matches"));
+
+        // Unions of scalars have nothing to construct
+        let schema = serde_json::json!({"oneOf": [{"type": "string"}, {"type": "integer"}]});
+        let output = get_generator("python")
+            .unwrap()
+            .generate(&schema, "Id")
+            .unwrap();
+        assert!(output.starts_with(
+            "# Auto-generated from JSON Schema\n\nfrom dataclasses import dataclass\n"
+        ));
+        assert!(!output.contains("def parse_id"));
+    }
+
+    #[test]
+    fn test_integer_enum_rust() {
+        let schema = serde_json::json!({"type": "integer", "enum": [1, 5, -1]});
+        let output = RustGenerator.generate(&schema, "Priority").unwrap();
+        assert!(output.contains("#[serde(try_from = \"i64\", into = \"i64\")]"));
+        assert!(
+            output.contains("pub enum Priority {\n    V1 = 1,\n    V5 = 5,\n    VNeg1 = -1,\n}")
+        );
+        assert!(output.contains("            5 => Ok(Self::V5),"));
+        assert!(output.contains("impl From<Priority> for i64 {"));
+
+        let named = serde_json::json!({"enum": [0, 1], "x-enum-varnames": ["low", "high"]});
+        let output = RustGenerator.generate(&named, "Level").unwrap();
+        assert!(output.contains("    Low = 0,\n    High = 1,"));
+    }
+
+    #[test]
+    fn test_mixed_enum_rust() {
+        let schema = serde_json::json!({"enum": ["auto", 0, 1.5]});
+        let output = RustGenerator.generate(&schema, "Size").unwrap();
+        assert!(
+            output.contains(
+                "/// Allowed values: \"auto\", 0, 1.5\npub type Size = serde_json::Value;"
+            )
+        );
+
+        let numbers = serde_json::json!({"enum": [0.5, 1]});
+        assert!(
+            RustGenerator
+                .generate(&numbers, "Ratio")
+                .unwrap()
+                .contains("pub type Ratio = f64;")
+        );
+
+        let typed = serde_json::json!({"type": ["string", "null"], "enum": ["a", null]});
+        assert!(
+            RustGenerator
+                .generate(&typed, "Mode")
+                .unwrap()
+                .contains("pub type Mode = Option<String>;")
+        );
+    }
+
+    #[test]
+    fn test_sample_synthesis() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "id": {"type": "integer"},
+                "name": {"type": "string", "example": "Ada"},
+                "tags": {"type": "array", "items": {"type": "string"}},
+                "role": {"enum": ["admin", "user"]},
+                "score": {"type": ["number", "null"]},
+                "active": {"type": "boolean", "default": true},
+                "manager": {"$ref": "#/$defs/Person"},
+                "address": {"$ref": "#/$defs/Address"}
+            },
+            "$defs": {
+                "Person": {
+                    "type": "object",
+                    "properties": {"name": {"type": "string"}, "manager": {"$ref": "#/$defs/Person"}}
+                },
+                "Address": {"type": "object", "examples": [{"city": "Paris"}]}
+            }
+        });
+
+        let root = sample(&schema, &schema);
+        assert_eq!(root["id"], 0);
+        assert_eq!(root["name"], "Ada");
+        assert_eq!(root["tags"], serde_json::json!(["string"]));
+        assert_eq!(root["role"], "admin");
+        assert_eq!(root["score"], 0.0);
+        assert_eq!(root["active"], true);
+        // Person's reference to itself is cut off
+        assert_eq!(
+            root["manager"],
+            serde_json::json!({"name": "string", "manager": null})
+        );
+        assert_eq!(root["address"], serde_json::json!({"city": "Paris"}));
+
+        let names: Vec<_> = samples(&schema, "Employee")
+            .into_iter()
+            .map(|s| s.type_name)
+            .collect();
+        if !cfg!(feature = "preserve_order") {
+            assert_eq!(names, vec!["Address", "Person", "Employee"]);
+        }
+    }
+
+    #[test]
+    fn test_sample_compositions() {
+        let openapi_examples = serde_json::json!({
+            "type": "string",
+            "examples": {"short": {"value": "hi"}}
+        });
+        assert_eq!(sample(&openapi_examples, &openapi_examples), "hi");
+
+        let all_of = serde_json::json!({"allOf": [
+            {"properties": {"a": {"type": "integer"}}},
+            {"properties": {"b": {"const": "x"}}}
+        ]});
+        assert_eq!(
+            sample(&all_of, &all_of),
+            serde_json::json!({"a": 0, "b": "x"})
+        );
+
+        let nullable = serde_json::json!({"anyOf": [{"type": "null"}, {"type": "boolean"}]});
+        assert_eq!(sample(&nullable, &nullable), false);
+
+        let unresolved = serde_json::json!({"$ref": "other.json#/Thing"});
+        assert_eq!(sample(&unresolved, &unresolved), Value::Null);
+    }
+
+    #[test]
+    fn test_infer_schema() {
+        let sample = serde_json::json!({
+            "id": 1,
+            "name": "Ada",
+            "score": 9.5,
+            "nickname": null,
+            "tags": ["a", "b"],
+            "address": {"city": "Paris", "zip": "75001"},
+            "orders": [
+                {"id": 1, "total": 10, "note": null},
+                {"id": 2, "total": 12.5, "note": "gift", "coupon": "X"}
+            ],
+            "manager": null,
+            "empty": []
+        });
+        let schema = infer_schema(&sample, "Customer");
+
+        let props = &schema["properties"];
+        assert_eq!(props["id"], serde_json::json!({"type": "integer"}));
+        assert_eq!(props["score"], serde_json::json!({"type": "number"}));
+        assert_eq!(props["nickname"], serde_json::json!({"type": "null"}));
+        assert_eq!(
+            props["tags"],
+            serde_json::json!({"type": "array", "items": {"type": "string"}})
+        );
+        assert_eq!(props["empty"], serde_json::json!({"type": "array"}));
+        assert_eq!(
+            props["address"],
+            serde_json::json!({"$ref": "#/$defs/CustomerAddress"})
+        );
+        assert_eq!(
+            props["orders"]["items"],
+            serde_json::json!({"$ref": "#/$defs/CustomerOrdersItem"})
+        );
+        assert_eq!(schema["required"].as_array().unwrap().len(), 9);
+
+        let defs = &schema["$defs"];
+        assert_eq!(
+            defs["CustomerAddress"]["required"],
+            serde_json::json!(["city", "zip"])
+        );
+        // Merged from both elements: widened, nullable, and optional
+        let order = &defs["CustomerOrdersItem"];
+        assert_eq!(
+            order["properties"]["total"],
+            serde_json::json!({"type": "number"})
+        );
+        assert_eq!(
+            order["properties"]["note"],
+            serde_json::json!({"type": ["string", "null"]})
+        );
+        assert_eq!(
+            order["properties"]["coupon"],
+            serde_json::json!({"type": "string"})
+        );
+        // `note` is in every element, `coupon` only in one
+        if !cfg!(feature = "preserve_order") {
+            assert_eq!(
+                order["required"],
+                serde_json::json!(["id", "note", "total"])
+            );
+        }
+
+        let rust = get_generator("rust")
+            .unwrap()
+            .generate(&schema, "Customer")
+            .unwrap();
+        assert!(rust.contains("pub struct CustomerOrdersItem {"));
+        assert!(rust.contains("pub note: Option<String>,"));
+        assert!(rust.contains("pub coupon: Option<String>,"));
+        assert!(rust.contains("pub orders: Vec<CustomerOrdersItem>,"));
+        assert!(rust.contains("pub address: CustomerAddress,"));
+    }
+
+    #[test]
+    fn test_infer_schema_merging() {
+        // Nullable objects, mixed scalars, and structures mixed with scalars
+        let sample = serde_json::json!([
+            {"parent": {"id": 1}, "value": 1, "data": {"a": 1}},
+            {"parent": null, "value": "one", "data": 3}
+        ]);
+        let schema = infer_schema(&sample, "Root");
+        assert_eq!(
+            schema["items"],
+            serde_json::json!({"$ref": "#/$defs/RootItem"})
+        );
+        let item = &schema["$defs"]["RootItem"]["properties"];
+        assert_eq!(
+            item["parent"],
+            serde_json::json!({"anyOf": [{"$ref": "#/$defs/RootItemParent"}, {"type": "null"}]})
+        );
+        assert_eq!(
+            item["value"],
+            serde_json::json!({"type": ["integer", "string"]})
+        );
+        assert_eq!(item["data"], serde_json::json!({}));
+        assert_eq!(
+            schema["$defs"]["RootItemParent"]["properties"]["id"],
+            serde_json::json!({"type": "integer"})
+        );
+    }
+
+    #[test]
+    fn test_inline_refs() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "home": {"$ref": "#/$defs/Address"},
+                "work": {"$ref": "#/$defs/Address", "description": "Office"},
+                "tree": {"$ref": "#/$defs/Node"},
+                "tags": {"type": "array", "items": {"$ref": "#/$defs/Tag"}},
+                "remote": {"$ref": "other.json#/Thing"}
+            },
+            "$defs": {
+                "Address": {
+                    "type": "object",
+                    "properties": {"city": {"type": "string"}, "geo": {"$ref": "#/$defs/Geo"}}
+                },
+                "Geo": {"type": "object", "properties": {"lat": {"type": "number"}}},
+                "Tag": {"type": "string", "enum": ["a", "b"], "default": {"$ref": "not a schema"}},
+                "Node": {
+                    "type": "object",
+                    "properties": {
+                        "value": {"$ref": "#/$defs/Tag"},
+                        "children": {"type": "array", "items": {"$ref": "#/$defs/Node"}}
+                    }
+                },
+                "Unused": {"type": "integer"}
+            }
+        });
+        let inlined = inline_refs(&schema);
+        let props = &inlined["properties"];
+
+        // Nested references are expanded too
+        assert_eq!(
+            props["home"]["properties"]["geo"]["properties"]["lat"]["type"],
+            "number"
+        );
+        assert_eq!(props["work"]["description"], "Office");
+        assert_eq!(props["work"]["properties"]["city"]["type"], "string");
+        assert_eq!(
+            props["tags"]["items"]["enum"],
+            serde_json::json!(["a", "b"])
+        );
+        assert_eq!(
+            props["tags"]["items"]["default"],
+            serde_json::json!({"$ref": "not a schema"})
+        );
+        assert_eq!(
+            props["remote"],
+            serde_json::json!({"$ref": "other.json#/Thing"})
+        );
+
+        // A recursive type stays a reference, and only its definition is kept
+        assert_eq!(props["tree"], serde_json::json!({"$ref": "#/$defs/Node"}));
+        let defs = inlined["$defs"].as_object().unwrap();
+        assert_eq!(defs.keys().collect::<Vec<_>>(), vec!["Node"]);
+        assert_eq!(defs["Node"]["properties"]["value"]["type"], "string");
+        assert_eq!(
+            defs["Node"]["properties"]["children"]["items"],
+            serde_json::json!({"$ref": "#/$defs/Node"})
+        );
+
+        let ts = TypeScriptGenerator.generate(&inlined, "Person").unwrap();
+        assert!(
+            ts.contains("  home?: { city?: string; geo?: { lat?: number } };"),
+            "{}",
+            ts
+        );
+        assert!(ts.contains("  tree?: Node;"), "{}", ts);
+        assert!(ts.contains("export interface Node {"), "{}", ts);
+        assert!(!ts.contains("Address"), "{}", ts);
+    }
+
+    #[test]
+    fn test_generation_is_deterministic() {
+        let schema: Value = serde_json::from_str(
+            r##"{
+            "type": "object",
+            "properties": {"zone": {"$ref": "#/$defs/Zone"}, "account": {"$ref": "#/$defs/Account"}},
+            "$defs": {
+                "Zone": {"type": "object", "properties": {"zid": {"type": "string"}, "area": {"type": "number"}}},
+                "Account": {"type": "object", "properties": {"owner": {"type": "string"}, "balance": {"type": "number"}}}
+            }
+        }"##,
+        )
+        .unwrap();
+        for generator in generators() {
+            let first = generator.generate(&schema, "Root").unwrap();
+            assert_eq!(first, generator.generate(&schema, "Root").unwrap());
+            if !cfg!(feature = "preserve_order") {
+                let position = |needle: &str| first.find(needle).unwrap();
+                assert!(position("Account") < position("Zone"), "{}", first);
+                assert!(position("balance") < position("owner"), "{}", first);
+            }
+        }
+    }
 }
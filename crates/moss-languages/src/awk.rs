@@ -26,6 +26,18 @@ impl Language for Awk {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        false
+    }
+
+    fn supports_docs(&self) -> bool {
+        true
+    }
+
+    fn supports_complexity(&self) -> bool {
+        true
+    }
+
     fn container_kinds(&self) -> &'static [&'static str] {
         &[]
     }
@@ -171,6 +183,10 @@ impl Language for Awk {
         }
     }
 
+    fn is_test_file(&self, _path: &Path) -> bool {
+        false
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
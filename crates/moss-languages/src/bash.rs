@@ -23,6 +23,18 @@ impl Language for Bash {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        false
+    }
+
+    fn supports_docs(&self) -> bool {
+        false
+    }
+
+    fn supports_complexity(&self) -> bool {
+        true
+    }
+
     fn container_kinds(&self) -> &'static [&'static str] {
         &[]
     }
@@ -152,6 +164,10 @@ impl Language for Bash {
         }
     }
 
+    fn is_test_file(&self, _path: &Path) -> bool {
+        false
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
@@ -23,6 +23,18 @@ impl Language for Batch {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        false
+    }
+
+    fn supports_docs(&self) -> bool {
+        false
+    }
+
+    fn supports_complexity(&self) -> bool {
+        false
+    }
+
     fn container_kinds(&self) -> &'static [&'static str] {
         &[]
     }
@@ -154,6 +166,10 @@ impl Language for Batch {
         }
     }
 
+    fn is_test_file(&self, _path: &Path) -> bool {
+        false
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
@@ -24,6 +24,18 @@ impl Language for C {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        false
+    }
+
+    fn supports_docs(&self) -> bool {
+        false
+    }
+
+    fn supports_complexity(&self) -> bool {
+        true
+    }
+
     fn container_kinds(&self) -> &'static [&'static str] {
         &[]
     } // C doesn't have containers
@@ -214,6 +226,10 @@ impl Language for C {
         }
     }
 
+    fn is_test_file(&self, _path: &Path) -> bool {
+        false
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
@@ -23,6 +23,18 @@ impl Language for Clojure {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        false
+    }
+
+    fn supports_docs(&self) -> bool {
+        true
+    }
+
+    fn supports_complexity(&self) -> bool {
+        true
+    }
+
     fn container_kinds(&self) -> &'static [&'static str] {
         &["list_lit"] // (defn ...), (ns ...), etc.
     }
@@ -249,6 +261,11 @@ impl Language for Clojure {
         }
     }
 
+    fn is_test_file(&self, path: &Path) -> bool {
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        stem.ends_with("_test")
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
@@ -24,6 +24,18 @@ impl Language for Cpp {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        false
+    }
+
+    fn supports_docs(&self) -> bool {
+        false
+    }
+
+    fn supports_complexity(&self) -> bool {
+        true
+    }
+
     fn container_kinds(&self) -> &'static [&'static str] {
         &["class_specifier", "struct_specifier"]
     }
@@ -246,6 +258,11 @@ impl Language for Cpp {
         }
     }
 
+    fn is_test_file(&self, path: &Path) -> bool {
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        stem.ends_with("_test") || stem.ends_with("_unittest")
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
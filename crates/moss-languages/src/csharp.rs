@@ -23,6 +23,18 @@ impl Language for CSharp {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        false
+    }
+
+    fn supports_docs(&self) -> bool {
+        true
+    }
+
+    fn supports_complexity(&self) -> bool {
+        true
+    }
+
     fn container_kinds(&self) -> &'static [&'static str] {
         &[
             "class_declaration",
@@ -350,6 +362,11 @@ impl Language for CSharp {
         }
     }
 
+    fn is_test_file(&self, path: &Path) -> bool {
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        stem.ends_with("Test") || stem.ends_with("Tests")
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
@@ -23,6 +23,18 @@ impl Language for Css {
         false
     }
 
+    fn supports_call_graph(&self) -> bool {
+        false
+    }
+
+    fn supports_docs(&self) -> bool {
+        false
+    }
+
+    fn supports_complexity(&self) -> bool {
+        false
+    }
+
     // CSS has no functions/containers/types in the traditional sense
     fn container_kinds(&self) -> &'static [&'static str] {
         &[]
@@ -105,6 +117,10 @@ impl Language for Css {
         false
     }
 
+    fn is_test_file(&self, _path: &Path) -> bool {
+        false
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
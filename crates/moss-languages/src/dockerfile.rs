@@ -23,6 +23,18 @@ impl Language for Dockerfile {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        false
+    }
+
+    fn supports_docs(&self) -> bool {
+        false
+    }
+
+    fn supports_complexity(&self) -> bool {
+        false
+    }
+
     // Dockerfiles have stages (FROM ... AS name) that act as containers
     fn container_kinds(&self) -> &'static [&'static str] {
         &["from_instruction"]
@@ -169,6 +181,10 @@ impl Language for Dockerfile {
         false
     }
 
+    fn is_test_file(&self, _path: &Path) -> bool {
+        false
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
@@ -88,6 +88,20 @@ pub const NESTING_NODES: &[&str] = &[
     "class_declaration",
 ];
 
+// ============================================================================
+// Test files
+// ============================================================================
+
+/// Whether `path` is a test file by the usual JavaScript/TypeScript conventions
+/// (`*.test.ts`, `*.spec.js`, anything under `__tests__/`). Their tests are
+/// `describe`/`it`/`test` calls rather than symbols, so the whole file is test code.
+pub fn is_test_file(path: &Path) -> bool {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    stem.ends_with(".test")
+        || stem.ends_with(".spec")
+        || path.components().any(|c| c.as_os_str() == "__tests__")
+}
+
 // ============================================================================
 // Symbol extraction
 // ============================================================================
@@ -123,7 +137,7 @@ pub fn extract_function(node: &Node, content: &str, in_container: bool, name: &s
         },
         signature,
         docstring: None,
-        attributes: Vec::new(),
+        attributes: extract_decorators(node, content),
         start_line: node.start_position().row + 1,
         end_line: node.end_position().row + 1,
         visibility: Visibility::Public,
@@ -175,7 +189,7 @@ pub fn extract_container(node: &Node, content: &str, name: &str) -> Symbol {
         kind,
         signature: format!("{} {}", keyword, name),
         docstring: None,
-        attributes: Vec::new(),
+        attributes: extract_decorators(node, content),
         start_line: node.start_position().row + 1,
         end_line: node.end_position().row + 1,
         visibility: Visibility::Public,
@@ -185,8 +199,45 @@ pub fn extract_container(node: &Node, content: &str, name: &str) -> Symbol {
     }
 }
 
+/// Extract decorators (`@Component(...)`) applied to a class, method or field.
+///
+/// Depending on the grammar version decorators are children of the decorated
+/// node, children of an enclosing `export_statement`, or preceding siblings in
+/// a class body; all three are collected, in source order.
+pub fn extract_decorators(node: &Node, content: &str) -> Vec<String> {
+    let mut decorators = Vec::new();
+
+    let mut prev = node.prev_sibling();
+    while let Some(sibling) = prev
+        && sibling.kind() == "decorator"
+    {
+        decorators.insert(0, content[sibling.byte_range()].to_string());
+        prev = sibling.prev_sibling();
+    }
+
+    if let Some(parent) = node.parent()
+        && parent.kind() == "export_statement"
+    {
+        let mut cursor = parent.walk();
+        decorators.extend(
+            parent
+                .children(&mut cursor)
+                .filter(|c| c.kind() == "decorator")
+                .map(|c| content[c.byte_range()].to_string()),
+        );
+    }
+
+    let mut cursor = node.walk();
+    decorators.extend(
+        node.children(&mut cursor)
+            .filter(|c| c.kind() == "decorator")
+            .map(|c| content[c.byte_range()].to_string()),
+    );
+    decorators
+}
+
 /// Extract a TypeScript type symbol (interface, type alias, enum).
-pub fn extract_type(node: &Node, name: &str) -> Option<Symbol> {
+pub fn extract_type(node: &Node, content: &str, name: &str) -> Option<Symbol> {
     let (kind, keyword) = match node.kind() {
         "interface_declaration" => (SymbolKind::Interface, "interface"),
         "type_alias_declaration" => (SymbolKind::Type, "type"),
@@ -200,7 +251,7 @@ pub fn extract_type(node: &Node, name: &str) -> Option<Symbol> {
         kind,
         signature: format!("{} {}", keyword, name),
         docstring: None,
-        attributes: Vec::new(),
+        attributes: extract_decorators(node, content),
         start_line: node.start_position().row + 1,
         end_line: node.end_position().row + 1,
         visibility: Visibility::Public,
@@ -23,6 +23,18 @@ impl Language for Gleam {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        false
+    }
+
+    fn supports_docs(&self) -> bool {
+        true
+    }
+
+    fn supports_complexity(&self) -> bool {
+        true
+    }
+
     fn container_kinds(&self) -> &'static [&'static str] {
         &["type_definition", "type_alias"]
     }
@@ -247,6 +259,10 @@ impl Language for Gleam {
         }
     }
 
+    fn is_test_file(&self, path: &Path) -> bool {
+        path.components().any(|c| c.as_os_str() == "test")
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
@@ -293,6 +293,18 @@ impl Language for Go {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        true
+    }
+
+    fn supports_docs(&self) -> bool {
+        false
+    }
+
+    fn supports_complexity(&self) -> bool {
+        true
+    }
+
     fn container_kinds(&self) -> &'static [&'static str] {
         &[] // Go types don't have children in the tree-sitter sense
     }
@@ -546,11 +558,17 @@ impl Language for Go {
                 name.starts_with("Test")
                     || name.starts_with("Benchmark")
                     || name.starts_with("Example")
+                    || name.starts_with("Fuzz")
             }
             _ => false,
         }
     }
 
+    fn is_test_file(&self, path: &Path) -> bool {
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        stem.ends_with("_test")
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
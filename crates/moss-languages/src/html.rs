@@ -23,6 +23,18 @@ impl Language for Html {
         false
     }
 
+    fn supports_call_graph(&self) -> bool {
+        false
+    }
+
+    fn supports_docs(&self) -> bool {
+        false
+    }
+
+    fn supports_complexity(&self) -> bool {
+        false
+    }
+
     // HTML has no functions/containers/types in the traditional sense
     fn container_kinds(&self) -> &'static [&'static str] {
         &[]
@@ -105,6 +117,10 @@ impl Language for Html {
         false
     }
 
+    fn is_test_file(&self, _path: &Path) -> bool {
+        false
+    }
+
     fn embedded_content(&self, node: &Node, content: &str) -> Option<crate::EmbeddedBlock> {
         match node.kind() {
             "script_element" => {
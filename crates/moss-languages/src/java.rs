@@ -330,6 +330,18 @@ impl Language for Java {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        true
+    }
+
+    fn supports_docs(&self) -> bool {
+        false
+    }
+
+    fn supports_complexity(&self) -> bool {
+        true
+    }
+
     fn container_kinds(&self) -> &'static [&'static str] {
         &[
             "class_declaration",
@@ -480,7 +492,7 @@ impl Language for Java {
             kind: SymbolKind::Method,
             signature: format!("{}{}", name, params),
             docstring: None,
-            attributes: Vec::new(),
+            attributes: self.extract_attributes(node, content),
             start_line: node.start_position().row + 1,
             end_line: node.end_position().row + 1,
             visibility: self.get_visibility(node, content),
@@ -503,7 +515,7 @@ impl Language for Java {
             kind,
             signature: format!("{} {}", kind.as_str(), name),
             docstring: None,
-            attributes: Vec::new(),
+            attributes: self.extract_attributes(node, content),
             start_line: node.start_position().row + 1,
             end_line: node.end_position().row + 1,
             visibility: self.get_visibility(node, content),
@@ -522,8 +534,16 @@ impl Language for Java {
         None
     }
 
-    fn extract_attributes(&self, _node: &Node, _content: &str) -> Vec<String> {
-        Vec::new()
+    fn extract_attributes(&self, node: &Node, content: &str) -> Vec<String> {
+        let mut cursor = node.walk();
+        let Some(mods) = node.children(&mut cursor).find(|c| c.kind() == "modifiers") else {
+            return Vec::new();
+        };
+        let mut cursor = mods.walk();
+        mods.children(&mut cursor)
+            .filter(|c| matches!(c.kind(), "marker_annotation" | "annotation"))
+            .map(|c| content[c.byte_range()].to_string())
+            .collect()
     }
 
     fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
@@ -587,6 +607,11 @@ impl Language for Java {
         }
     }
 
+    fn is_test_file(&self, path: &Path) -> bool {
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        stem.ends_with("Test") || stem.ends_with("Tests")
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
@@ -24,6 +24,18 @@ impl Language for JavaScript {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        true
+    }
+
+    fn supports_docs(&self) -> bool {
+        false
+    }
+
+    fn supports_complexity(&self) -> bool {
+        true
+    }
+
     fn container_kinds(&self) -> &'static [&'static str] {
         ecmascript::JS_CONTAINER_KINDS
     }
@@ -84,8 +96,8 @@ impl Language for JavaScript {
         None
     }
 
-    fn extract_attributes(&self, _node: &Node, _content: &str) -> Vec<String> {
-        Vec::new()
+    fn extract_attributes(&self, node: &Node, content: &str) -> Vec<String> {
+        ecmascript::extract_decorators(node, content)
     }
 
     fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
@@ -128,6 +140,10 @@ impl Language for JavaScript {
         }
     }
 
+    fn is_test_file(&self, path: &Path) -> bool {
+        ecmascript::is_test_file(path)
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
@@ -26,6 +26,18 @@ impl Language for Julia {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        false
+    }
+
+    fn supports_docs(&self) -> bool {
+        true
+    }
+
+    fn supports_complexity(&self) -> bool {
+        true
+    }
+
     fn container_kinds(&self) -> &'static [&'static str] {
         &[
             "module_definition",
@@ -257,6 +269,10 @@ impl Language for Julia {
         }
     }
 
+    fn is_test_file(&self, path: &Path) -> bool {
+        path.components().any(|c| c.as_os_str() == "test")
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
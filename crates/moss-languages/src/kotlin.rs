@@ -24,6 +24,18 @@ impl Language for Kotlin {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        false
+    }
+
+    fn supports_docs(&self) -> bool {
+        true
+    }
+
+    fn supports_complexity(&self) -> bool {
+        true
+    }
+
     fn container_kinds(&self) -> &'static [&'static str] {
         &["class_declaration", "object_declaration", "enum_class_body"]
     }
@@ -165,7 +177,7 @@ impl Language for Kotlin {
             kind: SymbolKind::Function,
             signature: format!("fun {}{}{}", name, params, return_type),
             docstring: None,
-            attributes: Vec::new(),
+            attributes: self.extract_attributes(node, content),
             start_line: node.start_position().row + 1,
             end_line: node.end_position().row + 1,
             visibility: self.get_visibility(node, content),
@@ -187,7 +199,7 @@ impl Language for Kotlin {
             kind,
             signature: format!("{} {}", keyword, name),
             docstring: None,
-            attributes: Vec::new(),
+            attributes: self.extract_attributes(node, content),
             start_line: node.start_position().row + 1,
             end_line: node.end_position().row + 1,
             visibility: self.get_visibility(node, content),
@@ -209,7 +221,7 @@ impl Language for Kotlin {
                 kind: SymbolKind::Type,
                 signature: format!("typealias {} = {}", name, target),
                 docstring: None,
-                attributes: Vec::new(),
+                attributes: self.extract_attributes(node, content),
                 start_line: node.start_position().row + 1,
                 end_line: node.end_position().row + 1,
                 visibility: self.get_visibility(node, content),
@@ -255,8 +267,16 @@ impl Language for Kotlin {
         None
     }
 
-    fn extract_attributes(&self, _node: &Node, _content: &str) -> Vec<String> {
-        Vec::new()
+    fn extract_attributes(&self, node: &Node, content: &str) -> Vec<String> {
+        let mut cursor = node.walk();
+        let Some(mods) = node.children(&mut cursor).find(|c| c.kind() == "modifiers") else {
+            return Vec::new();
+        };
+        let mut cursor = mods.walk();
+        mods.children(&mut cursor)
+            .filter(|c| c.kind() == "annotation")
+            .map(|c| content[c.byte_range()].trim().to_string())
+            .collect()
     }
 
     fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
@@ -312,6 +332,11 @@ impl Language for Kotlin {
         }
     }
 
+    fn is_test_file(&self, path: &Path) -> bool {
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        stem.ends_with("Test") || stem.ends_with("Tests")
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
@@ -319,6 +344,12 @@ impl Language for Kotlin {
     fn container_body<'a>(&self, node: &'a Node<'a>) -> Option<Node<'a>> {
         node.child_by_field_name("class_body")
             .or_else(|| node.child_by_field_name("body"))
+            .or_else(|| {
+                // Grammars without fields: the body is a class_body child
+                let mut cursor = node.walk();
+                node.children(&mut cursor)
+                    .find(|c| matches!(c.kind(), "class_body" | "enum_class_body"))
+            })
     }
 
     fn body_has_docstring(&self, _body: &Node, _content: &str) -> bool {
@@ -330,10 +361,11 @@ impl Language for Kotlin {
         if let Some(name_node) = node.child_by_field_name("name") {
             return Some(&content[name_node.byte_range()]);
         }
-        // For type alias, the name might be a simple_identifier
+        // Without a name field: classes are named by a type_identifier, type
+        // aliases and functions by a simple_identifier
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
-            if child.kind() == "simple_identifier" {
+            if matches!(child.kind(), "simple_identifier" | "type_identifier") {
                 return Some(&content[child.byte_range()]);
             }
         }
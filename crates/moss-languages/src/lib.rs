@@ -141,9 +141,9 @@ pub use registry::{
     validate_unused_kinds_audit,
 };
 pub use traits::{
-    EmbeddedBlock, Export, Import, Language, PackageSource, PackageSourceKind, Symbol, SymbolKind,
-    Visibility, VisibilityMechanism, has_extension, simple_function_symbol, simple_symbol,
-    skip_dotfiles,
+    Capabilities, EmbeddedBlock, Export, Import, Language, LineRange, PackageSource,
+    PackageSourceKind, Symbol, SymbolKind, Visibility, VisibilityMechanism, has_extension,
+    simple_function_symbol, simple_symbol, skip_dotfiles, test_line_ranges,
 };
 
 // Re-export language structs
@@ -23,6 +23,18 @@ impl Language for Markdown {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        false
+    }
+
+    fn supports_docs(&self) -> bool {
+        false
+    }
+
+    fn supports_complexity(&self) -> bool {
+        false
+    }
+
     // Markdown is documentation, not code - no functions/types/control flow
     fn container_kinds(&self) -> &'static [&'static str] {
         &["atx_heading", "setext_heading"]
@@ -144,6 +156,10 @@ impl Language for Markdown {
         false
     }
 
+    fn is_test_file(&self, _path: &Path) -> bool {
+        false
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
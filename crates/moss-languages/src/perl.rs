@@ -23,6 +23,18 @@ impl Language for Perl {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        false
+    }
+
+    fn supports_docs(&self) -> bool {
+        true
+    }
+
+    fn supports_complexity(&self) -> bool {
+        true
+    }
+
     fn container_kinds(&self) -> &'static [&'static str] {
         &["package_statement"]
     }
@@ -246,6 +258,11 @@ impl Language for Perl {
         }
     }
 
+    fn is_test_file(&self, path: &Path) -> bool {
+        // Test::More scripts
+        path.extension().is_some_and(|e| e == "t")
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
@@ -411,6 +411,18 @@ impl Language for Python {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        true
+    }
+
+    fn supports_docs(&self) -> bool {
+        true
+    }
+
+    fn supports_complexity(&self) -> bool {
+        true
+    }
+
     fn container_kinds(&self) -> &'static [&'static str] {
         &["class_definition"]
     }
@@ -536,7 +548,7 @@ impl Language for Python {
             },
             signature,
             docstring: self.extract_docstring(node, content),
-            attributes: Vec::new(),
+            attributes: self.extract_attributes(node, content),
             start_line: node.start_position().row + 1,
             end_line: node.end_position().row + 1,
             visibility,
@@ -565,7 +577,7 @@ impl Language for Python {
             kind: SymbolKind::Class,
             signature,
             docstring: self.extract_docstring(node, content),
-            attributes: Vec::new(),
+            attributes: self.extract_attributes(node, content),
             start_line: node.start_position().row + 1,
             end_line: node.end_position().row + 1,
             visibility: self.get_visibility(node, content),
@@ -624,8 +636,17 @@ impl Language for Python {
         }
     }
 
-    fn extract_attributes(&self, _node: &Node, _content: &str) -> Vec<String> {
-        Vec::new()
+    fn extract_attributes(&self, node: &Node, content: &str) -> Vec<String> {
+        // Decorators are siblings of the definition in a decorated_definition
+        let Some(parent) = node.parent().filter(|p| p.kind() == "decorated_definition") else {
+            return Vec::new();
+        };
+        let mut cursor = parent.walk();
+        parent
+            .children(&mut cursor)
+            .filter(|c| c.kind() == "decorator")
+            .map(|c| content[c.byte_range()].to_string())
+            .collect()
     }
 
     fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
@@ -795,6 +816,14 @@ impl Language for Python {
 
     fn is_test_symbol(&self, symbol: &crate::Symbol) -> bool {
         let name = symbol.name.as_str();
+        // `@pytest.mark.parametrize(...)`, `@pytest.fixture`, ...
+        if symbol
+            .attributes
+            .iter()
+            .any(|a| a.starts_with("@pytest.") || a.starts_with("@fixture"))
+        {
+            return true;
+        }
         match symbol.kind {
             crate::SymbolKind::Function | crate::SymbolKind::Method => name.starts_with("test_"),
             crate::SymbolKind::Class => name.starts_with("Test") && name.len() > 4,
@@ -803,6 +832,11 @@ impl Language for Python {
         }
     }
 
+    fn is_test_file(&self, path: &Path) -> bool {
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        stem.starts_with("test_") || stem.ends_with("_test") || stem == "conftest"
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
@@ -368,6 +368,26 @@ mod tests {
         }
     }
 
+    /// Capability flags must agree with what the trait methods actually provide.
+    #[test]
+    fn capabilities_match_trait_methods() {
+        for lang in supported_languages() {
+            assert_eq!(
+                lang.supports_complexity(),
+                !lang.complexity_nodes().is_empty(),
+                "{}: supports_complexity disagrees with complexity_nodes",
+                lang.name()
+            );
+            if lang.supports_call_graph() || lang.supports_docs() {
+                assert!(
+                    lang.has_symbols(),
+                    "{}: calls and docs are attached to symbols",
+                    lang.name()
+                );
+            }
+        }
+    }
+
     /// Cross-check grammar node kinds against Language implementations.
     /// Finds potentially useful kinds that exist in the grammar but aren't used.
     /// Run with: cargo test -p rhizome-moss-languages cross_check_node_kinds -- --nocapture --ignored
@@ -23,6 +23,18 @@ impl Language for Ruby {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        false
+    }
+
+    fn supports_docs(&self) -> bool {
+        false
+    }
+
+    fn supports_complexity(&self) -> bool {
+        true
+    }
+
     fn container_kinds(&self) -> &'static [&'static str] {
         &["class", "module"]
     }
@@ -59,15 +71,21 @@ impl Language for Ruby {
         &[
             "if",
             "unless",
-            "case",
+            "elsif",
             "when",
             "while",
             "until",
             "for",
-            "begin", // rescue clauses
             "rescue",
+            "if_modifier",
+            "unless_modifier",
+            "while_modifier",
+            "until_modifier",
+            "rescue_modifier",
             "and",
             "or",
+            "&&",
+            "||",
             "conditional",
         ]
     }
@@ -192,6 +210,11 @@ impl Language for Ruby {
         }
     }
 
+    fn is_test_file(&self, path: &Path) -> bool {
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        stem.ends_with("_spec") || stem.ends_with("_test") || stem.starts_with("test_")
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
@@ -280,14 +303,13 @@ mod tests {
             // STRUCTURAL
             "begin_block", "block_argument", "block_body", "block_parameter", "block_parameters",
             "body_statement", "class_variable", "destructured_left_assignment",
-            "destructured_parameter", "else", "elsif", "empty_statement", "end_block",
+            "destructured_parameter", "else", "empty_statement", "end_block",
             "exception_variable", "exceptions", "expression_reference_pattern", "forward_argument",
             "forward_parameter", "heredoc_body", "identifier", "lambda_parameters",
             "method_parameters", "operator", "operator_assignment", "parenthesized_statements",
             "singleton_class", "superclass",
             // CLAUSE
-            "case_match", "if_guard", "if_modifier", "in_clause", "match_pattern",
-            "rescue_modifier", "unless_modifier", "until_modifier", "while_modifier",
+            "case_match", "if_guard", "in_clause", "match_pattern",
             // EXPRESSION
             "yield",
         ];
@@ -122,6 +122,18 @@ impl Language for Rust {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        true
+    }
+
+    fn supports_docs(&self) -> bool {
+        true
+    }
+
+    fn supports_complexity(&self) -> bool {
+        true
+    }
+
     fn container_kinds(&self) -> &'static [&'static str] {
         &["impl_item", "trait_item", "mod_item"]
     }
@@ -518,10 +530,18 @@ impl Language for Rust {
     }
 
     fn is_test_symbol(&self, symbol: &crate::Symbol) -> bool {
-        let in_attrs = symbol
-            .attributes
-            .iter()
-            .any(|a| a.contains("#[test]") || a.contains("#[cfg(test)]"));
+        let in_attrs = symbol.attributes.iter().any(|a| {
+            a.contains("#[cfg(test)]") || {
+                // `#[test]`, `#[tokio::test]`, `#[rstest]`, `#[bench]`, ...
+                let path = a
+                    .trim_start_matches("#[")
+                    .split(['(', ']'])
+                    .next()
+                    .unwrap_or("")
+                    .trim();
+                matches!(path, "test" | "bench" | "rstest") || path.ends_with("::test")
+            }
+        });
         let in_sig =
             symbol.signature.contains("#[test]") || symbol.signature.contains("#[cfg(test)]");
         if in_attrs || in_sig {
@@ -536,6 +556,14 @@ impl Language for Rust {
         }
     }
 
+    fn is_test_file(&self, path: &Path) -> bool {
+        // Integration tests (`tests/`) and `mod tests;` split into its own file
+        path.file_stem().is_some_and(|s| s == "tests")
+            || path
+                .parent()
+                .is_some_and(|dir| dir.components().any(|c| c.as_os_str() == "tests"))
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
@@ -23,6 +23,18 @@ impl Language for Swift {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        false
+    }
+
+    fn supports_docs(&self) -> bool {
+        true
+    }
+
+    fn supports_complexity(&self) -> bool {
+        true
+    }
+
     fn container_kinds(&self) -> &'static [&'static str] {
         &["class_declaration", "protocol_declaration"]
     }
@@ -119,10 +131,13 @@ impl Language for Swift {
             "for_statement",
             "while_statement",
             "repeat_while_statement",
-            "switch_statement",
+            "guard_statement",
+            "switch_entry",
             "catch_block",
             "ternary_expression",
             "nil_coalescing_expression",
+            "conjunction_expression",
+            "disjunction_expression",
         ]
     }
 
@@ -396,6 +411,11 @@ impl Language for Swift {
         }
     }
 
+    fn is_test_file(&self, path: &Path) -> bool {
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        stem.ends_with("Tests")
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
@@ -524,7 +544,7 @@ mod tests {
             "property_modifier", "protocol_body", "protocol_composition_type",
             "protocol_function_declaration", "protocol_property_declaration", "self_expression",
             "setter_specifier", "simple_identifier", "statement_label", "statements",
-            "super_expression", "switch_entry", "throw_keyword", "throws", "try_operator",
+            "super_expression", "throw_keyword", "throws", "try_operator",
             "tuple_expression", "tuple_type", "tuple_type_item", "type_annotation",
             "type_arguments", "type_constraint", "type_constraints", "type_identifier",
             "type_modifiers", "type_pack_expansion", "type_parameter", "type_parameter_modifiers",
@@ -532,8 +552,8 @@ mod tests {
             "where_clause", "willset_clause", "willset_didset_block",
             // EXPRESSION
             "additive_expression", "as_expression", "await_expression", "call_expression",
-            "check_expression", "comparison_expression", "conjunction_expression",
-            "directly_assignable_expression", "disjunction_expression", "equality_expression",
+            "check_expression", "comparison_expression", "directly_assignable_expression",
+            "equality_expression",
             "infix_expression", "multiplicative_expression", "navigation_expression",
             "open_end_range_expression", "open_start_range_expression", "postfix_expression",
             "prefix_expression", "range_expression", "selector_expression", "try_expression",
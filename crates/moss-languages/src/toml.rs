@@ -23,6 +23,18 @@ impl Language for Toml {
         false
     }
 
+    fn supports_call_graph(&self) -> bool {
+        false
+    }
+
+    fn supports_docs(&self) -> bool {
+        false
+    }
+
+    fn supports_complexity(&self) -> bool {
+        false
+    }
+
     // TOML is config, not code - no functions/types/control flow
     fn container_kinds(&self) -> &'static [&'static str] {
         &["table", "table_array_element"]
@@ -127,6 +139,10 @@ impl Language for Toml {
         false
     }
 
+    fn is_test_file(&self, _path: &Path) -> bool {
+        false
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
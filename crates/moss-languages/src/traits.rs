@@ -5,7 +5,7 @@ use std::path::{Path, PathBuf};
 use tree_sitter::Node;
 
 /// Symbol kind classification
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SymbolKind {
     Function,
     Method,
@@ -22,6 +22,42 @@ pub enum SymbolKind {
 }
 
 impl SymbolKind {
+    pub const ALL: &'static [SymbolKind] = &[
+        SymbolKind::Function,
+        SymbolKind::Method,
+        SymbolKind::Class,
+        SymbolKind::Struct,
+        SymbolKind::Enum,
+        SymbolKind::Trait,
+        SymbolKind::Interface,
+        SymbolKind::Module,
+        SymbolKind::Type,
+        SymbolKind::Constant,
+        SymbolKind::Variable,
+        SymbolKind::Heading,
+    ];
+
+    /// Parse a kind name as typed by users: case-insensitive, plurals and
+    /// common abbreviations accepted (`functions`, `fn`, `const`).
+    pub fn from_name(name: &str) -> Option<SymbolKind> {
+        let name = name.trim().to_lowercase();
+        let kind = match name.as_str() {
+            "func" | "fn" => SymbolKind::Function,
+            "const" => SymbolKind::Constant,
+            "var" => SymbolKind::Variable,
+            "mod" => SymbolKind::Module,
+            "classes" => SymbolKind::Class,
+            _ => {
+                let singular = name.strip_suffix('s').unwrap_or(&name);
+                return SymbolKind::ALL
+                    .iter()
+                    .find(|k| k.as_str() == name || k.as_str() == singular)
+                    .copied();
+            }
+        };
+        Some(kind)
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             SymbolKind::Function => "function",
@@ -85,6 +121,44 @@ pub struct Symbol {
     pub implements: Vec<String>,
 }
 
+impl Symbol {
+    /// Iterate over this symbol and all its descendants, depth-first in source order
+    pub fn iter_flat(&self) -> impl Iterator<Item = &Symbol> {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let sym = stack.pop()?;
+            stack.extend(sym.children.iter().rev());
+            Some(sym)
+        })
+    }
+
+    /// Owned variant of [`Symbol::iter_flat`]. Yielded symbols have empty `children`.
+    pub fn into_iter_flat(self) -> impl Iterator<Item = Symbol> {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let mut sym = stack.pop()?;
+            stack.extend(std::mem::take(&mut sym.children).into_iter().rev());
+            Some(sym)
+        })
+    }
+
+    /// First symbol (this one or a descendant) with the given name
+    pub fn find_by_name(&self, name: &str) -> Option<&Symbol> {
+        self.iter_flat().find(|s| s.name == name)
+    }
+
+    /// Innermost symbol whose line range contains `line` (1-based)
+    pub fn find_by_line(&self, line: usize) -> Option<&Symbol> {
+        if line < self.start_line || line > self.end_line {
+            return None;
+        }
+        self.children
+            .iter()
+            .find_map(|c| c.find_by_line(line))
+            .or(Some(self))
+    }
+}
+
 /// An import statement
 #[derive(Debug, Clone)]
 pub struct Import {
@@ -191,6 +265,48 @@ pub fn simple_function_symbol(
     simple_symbol(node, content, name, SymbolKind::Function, docstring)
 }
 
+/// An inclusive range of 1-based lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LineRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl LineRange {
+    pub fn contains(&self, line: usize) -> bool {
+        self.start <= line && line <= self.end
+    }
+}
+
+/// Line ranges of test code among `symbols`: each outermost symbol for which
+/// [`Language::is_test_symbol`] holds, so everything nested in a test module
+/// or class (helpers, fixtures) counts as test code too.
+pub fn test_line_ranges(lang: &dyn Language, symbols: &[Symbol]) -> Vec<LineRange> {
+    let mut ranges = Vec::new();
+    let mut stack: Vec<&Symbol> = symbols.iter().collect();
+    while let Some(symbol) = stack.pop() {
+        if lang.is_test_symbol(symbol) {
+            ranges.push(LineRange {
+                start: symbol.start_line,
+                end: symbol.end_line,
+            });
+        } else {
+            stack.extend(&symbol.children);
+        }
+    }
+    ranges.sort_unstable();
+    ranges
+}
+
+/// Which analyses a language supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    pub symbols: bool,
+    pub call_graph: bool,
+    pub docs: bool,
+    pub complexity: bool,
+}
+
 /// Unified language support trait.
 ///
 /// Each language implements this trait to provide:
@@ -213,6 +329,17 @@ pub trait Language: Send + Sync {
     /// Whether this language has code symbols (functions, classes, etc.)
     fn has_symbols(&self) -> bool;
 
+    // === Capabilities ===
+
+    /// Whether calls are extracted for the call graph (`--callers`, `--callees`)
+    fn supports_call_graph(&self) -> bool;
+
+    /// Whether `extract_docstring` extracts doc comments/docstrings
+    fn supports_docs(&self) -> bool;
+
+    /// Whether complexity metrics are available (`complexity_nodes` is non-empty)
+    fn supports_complexity(&self) -> bool;
+
     // === Node Classification ===
 
     /// Container nodes that can hold methods (class, impl, module)
@@ -284,7 +411,12 @@ pub trait Language: Send + Sync {
 
     // === Complexity ===
 
-    /// Nodes that increase cyclomatic complexity
+    /// Nodes that increase cyclomatic complexity: the grammar's decision
+    /// points, each adding one path through a function. Typically conditionals
+    /// (`if`, ternaries), loops, `case`/`when` arms, `catch`/`rescue` clauses
+    /// and short-circuit operators (`&&`, `||`, `and`, `or`, `??`). Complexity
+    /// analysis counts these kinds and nothing else, so a language without
+    /// them reports every function as 1.
     fn complexity_nodes(&self) -> &'static [&'static str];
 
     /// Nodes that indicate nesting depth
@@ -310,6 +442,11 @@ pub trait Language: Send + Sync {
     /// Each language must implement this - test conventions are language-specific.
     fn is_test_symbol(&self, symbol: &Symbol) -> bool;
 
+    /// Check if a whole file is test code by its path (`foo_test.go`, `test_foo.py`,
+    /// `foo.spec.ts`), whatever symbols it contains.
+    /// Each language must implement this - test file conventions are language-specific.
+    fn is_test_file(&self, path: &Path) -> bool;
+
     // === Embedded Languages ===
 
     /// Extract embedded content from a node (e.g., JS/CSS in Vue/HTML).
@@ -532,3 +669,171 @@ pub enum PackageSourceKind {
     /// Deno cache structure (needs special handling for npm vs URL deps)
     Deno,
 }
+
+impl dyn Language {
+    /// All capability flags at once (for capability matrices)
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            symbols: self.has_symbols(),
+            call_graph: self.supports_call_graph(),
+            docs: self.supports_docs(),
+            complexity: self.supports_complexity(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(
+        name: &str,
+        kind: SymbolKind,
+        lines: (usize, usize),
+        children: Vec<Symbol>,
+    ) -> Symbol {
+        Symbol {
+            name: name.to_string(),
+            kind,
+            signature: String::new(),
+            docstring: None,
+            attributes: Vec::new(),
+            start_line: lines.0,
+            end_line: lines.1,
+            visibility: Visibility::Public,
+            children,
+            is_interface_impl: false,
+            implements: Vec::new(),
+        }
+    }
+
+    /// class Outer (1-20) → method run (2-10) → fn helper (3-5); method stop (12-18)
+    fn nested() -> Symbol {
+        let helper = symbol("helper", SymbolKind::Function, (3, 5), Vec::new());
+        let run = symbol("run", SymbolKind::Method, (2, 10), vec![helper]);
+        let stop = symbol("stop", SymbolKind::Method, (12, 18), Vec::new());
+        symbol("Outer", SymbolKind::Class, (1, 20), vec![run, stop])
+    }
+
+    #[test]
+    fn test_test_line_ranges() {
+        let with_attrs = |mut sym: Symbol, attrs: &[&str]| {
+            sym.attributes = attrs.iter().map(|a| a.to_string()).collect();
+            sym
+        };
+        let helper = symbol("helper", SymbolKind::Function, (12, 14), Vec::new());
+        let case = with_attrs(
+            symbol("parses", SymbolKind::Function, (16, 20), Vec::new()),
+            &["#[test]"],
+        );
+        let symbols = vec![
+            symbol("run", SymbolKind::Function, (1, 8), Vec::new()),
+            with_attrs(
+                symbol("fetches", SymbolKind::Function, (22, 25), Vec::new()),
+                &["#[tokio::test]"],
+            ),
+            symbol("tests", SymbolKind::Module, (10, 21), vec![helper, case]),
+            with_attrs(
+                symbol("testing", SymbolKind::Function, (30, 31), Vec::new()),
+                &["#[cfg(feature = \"testing\")]"],
+            ),
+        ];
+        let range = |start, end| LineRange { start, end };
+        assert_eq!(
+            test_line_ranges(&crate::Rust, &symbols),
+            vec![range(10, 21), range(22, 25)]
+        );
+
+        let symbols = vec![
+            with_attrs(
+                symbol("client", SymbolKind::Function, (1, 3), Vec::new()),
+                &["@pytest.fixture"],
+            ),
+            symbol(
+                "TestClient",
+                SymbolKind::Class,
+                (5, 9),
+                vec![symbol("setUp", SymbolKind::Method, (6, 7), Vec::new())],
+            ),
+            symbol("main", SymbolKind::Function, (11, 12), Vec::new()),
+        ];
+        assert_eq!(
+            test_line_ranges(&crate::Python, &symbols),
+            vec![range(1, 3), range(5, 9)]
+        );
+    }
+
+    #[test]
+    fn test_is_test_file() {
+        let js = &crate::JavaScript as &dyn Language;
+        assert!(js.is_test_file(Path::new("src/add.test.js")));
+        assert!(js.is_test_file(Path::new("src/add.spec.js")));
+        assert!(js.is_test_file(Path::new("src/__tests__/add.js")));
+        assert!(!js.is_test_file(Path::new("src/add.js")));
+        assert!(!js.is_test_file(Path::new("src/test.js")));
+        assert!(crate::Tsx.is_test_file(Path::new("src/Button.test.tsx")));
+
+        assert!(crate::Go.is_test_file(Path::new("client_test.go")));
+        assert!(!crate::Go.is_test_file(Path::new("client.go")));
+        assert!(crate::Python.is_test_file(Path::new("tests/test_client.py")));
+        assert!(crate::Python.is_test_file(Path::new("conftest.py")));
+        assert!(!crate::Python.is_test_file(Path::new("testing.py")));
+        assert!(crate::Rust.is_test_file(Path::new("tests/cli.rs")));
+        assert!(crate::Rust.is_test_file(Path::new("src/parser/tests.rs")));
+        assert!(!crate::Rust.is_test_file(Path::new("src/testing.rs")));
+        assert!(!crate::Json.is_test_file(Path::new("tests/fixture.json")));
+    }
+
+    #[test]
+    fn test_symbol_kind_from_name() {
+        assert_eq!(
+            SymbolKind::from_name("function"),
+            Some(SymbolKind::Function)
+        );
+        assert_eq!(
+            SymbolKind::from_name("Functions"),
+            Some(SymbolKind::Function)
+        );
+        assert_eq!(SymbolKind::from_name("fn"), Some(SymbolKind::Function));
+        assert_eq!(SymbolKind::from_name("classes"), Some(SymbolKind::Class));
+        assert_eq!(SymbolKind::from_name("structs"), Some(SymbolKind::Struct));
+        assert_eq!(SymbolKind::from_name("bogus"), None);
+    }
+
+    #[test]
+    fn test_iter_flat_depth_first() {
+        let outer = nested();
+        let names: Vec<&str> = outer.iter_flat().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Outer", "run", "helper", "stop"]);
+    }
+
+    #[test]
+    fn test_into_iter_flat() {
+        let flat: Vec<Symbol> = nested().into_iter_flat().collect();
+        let names: Vec<&str> = flat.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Outer", "run", "helper", "stop"]);
+        assert!(flat.iter().all(|s| s.children.is_empty()));
+    }
+
+    #[test]
+    fn test_find_by_name() {
+        let outer = nested();
+        assert_eq!(outer.find_by_name("helper").map(|s| s.start_line), Some(3));
+        assert_eq!(
+            outer.find_by_name("Outer").map(|s| s.kind),
+            Some(SymbolKind::Class)
+        );
+        assert!(outer.find_by_name("missing").is_none());
+    }
+
+    #[test]
+    fn test_find_by_line_innermost() {
+        let outer = nested();
+        let name_at = |line| outer.find_by_line(line).map(|s| s.name.as_str());
+        assert_eq!(name_at(4), Some("helper"));
+        assert_eq!(name_at(8), Some("run"));
+        assert_eq!(name_at(11), Some("Outer"));
+        assert_eq!(name_at(15), Some("stop"));
+        assert_eq!(name_at(21), None);
+    }
+}
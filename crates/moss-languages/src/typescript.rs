@@ -27,6 +27,18 @@ impl Language for TypeScript {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        true
+    }
+
+    fn supports_docs(&self) -> bool {
+        false
+    }
+
+    fn supports_complexity(&self) -> bool {
+        true
+    }
+
     fn container_kinds(&self) -> &'static [&'static str] {
         ecmascript::TS_CONTAINER_KINDS
     }
@@ -79,7 +91,7 @@ impl Language for TypeScript {
 
     fn extract_type(&self, node: &Node, content: &str) -> Option<Symbol> {
         let name = self.node_name(node, content)?;
-        ecmascript::extract_type(node, name)
+        ecmascript::extract_type(node, content, name)
     }
 
     fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
@@ -98,8 +110,8 @@ impl Language for TypeScript {
         None
     }
 
-    fn extract_attributes(&self, _node: &Node, _content: &str) -> Vec<String> {
-        Vec::new()
+    fn extract_attributes(&self, node: &Node, content: &str) -> Vec<String> {
+        ecmascript::extract_decorators(node, content)
     }
 
     fn is_public(&self, _node: &Node, _content: &str) -> bool {
@@ -125,6 +137,10 @@ impl Language for TypeScript {
         }
     }
 
+    fn is_test_file(&self, path: &Path) -> bool {
+        ecmascript::is_test_file(path)
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
@@ -277,6 +293,18 @@ impl Language for Tsx {
         true
     }
 
+    fn supports_call_graph(&self) -> bool {
+        true
+    }
+
+    fn supports_docs(&self) -> bool {
+        false
+    }
+
+    fn supports_complexity(&self) -> bool {
+        true
+    }
+
     fn container_kinds(&self) -> &'static [&'static str] {
         ecmascript::TS_CONTAINER_KINDS
     }
@@ -329,7 +357,7 @@ impl Language for Tsx {
 
     fn extract_type(&self, node: &Node, content: &str) -> Option<Symbol> {
         let name = self.node_name(node, content)?;
-        ecmascript::extract_type(node, name)
+        ecmascript::extract_type(node, content, name)
     }
 
     fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
@@ -348,8 +376,8 @@ impl Language for Tsx {
         None
     }
 
-    fn extract_attributes(&self, _node: &Node, _content: &str) -> Vec<String> {
-        Vec::new()
+    fn extract_attributes(&self, node: &Node, content: &str) -> Vec<String> {
+        ecmascript::extract_decorators(node, content)
     }
 
     fn is_public(&self, _node: &Node, _content: &str) -> bool {
@@ -375,6 +403,10 @@ impl Language for Tsx {
         }
     }
 
+    fn is_test_file(&self, path: &Path) -> bool {
+        ecmascript::is_test_file(path)
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
@@ -23,6 +23,18 @@ impl Language for Yaml {
         false
     }
 
+    fn supports_call_graph(&self) -> bool {
+        false
+    }
+
+    fn supports_docs(&self) -> bool {
+        false
+    }
+
+    fn supports_complexity(&self) -> bool {
+        false
+    }
+
     // YAML is data, not code - no functions/types/control flow
     fn container_kinds(&self) -> &'static [&'static str] {
         &["block_mapping", "flow_mapping"]
@@ -123,6 +135,10 @@ impl Language for Yaml {
         false
     }
 
+    fn is_test_file(&self, _path: &Path) -> bool {
+        false
+    }
+
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
         None
     }
@@ -7,7 +7,7 @@
 //! Users can register custom generators via [`register()`]:
 //!
 //! ```ignore
-//! use rhizome_moss_openapi::{OpenApiClientGenerator, register};
+//! use rhizome_moss_openapi::{GenerateError, GenerateOptions, OpenApiClientGenerator, register};
 //! use serde_json::Value;
 //!
 //! struct MyGenerator;
@@ -15,16 +15,52 @@
 //! impl OpenApiClientGenerator for MyGenerator {
 //!     fn language(&self) -> &'static str { "mylang" }
 //!     fn variant(&self) -> &'static str { "myvariant" }
-//!     fn generate(&self, spec: &Value) -> String { /* ... */ }
+//!     fn generate(&self, spec: &Value, options: &GenerateOptions) -> Result<String, GenerateError> { /* ... */ }
 //! }
 //!
 //! // Register before first use
 //! register(&MyGenerator);
 //! ```
+//!
+//! Built-in clients declare their operations as an `Api` interface (a trait
+//! in Rust, a `Protocol` in Python) that `ApiClient` implements, and with
+//! [`GenerateOptions::mock`] also a `MockApiClient` returning example responses.
+//!
+//! A [`GeneratorPreamble`] adds a header before the generated code or drops
+//! its banner; [`apply_preamble`] applies one to any generator's output.
+//!
+//! # Validation
+//!
+//! [`validate()`] checks a spec's structural invariants before generation.
+//! Generators enumerate operations via [`operations()`], which synthesizes
+//! names for operations without an `operationId`.
+
+mod operations;
+mod validate;
 
+pub use operations::{
+    FormField, Operation, Parameter, ResponseHeader, operations, synthesize_operation_id,
+};
+pub use rhizome_moss_core::{GenerateError, GeneratorPreamble};
+pub use validate::{HTTP_METHODS, Issue, Severity, escape_pointer, validate};
+
+use operations::{is_binary_schema, pascal_case};
+use rhizome_moss_core::object_entries;
 use serde_json::Value;
 use std::sync::{OnceLock, RwLock};
 
+/// Options that apply to every generator.
+#[derive(Debug, Clone, Default)]
+pub struct GenerateOptions {
+    /// Return response headers alongside the body for operations that declare
+    /// `responses.*.headers`. Other operations stay body-only.
+    pub with_headers: bool,
+    /// Also emit `MockApiClient`, implementing the client's `Api` interface
+    /// with canned responses: each operation's response example, or a sample
+    /// synthesized from its response schema.
+    pub mock: bool,
+}
+
 /// A code generator for a specific language/framework.
 pub trait OpenApiClientGenerator: Send + Sync {
     /// Language name (e.g., "typescript", "python")
@@ -34,7 +70,21 @@ pub trait OpenApiClientGenerator: Send + Sync {
     fn variant(&self) -> &'static str;
 
     /// Generate client code from OpenAPI JSON.
-    fn generate(&self, spec: &Value) -> String;
+    ///
+    /// Fails only for generators that delegate to something that can fail,
+    /// such as an external command.
+    fn generate(&self, spec: &Value, options: &GenerateOptions) -> Result<String, GenerateError>;
+}
+
+/// Generate client code under a custom preamble, treating the leading comment
+/// block of the generator's output as its banner.
+pub fn apply_preamble(
+    generator: &dyn OpenApiClientGenerator,
+    spec: &Value,
+    options: &GenerateOptions,
+    preamble: &GeneratorPreamble,
+) -> Result<String, GenerateError> {
+    Ok(preamble.apply(&generator.generate(spec, options)?))
 }
 
 /// Global registry of generator plugins.
@@ -107,8 +157,8 @@ impl OpenApiClientGenerator for GeneratorWrapper {
         self.0.variant()
     }
 
-    fn generate(&self, spec: &Value) -> String {
-        self.0.generate(spec)
+    fn generate(&self, spec: &Value, options: &GenerateOptions) -> Result<String, GenerateError> {
+        self.0.generate(spec, options)
     }
 }
 
@@ -135,7 +185,7 @@ impl OpenApiClientGenerator for TypeScriptFetch {
         "fetch"
     }
 
-    fn generate(&self, spec: &Value) -> String {
+    fn generate(&self, spec: &Value, options: &GenerateOptions) -> Result<String, GenerateError> {
         let mut out = String::new();
         out.push_str("// Auto-generated from OpenAPI spec\n");
         out.push_str("// Uses fetch (built-in)\n\n");
@@ -145,7 +195,7 @@ impl OpenApiClientGenerator for TypeScriptFetch {
             .pointer("/components/schemas")
             .and_then(|s| s.as_object())
         {
-            for (name, schema) in schemas {
+            for (name, schema) in object_entries(schemas) {
                 out.push_str(&format!("export interface {} {{\n", name));
                 if let Some(props) = schema.get("properties").and_then(|p| p.as_object()) {
                     let required: Vec<&str> = schema
@@ -153,13 +203,16 @@ impl OpenApiClientGenerator for TypeScriptFetch {
                         .and_then(|r| r.as_array())
                         .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
                         .unwrap_or_default();
-                    for (prop_name, prop) in props {
+                    for (prop_name, prop) in object_entries(props) {
                         let ts_type = json_schema_to_ts(prop);
                         let opt = if required.contains(&prop_name.as_str()) {
                             ""
                         } else {
                             "?"
                         };
+                        if let Some(note) = deprecation(prop) {
+                            out.push_str(&format!("  {}\n", ts_deprecated(&note)));
+                        }
                         out.push_str(&format!("  {}{}: {};\n", prop_name, opt, ts_type));
                     }
                 }
@@ -167,89 +220,218 @@ impl OpenApiClientGenerator for TypeScriptFetch {
             }
         }
 
+        let client_ops = client_operations(spec);
+        let any_headers = write_header_types(&mut out, &client_ops, spec, options, ts_header_type);
+
         // Generate client class
-        out.push_str("export class ApiClient {\n");
-        out.push_str("  constructor(private baseUrl = 'http://localhost:8080') {}\n\n");
-        out.push_str("  private async request<T>(path: string, params?: Record<string, string | number | undefined>): Promise<T> {\n");
-        out.push_str("    const url = new URL(path, this.baseUrl);\n");
+        out.push_str("/** Per-call overrides of the client's base URL and headers. */\n");
+        out.push_str("export interface RequestOptions {\n");
+        out.push_str("  headers?: Record<string, string>;\n");
+        out.push_str("  baseUrl?: string;\n");
+        out.push_str("}\n\n");
+        let class_start = out.len();
+        out.push_str("export class ApiClient implements Api {\n");
+        out.push_str("  constructor(private baseUrl = 'http://localhost:8080', private headers: Record<string, string> = {}) {}\n\n");
+        out.push_str("  private async send(path: string, params?: Record<string, string | number | boolean | undefined>, init?: RequestInit, options?: RequestOptions): Promise<Response> {\n");
+        out.push_str("    const url = new URL(path, options?.baseUrl ?? this.baseUrl);\n");
         out.push_str("    if (params) {\n");
         out.push_str("      for (const [k, v] of Object.entries(params)) {\n");
         out.push_str("        if (v !== undefined) url.searchParams.set(k, String(v));\n");
         out.push_str("      }\n");
         out.push_str("    }\n");
-        out.push_str("    const res = await fetch(url.toString());\n");
+        out.push_str("    const headers = { ...this.headers, ...options?.headers };\n");
+        out.push_str("    const res = await fetch(url.toString(), { ...init, headers });\n");
         out.push_str("    if (!res.ok) throw new Error(`HTTP ${res.status}`);\n");
+        out.push_str("    return res;\n");
+        out.push_str("  }\n\n");
+        out.push_str("  private async request<T>(path: string, params?: Record<string, string | number | boolean | undefined>, init?: RequestInit, options?: RequestOptions): Promise<T> {\n");
+        out.push_str("    const res = await this.send(path, params, init, options);\n");
         out.push_str("    return await res.json() as T;\n");
         out.push_str("  }\n\n");
+        if any_headers {
+            out.push_str("  private async requestWithHeaders<T>(path: string, params?: Record<string, string | number | boolean | undefined>, init?: RequestInit, options?: RequestOptions): Promise<{ data: T; headers: Headers }> {\n");
+            out.push_str("    const res = await this.send(path, params, init, options);\n");
+            out.push_str("    return { data: await res.json() as T, headers: res.headers };\n");
+            out.push_str("  }\n\n");
+        }
+        let binary_ops: Vec<&Operation> = client_ops
+            .iter()
+            .filter(|o| o.binary_response(spec))
+            .collect();
+        if !binary_ops.is_empty() {
+            out.push_str("  private async requestBytes(path: string, params?: Record<string, string | number | boolean | undefined>, init?: RequestInit, options?: RequestOptions): Promise<ArrayBuffer> {\n");
+            out.push_str("    const res = await this.send(path, params, init, options);\n");
+            out.push_str("    return await res.arrayBuffer();\n");
+            out.push_str("  }\n\n");
+        }
+        if binary_ops
+            .iter()
+            .any(|o| !exposed_headers(o, spec, options).is_empty())
+        {
+            out.push_str("  private async requestBytesWithHeaders(path: string, params?: Record<string, string | number | boolean | undefined>, init?: RequestInit, options?: RequestOptions): Promise<{ data: ArrayBuffer; headers: Headers }> {\n");
+            out.push_str("    const res = await this.send(path, params, init, options);\n");
+            out.push_str("    return { data: await res.arrayBuffer(), headers: res.headers };\n");
+            out.push_str("  }\n\n");
+        }
 
-        // Generate methods from paths
-        if let Some(paths) = spec.get("paths").and_then(|p| p.as_object()) {
-            for (path, methods) in paths {
-                if let Some(op) = methods.get("get").and_then(|g| g.as_object()) {
-                    let op_id = op
-                        .get("operationId")
-                        .and_then(|id| id.as_str())
-                        .unwrap_or("unknown");
-                    let params = op
-                        .get("parameters")
-                        .and_then(|p| p.as_array())
-                        .map(|a| a.as_slice())
-                        .unwrap_or(&[]);
-
-                    let path_params: Vec<&str> = params
-                        .iter()
-                        .filter(|p| p.get("in").and_then(|i| i.as_str()) == Some("path"))
-                        .filter_map(|p| p.get("name").and_then(|n| n.as_str()))
-                        .collect();
-                    let query_params: Vec<&str> = params
-                        .iter()
-                        .filter(|p| p.get("in").and_then(|i| i.as_str()) == Some("query"))
-                        .filter_map(|p| p.get("name").and_then(|n| n.as_str()))
-                        .collect();
-
-                    // Response type from nested path
-                    let op_value = Value::Object(op.clone());
-                    let resp_type = op_value
-                        .pointer("/responses/200/content/application~1json/schema")
-                        .map(json_schema_to_ts)
-                        .unwrap_or_else(|| "void".to_string());
-
-                    let mut args = Vec::new();
-                    for p in &path_params {
-                        args.push(format!("{}: string", p));
-                    }
-                    if !query_params.is_empty() {
-                        let opts: Vec<String> = query_params
-                            .iter()
-                            .map(|p| format!("{}?: string | number", p))
-                            .collect();
-                        args.push(format!("options?: {{ {} }}", opts.join("; ")));
+        // Generate methods from paths, declared by the `Api` interface and
+        // implemented by `ApiClient` and the mock
+        let mut interface = String::new();
+        let mut mock = String::new();
+        for operation in &client_ops {
+            let path = operation.path;
+            let op = operation.op;
+            let op_id = operation.id.as_str();
+            let params = operation.parameters(spec);
+            let (path_params, query_params): (Vec<&Parameter>, Vec<&Parameter>) =
+                params.iter().partition(|p| p.location == "path");
+
+            // Response type from nested path
+            let binary = operation.binary_response(spec);
+            let resp_type = if binary {
+                "ArrayBuffer".to_string()
+            } else {
+                operation
+                    .response_schema(spec)
+                    .map(json_schema_to_ts)
+                    .unwrap_or_else(|| "void".to_string())
+            };
+            let form_fields = operation.multipart_fields(spec);
+
+            let mut args = Vec::new();
+            for p in &path_params {
+                args.push(format!("{}: {}", p.name, ts_param_type(p)));
+            }
+            for field in form_fields.iter().flatten() {
+                let ts_type = if field.binary {
+                    "Blob".to_string()
+                } else {
+                    json_schema_to_ts(field.schema)
+                };
+                let opt = if field.required { "" } else { "?" };
+                args.push(format!("{}{}: {}", ts_ident(field.name), opt, ts_type));
+            }
+            if !query_params.is_empty() {
+                let opts: Vec<String> = query_params
+                    .iter()
+                    .map(|p| format!("{}?: {}", p.name, ts_param_type(p)))
+                    .collect();
+                args.push(format!("options?: {{ {} }}", opts.join("; ")));
+            }
+            args.push("init?: RequestOptions".to_string());
+
+            let url_template = path.replace('{', "${");
+            let query_arg = if query_params.is_empty() {
+                "undefined"
+            } else {
+                "options"
+            };
+            let request_init = match &form_fields {
+                Some(_) => format!(
+                    "{{ method: '{}', body: form }}",
+                    operation.method.to_uppercase()
+                ),
+                None => "undefined".to_string(),
+            };
+            let call_params = format!(", {}, {}, init", query_arg, request_init);
+
+            let (return_type, helper) = if exposed_headers(operation, spec, options).is_empty() {
+                (resp_type.clone(), "request")
+            } else {
+                (
+                    format!("{{ data: {}; headers: Headers }}", resp_type),
+                    "requestWithHeaders",
+                )
+            };
+            // Binary bodies are read as bytes, not parsed as JSON
+            let helper = match (binary, helper) {
+                (true, "request") => "requestBytes".to_string(),
+                (true, _) => "requestBytesWithHeaders".to_string(),
+                (false, helper) => format!("{}<{}>", helper, resp_type),
+            };
+            if let Some(note) = deprecation(op) {
+                out.push_str(&format!("  {}\n", ts_deprecated(&note)));
+                interface.push_str(&format!("  {}\n", ts_deprecated(&note)));
+            }
+            out.push_str(&format!(
+                "  async {}({}): Promise<{}> {{\n",
+                op_id,
+                args.join(", "),
+                return_type
+            ));
+            interface.push_str(&format!(
+                "  {}({}): Promise<{}>;\n",
+                op_id,
+                args.join(", "),
+                return_type
+            ));
+            if options.mock {
+                // A method may take fewer parameters than the interface declares.
+                // Examples are untyped JSON, so they are cast through `unknown`.
+                let data = if binary {
+                    "new ArrayBuffer(0)".to_string()
+                } else {
+                    match operation.response_example(spec) {
+                        Some(example) => format!("{} as unknown as {}", example, resp_type),
+                        None => "undefined".to_string(),
                     }
-
-                    let url_template = path.replace('{', "${");
-                    let call_params = if query_params.is_empty() {
-                        ""
+                };
+                let body = if return_type != resp_type {
+                    format!(
+                        "\n    return {{ data: {}, headers: new Headers() }};\n  ",
+                        data
+                    )
+                } else if resp_type == "void" {
+                    String::new()
+                } else {
+                    format!("\n    return {};\n  ", data)
+                };
+                mock.push_str(&format!(
+                    "  async {}(): Promise<{}> {{{}}}\n\n",
+                    op_id, return_type, body
+                ));
+            }
+            if let Some(fields) = &form_fields {
+                out.push_str("    const form = new FormData();\n");
+                for field in fields {
+                    let ident = ts_ident(field.name);
+                    let value = if field.binary {
+                        ident.clone()
+                    } else if is_scalar(field.schema) {
+                        format!("String({})", ident)
                     } else {
-                        ", options"
+                        format!("JSON.stringify({})", ident)
                     };
-
-                    out.push_str(&format!(
-                        "  async {}({}): Promise<{}> {{\n",
-                        op_id,
-                        args.join(", "),
-                        resp_type
-                    ));
-                    out.push_str(&format!(
-                        "    return this.request<{}>(`{}`{});\n",
-                        resp_type, url_template, call_params
-                    ));
-                    out.push_str("  }\n\n");
+                    let append = format!("form.append('{}', {});", field.name, value);
+                    if field.required {
+                        out.push_str(&format!("    {}\n", append));
+                    } else {
+                        out.push_str(&format!("    if ({} !== undefined) {}\n", ident, append));
+                    }
                 }
             }
+            out.push_str(&format!(
+                "    return this.{}(`{}`{});\n",
+                helper, url_template, call_params
+            ));
+            out.push_str("  }\n\n");
         }
 
         out.push_str("}\n");
-        out
+        out.insert_str(
+            class_start,
+            &format!(
+                "/** The client's operations, for substituting `ApiClient` in tests. */\nexport interface Api {{\n{}}}\n\n",
+                interface
+            ),
+        );
+        if options.mock {
+            out.push_str("\n/** `Api` returning canned example responses, for tests. */\n");
+            out.push_str("export class MockApiClient implements Api {\n");
+            out.push_str(mock.trim_end());
+            out.push_str("\n}\n");
+        }
+        Ok(out)
     }
 }
 
@@ -265,22 +447,42 @@ impl OpenApiClientGenerator for PythonUrllib {
         "urllib"
     }
 
-    fn generate(&self, spec: &Value) -> String {
+    fn generate(&self, spec: &Value, options: &GenerateOptions) -> Result<String, GenerateError> {
         let mut out = String::new();
         out.push_str("# Auto-generated from OpenAPI spec\n");
         out.push_str("# Uses urllib (stdlib)\n\n");
+        let client_ops = client_operations(spec);
+        let any_headers = client_ops
+            .iter()
+            .any(|o| !exposed_headers(o, spec, options).is_empty());
+        let any_multipart = client_ops
+            .iter()
+            .any(|o| o.multipart_fields(spec).is_some());
+        let any_deprecated = client_ops.iter().any(|o| deprecation(o.op).is_some());
+
         out.push_str("from dataclasses import dataclass\n");
-        out.push_str("from typing import Any, Optional\n");
+        if any_headers {
+            out.push_str("from typing import Any, Generic, Optional, Protocol, TypeVar\n");
+        } else {
+            out.push_str("from typing import Any, Optional, Protocol\n");
+        }
         out.push_str("from urllib.parse import urlencode\n");
-        out.push_str("from urllib.request import urlopen\n");
-        out.push_str("import json\n\n\n");
+        out.push_str("from urllib.request import Request, urlopen\n");
+        out.push_str("import json\n");
+        if any_multipart {
+            out.push_str("import uuid\n");
+        }
+        if any_deprecated {
+            out.push_str("import warnings\n");
+        }
+        out.push_str("\n\n");
 
         // Generate dataclasses from schemas
         if let Some(schemas) = spec
             .pointer("/components/schemas")
             .and_then(|s| s.as_object())
         {
-            for (name, schema) in schemas {
+            for (name, schema) in object_entries(schemas) {
                 out.push_str("@dataclass\n");
                 out.push_str(&format!("class {}:\n", name));
                 if let Some(props) = schema.get("properties").and_then(|p| p.as_object()) {
@@ -291,16 +493,18 @@ impl OpenApiClientGenerator for PythonUrllib {
                         .unwrap_or_default();
 
                     // Required fields first
-                    for (prop_name, prop) in props {
+                    for (prop_name, prop) in object_entries(props) {
                         if required.contains(&prop_name.as_str()) {
                             let py_type = json_schema_to_py(prop);
+                            out.push_str(&py_deprecated_field(prop));
                             out.push_str(&format!("    {}: {}\n", prop_name, py_type));
                         }
                     }
                     // Optional fields
-                    for (prop_name, prop) in props {
+                    for (prop_name, prop) in object_entries(props) {
                         if !required.contains(&prop_name.as_str()) {
                             let py_type = json_schema_to_py(prop);
+                            out.push_str(&py_deprecated_field(prop));
                             out.push_str(&format!(
                                 "    {}: Optional[{}] = None\n",
                                 prop_name, py_type
@@ -317,88 +521,297 @@ impl OpenApiClientGenerator for PythonUrllib {
             }
         }
 
+        if any_headers {
+            out.push_str("T = TypeVar('T')\n\n\n");
+            out.push_str("@dataclass\n");
+            out.push_str("class ApiResponse(Generic[T]):\n");
+            out.push_str("    data: T\n");
+            out.push_str("    headers: dict[str, str]\n\n\n");
+        }
+        write_header_types(&mut out, &client_ops, spec, options, py_header_type);
+        if any_multipart {
+            out.push_str(PY_ENCODE_MULTIPART);
+        }
+
         // Generate client class
+        let class_start = out.len();
         out.push_str("class ApiClient:\n");
-        out.push_str("    def __init__(self, base_url: str = 'http://localhost:8080'):\n");
-        out.push_str("        self.base_url = base_url.rstrip('/')\n\n");
-        out.push_str("    def _request(self, path: str, params: Optional[dict] = None) -> dict:\n");
-        out.push_str("        url = f'{self.base_url}{path}'\n");
+        out.push_str("    def __init__(self, base_url: str = 'http://localhost:8080', headers: Optional[dict[str, str]] = None):\n");
+        out.push_str("        self.base_url = base_url.rstrip('/')\n");
+        out.push_str("        self.headers = dict(headers or {})\n\n");
+        out.push_str("    def _open(self, path: str, params: Optional[dict] = None, headers: Optional[dict[str, str]] = None, base_url: Optional[str] = None, content_type: Optional[str] = None, **request_args):\n");
+        out.push_str("        url = f\"{(base_url or self.base_url).rstrip('/')}{path}\"\n");
         out.push_str("        if params:\n");
-        out.push_str("            filtered = {k: v for k, v in params.items() if v is not None}\n");
+        out.push_str("            filtered = {k: str(v).lower() if isinstance(v, bool) else v for k, v in params.items() if v is not None}\n");
         out.push_str("            if filtered:\n");
         out.push_str("                url = f'{url}?{urlencode(filtered)}'\n");
-        out.push_str("        with urlopen(url) as response:\n");
+        out.push_str("        headers = {**self.headers, **(headers or {})}\n");
+        out.push_str("        if content_type:\n");
+        out.push_str("            headers['Content-Type'] = content_type\n");
+        out.push_str("        return urlopen(Request(url, headers=headers, **request_args))\n\n");
+        out.push_str("    def _request(self, path: str, params: Optional[dict] = None, **request_args) -> dict:\n");
+        out.push_str("        with self._open(path, params, **request_args) as response:\n");
         out.push_str("            return json.load(response)\n\n");
+        if any_headers {
+            out.push_str("    def _request_with_headers(self, path: str, params: Optional[dict] = None, **request_args) -> tuple[dict, dict[str, str]]:\n");
+            out.push_str("        with self._open(path, params, **request_args) as response:\n");
+            out.push_str(
+                "            headers = {k.lower(): v for k, v in response.headers.items()}\n",
+            );
+            out.push_str("            return json.load(response), headers\n\n");
+        }
+        let binary_ops: Vec<&Operation> = client_ops
+            .iter()
+            .filter(|o| o.binary_response(spec))
+            .collect();
+        if !binary_ops.is_empty() {
+            out.push_str("    def _request_bytes(self, path: str, params: Optional[dict] = None, **request_args) -> bytes:\n");
+            out.push_str("        with self._open(path, params, **request_args) as response:\n");
+            out.push_str("            return response.read()\n\n");
+        }
+        if binary_ops
+            .iter()
+            .any(|o| !exposed_headers(o, spec, options).is_empty())
+        {
+            out.push_str("    def _request_bytes_with_headers(self, path: str, params: Optional[dict] = None, **request_args) -> tuple[bytes, dict[str, str]]:\n");
+            out.push_str("        with self._open(path, params, **request_args) as response:\n");
+            out.push_str(
+                "            headers = {k.lower(): v for k, v in response.headers.items()}\n",
+            );
+            out.push_str("            return response.read(), headers\n\n");
+        }
 
-        // Generate methods from paths
-        if let Some(paths) = spec.get("paths").and_then(|p| p.as_object()) {
-            for (path, methods) in paths {
-                if let Some(op) = methods.get("get").and_then(|g| g.as_object()) {
-                    let op_id = op
-                        .get("operationId")
-                        .and_then(|id| id.as_str())
-                        .unwrap_or("unknown");
-                    let params = op
-                        .get("parameters")
-                        .and_then(|p| p.as_array())
-                        .map(|a| a.as_slice())
-                        .unwrap_or(&[]);
-
-                    let path_params: Vec<&str> = params
-                        .iter()
-                        .filter(|p| p.get("in").and_then(|i| i.as_str()) == Some("path"))
-                        .filter_map(|p| p.get("name").and_then(|n| n.as_str()))
-                        .collect();
-                    let query_params: Vec<&str> = params
-                        .iter()
-                        .filter(|p| p.get("in").and_then(|i| i.as_str()) == Some("query"))
-                        .filter_map(|p| p.get("name").and_then(|n| n.as_str()))
-                        .collect();
-
-                    let op_value = Value::Object(op.clone());
-                    let resp_type = op_value
-                        .pointer("/responses/200/content/application~1json/schema")
-                        .map(json_schema_to_py)
-                        .unwrap_or_else(|| "dict".to_string());
-
-                    let mut args = vec!["self".to_string()];
-                    for p in &path_params {
-                        args.push(format!("{}: str", p));
-                    }
-                    if !query_params.is_empty() {
-                        args.push("*".to_string());
-                        for p in &query_params {
-                            args.push(format!("{}: Optional[str] = None", p));
-                        }
-                    }
-
-                    let url_template = path.replace('{', "{");
-                    let params_dict = if query_params.is_empty() {
-                        String::new()
-                    } else {
-                        let kv: Vec<_> = query_params
-                            .iter()
-                            .map(|p| format!("'{}': {}", p, p))
-                            .collect();
-                        format!(", {{{}}}", kv.join(", "))
-                    };
+        // Generate methods from paths, declared by the `Api` protocol and
+        // implemented by `ApiClient` and the mock
+        let mut protocol = String::new();
+        let mut mock = String::new();
+        for operation in &client_ops {
+            let path = operation.path;
+            let op = operation.op;
+            let op_id = operation.id.as_str();
+            let params = operation.parameters(spec);
+            let (path_params, query_params): (Vec<&Parameter>, Vec<&Parameter>) =
+                params.iter().partition(|p| p.location == "path");
+
+            let binary = operation.binary_response(spec);
+            let resp_type = if binary {
+                "bytes".to_string()
+            } else {
+                operation
+                    .response_schema(spec)
+                    .map(json_schema_to_py)
+                    .unwrap_or_else(|| "dict".to_string())
+            };
+            let form_fields = operation.multipart_fields(spec);
+            let py_field_type = |field: &FormField| {
+                if field.binary {
+                    "bytes".to_string()
+                } else {
+                    json_schema_to_py(field.schema)
+                }
+            };
 
-                    out.push_str(&format!(
-                        "    def {}({}) -> {}:\n",
-                        op_id,
-                        args.join(", "),
-                        resp_type
+            let mut args = vec!["self".to_string()];
+            for p in &path_params {
+                args.push(format!("{}: {}", to_snake_case(p.name), py_param_type(p)));
+            }
+            let (required_fields, optional_fields): (Vec<&FormField>, Vec<&FormField>) =
+                form_fields.iter().flatten().partition(|f| f.required);
+            for field in &required_fields {
+                args.push(format!(
+                    "{}: {}",
+                    to_snake_case(field.name),
+                    py_field_type(field)
+                ));
+            }
+            if !query_params.is_empty() || !optional_fields.is_empty() {
+                args.push("*".to_string());
+                for field in &optional_fields {
+                    args.push(format!(
+                        "{}: Optional[{}] = None",
+                        to_snake_case(field.name),
+                        py_field_type(field)
                     ));
-                    out.push_str(&format!(
-                        "        data = self._request(f'{}'{})\n",
-                        url_template, params_dict
+                }
+                for p in &query_params {
+                    args.push(format!(
+                        "{}: Optional[{}] = None",
+                        to_snake_case(p.name),
+                        py_param_type(p)
                     ));
-                    out.push_str(&format!("        return {}(**data)\n\n", resp_type));
                 }
             }
+            // Per-call `headers` and `base_url`
+            args.push("**options".to_string());
+
+            let url_template = path_params.iter().fold(path.to_string(), |acc, p| {
+                // Python would format booleans as `True`/`False`
+                let value = if p.scalar_type() == Some("boolean") {
+                    format!("{{str({}).lower()}}", to_snake_case(p.name))
+                } else {
+                    format!("{{{}}}", to_snake_case(p.name))
+                };
+                acc.replace(&format!("{{{}}}", p.name), &value)
+            });
+            let mut params_dict = if query_params.is_empty() {
+                String::new()
+            } else {
+                // Keys stay as the spec names them; only the Python arguments are renamed
+                let kv: Vec<_> = query_params
+                    .iter()
+                    .map(|p| format!("'{}': {}", p.name, to_snake_case(p.name)))
+                    .collect();
+                format!(", {{{}}}", kv.join(", "))
+            };
+            if form_fields.is_some() {
+                if params_dict.is_empty() {
+                    params_dict.push_str(", None");
+                }
+                params_dict.push_str(&format!(
+                    ", data=body, method='{}', content_type=content_type",
+                    operation.method.to_uppercase()
+                ));
+            }
+            params_dict.push_str(", **options");
+
+            let with_headers = !exposed_headers(operation, spec, options).is_empty();
+            let return_type = if with_headers {
+                format!("ApiResponse[{}]", resp_type)
+            } else {
+                resp_type.clone()
+            };
+            out.push_str(&format!(
+                "    def {}({}) -> {}:\n",
+                to_snake_case(op_id),
+                args.join(", "),
+                return_type
+            ));
+            protocol.push_str(&format!(
+                "    def {}({}) -> {}: ...\n",
+                to_snake_case(op_id),
+                args.join(", "),
+                return_type
+            ));
+            if options.mock {
+                let data = if binary {
+                    "b''".to_string()
+                } else {
+                    match operation.response_example(spec) {
+                        // A JSON string is also a valid Python string literal
+                        Some(example) => {
+                            let loaded =
+                                format!("json.loads({})", Value::String(example.to_string()));
+                            let schema = operation.response_schema(spec);
+                            let is_model =
+                                |s: Option<&Value>| s.is_some_and(|s| s.get("$ref").is_some());
+                            let item_type = resp_type
+                                .strip_prefix("list[")
+                                .and_then(|t| t.strip_suffix(']'));
+                            if is_model(schema) && example.is_object() {
+                                format!("{}(**{})", resp_type, loaded)
+                            } else if let Some(item_type) = item_type
+                                && is_model(schema.and_then(|s| s.get("items")))
+                                && example
+                                    .as_array()
+                                    .is_some_and(|a| a.iter().all(Value::is_object))
+                            {
+                                format!("[{}(**item) for item in {}]", item_type, loaded)
+                            } else {
+                                loaded
+                            }
+                        }
+                        None => "{}".to_string(),
+                    }
+                };
+                let data = if with_headers {
+                    format!("ApiResponse({}, {{}})", data)
+                } else {
+                    data
+                };
+                mock.push_str(&format!(
+                    "    def {}(self, *args, **kwargs) -> {}:\n        return {}\n\n",
+                    to_snake_case(op_id),
+                    return_type,
+                    data
+                ));
+            }
+            if let Some(note) = deprecation(op) {
+                let name = to_snake_case(op_id);
+                let (doc, message) = if note.is_empty() {
+                    ("Deprecated.".to_string(), format!("{} is deprecated", name))
+                } else {
+                    (
+                        format!("Deprecated: {}", note),
+                        format!("{} is deprecated: {}", name, note),
+                    )
+                };
+                out.push_str(&format!(
+                    "        \"\"\"{}\"\"\"\n",
+                    doc.replace('\\', "\\\\").replace('"', "\\\"")
+                ));
+                // A JSON string is also a valid Python string literal
+                out.push_str(&format!(
+                    "        warnings.warn({}, DeprecationWarning, stacklevel=2)\n",
+                    Value::String(message)
+                ));
+            }
+            if let Some(fields) = &form_fields {
+                let kv: Vec<_> = fields
+                    .iter()
+                    .map(|f| format!("'{}': {}", f.name, to_snake_case(f.name)))
+                    .collect();
+                out.push_str(&format!(
+                    "        body, content_type = _encode_multipart({{{}}})\n",
+                    kv.join(", ")
+                ));
+            }
+            if binary && !with_headers {
+                out.push_str(&format!(
+                    "        return self._request_bytes(f'{}'{})\n\n",
+                    url_template, params_dict
+                ));
+            } else if binary {
+                out.push_str(&format!(
+                    "        data, headers = self._request_bytes_with_headers(f'{}'{})\n",
+                    url_template, params_dict
+                ));
+                out.push_str("        return ApiResponse(data, headers)\n\n");
+            } else if !with_headers {
+                out.push_str(&format!(
+                    "        data = self._request(f'{}'{})\n",
+                    url_template, params_dict
+                ));
+                out.push_str(&format!("        return {}(**data)\n\n", resp_type));
+            } else {
+                out.push_str(&format!(
+                    "        data, headers = self._request_with_headers(f'{}'{})\n",
+                    url_template, params_dict
+                ));
+                out.push_str(&format!(
+                    "        return ApiResponse({}(**data), headers)\n\n",
+                    resp_type
+                ));
+            }
         }
 
-        out
+        if protocol.is_empty() {
+            protocol.push_str("    pass\n");
+        }
+        out.insert_str(
+            class_start,
+            &format!(
+                "class Api(Protocol):\n    \"\"\"The client's operations, for substituting `ApiClient` in tests.\"\"\"\n\n{}\n\n",
+                protocol
+            ),
+        );
+        if options.mock {
+            out.push_str("\nclass MockApiClient:\n");
+            out.push_str(
+                "    \"\"\"`Api` returning canned example responses, for tests.\"\"\"\n\n",
+            );
+            out.push_str(&mock);
+        }
+        Ok(out)
     }
 }
 
@@ -414,18 +827,33 @@ impl OpenApiClientGenerator for RustUreq {
         "ureq"
     }
 
-    fn generate(&self, spec: &Value) -> String {
+    fn generate(&self, spec: &Value, options: &GenerateOptions) -> Result<String, GenerateError> {
         let mut out = String::new();
         out.push_str("//! Auto-generated from OpenAPI spec\n");
         out.push_str("//! Uses ureq (blocking HTTP)\n\n");
-        out.push_str("use serde::{Deserialize, Serialize};\n\n");
+        let client_ops = client_operations(spec);
+        let any_headers = client_ops
+            .iter()
+            .any(|o| !exposed_headers(o, spec, options).is_empty());
+        let any_multipart = client_ops
+            .iter()
+            .any(|o| o.multipart_fields(spec).is_some());
+
+        out.push_str("use serde::{Deserialize, Serialize};\n");
+        if any_headers {
+            out.push_str("use std::collections::HashMap;\n");
+        }
+        if client_ops.iter().any(|o| o.binary_response(spec)) {
+            out.push_str("use std::io::Read;\n");
+        }
+        out.push('\n');
 
         // Generate structs from schemas
         if let Some(schemas) = spec
             .pointer("/components/schemas")
             .and_then(|s| s.as_object())
         {
-            for (name, schema) in schemas {
+            for (name, schema) in object_entries(schemas) {
                 out.push_str("#[derive(Debug, Clone, Serialize, Deserialize)]\n");
                 out.push_str(&format!("pub struct {} {{\n", name));
                 if let Some(props) = schema.get("properties").and_then(|p| p.as_object()) {
@@ -434,13 +862,16 @@ impl OpenApiClientGenerator for RustUreq {
                         .and_then(|r| r.as_array())
                         .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
                         .unwrap_or_default();
-                    for (prop_name, prop) in props {
+                    for (prop_name, prop) in object_entries(props) {
                         let rust_type = json_schema_to_rust(prop);
                         let field_type = if required.contains(&prop_name.as_str()) {
                             rust_type
                         } else {
                             format!("Option<{}>", rust_type)
                         };
+                        if let Some(note) = deprecation(prop) {
+                            out.push_str(&format!("    {}\n", rust_deprecated(&note)));
+                        }
                         out.push_str(&format!(
                             "    pub {}: {},\n",
                             to_snake_case(prop_name),
@@ -452,113 +883,336 @@ impl OpenApiClientGenerator for RustUreq {
             }
         }
 
+        write_header_types(&mut out, &client_ops, spec, options, rust_header_type);
+        if any_multipart {
+            out.push_str(RUST_MULTIPART);
+        }
+
         // Generate client struct
+        let client_start = out.len();
         out.push_str("pub struct ApiClient {\n");
         out.push_str("    base_url: String,\n");
+        out.push_str("    headers: Vec<(String, String)>,\n");
         out.push_str("}\n\n");
 
         out.push_str("impl ApiClient {\n");
         out.push_str("    pub fn new(base_url: impl Into<String>) -> Self {\n");
-        out.push_str("        Self { base_url: base_url.into() }\n");
+        out.push_str("        Self { base_url: base_url.into(), headers: Vec::new() }\n");
+        out.push_str("    }\n\n");
+        out.push_str("    /// Add a header sent with every request.\n");
+        out.push_str("    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {\n");
+        out.push_str("        self.headers.push((name.into(), value.into()));\n");
+        out.push_str("        self\n");
+        out.push_str("    }\n\n");
+        out.push_str("    fn request(&self, method: &str, url: &str) -> ureq::Request {\n");
+        out.push_str("        let mut req = ureq::request(method, url);\n");
+        out.push_str("        for (name, value) in &self.headers {\n");
+        out.push_str("            req = req.set(name, value);\n");
+        out.push_str("        }\n");
+        out.push_str("        req\n");
         out.push_str("    }\n\n");
 
-        // Generate methods from paths
-        if let Some(paths) = spec.get("paths").and_then(|p| p.as_object()) {
-            for (path, methods) in paths {
-                if let Some(op) = methods.get("get").and_then(|g| g.as_object()) {
-                    let op_id = op
-                        .get("operationId")
-                        .and_then(|id| id.as_str())
-                        .unwrap_or("unknown");
-                    let params = op
-                        .get("parameters")
-                        .and_then(|p| p.as_array())
-                        .map(|a| a.as_slice())
-                        .unwrap_or(&[]);
-
-                    let path_params: Vec<&str> = params
-                        .iter()
-                        .filter(|p| p.get("in").and_then(|i| i.as_str()) == Some("path"))
-                        .filter_map(|p| p.get("name").and_then(|n| n.as_str()))
-                        .collect();
-                    let query_params: Vec<(&str, bool)> = params
+        // Generate methods from paths. The `Api` trait declares them, and its
+        // `ApiClient` implementation delegates to the inherent methods, so
+        // callers don't need the trait in scope.
+        let mut trait_decls = String::new();
+        let mut delegates = String::new();
+        let mut mock = String::new();
+        for operation in &client_ops {
+            let path = operation.path;
+            let op = operation.op;
+            let op_id = operation.id.as_str();
+            let with_headers = !exposed_headers(operation, spec, options).is_empty();
+            let params = operation.parameters(spec);
+            let (path_params, query_params): (Vec<&Parameter>, Vec<&Parameter>) =
+                params.iter().partition(|p| p.location == "path");
+
+            let binary = operation.binary_response(spec);
+            let resp_type = if binary {
+                "Vec<u8>".to_string()
+            } else {
+                operation
+                    .response_schema(spec)
+                    .map(json_schema_to_rust)
+                    .unwrap_or_else(|| "()".to_string())
+            };
+            let form_fields = operation.multipart_fields(spec);
+
+            // Build function signature
+            let mut args = Vec::new();
+            args.push("&self".to_string());
+            for p in &path_params {
+                args.push(format!("{}: {}", to_snake_case(p.name), rust_param_type(p)));
+            }
+            for field in form_fields.iter().flatten() {
+                let field_type = if field.binary {
+                    "&[u8]".to_string()
+                } else if field.schema.get("type").and_then(|t| t.as_str()) == Some("string") {
+                    "&str".to_string()
+                } else {
+                    json_schema_to_rust(field.schema)
+                };
+                let field_type = if field.required {
+                    field_type
+                } else {
+                    format!("Option<{}>", field_type)
+                };
+                args.push(format!("{}: {}", to_snake_case(field.name), field_type));
+            }
+            for p in &query_params {
+                let param_type = if p.required {
+                    rust_param_type(p).to_string()
+                } else {
+                    format!("Option<{}>", rust_param_type(p))
+                };
+                args.push(format!("{}: {}", to_snake_case(p.name), param_type));
+            }
+
+            let return_type = if with_headers {
+                format!("({}, HashMap<String, String>)", resp_type)
+            } else {
+                resp_type.clone()
+            };
+            let name = to_snake_case(op_id);
+            let signature = format!(
+                "fn {}({}) -> Result<{}, ureq::Error>",
+                name,
+                args.join(", "),
+                return_type
+            );
+            if let Some(note) = deprecation(op) {
+                out.push_str(&format!("    {}\n", rust_deprecated(&note)));
+                trait_decls.push_str(&format!("    {}\n", rust_deprecated(&note)));
+            }
+            out.push_str(&format!("    pub {} {{\n", signature));
+            trait_decls.push_str(&format!("    {};\n", signature));
+            let call_args: Vec<&str> = std::iter::once("self")
+                .chain(
+                    args[1..]
                         .iter()
-                        .filter(|p| p.get("in").and_then(|i| i.as_str()) == Some("query"))
-                        .filter_map(|p| {
-                            let name = p.get("name").and_then(|n| n.as_str())?;
-                            let required =
-                                p.get("required").and_then(|r| r.as_bool()).unwrap_or(false);
-                            Some((name, required))
-                        })
-                        .collect();
-
-                    let op_value = Value::Object(op.clone());
-                    let resp_type = op_value
-                        .pointer("/responses/200/content/application~1json/schema")
-                        .map(json_schema_to_rust)
-                        .unwrap_or_else(|| "()".to_string());
-
-                    // Build function signature
-                    let mut args = Vec::new();
-                    args.push("&self".to_string());
-                    for p in &path_params {
-                        args.push(format!("{}: &str", to_snake_case(p)));
+                        .filter_map(|a| a.split_once(':'))
+                        .map(|(name, _)| name),
+                )
+                .collect();
+            delegates.push_str(&format!(
+                "    {} {{\n        ApiClient::{}({})\n    }}\n\n",
+                signature,
+                name,
+                call_args.join(", ")
+            ));
+            if options.mock {
+                let unused: Vec<String> = std::iter::once("&self".to_string())
+                    .chain(args[1..].iter().map(|a| format!("_{}", a)))
+                    .collect();
+                let data = if binary {
+                    "Vec::new()".to_string()
+                } else if resp_type == "()" {
+                    "()".to_string()
+                } else {
+                    match operation.response_example(spec) {
+                        Some(example) => format!(
+                            "serde_json::from_str({:?}).expect(\"example response of {} matches its type\")",
+                            example.to_string(),
+                            name
+                        ),
+                        None => "Default::default()".to_string(),
                     }
-                    for (p, required) in &query_params {
-                        let param_type = if *required {
-                            "&str".to_string()
+                };
+                let data = if with_headers {
+                    format!("({}, HashMap::new())", data)
+                } else {
+                    data
+                };
+                mock.push_str(&format!(
+                    "    fn {}({}) -> Result<{}, ureq::Error> {{\n        Ok({})\n    }}\n\n",
+                    name,
+                    unused.join(", "),
+                    return_type,
+                    data
+                ));
+            }
+
+            // Build URL with path params
+            let rust_path = path_params.iter().fold(path.to_string(), |acc, p| {
+                acc.replace(
+                    &format!("{{{}}}", p.name),
+                    &format!("{{{}}}", to_snake_case(p.name)),
+                )
+            });
+            out.push_str(&format!(
+                "        let url = format!(\"{{}}{}\", self.base_url);\n",
+                rust_path
+            ));
+
+            // Build request
+            if let Some(fields) = &form_fields {
+                out.push_str("        let mut form = Multipart::new();\n");
+                for field in fields {
+                    let snake = to_snake_case(field.name);
+                    let append = |var: &str| {
+                        if field.binary {
+                            format!("form.file(\"{}\", {});", field.name, var)
+                        } else if is_scalar(field.schema) {
+                            format!("form.text(\"{}\", &{}.to_string());", field.name, var)
                         } else {
-                            "Option<&str>".to_string()
-                        };
-                        args.push(format!("{}: {}", to_snake_case(p), param_type));
+                            format!(
+                                "form.text(\"{}\", &serde_json::to_string(&{}).unwrap_or_default());",
+                                field.name, var
+                            )
+                        }
+                    };
+                    if field.required {
+                        out.push_str(&format!("        {}\n", append(&snake)));
+                    } else {
+                        out.push_str(&format!(
+                            "        if let Some(v) = {} {{ {} }}\n",
+                            snake,
+                            append("v")
+                        ));
                     }
+                }
+                out.push_str("        let form = form.finish();\n");
+                out.push_str(&format!(
+                    "        let mut req = self.request(\"{}\", &url).set(\"Content-Type\", &form.content_type);\n",
+                    operation.method.to_uppercase()
+                ));
+            } else {
+                out.push_str("        let mut req = self.request(\"GET\", &url);\n");
+            }
+            for p in &query_params {
+                let snake = to_snake_case(p.name);
+                // `query` takes `&str`; other types are formatted first
+                let value = |var: &str| {
+                    if rust_param_type(p) == "&str" {
+                        var.to_string()
+                    } else {
+                        format!("&{}.to_string()", var)
+                    }
+                };
+                if p.required {
+                    out.push_str(&format!(
+                        "        req = req.query(\"{}\", {});\n",
+                        p.name,
+                        value(&snake)
+                    ));
+                } else {
+                    out.push_str(&format!(
+                        "        if let Some(v) = {} {{ req = req.query(\"{}\", {}); }}\n",
+                        snake,
+                        p.name,
+                        value("v")
+                    ));
+                }
+            }
 
+            let send = if form_fields.is_some() {
+                "req.send_bytes(&form.body)"
+            } else {
+                "req.call()"
+            };
+            if with_headers {
+                out.push_str(&format!("        let resp = {}?;\n", send));
+                out.push_str("        let headers: HashMap<String, String> = resp\n");
+                out.push_str("            .headers_names()\n");
+                out.push_str("            .into_iter()\n");
+                out.push_str(
+                    "            .filter_map(|name| resp.header(&name).map(|v| (name.clone(), v.to_string())))\n",
+                );
+                out.push_str("            .collect();\n");
+                if binary {
+                    out.push_str("        let mut body = Vec::new();\n");
+                    out.push_str("        resp.into_reader().read_to_end(&mut body)?;\n");
+                } else {
                     out.push_str(&format!(
-                        "    pub fn {}({}) -> Result<{}, ureq::Error> {{\n",
-                        to_snake_case(op_id),
-                        args.join(", "),
+                        "        let body: {} = resp.into_json()?;\n",
                         resp_type
                     ));
-
-                    // Build URL with path params
-                    let url_expr = if path_params.is_empty() {
-                        format!("format!(\"{{}}{}\"", path)
-                    } else {
-                        let rust_path = path_params.iter().fold(path.to_string(), |acc, p| {
-                            acc.replace(&format!("{{{}}}", p), &format!("{{{}}}", to_snake_case(p)))
-                        });
-                        format!("format!(\"{{}}{}\", ", rust_path)
-                    };
-                    out.push_str(&format!("        let url = {}self.base_url);\n", url_expr));
-
-                    // Build request
-                    out.push_str("        let mut req = ureq::get(&url);\n");
-                    for (p, required) in &query_params {
-                        let snake = to_snake_case(p);
-                        if *required {
-                            out.push_str(&format!(
-                                "        req = req.query(\"{}\", {});\n",
-                                p, snake
-                            ));
-                        } else {
-                            out.push_str(&format!(
-                                "        if let Some(v) = {} {{ req = req.query(\"{}\", v); }}\n",
-                                snake, p
-                            ));
-                        }
-                    }
-
-                    out.push_str("        let resp: ");
-                    out.push_str(&resp_type);
-                    out.push_str(" = req.call()?.into_json()?;\n");
-                    out.push_str("        Ok(resp)\n");
-                    out.push_str("    }\n\n");
                 }
+                out.push_str("        Ok((body, headers))\n");
+            } else if binary {
+                out.push_str("        let mut body = Vec::new();\n");
+                out.push_str(&format!(
+                    "        {}?.into_reader().read_to_end(&mut body)?;\n",
+                    send
+                ));
+                out.push_str("        Ok(body)\n");
+            } else {
+                out.push_str("        let resp: ");
+                out.push_str(&resp_type);
+                out.push_str(&format!(" = {}?.into_json()?;\n", send));
+                out.push_str("        Ok(resp)\n");
             }
+            out.push_str("    }\n\n");
         }
 
-        out.push_str("}\n");
-        out
+        out.push_str("}\n\n");
+        out.insert_str(
+            client_start,
+            &format!(
+                "/// The client's operations, for substituting `ApiClient` in tests.\npub trait Api {{\n{}}}\n\n",
+                trait_decls
+            ),
+        );
+        if client_ops.iter().any(|o| deprecation(o.op).is_some()) {
+            out.push_str("#[allow(deprecated)]\n");
+        }
+        out.push_str("impl Api for ApiClient {\n");
+        out.push_str(delegates.trim_end());
+        out.push_str("\n}\n");
+        if options.mock {
+            out.push_str("\n/// `Api` returning canned example responses, for tests.\n");
+            out.push_str("#[derive(Debug, Clone, Default)]\n");
+            out.push_str("pub struct MockApiClient;\n\n");
+            out.push_str("impl Api for MockApiClient {\n");
+            out.push_str(mock.trim_end());
+            out.push_str("\n}\n");
+        }
+        Ok(out)
+    }
+}
+
+/// The deprecation note of an operation or schema marked `deprecated: true`: its
+/// description on one line, or empty if it has none.
+fn deprecation(value: &Value) -> Option<String> {
+    if value.get("deprecated").and_then(|d| d.as_bool()) != Some(true) {
+        return None;
+    }
+    let description = value.get("description").and_then(|d| d.as_str());
+    Some(
+        description
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+/// JSDoc `@deprecated` tag.
+fn ts_deprecated(note: &str) -> String {
+    if note.is_empty() {
+        "/** @deprecated */".to_string()
+    } else {
+        format!("/** @deprecated {} */", note.replace("*/", "*\\/"))
+    }
+}
+
+/// Comment above a dataclass field marked deprecated, or nothing. Python has no
+/// deprecation marker for attributes, so this is documentation only.
+fn py_deprecated_field(prop: &Value) -> String {
+    match deprecation(prop) {
+        Some(note) if note.is_empty() => "    #: Deprecated.\n".to_string(),
+        Some(note) => format!("    #: Deprecated: {}\n", note),
+        None => String::new(),
+    }
+}
+
+/// `#[deprecated]` attribute.
+fn rust_deprecated(note: &str) -> String {
+    if note.is_empty() {
+        "#[deprecated]".to_string()
+    } else {
+        // Debug formatting escapes the note as a Rust string literal
+        format!("#[deprecated(note = {:?})]", note)
     }
 }
 
@@ -570,6 +1224,8 @@ fn to_snake_case(s: &str) -> String {
                 result.push('_');
             }
             result.push(c.to_lowercase().next().unwrap());
+        } else if c == '-' {
+            result.push('_');
         } else {
             result.push(c);
         }
@@ -604,6 +1260,9 @@ fn json_schema_to_rust(schema: &Value) -> String {
             }
             return "Vec<serde_json::Value>".to_string();
         }
+        if type_str == "string" && is_binary_schema(schema) {
+            return "Vec<u8>".to_string();
+        }
         return type_str_to_rust(type_str);
     }
 
@@ -623,6 +1282,344 @@ fn type_str_to_rust(t: &str) -> String {
 
 // --- Helpers ---
 
+/// Operations the client generators emit methods for: GETs, and operations
+/// with a `multipart/form-data` request body.
+fn client_operations(spec: &Value) -> Vec<Operation<'_>> {
+    operations(spec)
+        .into_iter()
+        .filter(|o| o.method == "get" || o.multipart_fields(spec).is_some())
+        .collect()
+}
+
+/// Whether a form field is sent as its plain text value rather than as JSON.
+fn is_scalar(schema: &Value) -> bool {
+    matches!(
+        schema.get("type").and_then(|t| t.as_str()),
+        Some("string" | "integer" | "number" | "boolean")
+    )
+}
+
+/// TypeScript type of a path or query parameter; strings unless declared otherwise.
+fn ts_param_type(param: &Parameter) -> &'static str {
+    match param.scalar_type() {
+        Some("integer" | "number") => "number",
+        Some("boolean") => "boolean",
+        _ => "string",
+    }
+}
+
+/// Python type of a path or query parameter.
+fn py_param_type(param: &Parameter) -> &'static str {
+    match param.scalar_type() {
+        Some("integer") => "int",
+        Some("number") => "float",
+        Some("boolean") => "bool",
+        _ => "str",
+    }
+}
+
+/// Rust argument type of a path or query parameter (strings are borrowed).
+fn rust_param_type(param: &Parameter) -> &'static str {
+    match param.scalar_type() {
+        Some("integer") => "i64",
+        Some("number") => "f64",
+        Some("boolean") => "bool",
+        _ => "&str",
+    }
+}
+
+/// Form field name as a TypeScript identifier.
+fn ts_ident(name: &str) -> String {
+    let ident: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '$' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", ident)
+    } else {
+        ident
+    }
+}
+
+/// Stdlib `multipart/form-data` encoder used by generated Python clients.
+/// `bytes` values are sent as files, dicts and lists as JSON, `None`s are skipped.
+const PY_ENCODE_MULTIPART: &str = r#"def _encode_multipart(fields: dict) -> tuple[bytes, str]:
+    boundary = uuid.uuid4().hex
+    body = bytearray()
+    for name, value in fields.items():
+        if value is None:
+            continue
+        body += f'--{boundary}\r\n'.encode()
+        if isinstance(value, (bytes, bytearray)):
+            body += f'Content-Disposition: form-data; name="{name}"; filename="{name}"\r\n'.encode()
+            body += b'Content-Type: application/octet-stream\r\n\r\n'
+            body += value
+        else:
+            if isinstance(value, (dict, list)):
+                value = json.dumps(value)
+            body += f'Content-Disposition: form-data; name="{name}"\r\n\r\n'.encode()
+            body += str(value).encode()
+        body += b'\r\n'
+    body += f'--{boundary}--\r\n'.encode()
+    return bytes(body), f'multipart/form-data; boundary={boundary}'
+
+
+"#;
+
+/// `multipart/form-data` body builder used by generated Rust clients (ureq has none).
+const RUST_MULTIPART: &str = r#"/// Body of a `multipart/form-data` request.
+struct Multipart {
+    boundary: String,
+    body: Vec<u8>,
+}
+
+impl Multipart {
+    fn new() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+        Self { boundary: format!("moss-boundary-{:x}", nanos), body: Vec::new() }
+    }
+
+    fn text(&mut self, name: &str, value: &str) {
+        self.part(&format!("form-data; name=\"{}\"\r\n", name), value.as_bytes());
+    }
+
+    fn file(&mut self, name: &str, data: &[u8]) {
+        let disposition = format!(
+            "form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n",
+            name, name
+        );
+        self.part(&disposition, data);
+    }
+
+    fn part(&mut self, disposition: &str, data: &[u8]) {
+        let head = format!("--{}\r\nContent-Disposition: {}\r\n", self.boundary, disposition);
+        self.body.extend_from_slice(head.as_bytes());
+        self.body.extend_from_slice(data);
+        self.body.extend_from_slice(b"\r\n");
+    }
+
+    /// Encoded body, with its content type.
+    fn finish(mut self) -> MultipartBody {
+        self.body.extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+        MultipartBody {
+            content_type: format!("multipart/form-data; boundary={}", self.boundary),
+            body: self.body,
+        }
+    }
+}
+
+/// An encoded `multipart/form-data` body and its content type.
+struct MultipartBody {
+    content_type: String,
+    body: Vec<u8>,
+}
+
+"#;
+
+/// Headers an operation's method should return: none unless requested.
+fn exposed_headers<'a>(
+    operation: &Operation<'a>,
+    spec: &'a Value,
+    options: &GenerateOptions,
+) -> Vec<ResponseHeader<'a>> {
+    if options.with_headers {
+        operation.response_headers(spec)
+    } else {
+        Vec::new()
+    }
+}
+
+/// Emit a typed headers declaration for each operation that exposes headers.
+/// Returns whether any operation does.
+fn write_header_types(
+    out: &mut String,
+    ops: &[Operation],
+    spec: &Value,
+    options: &GenerateOptions,
+    render: fn(&str, &[ResponseHeader]) -> String,
+) -> bool {
+    let mut any = false;
+    for operation in ops {
+        let headers = exposed_headers(operation, spec, options);
+        if !headers.is_empty() {
+            any = true;
+            let type_name = format!("{}Headers", pascal_case(&operation.id));
+            out.push_str(&render(&type_name, &headers));
+        }
+    }
+    any
+}
+
+/// Scalar type a header's string value is parsed into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HeaderKind {
+    Integer,
+    Number,
+    Boolean,
+    String,
+}
+
+fn header_kind(header: &ResponseHeader) -> HeaderKind {
+    let type_str = header.schema.and_then(|s| s.get("type")).and_then(|t| {
+        t.as_str().or_else(|| {
+            // `["integer", "null"]`
+            let mut non_null = t.as_array()?.iter().filter_map(|v| v.as_str());
+            non_null.find(|t| *t != "null")
+        })
+    });
+    match type_str {
+        Some("integer") => HeaderKind::Integer,
+        Some("number") => HeaderKind::Number,
+        Some("boolean") => HeaderKind::Boolean,
+        _ => HeaderKind::String,
+    }
+}
+
+/// Header name as a Python/Rust identifier: `X-Rate-Limit` -> `x_rate_limit`.
+fn header_ident(name: &str) -> String {
+    let ident: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", ident)
+    } else {
+        ident
+    }
+}
+
+fn ts_header_type(type_name: &str, headers: &[ResponseHeader]) -> String {
+    let mut out = format!("export interface {} {{\n", type_name);
+    for header in headers {
+        let ts_type = match header_kind(header) {
+            HeaderKind::Integer | HeaderKind::Number => "number",
+            HeaderKind::Boolean => "boolean",
+            HeaderKind::String => "string",
+        };
+        out.push_str(&format!("  '{}'?: {};\n", header.name, ts_type));
+    }
+    out.push_str("}\n\n");
+
+    out.push_str(&format!(
+        "export function parse{}(headers: Headers): {} {{\n",
+        type_name, type_name
+    ));
+    out.push_str(&format!("  const out: {} = {{}};\n", type_name));
+    out.push_str("  let v: string | null;\n");
+    for header in headers {
+        let value = match header_kind(header) {
+            HeaderKind::Integer | HeaderKind::Number => "Number(v)",
+            HeaderKind::Boolean => "v.toLowerCase() === 'true'",
+            HeaderKind::String => "v",
+        };
+        out.push_str(&format!("  v = headers.get('{}');\n", header.name));
+        out.push_str(&format!(
+            "  if (v !== null) out['{}'] = {};\n",
+            header.name, value
+        ));
+    }
+    out.push_str("  return out;\n");
+    out.push_str("}\n\n");
+    out
+}
+
+fn py_header_type(type_name: &str, headers: &[ResponseHeader]) -> String {
+    let mut out = format!("@dataclass\nclass {}:\n", type_name);
+    for header in headers {
+        let py_type = match header_kind(header) {
+            HeaderKind::Integer => "int",
+            HeaderKind::Number => "float",
+            HeaderKind::Boolean => "bool",
+            HeaderKind::String => "str",
+        };
+        out.push_str(&format!(
+            "    {}: Optional[{}] = None\n",
+            header_ident(&header.name),
+            py_type
+        ));
+    }
+    out.push_str("\n    @classmethod\n");
+    out.push_str(&format!(
+        "    def from_headers(cls, headers: dict[str, str]) -> '{}':\n",
+        type_name
+    ));
+    out.push_str("        return cls(\n");
+    for header in headers {
+        let name = &header.name;
+        let value = match header_kind(header) {
+            HeaderKind::Integer => {
+                format!("int(headers['{name}']) if '{name}' in headers else None")
+            }
+            HeaderKind::Number => {
+                format!("float(headers['{name}']) if '{name}' in headers else None")
+            }
+            HeaderKind::Boolean => {
+                format!("headers['{name}'].lower() == 'true' if '{name}' in headers else None")
+            }
+            HeaderKind::String => format!("headers.get('{name}')"),
+        };
+        out.push_str(&format!("            {}={},\n", header_ident(name), value));
+    }
+    out.push_str("        )\n\n\n");
+    out
+}
+
+fn rust_header_type(type_name: &str, headers: &[ResponseHeader]) -> String {
+    let mut out = String::from("#[derive(Debug, Clone, Default)]\n");
+    out.push_str(&format!("pub struct {} {{\n", type_name));
+    for header in headers {
+        let rust_type = match header_kind(header) {
+            HeaderKind::Integer => "i64",
+            HeaderKind::Number => "f64",
+            HeaderKind::Boolean => "bool",
+            HeaderKind::String => "String",
+        };
+        out.push_str(&format!(
+            "    pub {}: Option<{}>,\n",
+            header_ident(&header.name),
+            rust_type
+        ));
+    }
+    out.push_str("}\n\n");
+
+    out.push_str(&format!("impl {} {{\n", type_name));
+    out.push_str("    pub fn from_headers(headers: &HashMap<String, String>) -> Self {\n");
+    out.push_str("        Self {\n");
+    for header in headers {
+        let value = match header_kind(header) {
+            HeaderKind::String => format!("headers.get(\"{}\").cloned()", header.name),
+            _ => format!(
+                "headers.get(\"{}\").and_then(|v| v.parse().ok())",
+                header.name
+            ),
+        };
+        out.push_str(&format!(
+            "            {}: {},\n",
+            header_ident(&header.name),
+            value
+        ));
+    }
+    out.push_str("        }\n");
+    out.push_str("    }\n");
+    out.push_str("}\n\n");
+    out
+}
+
 fn json_schema_to_ts(schema: &Value) -> String {
     if let Some(ref_path) = schema.get("$ref").and_then(|r| r.as_str()) {
         return ref_path.split('/').last().unwrap_or("unknown").to_string();
@@ -646,6 +1643,9 @@ fn json_schema_to_ts(schema: &Value) -> String {
             }
             return "unknown[]".to_string();
         }
+        if type_str == "string" && is_binary_schema(schema) {
+            return "Blob".to_string();
+        }
         return type_str_to_ts(type_str);
     }
 
@@ -685,6 +1685,9 @@ fn json_schema_to_py(schema: &Value) -> String {
             }
             return "list".to_string();
         }
+        if type_str == "string" && is_binary_schema(schema) {
+            return "bytes".to_string();
+        }
         return type_str_to_py(type_str);
     }
 
@@ -730,5 +1733,731 @@ mod tests {
         assert_eq!(to_snake_case("getUserById"), "get_user_by_id");
         assert_eq!(to_snake_case("API"), "a_p_i");
         assert_eq!(to_snake_case("simple"), "simple");
+        assert_eq!(to_snake_case("page-size"), "page_size");
+    }
+
+    fn unnamed_spec() -> Value {
+        serde_json::json!({
+            "openapi": "3.0.0",
+            "paths": {
+                "/users": {"get": {"responses": {}}},
+                "/users/{id}": {"get": {
+                    "parameters": [{"name": "id", "in": "path", "required": true}],
+                    "responses": {}
+                }},
+                "/users/{id}/posts": {"get": {
+                    "parameters": [{"name": "id", "in": "path", "required": true}],
+                    "responses": {}
+                }}
+            }
+        })
+    }
+
+    #[test]
+    fn test_generators_synthesize_same_names() {
+        let spec = unnamed_spec();
+        let options = GenerateOptions::default();
+        let ts = TypeScriptFetch.generate(&spec, &options).unwrap();
+        let py = PythonUrllib.generate(&spec, &options).unwrap();
+        let rs = RustUreq.generate(&spec, &options).unwrap();
+
+        for name in ["getUsers", "getUsersById", "getUsersByIdPosts"] {
+            assert!(ts.contains(&format!("async {}(", name)), "{}", ts);
+            assert!(
+                py.contains(&format!("def {}(", to_snake_case(name))),
+                "{}",
+                py
+            );
+            assert!(
+                rs.contains(&format!("pub fn {}(", to_snake_case(name))),
+                "{}",
+                rs
+            );
+        }
+        assert!(!ts.contains("unknown("));
+        assert!(!py.contains("unknown("));
+        assert!(!rs.contains("unknown("));
+    }
+
+    #[test]
+    fn test_python_snake_case_methods() {
+        let spec = serde_json::json!({
+            "openapi": "3.0.0",
+            "paths": {"/users/{userId}/posts": {"get": {
+                "operationId": "getUserPosts",
+                "parameters": [
+                    {"name": "userId", "in": "path", "required": true},
+                    {"name": "pageSize", "in": "query"}
+                ],
+                "responses": {"200": {"content": {"application/json": {"schema": {
+                    "$ref": "#/components/schemas/Post"
+                }}}}}
+            }}},
+            "components": {"schemas": {"Post": {"properties": {"id": {"type": "integer"}}}}}
+        });
+        let py = PythonUrllib
+            .generate(&spec, &GenerateOptions::default())
+            .unwrap();
+        assert!(
+            py.contains(
+                "def get_user_posts(self, user_id: str, *, page_size: Optional[str] = None, **options) -> Post:"
+            ),
+            "{}",
+            py
+        );
+        assert!(
+            py.contains(
+                "self._request(f'/users/{user_id}/posts', {'pageSize': page_size}, **options)"
+            ),
+            "{}",
+            py
+        );
+        assert!(py.contains("class Post:"), "{}", py);
+    }
+
+    /// Parameters and responses shared through `components`.
+    fn shared_components_spec() -> Value {
+        serde_json::json!({
+            "openapi": "3.0.0",
+            "paths": {
+                "/users": {"get": {
+                    "operationId": "listUsers",
+                    "parameters": [{"$ref": "#/components/parameters/PageParam"}],
+                    "responses": {"200": {"$ref": "#/components/responses/UserPage"}}
+                }},
+                "/users/{userId}": {"get": {
+                    "operationId": "getUser",
+                    "parameters": [
+                        {"$ref": "#/components/parameters/UserId"},
+                        {"$ref": "#/components/parameters/PageParam"}
+                    ],
+                    "responses": {"200": {"$ref": "#/components/responses/SingleUser"}}
+                }}
+            },
+            "components": {
+                "parameters": {
+                    "PageParam": {"name": "page", "in": "query", "schema": {"type": "integer"}},
+                    "UserId": {"$ref": "#/components/parameters/UserIdParam"},
+                    "UserIdParam": {"name": "userId", "in": "path", "required": true}
+                },
+                "responses": {
+                    "UserPage": {"content": {"application/json": {"schema": {
+                        "type": "array", "items": {"$ref": "#/components/schemas/User"}
+                    }}}},
+                    "SingleUser": {"$ref": "#/components/responses/UserResponse"},
+                    "UserResponse": {"content": {"application/json": {"schema": {
+                        "$ref": "#/components/schemas/User"
+                    }}}}
+                },
+                "schemas": {"User": {"properties": {"id": {"type": "integer"}}}}
+            }
+        })
+    }
+
+    #[test]
+    fn test_shared_parameters_and_responses() {
+        let spec = shared_components_spec();
+        let options = GenerateOptions::default();
+
+        let ts = TypeScriptFetch.generate(&spec, &options).unwrap();
+        for expected in [
+            "async listUsers(options?: { page?: number }, init?: RequestOptions): Promise<User[]>",
+            "async getUser(userId: string, options?: { page?: number }, init?: RequestOptions): Promise<User>",
+        ] {
+            assert!(ts.contains(expected), "{}", ts);
+        }
+
+        let py = PythonUrllib.generate(&spec, &options).unwrap();
+        for expected in [
+            "def list_users(self, *, page: Optional[int] = None, **options) -> list[User]:",
+            "def get_user(self, user_id: str, *, page: Optional[int] = None, **options) -> User:",
+        ] {
+            assert!(py.contains(expected), "{}", py);
+        }
+
+        let rs = RustUreq.generate(&spec, &options).unwrap();
+        for expected in [
+            "pub fn list_users(&self, page: Option<i64>) -> Result<Vec<User>, ureq::Error>",
+            "pub fn get_user(&self, user_id: &str, page: Option<i64>) -> Result<User, ureq::Error>",
+        ] {
+            assert!(rs.contains(expected), "{}", rs);
+        }
+    }
+
+    fn headers_spec() -> Value {
+        serde_json::json!({
+            "openapi": "3.0.0",
+            "paths": {
+                "/users": {"get": {
+                    "operationId": "listUsers",
+                    "responses": {"200": {
+                        "headers": {
+                            "X-Rate-Limit": {"schema": {"type": "integer"}},
+                            "ETag": {"schema": {"type": "string"}}
+                        },
+                        "content": {"application/json": {"schema": {
+                            "type": "array", "items": {"$ref": "#/components/schemas/User"}
+                        }}}
+                    }}
+                }},
+                "/health": {"get": {"operationId": "health", "responses": {}}}
+            },
+            "components": {"schemas": {"User": {"properties": {"id": {"type": "integer"}}}}}
+        })
+    }
+
+    #[test]
+    fn test_headers_are_opt_in() {
+        let spec = headers_spec();
+        let options = GenerateOptions::default();
+        assert!(
+            !TypeScriptFetch
+                .generate(&spec, &options)
+                .unwrap()
+                .contains("Headers")
+        );
+        assert!(
+            !PythonUrllib
+                .generate(&spec, &options)
+                .unwrap()
+                .contains("ApiResponse")
+        );
+        assert!(
+            !RustUreq
+                .generate(&spec, &options)
+                .unwrap()
+                .contains("HashMap")
+        );
+    }
+
+    #[test]
+    fn test_with_headers() {
+        let spec = headers_spec();
+        let options = GenerateOptions {
+            with_headers: true,
+            ..Default::default()
+        };
+
+        let ts = TypeScriptFetch.generate(&spec, &options).unwrap();
+        assert!(
+            ts.contains("async listUsers(init?: RequestOptions): Promise<{ data: User[]; headers: Headers }>"),
+            "{}",
+            ts
+        );
+        assert!(
+            ts.contains("async health(init?: RequestOptions): Promise<void>"),
+            "{}",
+            ts
+        );
+        assert!(ts.contains("'x-rate-limit'?: number;"), "{}", ts);
+        assert!(
+            ts.contains(
+                "export function parseListUsersHeaders(headers: Headers): ListUsersHeaders"
+            ),
+            "{}",
+            ts
+        );
+
+        let py = PythonUrllib.generate(&spec, &options).unwrap();
+        assert!(py.contains("class ApiResponse(Generic[T]):"), "{}", py);
+        assert!(
+            py.contains("def list_users(self, **options) -> ApiResponse[list[User]]:"),
+            "{}",
+            py
+        );
+        assert!(
+            py.contains("    x_rate_limit: Optional[int] = None"),
+            "{}",
+            py
+        );
+        assert!(
+            py.contains("def health(self, **options) -> dict:"),
+            "{}",
+            py
+        );
+
+        let rs = RustUreq.generate(&spec, &options).unwrap();
+        assert!(
+            rs.contains(
+                "pub fn list_users(&self) -> Result<(Vec<User>, HashMap<String, String>), ureq::Error>"
+            ),
+            "{}",
+            rs
+        );
+        assert!(
+            rs.contains("pub fn health(&self) -> Result<(), ureq::Error>"),
+            "{}",
+            rs
+        );
+        assert!(rs.contains("pub x_rate_limit: Option<i64>,"), "{}", rs);
+        assert!(
+            rs.contains("etag: headers.get(\"etag\").cloned(),"),
+            "{}",
+            rs
+        );
+    }
+
+    #[test]
+    fn test_multipart_upload() {
+        let spec = serde_json::json!({
+            "paths": {"/users/{userId}/avatar": {"put": {
+                "operationId": "uploadAvatar",
+                "parameters": [{"name": "userId", "in": "path", "required": true}],
+                "requestBody": {"content": {"multipart/form-data": {"schema": {
+                    "type": "object",
+                    "required": ["file"],
+                    "properties": {
+                        "caption": {"type": "string"},
+                        "file": {"type": "string", "format": "binary"},
+                        "sizeHint": {"type": "integer"}
+                    }
+                }}}},
+                "responses": {"201": {"content": {"application/json": {"schema": {"type": "object"}}}}}
+            }}, "/users": {"post": {
+                "operationId": "createUser",
+                "requestBody": {"content": {"application/json": {"schema": {"type": "object"}}}}
+            }}}
+        });
+        let options = GenerateOptions::default();
+
+        let ts = TypeScriptFetch.generate(&spec, &options).unwrap();
+        assert!(
+            ts.contains("async uploadAvatar(userId: string, file: Blob, caption?: string, sizeHint?: number, init?: RequestOptions): Promise<Record<string, unknown>>"),
+            "{}",
+            ts
+        );
+        assert!(ts.contains("    form.append('file', file);"), "{}", ts);
+        assert!(
+            ts.contains("if (sizeHint !== undefined) form.append('sizeHint', String(sizeHint));"),
+            "{}",
+            ts
+        );
+        assert!(ts.contains("{ method: 'PUT', body: form }"), "{}", ts);
+        assert!(!ts.contains("createUser"), "{}", ts);
+
+        let py = PythonUrllib.generate(&spec, &options).unwrap();
+        assert!(py.contains("def _encode_multipart(fields: dict)"), "{}", py);
+        assert!(
+            py.contains("def upload_avatar(self, user_id: str, file: bytes, *, caption: Optional[str] = None, size_hint: Optional[int] = None, **options) -> dict:"),
+            "{}",
+            py
+        );
+        assert!(
+            py.contains(
+                "_encode_multipart({'file': file, 'caption': caption, 'sizeHint': size_hint})"
+            ),
+            "{}",
+            py
+        );
+        assert!(py.contains("method='PUT'"), "{}", py);
+
+        let rs = RustUreq.generate(&spec, &options).unwrap();
+        assert!(rs.contains("struct Multipart {"), "{}", rs);
+        assert!(
+            rs.contains("pub fn upload_avatar(&self, user_id: &str, file: &[u8], caption: Option<&str>, size_hint: Option<i64>)"),
+            "{}",
+            rs
+        );
+        assert!(rs.contains("        form.file(\"file\", file);"), "{}", rs);
+        assert!(rs.contains("req.send_bytes(&form.body)?"), "{}", rs);
+
+        // Without multipart operations, no encoder is emitted
+        let plain = serde_json::json!({"paths": {"/health": {"get": {}}}});
+        assert!(
+            !PythonUrllib
+                .generate(&plain, &options)
+                .unwrap()
+                .contains("uuid")
+        );
+        assert!(
+            !RustUreq
+                .generate(&plain, &options)
+                .unwrap()
+                .contains("Multipart")
+        );
+    }
+
+    #[test]
+    fn test_typed_parameters() {
+        let spec = serde_json::json!({
+            "openapi": "3.0.0",
+            "paths": {"/users/{userId}": {"get": {
+                "operationId": "getUser",
+                "parameters": [
+                    {"name": "userId", "in": "path", "required": true, "schema": {"type": "integer"}},
+                    {"name": "verbose", "in": "query", "schema": {"type": "boolean"}},
+                    {"name": "limit", "in": "query", "required": true, "schema": {"type": "integer"}},
+                    {"name": "q", "in": "query", "schema": {"type": "string"}}
+                ]
+            }}}
+        });
+        let options = GenerateOptions::default();
+
+        let ts = TypeScriptFetch.generate(&spec, &options).unwrap();
+        assert!(
+            ts.contains(
+                "async getUser(userId: number, options?: { verbose?: boolean; limit?: number; q?: string }, init?: RequestOptions)"
+            ),
+            "{}",
+            ts
+        );
+
+        let py = PythonUrllib.generate(&spec, &options).unwrap();
+        assert!(
+            py.contains("def get_user(self, user_id: int, *, verbose: Optional[bool] = None, limit: Optional[int] = None, q: Optional[str] = None, **options)"),
+            "{}",
+            py
+        );
+
+        let rs = RustUreq.generate(&spec, &options).unwrap();
+        assert!(
+            rs.contains("pub fn get_user(&self, user_id: i64, verbose: Option<bool>, limit: i64, q: Option<&str>)"),
+            "{}",
+            rs
+        );
+        assert!(
+            rs.contains("req = req.query(\"limit\", &limit.to_string());"),
+            "{}",
+            rs
+        );
+        assert!(
+            rs.contains("if let Some(v) = q { req = req.query(\"q\", v); }"),
+            "{}",
+            rs
+        );
+    }
+
+    #[test]
+    fn test_request_options() {
+        let spec = unnamed_spec();
+        let options = GenerateOptions::default();
+
+        let ts = TypeScriptFetch.generate(&spec, &options).unwrap();
+        assert!(
+            ts.contains("constructor(private baseUrl = 'http://localhost:8080', private headers: Record<string, string> = {})"),
+            "{}",
+            ts
+        );
+        assert!(
+            ts.contains("const headers = { ...this.headers, ...options?.headers };"),
+            "{}",
+            ts
+        );
+        assert!(
+            ts.contains("new URL(path, options?.baseUrl ?? this.baseUrl)"),
+            "{}",
+            ts
+        );
+
+        let py = PythonUrllib.generate(&spec, &options).unwrap();
+        assert!(
+            py.contains("headers = {**self.headers, **(headers or {})}"),
+            "{}",
+            py
+        );
+        assert!(
+            py.contains("urlopen(Request(url, headers=headers, **request_args))"),
+            "{}",
+            py
+        );
+
+        let rs = RustUreq.generate(&spec, &options).unwrap();
+        assert!(rs.contains("pub fn with_header("), "{}", rs);
+        assert!(rs.contains("req = req.set(name, value);"), "{}", rs);
+        assert!(
+            rs.contains("let mut req = self.request(\"GET\", &url);"),
+            "{}",
+            rs
+        );
+    }
+
+    #[test]
+    fn test_deprecated() {
+        let spec = serde_json::json!({
+            "openapi": "3.0.0",
+            "paths": {
+                "/users": {"get": {
+                    "operationId": "listUsers",
+                    "deprecated": true,
+                    "description": "Use \"searchUsers\"\ninstead.",
+                    "responses": {}
+                }},
+                "/legacy": {"get": {
+                    "operationId": "legacy",
+                    "deprecated": true,
+                    "responses": {}
+                }},
+                "/search": {"get": {"operationId": "searchUsers", "responses": {}}}
+            },
+            "components": {"schemas": {"User": {
+                "type": "object",
+                "required": ["id"],
+                "properties": {
+                    "id": {"type": "integer"},
+                    "login": {"type": "string", "deprecated": true, "description": "Use email."}
+                }
+            }}}
+        });
+        let options = GenerateOptions::default();
+
+        let ts = TypeScriptFetch.generate(&spec, &options).unwrap();
+        assert!(
+            ts.contains("  /** @deprecated Use \"searchUsers\" instead. */\n  async listUsers("),
+            "{}",
+            ts
+        );
+        assert!(
+            ts.contains("  /** @deprecated */\n  async legacy("),
+            "{}",
+            ts
+        );
+        assert!(ts.contains("  /** @deprecated Use email. */\n  login?: string;"));
+        assert!(
+            ts.contains("  /** @deprecated */\n  legacy(init?: RequestOptions): Promise<void>;")
+        );
+        // Both methods in the class and in the `Api` interface, and the field
+        assert_eq!(ts.matches("@deprecated").count(), 5);
+
+        let py = PythonUrllib.generate(&spec, &options).unwrap();
+        assert!(py.contains("import warnings\n"));
+        assert!(
+            py.contains(concat!(
+                "    def list_users(self, **options) -> dict:\n",
+                "        \"\"\"Deprecated: Use \\\"searchUsers\\\" instead.\"\"\"\n",
+                "        warnings.warn(\"list_users is deprecated: Use \\\"searchUsers\\\" instead.\", DeprecationWarning, stacklevel=2)\n",
+            )),
+            "{}",
+            py
+        );
+        assert!(py.contains("        \"\"\"Deprecated.\"\"\"\n"));
+        assert!(py.contains("    #: Deprecated: Use email.\n    login: Optional[str] = None\n"));
+        assert!(!py.contains("def search_users(self, **options) -> dict:\n        \"\"\""));
+
+        let rs = RustUreq.generate(&spec, &options).unwrap();
+        assert!(
+            rs.contains(
+                "    #[deprecated(note = \"Use \\\"searchUsers\\\" instead.\")]\n    pub fn list_users("
+            ),
+            "{}",
+            rs
+        );
+        assert!(rs.contains("    #[deprecated]\n    pub fn legacy("));
+        assert!(
+            rs.contains("    #[deprecated(note = \"Use email.\")]\n    pub login: Option<String>,")
+        );
+        assert!(rs.contains("    #[deprecated]\n    fn legacy(&self) -> Result<(), ureq::Error>;"));
+        assert!(rs.contains("#[allow(deprecated)]\nimpl Api for ApiClient {"));
+        assert_eq!(rs.matches("#[deprecated").count(), 5);
+    }
+
+    #[test]
+    fn test_mock_client() {
+        let spec = serde_json::json!({
+            "paths": {
+                "/users/{id}": {"get": {
+                    "operationId": "getUser",
+                    "parameters": [{"name": "id", "in": "path", "required": true, "schema": {"type": "integer"}}],
+                    "responses": {"200": {"content": {"application/json": {
+                        "schema": {"$ref": "#/components/schemas/User"}
+                    }}}}
+                }},
+                "/users": {"get": {
+                    "operationId": "listUsers",
+                    "responses": {"200": {"content": {"application/json": {
+                        "schema": {"type": "array", "items": {"$ref": "#/components/schemas/User"}},
+                        "example": [{"id": 7}]
+                    }}}}
+                }}
+            },
+            "components": {"schemas": {"User": {
+                "type": "object",
+                "properties": {"id": {"type": "integer"}, "name": {"type": "string", "example": "ann"}}
+            }}}
+        });
+        let plain = GenerateOptions::default();
+        let mock = GenerateOptions {
+            mock: true,
+            ..Default::default()
+        };
+
+        // The interface is always emitted, the mock only on request
+        let ts = TypeScriptFetch.generate(&spec, &plain).unwrap();
+        assert!(ts.contains("export interface Api {\n"), "{}", ts);
+        assert!(ts.contains("  getUser(id: number, init?: RequestOptions): Promise<User>;\n"));
+        assert!(ts.contains("export class ApiClient implements Api {"));
+        assert!(!ts.contains("MockApiClient"));
+        let ts = TypeScriptFetch.generate(&spec, &mock).unwrap();
+        assert!(
+            ts.contains("export class MockApiClient implements Api {"),
+            "{}",
+            ts
+        );
+        assert!(ts.contains(
+            "  async getUser(): Promise<User> {\n    return {\"id\":0,\"name\":\"ann\"} as unknown as User;\n  }"
+        ));
+        assert!(ts.contains("    return [{\"id\":7}] as unknown as User[];\n"));
+
+        let py = PythonUrllib.generate(&spec, &plain).unwrap();
+        assert!(py.contains("class Api(Protocol):\n"), "{}", py);
+        assert!(py.contains("    def get_user(self, id: int, **options) -> User: ...\n"));
+        assert!(!py.contains("MockApiClient"));
+        let py = PythonUrllib.generate(&spec, &mock).unwrap();
+        assert!(
+            py.contains(
+                "        return User(**json.loads(\"{\\\"id\\\":0,\\\"name\\\":\\\"ann\\\"}\"))\n"
+            ),
+            "{}",
+            py
+        );
+        assert!(py.contains(
+            "        return [User(**item) for item in json.loads(\"[{\\\"id\\\":7}]\")]\n"
+        ));
+
+        let rs = RustUreq.generate(&spec, &plain).unwrap();
+        assert!(rs.contains("pub trait Api {\n"), "{}", rs);
+        assert!(rs.contains("    fn get_user(&self, id: i64) -> Result<User, ureq::Error>;\n"));
+        assert!(rs.contains("        ApiClient::get_user(self, id)\n"));
+        assert!(!rs.contains("MockApiClient"));
+        let rs = RustUreq.generate(&spec, &mock).unwrap();
+        assert!(rs.contains("impl Api for MockApiClient {"), "{}", rs);
+        assert!(rs.contains("    fn get_user(&self, _id: i64) -> Result<User, ureq::Error> {\n"));
+        assert!(rs.contains("serde_json::from_str(\"[{\\\"id\\\":7}]\")"));
+    }
+
+    #[test]
+    fn test_binary_download() {
+        let spec = serde_json::json!({
+            "paths": {"/files/{fileId}/content": {"get": {
+                "operationId": "downloadFile",
+                "parameters": [{"name": "fileId", "in": "path", "required": true, "schema": {"type": "string"}}],
+                "responses": {"200": {
+                    "headers": {"Content-Disposition": {"schema": {"type": "string"}}},
+                    "content": {"application/octet-stream": {"schema": {"type": "string", "format": "binary"}}}
+                }}
+            }}, "/files/{fileId}": {"get": {
+                "operationId": "getFile",
+                "parameters": [{"name": "fileId", "in": "path", "required": true, "schema": {"type": "string"}}],
+                "responses": {"200": {"content": {"application/json": {"schema": {"$ref": "#/components/schemas/File"}}}}}
+            }}},
+            "components": {"schemas": {"File": {
+                "type": "object",
+                "properties": {
+                    "checksum": {"type": "string", "contentMediaType": "application/octet-stream", "contentEncoding": "base64"},
+                    "name": {"type": "string"},
+                    "thumbnail": {"type": "string", "format": "binary"}
+                }
+            }}}
+        });
+        let options = GenerateOptions::default();
+
+        let ts = TypeScriptFetch.generate(&spec, &options).unwrap();
+        assert!(
+            ts.contains(
+                "async downloadFile(fileId: string, init?: RequestOptions): Promise<ArrayBuffer>"
+            ),
+            "{}",
+            ts
+        );
+        assert!(
+            ts.contains("return this.requestBytes(`/files/${fileId}/content`"),
+            "{}",
+            ts
+        );
+        assert!(ts.contains("return await res.arrayBuffer();"), "{}", ts);
+        assert!(
+            ts.contains("return this.request<File>(`/files/${fileId}`"),
+            "{}",
+            ts
+        );
+        assert!(
+            ts.contains("  checksum?: string;\n  name?: string;\n  thumbnail?: Blob;"),
+            "{}",
+            ts
+        );
+
+        let py = PythonUrllib.generate(&spec, &options).unwrap();
+        assert!(
+            py.contains("def download_file(self, file_id: str, **options) -> bytes:\n        return self._request_bytes(f'/files/{file_id}/content', **options)"),
+            "{}",
+            py
+        );
+        assert!(
+            py.contains("            return response.read()\n"),
+            "{}",
+            py
+        );
+        assert!(
+            py.contains("    thumbnail: Optional[bytes] = None\n"),
+            "{}",
+            py
+        );
+
+        let rs = RustUreq.generate(&spec, &options).unwrap();
+        assert!(rs.contains("use std::io::Read;\n"), "{}", rs);
+        assert!(
+            rs.contains(
+                "pub fn download_file(&self, file_id: &str) -> Result<Vec<u8>, ureq::Error>"
+            ),
+            "{}",
+            rs
+        );
+        assert!(
+            rs.contains("req.call()?.into_reader().read_to_end(&mut body)?;"),
+            "{}",
+            rs
+        );
+        assert!(rs.contains("pub thumbnail: Option<Vec<u8>>,"), "{}", rs);
+
+        // With headers, the bytes come with them
+        let with_headers = GenerateOptions {
+            with_headers: true,
+            ..Default::default()
+        };
+        let ts = TypeScriptFetch.generate(&spec, &with_headers).unwrap();
+        assert!(
+            ts.contains("return this.requestBytesWithHeaders(`/files/${fileId}/content`"),
+            "{}",
+            ts
+        );
+        let py = PythonUrllib.generate(&spec, &with_headers).unwrap();
+        assert!(
+            py.contains("data, headers = self._request_bytes_with_headers("),
+            "{}",
+            py
+        );
+        let rs = RustUreq.generate(&spec, &with_headers).unwrap();
+        assert!(
+            rs.contains("resp.into_reader().read_to_end(&mut body)?;\n        Ok((body, headers))"),
+            "{}",
+            rs
+        );
+    }
+
+    #[test]
+    fn test_generation_is_deterministic() {
+        let spec: Value = serde_json::from_str(
+            r#"{
+                "paths": {
+                    "/zones": {"get": {"operationId": "listZones", "responses": {"200": {"description": "ok"}}}},
+                    "/accounts": {"get": {"operationId": "listAccounts", "responses": {"200": {"description": "ok"}}}}
+                },
+                "components": {"schemas": {
+                    "Zone": {"properties": {"zid": {"type": "string"}, "area": {"type": "number"}}},
+                    "Account": {"properties": {"owner": {"type": "string"}, "balance": {"type": "number"}}}
+                }}
+            }"#,
+        )
+        .unwrap();
+        let options = GenerateOptions::default();
+        for generator in generators() {
+            let first = generator.generate(&spec, &options).unwrap();
+            assert_eq!(first, generator.generate(&spec, &options).unwrap());
+            if !cfg!(feature = "preserve_order") {
+                let position = |needle: &str| first.find(needle).unwrap();
+                assert!(position("Account") < position("Zone"), "{}", first);
+                assert!(position("balance") < position("owner"), "{}", first);
+                assert!(position("/accounts") < position("/zones"), "{}", first);
+            }
+        }
     }
 }
@@ -0,0 +1,583 @@
+//! Operation enumeration with stable names.
+//!
+//! Every generator names methods after the operation's `operationId`. Specs often
+//! omit it, so names are synthesized from the method and path here, once, and
+//! shared by all generators: the same spec yields the same method names in every
+//! language.
+
+use crate::validate::HTTP_METHODS;
+use rhizome_moss_core::object_entries;
+use rhizome_moss_jsonschema::sample;
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// An operation (path + HTTP method) in an OpenAPI spec.
+#[derive(Debug, Clone)]
+pub struct Operation<'a> {
+    pub path: &'a str,
+    /// Lowercase HTTP method (e.g. "get")
+    pub method: &'static str,
+    /// The operation object
+    pub op: &'a Value,
+    /// The spec's `operationId`, or a synthesized one unique within the spec
+    pub id: String,
+    /// Whether `id` was synthesized because the spec has no `operationId`
+    pub synthesized: bool,
+}
+
+/// A response header declared by an operation.
+#[derive(Debug, Clone)]
+pub struct ResponseHeader<'a> {
+    /// Lowercase header name (HTTP header names are case-insensitive)
+    pub name: String,
+    /// The header's schema, with a top-level `$ref` resolved
+    pub schema: Option<&'a Value>,
+}
+
+/// A field of a `multipart/form-data` request body.
+#[derive(Debug, Clone)]
+pub struct FormField<'a> {
+    pub name: &'a str,
+    /// The field's schema, with a top-level `$ref` resolved
+    pub schema: &'a Value,
+    pub required: bool,
+    /// Whether the field carries file content (`format: binary`)
+    pub binary: bool,
+}
+
+/// A path or query parameter.
+#[derive(Debug, Clone)]
+pub struct Parameter<'a> {
+    pub name: &'a str,
+    /// `path` or `query`
+    pub location: &'a str,
+    pub required: bool,
+    /// The parameter's schema, with a top-level `$ref` resolved
+    pub schema: Option<&'a Value>,
+}
+
+impl Parameter<'_> {
+    /// The declared scalar type (`integer`, `number`, `boolean`, ...), from the
+    /// schema or, in Swagger 2 specs, the parameter itself. The first non-null
+    /// type of a `[T, "null"]` union counts.
+    pub fn scalar_type(&self) -> Option<&str> {
+        let type_val = self.schema?.get("type")?;
+        match type_val.as_array() {
+            Some(types) => types
+                .iter()
+                .filter_map(|t| t.as_str())
+                .find(|t| *t != "null"),
+            None => type_val.as_str(),
+        }
+    }
+}
+
+impl<'a> Operation<'a> {
+    /// Path and query parameters, in declaration order. `$ref`s into
+    /// `components/parameters` are resolved against `spec`.
+    pub fn parameters(&self, spec: &'a Value) -> Vec<Parameter<'a>> {
+        self.op
+            .get("parameters")
+            .and_then(|p| p.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|param| {
+                let param = resolve_ref(spec, param);
+                let location = param.get("in").and_then(|i| i.as_str())?;
+                if location != "path" && location != "query" {
+                    return None;
+                }
+                // Swagger 2 puts `type` on the parameter itself
+                let schema = match param.get("schema") {
+                    Some(schema) => Some(resolve_ref(spec, schema)),
+                    None => param.get("type").map(|_| param),
+                };
+                Some(Parameter {
+                    name: param.get("name").and_then(|n| n.as_str())?,
+                    location,
+                    required: param.get("required").and_then(|r| r.as_bool()) == Some(true),
+                    schema,
+                })
+            })
+            .collect()
+    }
+
+    /// JSON schema of the success response (200, else 201). `$ref`s into
+    /// `components/responses` are resolved against `spec`; the schema is
+    /// returned as declared, so a `$ref` to a named type stays one.
+    pub fn response_schema(&self, spec: &'a Value) -> Option<&'a Value> {
+        let responses = self.op.get("responses")?;
+        ["200", "201"].into_iter().find_map(|status| {
+            resolve_ref(spec, responses.get(status)?).pointer("/content/application~1json/schema")
+        })
+    }
+
+    /// Example body of the success response (200, else 201), for mocks: the
+    /// JSON media type's `example` (or first of `examples`), else a sample
+    /// synthesized from its schema. None without a JSON response.
+    pub fn response_example(&self, spec: &'a Value) -> Option<Value> {
+        let responses = self.op.get("responses")?;
+        let media = ["200", "201"].into_iter().find_map(|status| {
+            resolve_ref(spec, responses.get(status)?).pointer("/content/application~1json")
+        })?;
+        if let Some(example) = media.get("example") {
+            return Some(example.clone());
+        }
+        if let Some(example) = media
+            .get("examples")
+            .and_then(|e| e.as_object())
+            .and_then(|e| e.values().find_map(|e| resolve_ref(spec, e).get("value")))
+        {
+            return Some(example.clone());
+        }
+        Some(sample(media.get("schema")?, spec))
+    }
+
+    /// Whether the success response (200, else 201) carries raw bytes: none of
+    /// its content types is JSON, and one is binary (`application/octet-stream`,
+    /// images, audio, video, archives) or has a binary schema.
+    pub fn binary_response(&self, spec: &'a Value) -> bool {
+        let Some(responses) = self.op.get("responses") else {
+            return false;
+        };
+        let content = ["200", "201"].into_iter().find_map(|status| {
+            resolve_ref(spec, responses.get(status)?)
+                .get("content")?
+                .as_object()
+        });
+        content.is_some_and(|content| {
+            !content.keys().any(|media| is_json_media_type(media))
+                && content.iter().any(|(media, body)| {
+                    is_binary_media_type(media)
+                        || body
+                            .get("schema")
+                            .is_some_and(|schema| is_binary_schema(resolve_ref(spec, schema)))
+                })
+        })
+    }
+
+    /// Headers declared under `responses.*.headers`.
+    ///
+    /// A header declared by several responses is listed once, with the schema
+    /// of the first response (in status-code order) that declares it. `$ref`s into `components/headers` are resolved
+    /// against `spec`.
+    pub fn response_headers(&self, spec: &'a Value) -> Vec<ResponseHeader<'a>> {
+        let Some(responses) = self.op.get("responses").and_then(|r| r.as_object()) else {
+            return Vec::new();
+        };
+
+        let mut headers: Vec<ResponseHeader<'a>> = Vec::new();
+        for (_, response) in object_entries(responses) {
+            let response = resolve_ref(spec, response);
+            let Some(declared) = response.get("headers").and_then(|h| h.as_object()) else {
+                continue;
+            };
+            for (name, header) in object_entries(declared) {
+                let name = name.to_lowercase();
+                if headers.iter().any(|h| h.name == name) {
+                    continue;
+                }
+                let schema = resolve_ref(spec, header)
+                    .get("schema")
+                    .map(|s| resolve_ref(spec, s));
+                headers.push(ResponseHeader { name, schema });
+            }
+        }
+        headers
+    }
+
+    /// Fields of the operation's `multipart/form-data` request body, or None if
+    /// its request body has no such content type.
+    ///
+    /// Required fields come first; otherwise fields keep their schema order.
+    pub fn multipart_fields(&self, spec: &'a Value) -> Option<Vec<FormField<'a>>> {
+        let body = resolve_ref(spec, self.op.get("requestBody")?);
+        let media = body.pointer("/content/multipart~1form-data")?;
+        let schema = media.get("schema").map(|s| resolve_ref(spec, s));
+        let Some(schema) = schema else {
+            return Some(Vec::new());
+        };
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(|r| r.as_array())
+            .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+
+        let mut fields: Vec<FormField<'a>> = schema
+            .get("properties")
+            .and_then(|p| p.as_object())
+            .into_iter()
+            .flat_map(object_entries)
+            .map(|(name, field)| {
+                let field = resolve_ref(spec, field);
+                FormField {
+                    name,
+                    schema: field,
+                    required: required.contains(&name.as_str()),
+                    binary: is_binary_schema(field),
+                }
+            })
+            .collect();
+        fields.sort_by_key(|f| !f.required);
+        Some(fields)
+    }
+}
+
+/// Whether a schema describes raw bytes: a string with `format: binary`, or
+/// (OpenAPI 3.1) a `contentMediaType` without a `contentEncoding`. Encoded
+/// content (`contentEncoding: base64`) travels as text and stays a string.
+pub(crate) fn is_binary_schema(schema: &Value) -> bool {
+    if schema.get("contentEncoding").is_some() {
+        return false;
+    }
+    schema.get("format").and_then(|f| f.as_str()) == Some("binary")
+        || schema.get("contentMediaType").is_some()
+}
+
+fn is_json_media_type(media: &str) -> bool {
+    let media = media.split(';').next().unwrap_or(media).trim();
+    media.eq_ignore_ascii_case("application/json") || media.ends_with("+json")
+}
+
+fn is_binary_media_type(media: &str) -> bool {
+    let media = media
+        .split(';')
+        .next()
+        .unwrap_or(media)
+        .trim()
+        .to_ascii_lowercase();
+    let (top, sub) = media.split_once('/').unwrap_or((&media, ""));
+    match top {
+        "image" => sub != "svg+xml",
+        "audio" | "video" => true,
+        "application" => matches!(
+            sub,
+            "octet-stream" | "pdf" | "zip" | "gzip" | "x-tar" | "x-7z-compressed"
+        ),
+        _ => false,
+    }
+}
+
+/// Follow a chain of local `$ref`s (`#/components/...`) to the value it ends at.
+///
+/// A reference that does not resolve (external or dangling) is returned as-is.
+/// A reference cycle is reported and returned where it closes.
+pub(crate) fn resolve_ref<'a>(spec: &'a Value, value: &'a Value) -> &'a Value {
+    let mut current = value;
+    let mut visited = HashSet::new();
+    while let Some(reference) = current.get("$ref").and_then(|r| r.as_str()) {
+        let Some(target) = reference
+            .strip_prefix('#')
+            .and_then(|pointer| spec.pointer(pointer))
+        else {
+            break;
+        };
+        if !visited.insert(reference) {
+            eprintln!("Warning: `$ref` cycle through `{}`", reference);
+            break;
+        }
+        current = target;
+    }
+    current
+}
+
+/// All operations in a spec, in document order, with unique ids.
+///
+/// Explicit `operationId`s are kept as-is (duplicates among them are a spec error
+/// reported by validation). Synthesized ids never collide with explicit ones or
+/// each other: a clash gets a numeric suffix (`getUsers2`).
+pub fn operations(spec: &Value) -> Vec<Operation<'_>> {
+    let Some(paths) = spec.get("paths").and_then(|p| p.as_object()) else {
+        return Vec::new();
+    };
+
+    let mut found = Vec::new();
+    for (path, item) in object_entries(paths) {
+        for method in HTTP_METHODS {
+            if let Some(op) = item.get(*method).filter(|op| op.is_object()) {
+                found.push((path.as_str(), *method, op));
+            }
+        }
+    }
+
+    let mut taken: HashSet<String> = found
+        .iter()
+        .filter_map(|(_, _, op)| op.get("operationId").and_then(|id| id.as_str()))
+        .map(|id| id.to_string())
+        .collect();
+
+    found
+        .into_iter()
+        .map(|(path, method, op)| {
+            let (id, synthesized) = match op.get("operationId").and_then(|id| id.as_str()) {
+                Some(id) => (id.to_string(), false),
+                None => {
+                    let base = synthesize_operation_id(method, path);
+                    let mut id = base.clone();
+                    let mut n = 2;
+                    while taken.contains(&id) {
+                        id = format!("{}{}", base, n);
+                        n += 1;
+                    }
+                    taken.insert(id.clone());
+                    (id, true)
+                }
+            };
+            Operation {
+                path,
+                method,
+                op,
+                id,
+                synthesized,
+            }
+        })
+        .collect()
+}
+
+/// Derive an operationId from the HTTP method and path.
+///
+/// `get /users/{id}` becomes `getUsersById`; the root path becomes `getRoot`.
+/// Use [`operations`] to get ids that are unique within a spec.
+pub fn synthesize_operation_id(method: &str, path: &str) -> String {
+    let mut id = method.to_lowercase();
+    let mut any_segment = false;
+    for segment in path.split('/').filter(|s| !s.is_empty()) {
+        any_segment = true;
+        if let Some(param) = segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            id.push_str("By");
+            id.push_str(&pascal_case(param));
+        } else {
+            id.push_str(&pascal_case(segment));
+        }
+    }
+    if !any_segment {
+        id.push_str("Root");
+    }
+    id
+}
+
+pub(crate) fn pascal_case(s: &str) -> String {
+    s.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_synthesize_operation_id() {
+        assert_eq!(
+            synthesize_operation_id("get", "/users/{id}"),
+            "getUsersById"
+        );
+        assert_eq!(
+            synthesize_operation_id("POST", "/user-groups"),
+            "postUserGroups"
+        );
+        assert_eq!(synthesize_operation_id("get", "/"), "getRoot");
+    }
+
+    #[test]
+    fn test_synthesized_ids_are_unique() {
+        let spec = json!({
+            "paths": {
+                "/users": {"get": {}, "post": {"operationId": "getUsers"}},
+                "/users/": {"get": {}},
+                "/user_s": {"get": {}}
+            }
+        });
+        let ids: Vec<String> = operations(&spec).into_iter().map(|o| o.id).collect();
+        let unique: HashSet<&String> = ids.iter().collect();
+        assert_eq!(ids.len(), 4);
+        assert_eq!(unique.len(), 4, "{:?}", ids);
+        assert!(ids.contains(&"getUsers".to_string()));
+        assert!(ids.contains(&"getUsers2".to_string()));
+    }
+
+    #[test]
+    fn test_response_headers() {
+        let spec = json!({
+            "paths": {"/users": {"get": {"responses": {
+                "200": {"headers": {
+                    "X-Rate-Limit": {"schema": {"type": "integer"}},
+                    "ETag": {"$ref": "#/components/headers/ETag"}
+                }},
+                "404": {"headers": {"x-rate-limit": {"schema": {"type": "string"}}}}
+            }}}},
+            "components": {"headers": {"ETag": {"schema": {"type": "string"}}}}
+        });
+        let ops = operations(&spec);
+        let headers = ops[0].response_headers(&spec);
+        assert_eq!(headers.len(), 2);
+        let schema = |name: &str| headers.iter().find(|h| h.name == name).unwrap().schema;
+        assert_eq!(schema("x-rate-limit"), Some(&json!({"type": "integer"})));
+        assert_eq!(schema("etag"), Some(&json!({"type": "string"})));
+    }
+
+    #[test]
+    fn test_multipart_fields() {
+        let spec = json!({
+            "paths": {"/avatar": {"post": {"requestBody": {"content": {
+                "multipart/form-data": {"schema": {"$ref": "#/components/schemas/Upload"}}
+            }}}}, "/users": {"post": {"requestBody": {"content": {
+                "application/json": {"schema": {"type": "object"}}
+            }}}}},
+            "components": {"schemas": {"Upload": {
+                "type": "object",
+                "required": ["file"],
+                "properties": {
+                    "caption": {"type": "string"},
+                    "file": {"type": "string", "format": "binary"}
+                }
+            }}}
+        });
+        let ops = operations(&spec);
+        let fields = ops[0].multipart_fields(&spec).unwrap();
+        let summary: Vec<_> = fields
+            .iter()
+            .map(|f| (f.name, f.required, f.binary))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("file", true, true), ("caption", false, false)]
+        );
+        assert!(ops[1].multipart_fields(&spec).is_none());
+    }
+
+    #[test]
+    fn test_parameters() {
+        let spec = serde_json::json!({
+            "paths": {"/users/{id}": {"get": {"parameters": [
+                {"name": "id", "in": "path", "required": true, "schema": {"type": "integer"}},
+                {"$ref": "#/components/parameters/Limit"},
+                {"name": "verbose", "in": "query", "type": "boolean"},
+                {"name": "X-Trace", "in": "header"}
+            ]}}},
+            "components": {"parameters": {"Limit": {
+                "name": "limit", "in": "query", "schema": {"type": ["number", "null"]}
+            }}}
+        });
+        let ops = operations(&spec);
+        let params = ops[0].parameters(&spec);
+        let summary: Vec<_> = params
+            .iter()
+            .map(|p| (p.name, p.location, p.required, p.scalar_type()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("id", "path", true, Some("integer")),
+                ("limit", "query", false, Some("number")),
+                ("verbose", "query", false, Some("boolean")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_ref_chains() {
+        // Longer than any fixed hop limit
+        let mut parameters = serde_json::Map::new();
+        for i in 0..20 {
+            parameters.insert(
+                format!("P{}", i),
+                json!({"$ref": format!("#/components/parameters/P{}", i + 1)}),
+            );
+        }
+        parameters.insert("P20".into(), json!({"name": "id", "in": "path"}));
+        parameters.insert("A".into(), json!({"$ref": "#/components/parameters/B"}));
+        parameters.insert("B".into(), json!({"$ref": "#/components/parameters/A"}));
+        let spec = json!({"components": {"parameters": parameters}});
+
+        let chained = json!({"$ref": "#/components/parameters/P0"});
+        assert_eq!(
+            resolve_ref(&spec, &chained),
+            &json!({"name": "id", "in": "path"})
+        );
+
+        let cyclic = json!({"$ref": "#/components/parameters/A"});
+        assert!(resolve_ref(&spec, &cyclic).get("$ref").is_some());
+
+        let dangling = json!({"$ref": "#/components/parameters/Missing"});
+        assert_eq!(resolve_ref(&spec, &dangling), &dangling);
+    }
+
+    #[test]
+    fn test_binary_response() {
+        let spec = json!({
+            "paths": {
+                "/files/{id}": {"get": {"responses": {"200": {"content": {
+                    "application/octet-stream": {"schema": {"type": "string", "format": "binary"}}
+                }}}}},
+                "/avatar": {"get": {"responses": {"200": {"content": {"image/png": {}}}}}},
+                "/report": {"get": {"responses": {"200": {"content": {
+                    "application/json": {"schema": {"type": "object"}},
+                    "application/pdf": {}
+                }}}}},
+                "/logo": {"get": {"responses": {"200": {"content": {"image/svg+xml": {}}}}}},
+                "/blob": {"get": {"responses": {"200": {"content": {"application/x-custom": {
+                    "schema": {"type": "string", "contentMediaType": "application/x-custom"}
+                }}}}}},
+                "/encoded": {"get": {"responses": {"200": {"content": {"text/plain": {
+                    "schema": {"type": "string", "contentMediaType": "image/png", "contentEncoding": "base64"}
+                }}}}}}
+            }
+        });
+        let ops = operations(&spec);
+        let binary = |path: &str| {
+            let op = ops.iter().find(|o| o.path == path).unwrap();
+            op.binary_response(&spec)
+        };
+        assert!(binary("/files/{id}"));
+        assert!(binary("/avatar"));
+        assert!(binary("/blob"));
+        // JSON wins over other content types; SVG and base64 text are not bytes
+        assert!(!binary("/report"));
+        assert!(!binary("/logo"));
+        assert!(!binary("/encoded"));
+    }
+
+    #[test]
+    fn test_response_schema() {
+        let spec = json!({
+            "paths": {
+                "/users": {"get": {"responses": {
+                    "200": {"$ref": "#/components/responses/UserList"}
+                }}},
+                "/users/{id}": {"get": {"responses": {
+                    "200": {"description": "no body"},
+                    "201": {"content": {"application/json": {"schema": {"type": "string"}}}}
+                }}},
+                "/health": {"get": {"responses": {"default": {"$ref": "#/components/responses/Error"}}}}
+            },
+            "components": {"responses": {
+                "UserList": {"$ref": "#/components/responses/Users"},
+                "Users": {"content": {"application/json": {"schema": {
+                    "type": "array", "items": {"$ref": "#/components/schemas/User"}
+                }}}},
+                "Error": {"content": {"application/json": {"schema": {"type": "object"}}}}
+            }}
+        });
+        let ops = operations(&spec);
+        let schema = |path: &str| {
+            let op = ops.iter().find(|o| o.path == path).unwrap();
+            op.response_schema(&spec)
+        };
+        assert_eq!(
+            schema("/users"),
+            Some(&json!({"type": "array", "items": {"$ref": "#/components/schemas/User"}}))
+        );
+        // No JSON body on 200: the 201 response is used
+        assert_eq!(schema("/users/{id}"), Some(&json!({"type": "string"})));
+        assert_eq!(schema("/health"), None);
+    }
+}
@@ -0,0 +1,353 @@
+//! Structural validation of OpenAPI specs.
+//!
+//! Generators assume a well-formed spec; this pass catches the problems that would
+//! otherwise produce subtly broken output (dangling `$ref`s, undeclared path
+//! parameters, operations without responses). Locations are JSON Pointers into
+//! the spec, e.g. `/paths/~1users~1{id}/get`.
+
+use crate::operations::{Operation, operations, resolve_ref};
+use rhizome_moss_core::object_entries;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// HTTP methods that may appear as operations under a path item.
+pub const HTTP_METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// How serious a validation issue is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Generated code would be wrong; generation should not proceed.
+    Error,
+    /// Generation can proceed, possibly with synthesized values.
+    Warning,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+/// A problem found in an OpenAPI spec.
+#[derive(Debug, Clone)]
+pub struct Issue {
+    pub severity: Severity,
+    /// JSON Pointer to the offending location
+    pub pointer: String,
+    pub message: String,
+}
+
+/// Check structural invariants of an OpenAPI spec.
+///
+/// Issues are returned paths first, then from the `$ref` sweep, each in the key
+/// order of [`object_entries`].
+pub fn validate(spec: &Value) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    match spec.get("paths") {
+        Some(Value::Object(paths)) => {
+            let ops = operations(spec);
+            let mut seen_ids = HashMap::new();
+            for (path, item) in object_entries(paths) {
+                let item_pointer = format!("/paths/{}", escape_pointer(path));
+                check_path_item(
+                    spec,
+                    path,
+                    item,
+                    &item_pointer,
+                    &ops,
+                    &mut seen_ids,
+                    &mut issues,
+                );
+            }
+        }
+        Some(_) => issues.push(error("/paths", "`paths` must be an object")),
+        None => issues.push(error("", "missing `paths`")),
+    }
+
+    check_refs(spec, spec, &mut String::new(), &mut issues);
+    issues
+}
+
+/// Escape a single JSON Pointer reference token (RFC 6901).
+pub fn escape_pointer(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+fn error(pointer: &str, message: impl Into<String>) -> Issue {
+    Issue {
+        severity: Severity::Error,
+        pointer: pointer.to_string(),
+        message: message.into(),
+    }
+}
+
+fn warning(pointer: &str, message: impl Into<String>) -> Issue {
+    Issue {
+        severity: Severity::Warning,
+        pointer: pointer.to_string(),
+        message: message.into(),
+    }
+}
+
+/// Names of `{param}` placeholders in a path template.
+fn template_params(path: &str) -> Vec<&str> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|s| s.split_once('}').map(|(name, _)| name))
+        .collect()
+}
+
+/// Resolve a chain of local `$ref`s, returning the value unchanged if it is not
+/// a reference. None if the chain dangles or cycles.
+fn resolve<'a>(spec: &'a Value, value: &'a Value) -> Option<&'a Value> {
+    let resolved = resolve_ref(spec, value);
+    resolved.get("$ref").is_none().then_some(resolved)
+}
+
+/// Path parameter names declared in a `parameters` array.
+fn declared_path_params<'a>(spec: &'a Value, params: Option<&'a Value>) -> Vec<&'a str> {
+    params
+        .and_then(|p| p.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|p| resolve(spec, p))
+        .filter(|p| p.get("in").and_then(|i| i.as_str()) == Some("path"))
+        .filter_map(|p| p.get("name").and_then(|n| n.as_str()))
+        .collect()
+}
+
+fn check_path_item(
+    spec: &Value,
+    path: &str,
+    item: &Value,
+    item_pointer: &str,
+    ops: &[Operation],
+    seen_ids: &mut HashMap<String, String>,
+    issues: &mut Vec<Issue>,
+) {
+    if !item.is_object() {
+        issues.push(error(item_pointer, "path item must be an object"));
+        return;
+    }
+    let template = template_params(path);
+    let shared = declared_path_params(spec, item.get("parameters"));
+
+    for method in HTTP_METHODS {
+        let Some(op) = item.get(*method) else {
+            continue;
+        };
+        let op_pointer = format!("{}/{}", item_pointer, method);
+
+        match op.get("operationId").and_then(|id| id.as_str()) {
+            Some(id) => {
+                if let Some(first) = seen_ids.get(id) {
+                    issues.push(error(
+                        &format!("{}/operationId", op_pointer),
+                        format!("duplicate operationId `{}` (first used at {})", id, first),
+                    ));
+                } else {
+                    seen_ids.insert(id.to_string(), op_pointer.clone());
+                }
+            }
+            None => {
+                let synthesized = ops
+                    .iter()
+                    .find(|o| o.path == path && o.method == *method)
+                    .map(|o| o.id.as_str())
+                    .unwrap_or_default();
+                issues.push(warning(
+                    &op_pointer,
+                    format!("missing operationId; using `{}`", synthesized),
+                ));
+            }
+        }
+
+        let mut declared = shared.clone();
+        declared.extend(declared_path_params(spec, op.get("parameters")));
+        for name in &template {
+            if !declared.contains(name) {
+                issues.push(error(
+                    &op_pointer,
+                    format!("path parameter `{}` is not declared in `parameters`", name),
+                ));
+            }
+        }
+        for name in declared_path_params(spec, op.get("parameters")) {
+            if !template.contains(&name) {
+                issues.push(error(
+                    &format!("{}/parameters", op_pointer),
+                    format!("path parameter `{}` does not appear in `{}`", name, path),
+                ));
+            }
+        }
+
+        check_responses(spec, op, &op_pointer, issues);
+    }
+}
+
+fn check_responses(spec: &Value, op: &Value, op_pointer: &str, issues: &mut Vec<Issue>) {
+    let Some(responses) = op.get("responses").and_then(|r| r.as_object()) else {
+        issues.push(error(op_pointer, "operation has no `responses`"));
+        return;
+    };
+    if responses.is_empty() {
+        issues.push(error(
+            &format!("{}/responses", op_pointer),
+            "operation has no responses",
+        ));
+        return;
+    }
+    for (status, response) in object_entries(responses) {
+        let response_pointer = format!("{}/responses/{}", op_pointer, escape_pointer(status));
+        // Dangling refs are reported by the $ref sweep
+        let Some(response) = resolve(spec, response) else {
+            continue;
+        };
+        let Some(content) = response.get("content").and_then(|c| c.as_object()) else {
+            continue;
+        };
+        for (media_type, media) in object_entries(content) {
+            if media.get("schema").is_none() {
+                issues.push(warning(
+                    &format!(
+                        "{}/content/{}",
+                        response_pointer,
+                        escape_pointer(media_type)
+                    ),
+                    "response content has no `schema`",
+                ));
+            }
+        }
+    }
+}
+
+/// Report every `$ref` that does not resolve within the spec.
+fn check_refs(spec: &Value, value: &Value, pointer: &mut String, issues: &mut Vec<Issue>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(reference) = map.get("$ref").and_then(|r| r.as_str()) {
+                let ref_pointer = format!("{}/$ref", pointer);
+                match reference.strip_prefix('#') {
+                    Some(target) if spec.pointer(target).is_some() => {}
+                    Some(_) => issues.push(error(
+                        &ref_pointer,
+                        format!("`{}` does not resolve", reference),
+                    )),
+                    None => issues.push(warning(
+                        &ref_pointer,
+                        format!("external reference `{}` is not checked", reference),
+                    )),
+                }
+            }
+            for (key, child) in object_entries(map) {
+                let len = pointer.len();
+                pointer.push('/');
+                pointer.push_str(&escape_pointer(key));
+                check_refs(spec, child, pointer, issues);
+                pointer.truncate(len);
+            }
+        }
+        Value::Array(items) => {
+            for (i, child) in items.iter().enumerate() {
+                let len = pointer.len();
+                pointer.push_str(&format!("/{}", i));
+                check_refs(spec, child, pointer, issues);
+                pointer.truncate(len);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_valid_spec_has_no_issues() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "paths": {
+                "/users/{id}": {
+                    "get": {
+                        "operationId": "getUser",
+                        "parameters": [{"name": "id", "in": "path", "required": true}],
+                        "responses": {
+                            "200": {"content": {"application/json": {
+                                "schema": {"$ref": "#/components/schemas/User"}
+                            }}}
+                        }
+                    }
+                }
+            },
+            "components": {"schemas": {"User": {"type": "object"}}}
+        });
+        let issues = validate(&spec);
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn test_chained_parameter_ref_is_declared() {
+        let spec = json!({
+            "paths": {
+                "/users/{id}": {
+                    "get": {
+                        "operationId": "getUser",
+                        "parameters": [{"$ref": "#/components/parameters/UserId"}],
+                        "responses": {"204": {"description": "ok"}}
+                    }
+                }
+            },
+            "components": {"parameters": {
+                "UserId": {"$ref": "#/components/parameters/UserIdParam"},
+                "UserIdParam": {"name": "id", "in": "path", "required": true}
+            }}
+        });
+        let issues = validate(&spec);
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn test_reports_structural_problems() {
+        let spec = json!({
+            "paths": {
+                "/users/{id}": {
+                    "get": {
+                        "responses": {
+                            "200": {"content": {"application/json": {
+                                "schema": {"$ref": "#/components/schemas/Missing"}
+                            }}}
+                        }
+                    },
+                    "delete": {"operationId": "deleteUser"}
+                }
+            }
+        });
+        let issues = validate(&spec);
+        let find = |needle: &str| issues.iter().find(|i| i.message.contains(needle)).unwrap();
+
+        let missing_id = find("missing operationId");
+        assert_eq!(missing_id.severity, Severity::Warning);
+        assert_eq!(missing_id.pointer, "/paths/~1users~1{id}/get");
+        assert!(missing_id.message.contains("getUsersById"));
+
+        assert_eq!(find("`id` is not declared").severity, Severity::Error);
+        assert_eq!(
+            find("no `responses`").pointer,
+            "/paths/~1users~1{id}/delete"
+        );
+
+        let dangling = find("does not resolve");
+        assert_eq!(
+            dangling.pointer,
+            "/paths/~1users~1{id}/get/responses/200/content/application~1json/schema/$ref"
+        );
+    }
+}
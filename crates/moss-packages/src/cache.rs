@@ -4,7 +4,8 @@
 //! - **Package cache**: Individual package metadata (JSON)
 //! - **Index cache**: Full package indices with HTTP staleness (ETag/Last-Modified)
 
-use crate::PackageInfo;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 use std::fs;
 use std::io::Read;
 use std::path::PathBuf;
@@ -12,8 +13,8 @@ use std::time::{Duration, SystemTime};
 
 /// Cache entry with timestamp.
 #[derive(serde::Serialize, serde::Deserialize)]
-struct CacheEntry {
-    info: PackageInfo,
+struct CacheEntry<T> {
+    info: T,
     cached_at: u64, // Unix timestamp
 }
 
@@ -59,10 +60,10 @@ fn cache_path(ecosystem: &str, package: &str) -> Option<PathBuf> {
 }
 
 /// Read from cache if exists and not expired.
-pub fn read(ecosystem: &str, package: &str, max_age: Duration) -> Option<PackageInfo> {
+pub fn read<T: DeserializeOwned>(ecosystem: &str, package: &str, max_age: Duration) -> Option<T> {
     let path = cache_path(ecosystem, package)?;
     let content = fs::read_to_string(&path).ok()?;
-    let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+    let entry: CacheEntry<T> = serde_json::from_str(&content).ok()?;
 
     // Check expiry
     let now = SystemTime::now()
@@ -78,15 +79,15 @@ pub fn read(ecosystem: &str, package: &str, max_age: Duration) -> Option<Package
 }
 
 /// Read from cache regardless of age (for offline fallback).
-pub fn read_any(ecosystem: &str, package: &str) -> Option<PackageInfo> {
+pub fn read_any<T: DeserializeOwned>(ecosystem: &str, package: &str) -> Option<T> {
     let path = cache_path(ecosystem, package)?;
     let content = fs::read_to_string(&path).ok()?;
-    let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+    let entry: CacheEntry<T> = serde_json::from_str(&content).ok()?;
     Some(entry.info)
 }
 
 /// Write to cache.
-pub fn write(ecosystem: &str, package: &str, info: &PackageInfo) {
+pub fn write<T: Serialize>(ecosystem: &str, package: &str, info: &T) {
     let Some(path) = cache_path(ecosystem, package) else {
         return;
     };
@@ -102,7 +103,7 @@ pub fn write(ecosystem: &str, package: &str, info: &PackageInfo) {
         .unwrap_or(0);
 
     let entry = CacheEntry {
-        info: info.clone(),
+        info,
         cached_at: now,
     };
 
@@ -1,8 +1,9 @@
 //! Cargo (Rust) ecosystem.
 
 use crate::{
-    AuditResult, Dependency, DependencyTree, Ecosystem, Feature, LockfileManager, PackageError,
-    PackageInfo, PackageQuery, TreeNode, Vulnerability, VulnerabilitySeverity,
+    AuditResult, Dependency, DependencyKind, DependencyTree, Ecosystem, Feature, LockfileManager,
+    PackageError, PackageInfo, PackageQuery, ReleaseNote, SearchResult, TreeBudget, TreeNode,
+    TreeOptions, Vulnerability, VulnerabilitySeverity,
 };
 use std::path::Path;
 use std::process::Command;
@@ -48,6 +49,26 @@ impl Ecosystem for Cargo {
             .map(String::from)
     }
 
+    fn version_satisfies(&self, version_req: &str, version: &str) -> Option<bool> {
+        // `serde = "1.0"` means `^1.0`
+        crate::version_req::satisfies(
+            version_req,
+            version,
+            crate::version_req::BareVersion::Caret,
+            crate::version_req::Tilde::Minor,
+        )
+    }
+
+    fn package_for_import<'a>(
+        &self,
+        import: &str,
+        dependencies: &'a [Dependency],
+    ) -> Option<&'a Dependency> {
+        // `serde_json::Value` is in crate `serde_json`, published as `serde-json` or `serde_json`
+        let krate = import.split("::").next()?;
+        crate::find_normalized(dependencies, krate)
+    }
+
     fn list_dependencies(&self, project_root: &Path) -> Result<Vec<Dependency>, PackageError> {
         let manifest = project_root.join("Cargo.toml");
         let content = std::fs::read_to_string(&manifest)
@@ -60,28 +81,32 @@ impl Ecosystem for Cargo {
         // Parse [dependencies]
         if let Some(table) = parsed.get("dependencies").and_then(|d| d.as_table()) {
             for (name, value) in table {
-                deps.push(parse_cargo_dep(name, value, false));
+                deps.push(parse_cargo_dep(name, value, DependencyKind::Normal));
             }
         }
 
         // Parse [dev-dependencies]
         if let Some(table) = parsed.get("dev-dependencies").and_then(|d| d.as_table()) {
             for (name, value) in table {
-                deps.push(parse_cargo_dep(name, value, false));
+                deps.push(parse_cargo_dep(name, value, DependencyKind::Dev));
             }
         }
 
         // Parse [build-dependencies]
         if let Some(table) = parsed.get("build-dependencies").and_then(|d| d.as_table()) {
             for (name, value) in table {
-                deps.push(parse_cargo_dep(name, value, false));
+                deps.push(parse_cargo_dep(name, value, DependencyKind::Build));
             }
         }
 
         Ok(deps)
     }
 
-    fn dependency_tree(&self, project_root: &Path) -> Result<DependencyTree, PackageError> {
+    fn dependency_tree(
+        &self,
+        project_root: &Path,
+        options: &TreeOptions,
+    ) -> Result<DependencyTree, PackageError> {
         // Find Cargo.lock - may be in parent if this is a workspace member
         let (lockfile, workspace_root) = find_cargo_lock(project_root)?;
         let content = std::fs::read_to_string(&lockfile)
@@ -154,23 +179,30 @@ impl Ecosystem for Cargo {
         // Build tree structure
         let mut visited = std::collections::HashSet::new();
 
+        // Roots (the workspace's own packages) are depth 0
         fn build_node(
             name: &str,
             packages: &std::collections::HashMap<String, (String, Vec<String>)>,
             visited: &mut std::collections::HashSet<String>,
+            depth: usize,
+            budget: &mut TreeBudget,
         ) -> TreeNode {
             let (version, deps) = packages
                 .get(name)
                 .map(|(v, d)| (v.clone(), d.clone()))
                 .unwrap_or_default();
 
-            let children = if visited.contains(name) {
-                Vec::new() // Already visited, don't recurse
-            } else {
+            // Already visited packages aren't recursed into; the roots always are
+            let expand = !visited.contains(name)
+                && !deps.is_empty()
+                && (depth == 0 || budget.expand(&format!("{}@{}", name, version), depth));
+            let children = if expand {
                 visited.insert(name.to_string());
                 deps.iter()
-                    .map(|dep| build_node(dep, packages, visited))
+                    .map(|dep| build_node(dep, packages, visited, depth + 1, budget))
                     .collect()
+            } else {
+                Vec::new()
             };
 
             TreeNode {
@@ -180,12 +212,13 @@ impl Ecosystem for Cargo {
             }
         }
 
+        let mut budget = TreeBudget::new(options);
         let roots: Vec<TreeNode> = root_names
             .iter()
-            .map(|name| build_node(name, &packages, &mut visited))
+            .map(|name| build_node(name, &packages, &mut visited, 0, &mut budget))
             .collect();
 
-        Ok(DependencyTree { roots })
+        Ok(budget.finish(roots))
     }
 
     fn audit(&self, project_root: &Path) -> Result<AuditResult, PackageError> {
@@ -298,6 +331,19 @@ impl Ecosystem for Cargo {
 
         Ok(AuditResult { vulnerabilities })
     }
+
+    fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>, PackageError> {
+        search_crates_io(query, limit)
+    }
+
+    fn release_notes(
+        &self,
+        info: &PackageInfo,
+        from: Option<&str>,
+        to: &str,
+    ) -> Result<Vec<ReleaseNote>, PackageError> {
+        crate::release_notes::from_repository(info, from, to)
+    }
 }
 
 /// Find Cargo.lock, searching up from project_root to find workspace root
@@ -320,36 +366,75 @@ fn find_cargo_lock(
     )))
 }
 
-fn parse_cargo_dep(name: &str, value: &toml::Value, optional: bool) -> Dependency {
-    match value {
-        toml::Value::String(version) => Dependency {
-            name: name.to_string(),
-            version_req: Some(version.clone()),
-            optional,
-        },
-        toml::Value::Table(table) => {
-            let version = table
+/// Parse a manifest dependency entry. An optional dependency of the `Normal`
+/// section is `Optional`; dev and build dependencies keep their kind.
+fn parse_cargo_dep(name: &str, value: &toml::Value, kind: DependencyKind) -> Dependency {
+    let (version_req, optional) = match value {
+        toml::Value::String(version) => (Some(version.clone()), false),
+        toml::Value::Table(table) => (
+            table
                 .get("version")
                 .and_then(|v| v.as_str())
-                .map(String::from);
-            let opt = table
+                .map(String::from),
+            table
                 .get("optional")
                 .and_then(|o| o.as_bool())
-                .unwrap_or(optional);
-            Dependency {
-                name: name.to_string(),
-                version_req: version,
-                optional: opt,
-            }
-        }
-        _ => Dependency {
-            name: name.to_string(),
-            version_req: None,
-            optional,
+                .unwrap_or(false),
+        ),
+        _ => (None, false),
+    };
+    Dependency {
+        name: name.to_string(),
+        version_req,
+        optional,
+        kind: if optional && kind == DependencyKind::Normal {
+            DependencyKind::Optional
+        } else {
+            kind
         },
     }
 }
 
+fn search_crates_io(query: &str, limit: usize) -> Result<Vec<SearchResult>, PackageError> {
+    // crates.io caps per_page at 100
+    let per_page = limit.min(100).to_string();
+    let body = crate::http::get_with_query(
+        "https://crates.io/api/v1/crates",
+        &[("q", query), ("per_page", &per_page)],
+        &[("User-Agent", "moss-packages")],
+    )?;
+    parse_crates_io_search(&body, limit)
+}
+
+fn parse_crates_io_search(body: &str, limit: usize) -> Result<Vec<SearchResult>, PackageError> {
+    let v: serde_json::Value = serde_json::from_str(body)
+        .map_err(|e| PackageError::ParseError(format!("invalid JSON: {}", e)))?;
+    let crates = v
+        .get("crates")
+        .and_then(|c| c.as_array())
+        .ok_or_else(|| PackageError::ParseError("missing crates".to_string()))?;
+
+    Ok(crates
+        .iter()
+        .filter_map(|c| {
+            Some(SearchResult {
+                name: c.get("name")?.as_str()?.to_string(),
+                version: c
+                    .get("max_stable_version")
+                    .and_then(|v| v.as_str())
+                    .or_else(|| c.get("max_version").and_then(|v| v.as_str()))
+                    .map(String::from),
+                description: c
+                    .get("description")
+                    .and_then(|d| d.as_str())
+                    .map(|d| d.trim().to_string()),
+                downloads: c.get("downloads").and_then(|d| d.as_u64()),
+            })
+        })
+        .take(limit)
+        .collect())
+}
+
 fn fetch_crates_io_info(query: &PackageQuery) -> Result<PackageInfo, PackageError> {
     let package = &query.name;
 
@@ -376,7 +461,23 @@ fn fetch_crates_io_info(query: &PackageQuery) -> Result<PackageInfo, PackageErro
     // Get version-specific info
     let version_url = format!("https://crates.io/api/v1/crates/{}/{}", package, version);
     let body = crate::http::get_with_headers(&version_url, headers)?;
-    let v: serde_json::Value = serde_json::from_str(&body)
+    let mut info = parse_crates_io_version(&body, package, &version)?;
+
+    // Crate-level info (description, homepage, repository, downloads)
+    let crate_url = format!("https://crates.io/api/v1/crates/{}", package);
+    if let Ok(body) = crate::http::get_with_headers(&crate_url, headers) {
+        add_crates_io_crate_info(&mut info, &body);
+    }
+    Ok(info)
+}
+
+/// Info from the crates.io version endpoint. Crate-level fields are left empty.
+fn parse_crates_io_version(
+    body: &str,
+    package: &str,
+    version: &str,
+) -> Result<PackageInfo, PackageError> {
+    let v: serde_json::Value = serde_json::from_str(body)
         .map_err(|e| PackageError::ParseError(format!("invalid JSON: {}", e)))?;
 
     let ver = v
@@ -392,7 +493,7 @@ fn fetch_crates_io_info(query: &PackageQuery) -> Result<PackageInfo, PackageErro
     let version = ver
         .get("num")
         .and_then(|n| n.as_str())
-        .unwrap_or(&version)
+        .unwrap_or(version)
         .to_string();
 
     let license = ver
@@ -421,45 +522,47 @@ fn fetch_crates_io_info(query: &PackageQuery) -> Result<PackageInfo, PackageErro
         })
         .unwrap_or_default();
 
-    // Get crate-level info (description, homepage, repository)
-    let crate_url = format!("https://crates.io/api/v1/crates/{}", package);
-    let (description, homepage, repository) = if let Ok(body) =
-        crate::http::get_with_headers(&crate_url, headers)
-        && let Ok(cv) = serde_json::from_str::<serde_json::Value>(&body)
-    {
-        let crate_info = cv.get("crate");
-        (
-            crate_info
-                .and_then(|c| c.get("description"))
-                .and_then(|d| d.as_str())
-                .map(String::from),
-            crate_info
-                .and_then(|c| c.get("homepage"))
-                .and_then(|h| h.as_str())
-                .filter(|s| !s.is_empty())
-                .map(String::from),
-            crate_info
-                .and_then(|c| c.get("repository"))
-                .and_then(|r| r.as_str())
-                .filter(|s| !s.is_empty())
-                .map(String::from),
-        )
-    } else {
-        (None, None, None)
-    };
+    let published = ver
+        .get("created_at")
+        .and_then(|c| c.as_str())
+        .map(String::from);
 
     Ok(PackageInfo {
         name,
         version,
-        description,
+        description: None,
         license,
-        homepage,
-        repository,
+        homepage: None,
+        repository: None,
         features,
         dependencies: Vec::new(),
+        downloads: None,
+        published,
     })
 }
 
+/// Fill in description, homepage, repository and downloads from the crates.io
+/// crate endpoint. Unparseable responses leave `info` as it is.
+fn add_crates_io_crate_info(info: &mut PackageInfo, body: &str) {
+    let Ok(v) = serde_json::from_str::<serde_json::Value>(body) else {
+        return;
+    };
+    let Some(crate_info) = v.get("crate") else {
+        return;
+    };
+    let text = |key: &str| {
+        crate_info
+            .get(key)
+            .and_then(|s| s.as_str())
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+    };
+    info.description = text("description");
+    info.homepage = text("homepage");
+    info.repository = text("repository");
+    info.downloads = crate_info.get("downloads").and_then(|d| d.as_u64());
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -470,4 +573,91 @@ mod tests {
         assert_eq!(eco.name(), "cargo");
         assert_eq!(eco.manifest_files(), &["Cargo.toml"]);
     }
+
+    #[test]
+    fn test_parse_crates_io_search() {
+        let json = r#"{
+            "crates": [
+                {"name": "serde", "max_version": "1.0.1-rc", "max_stable_version": "1.0.0",
+                 "description": "A serialization framework\n", "downloads": 500},
+                {"name": "serde_json", "max_version": "1.0.2", "description": null, "downloads": 300},
+                {"name": "serde_yaml", "max_version": "0.9.0"}
+            ]
+        }"#;
+        let results = parse_crates_io_search(json, 2).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].name, "serde");
+        assert_eq!(results[0].version.as_deref(), Some("1.0.0"));
+        assert_eq!(
+            results[0].description.as_deref(),
+            Some("A serialization framework")
+        );
+        assert_eq!(results[0].downloads, Some(500));
+        assert_eq!(results[1].version.as_deref(), Some("1.0.2"));
+        assert_eq!(results[1].description, None);
+    }
+
+    #[test]
+    fn test_parse_crates_io_info() {
+        let version = r#"{
+            "version": {
+                "crate": "serde",
+                "num": "1.0.200",
+                "license": "MIT OR Apache-2.0",
+                "created_at": "2024-05-01T12:00:00.000000+00:00",
+                "features": {"default": ["std"], "std": []}
+            }
+        }"#;
+        let mut info = parse_crates_io_version(version, "serde", "1.0.200").unwrap();
+        assert_eq!(info.name, "serde");
+        assert_eq!(info.version, "1.0.200");
+        assert_eq!(info.license.as_deref(), Some("MIT OR Apache-2.0"));
+        assert_eq!(
+            info.published.as_deref(),
+            Some("2024-05-01T12:00:00.000000+00:00")
+        );
+        assert_eq!(info.features.len(), 2);
+
+        let krate = r#"{
+            "crate": {
+                "description": "A serialization framework",
+                "homepage": "",
+                "repository": "https://github.com/serde-rs/serde",
+                "downloads": 123456
+            }
+        }"#;
+        add_crates_io_crate_info(&mut info, krate);
+        assert_eq!(
+            info.description.as_deref(),
+            Some("A serialization framework")
+        );
+        assert_eq!(info.homepage, None);
+        assert_eq!(
+            info.repository.as_deref(),
+            Some("https://github.com/serde-rs/serde")
+        );
+        assert_eq!(info.downloads, Some(123456));
+    }
+
+    #[test]
+    fn test_parse_cargo_dep_kind() {
+        let manifest: toml::Value = toml::from_str(
+            r#"
+            serde = "1"
+            tokio = { version = "1", optional = true }
+            "#,
+        )
+        .unwrap();
+        let serde = parse_cargo_dep("serde", &manifest["serde"], DependencyKind::Normal);
+        assert_eq!(serde.kind, DependencyKind::Normal);
+        assert_eq!(serde.version_req.as_deref(), Some("1"));
+
+        let tokio = parse_cargo_dep("tokio", &manifest["tokio"], DependencyKind::Normal);
+        assert_eq!(tokio.kind, DependencyKind::Optional);
+        assert!(tokio.optional);
+
+        // Optional only matters for normal dependencies
+        let tokio = parse_cargo_dep("tokio", &manifest["tokio"], DependencyKind::Dev);
+        assert_eq!(tokio.kind, DependencyKind::Dev);
+    }
 }
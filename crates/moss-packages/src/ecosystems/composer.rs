@@ -1,8 +1,8 @@
 //! Composer (PHP) ecosystem.
 
 use crate::{
-    AuditResult, Dependency, DependencyTree, Ecosystem, LockfileManager, PackageError, PackageInfo,
-    PackageQuery, TreeNode,
+    AuditResult, Dependency, DependencyKind, DependencyTree, Ecosystem, LockfileManager,
+    PackageError, PackageInfo, PackageQuery, ReleaseNote, SearchResult, TreeNode, TreeOptions,
 };
 use std::path::Path;
 
@@ -53,6 +53,51 @@ impl Ecosystem for Composer {
         None
     }
 
+    fn version_satisfies(&self, version_req: &str, version: &str) -> Option<bool> {
+        crate::version_req::satisfies(
+            version_req,
+            version,
+            crate::version_req::BareVersion::Exact,
+            crate::version_req::Tilde::AllButLast,
+        )
+    }
+
+    fn package_for_import<'a>(
+        &self,
+        import: &str,
+        dependencies: &'a [Dependency],
+    ) -> Option<&'a Dependency> {
+        // `Symfony\Component\Console\Command` is in `symfony/console`: the vendor is the
+        // root namespace, the package one of the namespaces below it
+        let mut namespaces = import
+            .trim_start_matches('\\')
+            .split('\\')
+            .map(|ns| ns.to_lowercase());
+        let vendor = namespaces.next()?;
+        let namespaces: Vec<String> = namespaces.collect();
+        let candidates: Vec<&Dependency> = dependencies
+            .iter()
+            .filter(|dep| {
+                dep.name
+                    .split_once('/')
+                    .is_some_and(|(v, _)| v.replace('-', "") == vendor)
+            })
+            .collect();
+        let by_package = candidates.iter().copied().find(|dep| {
+            let package = dep
+                .name
+                .split_once('/')
+                .map_or("", |(_, p)| p)
+                .replace('-', "");
+            package == vendor || namespaces.contains(&package)
+        });
+        match (by_package, candidates.as_slice()) {
+            (Some(dep), _) => Some(dep),
+            (None, [only]) => Some(*only),
+            _ => None,
+        }
+    }
+
     fn list_dependencies(&self, project_root: &Path) -> Result<Vec<Dependency>, PackageError> {
         let manifest = project_root.join("composer.json");
         let content = std::fs::read_to_string(&manifest).map_err(|e| {
@@ -72,6 +117,7 @@ impl Ecosystem for Composer {
                     name: name.clone(),
                     version_req: version.as_str().map(String::from),
                     optional: false,
+                    kind: DependencyKind::Normal,
                 });
             }
         }
@@ -82,6 +128,7 @@ impl Ecosystem for Composer {
                     name: name.clone(),
                     version_req: version.as_str().map(String::from),
                     optional: false,
+                    kind: DependencyKind::Dev,
                 });
             }
         }
@@ -89,7 +136,13 @@ impl Ecosystem for Composer {
         Ok(deps)
     }
 
-    fn dependency_tree(&self, project_root: &Path) -> Result<DependencyTree, PackageError> {
+    fn dependency_tree(
+        &self,
+        project_root: &Path,
+        _options: &TreeOptions,
+    ) -> Result<DependencyTree, PackageError> {
+        // The lockfile lists packages without their dependencies, so every package
+        // is a leaf under the root and there is nothing for `options` to bound
         // Parse composer.lock for full dependency list
         let lockfile = project_root.join("composer.lock");
         let content = std::fs::read_to_string(&lockfile).map_err(|e| {
@@ -114,13 +167,11 @@ impl Ecosystem for Composer {
             }
         }
 
-        Ok(DependencyTree {
-            roots: vec![TreeNode {
-                name: "composer.lock".to_string(),
-                version: String::new(),
-                dependencies: deps,
-            }],
-        })
+        Ok(DependencyTree::new(vec![TreeNode {
+            name: "composer.lock".to_string(),
+            version: String::new(),
+            dependencies: deps,
+        }]))
     }
 
     fn audit(&self, _project_root: &Path) -> Result<AuditResult, PackageError> {
@@ -129,6 +180,19 @@ impl Ecosystem for Composer {
             "audit not yet supported for Composer. Use: composer audit".to_string(),
         ))
     }
+
+    fn search(&self, _query: &str, _limit: usize) -> Result<Vec<SearchResult>, PackageError> {
+        Err(PackageError::NotFound(format!("{} search", self.name())))
+    }
+
+    fn release_notes(
+        &self,
+        info: &PackageInfo,
+        from: Option<&str>,
+        to: &str,
+    ) -> Result<Vec<ReleaseNote>, PackageError> {
+        crate::release_notes::from_repository(info, from, to)
+    }
 }
 
 fn fetch_packagist_info(package: &str) -> Result<PackageInfo, PackageError> {
@@ -160,7 +224,12 @@ fn fetch_packagist_info(package: &str) -> Result<PackageInfo, PackageError> {
     // Get latest version from versions object
     let versions = pkg.get("versions").and_then(|v| v.as_object());
 
-    let (version, license, dependencies) = if let Some(vers) = versions {
+    let downloads = pkg
+        .get("downloads")
+        .and_then(|d| d.get("total"))
+        .and_then(|d| d.as_u64());
+
+    let (version, license, dependencies, published) = if let Some(vers) = versions {
         // Find latest non-dev version
         let latest = vers
             .iter()
@@ -190,6 +259,7 @@ fn fetch_packagist_info(package: &str) -> Result<PackageInfo, PackageError> {
                         name: dep_name.clone(),
                         version_req: ver_req.as_str().map(String::from),
                         optional: false,
+                        kind: DependencyKind::Normal,
                     });
                 }
             }
@@ -199,16 +269,19 @@ fn fetch_packagist_info(package: &str) -> Result<PackageInfo, PackageError> {
                         name: dep_name.clone(),
                         version_req: ver_req.as_str().map(String::from),
                         optional: true,
+                        kind: DependencyKind::Dev,
                     });
                 }
             }
 
-            (ver.clone(), lic, deps)
+            let time = data.get("time").and_then(|t| t.as_str()).map(String::from);
+
+            (ver.clone(), lic, deps, time)
         } else {
-            (String::new(), None, Vec::new())
+            (String::new(), None, Vec::new(), None)
         }
     } else {
-        (String::new(), None, Vec::new())
+        (String::new(), None, Vec::new(), None)
     };
 
     if version.is_empty() {
@@ -224,5 +297,7 @@ fn fetch_packagist_info(package: &str) -> Result<PackageInfo, PackageError> {
         repository,
         features: Vec::new(),
         dependencies,
+        downloads,
+        published,
     })
 }
@@ -1,8 +1,8 @@
 //! Conan (C++) ecosystem.
 
 use crate::{
-    AuditResult, Dependency, DependencyTree, Ecosystem, LockfileManager, PackageError, PackageInfo,
-    PackageQuery, TreeNode,
+    AuditResult, Dependency, DependencyKind, DependencyTree, Ecosystem, LockfileManager,
+    PackageError, PackageInfo, PackageQuery, ReleaseNote, SearchResult, TreeNode, TreeOptions,
 };
 use std::path::Path;
 
@@ -52,26 +52,53 @@ impl Ecosystem for Conan {
         None
     }
 
+    fn version_satisfies(&self, version_req: &str, version: &str) -> Option<bool> {
+        crate::version_req::satisfies(
+            version_req,
+            version,
+            crate::version_req::BareVersion::Exact,
+            crate::version_req::Tilde::Minor,
+        )
+    }
+
+    fn package_for_import<'a>(
+        &self,
+        import: &str,
+        dependencies: &'a [Dependency],
+    ) -> Option<&'a Dependency> {
+        // `#include <fmt/core.h>` is in `fmt`, `<zlib.h>` in `zlib`
+        let first = import.trim_matches(['<', '>', '"']).split('/').next()?;
+        let name = first.split('.').next()?;
+        dependencies
+            .iter()
+            .find(|dep| dep.name.eq_ignore_ascii_case(name))
+    }
+
     fn list_dependencies(&self, project_root: &Path) -> Result<Vec<Dependency>, PackageError> {
         // Parse conanfile.txt
         let conanfile_txt = project_root.join("conanfile.txt");
         if let Ok(content) = std::fs::read_to_string(&conanfile_txt) {
             let mut deps = Vec::new();
-            let mut in_requires = false;
+            // Kind of the current requirements section, None outside them
+            let mut section: Option<DependencyKind> = None;
 
             for line in content.lines() {
                 let line = line.trim();
 
-                if line == "[requires]" {
-                    in_requires = true;
-                    continue;
-                }
                 if line.starts_with('[') {
-                    in_requires = false;
+                    section = match line {
+                        "[requires]" => Some(DependencyKind::Normal),
+                        "[tool_requires]" | "[build_requires]" => Some(DependencyKind::Build),
+                        "[test_requires]" => Some(DependencyKind::Dev),
+                        _ => None,
+                    };
                     continue;
                 }
 
-                if in_requires && !line.is_empty() {
+                if let Some(kind) = section
+                    && !line.is_empty()
+                    && !line.starts_with('#')
+                {
                     // Format: pkg/version or pkg/version@user/channel
                     if let Some((name, rest)) = line.split_once('/') {
                         let version = rest.split('@').next().unwrap_or(rest);
@@ -79,6 +106,7 @@ impl Ecosystem for Conan {
                             name: name.to_string(),
                             version_req: Some(version.to_string()),
                             optional: false,
+                            kind,
                         });
                     }
                 }
@@ -94,7 +122,14 @@ impl Ecosystem for Conan {
 
             // Look for requires = ["pkg/version", ...]
             for line in content.lines() {
-                if line.contains("requires") || line.contains("self.requires(") {
+                if line.contains("requires") {
+                    let kind = if line.contains("test_requires") {
+                        DependencyKind::Dev
+                    } else if line.contains("tool_requires") || line.contains("build_requires") {
+                        DependencyKind::Build
+                    } else {
+                        DependencyKind::Normal
+                    };
                     // Extract quoted strings
                     let mut pos = 0;
                     while let Some(start) = line[pos..].find('"') {
@@ -107,6 +142,7 @@ impl Ecosystem for Conan {
                                     name: name.to_string(),
                                     version_req: Some(version.to_string()),
                                     optional: false,
+                                    kind,
                                 });
                             }
                             pos = pos + start + 2 + end;
@@ -123,7 +159,13 @@ impl Ecosystem for Conan {
         Err(PackageError::ParseError("no conanfile found".to_string()))
     }
 
-    fn dependency_tree(&self, project_root: &Path) -> Result<DependencyTree, PackageError> {
+    fn dependency_tree(
+        &self,
+        project_root: &Path,
+        _options: &TreeOptions,
+    ) -> Result<DependencyTree, PackageError> {
+        // The lockfile lists packages without their dependencies, so every package
+        // is a leaf under the root and there is nothing for `options` to bound
         // Parse conan.lock
         let lockfile = project_root.join("conan.lock");
         let content = std::fs::read_to_string(&lockfile)
@@ -156,13 +198,11 @@ impl Ecosystem for Conan {
             }
         }
 
-        Ok(DependencyTree {
-            roots: vec![TreeNode {
-                name: "conan.lock".to_string(),
-                version: String::new(),
-                dependencies: deps,
-            }],
-        })
+        Ok(DependencyTree::new(vec![TreeNode {
+            name: "conan.lock".to_string(),
+            version: String::new(),
+            dependencies: deps,
+        }]))
     }
 
     fn audit(&self, _project_root: &Path) -> Result<AuditResult, PackageError> {
@@ -170,6 +210,19 @@ impl Ecosystem for Conan {
             "audit not yet supported for Conan".to_string(),
         ))
     }
+
+    fn search(&self, _query: &str, _limit: usize) -> Result<Vec<SearchResult>, PackageError> {
+        Err(PackageError::NotFound(format!("{} search", self.name())))
+    }
+
+    fn release_notes(
+        &self,
+        info: &PackageInfo,
+        from: Option<&str>,
+        to: &str,
+    ) -> Result<Vec<ReleaseNote>, PackageError> {
+        crate::release_notes::from_repository(info, from, to)
+    }
 }
 
 fn fetch_conancenter_api(package: &str) -> Result<PackageInfo, PackageError> {
@@ -212,5 +265,7 @@ fn fetch_conancenter_api(package: &str) -> Result<PackageInfo, PackageError> {
         )),
         features: Vec::new(),
         dependencies: Vec::new(),
+        downloads: None,
+        published: None,
     })
 }
@@ -1,8 +1,8 @@
 //! Deno ecosystem (deno.json, jsr/npm/url imports, deno.lock)
 
 use crate::{
-    AuditResult, Dependency, DependencyTree, Ecosystem, LockfileManager, PackageError, PackageInfo,
-    PackageQuery, TreeNode,
+    AuditResult, Dependency, DependencyKind, DependencyTree, Ecosystem, LockfileManager,
+    PackageError, PackageInfo, PackageQuery, ReleaseNote, SearchResult, TreeNode, TreeOptions,
 };
 use std::path::Path;
 
@@ -61,6 +61,43 @@ impl Ecosystem for Deno {
         None
     }
 
+    fn version_satisfies(&self, version_req: &str, version: &str) -> Option<bool> {
+        crate::version_req::satisfies(
+            version_req,
+            version,
+            crate::version_req::BareVersion::Exact,
+            crate::version_req::Tilde::Minor,
+        )
+    }
+
+    fn package_for_import<'a>(
+        &self,
+        import: &str,
+        dependencies: &'a [Dependency],
+    ) -> Option<&'a Dependency> {
+        // Import map keys: `oak`, or prefixes ending in `/` (`std/` for `std/fs/mod.ts`)
+        let mapped = dependencies
+            .iter()
+            .filter(|dep| {
+                import == dep.name || (dep.name.ends_with('/') && import.starts_with(&dep.name))
+            })
+            .max_by_key(|dep| dep.name.len());
+        if mapped.is_some() {
+            return mapped;
+        }
+        // `npm:lodash@4/fp`, `jsr:@std/path@1/join`: the package without its version
+        let bare = import
+            .strip_prefix("npm:")
+            .or_else(|| import.strip_prefix("jsr:"))
+            .unwrap_or(import);
+        let name = super::npm::package_name(bare)?;
+        let name = match name[1..].rfind('@') {
+            Some(at) => &name[..at + 1],
+            None => name,
+        };
+        dependencies.iter().find(|dep| dep.name == name)
+    }
+
     fn list_dependencies(&self, project_root: &Path) -> Result<Vec<Dependency>, PackageError> {
         // Try deno.json first
         let manifest = project_root.join("deno.json");
@@ -90,6 +127,7 @@ impl Ecosystem for Deno {
                     name: name.clone(),
                     version_req: version,
                     optional: false,
+                    kind: DependencyKind::Normal,
                 });
             }
         }
@@ -97,7 +135,13 @@ impl Ecosystem for Deno {
         Ok(deps)
     }
 
-    fn dependency_tree(&self, project_root: &Path) -> Result<DependencyTree, PackageError> {
+    fn dependency_tree(
+        &self,
+        project_root: &Path,
+        _options: &TreeOptions,
+    ) -> Result<DependencyTree, PackageError> {
+        // The lockfile lists packages without their dependencies, so every package
+        // is a leaf under the root and there is nothing for `options` to bound
         let lockfile = project_root.join("deno.lock");
         if !lockfile.exists() {
             return Err(PackageError::ParseError("deno.lock not found".to_string()));
@@ -118,6 +162,19 @@ impl Ecosystem for Deno {
             vulnerabilities: Vec::new(),
         })
     }
+
+    fn search(&self, _query: &str, _limit: usize) -> Result<Vec<SearchResult>, PackageError> {
+        Err(PackageError::NotFound(format!("{} search", self.name())))
+    }
+
+    fn release_notes(
+        &self,
+        info: &PackageInfo,
+        from: Option<&str>,
+        to: &str,
+    ) -> Result<Vec<ReleaseNote>, PackageError> {
+        crate::release_notes::from_repository(info, from, to)
+    }
 }
 
 fn fetch_jsr_info(package: &str, version: Option<&str>) -> Result<PackageInfo, PackageError> {
@@ -160,6 +217,8 @@ fn fetch_jsr_info(package: &str, version: Option<&str>) -> Result<PackageInfo, P
         repository: None,
         features: Vec::new(),
         dependencies: Vec::new(),
+        downloads: None,
+        published: None,
     })
 }
 
@@ -326,7 +385,7 @@ fn build_tree(
         dependencies: root_deps,
     };
 
-    Ok(DependencyTree { roots: vec![root] })
+    Ok(DependencyTree::new(vec![root]))
 }
 
 /// Parse deno.land URL into (name, version)
@@ -1,8 +1,9 @@
 //! RubyGems ecosystem.
 
 use crate::{
-    AuditResult, Dependency, DependencyTree, Ecosystem, LockfileManager, PackageError, PackageInfo,
-    PackageQuery, TreeNode, Vulnerability, VulnerabilitySeverity,
+    AuditResult, Dependency, DependencyKind, DependencyTree, Ecosystem, LockfileManager,
+    PackageError, PackageInfo, PackageQuery, ReleaseNote, SearchResult, TreeNode, TreeOptions,
+    Vulnerability, VulnerabilitySeverity,
 };
 use std::path::Path;
 use std::process::Command;
@@ -54,6 +55,33 @@ impl Ecosystem for Gem {
         None
     }
 
+    fn version_satisfies(&self, version_req: &str, version: &str) -> Option<bool> {
+        // `~>` (pessimistic) requirements aren't understood and only checked for presence
+        crate::version_req::satisfies(
+            version_req,
+            version,
+            crate::version_req::BareVersion::Exact,
+            crate::version_req::Tilde::Minor,
+        )
+    }
+
+    fn package_for_import<'a>(
+        &self,
+        import: &str,
+        dependencies: &'a [Dependency],
+    ) -> Option<&'a Dependency> {
+        // `require "rspec/core"` loads gem `rspec-core`, `active_support/...` gem
+        // `activesupport`: try the longest leading segments first
+        let squash = |name: &str| name.replace(['-', '_'], "").to_lowercase();
+        let segments: Vec<&str> = import.split('/').collect();
+        (1..=segments.len()).rev().find_map(|n| {
+            let name = segments[..n].join("-");
+            dependencies
+                .iter()
+                .find(|dep| dep.name == name || squash(&dep.name) == squash(&name))
+        })
+    }
+
     fn list_dependencies(&self, project_root: &Path) -> Result<Vec<Dependency>, PackageError> {
         // Parse Gemfile (Ruby DSL, but we can extract simple patterns)
         let gemfile = project_root.join("Gemfile");
@@ -61,6 +89,8 @@ impl Ecosystem for Gem {
             .map_err(|e| PackageError::ParseError(format!("failed to read Gemfile: {}", e)))?;
 
         let mut deps = Vec::new();
+        // Open `do` blocks, and whether each is a development/test group
+        let mut blocks: Vec<bool> = Vec::new();
 
         for line in content.lines() {
             let line = line.trim();
@@ -68,6 +98,20 @@ impl Ecosystem for Gem {
                 continue;
             }
 
+            if line == "end" {
+                blocks.pop();
+                continue;
+            }
+            let opens_block = line.ends_with(" do")
+                || line.contains(" do |")
+                || line.starts_with("if ")
+                || line.starts_with("unless ");
+            if opens_block {
+                let dev = line.starts_with("group ") && is_dev_group(line);
+                blocks.push(dev || blocks.last().copied().unwrap_or(false));
+                continue;
+            }
+
             // gem 'name' or gem 'name', 'version' or gem "name", "~> 1.0"
             if line.starts_with("gem ") {
                 let rest = &line[4..];
@@ -85,10 +129,19 @@ impl Ecosystem for Gem {
                         } else {
                             None
                         };
+                        let in_dev_block = blocks.last().copied().unwrap_or(false);
+                        let kind = if in_dev_block
+                            || (line.contains("group") && is_dev_group(after_name))
+                        {
+                            DependencyKind::Dev
+                        } else {
+                            DependencyKind::Normal
+                        };
                         deps.push(Dependency {
                             name,
                             version_req,
                             optional: false,
+                            kind,
                         });
                     }
                 }
@@ -98,7 +151,13 @@ impl Ecosystem for Gem {
         Ok(deps)
     }
 
-    fn dependency_tree(&self, project_root: &Path) -> Result<DependencyTree, PackageError> {
+    fn dependency_tree(
+        &self,
+        project_root: &Path,
+        _options: &TreeOptions,
+    ) -> Result<DependencyTree, PackageError> {
+        // The lockfile lists packages without their dependencies, so every package
+        // is a leaf under the root and there is nothing for `options` to bound
         // Parse Gemfile.lock for all gems
         let lockfile = project_root.join("Gemfile.lock");
         let content = std::fs::read_to_string(&lockfile)
@@ -133,13 +192,11 @@ impl Ecosystem for Gem {
             }
         }
 
-        Ok(DependencyTree {
-            roots: vec![TreeNode {
-                name: "Gemfile.lock".to_string(),
-                version: String::new(),
-                dependencies: deps,
-            }],
-        })
+        Ok(DependencyTree::new(vec![TreeNode {
+            name: "Gemfile.lock".to_string(),
+            version: String::new(),
+            dependencies: deps,
+        }]))
     }
 
     fn audit(&self, project_root: &Path) -> Result<AuditResult, PackageError> {
@@ -237,6 +294,30 @@ impl Ecosystem for Gem {
 
         Ok(AuditResult { vulnerabilities })
     }
+
+    fn search(&self, _query: &str, _limit: usize) -> Result<Vec<SearchResult>, PackageError> {
+        Err(PackageError::NotFound(format!("{} search", self.name())))
+    }
+
+    fn release_notes(
+        &self,
+        info: &PackageInfo,
+        from: Option<&str>,
+        to: &str,
+    ) -> Result<Vec<ReleaseNote>, PackageError> {
+        crate::release_notes::from_repository(info, from, to)
+    }
+}
+
+/// Whether a Gemfile `group` line or `group:`/`groups:` option names only the
+/// development and test groups.
+fn is_dev_group(text: &str) -> bool {
+    let groups: Vec<&str> = text
+        .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+        .filter_map(|word| word.strip_prefix(':'))
+        .filter(|g| !g.is_empty())
+        .collect();
+    !groups.is_empty() && groups.iter().all(|g| matches!(*g, "development" | "test"))
 }
 
 fn fetch_rubygems_info(package: &str) -> Result<PackageInfo, PackageError> {
@@ -291,6 +372,7 @@ fn fetch_rubygems_info(package: &str) -> Result<PackageInfo, PackageError> {
                         name: dep_name.to_string(),
                         version_req,
                         optional: false,
+                        kind: DependencyKind::Normal,
                     });
                 }
             }
@@ -307,12 +389,19 @@ fn fetch_rubygems_info(package: &str) -> Result<PackageInfo, PackageError> {
                         name: dep_name.to_string(),
                         version_req,
                         optional: true,
+                        kind: DependencyKind::Dev,
                     });
                 }
             }
         }
     }
 
+    let downloads = v.get("downloads").and_then(|d| d.as_u64());
+    let published = v
+        .get("version_created_at")
+        .and_then(|t| t.as_str())
+        .map(String::from);
+
     Ok(PackageInfo {
         name,
         version,
@@ -322,5 +411,7 @@ fn fetch_rubygems_info(package: &str) -> Result<PackageInfo, PackageError> {
         repository,
         features: Vec::new(),
         dependencies,
+        downloads,
+        published,
     })
 }
@@ -1,8 +1,9 @@
 //! Go modules ecosystem.
 
 use crate::{
-    AuditResult, Dependency, DependencyTree, Ecosystem, LockfileManager, PackageError, PackageInfo,
-    PackageQuery, TreeNode, Vulnerability, VulnerabilitySeverity,
+    AuditResult, Dependency, DependencyKind, DependencyTree, Ecosystem, LockfileManager,
+    PackageError, PackageInfo, PackageQuery, ReleaseNote, SearchResult, TreeNode, TreeOptions,
+    Vulnerability, VulnerabilitySeverity,
 };
 use std::path::Path;
 use std::process::Command;
@@ -51,6 +52,25 @@ impl Ecosystem for Go {
         None
     }
 
+    fn version_satisfies(&self, version_req: &str, version: &str) -> Option<bool> {
+        // go.mod requirements are minimum versions (minimal version selection)
+        crate::version_req::satisfies(
+            &format!(">={}", version_req),
+            version,
+            crate::version_req::BareVersion::Exact,
+            crate::version_req::Tilde::Minor,
+        )
+    }
+
+    fn package_for_import<'a>(
+        &self,
+        import: &str,
+        dependencies: &'a [Dependency],
+    ) -> Option<&'a Dependency> {
+        // Packages live under their module path: `github.com/spf13/cobra/doc`
+        crate::find_longest_prefix(dependencies, import, '/')
+    }
+
     fn list_dependencies(&self, project_root: &Path) -> Result<Vec<Dependency>, PackageError> {
         let go_mod = project_root.join("go.mod");
         let content = std::fs::read_to_string(&go_mod)
@@ -79,6 +99,7 @@ impl Ecosystem for Go {
                         name: module.to_string(),
                         version_req: Some(version.to_string()),
                         optional: false,
+                        kind: DependencyKind::Normal,
                     });
                 }
             } else if in_require_block && !line.is_empty() && !line.starts_with("//") {
@@ -91,6 +112,7 @@ impl Ecosystem for Go {
                         name: parts[0].to_string(),
                         version_req: Some(parts[1].to_string()),
                         optional: indirect,
+                        kind: DependencyKind::Normal,
                     });
                 }
             }
@@ -99,7 +121,13 @@ impl Ecosystem for Go {
         Ok(deps)
     }
 
-    fn dependency_tree(&self, project_root: &Path) -> Result<DependencyTree, PackageError> {
+    fn dependency_tree(
+        &self,
+        project_root: &Path,
+        _options: &TreeOptions,
+    ) -> Result<DependencyTree, PackageError> {
+        // The lockfile lists packages without their dependencies, so every package
+        // is a leaf under the root and there is nothing for `options` to bound
         // go.sum has all dependencies but not the tree structure
         // go.mod has direct deps, go.sum has transitive
         let go_sum = project_root.join("go.sum");
@@ -141,13 +169,11 @@ impl Ecosystem for Go {
             }
         }
 
-        Ok(DependencyTree {
-            roots: vec![TreeNode {
-                name: root_name,
-                version: String::new(),
-                dependencies: deps,
-            }],
-        })
+        Ok(DependencyTree::new(vec![TreeNode {
+            name: root_name,
+            version: String::new(),
+            dependencies: deps,
+        }]))
     }
 
     fn audit(&self, project_root: &Path) -> Result<AuditResult, PackageError> {
@@ -206,6 +232,19 @@ impl Ecosystem for Go {
 
         Ok(AuditResult { vulnerabilities })
     }
+
+    fn search(&self, _query: &str, _limit: usize) -> Result<Vec<SearchResult>, PackageError> {
+        Err(PackageError::NotFound(format!("{} search", self.name())))
+    }
+
+    fn release_notes(
+        &self,
+        info: &PackageInfo,
+        from: Option<&str>,
+        to: &str,
+    ) -> Result<Vec<ReleaseNote>, PackageError> {
+        crate::release_notes::from_repository(info, from, to)
+    }
 }
 
 fn fetch_go_proxy_info(package: &str) -> Result<PackageInfo, PackageError> {
@@ -242,6 +281,8 @@ fn fetch_go_proxy_info(package: &str) -> Result<PackageInfo, PackageError> {
         repository,
         features: Vec::new(),
         dependencies: Vec::new(), // Would need to parse go.mod
+        downloads: None,
+        published: v.get("Time").and_then(|t| t.as_str()).map(String::from),
     })
 }
 
@@ -255,4 +296,11 @@ mod tests {
         assert_eq!(eco.name(), "go");
         assert_eq!(eco.manifest_files(), &["go.mod"]);
     }
+
+    #[test]
+    fn test_version_satisfies_minimum() {
+        assert_eq!(Go.version_satisfies("v1.2.3", "v1.2.3"), Some(true));
+        assert_eq!(Go.version_satisfies("v1.2.3", "v1.4.0"), Some(true));
+        assert_eq!(Go.version_satisfies("v1.2.3", "v1.2.0"), Some(false));
+    }
 }
@@ -1,8 +1,8 @@
 //! Hex (Elixir/Erlang) ecosystem.
 
 use crate::{
-    AuditResult, Dependency, DependencyTree, Ecosystem, LockfileManager, PackageError, PackageInfo,
-    PackageQuery, TreeNode,
+    AuditResult, Dependency, DependencyKind, DependencyTree, Ecosystem, LockfileManager,
+    PackageError, PackageInfo, PackageQuery, ReleaseNote, SearchResult, TreeNode, TreeOptions,
 };
 use std::path::Path;
 
@@ -49,6 +49,34 @@ impl Ecosystem for Hex {
         None
     }
 
+    fn version_satisfies(&self, version_req: &str, version: &str) -> Option<bool> {
+        // `~>` requirements aren't understood and only checked for presence
+        crate::version_req::satisfies(
+            version_req,
+            version,
+            crate::version_req::BareVersion::Exact,
+            crate::version_req::Tilde::Minor,
+        )
+    }
+
+    fn package_for_import<'a>(
+        &self,
+        import: &str,
+        dependencies: &'a [Dependency],
+    ) -> Option<&'a Dependency> {
+        // Erlang modules are atoms (`:cowboy`); Elixir modules map to the
+        // snake_case package of their leading segments (`Phoenix.LiveView.Socket`
+        // in `phoenix_live_view`, `Plug.Conn` in `plug`)
+        if let Some(atom) = import.strip_prefix(':') {
+            return dependencies.iter().find(|dep| dep.name == atom);
+        }
+        let segments: Vec<String> = import.split('.').map(snake_case).collect();
+        (1..=segments.len()).rev().find_map(|n| {
+            let name = segments[..n].join("_");
+            dependencies.iter().find(|dep| dep.name == name)
+        })
+    }
+
     fn list_dependencies(&self, project_root: &Path) -> Result<Vec<Dependency>, PackageError> {
         // mix.exs is Elixir code, we can extract deps from defp deps do [...] end
         let mixfile = project_root.join("mix.exs");
@@ -73,10 +101,18 @@ impl Ecosystem for Hex {
                         None
                     };
                     let optional = rest.contains("optional: true");
+                    let kind = if is_dev_only(rest) {
+                        DependencyKind::Dev
+                    } else if optional {
+                        DependencyKind::Optional
+                    } else {
+                        DependencyKind::Normal
+                    };
                     deps.push(Dependency {
                         name,
                         version_req,
                         optional,
+                        kind,
                     });
                 }
             }
@@ -85,7 +121,13 @@ impl Ecosystem for Hex {
         Ok(deps)
     }
 
-    fn dependency_tree(&self, project_root: &Path) -> Result<DependencyTree, PackageError> {
+    fn dependency_tree(
+        &self,
+        project_root: &Path,
+        _options: &TreeOptions,
+    ) -> Result<DependencyTree, PackageError> {
+        // The lockfile lists packages without their dependencies, so every package
+        // is a leaf under the root and there is nothing for `options` to bound
         // Parse mix.lock for all deps
         let lockfile = project_root.join("mix.lock");
         let content = std::fs::read_to_string(&lockfile)
@@ -117,13 +159,11 @@ impl Ecosystem for Hex {
             }
         }
 
-        Ok(DependencyTree {
-            roots: vec![TreeNode {
-                name: "mix.lock".to_string(),
-                version: String::new(),
-                dependencies: deps,
-            }],
-        })
+        Ok(DependencyTree::new(vec![TreeNode {
+            name: "mix.lock".to_string(),
+            version: String::new(),
+            dependencies: deps,
+        }]))
     }
 
     fn audit(&self, _project_root: &Path) -> Result<AuditResult, PackageError> {
@@ -131,6 +171,54 @@ impl Ecosystem for Hex {
             "audit not yet supported for Hex. Use: mix deps.audit".to_string(),
         ))
     }
+
+    fn search(&self, _query: &str, _limit: usize) -> Result<Vec<SearchResult>, PackageError> {
+        Err(PackageError::NotFound(format!("{} search", self.name())))
+    }
+
+    fn release_notes(
+        &self,
+        info: &PackageInfo,
+        from: Option<&str>,
+        to: &str,
+    ) -> Result<Vec<ReleaseNote>, PackageError> {
+        crate::release_notes::from_repository(info, from, to)
+    }
+}
+
+/// Whether a mix.exs dep's options restrict it to the dev and test environments
+/// (`only: :test`, `only: [:dev, :test]`).
+/// `LiveView` -> `live_view`.
+fn snake_case(segment: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in segment.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn is_dev_only(opts: &str) -> bool {
+    let Some(start) = opts.find("only:") else {
+        return false;
+    };
+    let after = opts[start + "only:".len()..].trim_start();
+    let list = match after.strip_prefix('[') {
+        Some(inner) => inner.split(']').next().unwrap_or(inner),
+        None => after.split([',', '}']).next().unwrap_or(after),
+    };
+    let envs: Vec<&str> = list
+        .split(',')
+        .map(|env| env.trim().trim_start_matches(':'))
+        .filter(|env| !env.is_empty())
+        .collect();
+    !envs.is_empty() && envs.iter().all(|env| matches!(*env, "dev" | "test"))
 }
 
 fn fetch_hex_info(package: &str) -> Result<PackageInfo, PackageError> {
@@ -202,10 +290,27 @@ fn fetch_hex_info(package: &str) -> Result<PackageInfo, PackageError> {
                 name: dep_name.clone(),
                 version_req,
                 optional,
+                kind: if optional {
+                    DependencyKind::Optional
+                } else {
+                    DependencyKind::Normal
+                },
             });
         }
     }
 
+    let downloads = v
+        .get("downloads")
+        .and_then(|d| d.get("all"))
+        .and_then(|d| d.as_u64());
+    let published = v
+        .get("releases")
+        .and_then(|r| r.as_array())
+        .and_then(|a| a.first())
+        .and_then(|r| r.get("inserted_at"))
+        .and_then(|t| t.as_str())
+        .map(String::from);
+
     Ok(PackageInfo {
         name,
         version,
@@ -215,5 +320,7 @@ fn fetch_hex_info(package: &str) -> Result<PackageInfo, PackageError> {
         repository,
         features: Vec::new(),
         dependencies,
+        downloads,
+        published,
     })
 }
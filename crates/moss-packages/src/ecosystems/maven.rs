@@ -1,8 +1,8 @@
 //! Maven (Java) ecosystem.
 
 use crate::{
-    AuditResult, Dependency, DependencyTree, Ecosystem, LockfileManager, PackageError, PackageInfo,
-    PackageQuery, TreeNode,
+    AuditResult, Dependency, DependencyKind, DependencyTree, Ecosystem, LockfileManager,
+    PackageError, PackageInfo, PackageQuery, ReleaseNote, SearchResult, TreeNode, TreeOptions,
 };
 use std::path::Path;
 
@@ -63,6 +63,33 @@ impl Ecosystem for Maven {
         None
     }
 
+    fn version_satisfies(&self, version_req: &str, version: &str) -> Option<bool> {
+        // Version ranges (`[1.0,2.0)`) aren't understood and only checked for presence
+        crate::version_req::satisfies(
+            version_req,
+            version,
+            crate::version_req::BareVersion::Exact,
+            crate::version_req::Tilde::Minor,
+        )
+    }
+
+    fn package_for_import<'a>(
+        &self,
+        import: &str,
+        dependencies: &'a [Dependency],
+    ) -> Option<&'a Dependency> {
+        // Java packages usually start with the artifact's groupId
+        // (`org.apache.commons.lang3.StringUtils` in `org.apache.commons:commons-lang3`).
+        // Artifacts whose packages don't (Guava's `com.google.common`) are not found.
+        dependencies
+            .iter()
+            .filter(|dep| {
+                let group = dep.name.split(':').next().unwrap_or(&dep.name);
+                crate::is_segment_prefix(group, import, '.')
+            })
+            .max_by_key(|dep| dep.name.split(':').next().unwrap_or(&dep.name).len())
+    }
+
     fn list_dependencies(&self, project_root: &Path) -> Result<Vec<Dependency>, PackageError> {
         // Try pom.xml first
         let pom = project_root.join("pom.xml");
@@ -81,7 +108,13 @@ impl Ecosystem for Maven {
         Err(PackageError::ParseError("no manifest found".to_string()))
     }
 
-    fn dependency_tree(&self, project_root: &Path) -> Result<DependencyTree, PackageError> {
+    fn dependency_tree(
+        &self,
+        project_root: &Path,
+        _options: &TreeOptions,
+    ) -> Result<DependencyTree, PackageError> {
+        // The lockfile lists packages without their dependencies, so every package
+        // is a leaf under the root and there is nothing for `options` to bound
         // Try gradle.lockfile first
         let lockfile = project_root.join("gradle.lockfile");
         if let Ok(content) = std::fs::read_to_string(&lockfile) {
@@ -108,13 +141,11 @@ impl Ecosystem for Maven {
                 }
             }
 
-            return Ok(DependencyTree {
-                roots: vec![TreeNode {
-                    name: "gradle.lockfile".to_string(),
-                    version: String::new(),
-                    dependencies: deps,
-                }],
-            });
+            return Ok(DependencyTree::new(vec![TreeNode {
+                name: "gradle.lockfile".to_string(),
+                version: String::new(),
+                dependencies: deps,
+            }]));
         }
 
         // Fall back to listing direct deps from manifest
@@ -128,13 +159,11 @@ impl Ecosystem for Maven {
             })
             .collect();
 
-        Ok(DependencyTree {
-            roots: vec![TreeNode {
-                name: "dependencies".to_string(),
-                version: String::new(),
-                dependencies: deps,
-            }],
-        })
+        Ok(DependencyTree::new(vec![TreeNode {
+            name: "dependencies".to_string(),
+            version: String::new(),
+            dependencies: deps,
+        }]))
     }
 
     fn audit(&self, _project_root: &Path) -> Result<AuditResult, PackageError> {
@@ -142,6 +171,19 @@ impl Ecosystem for Maven {
             "audit not yet supported for Maven. Use OWASP dependency-check or Snyk".to_string(),
         ))
     }
+
+    fn search(&self, _query: &str, _limit: usize) -> Result<Vec<SearchResult>, PackageError> {
+        Err(PackageError::NotFound(format!("{} search", self.name())))
+    }
+
+    fn release_notes(
+        &self,
+        info: &PackageInfo,
+        from: Option<&str>,
+        to: &str,
+    ) -> Result<Vec<ReleaseNote>, PackageError> {
+        crate::release_notes::from_repository(info, from, to)
+    }
 }
 
 fn parse_pom_dependencies(content: &str) -> Result<Vec<Dependency>, PackageError> {
@@ -153,6 +195,7 @@ fn parse_pom_dependencies(content: &str) -> Result<Vec<Dependency>, PackageError
     let mut artifact_id = String::new();
     let mut version = String::new();
     let mut optional = false;
+    let mut scope = String::new();
 
     for line in content.lines() {
         let line = line.trim();
@@ -163,6 +206,7 @@ fn parse_pom_dependencies(content: &str) -> Result<Vec<Dependency>, PackageError
             artifact_id.clear();
             version.clear();
             optional = false;
+            scope.clear();
         } else if line.contains("</dependency>") {
             if in_dependency && !artifact_id.is_empty() {
                 deps.push(Dependency {
@@ -177,6 +221,13 @@ fn parse_pom_dependencies(content: &str) -> Result<Vec<Dependency>, PackageError
                         Some(version.clone())
                     },
                     optional,
+                    kind: if scope == "test" {
+                        DependencyKind::Dev
+                    } else if optional {
+                        DependencyKind::Optional
+                    } else {
+                        DependencyKind::Normal
+                    },
                 });
             }
             in_dependency = false;
@@ -187,6 +238,8 @@ fn parse_pom_dependencies(content: &str) -> Result<Vec<Dependency>, PackageError
                 artifact_id = val;
             } else if let Some(val) = extract_xml_value(line, "version") {
                 version = val;
+            } else if let Some(val) = extract_xml_value(line, "scope") {
+                scope = val;
             } else if line.contains("<optional>true</optional>") {
                 optional = true;
             }
@@ -244,6 +297,11 @@ fn parse_gradle_dependencies(content: &str) -> Result<Vec<Dependency>, PackageEr
                             name: format!("{}:{}", parts[0], parts[1]),
                             version_req: parts.get(2).map(|s| s.to_string()),
                             optional: prefix == "compileOnly" || prefix == "testImplementation",
+                            kind: match prefix {
+                                "testImplementation" => DependencyKind::Dev,
+                                "compileOnly" => DependencyKind::Optional,
+                                _ => DependencyKind::Normal,
+                            },
                         });
                     }
                 }
@@ -325,5 +383,7 @@ fn parse_maven_response(json: &str, package: &str) -> Result<PackageInfo, Packag
         repository: None,
         features: Vec::new(),
         dependencies: Vec::new(),
+        downloads: None,
+        published: None,
     })
 }
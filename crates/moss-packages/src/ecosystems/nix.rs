@@ -1,8 +1,8 @@
 //! Nix ecosystem.
 
 use crate::{
-    AuditResult, Dependency, DependencyTree, Ecosystem, LockfileManager, PackageError, PackageInfo,
-    PackageQuery, TreeNode,
+    AuditResult, Dependency, DependencyKind, DependencyTree, Ecosystem, LockfileManager,
+    PackageError, PackageInfo, PackageQuery, ReleaseNote, SearchResult, TreeNode, TreeOptions,
 };
 use std::path::Path;
 use std::process::Command;
@@ -39,6 +39,25 @@ impl Ecosystem for Nix {
         None
     }
 
+    fn version_satisfies(&self, version_req: &str, version: &str) -> Option<bool> {
+        crate::version_req::satisfies(
+            version_req,
+            version,
+            crate::version_req::BareVersion::Exact,
+            crate::version_req::Tilde::Minor,
+        )
+    }
+
+    fn package_for_import<'a>(
+        &self,
+        import: &str,
+        dependencies: &'a [Dependency],
+    ) -> Option<&'a Dependency> {
+        // `<nixpkgs>` and `nixpkgs/lib` refer to the `nixpkgs` input
+        let input = import.trim_matches(['<', '>']).split('/').next()?;
+        dependencies.iter().find(|dep| dep.name == input)
+    }
+
     fn list_dependencies(&self, project_root: &Path) -> Result<Vec<Dependency>, PackageError> {
         // Nix flake inputs from flake.nix
         let flake = project_root.join("flake.nix");
@@ -63,6 +82,7 @@ impl Ecosystem for Nix {
                                         name: name.to_string(),
                                         version_req: Some(rest[..end].to_string()),
                                         optional: false,
+                                        kind: DependencyKind::Normal,
                                     });
                                 }
                             }
@@ -77,7 +97,13 @@ impl Ecosystem for Nix {
         Err(PackageError::ParseError("no flake.nix found".to_string()))
     }
 
-    fn dependency_tree(&self, project_root: &Path) -> Result<DependencyTree, PackageError> {
+    fn dependency_tree(
+        &self,
+        project_root: &Path,
+        _options: &TreeOptions,
+    ) -> Result<DependencyTree, PackageError> {
+        // The lockfile lists packages without their dependencies, so every package
+        // is a leaf under the root and there is nothing for `options` to bound
         // Parse flake.lock for input revisions
         let lockfile = project_root.join("flake.lock");
         let content = std::fs::read_to_string(&lockfile)
@@ -106,13 +132,11 @@ impl Ecosystem for Nix {
             }
         }
 
-        Ok(DependencyTree {
-            roots: vec![TreeNode {
-                name: "flake.lock".to_string(),
-                version: String::new(),
-                dependencies: deps,
-            }],
-        })
+        Ok(DependencyTree::new(vec![TreeNode {
+            name: "flake.lock".to_string(),
+            version: String::new(),
+            dependencies: deps,
+        }]))
     }
 
     fn audit(&self, _project_root: &Path) -> Result<AuditResult, PackageError> {
@@ -120,6 +144,19 @@ impl Ecosystem for Nix {
             "audit not yet supported for Nix".to_string(),
         ))
     }
+
+    fn search(&self, _query: &str, _limit: usize) -> Result<Vec<SearchResult>, PackageError> {
+        Err(PackageError::NotFound(format!("{} search", self.name())))
+    }
+
+    fn release_notes(
+        &self,
+        info: &PackageInfo,
+        from: Option<&str>,
+        to: &str,
+    ) -> Result<Vec<ReleaseNote>, PackageError> {
+        crate::release_notes::from_repository(info, from, to)
+    }
 }
 
 fn fetch_nix_info(package: &str) -> Result<PackageInfo, PackageError> {
@@ -166,6 +203,8 @@ fn fetch_nix_info(package: &str) -> Result<PackageInfo, PackageError> {
                 repository: None,
                 features: Vec::new(),
                 dependencies: Vec::new(),
+                downloads: None,
+                published: None,
             });
         }
     }
@@ -210,6 +249,8 @@ fn fetch_nix_info(package: &str) -> Result<PackageInfo, PackageError> {
                     repository: None,
                     features: Vec::new(),
                     dependencies: Vec::new(),
+                    downloads: None,
+                    published: None,
                 });
             }
         }
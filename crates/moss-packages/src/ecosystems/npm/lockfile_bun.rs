@@ -16,10 +16,13 @@
 //! - Package.zig: Package struct and MultiArrayList serialization
 //! - semver/SemverString.zig: String encoding (inline vs external)
 
-use crate::{DependencyTree, PackageError, TreeNode};
+use crate::{DependencyTree, PackageError, TreeBudget, TreeNode, TreeOptions};
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
+/// Prevent runaway recursion
+const MAX_DEPTH: usize = 50;
+
 /// Project name and version from package.json or lockfile.
 struct ProjectInfo {
     name: String,
@@ -80,18 +83,21 @@ fn installed_version_binary(package: &str, project_root: &Path) -> Option<String
 }
 
 /// Build dependency tree from bun.lock or bun.lockb
-pub fn dependency_tree(project_root: &Path) -> Option<Result<DependencyTree, PackageError>> {
+pub fn dependency_tree(
+    project_root: &Path,
+    options: &TreeOptions,
+) -> Option<Result<DependencyTree, PackageError>> {
     // Try text format first (preferred - has version info)
     if let Some(lockfile) = find_text_lockfile(project_root) {
         let content = std::fs::read_to_string(&lockfile).ok()?;
         let parsed: serde_json::Value = serde_json_lenient::from_str(&content).ok()?;
-        return Some(build_tree_text(&parsed, project_root));
+        return Some(build_tree_text(&parsed, project_root, options));
     }
 
     // Fall back to binary format
     if let Some(lockfile) = find_binary_lockfile(project_root) {
         if lockfile.exists() {
-            return Some(build_tree_binary(project_root));
+            return Some(build_tree_binary(project_root, options));
         }
     }
 
@@ -620,36 +626,44 @@ impl<'a> BunLockb<'a> {
         deps_map
     }
 
-    /// Recursively build a TreeNode using logical dependencies
+    /// Recursively build a TreeNode at `depth` using logical dependencies
     fn build_node(
         &self,
         name: &str,
         deps_map: &HashMap<String, (String, Vec<String>)>,
         visited: &mut HashSet<String>,
-        max_depth: usize,
+        depth: usize,
+        budget: &mut TreeBudget,
     ) -> TreeNode {
         let version = deps_map
             .get(name)
             .map(|(v, _)| v.clone())
             .unwrap_or_else(|| "?".to_string());
 
-        if visited.contains(name) || max_depth == 0 {
-            return TreeNode {
-                name: name.to_string(),
-                version,
-                dependencies: Vec::new(),
-            };
-        }
+        let deps = match deps_map.get(name) {
+            Some((_, deps))
+                if !deps.is_empty()
+                    && !visited.contains(name)
+                    && depth <= MAX_DEPTH
+                    && budget.expand(name, depth) =>
+            {
+                deps
+            }
+            _ => {
+                return TreeNode {
+                    name: name.to_string(),
+                    version,
+                    dependencies: Vec::new(),
+                };
+            }
+        };
 
         visited.insert(name.to_string());
 
-        let children = if let Some((_, deps)) = deps_map.get(name) {
-            deps.iter()
-                .map(|dep| self.build_node(dep, deps_map, visited, max_depth - 1))
-                .collect()
-        } else {
-            Vec::new()
-        };
+        let children = deps
+            .iter()
+            .map(|dep| self.build_node(dep, deps_map, visited, depth + 1, budget))
+            .collect();
 
         visited.remove(name);
 
@@ -660,7 +674,7 @@ impl<'a> BunLockb<'a> {
         }
     }
 
-    fn to_tree(&self, project_root: &Path) -> DependencyTree {
+    fn to_tree(&self, project_root: &Path, options: &TreeOptions) -> DependencyTree {
         let proj_info = get_project_info_from_package_json(project_root);
 
         // Build logical dependency map from packages
@@ -668,13 +682,13 @@ impl<'a> BunLockb<'a> {
 
         // Get direct dependencies from root package (package 0 is usually the root)
         let mut visited = HashSet::new();
-        const MAX_DEPTH: usize = 50;
+        let mut budget = TreeBudget::new(options);
 
         let root_deps = if let Some(root_pkg) = self.packages.first() {
             if let Some((_, direct_deps)) = deps_map.get(&root_pkg.name) {
                 direct_deps
                     .iter()
-                    .map(|dep| self.build_node(dep, &deps_map, &mut visited, MAX_DEPTH))
+                    .map(|dep| self.build_node(dep, &deps_map, &mut visited, 1, &mut budget))
                     .collect()
             } else {
                 Vec::new()
@@ -692,13 +706,11 @@ impl<'a> BunLockb<'a> {
                 .collect()
         };
 
-        DependencyTree {
-            roots: vec![TreeNode {
-                name: proj_info.name,
-                version: proj_info.version,
-                dependencies: root_deps,
-            }],
-        }
+        budget.finish(vec![TreeNode {
+            name: proj_info.name,
+            version: proj_info.version,
+            dependencies: root_deps,
+        }])
     }
 }
 
@@ -797,12 +809,14 @@ fn build_deps_map_text(parsed: &serde_json::Value) -> HashMap<String, (String, V
     deps_map
 }
 
-/// Recursively build a TreeNode for bun.lock text format
+/// Recursively build a TreeNode at `depth` (1 for direct dependencies) for
+/// bun.lock text format
 fn build_node_text(
     name: &str,
     deps_map: &HashMap<String, (String, Vec<String>)>,
     visited: &mut HashSet<String>,
-    max_depth: usize,
+    depth: usize,
+    budget: &mut TreeBudget,
 ) -> TreeNode {
     // Get version from deps map
     let version = deps_map
@@ -810,24 +824,31 @@ fn build_node_text(
         .map(|(v, _)| v.clone())
         .unwrap_or_else(|| "?".to_string());
 
-    // Avoid cycles and limit depth
-    if visited.contains(name) || max_depth == 0 {
-        return TreeNode {
-            name: name.to_string(),
-            version,
-            dependencies: Vec::new(),
-        };
-    }
+    // Avoid cycles, limit depth, and stop where the options say
+    let deps = match deps_map.get(name) {
+        Some((_, deps))
+            if !deps.is_empty()
+                && !visited.contains(name)
+                && depth <= MAX_DEPTH
+                && budget.expand(name, depth) =>
+        {
+            deps
+        }
+        _ => {
+            return TreeNode {
+                name: name.to_string(),
+                version,
+                dependencies: Vec::new(),
+            };
+        }
+    };
 
     visited.insert(name.to_string());
 
-    let children = if let Some((_, deps)) = deps_map.get(name) {
-        deps.iter()
-            .map(|dep| build_node_text(dep, deps_map, visited, max_depth - 1))
-            .collect()
-    } else {
-        Vec::new()
-    };
+    let children = deps
+        .iter()
+        .map(|dep| build_node_text(dep, deps_map, visited, depth + 1, budget))
+        .collect();
 
     visited.remove(name);
 
@@ -841,6 +862,7 @@ fn build_node_text(
 fn build_tree_text(
     parsed: &serde_json::Value,
     project_root: &Path,
+    options: &TreeOptions,
 ) -> Result<DependencyTree, PackageError> {
     let info = get_project_info(parsed, project_root);
 
@@ -849,7 +871,7 @@ fn build_tree_text(
 
     let mut root_deps = Vec::new();
     let mut visited = HashSet::new();
-    const MAX_DEPTH: usize = 50;
+    let mut budget = TreeBudget::new(options);
 
     // Get direct dependencies from root workspace
     if let Some(workspaces) = parsed.get("workspaces").and_then(|w| w.as_object()) {
@@ -861,7 +883,8 @@ fn build_tree_text(
                             dep_name,
                             &deps_map,
                             &mut visited,
-                            MAX_DEPTH,
+                            1,
+                            &mut budget,
                         ));
                     }
                 }
@@ -893,10 +916,13 @@ fn build_tree_text(
         dependencies: root_deps,
     };
 
-    Ok(DependencyTree { roots: vec![root] })
+    Ok(budget.finish(vec![root]))
 }
 
-fn build_tree_binary(project_root: &Path) -> Result<DependencyTree, PackageError> {
+fn build_tree_binary(
+    project_root: &Path,
+    options: &TreeOptions,
+) -> Result<DependencyTree, PackageError> {
     let lockfile = find_binary_lockfile(project_root)
         .ok_or_else(|| PackageError::ParseError("bun.lockb not found".to_string()))?;
 
@@ -906,7 +932,7 @@ fn build_tree_binary(project_root: &Path) -> Result<DependencyTree, PackageError
     let parsed = BunLockb::parse(&data)
         .ok_or_else(|| PackageError::ParseError("invalid bun.lockb format".to_string()))?;
 
-    Ok(parsed.to_tree(project_root))
+    Ok(parsed.to_tree(project_root, options))
 }
 
 fn get_project_info(parsed: &serde_json::Value, project_root: &Path) -> ProjectInfo {
@@ -1,6 +1,6 @@
 //! package-lock.json parser (npm)
 
-use crate::{DependencyTree, PackageError, TreeNode};
+use crate::{DependencyTree, PackageError, TreeBudget, TreeNode, TreeOptions};
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
@@ -32,11 +32,14 @@ pub fn installed_version(package: &str, project_root: &Path) -> Option<String> {
 }
 
 /// Build dependency tree from package-lock.json
-pub fn dependency_tree(project_root: &Path) -> Option<Result<DependencyTree, PackageError>> {
+pub fn dependency_tree(
+    project_root: &Path,
+    options: &TreeOptions,
+) -> Option<Result<DependencyTree, PackageError>> {
     let lockfile = find_lockfile(project_root)?;
     let content = std::fs::read_to_string(&lockfile).ok()?;
     let parsed: serde_json::Value = serde_json::from_str(&content).ok()?;
-    Some(build_tree(&parsed))
+    Some(build_tree(&parsed, options))
 }
 
 fn find_lockfile(project_root: &Path) -> Option<std::path::PathBuf> {
@@ -55,7 +58,10 @@ fn find_lockfile(project_root: &Path) -> Option<std::path::PathBuf> {
 
 /// Build dependency tree from parsed package-lock.json
 /// Exposed for testing
-fn build_tree(parsed: &serde_json::Value) -> Result<DependencyTree, PackageError> {
+fn build_tree(
+    parsed: &serde_json::Value,
+    options: &TreeOptions,
+) -> Result<DependencyTree, PackageError> {
     let name = parsed
         .get("name")
         .and_then(|n| n.as_str())
@@ -104,24 +110,27 @@ fn build_tree(parsed: &serde_json::Value) -> Result<DependencyTree, PackageError
             parent_path: &str,
             deps_map: &HashMap<String, Vec<(String, String)>>,
             visited: &mut HashSet<String>,
+            depth: usize,
+            budget: &mut TreeBudget,
         ) -> TreeNode {
+            let child_path = if parent_path.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}/node_modules/{}", parent_path, name)
+            };
             let children = if visited.contains(name) {
                 Vec::new()
             } else {
                 visited.insert(name.to_string());
-                let child_path = if parent_path.is_empty() {
-                    name.to_string()
-                } else {
-                    format!("{}/node_modules/{}", parent_path, name)
-                };
-                deps_map
-                    .get(&child_path)
-                    .map(|deps| {
-                        deps.iter()
-                            .map(|(n, v)| build_node(n, v, &child_path, deps_map, visited))
-                            .collect()
-                    })
-                    .unwrap_or_default()
+                match deps_map.get(&child_path) {
+                    Some(deps) if budget.expand(&format!("{}@{}", name, version), depth) => deps
+                        .iter()
+                        .map(|(n, v)| {
+                            build_node(n, v, &child_path, deps_map, visited, depth + 1, budget)
+                        })
+                        .collect(),
+                    _ => Vec::new(),
+                }
             };
 
             TreeNode {
@@ -133,11 +142,12 @@ fn build_tree(parsed: &serde_json::Value) -> Result<DependencyTree, PackageError
 
         // Build root children
         let mut visited = HashSet::new();
+        let mut budget = TreeBudget::new(options);
         let root_deps = deps_map
             .get("")
             .map(|deps| {
                 deps.iter()
-                    .map(|(n, v)| build_node(n, v, "", &deps_map, &mut visited))
+                    .map(|(n, v)| build_node(n, v, "", &deps_map, &mut visited, 1, &mut budget))
                     .collect()
             })
             .unwrap_or_default();
@@ -148,16 +158,14 @@ fn build_tree(parsed: &serde_json::Value) -> Result<DependencyTree, PackageError
             dependencies: root_deps,
         };
 
-        Ok(DependencyTree { roots: vec![root] })
+        Ok(budget.finish(vec![root]))
     } else {
         // No packages section, return minimal tree
-        Ok(DependencyTree {
-            roots: vec![TreeNode {
-                name: name.to_string(),
-                version: version.to_string(),
-                dependencies: Vec::new(),
-            }],
-        })
+        Ok(DependencyTree::new(vec![TreeNode {
+            name: name.to_string(),
+            version: version.to_string(),
+            dependencies: Vec::new(),
+        }]))
     }
 }
 
@@ -192,7 +200,7 @@ mod tests {
         }"#;
 
         let parsed: serde_json::Value = serde_json::from_str(json).unwrap();
-        let tree = build_tree(&parsed).unwrap();
+        let tree = build_tree(&parsed, &TreeOptions::default()).unwrap();
 
         assert_eq!(tree.roots.len(), 1);
         let root = &tree.roots[0];
@@ -232,7 +240,7 @@ mod tests {
         }"#;
 
         let parsed: serde_json::Value = serde_json::from_str(json).unwrap();
-        let tree = build_tree(&parsed).unwrap();
+        let tree = build_tree(&parsed, &TreeOptions::default()).unwrap();
 
         let root = &tree.roots[0];
         assert_eq!(root.dependencies.len(), 2);
@@ -254,6 +262,51 @@ mod tests {
         assert_eq!(babel_core.dependencies[0].name, "@babel/parser");
     }
 
+    #[test]
+    fn test_build_tree_bounded() {
+        let json = r#"{
+            "name": "app",
+            "version": "1.0.0",
+            "packages": {
+                "": {},
+                "node_modules/react": {"version": "18.2.0"},
+                "node_modules/react/node_modules/loose-envify": {"version": "1.4.0"},
+                "node_modules/typescript": {"version": "5.0.0"}
+            }
+        }"#;
+        let parsed: serde_json::Value = serde_json::from_str(json).unwrap();
+        let react = |tree: &DependencyTree| {
+            tree.roots[0]
+                .dependencies
+                .iter()
+                .find(|d| d.name == "react")
+                .unwrap()
+                .dependencies
+                .len()
+        };
+
+        let tree = build_tree(&parsed, &TreeOptions::default()).unwrap();
+        assert_eq!(react(&tree), 1);
+        assert!(!tree.truncated);
+
+        let options = TreeOptions {
+            max_depth: Some(1),
+            ..Default::default()
+        };
+        let tree = build_tree(&parsed, &options).unwrap();
+        assert_eq!(tree.roots[0].dependencies.len(), 2);
+        assert_eq!(react(&tree), 0);
+        assert!(!tree.truncated);
+
+        let options = TreeOptions {
+            timeout: Some(std::time::Duration::ZERO),
+            ..Default::default()
+        };
+        let tree = build_tree(&parsed, &options).unwrap();
+        assert_eq!(react(&tree), 0);
+        assert!(tree.truncated);
+    }
+
     #[test]
     fn test_build_tree_empty_packages() {
         let json = r#"{
@@ -262,7 +315,7 @@ mod tests {
         }"#;
 
         let parsed: serde_json::Value = serde_json::from_str(json).unwrap();
-        let tree = build_tree(&parsed).unwrap();
+        let tree = build_tree(&parsed, &TreeOptions::default()).unwrap();
 
         assert_eq!(tree.roots[0].name, "empty-project");
         assert_eq!(tree.roots[0].version, "0.0.1");
@@ -1,6 +1,6 @@
 //! pnpm-lock.yaml parser
 
-use crate::{DependencyTree, PackageError, TreeNode};
+use crate::{DependencyTree, PackageError, TreeBudget, TreeNode, TreeOptions};
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
@@ -43,11 +43,14 @@ pub fn installed_version(package: &str, project_root: &Path) -> Option<String> {
 }
 
 /// Build dependency tree from pnpm-lock.yaml
-pub fn dependency_tree(project_root: &Path) -> Option<Result<DependencyTree, PackageError>> {
+pub fn dependency_tree(
+    project_root: &Path,
+    options: &TreeOptions,
+) -> Option<Result<DependencyTree, PackageError>> {
     let lockfile = find_lockfile(project_root)?;
     let content = std::fs::read_to_string(&lockfile).ok()?;
     let parsed: serde_yaml::Value = serde_yaml::from_str(&content).ok()?;
-    Some(build_tree(&parsed, project_root))
+    Some(build_tree(&parsed, project_root, options))
 }
 
 fn find_lockfile(project_root: &Path) -> Option<std::path::PathBuf> {
@@ -135,39 +138,53 @@ fn build_deps_map(parsed: &serde_yaml::Value) -> HashMap<String, Vec<String>> {
     deps_map
 }
 
-/// Recursively build a TreeNode, tracking visited packages to avoid cycles
+/// Prevent runaway recursion
+const MAX_DEPTH: usize = 50;
+
+/// Recursively build a TreeNode at `depth` (1 for direct dependencies), tracking
+/// visited packages to avoid cycles
 fn build_node(
     name: &str,
     version: &str,
     deps_map: &HashMap<String, Vec<String>>,
     visited: &mut HashSet<String>,
-    max_depth: usize,
+    depth: usize,
+    budget: &mut TreeBudget,
 ) -> TreeNode {
     let pkg_key = format!("{}@{}", name, version);
 
-    // Avoid infinite recursion on cycles
-    if visited.contains(&pkg_key) || max_depth == 0 {
-        return TreeNode {
-            name: name.to_string(),
-            version: version.to_string(),
-            dependencies: Vec::new(),
-        };
-    }
+    // Avoid infinite recursion on cycles, and stop where the options say
+    let deps = match deps_map.get(&pkg_key) {
+        Some(deps)
+            if !deps.is_empty()
+                && !visited.contains(&pkg_key)
+                && depth <= MAX_DEPTH
+                && budget.expand(&pkg_key, depth) =>
+        {
+            deps
+        }
+        _ => {
+            return TreeNode {
+                name: name.to_string(),
+                version: version.to_string(),
+                dependencies: Vec::new(),
+            };
+        }
+    };
 
     visited.insert(pkg_key.clone());
 
     let mut children = Vec::new();
-    if let Some(deps) = deps_map.get(&pkg_key) {
-        for dep_key in deps {
-            if let Some((dep_name, dep_version)) = parse_package_key(dep_key) {
-                children.push(build_node(
-                    &dep_name,
-                    &dep_version,
-                    deps_map,
-                    visited,
-                    max_depth - 1,
-                ));
-            }
+    for dep_key in deps {
+        if let Some((dep_name, dep_version)) = parse_package_key(dep_key) {
+            children.push(build_node(
+                &dep_name,
+                &dep_version,
+                deps_map,
+                visited,
+                depth + 1,
+                budget,
+            ));
         }
     }
 
@@ -183,6 +200,7 @@ fn build_node(
 fn build_tree(
     parsed: &serde_yaml::Value,
     project_root: &Path,
+    options: &TreeOptions,
 ) -> Result<DependencyTree, PackageError> {
     // Get project name from package.json
     let pkg_json = project_root.join("package.json");
@@ -208,7 +226,7 @@ fn build_tree(
 
     let mut root_deps = Vec::new();
     let mut visited = HashSet::new();
-    const MAX_DEPTH: usize = 50; // Prevent runaway recursion
+    let mut budget = TreeBudget::new(options);
 
     // Get direct dependencies from importers section
     if let Some(importers) = parsed.get("importers").and_then(|i| i.as_mapping())
@@ -230,7 +248,8 @@ fn build_tree(
                             dep_version,
                             &deps_map,
                             &mut visited,
-                            MAX_DEPTH,
+                            1,
+                            &mut budget,
                         ));
                     }
                 }
@@ -244,7 +263,7 @@ fn build_tree(
         dependencies: root_deps,
     };
 
-    Ok(DependencyTree { roots: vec![root] })
+    Ok(budget.finish(vec![root]))
 }
 
 #[cfg(test)]
@@ -1,6 +1,6 @@
 //! yarn.lock parser (Yarn v1 classic format)
 
-use crate::{DependencyTree, PackageError, TreeNode};
+use crate::{DependencyTree, PackageError, TreeBudget, TreeNode, TreeOptions};
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
@@ -35,10 +35,13 @@ pub fn installed_version(package: &str, project_root: &Path) -> Option<String> {
 }
 
 /// Build dependency tree from yarn.lock
-pub fn dependency_tree(project_root: &Path) -> Option<Result<DependencyTree, PackageError>> {
+pub fn dependency_tree(
+    project_root: &Path,
+    options: &TreeOptions,
+) -> Option<Result<DependencyTree, PackageError>> {
     let lockfile = find_lockfile(project_root)?;
     let _content = std::fs::read_to_string(&lockfile).ok()?;
-    Some(build_tree(project_root))
+    Some(build_tree(project_root, options))
 }
 
 fn find_lockfile(project_root: &Path) -> Option<std::path::PathBuf> {
@@ -166,12 +169,16 @@ fn extract_package_name(spec: &str) -> Option<String> {
     }
 }
 
-/// Recursively build a TreeNode
+/// Prevent runaway recursion
+const MAX_DEPTH: usize = 50;
+
+/// Recursively build a TreeNode at `depth` (1 for direct dependencies)
 fn build_node(
     name: &str,
     entries: &HashMap<String, YarnEntry>,
     visited: &mut HashSet<String>,
-    max_depth: usize,
+    depth: usize,
+    budget: &mut TreeBudget,
 ) -> TreeNode {
     // Find entry for this package
     let entry = entries.get(name);
@@ -179,26 +186,32 @@ fn build_node(
         .map(|e| e.version.clone())
         .unwrap_or_else(|| "?".to_string());
 
-    // Avoid cycles and limit depth
-    if visited.contains(name) || max_depth == 0 {
-        return TreeNode {
-            name: name.to_string(),
-            version,
-            dependencies: Vec::new(),
-        };
-    }
+    // Avoid cycles, limit depth, and stop where the options say
+    let entry = match entry {
+        Some(entry)
+            if !entry.dependencies.is_empty()
+                && !visited.contains(name)
+                && depth <= MAX_DEPTH
+                && budget.expand(name, depth) =>
+        {
+            entry
+        }
+        _ => {
+            return TreeNode {
+                name: name.to_string(),
+                version,
+                dependencies: Vec::new(),
+            };
+        }
+    };
 
     visited.insert(name.to_string());
 
-    let children = if let Some(entry) = entry {
-        entry
-            .dependencies
-            .iter()
-            .map(|dep| build_node(dep, entries, visited, max_depth - 1))
-            .collect()
-    } else {
-        Vec::new()
-    };
+    let children = entry
+        .dependencies
+        .iter()
+        .map(|dep| build_node(dep, entries, visited, depth + 1, budget))
+        .collect();
 
     visited.remove(name);
 
@@ -209,7 +222,7 @@ fn build_node(
     }
 }
 
-fn build_tree(project_root: &Path) -> Result<DependencyTree, PackageError> {
+fn build_tree(project_root: &Path, options: &TreeOptions) -> Result<DependencyTree, PackageError> {
     // Get project info and direct dependencies from package.json
     let pkg_json = project_root.join("package.json");
     let content = std::fs::read_to_string(&pkg_json)
@@ -232,13 +245,13 @@ fn build_tree(project_root: &Path) -> Result<DependencyTree, PackageError> {
 
     let mut root_deps = Vec::new();
     let mut visited = HashSet::new();
-    const MAX_DEPTH: usize = 50;
+    let mut budget = TreeBudget::new(options);
 
     // Read direct dependencies from package.json
     for dep_type in ["dependencies", "devDependencies"] {
         if let Some(deps) = pkg.get(dep_type).and_then(|d| d.as_object()) {
             for (dep_name, _version_req) in deps {
-                root_deps.push(build_node(dep_name, &entries, &mut visited, MAX_DEPTH));
+                root_deps.push(build_node(dep_name, &entries, &mut visited, 1, &mut budget));
             }
         }
     }
@@ -249,7 +262,7 @@ fn build_tree(project_root: &Path) -> Result<DependencyTree, PackageError> {
         dependencies: root_deps,
     };
 
-    Ok(DependencyTree { roots: vec![root] })
+    Ok(budget.finish(vec![root]))
 }
 
 #[cfg(test)]
@@ -6,14 +6,23 @@ mod lockfile_pnpm;
 mod lockfile_yarn;
 
 use crate::{
-    AuditResult, Dependency, DependencyTree, Ecosystem, LockfileManager, PackageError, PackageInfo,
-    PackageQuery, Vulnerability, VulnerabilitySeverity,
+    AuditResult, Dependency, DependencyKind, DependencyTree, Ecosystem, LockfileManager,
+    PackageError, PackageInfo, PackageQuery, ReleaseNote, SearchResult, TreeOptions, Vulnerability,
+    VulnerabilitySeverity,
 };
 use std::path::Path;
 use std::process::Command;
 
 pub struct Npm;
 
+/// package.json dependency sections and the kind of their entries.
+const DEPENDENCY_SECTIONS: [(&str, DependencyKind); 4] = [
+    ("dependencies", DependencyKind::Normal),
+    ("devDependencies", DependencyKind::Dev),
+    ("peerDependencies", DependencyKind::Peer),
+    ("optionalDependencies", DependencyKind::Optional),
+];
+
 impl Ecosystem for Npm {
     fn name(&self) -> &'static str {
         "npm"
@@ -74,6 +83,24 @@ impl Ecosystem for Npm {
         None
     }
 
+    fn version_satisfies(&self, version_req: &str, version: &str) -> Option<bool> {
+        crate::version_req::satisfies(
+            version_req,
+            version,
+            crate::version_req::BareVersion::Exact,
+            crate::version_req::Tilde::Minor,
+        )
+    }
+
+    fn package_for_import<'a>(
+        &self,
+        import: &str,
+        dependencies: &'a [Dependency],
+    ) -> Option<&'a Dependency> {
+        let name = package_name(import)?;
+        dependencies.iter().find(|dep| dep.name == name)
+    }
+
     fn list_dependencies(&self, project_root: &Path) -> Result<Vec<Dependency>, PackageError> {
         let manifest = project_root.join("package.json");
         let content = std::fs::read_to_string(&manifest)
@@ -82,55 +109,38 @@ impl Ecosystem for Npm {
             .map_err(|e| PackageError::ParseError(format!("invalid JSON: {}", e)))?;
 
         let mut deps = Vec::new();
-
-        if let Some(d) = parsed.get("dependencies").and_then(|d| d.as_object()) {
-            for (name, version) in d {
-                deps.push(Dependency {
-                    name: name.clone(),
-                    version_req: version.as_str().map(String::from),
-                    optional: false,
-                });
-            }
-        }
-
-        if let Some(d) = parsed.get("devDependencies").and_then(|d| d.as_object()) {
-            for (name, version) in d {
-                deps.push(Dependency {
-                    name: name.clone(),
-                    version_req: version.as_str().map(String::from),
-                    optional: false,
-                });
-            }
-        }
-
-        if let Some(d) = parsed
-            .get("optionalDependencies")
-            .and_then(|d| d.as_object())
-        {
-            for (name, version) in d {
-                deps.push(Dependency {
-                    name: name.clone(),
-                    version_req: version.as_str().map(String::from),
-                    optional: true,
-                });
+        for (section, kind) in DEPENDENCY_SECTIONS {
+            if let Some(d) = parsed.get(section).and_then(|d| d.as_object()) {
+                for (name, version) in d {
+                    deps.push(Dependency {
+                        name: name.clone(),
+                        version_req: version.as_str().map(String::from),
+                        optional: kind == DependencyKind::Optional,
+                        kind,
+                    });
+                }
             }
         }
 
         Ok(deps)
     }
 
-    fn dependency_tree(&self, project_root: &Path) -> Result<DependencyTree, PackageError> {
+    fn dependency_tree(
+        &self,
+        project_root: &Path,
+        options: &TreeOptions,
+    ) -> Result<DependencyTree, PackageError> {
         // Try each lockfile format in order of preference
-        if let Some(tree) = lockfile_pnpm::dependency_tree(project_root) {
+        if let Some(tree) = lockfile_pnpm::dependency_tree(project_root, options) {
             return tree;
         }
-        if let Some(tree) = lockfile_yarn::dependency_tree(project_root) {
+        if let Some(tree) = lockfile_yarn::dependency_tree(project_root, options) {
             return tree;
         }
-        if let Some(tree) = lockfile_npm::dependency_tree(project_root) {
+        if let Some(tree) = lockfile_npm::dependency_tree(project_root, options) {
             return tree;
         }
-        if let Some(tree) = lockfile_bun::dependency_tree(project_root) {
+        if let Some(tree) = lockfile_bun::dependency_tree(project_root, options) {
             return tree;
         }
 
@@ -231,29 +241,123 @@ impl Ecosystem for Npm {
 
         Ok(AuditResult { vulnerabilities })
     }
+
+    fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>, PackageError> {
+        search_npm_registry(query, limit)
+    }
+
+    fn release_notes(
+        &self,
+        info: &PackageInfo,
+        from: Option<&str>,
+        to: &str,
+    ) -> Result<Vec<ReleaseNote>, PackageError> {
+        crate::release_notes::from_repository(info, from, to)
+    }
 }
 
 /// Fetch package info from npm registry API.
 /// Used by both npm and deno ecosystems.
+/// The package an import specifier names: `lodash` for `lodash/fp`,
+/// `@scope/pkg` for `@scope/pkg/x`. None for relative and absolute paths.
+pub(crate) fn package_name(specifier: &str) -> Option<&str> {
+    if specifier.is_empty() || specifier.starts_with('.') || specifier.starts_with('/') {
+        return None;
+    }
+    let segments = if specifier.starts_with('@') { 2 } else { 1 };
+    let end = specifier
+        .match_indices('/')
+        .nth(segments - 1)
+        .map_or(specifier.len(), |(i, _)| i);
+    Some(&specifier[..end])
+}
+
+fn search_npm_registry(query: &str, limit: usize) -> Result<Vec<SearchResult>, PackageError> {
+    // The registry caps size at 250
+    let size = limit.min(250).to_string();
+    let body = crate::http::get_with_query(
+        "https://registry.npmjs.org/-/v1/search",
+        &[("text", query), ("size", &size)],
+        &[],
+    )?;
+    parse_npm_search(&body, limit)
+}
+
+fn parse_npm_search(body: &str, limit: usize) -> Result<Vec<SearchResult>, PackageError> {
+    let v: serde_json::Value = serde_json::from_str(body)
+        .map_err(|e| PackageError::ParseError(format!("invalid JSON: {}", e)))?;
+    let objects = v
+        .get("objects")
+        .and_then(|o| o.as_array())
+        .ok_or_else(|| PackageError::ParseError("missing objects".to_string()))?;
+
+    Ok(objects
+        .iter()
+        .filter_map(|obj| {
+            let pkg = obj.get("package")?;
+            Some(SearchResult {
+                name: pkg.get("name")?.as_str()?.to_string(),
+                version: pkg
+                    .get("version")
+                    .and_then(|v| v.as_str())
+                    .map(String::from),
+                description: pkg
+                    .get("description")
+                    .and_then(|d| d.as_str())
+                    .map(|d| d.trim().to_string()),
+                downloads: obj.pointer("/downloads/monthly").and_then(|d| d.as_u64()),
+            })
+        })
+        .take(limit)
+        .collect())
+}
+
 pub(crate) fn fetch_npm_registry(
     package: &str,
     version: Option<&str>,
 ) -> Result<PackageInfo, PackageError> {
-    // registry.npmjs.org/{package} returns full metadata
-    // registry.npmjs.org/{package}/{version} returns version-specific
-    let url = match version {
-        Some(v) => format!("https://registry.npmjs.org/{}/{}", package, v),
-        None => format!("https://registry.npmjs.org/{}/latest", package),
-    };
+    // registry.npmjs.org/{package}/{version} has no publish times; the full
+    // document for registry.npmjs.org/{package} does
+    let url = format!("https://registry.npmjs.org/{}", package);
 
     let body = crate::http::get(&url)?;
-    parse_npm_json(&body, package)
+    let mut info = parse_npm_json(&body, package, version)?;
+    info.downloads = fetch_npm_downloads(package);
+    Ok(info)
+}
+
+/// Last week's downloads from the npm downloads API (the registry itself has none).
+fn fetch_npm_downloads(package: &str) -> Option<u64> {
+    let url = format!(
+        "https://api.npmjs.org/downloads/point/last-week/{}",
+        package
+    );
+    let body = crate::http::get(&url).ok()?;
+    let v: serde_json::Value = serde_json::from_str(&body).ok()?;
+    v.get("downloads").and_then(|d| d.as_u64())
 }
 
-fn parse_npm_json(json_str: &str, package: &str) -> Result<PackageInfo, PackageError> {
-    let v: serde_json::Value = serde_json::from_str(json_str)
+/// Info for `version` (a version or dist-tag, `latest` by default) from a
+/// package's full registry document.
+fn parse_npm_json(
+    json_str: &str,
+    package: &str,
+    version: Option<&str>,
+) -> Result<PackageInfo, PackageError> {
+    let doc: serde_json::Value = serde_json::from_str(json_str)
         .map_err(|e| PackageError::ParseError(format!("invalid JSON: {}", e)))?;
 
+    let requested = version.unwrap_or("latest");
+    let resolved = doc
+        .get("dist-tags")
+        .and_then(|tags| tags.get(requested))
+        .and_then(|v| v.as_str())
+        .unwrap_or(requested);
+    let v = doc
+        .get("versions")
+        .and_then(|versions| versions.get(resolved))
+        .ok_or_else(|| PackageError::ParseError(format!("version {} not found", resolved)))?;
+
     let name = v
         .get("name")
         .and_then(|v| v.as_str())
@@ -285,37 +389,32 @@ fn parse_npm_json(json_str: &str, package: &str) -> Result<PackageInfo, PackageE
 
     // Dependencies
     let mut dependencies = Vec::new();
-    if let Some(deps) = v.get("dependencies").and_then(|d| d.as_object()) {
-        for (name, version) in deps {
-            dependencies.push(Dependency {
-                name: name.clone(),
-                version_req: version.as_str().map(String::from),
-                optional: false,
-            });
-        }
-    }
-    if let Some(deps) = v.get("peerDependencies").and_then(|d| d.as_object()) {
-        for (name, version) in deps {
-            dependencies.push(Dependency {
-                name: name.clone(),
-                version_req: version.as_str().map(String::from),
-                optional: false,
-            });
+    for (section, kind) in DEPENDENCY_SECTIONS {
+        // A published version's devDependencies aren't installed with it
+        if kind == DependencyKind::Dev {
+            continue;
         }
-    }
-    if let Some(deps) = v.get("optionalDependencies").and_then(|d| d.as_object()) {
-        for (name, version) in deps {
-            dependencies.push(Dependency {
-                name: name.clone(),
-                version_req: version.as_str().map(String::from),
-                optional: true,
-            });
+        if let Some(deps) = v.get(section).and_then(|d| d.as_object()) {
+            for (name, version) in deps {
+                dependencies.push(Dependency {
+                    name: name.clone(),
+                    version_req: version.as_str().map(String::from),
+                    optional: kind == DependencyKind::Optional,
+                    kind,
+                });
+            }
         }
     }
 
     // npm doesn't have features like Cargo, but we could map optionalDependencies
     let features = Vec::new();
 
+    let published = doc
+        .get("time")
+        .and_then(|t| t.get(&version))
+        .and_then(|t| t.as_str())
+        .map(String::from);
+
     Ok(PackageInfo {
         name,
         version,
@@ -325,6 +424,8 @@ fn parse_npm_json(json_str: &str, package: &str) -> Result<PackageInfo, PackageE
         repository,
         features,
         dependencies,
+        downloads: None,
+        published,
     })
 }
 
@@ -336,21 +437,61 @@ mod tests {
     fn test_parse_npm_json() {
         let json = r#"{
             "name": "react",
-            "version": "18.2.0",
-            "description": "React is a JavaScript library for building user interfaces.",
-            "license": "MIT",
-            "homepage": "https://reactjs.org/",
-            "repository": {"url": "https://github.com/facebook/react.git"},
-            "dependencies": {"loose-envify": "^1.1.0"},
-            "peerDependencies": {},
-            "optionalDependencies": {}
+            "dist-tags": {"latest": "18.2.0", "next": "19.0.0-rc"},
+            "versions": {
+                "18.2.0": {
+                    "name": "react",
+                    "version": "18.2.0",
+                    "description": "React is a JavaScript library for building user interfaces.",
+                    "license": "MIT",
+                    "homepage": "https://reactjs.org/",
+                    "repository": {"url": "https://github.com/facebook/react.git"},
+                    "dependencies": {"loose-envify": "^1.1.0"},
+                    "peerDependencies": {},
+                    "optionalDependencies": {}
+                },
+                "19.0.0-rc": {"name": "react", "version": "19.0.0-rc"}
+            },
+            "time": {
+                "18.2.0": "2022-06-14T19:46:38.369Z",
+                "19.0.0-rc": "2024-04-25T16:52:10.183Z"
+            }
         }"#;
 
-        let info = parse_npm_json(json, "react").unwrap();
+        let info = parse_npm_json(json, "react", None).unwrap();
         assert_eq!(info.name, "react");
         assert_eq!(info.version, "18.2.0");
         assert_eq!(info.license, Some("MIT".to_string()));
+        assert_eq!(
+            info.repository.as_deref(),
+            Some("https://github.com/facebook/react.git")
+        );
         assert_eq!(info.dependencies.len(), 1);
         assert_eq!(info.dependencies[0].name, "loose-envify");
+        assert_eq!(info.published.as_deref(), Some("2022-06-14T19:46:38.369Z"));
+
+        let next = parse_npm_json(json, "react", Some("next")).unwrap();
+        assert_eq!(next.version, "19.0.0-rc");
+        assert_eq!(next.published.as_deref(), Some("2024-04-25T16:52:10.183Z"));
+
+        assert!(parse_npm_json(json, "react", Some("1.0.0")).is_err());
+    }
+
+    #[test]
+    fn test_parse_npm_search() {
+        let json = r#"{
+            "objects": [
+                {"package": {"name": "react", "version": "18.2.0", "description": "UI library"},
+                 "downloads": {"monthly": 1000, "weekly": 250}},
+                {"package": {"name": "react-dom", "version": "18.2.0"}}
+            ],
+            "total": 2
+        }"#;
+        let results = parse_npm_search(json, 10).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].name, "react");
+        assert_eq!(results[0].description.as_deref(), Some("UI library"));
+        assert_eq!(results[0].downloads, Some(1000));
+        assert_eq!(results[1].downloads, None);
     }
 }
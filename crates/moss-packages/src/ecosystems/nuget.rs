@@ -1,8 +1,9 @@
 //! NuGet (.NET) ecosystem.
 
 use crate::{
-    AuditResult, Dependency, DependencyTree, Ecosystem, LockfileManager, PackageError, PackageInfo,
-    PackageQuery, TreeNode,
+    AuditResult, Dependency, DependencyKind, DependencyTree, Ecosystem, LockfileManager,
+    PackageError, PackageInfo, PackageQuery, ReleaseNote, SearchResult, TreeBudget, TreeNode,
+    TreeOptions,
 };
 use std::path::Path;
 
@@ -52,6 +53,24 @@ impl Ecosystem for Nuget {
         None
     }
 
+    fn version_satisfies(&self, version_req: &str, version: &str) -> Option<bool> {
+        crate::version_req::satisfies(
+            version_req,
+            version,
+            crate::version_req::BareVersion::Exact,
+            crate::version_req::Tilde::Minor,
+        )
+    }
+
+    fn package_for_import<'a>(
+        &self,
+        import: &str,
+        dependencies: &'a [Dependency],
+    ) -> Option<&'a Dependency> {
+        // `using Newtonsoft.Json.Linq;` is in package `Newtonsoft.Json`
+        crate::find_longest_prefix(dependencies, import, '.')
+    }
+
     fn list_dependencies(&self, project_root: &Path) -> Result<Vec<Dependency>, PackageError> {
         // Look for .csproj files and parse PackageReference elements
         let entries = std::fs::read_dir(project_root)
@@ -80,10 +99,20 @@ impl Ecosystem for Nuget {
                                             } else {
                                                 None
                                             };
+                                        // Private assets don't flow to dependents:
+                                        // analyzers, build tooling, test frameworks
+                                        let kind = if line.contains("PrivateAssets=\"all\"")
+                                            || line.contains("PrivateAssets=\"All\"")
+                                        {
+                                            DependencyKind::Dev
+                                        } else {
+                                            DependencyKind::Normal
+                                        };
                                         deps.push(Dependency {
                                             name,
                                             version_req,
                                             optional: false,
+                                            kind,
                                         });
                                     }
                                 }
@@ -97,7 +126,11 @@ impl Ecosystem for Nuget {
         Ok(deps)
     }
 
-    fn dependency_tree(&self, project_root: &Path) -> Result<DependencyTree, PackageError> {
+    fn dependency_tree(
+        &self,
+        project_root: &Path,
+        options: &TreeOptions,
+    ) -> Result<DependencyTree, PackageError> {
         // Parse packages.lock.json
         let lockfile = project_root.join("packages.lock.json");
         let content = std::fs::read_to_string(&lockfile).map_err(|e| {
@@ -107,11 +140,16 @@ impl Ecosystem for Nuget {
             .map_err(|e| PackageError::ParseError(format!("invalid JSON: {}", e)))?;
 
         let mut framework_nodes = Vec::new();
+        let mut budget = TreeBudget::new(options);
 
         if let Some(deps) = parsed.get("dependencies").and_then(|d| d.as_object()) {
             for (framework, framework_deps) in deps {
                 let mut pkg_nodes = Vec::new();
-                if let Some(pkgs) = framework_deps.as_object() {
+                // Target frameworks are depth 1, their packages leaves below them
+                if let Some(pkgs) = framework_deps.as_object()
+                    && !pkgs.is_empty()
+                    && budget.expand(framework, 1)
+                {
                     for (name, info) in pkgs {
                         let version = info.get("resolved").and_then(|v| v.as_str()).unwrap_or("");
                         pkg_nodes.push(TreeNode {
@@ -129,13 +167,11 @@ impl Ecosystem for Nuget {
             }
         }
 
-        Ok(DependencyTree {
-            roots: vec![TreeNode {
-                name: "packages.lock.json".to_string(),
-                version: String::new(),
-                dependencies: framework_nodes,
-            }],
-        })
+        Ok(budget.finish(vec![TreeNode {
+            name: "packages.lock.json".to_string(),
+            version: String::new(),
+            dependencies: framework_nodes,
+        }]))
     }
 
     fn audit(&self, _project_root: &Path) -> Result<AuditResult, PackageError> {
@@ -143,6 +179,19 @@ impl Ecosystem for Nuget {
             "audit not yet supported for NuGet. Use: dotnet list package --vulnerable".to_string(),
         ))
     }
+
+    fn search(&self, _query: &str, _limit: usize) -> Result<Vec<SearchResult>, PackageError> {
+        Err(PackageError::NotFound(format!("{} search", self.name())))
+    }
+
+    fn release_notes(
+        &self,
+        info: &PackageInfo,
+        from: Option<&str>,
+        to: &str,
+    ) -> Result<Vec<ReleaseNote>, PackageError> {
+        crate::release_notes::from_repository(info, from, to)
+    }
 }
 
 fn fetch_nuget_info(package: &str) -> Result<PackageInfo, PackageError> {
@@ -184,6 +233,8 @@ fn fetch_nuget_info(package: &str) -> Result<PackageInfo, PackageError> {
                 repository: None,
                 features: Vec::new(),
                 dependencies: Vec::new(),
+                downloads: None,
+                published: None,
             });
         }
     };
@@ -238,6 +289,7 @@ fn parse_nuspec(xml: &str, package: &str, version: &str) -> Result<PackageInfo,
                         name: dep_name,
                         version_req,
                         optional: false,
+                        kind: DependencyKind::Normal,
                     });
                 }
             }
@@ -253,5 +305,7 @@ fn parse_nuspec(xml: &str, package: &str, version: &str) -> Result<PackageInfo,
         repository,
         features: Vec::new(),
         dependencies,
+        downloads: None,
+        published: None,
     })
 }
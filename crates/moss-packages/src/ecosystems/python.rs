@@ -1,8 +1,9 @@
 //! Python (pip/uv/poetry) ecosystem.
 
 use crate::{
-    AuditResult, Dependency, DependencyTree, Ecosystem, Feature, LockfileManager, PackageError,
-    PackageInfo, PackageQuery, TreeNode, Vulnerability, VulnerabilitySeverity,
+    AuditResult, Dependency, DependencyKind, DependencyTree, Ecosystem, Feature, LockfileManager,
+    PackageError, PackageInfo, PackageQuery, ReleaseNote, SearchResult, TreeBudget, TreeNode,
+    TreeOptions, Vulnerability, VulnerabilitySeverity,
 };
 use std::path::Path;
 use std::process::Command;
@@ -109,6 +110,28 @@ impl Ecosystem for Python {
         None
     }
 
+    fn version_satisfies(&self, version_req: &str, version: &str) -> Option<bool> {
+        crate::version_req::satisfies(
+            version_req,
+            version,
+            crate::version_req::BareVersion::Exact,
+            crate::version_req::Tilde::Minor,
+        )
+    }
+
+    fn package_for_import<'a>(
+        &self,
+        import: &str,
+        dependencies: &'a [Dependency],
+    ) -> Option<&'a Dependency> {
+        // `yaml.loader` is in module `yaml`; relative imports are local
+        if import.starts_with('.') {
+            return None;
+        }
+        let module = import.split('.').next()?;
+        crate::find_normalized(dependencies, distribution_for_module(module))
+    }
+
     fn list_dependencies(
         &self,
         project_root: &Path,
@@ -143,6 +166,7 @@ impl Ecosystem for Python {
                                     dep.as_str().and_then(|s| parse_requirement(s))
                                 {
                                     req.optional = true;
+                                    req.kind = DependencyKind::Optional;
                                     deps.push(req);
                                 }
                             }
@@ -151,26 +175,50 @@ impl Ecosystem for Python {
                 }
             }
 
-            // Poetry: [tool.poetry.dependencies]
-            if let Some(poetry) = parsed.get("tool").and_then(|t| t.get("poetry"))
-                && let Some(dependencies) = poetry.get("dependencies").and_then(|d| d.as_table())
-            {
-                for (name, value) in dependencies {
-                    if name == "python" {
-                        continue;
+            // PEP 735: [dependency-groups], and uv's older [tool.uv] dev-dependencies
+            let groups = parsed
+                .get("dependency-groups")
+                .and_then(|g| g.as_table())
+                .into_iter()
+                .flat_map(|g| g.values());
+            let uv_dev = parsed
+                .get("tool")
+                .and_then(|t| t.get("uv"))
+                .and_then(|u| u.get("dev-dependencies"));
+            for group in groups.chain(uv_dev) {
+                // `{include-group = "..."}` entries are not requirements
+                for dep in group.as_array().into_iter().flatten() {
+                    if let Some(mut req) = dep.as_str().and_then(parse_requirement) {
+                        req.kind = DependencyKind::Dev;
+                        deps.push(req);
                     }
-                    let version_req = match value {
-                        toml::Value::String(v) => Some(v.clone()),
-                        toml::Value::Table(t) => {
-                            t.get("version").and_then(|v| v.as_str()).map(String::from)
+                }
+            }
+
+            // Poetry: [tool.poetry.dependencies], dev groups and [tool.poetry.dev-dependencies]
+            if let Some(poetry) = parsed.get("tool").and_then(|t| t.get("poetry")) {
+                let main = poetry
+                    .get("dependencies")
+                    .map(|d| (d, DependencyKind::Normal));
+                let dev = poetry
+                    .get("dev-dependencies")
+                    .into_iter()
+                    .chain(
+                        poetry
+                            .get("group")
+                            .and_then(|g| g.as_table())
+                            .into_iter()
+                            .flat_map(|g| g.values())
+                            .filter_map(|group| group.get("dependencies")),
+                    )
+                    .map(|d| (d, DependencyKind::Dev));
+                for (table, kind) in main.into_iter().chain(dev) {
+                    for (name, value) in table.as_table().into_iter().flatten() {
+                        if name == "python" {
+                            continue;
                         }
-                        _ => None,
-                    };
-                    deps.push(crate::Dependency {
-                        name: name.clone(),
-                        version_req,
-                        optional: false,
-                    });
+                        deps.push(parse_poetry_dep(name, value, kind));
+                    }
                 }
             }
 
@@ -193,13 +241,17 @@ impl Ecosystem for Python {
         Err(PackageError::ParseError("no manifest found".to_string()))
     }
 
-    fn dependency_tree(&self, project_root: &Path) -> Result<DependencyTree, PackageError> {
+    fn dependency_tree(
+        &self,
+        project_root: &Path,
+        options: &TreeOptions,
+    ) -> Result<DependencyTree, PackageError> {
         // Try uv.lock first (TOML with package entries and dependencies)
         let uv_lock = project_root.join("uv.lock");
         if let Ok(content) = std::fs::read_to_string(&uv_lock)
             && let Ok(parsed) = toml::from_str::<toml::Value>(&content)
         {
-            return build_python_tree(&parsed, project_root);
+            return build_python_tree(&parsed, project_root, options);
         }
 
         // Try poetry.lock
@@ -207,7 +259,7 @@ impl Ecosystem for Python {
         if let Ok(content) = std::fs::read_to_string(&poetry_lock)
             && let Ok(parsed) = toml::from_str::<toml::Value>(&content)
         {
-            return build_python_tree(&parsed, project_root);
+            return build_python_tree(&parsed, project_root, options);
         }
 
         Err(PackageError::ParseError(
@@ -301,11 +353,51 @@ impl Ecosystem for Python {
 
         Ok(AuditResult { vulnerabilities })
     }
+
+    fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>, PackageError> {
+        search_pypi_simple_index(query, limit)
+    }
+
+    fn release_notes(
+        &self,
+        info: &PackageInfo,
+        from: Option<&str>,
+        to: &str,
+    ) -> Result<Vec<ReleaseNote>, PackageError> {
+        crate::release_notes::from_repository(info, from, to)
+    }
+}
+
+/// The distribution that provides a top-level module, for well-known packages
+/// imported under another name than they are published as; otherwise the module
+/// name itself.
+fn distribution_for_module(module: &str) -> &str {
+    match module {
+        "yaml" => "PyYAML",
+        "PIL" => "Pillow",
+        "bs4" => "beautifulsoup4",
+        "sklearn" => "scikit-learn",
+        "skimage" => "scikit-image",
+        "cv2" => "opencv-python",
+        "dateutil" => "python-dateutil",
+        "dotenv" => "python-dotenv",
+        "jwt" => "PyJWT",
+        "magic" => "python-magic",
+        "git" => "GitPython",
+        "Crypto" => "pycryptodome",
+        "OpenSSL" => "pyOpenSSL",
+        "serial" => "pyserial",
+        "usb" => "pyusb",
+        "zmq" => "pyzmq",
+        "attr" => "attrs",
+        _ => module,
+    }
 }
 
 fn build_python_tree(
     parsed: &toml::Value,
     project_root: &Path,
+    options: &TreeOptions,
 ) -> Result<DependencyTree, PackageError> {
     // Get project name from pyproject.toml
     let pyproject = project_root.join("pyproject.toml");
@@ -359,6 +451,8 @@ fn build_python_tree(
         name: &str,
         packages: &std::collections::HashMap<String, (String, Vec<String>)>,
         visited: &mut std::collections::HashSet<String>,
+        depth: usize,
+        budget: &mut TreeBudget,
     ) -> Option<TreeNode> {
         let normalized = name.to_lowercase().replace(['-', '.'], "_");
         let (version, deps) = packages.get(&normalized)?;
@@ -367,9 +461,13 @@ fn build_python_tree(
             Vec::new()
         } else {
             visited.insert(normalized);
-            deps.iter()
-                .filter_map(|dep| build_node(dep, packages, visited))
-                .collect()
+            if !deps.is_empty() && budget.expand(&format!("{}@{}", name, version), depth) {
+                deps.iter()
+                    .filter_map(|dep| build_node(dep, packages, visited, depth + 1, budget))
+                    .collect()
+            } else {
+                Vec::new()
+            }
         };
 
         Some(TreeNode {
@@ -381,6 +479,7 @@ fn build_python_tree(
 
     // Build tree from all packages
     let mut visited = std::collections::HashSet::new();
+    let mut budget = TreeBudget::new(options);
     let mut root_deps = Vec::new();
 
     if let Some(pkgs) = parsed.get("package").and_then(|p| p.as_array()) {
@@ -388,7 +487,7 @@ fn build_python_tree(
             let name = pkg.get("name").and_then(|n| n.as_str()).unwrap_or("");
             let normalized = name.to_lowercase().replace(['-', '.'], "_");
             if !visited.contains(&normalized) {
-                if let Some(node) = build_node(name, &packages, &mut visited) {
+                if let Some(node) = build_node(name, &packages, &mut visited, 1, &mut budget) {
                     root_deps.push(node);
                 }
             }
@@ -401,7 +500,61 @@ fn build_python_tree(
         dependencies: root_deps,
     };
 
-    Ok(DependencyTree { roots: vec![root] })
+    Ok(budget.finish(vec![root]))
+}
+
+fn search_pypi_simple_index(query: &str, limit: usize) -> Result<Vec<SearchResult>, PackageError> {
+    // PyPI has no search API; match names from the simple index (large, so cache it)
+    const SIMPLE_INDEX: &str = "https://pypi.org/simple/";
+    let ttl = std::time::Duration::from_secs(24 * 60 * 60);
+    let data = match crate::cache::fetch_with_cache("pypi", "simple", SIMPLE_INDEX, ttl) {
+        Ok((data, _was_cached)) => data,
+        Err(e) => {
+            crate::cache::read_index("pypi", "simple").ok_or(PackageError::RegistryError(e))?
+        }
+    };
+    let html = String::from_utf8_lossy(&data);
+    Ok(match_simple_index(&html, query, limit))
+}
+
+/// Match project names in a PEP 503 simple index page against a partial name.
+///
+/// Names are compared normalized (case-insensitive, `-`/`_`/`.` equivalent).
+/// Exact matches rank first, then prefix matches, then shorter names.
+fn match_simple_index(html: &str, query: &str, limit: usize) -> Vec<SearchResult> {
+    let normalize = |s: &str| s.to_lowercase().replace(['_', '.'], "-");
+    let needle = normalize(query);
+
+    let mut matches: Vec<(u8, &str)> = html
+        .split("</a>")
+        .filter_map(|chunk| chunk.rsplit_once('>').map(|(_, name)| name.trim()))
+        .filter(|name| !name.is_empty())
+        .filter_map(|name| {
+            let normalized = normalize(name);
+            let rank = if normalized == needle {
+                0
+            } else if normalized.starts_with(&needle) {
+                1
+            } else if normalized.contains(&needle) {
+                2
+            } else {
+                return None;
+            };
+            Some((rank, name))
+        })
+        .collect();
+    matches.sort_by(|a, b| (a.0, a.1.len(), a.1).cmp(&(b.0, b.1.len(), b.1)));
+
+    matches
+        .into_iter()
+        .take(limit)
+        .map(|(_, name)| SearchResult {
+            name: name.to_string(),
+            version: None,
+            description: None,
+            downloads: None,
+        })
+        .collect()
 }
 
 fn fetch_pypi_info(query: &PackageQuery) -> Result<PackageInfo, PackageError> {
@@ -497,6 +650,18 @@ fn parse_pypi_json(json_str: &str, package: &str) -> Result<PackageInfo, Package
         }
     }
 
+    // PyPI has no download counts; the release was published with its first file
+    let published = v
+        .get("urls")
+        .and_then(|u| u.as_array())
+        .and_then(|files| {
+            files
+                .iter()
+                .filter_map(|f| f.get("upload_time_iso_8601").and_then(|t| t.as_str()))
+                .min()
+        })
+        .map(String::from);
+
     Ok(PackageInfo {
         name,
         version,
@@ -506,9 +671,34 @@ fn parse_pypi_json(json_str: &str, package: &str) -> Result<PackageInfo, Package
         repository,
         features,
         dependencies,
+        downloads: None,
+        published,
     })
 }
 
+/// Parse a Poetry dependency entry: a version string or a table with `version`
+/// and `optional`.
+fn parse_poetry_dep(name: &str, value: &toml::Value, kind: DependencyKind) -> Dependency {
+    let (version_req, optional) = match value {
+        toml::Value::String(v) => (Some(v.clone()), false),
+        toml::Value::Table(t) => (
+            t.get("version").and_then(|v| v.as_str()).map(String::from),
+            t.get("optional").and_then(|o| o.as_bool()).unwrap_or(false),
+        ),
+        _ => (None, false),
+    };
+    Dependency {
+        name: name.to_string(),
+        version_req,
+        optional,
+        kind: if optional && kind == DependencyKind::Normal {
+            DependencyKind::Optional
+        } else {
+            kind
+        },
+    }
+}
+
 fn parse_requirement(req: &str) -> Option<Dependency> {
     // Parse PEP 508 requirement: "name[extra] (>=1.0) ; marker"
     let req = req.trim();
@@ -552,6 +742,11 @@ fn parse_requirement(req: &str) -> Option<Dependency> {
         name,
         version_req,
         optional,
+        kind: if optional {
+            DependencyKind::Optional
+        } else {
+            DependencyKind::Normal
+        },
     })
 }
 
@@ -569,12 +764,55 @@ mod tests {
         let dep = parse_requirement("pytest ; extra == 'dev'").unwrap();
         assert_eq!(dep.name, "pytest");
         assert!(dep.optional);
+        assert_eq!(dep.kind, DependencyKind::Optional);
 
         let dep = parse_requirement("numpy").unwrap();
         assert_eq!(dep.name, "numpy");
         assert_eq!(dep.version_req, None);
     }
 
+    #[test]
+    fn test_parse_poetry_dep() {
+        let table: toml::Value = toml::from_str(
+            r#"
+            requests = "^2.0"
+            redis = { version = "^5", optional = true }
+            "#,
+        )
+        .unwrap();
+        let dep = parse_poetry_dep("requests", &table["requests"], DependencyKind::Normal);
+        assert_eq!(dep.version_req.as_deref(), Some("^2.0"));
+        assert_eq!(dep.kind, DependencyKind::Normal);
+
+        let dep = parse_poetry_dep("redis", &table["redis"], DependencyKind::Normal);
+        assert_eq!(dep.kind, DependencyKind::Optional);
+
+        let dep = parse_poetry_dep("requests", &table["requests"], DependencyKind::Dev);
+        assert_eq!(dep.kind, DependencyKind::Dev);
+    }
+
+    #[test]
+    fn test_match_simple_index() {
+        let html = r#"<!DOCTYPE html>
+<html><body>
+<a href="/simple/flask-login/">Flask-Login</a>
+<a href="/simple/flask/">Flask</a>
+<a href="/simple/django/">Django</a>
+<a href="/simple/pytest-flask/">pytest-flask</a>
+</body></html>"#;
+        let names: Vec<String> = match_simple_index(html, "flask", 10)
+            .into_iter()
+            .map(|r| r.name)
+            .collect();
+        assert_eq!(names, vec!["Flask", "Flask-Login", "pytest-flask"]);
+
+        let names: Vec<String> = match_simple_index(html, "flask_login", 1)
+            .into_iter()
+            .map(|r| r.name)
+            .collect();
+        assert_eq!(names, vec!["Flask-Login"]);
+    }
+
     #[test]
     fn test_parse_pypi_json() {
         let json = r#"{
@@ -592,7 +830,11 @@ mod tests {
                     "idna>=2.5,<4"
                 ],
                 "provides_extra": ["socks"]
-            }
+            },
+            "urls": [
+                {"filename": "requests-2.32.0.tar.gz", "upload_time_iso_8601": "2024-05-20T15:46:12.123456Z"},
+                {"filename": "requests-2.32.0-py3-none-any.whl", "upload_time_iso_8601": "2024-05-20T15:46:09.987654Z"}
+            ]
         }"#;
 
         let info = parse_pypi_json(json, "requests").unwrap();
@@ -600,5 +842,51 @@ mod tests {
         assert_eq!(info.version, "2.32.0");
         assert_eq!(info.license, Some("Apache-2.0".to_string()));
         assert_eq!(info.dependencies.len(), 2);
+        assert_eq!(
+            info.published.as_deref(),
+            Some("2024-05-20T15:46:09.987654Z")
+        );
+        assert_eq!(info.downloads, None);
+    }
+
+    #[test]
+    fn test_build_python_tree_bounded() {
+        let lock: toml::Value = toml::from_str(
+            r#"
+            [[package]]
+            name = "app-core"
+            version = "1.0.0"
+            dependencies = [{ name = "requests" }]
+
+            [[package]]
+            name = "requests"
+            version = "2.32.0"
+            dependencies = [{ name = "urllib3" }]
+
+            [[package]]
+            name = "urllib3"
+            version = "2.2.0"
+            "#,
+        )
+        .unwrap();
+        let root = Path::new("/nonexistent");
+        let depth = |tree: &DependencyTree| {
+            fn depth(node: &TreeNode) -> usize {
+                1 + node.dependencies.iter().map(depth).max().unwrap_or(0)
+            }
+            depth(&tree.roots[0])
+        };
+
+        let full = build_python_tree(&lock, root, &TreeOptions::default()).unwrap();
+        assert_eq!(depth(&full), 4);
+        assert!(!full.truncated);
+
+        let options = TreeOptions {
+            max_depth: Some(1),
+            ..Default::default()
+        };
+        let direct = build_python_tree(&lock, root, &options).unwrap();
+        assert_eq!(depth(&direct), 2);
+        assert_eq!(direct.roots[0].dependencies[0].name, "app-core");
     }
 }
@@ -9,7 +9,19 @@ pub fn get(url: &str) -> Result<String, PackageError> {
 
 /// Perform a GET request with custom headers.
 pub fn get_with_headers(url: &str, headers: &[(&str, &str)]) -> Result<String, PackageError> {
+    get_with_query(url, &[], headers)
+}
+
+/// Perform a GET request with URL-encoded query parameters and custom headers.
+pub fn get_with_query(
+    url: &str,
+    query: &[(&str, &str)],
+    headers: &[(&str, &str)],
+) -> Result<String, PackageError> {
     let mut request = ureq::get(url);
+    for (key, value) in query {
+        request = request.query(key, value);
+    }
     for (key, value) in headers {
         request = request.set(key, value);
     }
@@ -39,13 +39,20 @@ mod cache;
 pub mod ecosystems;
 #[cfg(feature = "ecosystem")]
 mod http;
+#[cfg(feature = "ecosystem")]
+pub mod release_notes;
+#[cfg(feature = "ecosystem")]
+pub mod version_req;
 
 #[cfg(feature = "index")]
 pub mod index;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 #[cfg(feature = "ecosystem")]
 use std::path::Path;
+use std::time::{Duration, Instant};
 
 // ============================================================================
 // Shared types (available with either feature)
@@ -84,7 +91,7 @@ impl PackageQuery {
 }
 
 /// Information about a package from a registry.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PackageInfo {
     pub name: String,
     pub version: String,
@@ -94,10 +101,38 @@ pub struct PackageInfo {
     pub repository: Option<String>,
     pub features: Vec<Feature>,
     pub dependencies: Vec<Dependency>,
+    /// Download count reported by the registry (all-time, except npm: last week)
+    #[serde(default)]
+    pub downloads: Option<u64>,
+    /// When this version was published, as the registry's ISO 8601 timestamp
+    #[serde(default)]
+    pub published: Option<String>,
 }
 
-/// A package feature (Rust features, Python extras, npm optional deps).
+/// A package matching a registry search.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub name: String,
+    /// Latest version, if the search endpoint reports it
+    pub version: Option<String>,
+    pub description: Option<String>,
+    /// Total downloads, if the search endpoint reports them
+    pub downloads: Option<u64>,
+}
+
+/// Release notes for one version of a package.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseNote {
+    pub version: String,
+    pub title: Option<String>,
+    /// Markdown body
+    pub body: String,
+    pub url: Option<String>,
+    pub published: Option<String>,
+}
+
+/// A package feature (Rust features, Python extras, npm optional deps).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Feature {
     pub name: String,
     pub description: Option<String>,
@@ -105,15 +140,60 @@ pub struct Feature {
 }
 
 /// A package dependency.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Dependency {
     pub name: String,
     pub version_req: Option<String>,
     pub optional: bool,
+    /// Manifest section the dependency was declared in
+    #[serde(default)]
+    pub kind: DependencyKind,
+}
+
+/// How a dependency is used, from the manifest section that declares it.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyKind {
+    /// Needed at runtime
+    #[default]
+    Normal,
+    /// Only for tests and development
+    Dev,
+    /// Only for build scripts
+    Build,
+    /// Enabled by a feature or extra
+    Optional,
+    /// Expected to be provided by the dependent (npm `peerDependencies`)
+    Peer,
+}
+
+impl DependencyKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DependencyKind::Normal => "normal",
+            DependencyKind::Dev => "dev",
+            DependencyKind::Build => "build",
+            DependencyKind::Optional => "optional",
+            DependencyKind::Peer => "peer",
+        }
+    }
 }
 
 /// A node in the dependency tree.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TreeNode {
     pub name: String,
     pub version: String,
@@ -121,13 +201,82 @@ pub struct TreeNode {
 }
 
 /// Full dependency tree.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DependencyTree {
     pub roots: Vec<TreeNode>,
+    /// The time budget ran out, so some packages were left unexpanded
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+impl DependencyTree {
+    /// Tree of the given roots, complete.
+    pub fn new(roots: Vec<TreeNode>) -> Self {
+        Self {
+            roots,
+            truncated: false,
+        }
+    }
+}
+
+/// Bounds on building a [`DependencyTree`]. The default has no bounds.
+#[derive(Debug, Clone, Default)]
+pub struct TreeOptions {
+    /// Levels of dependencies to expand below the project (1 = direct
+    /// dependencies only, without their own); None for no limit
+    pub max_depth: Option<usize>,
+    /// Expand each package only where it first appears; later occurrences are
+    /// leaves
+    pub dedup: bool,
+    /// Time budget for building the tree; once spent, the remaining packages
+    /// are leaves and the tree is marked truncated
+    pub timeout: Option<Duration>,
+}
+
+/// Tracks [`TreeOptions`] while a tree is built.
+///
+/// Builders call [`TreeBudget::expand`] before building the children of a
+/// package that has dependencies, and [`TreeBudget::finish`] with the roots.
+pub(crate) struct TreeBudget<'a> {
+    options: &'a TreeOptions,
+    deadline: Option<Instant>,
+    expanded: HashSet<String>,
+    truncated: bool,
+}
+
+impl<'a> TreeBudget<'a> {
+    pub(crate) fn new(options: &'a TreeOptions) -> Self {
+        Self {
+            options,
+            deadline: options.timeout.map(|t| Instant::now() + t),
+            expanded: HashSet::new(),
+            truncated: false,
+        }
+    }
+
+    /// Whether to build the dependencies of package `key` (e.g. `name@version`)
+    /// found at `depth`, where direct dependencies of the project are depth 1.
+    pub(crate) fn expand(&mut self, key: &str, depth: usize) -> bool {
+        if self.options.max_depth.is_some_and(|max| depth >= max) {
+            return false;
+        }
+        if self.truncated || self.deadline.is_some_and(|d| Instant::now() >= d) {
+            self.truncated = true;
+            return false;
+        }
+        !self.options.dedup || self.expanded.insert(key.to_string())
+    }
+
+    pub(crate) fn finish(self, roots: Vec<TreeNode>) -> DependencyTree {
+        DependencyTree {
+            roots,
+            truncated: self.truncated,
+        }
+    }
 }
 
 /// Security vulnerability found by audit.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Vulnerability {
     pub package: String,
     pub version: String,
@@ -138,8 +287,10 @@ pub struct Vulnerability {
     pub fixed_in: Option<String>,
 }
 
-/// Severity level for vulnerabilities.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Severity level for vulnerabilities, ordered from most to least severe.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum VulnerabilitySeverity {
     Critical,
@@ -161,12 +312,84 @@ impl VulnerabilitySeverity {
     }
 }
 
+impl std::str::FromStr for VulnerabilitySeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "critical" => Ok(VulnerabilitySeverity::Critical),
+            "high" => Ok(VulnerabilitySeverity::High),
+            "medium" | "moderate" => Ok(VulnerabilitySeverity::Medium),
+            "low" => Ok(VulnerabilitySeverity::Low),
+            "unknown" => Ok(VulnerabilitySeverity::Unknown),
+            _ => Err(format!(
+                "unknown severity '{}' (expected critical, high, medium, low or unknown)",
+                s
+            )),
+        }
+    }
+}
+
 /// Result of security audit.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AuditResult {
     pub vulnerabilities: Vec<Vulnerability>,
 }
 
+/// Result of checking a lockfile against the manifest.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LockCheck {
+    /// Lockfile checked, or None if the project has none
+    pub lockfile: Option<String>,
+    /// Declared dependencies checked
+    pub checked: usize,
+    pub issues: Vec<LockIssue>,
+}
+
+/// A declared dependency the lockfile doesn't agree with.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LockIssue {
+    pub name: String,
+    pub kind: LockIssueKind,
+    /// Requirement from the manifest
+    pub version_req: Option<String>,
+    /// Version in the lockfile
+    pub locked: Option<String>,
+}
+
+/// How the lockfile disagrees with the manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LockIssueKind {
+    /// Declared but not in the lockfile
+    Missing,
+    /// Locked at a version the requirement excludes
+    Unsatisfied,
+}
+
+/// JSON Schema of the types above, as they appear in JSON output.
+///
+/// Each type is a definition under `$defs` (`PackageInfo`, `Dependency`,
+/// `Feature`, `DependencyTree`, `Vulnerability`, `AuditResult`, `LockCheck`
+/// and the types they use), so consumers can validate a value against `#/$defs/<Type>`.
+pub fn json_schema() -> serde_json::Value {
+    let mut generator = schemars::generate::SchemaSettings::draft2020_12()
+        .for_serialize()
+        .into_generator();
+    generator.subschema_for::<PackageInfo>();
+    generator.subschema_for::<Dependency>();
+    generator.subschema_for::<Feature>();
+    generator.subschema_for::<DependencyTree>();
+    generator.subschema_for::<Vulnerability>();
+    generator.subschema_for::<AuditResult>();
+    generator.subschema_for::<LockCheck>();
+    serde_json::json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "moss package types",
+        "$defs": generator.take_definitions(true),
+    })
+}
+
 /// Error type for package operations.
 #[derive(Debug)]
 pub enum PackageError {
@@ -239,14 +462,48 @@ pub trait Ecosystem: Send + Sync {
     /// List declared dependencies from manifest file.
     fn list_dependencies(&self, project_root: &Path) -> Result<Vec<Dependency>, PackageError>;
 
-    /// Get dependency tree from lockfile.
-    /// Returns structured tree data.
-    fn dependency_tree(&self, project_root: &Path) -> Result<DependencyTree, PackageError>;
+    /// Get dependency tree from lockfile, bounded by `options`.
+    /// The bounds apply while the tree is built, so a depth limit or timeout
+    /// also limits the work done on a huge lockfile.
+    fn dependency_tree(
+        &self,
+        project_root: &Path,
+        options: &TreeOptions,
+    ) -> Result<DependencyTree, PackageError>;
 
     /// Run security audit for known vulnerabilities.
     /// Default implementation returns empty result (no audit tool available).
     fn audit(&self, project_root: &Path) -> Result<AuditResult, PackageError>;
 
+    /// Search the registry for packages matching a (partial) name, best matches first.
+    /// Ecosystems without a search endpoint return `PackageError::NotFound`.
+    fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>, PackageError>;
+
+    /// Release notes for versions after `from` up to and including `to`, newest first.
+    /// Ecosystems whose packages link a GitHub repo use [`release_notes::from_repository`].
+    fn release_notes(
+        &self,
+        info: &PackageInfo,
+        from: Option<&str>,
+        to: &str,
+    ) -> Result<Vec<ReleaseNote>, PackageError>;
+
+    /// Whether a locked `version` meets a manifest requirement, or None if the
+    /// requirement isn't a version range (git, path, tags). Implementations pick
+    /// how a version without an operator and a tilde range read, see
+    /// [`version_req::BareVersion`] and [`version_req::Tilde`].
+    fn version_satisfies(&self, version_req: &str, version: &str) -> Option<bool>;
+
+    /// The declared dependency an import refers to (`serde_json::Value`, `lodash/fp`,
+    /// `yaml.loader`, `github.com/spf13/cobra`), or None for relative imports
+    /// and packages not among `dependencies`. Each ecosystem maps its own import
+    /// syntax to package names.
+    fn package_for_import<'a>(
+        &self,
+        import: &str,
+        dependencies: &'a [Dependency],
+    ) -> Option<&'a Dependency>;
+
     /// Find the first available tool in PATH.
     fn find_tool(&self) -> Option<&'static str> {
         for tool in self.tools() {
@@ -314,6 +571,161 @@ pub trait Ecosystem: Send + Sync {
     }
 }
 
+/// Check that each declared dependency is in the lockfile at a version its
+/// requirement allows. Built on [`Ecosystem::list_dependencies`],
+/// [`Ecosystem::installed_version`] and [`Ecosystem::version_satisfies`];
+/// requirements that aren't version ranges are only checked for presence.
+#[cfg(feature = "ecosystem")]
+pub fn lock_check(eco: &dyn Ecosystem, project_root: &Path) -> Result<LockCheck, PackageError> {
+    let deps = eco.list_dependencies(project_root)?;
+    let lockfile = eco
+        .lockfiles()
+        .iter()
+        .find(|l| project_root.join(l.filename).exists())
+        .map(|l| l.filename.to_string());
+    if lockfile.is_none() {
+        return Ok(LockCheck {
+            lockfile,
+            checked: 0,
+            issues: Vec::new(),
+        });
+    }
+
+    let mut issues = Vec::new();
+    for dep in &deps {
+        let locked = eco.installed_version(&dep.name, project_root);
+        let kind = match (&locked, &dep.version_req) {
+            (None, _) => LockIssueKind::Missing,
+            (Some(v), Some(req)) if eco.version_satisfies(req, v) == Some(false) => {
+                LockIssueKind::Unsatisfied
+            }
+            _ => continue,
+        };
+        issues.push(LockIssue {
+            name: dep.name.clone(),
+            kind,
+            version_req: dep.version_req.clone(),
+            locked,
+        });
+    }
+    Ok(LockCheck {
+        lockfile,
+        checked: deps.len(),
+        issues,
+    })
+}
+
+/// Convenience: search with caching, same strategy as [`Ecosystem::query`].
+///
+/// Fresh cache first, then network, then stale cache when the network is unavailable.
+#[cfg(feature = "ecosystem")]
+pub fn cached_search(
+    ecosystem: &dyn Ecosystem,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<SearchResult>, PackageError> {
+    use std::time::Duration;
+
+    let cache_name = format!("{}-search", ecosystem.name());
+    let cache_key = format!("{}@{}", query, limit);
+    let cache_ttl = Duration::from_secs(60 * 60); // 1 hour
+
+    if let Some(cached) = cache::read(&cache_name, &cache_key, cache_ttl) {
+        return Ok(cached);
+    }
+
+    match ecosystem.search(query, limit) {
+        Ok(results) => {
+            cache::write(&cache_name, &cache_key, &results);
+            Ok(results)
+        }
+        Err(e) => {
+            if let Some(cached) = cache::read_any(&cache_name, &cache_key) {
+                return Ok(cached);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Convenience: fetch release notes with caching, same strategy as [`Ecosystem::query`].
+#[cfg(feature = "ecosystem")]
+pub fn cached_release_notes(
+    ecosystem: &dyn Ecosystem,
+    info: &PackageInfo,
+    from: Option<&str>,
+    to: &str,
+) -> Result<Vec<ReleaseNote>, PackageError> {
+    use std::time::Duration;
+
+    let cache_name = format!("{}-release-notes", ecosystem.name());
+    let cache_key = format!("{}@{}..{}", info.name, from.unwrap_or(""), to);
+    let cache_ttl = Duration::from_secs(24 * 60 * 60); // 24 hours
+
+    if let Some(cached) = cache::read(&cache_name, &cache_key, cache_ttl) {
+        return Ok(cached);
+    }
+
+    match ecosystem.release_notes(info, from, to) {
+        Ok(notes) => {
+            cache::write(&cache_name, &cache_key, &notes);
+            Ok(notes)
+        }
+        Err(e) => {
+            if let Some(cached) = cache::read_any(&cache_name, &cache_key) {
+                return Ok(cached);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// The dependency named `name`, treating case, `-`, `_` and `.` as equivalent
+/// the way Cargo and PyPI do.
+#[cfg(feature = "ecosystem")]
+pub(crate) fn find_normalized<'a>(
+    dependencies: &'a [Dependency],
+    name: &str,
+) -> Option<&'a Dependency> {
+    let name = normalize_package_name(name);
+    dependencies
+        .iter()
+        .find(|dep| normalize_package_name(&dep.name) == name)
+}
+
+/// The dependency with the longest name that is `import` itself or a prefix of
+/// it ending at `separator`, ignoring ASCII case (Go module paths, .NET namespaces).
+#[cfg(feature = "ecosystem")]
+pub(crate) fn find_longest_prefix<'a>(
+    dependencies: &'a [Dependency],
+    import: &str,
+    separator: char,
+) -> Option<&'a Dependency> {
+    dependencies
+        .iter()
+        .filter(|dep| is_segment_prefix(&dep.name, import, separator))
+        .max_by_key(|dep| dep.name.len())
+}
+
+/// Whether `prefix` is `path` or its leading `separator`-delimited segments,
+/// ignoring ASCII case.
+#[cfg(feature = "ecosystem")]
+pub(crate) fn is_segment_prefix(prefix: &str, path: &str, separator: char) -> bool {
+    !prefix.is_empty()
+        && path.len() >= prefix.len()
+        && path.is_char_boundary(prefix.len())
+        && path[..prefix.len()].eq_ignore_ascii_case(prefix)
+        && path[prefix.len()..]
+            .chars()
+            .next()
+            .is_none_or(|c| c == separator)
+}
+
+#[cfg(feature = "ecosystem")]
+fn normalize_package_name(name: &str) -> String {
+    name.to_lowercase().replace(['-', '.'], "_")
+}
+
 /// Check if a command exists in PATH.
 #[cfg(feature = "ecosystem")]
 fn which(cmd: &str) -> bool {
@@ -346,3 +758,163 @@ pub use ecosystems::{
     all_ecosystems, detect_all_ecosystems, detect_ecosystem, get_ecosystem, list_ecosystems,
     register as register_ecosystem,
 };
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_schema_defs() {
+        let schema = json_schema();
+        let defs = schema["$defs"].as_object().unwrap();
+        for name in [
+            "PackageInfo",
+            "Dependency",
+            "DependencyKind",
+            "Feature",
+            "DependencyTree",
+            "Vulnerability",
+            "AuditResult",
+            "LockCheck",
+        ] {
+            assert!(defs.contains_key(name), "missing $defs/{}", name);
+        }
+    }
+
+    #[test]
+    fn test_json_schema_validates_package_info() {
+        let mut schema = json_schema();
+        schema["$ref"] = serde_json::json!("#/$defs/PackageInfo");
+        let validator = jsonschema::validator_for(&schema).unwrap();
+
+        let info = PackageInfo {
+            name: "serde".to_string(),
+            version: "1.0.200".to_string(),
+            description: Some("A serialization framework".to_string()),
+            license: None,
+            homepage: None,
+            repository: None,
+            features: vec![Feature {
+                name: "std".to_string(),
+                description: None,
+                dependencies: Vec::new(),
+            }],
+            dependencies: vec![Dependency {
+                name: "serde_derive".to_string(),
+                version_req: Some("=1.0.200".to_string()),
+                optional: true,
+                kind: DependencyKind::Optional,
+            }],
+            downloads: Some(1),
+            published: None,
+        };
+        let mut value = serde_json::to_value(&info).unwrap();
+        let errors: Vec<String> = validator
+            .iter_errors(&value)
+            .map(|e| e.to_string())
+            .collect();
+        assert!(errors.is_empty(), "{:?}", errors);
+
+        value["version"] = serde_json::json!(1);
+        assert!(!validator.is_valid(&value));
+    }
+
+    #[cfg(feature = "ecosystem")]
+    #[test]
+    fn test_package_for_import() {
+        use ecosystems::*;
+
+        fn found(eco: &dyn Ecosystem, import: &str, names: &[&str]) -> Option<String> {
+            let deps: Vec<Dependency> = names
+                .iter()
+                .map(|name| Dependency {
+                    name: name.to_string(),
+                    version_req: None,
+                    optional: false,
+                    kind: DependencyKind::Normal,
+                })
+                .collect();
+            eco.package_for_import(import, &deps)
+                .map(|d| d.name.clone())
+        }
+        let name = |s: &str| Some(s.to_string());
+
+        assert_eq!(
+            found(&Cargo, "serde_json::Value", &["serde-json"]),
+            name("serde-json")
+        );
+        assert_eq!(found(&Cargo, "crate::util", &["util"]), None);
+        assert_eq!(found(&Npm, "lodash/fp", &["lodash"]), name("lodash"));
+        assert_eq!(
+            found(&Npm, "@scope/pkg/x", &["@scope/pkg"]),
+            name("@scope/pkg")
+        );
+        assert_eq!(found(&Npm, "./lodash", &["lodash"]), None);
+        assert_eq!(found(&Deno, "std/fs/mod.ts", &["std/"]), name("std/"));
+        assert_eq!(
+            found(&Deno, "jsr:@std/path@1/join", &["@std/path"]),
+            name("@std/path")
+        );
+        assert_eq!(found(&Python, "yaml.loader", &["PyYAML"]), name("PyYAML"));
+        assert_eq!(
+            found(&Python, "requests.auth", &["requests"]),
+            name("requests")
+        );
+        assert_eq!(found(&Python, ".models", &["models"]), None);
+        assert_eq!(
+            found(
+                &Go,
+                "github.com/spf13/cobra/doc",
+                &["github.com/spf13/cob", "github.com/spf13/cobra"]
+            ),
+            name("github.com/spf13/cobra")
+        );
+        assert_eq!(
+            found(
+                &Nuget,
+                "Newtonsoft.Json.Linq",
+                &["Newtonsoft.Json", "Newtonsoft.Json.Bson"]
+            ),
+            name("Newtonsoft.Json")
+        );
+        assert_eq!(
+            found(
+                &Maven,
+                "org.apache.commons.lang3.StringUtils",
+                &["org.apache.commons:commons-lang3"]
+            ),
+            name("org.apache.commons:commons-lang3")
+        );
+        assert_eq!(
+            found(
+                &Composer,
+                "Symfony\\Component\\Console\\Command",
+                &["symfony/yaml", "symfony/console"]
+            ),
+            name("symfony/console")
+        );
+        assert_eq!(
+            found(&Composer, "GuzzleHttp\\Client", &["guzzlehttp/guzzle"]),
+            name("guzzlehttp/guzzle")
+        );
+        assert_eq!(
+            found(&Gem, "rspec/core", &["rspec", "rspec-core"]),
+            name("rspec-core")
+        );
+        assert_eq!(
+            found(&Gem, "active_support/core_ext", &["activesupport"]),
+            name("activesupport")
+        );
+        assert_eq!(
+            found(
+                &Hex,
+                "Phoenix.LiveView.Socket",
+                &["phoenix", "phoenix_live_view"]
+            ),
+            name("phoenix_live_view")
+        );
+        assert_eq!(found(&Hex, ":cowboy", &["cowboy"]), name("cowboy"));
+        assert_eq!(found(&Conan, "fmt/core.h", &["fmt"]), name("fmt"));
+        assert_eq!(found(&Nix, "<nixpkgs>", &["nixpkgs"]), name("nixpkgs"));
+    }
+}
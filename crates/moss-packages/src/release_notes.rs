@@ -0,0 +1,285 @@
+//! Release notes from a package's GitHub repository.
+//!
+//! Registries rarely carry changelogs, but most packages link their source repo.
+//! GitHub Releases are tried first; repos that don't publish releases often keep a
+//! `CHANGELOG.md`, which is parsed by version headings as a fallback.
+
+use crate::{PackageError, PackageInfo, ReleaseNote};
+use std::cmp::Ordering;
+
+/// A GitHub repository reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GithubRepo {
+    pub owner: String,
+    pub name: String,
+}
+
+/// Extract the GitHub repository from a repository URL.
+///
+/// Accepts `https://github.com/o/r`, `git+https://github.com/o/r.git`,
+/// `git@github.com:o/r.git` and URLs with trailing paths (`/tree/main/crate`).
+pub fn github_repo(url: &str) -> Option<GithubRepo> {
+    let (_, rest) = url.split_once("github.com")?;
+    let mut parts = rest
+        .trim_start_matches([':', '/'])
+        .split('/')
+        .filter(|s| !s.is_empty());
+    let owner = parts.next()?;
+    let name = parts.next()?.trim_end_matches(".git");
+    if name.is_empty() {
+        return None;
+    }
+    Some(GithubRepo {
+        owner: owner.to_string(),
+        name: name.to_string(),
+    })
+}
+
+/// Release notes for versions after `from` up to and including `to`, newest first.
+///
+/// With no `from`, only the notes for `to` are returned.
+pub fn from_repository(
+    info: &PackageInfo,
+    from: Option<&str>,
+    to: &str,
+) -> Result<Vec<ReleaseNote>, PackageError> {
+    let repo = info
+        .repository
+        .as_deref()
+        .and_then(github_repo)
+        .ok_or_else(|| PackageError::NotFound(format!("GitHub repository for {}", info.name)))?;
+
+    let releases = fetch_github_releases(&repo, &info.name).map(|notes| in_range(notes, from, to));
+    match releases {
+        Ok(notes) if !notes.is_empty() => Ok(notes),
+        Ok(notes) => Ok(fetch_changelog(&repo)
+            .map(|content| in_range(parse_changelog(&content), from, to))
+            .unwrap_or(notes)),
+        Err(e) => fetch_changelog(&repo)
+            .map(|content| in_range(parse_changelog(&content), from, to))
+            .map_err(|_| e),
+    }
+}
+
+fn fetch_github_releases(
+    repo: &GithubRepo,
+    package: &str,
+) -> Result<Vec<ReleaseNote>, PackageError> {
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/releases?per_page=100",
+        repo.owner, repo.name
+    );
+    let token = std::env::var("GITHUB_TOKEN").ok();
+    let auth = token.as_ref().map(|t| format!("Bearer {}", t));
+    let mut headers = vec![
+        ("User-Agent", "moss-packages"),
+        ("Accept", "application/vnd.github+json"),
+    ];
+    if let Some(auth) = &auth {
+        headers.push(("Authorization", auth));
+    }
+    let body = crate::http::get_with_headers(&url, &headers)?;
+    parse_github_releases(&body, package)
+}
+
+fn fetch_changelog(repo: &GithubRepo) -> Result<String, PackageError> {
+    let url = format!(
+        "https://raw.githubusercontent.com/{}/{}/HEAD/CHANGELOG.md",
+        repo.owner, repo.name
+    );
+    crate::http::get_with_headers(&url, &[("User-Agent", "moss-packages")])
+}
+
+/// Parse a GitHub releases API response. Tags that don't name a version of
+/// `package` (other crates in a monorepo, non-version tags) are skipped.
+fn parse_github_releases(body: &str, package: &str) -> Result<Vec<ReleaseNote>, PackageError> {
+    let v: serde_json::Value = serde_json::from_str(body)
+        .map_err(|e| PackageError::ParseError(format!("invalid JSON: {}", e)))?;
+    let releases = v
+        .as_array()
+        .ok_or_else(|| PackageError::ParseError("expected release list".to_string()))?;
+
+    Ok(releases
+        .iter()
+        .filter(|r| !r.get("draft").and_then(|d| d.as_bool()).unwrap_or(false))
+        .filter_map(|r| {
+            let tag = r.get("tag_name")?.as_str()?;
+            Some(ReleaseNote {
+                version: tag_version(tag, package)?.to_string(),
+                title: r
+                    .get("name")
+                    .and_then(|n| n.as_str())
+                    .filter(|n| !n.is_empty())
+                    .map(String::from),
+                body: r
+                    .get("body")
+                    .and_then(|b| b.as_str())
+                    .unwrap_or("")
+                    .trim()
+                    .to_string(),
+                url: r.get("html_url").and_then(|u| u.as_str()).map(String::from),
+                published: r
+                    .get("published_at")
+                    .and_then(|p| p.as_str())
+                    .map(String::from),
+            })
+        })
+        .collect())
+}
+
+/// Version named by a release tag: `v1.2.0`, `1.2.0`, `serde-1.2.0`, `pkg@1.2.0`.
+fn tag_version<'a>(tag: &'a str, package: &str) -> Option<&'a str> {
+    let tag = match tag.strip_prefix(package) {
+        Some(rest) => rest.trim_start_matches(['-', '_', '@', '/']),
+        None => tag,
+    };
+    let version = tag.strip_prefix('v').unwrap_or(tag);
+    version
+        .starts_with(|c: char| c.is_ascii_digit())
+        .then_some(version)
+}
+
+/// Split a changelog into sections at headings that name a version
+/// (`## [1.2.0] - 2024-01-01`, `## v1.2.0`, `# 1.2.0`).
+fn parse_changelog(content: &str) -> Vec<ReleaseNote> {
+    let mut notes: Vec<ReleaseNote> = Vec::new();
+    let mut body = String::new();
+
+    for line in content.lines() {
+        let heading_version = line
+            .strip_prefix('#')
+            .and_then(|h| h.trim_start_matches('#').split_whitespace().next())
+            .map(|word| word.trim_matches(['[', ']']))
+            .map(|word| word.strip_prefix('v').unwrap_or(word))
+            .filter(|word| word.starts_with(|c: char| c.is_ascii_digit()) && word.contains('.'));
+
+        if let Some(version) = heading_version {
+            if let Some(last) = notes.last_mut() {
+                last.body = body.trim().to_string();
+            }
+            body.clear();
+            notes.push(ReleaseNote {
+                version: version.to_string(),
+                title: Some(line.trim_start_matches('#').trim().to_string()),
+                body: String::new(),
+                url: None,
+                published: None,
+            });
+        } else if !notes.is_empty() {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+    if let Some(last) = notes.last_mut() {
+        last.body = body.trim().to_string();
+    }
+    notes
+}
+
+/// Keep notes with `from < version <= to` (only `to` when `from` is None), newest first.
+fn in_range(mut notes: Vec<ReleaseNote>, from: Option<&str>, to: &str) -> Vec<ReleaseNote> {
+    notes.retain(|n| match from {
+        Some(from) => {
+            compare_versions(&n.version, from) == Ordering::Greater
+                && compare_versions(&n.version, to) != Ordering::Greater
+        }
+        None => compare_versions(&n.version, to) == Ordering::Equal,
+    });
+    notes.sort_by(|a, b| compare_versions(&b.version, &a.version));
+    notes
+}
+
+/// Semver-like comparison: numeric release components, then a release sorts
+/// after its pre-releases (`1.0.0-rc.1 < 1.0.0`). Build metadata is ignored.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let split = |v: &str| {
+        let v = v.split('+').next().unwrap_or(v);
+        match v.split_once('-') {
+            Some((release, pre)) => (release.to_string(), Some(pre.to_string())),
+            None => (v.to_string(), None),
+        }
+    };
+    let (release_a, pre_a) = split(a);
+    let (release_b, pre_b) = split(b);
+
+    let nums = |r: &str| -> Vec<u64> { r.split('.').map(|p| p.parse().unwrap_or(0)).collect() };
+    let (na, nb) = (nums(&release_a), nums(&release_b));
+    for i in 0..na.len().max(nb.len()) {
+        let ord = na.get(i).unwrap_or(&0).cmp(nb.get(i).unwrap_or(&0));
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+
+    match (pre_a, pre_b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(pa), Some(pb)) => pa.cmp(&pb),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_github_repo() {
+        let expected = Some(GithubRepo {
+            owner: "serde-rs".to_string(),
+            name: "serde".to_string(),
+        });
+        assert_eq!(github_repo("https://github.com/serde-rs/serde"), expected);
+        assert_eq!(
+            github_repo("git+https://github.com/serde-rs/serde.git"),
+            expected
+        );
+        assert_eq!(github_repo("git@github.com:serde-rs/serde.git"), expected);
+        assert_eq!(
+            github_repo("https://github.com/serde-rs/serde/tree/master/serde"),
+            expected
+        );
+        assert_eq!(github_repo("https://gitlab.com/a/b"), None);
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.10.0", "1.9.0"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0", "1.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.0.0-rc.1", "1.0.0"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.0+build", "1.0.0"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_parse_github_releases_in_range() {
+        let json = r#"[
+            {"tag_name": "serde-v1.0.3", "name": "v1.0.3", "body": "Fix\n", "draft": false},
+            {"tag_name": "v1.0.2", "name": "", "body": "Feature"},
+            {"tag_name": "serde_derive-1.0.2", "body": "Other crate"},
+            {"tag_name": "v1.0.1", "body": "Old"},
+            {"tag_name": "v1.0.4", "body": "Unreleased", "draft": true}
+        ]"#;
+        let notes = parse_github_releases(json, "serde").unwrap();
+        assert_eq!(notes.len(), 3);
+
+        let notes = in_range(notes, Some("1.0.1"), "1.0.3");
+        let versions: Vec<&str> = notes.iter().map(|n| n.version.as_str()).collect();
+        assert_eq!(versions, vec!["1.0.3", "1.0.2"]);
+        assert_eq!(notes[0].body, "Fix");
+        assert_eq!(notes[1].title, None);
+    }
+
+    #[test]
+    fn test_parse_changelog() {
+        let content = "# Changelog\n\n## [Unreleased]\n\n## [2.0.0] - 2024-02-01\n\n### Changed\n- Breaking\n\n## v1.1.0\n- Added thing\n\n## 1.0.0\n- Initial\n";
+        let notes = parse_changelog(content);
+        let versions: Vec<&str> = notes.iter().map(|n| n.version.as_str()).collect();
+        assert_eq!(versions, vec!["2.0.0", "1.1.0", "1.0.0"]);
+        assert_eq!(notes[0].title.as_deref(), Some("[2.0.0] - 2024-02-01"));
+        assert_eq!(notes[0].body, "### Changed\n- Breaking");
+
+        let notes = in_range(notes, Some("1.0.0"), "2.0.0");
+        assert_eq!(notes.len(), 2);
+        assert_eq!(in_range(parse_changelog(content), None, "1.1.0").len(), 1);
+    }
+}
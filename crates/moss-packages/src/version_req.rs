@@ -0,0 +1,212 @@
+//! Checking versions against manifest version requirements.
+//!
+//! One parser covers the requirement syntaxes of the common manifests:
+//!
+//! - Comparators: `>=1.2`, `<2`, `==1.4.2`, `!=1.5`, `=1.2.3`
+//! - Caret and tilde ranges: `^1.2.3`, `~1.2`, and pip's `~=1.4.2`
+//! - Wildcards: `1.2.x`, `1.*`, `==1.2.*`, `*`
+//! - npm hyphen ranges (`1.2 - 1.4`) and `||` alternatives (Composer also writes `|`)
+//! - Comparators joined by `,` (Cargo, pip) or spaces (npm)
+//!
+//! A requirement without an operator is read as [`BareVersion::Exact`] by
+//! default; Cargo reads it as a caret range. Tilde ranges differ between npm
+//! and Composer, see [`Tilde`]. Anything else (git URLs, paths, `workspace:*`,
+//! dist-tags) is not understood, and checks return `None`.
+
+use crate::release_notes::compare_versions;
+use std::cmp::Ordering;
+
+/// How a version written without an operator (`1.2.3`) is matched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BareVersion {
+    /// Versions starting with it: `1.2` matches `1.2.0` and `1.2.7` (npm, pip, Poetry)
+    Exact,
+    /// Compatible versions, as `^1.2.3` (Cargo)
+    Caret,
+}
+
+/// Which components a tilde range (`~1.2`) keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tilde {
+    /// Up to the minor version: `~1.2` is `>=1.2 <1.3`, `~1` is `>=1 <2` (npm, Cargo)
+    Minor,
+    /// All but the last component given: `~1.2` is `>=1.2 <2.0`, `~1.2.3` is
+    /// `>=1.2.3 <1.3.0` (Composer)
+    AllButLast,
+}
+
+/// Whether `version` meets `req`, or None if `req` isn't understood.
+pub fn satisfies(req: &str, version: &str, bare: BareVersion, tilde: Tilde) -> Option<bool> {
+    let req = req.trim().trim_start_matches('(').trim_end_matches(')');
+    let version = version.trim().trim_start_matches('v');
+    let mut any = false;
+    // `||` splits into an empty alternative between the bars, which isn't one
+    for alternative in req.split('|').filter(|a| !a.is_empty()) {
+        // Evaluate every alternative, so that one not understood fails the check
+        if comparators(alternative)?
+            .iter()
+            .try_fold(true, |ok, c| Some(ok & matches(c, version, bare, tilde)?))?
+        {
+            any = true;
+        }
+    }
+    Some(any)
+}
+
+/// Comparators of one `||` (or `|`) alternative, with operators joined to their
+/// versions (`>= 1.2` is `>=1.2`) and hyphen ranges expanded.
+fn comparators(alternative: &str) -> Option<Vec<String>> {
+    let tokens: Vec<&str> = alternative
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|t| !t.is_empty())
+        .collect();
+    if let [low, "-", high] = tokens.as_slice() {
+        return Some(vec![format!(">={}", low), format!("<={}", high)]);
+    }
+    let mut result = Vec::new();
+    let mut pending = String::new();
+    for token in tokens {
+        pending.push_str(token);
+        if token.chars().all(|c| "<>=!~^".contains(c)) {
+            continue;
+        }
+        result.push(std::mem::take(&mut pending));
+    }
+    if !pending.is_empty() {
+        return None;
+    }
+    if result.is_empty() {
+        result.push("*".to_string());
+    }
+    Some(result)
+}
+
+/// Whether `version` meets a single comparator, or None if it isn't understood.
+fn matches(comparator: &str, version: &str, bare: BareVersion, tilde: Tilde) -> Option<bool> {
+    let op_len = comparator
+        .find(|c: char| !"<>=!~^".contains(c))
+        .unwrap_or(comparator.len());
+    let (op, target) = comparator.split_at(op_len);
+    let target = target.trim_start_matches('v');
+
+    // Leading release components, up to the first wildcard
+    let mut parts = Vec::new();
+    let mut wildcard = false;
+    for part in target.split('.') {
+        if matches!(part, "*" | "x" | "X") {
+            wildcard = true;
+            break;
+        }
+        let number = part.split(['-', '+']).next().unwrap_or(part);
+        if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        parts.push(number.parse::<u64>().ok()?);
+    }
+    if parts.is_empty() && !wildcard {
+        return None;
+    }
+    let cmp = compare_versions(version, target);
+
+    Some(match op {
+        _ if wildcard => match op {
+            "" | "=" | "==" => prefix_matches(version, &parts),
+            "!=" => !prefix_matches(version, &parts),
+            _ => return None,
+        },
+        "" if bare == BareVersion::Caret => caret(version, target, &parts),
+        "" | "=" => prefix_matches(version, &parts) && cmp != Ordering::Less,
+        "==" | "===" => cmp == Ordering::Equal,
+        "!=" => cmp != Ordering::Equal,
+        ">" => cmp == Ordering::Greater,
+        ">=" => cmp != Ordering::Less,
+        "<" => cmp == Ordering::Less,
+        "<=" => cmp != Ordering::Greater,
+        "^" => caret(version, target, &parts),
+        "~" => {
+            let kept = match tilde {
+                Tilde::Minor => parts.len().min(2),
+                Tilde::AllButLast => parts.len().saturating_sub(1).max(1),
+            };
+            cmp != Ordering::Less && prefix_matches(version, &parts[..kept])
+        }
+        // `~=1.4.2` keeps all but the last component; `~=2` is not valid
+        "~=" if parts.len() > 1 => {
+            cmp != Ordering::Less && prefix_matches(version, &parts[..parts.len() - 1])
+        }
+        _ => return None,
+    })
+}
+
+/// `^target`: at least `target`, with the same leftmost non-zero component.
+fn caret(version: &str, target: &str, parts: &[u64]) -> bool {
+    let fixed = parts
+        .iter()
+        .position(|&p| p != 0)
+        .map_or(parts.len(), |i| i + 1);
+    compare_versions(version, target) != Ordering::Less && prefix_matches(version, &parts[..fixed])
+}
+
+/// Whether the release components of `version` start with `prefix`.
+fn prefix_matches(version: &str, prefix: &[u64]) -> bool {
+    let release = version.split(['-', '+']).next().unwrap_or(version);
+    let mut components = release.split('.').map(|p| p.parse::<u64>().unwrap_or(0));
+    prefix.iter().all(|&p| components.next().unwrap_or(0) == p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(req: &str, version: &str) -> Option<bool> {
+        satisfies(req, version, BareVersion::Exact, Tilde::Minor)
+    }
+
+    #[test]
+    fn test_satisfies() {
+        // Cargo
+        let cargo = |req, version| satisfies(req, version, BareVersion::Caret, Tilde::Minor);
+        assert_eq!(cargo("1.2", "1.9.0"), Some(true));
+        assert_eq!(cargo("1.2", "2.0.0"), Some(false));
+        assert_eq!(cargo("0.4.1", "0.4.9"), Some(true));
+        assert_eq!(cargo("0.4.1", "0.5.0"), Some(false));
+        assert_eq!(cargo(">=1.0, <1.5", "1.4.2"), Some(true));
+        assert_eq!(cargo("~1.2", "1.3.0"), Some(false));
+        assert_eq!(cargo("=1.2.3", "1.2.3"), Some(true));
+
+        // npm
+        assert_eq!(check("^18.2.0", "18.3.1"), Some(true));
+        assert_eq!(check("^0.0.3", "0.0.4"), Some(false));
+        assert_eq!(check("~4.17", "4.17.21"), Some(true));
+        assert_eq!(check("1.x", "1.8.0"), Some(true));
+        assert_eq!(check(">= 2.1 < 3", "3.0.0"), Some(false));
+        assert_eq!(check("1.2 - 1.4", "1.4"), Some(true));
+        assert_eq!(check("^1.0.0 || ^2.0.0", "2.5.0"), Some(true));
+        assert_eq!(check("4.17.21", "4.17.20"), Some(false));
+        assert_eq!(check("*", "0.1.0"), Some(true));
+
+        // Composer
+        let composer =
+            |req, version| satisfies(req, version, BareVersion::Exact, Tilde::AllButLast);
+        assert_eq!(composer("~1.2", "1.9.0"), Some(true));
+        assert_eq!(composer("~1.2", "2.0.0"), Some(false));
+        assert_eq!(composer("~1.2.3", "1.2.9"), Some(true));
+        assert_eq!(composer("~1.2.3", "1.3.0"), Some(false));
+        assert_eq!(composer("~1", "1.5.0"), Some(true));
+        assert_eq!(composer("^1.0|^2.0", "2.3.0"), Some(true));
+        assert_eq!(composer("^1.0|^2.0", "3.0.0"), Some(false));
+        assert_eq!(composer("^1.0 || ^2.0", "1.1.0"), Some(true));
+
+        // pip and Poetry
+        assert_eq!(check(">=2.0,!=2.1.0", "2.1.0"), Some(false));
+        assert_eq!(check("~=1.4.2", "1.4.9"), Some(true));
+        assert_eq!(check("~=1.4.2", "1.5.0"), Some(false));
+        assert_eq!(check("==1.2.*", "1.2.7"), Some(true));
+        assert_eq!(check("(>=1.0)", "1.1"), Some(true));
+
+        // Not version requirements
+        assert_eq!(check("workspace:*", "1.0.0"), None);
+        assert_eq!(check("git+https://github.com/a/b.git", "1.0.0"), None);
+        assert_eq!(check("latest", "1.0.0"), None);
+    }
+}
@@ -1,7 +1,8 @@
-//! Embedded builtin rules for syntax-based linting.
+//! Embedded builtin rules for syntax-based linting, and route queries.
 //!
 //! Rules are embedded at compile time and loaded as the lowest-priority source.
-//! Users can override or disable them via ~/.config/moss/rules/ or .moss/rules/.
+//! Users can override or disable them via ~/.config/moss/rules/ or .moss/rules/
+//! (route queries: ~/.config/moss/routes/ or .moss/routes/).
 
 use crate::BuiltinRule;
 
@@ -102,3 +103,30 @@ pub const BUILTIN_RULES: &[BuiltinRule] = &[
         content: include_str!("no_fixme_comment.scm"),
     },
 ];
+
+/// Embedded route queries for `moss analyze routes`.
+///
+/// Each query captures `@method`, `@path` and usually `@handler` for one HTTP
+/// route definition; the rule message names the framework.
+pub const BUILTIN_ROUTES: &[BuiltinRule] = &[
+    BuiltinRule {
+        id: "python/flask-fastapi",
+        content: include_str!("route_python.scm"),
+    },
+    BuiltinRule {
+        id: "js/express",
+        content: include_str!("route_express.scm"),
+    },
+    BuiltinRule {
+        id: "rust/axum",
+        content: include_str!("route_axum.scm"),
+    },
+    BuiltinRule {
+        id: "rust/actix",
+        content: include_str!("route_actix.scm"),
+    },
+    BuiltinRule {
+        id: "java/spring",
+        content: include_str!("route_spring.scm"),
+    },
+];
@@ -0,0 +1,40 @@
+# ---
+# id = "rust/actix"
+# message = "actix-web"
+# languages = ["rust"]
+# ---
+
+; Detects: #[get("/users/{id}")] async fn show_user(...)
+((attribute_item
+  (attribute
+    (identifier) @method
+    arguments: (token_tree
+      .
+      (string_literal) @path))) @match
+  .
+  (function_item
+    name: (identifier) @handler)
+  (#any-of? @method "get" "post" "put" "patch" "delete" "head" "options" "trace" "route"))
+
+; Detects: App::new().route("/users/{id}", web::get().to(show_user))
+(call_expression
+  function: (field_expression
+    field: (field_identifier) @_route)
+  arguments: (arguments
+    .
+    (string_literal) @path
+    .
+    (call_expression
+      function: (field_expression
+        value: (call_expression
+          function: [
+            (identifier) @method
+            (scoped_identifier name: (identifier) @method)
+          ])
+        field: (field_identifier) @_to)
+      arguments: (arguments
+        .
+        (_) @handler)))
+  (#eq? @_route "route")
+  (#eq? @_to "to")
+  (#any-of? @method "get" "post" "put" "patch" "delete" "head" "options" "trace")) @match
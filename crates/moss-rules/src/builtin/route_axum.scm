@@ -0,0 +1,25 @@
+# ---
+# id = "rust/axum"
+# message = "axum"
+# languages = ["rust"]
+# ---
+
+; Detects: Router::new().route("/users/:id", get(show_user))
+; Only the first method of a chained method router (get(a).post(b)) is seen.
+(call_expression
+  function: (field_expression
+    field: (field_identifier) @_route)
+  arguments: (arguments
+    .
+    (string_literal) @path
+    .
+    (call_expression
+      function: [
+        (identifier) @method
+        (scoped_identifier name: (identifier) @method)
+      ]
+      arguments: (arguments
+        .
+        (_) @handler)))
+  (#eq? @_route "route")
+  (#any-of? @method "get" "post" "put" "patch" "delete" "head" "options" "trace" "any")) @match
@@ -0,0 +1,19 @@
+# ---
+# id = "js/express"
+# message = "Express"
+# languages = ["javascript", "typescript", "tsx"]
+# ---
+
+; Detects: app.get('/users/:id', handler), router.post(`/x`, auth, (req, res) => ...)
+; The handler is the last argument; paths must start with "/" so that
+; map.get('key', fallback) doesn't count.
+(call_expression
+  function: (member_expression
+    property: (property_identifier) @method)
+  arguments: (arguments
+    .
+    [(string) (template_string)] @path
+    (_) @handler
+    .)
+  (#any-of? @method "get" "post" "put" "patch" "delete" "head" "options" "all")
+  (#match? @path "^['\"`]/")) @match
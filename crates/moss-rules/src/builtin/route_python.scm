@@ -0,0 +1,18 @@
+# ---
+# id = "python/flask-fastapi"
+# message = "Flask/FastAPI"
+# languages = ["python"]
+# ---
+
+; Detects: @app.get("/items/{id}"), @router.post(...), @app.route("/x")
+(decorated_definition
+  (decorator
+    (call
+      function: (attribute
+        attribute: (identifier) @method)
+      arguments: (argument_list
+        .
+        (string) @path)))
+  definition: (function_definition
+    name: (identifier) @handler)
+  (#any-of? @method "get" "post" "put" "patch" "delete" "head" "options" "route" "api_route" "websocket")) @match
@@ -0,0 +1,37 @@
+# ---
+# id = "java/spring"
+# message = "Spring"
+# languages = ["java"]
+# ---
+
+; Detects: @GetMapping("/users/{id}")
+(method_declaration
+  (modifiers
+    (annotation
+      name: (identifier) @method
+      arguments: (annotation_argument_list
+        .
+        (string_literal) @path)))
+  name: (identifier) @handler
+  (#any-of? @method "GetMapping" "PostMapping" "PutMapping" "PatchMapping" "DeleteMapping" "RequestMapping")) @match
+
+; Detects: @PostMapping(value = "/users"), @RequestMapping(path = "/users")
+(method_declaration
+  (modifiers
+    (annotation
+      name: (identifier) @method
+      arguments: (annotation_argument_list
+        (element_value_pair
+          key: (identifier) @_key
+          value: (string_literal) @path))))
+  name: (identifier) @handler
+  (#any-of? @method "GetMapping" "PostMapping" "PutMapping" "PatchMapping" "DeleteMapping" "RequestMapping")
+  (#any-of? @_key "value" "path")) @match
+
+; Detects: @GetMapping without a path (the class-level mapping's path)
+(method_declaration
+  (modifiers
+    (marker_annotation
+      name: (identifier) @method))
+  name: (identifier) @handler
+  (#any-of? @method "GetMapping" "PostMapping" "PutMapping" "PatchMapping" "DeleteMapping" "RequestMapping")) @match
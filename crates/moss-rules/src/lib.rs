@@ -4,6 +4,7 @@
 //! - Rule loading from multiple sources (builtins, user global, project)
 //! - Rule execution with combined query optimization
 //! - Pluggable data sources for rule conditionals
+//! - Route queries for `moss analyze routes`, loaded the same way
 //!
 //! # Rule File Format
 //!
@@ -29,9 +30,13 @@ mod loader;
 mod runner;
 mod sources;
 
-pub use builtin::BUILTIN_RULES;
-pub use loader::{RuleOverride, RulesConfig, load_all_rules, parse_rule_content};
-pub use runner::{DebugFlags, Finding, apply_fixes, evaluate_predicates, run_rules};
+pub use builtin::{BUILTIN_ROUTES, BUILTIN_RULES};
+pub use loader::{
+    RuleOverride, RulesConfig, load_all_rules, load_route_queries, parse_rule_content,
+};
+pub use runner::{
+    DebugFlags, Finding, apply_fixes, evaluate_predicates, run_rules, run_rules_on_source,
+};
 pub use sources::{
     EnvSource, GitSource, GoSource, PathSource, PythonSource, RuleSource, RustSource,
     SourceContext, SourceRegistry, TypeScriptSource, builtin_registry,
@@ -4,9 +4,12 @@
 //! 1. Embedded builtins (compiled into moss)
 //! 2. User global rules (`~/.config/moss/rules/*.scm`)
 //! 3. Project rules (`.moss/rules/*.scm`)
+//!
+//! Route queries (`moss analyze routes`) use the same format and order, from
+//! `routes/` directories instead.
 
-use crate::builtin::BUILTIN_RULES;
-use crate::{Rule, Severity};
+use crate::builtin::{BUILTIN_ROUTES, BUILTIN_RULES};
+use crate::{BuiltinRule, Rule, Severity};
 use glob::Pattern;
 use rhizome_moss_derive::Merge;
 use serde::{Deserialize, Serialize};
@@ -21,7 +24,7 @@ use std::path::{Path, PathBuf};
 pub struct RulesConfig(pub HashMap<String, RuleOverride>);
 
 /// Per-rule configuration override.
-#[derive(Debug, Clone, Deserialize, Serialize, Default, schemars::JsonSchema)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default, Merge, schemars::JsonSchema)]
 #[serde(default)]
 pub struct RuleOverride {
     /// Override the rule's severity.
@@ -37,25 +40,43 @@ pub struct RuleOverride {
 /// Order: builtins → ~/.config/moss/rules/ → .moss/rules/
 /// Then applies config overrides (severity, disable).
 pub fn load_all_rules(project_root: &Path, config: &RulesConfig) -> Vec<Rule> {
+    load_rule_set(project_root, "rules", BUILTIN_RULES, config)
+}
+
+/// Load all route queries, merged by ID.
+/// Order: builtins → ~/.config/moss/routes/ → .moss/routes/
+/// Then applies config overrides (disable, allow).
+pub fn load_route_queries(project_root: &Path, config: &RulesConfig) -> Vec<Rule> {
+    load_rule_set(project_root, "routes", BUILTIN_ROUTES, config)
+}
+
+/// Load `builtins` and the `.scm` files of the `dir_name` directories, then
+/// apply config overrides.
+fn load_rule_set(
+    project_root: &Path,
+    dir_name: &str,
+    builtins: &[BuiltinRule],
+    config: &RulesConfig,
+) -> Vec<Rule> {
     let mut rules_by_id: HashMap<String, Rule> = HashMap::new();
 
     // 1. Load embedded builtins
-    for builtin in BUILTIN_RULES {
+    for builtin in builtins {
         if let Some(rule) = parse_rule_content(builtin.content, builtin.id, true) {
             rules_by_id.insert(rule.id.clone(), rule);
         }
     }
 
-    // 2. Load user global rules (~/.config/moss/rules/)
+    // 2. Load user global rules (~/.config/moss/<dir_name>/)
     if let Some(config_dir) = dirs::config_dir() {
-        let user_rules_dir = config_dir.join("moss").join("rules");
+        let user_rules_dir = config_dir.join("moss").join(dir_name);
         for rule in load_rules_from_dir(&user_rules_dir) {
             rules_by_id.insert(rule.id.clone(), rule);
         }
     }
 
-    // 3. Load project rules (.moss/rules/)
-    let project_rules_dir = project_root.join(".moss").join("rules");
+    // 3. Load project rules (.moss/<dir_name>/)
+    let project_rules_dir = project_root.join(".moss").join(dir_name);
     for rule in load_rules_from_dir(&project_rules_dir) {
         rules_by_id.insert(rule.id.clone(), rule);
     }
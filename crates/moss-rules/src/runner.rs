@@ -27,6 +27,16 @@ pub struct Finding {
     pub captures: HashMap<String, String>,
 }
 
+impl Finding {
+    /// Text that replaces the matched region when the fix is applied: the fix
+    /// template with captures substituted. Empty for fixes that delete the
+    /// match; None if the rule has no fix.
+    pub fn fix_text(&self) -> Option<String> {
+        let template = self.fix.as_ref()?;
+        Some(expand_fix_template(template, &self.captures))
+    }
+}
+
 /// Debug output categories.
 #[derive(Default)]
 pub struct DebugFlags {
@@ -171,10 +181,6 @@ pub fn run_rules(
     }
     let compile_start = std::time::Instant::now();
 
-    // Separate rules: language-specific vs cross-language (need per-grammar validation)
-    let (specific_rules, global_rules): (Vec<&&Rule>, Vec<&&Rule>) =
-        active_rules.iter().partition(|r| !r.languages.is_empty());
-
     // Build combined queries: one per grammar
     let mut combined_by_grammar: HashMap<String, CombinedQuery> = HashMap::new();
 
@@ -182,65 +188,9 @@ pub fn run_rules(
         let Some(grammar) = loader.get(grammar_name) else {
             continue;
         };
-
-        let mut compiled_rules: Vec<(&Rule, tree_sitter::Query)> = Vec::new();
-
-        // Pass 1: Language-specific rules - compile directly (trust the author)
-        for rule in &specific_rules {
-            if rule.languages.iter().any(|l| l == grammar_name) {
-                if let Ok(q) = tree_sitter::Query::new(&grammar, &rule.query_str) {
-                    compiled_rules.push((rule, q));
-                }
-            }
-        }
-
-        // Pass 2: Cross-language rules - validate each one
-        for rule in &global_rules {
-            if let Ok(q) = tree_sitter::Query::new(&grammar, &rule.query_str) {
-                compiled_rules.push((rule, q));
-            }
-        }
-
-        if compiled_rules.is_empty() {
-            continue;
-        }
-
-        // Combine all into one query
-        let combined_str = compiled_rules
-            .iter()
-            .map(|(r, _)| r.query_str.as_str())
-            .collect::<Vec<_>>()
-            .join("\n\n");
-
-        let query = match tree_sitter::Query::new(&grammar, &combined_str) {
-            Ok(q) => q,
-            Err(e) => {
-                eprintln!("Warning: combined query failed for {}: {}", grammar_name, e);
-                continue;
-            }
-        };
-
-        // Map pattern indices to rules
-        let mut pattern_to_rule: Vec<(&Rule, usize)> = Vec::new();
-        let combined_match_idx = query
-            .capture_names()
-            .iter()
-            .position(|n| *n == "match")
-            .unwrap_or(0);
-
-        for (rule, individual_query) in &compiled_rules {
-            for _ in 0..individual_query.pattern_count() {
-                pattern_to_rule.push((*rule, combined_match_idx));
-            }
+        if let Some(combined) = combine_rules(&active_rules, grammar_name, &grammar) {
+            combined_by_grammar.insert(grammar_name.clone(), combined);
         }
-
-        combined_by_grammar.insert(
-            grammar_name.clone(),
-            CombinedQuery {
-                query,
-                pattern_to_rule,
-            },
-        );
     }
 
     if debug.timing {
@@ -268,88 +218,19 @@ pub fn run_rules(
         }
 
         for file in files {
-            let rel_path = file.strip_prefix(root).unwrap_or(file);
-            let rel_path_str = rel_path.to_string_lossy();
-
-            // Build source context for this file (used for requires evaluation)
-            let source_ctx = SourceContext {
-                file_path: file,
-                rel_path: &rel_path_str,
-                project_root: root,
-            };
-
             let content = match std::fs::read_to_string(file) {
                 Ok(c) => c,
                 Err(_) => continue,
             };
-
-            let tree = match parser.parse(&content, None) {
-                Some(t) => t,
-                None => continue,
-            };
-
-            // Single query execution - one traversal for all rules
-            let mut cursor = tree_sitter::QueryCursor::new();
-            let mut matches = cursor.matches(&combined.query, tree.root_node(), content.as_bytes());
-
-            while let Some(m) = matches.next() {
-                // Look up which rule this pattern belongs to
-                let Some((rule, match_idx)) = combined.pattern_to_rule.get(m.pattern_index) else {
-                    continue;
-                };
-
-                // Check allow patterns for this specific rule
-                if rule.allow.iter().any(|p| p.matches(&rel_path_str)) {
-                    continue;
-                }
-
-                // Check requires conditions
-                if !check_requires(rule, &source_registry, &source_ctx) {
-                    continue;
-                }
-
-                if !evaluate_predicates(&combined.query, m, content.as_bytes()) {
-                    continue;
-                }
-
-                let capture = m.captures.iter().find(|c| c.index as usize == *match_idx);
-
-                if let Some(cap) = capture {
-                    let node = cap.node;
-                    let start_line = node.start_position().row + 1;
-
-                    if is_allowed_by_comment(&content, start_line, &rule.id) {
-                        continue;
-                    }
-
-                    let text = node.utf8_text(content.as_bytes()).unwrap_or("");
-
-                    // Collect all captures for fix substitution
-                    let mut captures_map: HashMap<String, String> = HashMap::new();
-                    for cap in m.captures {
-                        let name = combined.query.capture_names()[cap.index as usize].to_string();
-                        if let Ok(cap_text) = cap.node.utf8_text(content.as_bytes()) {
-                            captures_map.insert(name, cap_text.to_string());
-                        }
-                    }
-
-                    findings.push(Finding {
-                        rule_id: rule.id.clone(),
-                        file: file.clone(),
-                        start_line,
-                        start_col: node.start_position().column + 1,
-                        end_line: node.end_position().row + 1,
-                        end_col: node.end_position().column + 1,
-                        start_byte: node.start_byte(),
-                        end_byte: node.end_byte(),
-                        message: rule.message.clone(),
-                        severity: rule.severity,
-                        matched_text: text.lines().next().unwrap_or("").to_string(),
-                        fix: rule.fix.clone(),
-                        captures: captures_map,
-                    });
-                }
-            }
+            match_source(
+                combined,
+                &mut parser,
+                &source_registry,
+                root,
+                file,
+                &content,
+                &mut findings,
+            );
         }
     }
 
@@ -365,6 +246,201 @@ pub fn run_rules(
     findings
 }
 
+/// Run rules against one file's `content`, which may differ from what is on
+/// disk (e.g. an unsaved editor buffer). `file` decides the grammar and is
+/// matched against allow patterns relative to `root`.
+pub fn run_rules_on_source(
+    rules: &[Rule],
+    root: &Path,
+    file: &Path,
+    content: &str,
+    loader: &GrammarLoader,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let Some(lang) = support_for_path(file) else {
+        return findings;
+    };
+    let grammar_name = lang.grammar_name();
+    let Some(grammar) = loader.get(grammar_name) else {
+        return findings;
+    };
+    let active_rules: Vec<&Rule> = rules.iter().collect();
+    let Some(combined) = combine_rules(&active_rules, grammar_name, &grammar) else {
+        return findings;
+    };
+    let mut parser = tree_sitter::Parser::new();
+    if parser.set_language(&grammar).is_err() {
+        return findings;
+    }
+    match_source(
+        &combined,
+        &mut parser,
+        &builtin_registry(),
+        root,
+        file,
+        content,
+        &mut findings,
+    );
+    findings
+}
+
+/// Combine the rules that compile for a grammar into one query, or None if
+/// none apply.
+fn combine_rules<'a>(
+    rules: &[&'a Rule],
+    grammar_name: &str,
+    grammar: &tree_sitter::Language,
+) -> Option<CombinedQuery<'a>> {
+    // Separate rules: language-specific vs cross-language (need per-grammar validation)
+    let (specific_rules, global_rules): (Vec<&&Rule>, Vec<&&Rule>) =
+        rules.iter().partition(|r| !r.languages.is_empty());
+
+    let mut compiled_rules: Vec<(&Rule, tree_sitter::Query)> = Vec::new();
+
+    // Pass 1: Language-specific rules - compile directly (trust the author)
+    for rule in &specific_rules {
+        if rule.languages.iter().any(|l| l == grammar_name)
+            && let Ok(q) = tree_sitter::Query::new(grammar, &rule.query_str)
+        {
+            compiled_rules.push((rule, q));
+        }
+    }
+
+    // Pass 2: Cross-language rules - validate each one
+    for rule in &global_rules {
+        if let Ok(q) = tree_sitter::Query::new(grammar, &rule.query_str) {
+            compiled_rules.push((rule, q));
+        }
+    }
+
+    if compiled_rules.is_empty() {
+        return None;
+    }
+
+    // Combine all into one query
+    let combined_str = compiled_rules
+        .iter()
+        .map(|(r, _)| r.query_str.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    let query = match tree_sitter::Query::new(grammar, &combined_str) {
+        Ok(q) => q,
+        Err(e) => {
+            eprintln!("Warning: combined query failed for {}: {}", grammar_name, e);
+            return None;
+        }
+    };
+
+    // Map pattern indices to rules
+    let mut pattern_to_rule: Vec<(&Rule, usize)> = Vec::new();
+    let combined_match_idx = query
+        .capture_names()
+        .iter()
+        .position(|n| *n == "match")
+        .unwrap_or(0);
+
+    for (rule, individual_query) in &compiled_rules {
+        for _ in 0..individual_query.pattern_count() {
+            pattern_to_rule.push((*rule, combined_match_idx));
+        }
+    }
+
+    Some(CombinedQuery {
+        query,
+        pattern_to_rule,
+    })
+}
+
+/// Match a combined query against one file's content, appending findings.
+fn match_source(
+    combined: &CombinedQuery,
+    parser: &mut tree_sitter::Parser,
+    source_registry: &SourceRegistry,
+    root: &Path,
+    file: &Path,
+    content: &str,
+    findings: &mut Vec<Finding>,
+) {
+    let rel_path = file.strip_prefix(root).unwrap_or(file);
+    let rel_path_str = rel_path.to_string_lossy();
+
+    // Build source context for this file (used for requires evaluation)
+    let source_ctx = SourceContext {
+        file_path: file,
+        rel_path: &rel_path_str,
+        project_root: root,
+    };
+
+    let tree = match parser.parse(content, None) {
+        Some(t) => t,
+        None => return,
+    };
+
+    // Single query execution - one traversal for all rules
+    let mut cursor = tree_sitter::QueryCursor::new();
+    let mut matches = cursor.matches(&combined.query, tree.root_node(), content.as_bytes());
+
+    while let Some(m) = matches.next() {
+        // Look up which rule this pattern belongs to
+        let Some((rule, match_idx)) = combined.pattern_to_rule.get(m.pattern_index) else {
+            continue;
+        };
+
+        // Check allow patterns for this specific rule
+        if rule.allow.iter().any(|p| p.matches(&rel_path_str)) {
+            continue;
+        }
+
+        // Check requires conditions
+        if !check_requires(rule, source_registry, &source_ctx) {
+            continue;
+        }
+
+        if !evaluate_predicates(&combined.query, m, content.as_bytes()) {
+            continue;
+        }
+
+        let capture = m.captures.iter().find(|c| c.index as usize == *match_idx);
+
+        if let Some(cap) = capture {
+            let node = cap.node;
+            let start_line = node.start_position().row + 1;
+
+            if is_allowed_by_comment(content, start_line, &rule.id) {
+                continue;
+            }
+
+            let text = node.utf8_text(content.as_bytes()).unwrap_or("");
+
+            // Collect all captures for fix substitution
+            let mut captures_map: HashMap<String, String> = HashMap::new();
+            for cap in m.captures {
+                let name = combined.query.capture_names()[cap.index as usize].to_string();
+                if let Ok(cap_text) = cap.node.utf8_text(content.as_bytes()) {
+                    captures_map.insert(name, cap_text.to_string());
+                }
+            }
+
+            findings.push(Finding {
+                rule_id: rule.id.clone(),
+                file: file.to_path_buf(),
+                start_line,
+                start_col: node.start_position().column + 1,
+                end_line: node.end_position().row + 1,
+                end_col: node.end_position().column + 1,
+                start_byte: node.start_byte(),
+                end_byte: node.end_byte(),
+                message: rule.message.clone(),
+                severity: rule.severity,
+                matched_text: text.lines().next().unwrap_or("").to_string(),
+                fix: rule.fix.clone(),
+                captures: captures_map,
+            });
+        }
+    }
+}
+
 /// Evaluate predicates for a match.
 pub fn evaluate_predicates(
     query: &tree_sitter::Query,
@@ -513,8 +589,7 @@ pub fn apply_fixes(findings: &[Finding]) -> std::io::Result<usize> {
         let mut content = std::fs::read_to_string(file)?;
 
         for finding in file_findings {
-            let fix_template = finding.fix.as_ref().unwrap();
-            let replacement = expand_fix_template(fix_template, &finding.captures);
+            let replacement = finding.fix_text().unwrap_or_default();
 
             // Replace the matched region with the fix
             let before = &content[..finding.start_byte];
@@ -257,9 +257,8 @@ fn parse_message(entry: &Value, role: Role) -> Message {
                         .to_string();
                     let result_content = block
                         .get("content")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("")
-                        .to_string();
+                        .map(tool_result_text)
+                        .unwrap_or_default();
                     let is_error = block
                         .get("is_error")
                         .and_then(|v| v.as_bool())
@@ -291,3 +290,16 @@ fn parse_message(entry: &Value, role: Role) -> Message {
             .map(String::from),
     }
 }
+
+/// Text of a tool_result's content, which is either a string or a list of blocks.
+fn tool_result_text(content: &Value) -> String {
+    match content {
+        Value::String(s) => s.clone(),
+        Value::Array(blocks) => blocks
+            .iter()
+            .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
@@ -253,7 +253,12 @@ impl LogFormat for MossAgentFormat {
                         timestamp: None,
                     });
                 }
-                AgentEvent::Command { cmd, success, .. } => {
+                AgentEvent::Command {
+                    cmd,
+                    success,
+                    extra,
+                    ..
+                } => {
                     // Extract command name for tool use
                     let cmd_name = cmd.split_whitespace().next().unwrap_or("shell").to_string();
 
@@ -277,7 +282,13 @@ impl LogFormat for MossAgentFormat {
                             content: if success {
                                 "(success)".to_string()
                             } else {
-                                "(failed)".to_string()
+                                // Use the failure output when the log records it
+                                extra
+                                    .get("error")
+                                    .or_else(|| extra.get("output"))
+                                    .and_then(|v| v.as_str())
+                                    .unwrap_or("(failed)")
+                                    .to_string()
                             },
                             is_error: !success,
                         }],
@@ -4,7 +4,8 @@
 //! https://doc.rust-lang.org/clippy/
 
 use crate::{
-    Diagnostic, DiagnosticSeverity, Location, Tool, ToolCategory, ToolError, ToolInfo, ToolResult,
+    Diagnostic, DiagnosticSeverity, Fix, Location, Tool, ToolCategory, ToolError, ToolInfo,
+    ToolResult,
 };
 use serde::Deserialize;
 use std::path::Path;
@@ -46,15 +47,14 @@ struct CompilerMessage {
     level: String,
     message: String,
     spans: Vec<CompilerSpan>,
-    #[allow(dead_code)]
-    rendered: Option<String>,
+    /// Notes and suggestions attached to the message
+    #[serde(default)]
+    children: Vec<CompilerMessage>,
 }
 
 #[derive(Debug, Deserialize)]
 struct DiagnosticCode {
     code: String,
-    #[allow(dead_code)]
-    explanation: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -65,10 +65,112 @@ struct CompilerSpan {
     column_start: usize,
     column_end: usize,
     is_primary: bool,
-    #[allow(dead_code)]
-    label: Option<String>,
-    #[allow(dead_code)]
     suggested_replacement: Option<String>,
+    suggestion_applicability: Option<String>,
+}
+
+impl CompilerSpan {
+    fn same_range(&self, other: &CompilerSpan) -> bool {
+        self.file_name == other.file_name
+            && self.line_start == other.line_start
+            && self.column_start == other.column_start
+            && self.line_end == other.line_end
+            && self.column_end == other.column_end
+    }
+}
+
+impl CompilerMessage {
+    /// The suggestion `cargo clippy --fix` would apply, if any.
+    ///
+    /// Only machine-applicable suggestions that replace exactly the `primary`
+    /// span are kept: a [`Fix`] has no location of its own, so consumers apply
+    /// it to the diagnostic's location.
+    fn fix(&self, primary: &CompilerSpan) -> Option<Fix> {
+        self.children.iter().find_map(|child| {
+            let [span] = child.spans.as_slice() else {
+                return None;
+            };
+            if span.suggestion_applicability.as_deref() != Some("MachineApplicable")
+                || !span.same_range(primary)
+            {
+                return None;
+            }
+            Some(Fix {
+                description: child.message.clone(),
+                replacement: span.suggested_replacement.clone()?,
+            })
+        })
+    }
+}
+
+/// Documentation URL of a lint: the clippy lint list for `clippy::` lints, the
+/// rustc error index for error codes.
+fn help_url(rule_id: &str) -> Option<String> {
+    if let Some(lint) = rule_id.strip_prefix("clippy::") {
+        Some(format!(
+            "https://rust-lang.github.io/rust-clippy/master/index.html#{}",
+            lint
+        ))
+    } else if rule_id.len() == 5
+        && rule_id.starts_with('E')
+        && rule_id[1..].bytes().all(|b| b.is_ascii_digit())
+    {
+        Some(format!(
+            "https://doc.rust-lang.org/error_codes/{}.html",
+            rule_id
+        ))
+    } else {
+        None
+    }
+}
+
+/// Parse the `compiler-message` records of `cargo clippy --message-format=json`.
+fn parse_messages(stdout: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for line in stdout.lines() {
+        let Ok(msg) = serde_json::from_str::<CargoMessage>(line) else {
+            continue;
+        };
+        if msg.reason != "compiler-message" {
+            continue;
+        }
+        let Some(compiler_msg) = msg.message else {
+            continue;
+        };
+        // Summaries ("3 warnings emitted") have no spans and are skipped here
+        let Some(span) = compiler_msg.spans.iter().find(|s| s.is_primary) else {
+            continue;
+        };
+        let severity = match compiler_msg.level.as_str() {
+            "error" | "error: internal compiler error" => DiagnosticSeverity::Error,
+            "warning" => DiagnosticSeverity::Warning,
+            "note" | "failure-note" => DiagnosticSeverity::Info,
+            "help" => DiagnosticSeverity::Hint,
+            _ => DiagnosticSeverity::Warning,
+        };
+        let rule_id = compiler_msg
+            .code
+            .as_ref()
+            .map(|c| c.code.clone())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        diagnostics.push(Diagnostic {
+            tool: "clippy".to_string(),
+            help_url: help_url(&rule_id),
+            rule_id,
+            message: compiler_msg.message.clone(),
+            severity,
+            location: Location {
+                file: span.file_name.clone().into(),
+                line: span.line_start,
+                column: span.column_start,
+                end_line: Some(span.line_end),
+                end_column: Some(span.column_end),
+            },
+            fix: compiler_msg.fix(span),
+        });
+    }
+    diagnostics
 }
 
 impl Tool for Clippy {
@@ -110,59 +212,7 @@ impl Tool for Clippy {
             .output()?;
 
         let stdout = String::from_utf8_lossy(&output.stdout);
-
-        let mut diagnostics = Vec::new();
-
-        for line in stdout.lines() {
-            if line.trim().is_empty() {
-                continue;
-            }
-
-            if let Ok(msg) = serde_json::from_str::<CargoMessage>(line) {
-                if msg.reason != "compiler-message" {
-                    continue;
-                }
-
-                if let Some(compiler_msg) = msg.message {
-                    // Skip notes and help messages
-                    if compiler_msg.level == "note" || compiler_msg.level == "help" {
-                        continue;
-                    }
-
-                    // Get primary span
-                    if let Some(span) = compiler_msg.spans.iter().find(|s| s.is_primary) {
-                        let severity = match compiler_msg.level.as_str() {
-                            "error" => DiagnosticSeverity::Error,
-                            "warning" => DiagnosticSeverity::Warning,
-                            _ => DiagnosticSeverity::Warning,
-                        };
-
-                        let rule_id = compiler_msg
-                            .code
-                            .map(|c| c.code)
-                            .unwrap_or_else(|| "unknown".to_string());
-
-                        diagnostics.push(Diagnostic {
-                            tool: "clippy".to_string(),
-                            rule_id,
-                            message: compiler_msg.message,
-                            severity,
-                            location: Location {
-                                file: span.file_name.clone().into(),
-                                line: span.line_start,
-                                column: span.column_start,
-                                end_line: Some(span.line_end),
-                                end_column: Some(span.column_end),
-                            },
-                            fix: None,
-                            help_url: None,
-                        });
-                    }
-                }
-            }
-        }
-
-        Ok(ToolResult::success("clippy", diagnostics))
+        Ok(ToolResult::success("clippy", parse_messages(&stdout)))
     }
 
     fn can_fix(&self) -> bool {
@@ -182,46 +232,126 @@ impl Tool for Clippy {
             .current_dir(root)
             .output()?;
 
+        // Remaining diagnostics are the ones clippy couldn't fix
         let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(ToolResult::success("clippy", parse_messages(&stdout)))
+    }
+}
 
-        let mut diagnostics = Vec::new();
-
-        // Parse remaining unfixed warnings
-        for line in stdout.lines() {
-            if let Ok(msg) = serde_json::from_str::<CargoMessage>(line) {
-                if msg.reason != "compiler-message" {
-                    continue;
-                }
-
-                if let Some(compiler_msg) = msg.message {
-                    if compiler_msg.level == "note" || compiler_msg.level == "help" {
-                        continue;
-                    }
-
-                    if let Some(span) = compiler_msg.spans.iter().find(|s| s.is_primary) {
-                        diagnostics.push(Diagnostic {
-                            tool: "clippy".to_string(),
-                            rule_id: compiler_msg
-                                .code
-                                .map(|c| c.code)
-                                .unwrap_or_else(|| "unknown".to_string()),
-                            message: compiler_msg.message,
-                            severity: DiagnosticSeverity::Warning,
-                            location: Location {
-                                file: span.file_name.clone().into(),
-                                line: span.line_start,
-                                column: span.column_start,
-                                end_line: Some(span.line_end),
-                                end_column: Some(span.column_end),
-                            },
-                            fix: None,
-                            help_url: None,
-                        });
-                    }
-                }
-            }
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_messages() {
+        let span = |line: usize, replacement: Option<&str>, applicability: Option<&str>| {
+            serde_json::json!({
+                "file_name": "src/lib.rs",
+                "line_start": line,
+                "line_end": line,
+                "column_start": 5,
+                "column_end": 17,
+                "is_primary": true,
+                "suggested_replacement": replacement,
+                "suggestion_applicability": applicability,
+            })
+        };
+        let needless_return = serde_json::json!({
+            "reason": "compiler-message",
+            "message": {
+                "code": {"code": "clippy::needless_return", "explanation": null},
+                "level": "warning",
+                "message": "unneeded `return` statement",
+                "spans": [span(3, None, None)],
+                "children": [
+                    {"code": null, "level": "note", "message": "`#[warn(clippy::needless_return)]` on by default", "spans": [], "children": []},
+                    {"code": null, "level": "help", "message": "remove `return`", "spans": [span(3, Some("x"), Some("MachineApplicable"))], "children": []},
+                ],
+            },
+        });
+        let unused = serde_json::json!({
+            "reason": "compiler-message",
+            "message": {
+                "code": {"code": "unused_variables", "explanation": null},
+                "level": "warning",
+                "message": "unused variable: `y`",
+                "spans": [span(7, None, None)],
+                "children": [
+                    {"code": null, "level": "help", "message": "if this is intentional, prefix it with an underscore", "spans": [span(7, Some("_y"), Some("MaybeIncorrect"))], "children": []},
+                ],
+            },
+        });
+        let summary = serde_json::json!({
+            "reason": "compiler-message",
+            "message": {"code": null, "level": "warning", "message": "2 warnings emitted", "spans": [], "children": []},
+        });
+        let stdout = format!(
+            "{}\n{}\n{}\n{}\n",
+            serde_json::json!({"reason": "compiler-artifact", "target": {}}),
+            needless_return,
+            unused,
+            summary
+        );
+
+        let diagnostics = parse_messages(&stdout);
+        assert_eq!(diagnostics.len(), 2);
+
+        let first = &diagnostics[0];
+        assert_eq!(first.rule_id, "clippy::needless_return");
+        assert_eq!(first.severity, DiagnosticSeverity::Warning);
+        assert_eq!(
+            first.location,
+            Location::new("src/lib.rs", 3, 5).with_end(3, 17)
+        );
+        let fix = first.fix.as_ref().unwrap();
+        assert_eq!(fix.description, "remove `return`");
+        assert_eq!(fix.replacement, "x");
+        assert_eq!(
+            first.help_url.as_deref(),
+            Some("https://rust-lang.github.io/rust-clippy/master/index.html#needless_return")
+        );
+
+        // Suggestions that may be wrong aren't offered as fixes
+        assert_eq!(diagnostics[1].rule_id, "unused_variables");
+        assert!(diagnostics[1].fix.is_none());
+        assert!(diagnostics[1].help_url.is_none());
+    }
+
+    #[test]
+    fn test_parse_messages_fix_on_other_span() {
+        // unused_mut flags `mut x` but the suggestion only removes `mut `
+        let span = |column_end: usize, replacement: Option<&str>| {
+            serde_json::json!({
+                "file_name": "src/lib.rs",
+                "line_start": 2,
+                "line_end": 2,
+                "column_start": 9,
+                "column_end": column_end,
+                "is_primary": true,
+                "suggested_replacement": replacement,
+                "suggestion_applicability": replacement.map(|_| "MachineApplicable"),
+            })
+        };
+        let unused_mut = serde_json::json!({
+            "reason": "compiler-message",
+            "message": {
+                "code": {"code": "unused_mut", "explanation": null},
+                "level": "warning",
+                "message": "variable does not need to be mutable",
+                "spans": [span(14, None)],
+                "children": [
+                    {"code": null, "level": "help", "message": "remove this `mut`", "spans": [span(13, Some(""))], "children": []},
+                ],
+            },
+        });
 
-        Ok(ToolResult::success("clippy", diagnostics))
+        let diagnostics = parse_messages(&unused_mut.to_string());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].location,
+            Location::new("src/lib.rs", 2, 9).with_end(2, 14)
+        );
+        // Applying "" to `mut x` would delete the variable name too
+        assert!(diagnostics[0].fix.is_none());
     }
 }
@@ -1,4 +1,4 @@
-//! Custom tool support - run arbitrary commands that output SARIF or JSON.
+//! Custom tool support - run arbitrary commands that output SARIF, JSON, or text.
 //!
 //! Tools are configured in `.moss/tools.toml`:
 //!
@@ -15,21 +15,37 @@
 //! command = ["./scripts/lint.sh"]
 //! output = "sarif"
 //! category = "linter"
+//!
+//! [tools.shellcheck]
+//! command = ["shellcheck", "--format=gcc"]
+//! output = "regex"
+//! pattern = '^(?P<file>[^:]+):(?P<line>\d+):(?P<col>\d+): (?P<severity>\w+): (?P<message>.*)$'
+//! extensions = ["sh"]
 //! ```
+//!
+//! With `output = "regex"`, each stdout line matching `pattern` becomes a
+//! diagnostic. The pattern must capture `file`, `line` and `message`, and may
+//! capture `col`, `end_line`, `end_col`, `severity` and `rule`.
 
 use crate::{
-    Diagnostic, SarifReport, Tool, ToolCategory, ToolError, ToolInfo, ToolResult, has_config_file,
+    Diagnostic, DiagnosticSeverity, Location, SarifReport, SeverityOverride, Tool, ToolCategory,
+    ToolError, ToolInfo, ToolResult, has_config_file,
 };
+use regex::Regex;
 use serde::Deserialize;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
-/// Configuration for custom tools.
+/// Contents of `.moss/tools.toml`: custom tools and severity overrides.
 #[derive(Debug, Clone, Deserialize)]
 pub struct ToolsConfig {
     #[serde(default)]
     pub tools: HashMap<String, CustomToolConfig>,
+
+    /// Severity overrides by `tool:rule_id` (see [`crate::SeverityRemap`]).
+    #[serde(default)]
+    pub severity: HashMap<String, SeverityOverride>,
 }
 
 /// Configuration for a single custom tool.
@@ -38,10 +54,14 @@ pub struct CustomToolConfig {
     /// Command to run (first element is executable, rest are args).
     pub command: Vec<String>,
 
-    /// Output format: "sarif" or "json".
+    /// Output format: "sarif", "json" or "regex".
     #[serde(default = "default_output")]
     pub output: OutputFormat,
 
+    /// Pattern with named captures for each line of output (`output = "regex"`).
+    #[serde(default)]
+    pub pattern: Option<String>,
+
     /// Tool category.
     #[serde(default)]
     pub category: CategoryConfig,
@@ -73,6 +93,8 @@ pub enum OutputFormat {
     #[default]
     Sarif,
     Json,
+    /// One diagnostic per line, parsed by [`CustomToolConfig::pattern`].
+    Regex,
 }
 
 fn default_output() -> OutputFormat {
@@ -98,11 +120,100 @@ impl From<CategoryConfig> for ToolCategory {
     }
 }
 
+impl CustomToolConfig {
+    /// Check the configuration for errors that would only show when running.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.command.is_empty() {
+            return Err("empty command".to_string());
+        }
+        match (self.output, &self.pattern) {
+            (OutputFormat::Regex, Some(pattern)) => LineParser::new(pattern).map(|_| ()),
+            (OutputFormat::Regex, None) => Err("output = \"regex\" requires a pattern".to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Captures a `pattern` may use.
+const CAPTURE_NAMES: [&str; 8] = [
+    "file", "line", "col", "end_line", "end_col", "severity", "rule", "message",
+];
+
+/// Captures a `pattern` must have.
+const REQUIRED_CAPTURES: [&str; 3] = ["file", "line", "message"];
+
+/// Parses lines of text output into diagnostics with a named-capture regex.
+#[derive(Debug, Clone)]
+pub struct LineParser {
+    regex: Regex,
+}
+
+impl LineParser {
+    /// Compile `pattern`, checking that its capture names are known and that the
+    /// required ones are present.
+    pub fn new(pattern: &str) -> Result<Self, String> {
+        let regex = Regex::new(pattern).map_err(|e| format!("invalid pattern: {}", e))?;
+        let names: Vec<&str> = regex.capture_names().flatten().collect();
+        if let Some(unknown) = names.iter().find(|n| !CAPTURE_NAMES.contains(n)) {
+            return Err(format!(
+                "unknown capture `{}` in pattern (expected {})",
+                unknown,
+                CAPTURE_NAMES.join(", ")
+            ));
+        }
+        if let Some(missing) = REQUIRED_CAPTURES.iter().find(|n| !names.contains(n)) {
+            return Err(format!("pattern must capture `{}`", missing));
+        }
+        Ok(Self { regex })
+    }
+
+    /// Diagnostics from the lines of `output` that match; other lines are skipped.
+    pub fn parse(&self, tool_name: &str, output: &str) -> Vec<Diagnostic> {
+        output
+            .lines()
+            .filter_map(|line| self.parse_line(tool_name, line))
+            .collect()
+    }
+
+    fn parse_line(&self, tool_name: &str, line: &str) -> Option<Diagnostic> {
+        let caps = self.regex.captures(line)?;
+        let text = |name: &str| caps.name(name).map(|m| m.as_str());
+        let number = |name: &str| text(name).and_then(|n| n.trim().parse::<usize>().ok());
+
+        let mut location =
+            Location::new(text("file")?, number("line")?, number("col").unwrap_or(1));
+        location.end_line = number("end_line");
+        location.end_column = number("end_col");
+
+        Some(Diagnostic {
+            tool: tool_name.to_string(),
+            rule_id: text("rule").unwrap_or("unknown").to_string(),
+            message: text("message")?.trim().to_string(),
+            severity: text("severity").map_or(DiagnosticSeverity::Warning, parse_severity),
+            location,
+            fix: None,
+            help_url: None,
+        })
+    }
+}
+
+/// Severity from the words tools commonly print; unknown words are warnings.
+fn parse_severity(severity: &str) -> DiagnosticSeverity {
+    match severity.to_lowercase().as_str() {
+        "error" | "err" | "e" | "fatal" | "critical" => DiagnosticSeverity::Error,
+        "info" | "information" | "note" | "i" => DiagnosticSeverity::Info,
+        "hint" | "help" | "style" | "suggestion" => DiagnosticSeverity::Hint,
+        _ => DiagnosticSeverity::Warning,
+    }
+}
+
 /// A custom tool loaded from configuration.
 pub struct CustomTool {
     name: String,
     config: CustomToolConfig,
     info: ToolInfo,
+    /// Parser for `output = "regex"`, if the pattern is valid
+    line_parser: Option<LineParser>,
 }
 
 impl CustomTool {
@@ -150,7 +261,17 @@ impl CustomTool {
             website: website_static,
         };
 
-        Self { name, config, info }
+        let line_parser = config
+            .pattern
+            .as_deref()
+            .and_then(|p| LineParser::new(p).ok());
+
+        Self {
+            name,
+            config,
+            info,
+            line_parser,
+        }
     }
 }
 
@@ -223,7 +344,7 @@ impl Tool for CustomTool {
         run_custom_command(
             &self.name,
             &self.config.command,
-            self.config.output,
+            self.output_parser(),
             paths,
             root,
         )
@@ -235,17 +356,35 @@ impl Tool for CustomTool {
 
     fn fix(&self, paths: &[&Path], root: &Path) -> Result<ToolResult, ToolError> {
         if let Some(fix_cmd) = &self.config.fix_command {
-            run_custom_command(&self.name, fix_cmd, self.config.output, paths, root)
+            run_custom_command(&self.name, fix_cmd, self.output_parser(), paths, root)
         } else {
             self.run(paths, root)
         }
     }
 }
 
+impl CustomTool {
+    fn output_parser(&self) -> OutputParser<'_> {
+        match self.config.output {
+            OutputFormat::Sarif => OutputParser::Sarif,
+            OutputFormat::Json => OutputParser::Json,
+            OutputFormat::Regex => OutputParser::Regex(self.line_parser.as_ref()),
+        }
+    }
+}
+
+/// How to turn a custom tool's stdout into diagnostics.
+enum OutputParser<'a> {
+    Sarif,
+    Json,
+    /// None if the configured pattern is missing or invalid
+    Regex(Option<&'a LineParser>),
+}
+
 fn run_custom_command(
     tool_name: &str,
     command: &[String],
-    output_format: OutputFormat,
+    output_parser: OutputParser,
     paths: &[&Path],
     root: &Path,
 ) -> Result<ToolResult, ToolError> {
@@ -272,47 +411,135 @@ fn run_custom_command(
         return Ok(ToolResult::success(tool_name, vec![]));
     }
 
-    let diagnostics = match output_format {
-        OutputFormat::Sarif => {
+    let diagnostics = match output_parser {
+        OutputParser::Sarif => {
             let report = SarifReport::from_json(&stdout).map_err(|e| {
                 ToolError::ParseError(format!("failed to parse SARIF output: {}", e))
             })?;
             report.to_diagnostics()
         }
-        OutputFormat::Json => {
+        OutputParser::Json => {
             // Try parsing as array of diagnostics directly
             serde_json::from_str::<Vec<Diagnostic>>(&stdout)
                 .map_err(|e| ToolError::ParseError(format!("failed to parse JSON output: {}", e)))?
         }
+        OutputParser::Regex(Some(parser)) => parser.parse(tool_name, &stdout),
+        OutputParser::Regex(None) => {
+            return Err(ToolError::ParseError(
+                "output = \"regex\" requires a valid pattern".to_string(),
+            ));
+        }
     };
 
     Ok(ToolResult::success(tool_name, diagnostics))
 }
 
-/// Load custom tools from a config file.
-pub fn load_custom_tools(root: &Path) -> Vec<Box<dyn Tool>> {
+/// A parsed `.moss/tools.toml` and where it was read from.
+#[derive(Debug, Clone)]
+pub struct LoadedToolsConfig {
+    pub path: PathBuf,
+    pub config: ToolsConfig,
+}
+
+/// Read `.moss/tools.toml` under `root`, if there is one. A file that fails to
+/// parse is reported and skipped.
+pub fn load_tools_config(root: &Path) -> Option<LoadedToolsConfig> {
     let config_path = root.join(".moss").join("tools.toml");
 
     if !config_path.exists() {
-        return Vec::new();
+        return None;
     }
 
-    let content = match std::fs::read_to_string(&config_path) {
-        Ok(c) => c,
-        Err(_) => return Vec::new(),
-    };
+    let content = std::fs::read_to_string(&config_path).ok()?;
 
-    let config: ToolsConfig = match toml::from_str(&content) {
-        Ok(c) => c,
+    match toml::from_str(&content) {
+        Ok(config) => Some(LoadedToolsConfig {
+            path: config_path,
+            config,
+        }),
         Err(e) => {
             eprintln!("Warning: failed to parse {}: {}", config_path.display(), e);
-            return Vec::new();
+            None
         }
-    };
+    }
+}
 
-    config
-        .tools
+/// Load custom tools from a config file.
+pub fn load_custom_tools(root: &Path) -> Vec<Box<dyn Tool>> {
+    match load_tools_config(root) {
+        Some(loaded) => custom_tools(&loaded.path, loaded.config.tools),
+        None => Vec::new(),
+    }
+}
+
+/// Tools from the `[tools]` section of `config_path`, skipping invalid ones.
+pub(crate) fn custom_tools(
+    config_path: &Path,
+    tools: HashMap<String, CustomToolConfig>,
+) -> Vec<Box<dyn Tool>> {
+    tools
         .into_iter()
+        .filter(|(name, tool_config)| match tool_config.validate() {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!(
+                    "Warning: skipping tool `{}` in {}: {}",
+                    name,
+                    config_path.display(),
+                    e
+                );
+                false
+            }
+        })
         .map(|(name, tool_config)| Box::new(CustomTool::new(name, tool_config)) as Box<dyn Tool>)
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_parser() {
+        let parser = LineParser::new(
+            r"^(?P<file>[^:]+):(?P<line>\d+):(?P<col>\d+): (?P<severity>\w+) (?P<message>.*)$",
+        )
+        .unwrap();
+        let output = "\
+src/main.sh:3:7: error unquoted variable
+checked 2 files
+src/lib.sh:12:1: style prefer [[ ]]
+src/lib.sh:20:4: weird  something else
+";
+        let diagnostics = parser.parse("shlint", output);
+        assert_eq!(diagnostics.len(), 3);
+
+        assert_eq!(diagnostics[0].tool, "shlint");
+        assert_eq!(diagnostics[0].location, Location::new("src/main.sh", 3, 7));
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Error);
+        assert_eq!(diagnostics[0].message, "unquoted variable");
+        assert_eq!(diagnostics[0].rule_id, "unknown");
+        assert_eq!(diagnostics[1].severity, DiagnosticSeverity::Hint);
+        assert_eq!(diagnostics[2].severity, DiagnosticSeverity::Warning);
+        assert_eq!(diagnostics[2].message, "something else");
+    }
+
+    #[test]
+    fn test_line_parser_validation() {
+        assert!(LineParser::new(r"(?P<file>.+):(?P<line>\d+): (?P<message>.*)").is_ok());
+        let err = LineParser::new(r"(?P<file>.+):(?P<line>\d+)").unwrap_err();
+        assert!(err.contains("`message`"), "{}", err);
+        let err = LineParser::new(r"(?P<path>.+):(?P<line>\d+): (?P<message>.*)").unwrap_err();
+        assert!(err.contains("unknown capture `path`"), "{}", err);
+        assert!(LineParser::new(r"(?P<file>").is_err());
+
+        let config: CustomToolConfig =
+            toml::from_str("command = [\"lint\"]\noutput = \"regex\"").unwrap();
+        assert!(
+            config
+                .validate()
+                .unwrap_err()
+                .contains("requires a pattern")
+        );
+    }
+}
@@ -20,6 +20,9 @@
 //! category = "linter"
 //! extensions = ["py", "js", "go"]
 //! detect = ["semgrep.yaml", ".semgrep.yml"]
+//!
+//! [severity]
+//! "ruff:E501" = "info"
 //! ```
 //!
 //! # Example
@@ -39,13 +42,18 @@ mod custom;
 mod diagnostic;
 mod registry;
 mod sarif;
+mod severity;
 pub mod test_runners;
 mod tools;
 
-pub use custom::{CustomTool, CustomToolConfig, ToolsConfig, load_custom_tools};
+pub use custom::{
+    CustomTool, CustomToolConfig, LineParser, LoadedToolsConfig, ToolsConfig, load_custom_tools,
+    load_tools_config,
+};
 pub use diagnostic::{Diagnostic, DiagnosticSeverity, Fix, Location};
 pub use registry::{ToolRegistry, detect_tools, get_tool, list_tools, register as register_tool};
 pub use sarif::SarifReport;
+pub use severity::{SeverityOverride, SeverityRemap};
 pub use tools::{Tool, ToolCategory, ToolError, ToolInfo, ToolResult, has_config_file};
 
 use std::path::Path;
@@ -59,11 +67,27 @@ pub fn default_registry() -> ToolRegistry {
     registry
 }
 
-/// Create a registry with built-in tools and custom tools from the given root.
+/// Create a registry with built-in tools, plus the custom tools and severity
+/// overrides from `.moss/tools.toml` under the given root.
 pub fn registry_with_custom(root: &Path) -> ToolRegistry {
     let mut registry = default_registry();
-    for tool in load_custom_tools(root) {
+    let Some(LoadedToolsConfig {
+        path: config_path,
+        config,
+    }) = load_tools_config(root)
+    else {
+        return registry;
+    };
+    for tool in custom::custom_tools(&config_path, config.tools) {
         registry.register(tool);
     }
+    match SeverityRemap::new(&config.severity) {
+        Ok(remap) => registry.set_severity_remap(remap),
+        Err(e) => eprintln!(
+            "Warning: ignoring [severity] in {}: {}",
+            config_path.display(),
+            e
+        ),
+    }
     registry
 }
@@ -21,7 +21,7 @@
 //! register_tool(&MyTool);
 //! ```
 
-use crate::{Diagnostic, Tool, ToolCategory, ToolResult};
+use crate::{Diagnostic, SeverityRemap, Tool, ToolCategory, ToolResult};
 use rayon::prelude::*;
 use std::path::Path;
 use std::sync::{OnceLock, RwLock};
@@ -128,6 +128,7 @@ pub fn detect_tools(root: &Path) -> Vec<(&'static dyn Tool, f32)> {
 /// Registry of available tools.
 pub struct ToolRegistry {
     tools: Vec<Box<dyn Tool>>,
+    severity: SeverityRemap,
 }
 
 impl Default for ToolRegistry {
@@ -139,7 +140,10 @@ impl Default for ToolRegistry {
 impl ToolRegistry {
     /// Create a new empty registry.
     pub fn new() -> Self {
-        Self { tools: Vec::new() }
+        Self {
+            tools: Vec::new(),
+            severity: SeverityRemap::default(),
+        }
     }
 
     /// Create a registry with all built-in tools.
@@ -152,6 +156,17 @@ impl ToolRegistry {
         self.tools.push(tool);
     }
 
+    /// Set the severity overrides applied to tool results.
+    pub fn set_severity_remap(&mut self, remap: SeverityRemap) {
+        self.severity = remap;
+    }
+
+    /// Apply the severity overrides to the result of running one of the tools.
+    /// [`Self::run_detected`] and [`Self::run_named`] do this already.
+    pub fn remap_severity(&self, result: &mut ToolResult) {
+        self.severity.apply_to_result(result);
+    }
+
     /// Get all registered tools.
     pub fn tools(&self) -> &[Box<dyn Tool>] {
         &self.tools
@@ -202,10 +217,7 @@ impl ToolRegistry {
         let detected = self.detect(root);
         detected
             .into_iter()
-            .filter_map(|(tool, _)| match tool.run(paths, root) {
-                Ok(result) => Some(result),
-                Err(e) => Some(ToolResult::failure(tool.info().name, e)),
-            })
+            .map(|(tool, _)| self.run_tool(tool, root, paths))
             .collect()
     }
 
@@ -214,13 +226,20 @@ impl ToolRegistry {
         self.tools
             .iter()
             .filter(|t| names.contains(&t.info().name))
-            .filter_map(|tool| match tool.run(paths, root) {
-                Ok(result) => Some(result),
-                Err(e) => Some(ToolResult::failure(tool.info().name, e)),
-            })
+            .map(|tool| self.run_tool(tool.as_ref(), root, paths))
             .collect()
     }
 
+    fn run_tool(&self, tool: &dyn Tool, root: &Path, paths: &[&Path]) -> ToolResult {
+        match tool.run(paths, root) {
+            Ok(mut result) => {
+                self.remap_severity(&mut result);
+                result
+            }
+            Err(e) => ToolResult::failure(tool.info().name, e),
+        }
+    }
+
     /// Collect all diagnostics from multiple tool results.
     pub fn collect_diagnostics(results: &[ToolResult]) -> Vec<Diagnostic> {
         results.iter().flat_map(|r| r.diagnostics.clone()).collect()
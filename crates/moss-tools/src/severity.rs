@@ -0,0 +1,155 @@
+//! User overrides of diagnostic severities.
+//!
+//! Configured in the `[severity]` section of `.moss/tools.toml`, keyed by
+//! `tool:rule_id`:
+//!
+//! ```toml
+//! [severity]
+//! "ruff:F401" = "error"      # fail on unused imports
+//! "ruff:E501" = "info"       # line length is only informational
+//! "eslint:no-console" = "off"
+//! ```
+//!
+//! The rule ID is everything after the first `:`, so Clippy lints are written
+//! `"clippy:clippy::unwrap_used"`. `off` drops matching diagnostics.
+
+use crate::{Diagnostic, DiagnosticSeverity, ToolResult};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Severity a rule's diagnostics are changed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SeverityOverride {
+    Error,
+    Warning,
+    Info,
+    /// Drop the diagnostic
+    Off,
+}
+
+/// Severity overrides by tool and rule ID.
+#[derive(Debug, Clone, Default)]
+pub struct SeverityRemap {
+    rules: HashMap<String, HashMap<String, SeverityOverride>>,
+}
+
+impl SeverityRemap {
+    /// Build from `tool:rule_id` keys. Fails on a key without a tool or rule.
+    pub fn new(entries: &HashMap<String, SeverityOverride>) -> Result<Self, String> {
+        let mut remap = Self::default();
+        for (key, &severity) in entries {
+            let (tool, rule) = key
+                .split_once(':')
+                .filter(|(tool, rule)| !tool.is_empty() && !rule.is_empty())
+                .ok_or_else(|| format!("`{}` is not `tool:rule_id`", key))?;
+            remap
+                .rules
+                .entry(tool.to_string())
+                .or_default()
+                .insert(rule.to_string(), severity);
+        }
+        Ok(remap)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Override for diagnostics of `rule_id` from `tool`, if configured.
+    pub fn get(&self, tool: &str, rule_id: &str) -> Option<SeverityOverride> {
+        self.rules.get(tool)?.get(rule_id).copied()
+    }
+
+    /// Change the severities of `diagnostics`, dropping those turned off.
+    pub fn apply(&self, diagnostics: &mut Vec<Diagnostic>) {
+        if self.is_empty() {
+            return;
+        }
+        diagnostics.retain_mut(|d| {
+            d.severity = match self.get(&d.tool, &d.rule_id) {
+                None => return true,
+                Some(SeverityOverride::Off) => return false,
+                Some(SeverityOverride::Error) => DiagnosticSeverity::Error,
+                Some(SeverityOverride::Warning) => DiagnosticSeverity::Warning,
+                Some(SeverityOverride::Info) => DiagnosticSeverity::Info,
+            };
+            true
+        });
+    }
+
+    /// Change the severities of a tool run's diagnostics.
+    pub fn apply_to_result(&self, result: &mut ToolResult) {
+        self.apply(&mut result.diagnostics);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Location, ToolsConfig};
+
+    fn diagnostic(tool: &str, rule: &str, severity: DiagnosticSeverity) -> Diagnostic {
+        let mut d = Diagnostic::warning(tool, rule, "message", Location::new("a.py", 1, 1));
+        d.severity = severity;
+        d
+    }
+
+    #[test]
+    fn test_severity_remap() {
+        let config: ToolsConfig = toml::from_str(
+            r#"
+[severity]
+"ruff:F401" = "error"
+"ruff:E501" = "info"
+"ruff:T201" = "off"
+"clippy:clippy::unwrap_used" = "warning"
+"#,
+        )
+        .unwrap();
+        let remap = SeverityRemap::new(&config.severity).unwrap();
+
+        let mut result = ToolResult::success(
+            "ruff",
+            vec![
+                diagnostic("ruff", "F401", DiagnosticSeverity::Warning),
+                diagnostic("ruff", "E501", DiagnosticSeverity::Error),
+                diagnostic("ruff", "T201", DiagnosticSeverity::Error),
+                diagnostic("ruff", "E711", DiagnosticSeverity::Warning),
+                diagnostic("mypy", "F401", DiagnosticSeverity::Warning),
+            ],
+        );
+        remap.apply_to_result(&mut result);
+
+        let severities: Vec<(&str, DiagnosticSeverity)> = result
+            .diagnostics
+            .iter()
+            .map(|d| (d.rule_id.as_str(), d.severity))
+            .collect();
+        assert_eq!(
+            severities,
+            vec![
+                // Upgraded
+                ("F401", DiagnosticSeverity::Error),
+                // Downgraded
+                ("E501", DiagnosticSeverity::Info),
+                // T201 suppressed; unlisted rules and other tools are unchanged
+                ("E711", DiagnosticSeverity::Warning),
+                ("F401", DiagnosticSeverity::Warning),
+            ]
+        );
+        assert_eq!(result.error_count(), 1);
+        assert_eq!(
+            remap.get("clippy", "clippy::unwrap_used"),
+            Some(SeverityOverride::Warning)
+        );
+    }
+
+    #[test]
+    fn test_severity_remap_invalid() {
+        let entries = HashMap::from([("E501".to_string(), SeverityOverride::Off)]);
+        let err = SeverityRemap::new(&entries).unwrap_err();
+        assert!(err.contains("`E501`"), "{}", err);
+        assert!(toml::from_str::<ToolsConfig>("[severity]\n\"ruff:E501\" = \"fatal\"").is_err());
+    }
+}
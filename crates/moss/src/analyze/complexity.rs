@@ -4,7 +4,7 @@
 //! Complexity = number of decision points + 1
 
 use crate::parsers;
-use rhizome_moss_languages::{Language, support_for_path};
+use rhizome_moss_languages::{Language, SymbolKind, support_for_path};
 use serde::Serialize;
 use std::path::Path;
 use tree_sitter;
@@ -76,6 +76,15 @@ impl FunctionComplexity {
         }
     }
 
+    /// Method if it has a parent container, function otherwise
+    pub fn kind(&self) -> SymbolKind {
+        if self.parent.is_some() {
+            SymbolKind::Method
+        } else {
+            SymbolKind::Function
+        }
+    }
+
     /// Line count of the function
     pub fn line_count(&self) -> usize {
         self.end_line.saturating_sub(self.start_line) + 1
@@ -172,7 +181,7 @@ impl ComplexityAnalyzer {
 
     /// Analyze using the Language trait
     fn analyze_with_trait(&self, content: &str, support: &dyn Language) -> Vec<FunctionComplexity> {
-        let tree = match parsers::parse_with_grammar(support.grammar_name(), content) {
+        let tree = match parsers::parse_cached(support.grammar_name(), content) {
             Some(t) => t,
             None => return Vec::new(),
         };
@@ -200,12 +209,9 @@ impl ComplexityAnalyzer {
             // Check if this is a function
             if support.function_kinds().contains(&kind) {
                 if let Some(name) = support.node_name(&node, content) {
-                    let mut complexity = 1; // Base complexity
-                    self.count_complexity_with_trait(&node, support, &mut complexity);
-
                     functions.push(FunctionComplexity {
                         name: name.to_string(),
-                        complexity,
+                        complexity: compute_complexity(&node, support),
                         start_line: node.start_position().row + 1,
                         end_line: node.end_position().row + 1,
                         parent: parent.map(String::from),
@@ -245,50 +251,65 @@ impl ComplexityAnalyzer {
             }
         }
     }
+}
 
-    fn count_complexity_with_trait(
-        &self,
-        node: &tree_sitter::Node,
-        support: &dyn Language,
-        complexity: &mut usize,
-    ) {
-        let complexity_nodes = support.complexity_nodes();
-        let mut cursor = node.walk();
+/// Cyclomatic complexity of a function node: 1 plus one for each descendant
+/// whose kind is in the language's [`Language::complexity_nodes`].
+///
+/// Kinds may name anonymous tokens (`&&`, `and`). A token whose parent is
+/// itself counted (the `if` keyword of Ruby's `if` or `if_modifier`) adds
+/// nothing, and neither does a fallback branch opened by `else`/`default`
+/// (Kotlin's `else ->`, Swift's `default:`), which is not a decision.
+pub fn compute_complexity(node: &tree_sitter::Node, support: &dyn Language) -> usize {
+    let complexity_nodes = support.complexity_nodes();
+    let mut complexity = 1;
+    let mut cursor = node.walk();
+
+    if !cursor.goto_first_child() {
+        return complexity;
+    }
 
-        if !cursor.goto_first_child() {
-            return;
+    loop {
+        let node = cursor.node();
+        if complexity_nodes.contains(&node.kind())
+            && !is_keyword_of_counted(&node, complexity_nodes)
+            && !is_fallback_branch(&node)
+        {
+            complexity += 1;
         }
 
+        // Depth-first traversal
+        if cursor.goto_first_child() {
+            continue;
+        }
+        if cursor.goto_next_sibling() {
+            continue;
+        }
         loop {
-            let current = cursor.node();
-            let kind = current.kind();
-
-            // Count if this node type contributes to complexity
-            if complexity_nodes.contains(&kind) {
-                *complexity += 1;
+            if !cursor.goto_parent() {
+                return complexity;
             }
-
-            // Depth-first traversal
-            if cursor.goto_first_child() {
-                continue;
-            }
-
             if cursor.goto_next_sibling() {
-                continue;
-            }
-
-            loop {
-                if !cursor.goto_parent() {
-                    return;
-                }
-                if cursor.goto_next_sibling() {
-                    break;
-                }
+                break;
             }
         }
     }
 }
 
+/// An anonymous token belonging to a node that is counted itself.
+fn is_keyword_of_counted(node: &tree_sitter::Node, complexity_nodes: &[&str]) -> bool {
+    !node.is_named()
+        && node
+            .parent()
+            .is_some_and(|p| complexity_nodes.contains(&p.kind()))
+}
+
+/// A branch taken when no other matches: its first token is `else`/`default`.
+fn is_fallback_branch(node: &tree_sitter::Node) -> bool {
+    node.child(0)
+        .is_some_and(|c| matches!(c.kind(), "else" | "default" | "default_keyword"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -392,4 +413,163 @@ fn with_match(x: Option<i32>) -> i32 {
             with_match.complexity
         );
     }
+
+    /// Assert the complexity of every function in `content`, parsed as `file`.
+    fn assert_complexities(file: &str, content: &str, expected: &[(&str, usize)]) {
+        let report = ComplexityAnalyzer::new().analyze(&PathBuf::from(file), content);
+        let actual: Vec<_> = report
+            .functions
+            .iter()
+            .map(|f| (f.name.as_str(), f.complexity))
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_ruby_complexity() {
+        let content = r#"
+def simple
+  1
+end
+
+def with_branches(x)
+  if x > 0 and x < 10
+    x
+  elsif x < 0
+    -x
+  end
+  x unless x.nil?
+end
+
+def with_case(x)
+  case x
+  when 1 then :one
+  when 2 then :two
+  else :many
+  end
+end
+
+def with_rescue
+  while busy? || waiting?
+    work
+  end
+  flush rescue nil
+rescue IOError
+  nil
+end
+"#;
+        // McCabe: 1 + one per decision point
+        assert_complexities(
+            "test.rb",
+            content,
+            &[
+                ("simple", 1),
+                // if, and, elsif, unless
+                ("with_branches", 5),
+                // two whens; else is the fallback
+                ("with_case", 3),
+                // while, ||, rescue modifier, rescue clause
+                ("with_rescue", 5),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_kotlin_complexity() {
+        let content = r#"
+fun simple(): Int = 1
+
+fun withBranches(x: Int?): Int {
+    if (x != null && x > 0) {
+        return x
+    }
+    return x ?: 0
+}
+
+fun withWhen(x: Int): String {
+    for (i in 0..x) {
+        println(i)
+    }
+    return when (x) {
+        1 -> "one"
+        2 -> "two"
+        else -> "many"
+    }
+}
+
+fun withTry(): Int {
+    try {
+        return risky()
+    } catch (e: Exception) {
+        return 0
+    }
+}
+"#;
+        // McCabe: 1 + one per decision point
+        assert_complexities(
+            "test.kt",
+            content,
+            &[
+                ("simple", 1),
+                // if, &&, ?:
+                ("withBranches", 4),
+                // for, two when entries; else is the fallback
+                ("withWhen", 4),
+                // catch
+                ("withTry", 2),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_swift_complexity() {
+        let content = r#"
+func simple() -> Int {
+    return 1
+}
+
+func withBranches(x: Int?) -> Int {
+    if let v = x {
+        return v > 0 ? v : -v
+    }
+    return x ?? 0
+}
+
+func withSwitch(x: Int) -> String {
+    for i in 0..<x {
+        print(i)
+    }
+    switch x {
+    case 1: return "one"
+    case 2: return "two"
+    default: return "many"
+    }
+}
+
+func withLoops(n: Int, flag: Bool) {
+    var i = 0
+    while i < n && flag {
+        i += 1
+    }
+    repeat {
+        i -= 1
+    } while i > 0 || flag
+    guard i == 0 else { return }
+}
+"#;
+        // McCabe: 1 + one per decision point
+        assert_complexities(
+            "test.swift",
+            content,
+            &[
+                ("simple", 1),
+                // if, ?:, ??
+                ("withBranches", 4),
+                // for, two cases; default is the fallback
+                ("withSwitch", 4),
+                // while, &&, repeat-while, ||, guard
+                ("withLoops", 6),
+            ],
+        );
+    }
 }
@@ -118,7 +118,7 @@ impl LengthAnalyzer {
         }
     }
     fn analyze_with_trait(&self, content: &str, support: &dyn Language) -> Vec<FunctionLength> {
-        let tree = match parsers::parse_with_grammar(support.grammar_name(), content) {
+        let tree = match parsers::parse_cached(support.grammar_name(), content) {
             Some(t) => t,
             None => return Vec::new(),
         };
@@ -0,0 +1,146 @@
+//! CODEOWNERS parsing and path-to-owner matching.
+//!
+//! Follows GitHub's rules: the file is looked up in `.github/`, the repository
+//! root, then `docs/`. Each line is a gitignore-style pattern followed by owners
+//! (`@user`, `@org/team` or an email), and the last matching line wins. A pattern
+//! without owners makes matching paths unowned.
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::{Path, PathBuf};
+
+/// Where CODEOWNERS is looked up, relative to the root, in order.
+pub const CODEOWNERS_LOCATIONS: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// One line of a CODEOWNERS file.
+pub struct OwnerRule {
+    /// The pattern as written
+    pub pattern: String,
+    pub owners: Vec<String>,
+    matcher: Gitignore,
+}
+
+/// Parsed CODEOWNERS file.
+pub struct CodeOwners {
+    root: PathBuf,
+    path: PathBuf,
+    rules: Vec<OwnerRule>,
+}
+
+impl CodeOwners {
+    /// Load the CODEOWNERS file of `root`, if there is one.
+    pub fn load(root: &Path) -> Option<Self> {
+        CODEOWNERS_LOCATIONS.iter().find_map(|location| {
+            let path = root.join(location);
+            let content = std::fs::read_to_string(&path).ok()?;
+            Some(Self::parse(root, path, &content))
+        })
+    }
+
+    /// Parse CODEOWNERS `content`; patterns are relative to `root`. Lines with
+    /// invalid patterns are skipped.
+    pub fn parse(root: &Path, path: PathBuf, content: &str) -> Self {
+        let rules = content
+            .lines()
+            .filter_map(|line| {
+                // Comments start at a `#` at the start of the line or after whitespace
+                let line = match line.find(" #") {
+                    Some(i) => &line[..i],
+                    None => line,
+                };
+                let mut fields = line.split_whitespace();
+                let pattern = fields.next().filter(|p| !p.starts_with('#'))?;
+                let mut builder = GitignoreBuilder::new(root);
+                builder.add_line(None, pattern).ok()?;
+                Some(OwnerRule {
+                    pattern: pattern.to_string(),
+                    owners: fields.map(String::from).collect(),
+                    matcher: builder.build().ok()?,
+                })
+            })
+            .collect();
+        Self {
+            root: root.to_path_buf(),
+            path,
+            rules,
+        }
+    }
+
+    /// Directory the patterns are relative to.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Path of the CODEOWNERS file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The rule deciding the owners of `path`, if any matches. Its owners may be
+    /// empty. `path` is relative to the root, or absolute; paths outside the root
+    /// match nothing.
+    pub fn rule_for(&self, path: &Path) -> Option<&OwnerRule> {
+        let path = if path.is_absolute() {
+            path.strip_prefix(&self.root).ok().or_else(|| {
+                let root = self.root.canonicalize().ok()?;
+                path.strip_prefix(root).ok()
+            })?
+        } else {
+            path.strip_prefix(".").unwrap_or(path)
+        };
+        self.rules.iter().rev().find(|rule| {
+            rule.matcher
+                .matched_path_or_any_parents(path, false)
+                .is_ignore()
+        })
+    }
+
+    /// Owners of `path` as written in CODEOWNERS (space-separated), or None if
+    /// it is unowned.
+    pub fn owner_of(&self, path: &Path) -> Option<String> {
+        self.rule_for(path)
+            .filter(|rule| !rule.owners.is_empty())
+            .map(|rule| rule.owners.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_owner_of() {
+        let content = "\
+# Default owners
+*       @org/everyone
+
+/docs/           @org/docs   # top-level docs only
+*.rs             @org/rust
+src/server/      @org/backend alice@example.com
+/src/vendored/
+";
+        let owners = CodeOwners::parse(Path::new("/repo"), PathBuf::from("CODEOWNERS"), content);
+        let owner = |path: &str| owners.owner_of(Path::new(path));
+
+        assert_eq!(owner("README.md").as_deref(), Some("@org/everyone"));
+        assert_eq!(owner("docs/guide.md").as_deref(), Some("@org/docs"));
+        assert_eq!(owner("src/docs/guide.md").as_deref(), Some("@org/everyone"));
+        assert_eq!(owner("src/lib.rs").as_deref(), Some("@org/rust"));
+        // Last match wins
+        assert_eq!(
+            owner("src/server/main.rs").as_deref(),
+            Some("@org/backend alice@example.com")
+        );
+        assert_eq!(
+            owners
+                .rule_for(Path::new("src/server/main.rs"))
+                .map(|r| r.pattern.as_str()),
+            Some("src/server/")
+        );
+        // A pattern without owners removes ownership
+        assert_eq!(owner("src/vendored/lib.rs"), None);
+
+        assert_eq!(owner("/repo/src/lib.rs").as_deref(), Some("@org/rust"));
+        assert_eq!(owner("./docs/a.md").as_deref(), Some("@org/docs"));
+        assert_eq!(owner("/elsewhere/lib.rs"), None);
+    }
+}
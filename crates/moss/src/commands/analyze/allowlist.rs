@@ -0,0 +1,162 @@
+//! Allow and ignore files in .moss/ (hotspots-allow, security-allow, ...).
+//!
+//! One entry per line, `#` starts a comment. New entries are inserted next to
+//! related existing entries, or appended under a `# reason` comment.
+
+use std::path::Path;
+
+/// The entry on a line, without its trailing comment. `None` for blank and comment lines.
+fn entry(line: &str) -> Option<&str> {
+    let without_comment = line.split('#').next().unwrap_or(line);
+    let trimmed = without_comment.trim();
+    (!trimmed.is_empty()).then_some(trimmed)
+}
+
+/// Load entries from a .moss allow file, in file order
+pub(crate) fn load(root: &Path, filename: &str) -> Vec<String> {
+    let path = root.join(".moss").join(filename);
+    let content = std::fs::read_to_string(&path).unwrap_or_default();
+    content
+        .lines()
+        .filter_map(entry)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Add entries to a .moss allowlist file.
+///
+/// `related` are entries belonging to the same group as `entries`. If any of them
+/// is already listed, new entries are inserted after the last one and no reason is
+/// needed; otherwise a reason is required and becomes a comment above the new group.
+pub(crate) fn add_entries(
+    root: &Path,
+    filename: &str,
+    related: &[String],
+    entries: &[String],
+    reason: Option<&str>,
+) -> i32 {
+    let moss_dir = root.join(".moss");
+    let allowlist_path = moss_dir.join(filename);
+    let existing_content = std::fs::read_to_string(&allowlist_path).unwrap_or_default();
+    let existing_lines: Vec<&str> = existing_content.lines().collect();
+
+    // Find the last existing entry from this group
+    let insert_after = existing_lines
+        .iter()
+        .rposition(|line| entry(line).is_some_and(|e| related.iter().any(|r| e == r)));
+
+    // Require reason for new groups
+    if insert_after.is_none() && reason.is_none() {
+        eprintln!("Reason required for new groups. Use --reason \"...\"");
+        return 1;
+    }
+
+    let to_add: Vec<&String> = entries
+        .iter()
+        .filter(|e| !existing_lines.iter().any(|l| entry(l) == Some(e.as_str())))
+        .collect();
+
+    if to_add.is_empty() {
+        println!("All entries from this group are already allowed.");
+        return 0;
+    }
+
+    // Build new content with smart insertion
+    let mut new_lines: Vec<String> = existing_lines.iter().map(|s| s.to_string()).collect();
+
+    if let Some(idx) = insert_after {
+        // Insert near existing entries from this group
+        let insert_pos = idx + 1;
+        for (i, entry) in to_add.iter().enumerate() {
+            new_lines.insert(insert_pos + i, entry.to_string());
+        }
+    } else {
+        // Append at end with reason as comment
+        if !new_lines.is_empty() && !new_lines.last().is_none_or(|l| l.is_empty()) {
+            new_lines.push(String::new());
+        }
+        if let Some(r) = reason {
+            new_lines.push(format!("# {}", r));
+        }
+        for entry in &to_add {
+            new_lines.push(entry.to_string());
+        }
+    }
+
+    if let Err(e) = std::fs::create_dir_all(&moss_dir) {
+        eprintln!("Failed to create .moss directory: {}", e);
+        return 1;
+    }
+
+    // Write back
+    let new_content = new_lines.join("\n") + "\n";
+    if let Err(e) = std::fs::write(&allowlist_path, new_content) {
+        eprintln!("Failed to write .moss/{}: {}", filename, e);
+        return 1;
+    }
+
+    println!("Added {} entries to .moss/{}:", to_add.len(), filename);
+    for entry in &to_add {
+        println!("  {}", entry);
+    }
+
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_add_entries_requires_reason_for_new_group() {
+        let tmp = tempdir().unwrap();
+        let entries = vec!["a".to_string()];
+        assert_eq!(
+            add_entries(tmp.path(), "test-allow", &entries, &entries, None),
+            1
+        );
+        assert!(load(tmp.path(), "test-allow").is_empty());
+    }
+
+    #[test]
+    fn test_add_entries_inserts_near_group() {
+        let tmp = tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".moss")).unwrap();
+        std::fs::write(
+            tmp.path().join(".moss/test-allow"),
+            "# first\na1\n\n# second\nb1\n",
+        )
+        .unwrap();
+
+        let related = vec!["a1".to_string(), "a2".to_string()];
+        let entries = vec!["a2".to_string()];
+        assert_eq!(
+            add_entries(tmp.path(), "test-allow", &related, &entries, None),
+            0
+        );
+
+        let content = std::fs::read_to_string(tmp.path().join(".moss/test-allow")).unwrap();
+        assert_eq!(content, "# first\na1\na2\n\n# second\nb1\n");
+    }
+
+    #[test]
+    fn test_load_strips_comments() {
+        let tmp = tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".moss")).unwrap();
+        std::fs::write(
+            tmp.path().join(".moss/test-allow"),
+            "# reason\na1  # inline\n\n  b1\n",
+        )
+        .unwrap();
+        assert_eq!(load(tmp.path(), "test-allow"), vec!["a1", "b1"]);
+
+        // Entries with a trailing comment count as present
+        let entries = vec!["a1".to_string()];
+        assert_eq!(
+            add_entries(tmp.path(), "test-allow", &entries, &entries, None),
+            0
+        );
+        assert_eq!(load(tmp.path(), "test-allow"), vec!["a1", "b1"]);
+    }
+}
@@ -0,0 +1,390 @@
+//! Public API surface listing and baseline comparison.
+//!
+//! Lists every public symbol with its signature, sorted so that two listings of
+//! the same code are identical. Line numbers are left out: moving code around is
+//! not an API change.
+
+use crate::deps::DepsExtractor;
+use crate::filter::Filter;
+use crate::skeleton::{SkeletonExtractor, SkeletonSymbol};
+use rhizome_moss_languages::{Visibility, VisibilityMechanism, support_for_path};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/// A public symbol.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ApiItem {
+    /// File path relative to the root
+    pub file: String,
+    /// Symbol path within the file (`Client/send`)
+    pub name: String,
+    pub kind: String,
+    /// Signature with whitespace runs collapsed to one space
+    pub signature: String,
+}
+
+impl ApiItem {
+    /// Path as used by `moss view` (`src/lib.rs/Client/send`).
+    pub fn path(&self) -> String {
+        format!("{}/{}", self.file, self.name)
+    }
+}
+
+/// Differences between a baseline listing and the current one, keyed by symbol path.
+#[derive(Debug, Default, Serialize)]
+pub struct ApiDiff {
+    pub removed: Vec<ApiChange>,
+    pub changed: Vec<ApiChange>,
+    pub added: Vec<ApiChange>,
+}
+
+/// A symbol whose signatures differ from the baseline. Overloads share a path,
+/// so each side lists every signature for it.
+#[derive(Debug, Serialize)]
+pub struct ApiChange {
+    pub path: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub old: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub new: Vec<String>,
+}
+
+impl ApiDiff {
+    /// Whether the change could break users: an export was removed or its signature changed.
+    pub fn is_breaking(&self) -> bool {
+        !self.removed.is_empty() || !self.changed.is_empty()
+    }
+}
+
+/// Run API surface listing, or comparison against `baseline`.
+pub fn cmd_api_surface(
+    root: &Path,
+    baseline: Option<&Path>,
+    json: bool,
+    filter: Option<&Filter>,
+) -> i32 {
+    let items = collect_api_surface(root, filter);
+
+    let Some(baseline) = baseline else {
+        if json {
+            println!("{}", serde_json::to_string_pretty(&items).unwrap());
+        } else {
+            for item in &items {
+                println!("{}", format_item(item));
+            }
+        }
+        return 0;
+    };
+
+    let content = match std::fs::read_to_string(baseline) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: Failed to read {}: {}", baseline.display(), e);
+            return 1;
+        }
+    };
+    let old = match parse_baseline(&content) {
+        Ok(old) => old,
+        Err(e) => {
+            eprintln!("error: Invalid baseline {}: {}", baseline.display(), e);
+            return 1;
+        }
+    };
+    let current = items
+        .iter()
+        .map(|item| ApiSignature {
+            path: item.path(),
+            signature: item.signature.clone(),
+        })
+        .collect::<Vec<_>>();
+    let diff = diff_surfaces(&old, &current);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&diff).unwrap());
+    } else {
+        print_diff(&diff, current.len());
+    }
+    if diff.is_breaking() { 1 } else { 0 }
+}
+
+/// Collect the public symbols of all (non-test) files under `root`, sorted.
+pub fn collect_api_surface(root: &Path, filter: Option<&Filter>) -> Vec<ApiItem> {
+    let mut items = Vec::new();
+    for file in crate::path_resolve::all_files(root) {
+        if file.kind != "file" || super::docs::is_test_file(&file.path) {
+            continue;
+        }
+        if let Some(flt) = filter
+            && !flt.matches(Path::new(&file.path))
+        {
+            continue;
+        }
+        let path = root.join(&file.path);
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            items.extend(file_api_surface(&path, &file.path, &content));
+        }
+    }
+    items.sort();
+    items
+}
+
+/// Public symbols of one file, as determined by its language's visibility mechanism.
+fn file_api_surface(path: &Path, rel_path: &str, content: &str) -> Vec<ApiItem> {
+    let Some(support) = support_for_path(path) else {
+        return Vec::new();
+    };
+    if !support.has_symbols() {
+        return Vec::new();
+    }
+
+    let mechanism = support.visibility_mechanism();
+    // Languages with export statements say which top-level names are public
+    let exported: Option<HashSet<String>> = match mechanism {
+        VisibilityMechanism::NotApplicable => return Vec::new(),
+        VisibilityMechanism::HeaderBased if !is_header(path) => return Vec::new(),
+        VisibilityMechanism::ExplicitExport => Some(
+            DepsExtractor::new()
+                .extract(path, content)
+                .exports
+                .into_iter()
+                .map(|e| e.name)
+                .collect(),
+        ),
+        _ => None,
+    };
+
+    let skeleton = SkeletonExtractor::new()
+        .extract(path, content)
+        .filter_tests();
+    let mut items = Vec::new();
+    for sym in &skeleton.symbols {
+        let public = match &exported {
+            Some(names) => names.contains(&sym.name),
+            None => is_public(sym, mechanism),
+        };
+        if public {
+            push_public(sym, "", rel_path, mechanism, &mut items);
+        }
+    }
+    items
+}
+
+fn push_public(
+    sym: &SkeletonSymbol,
+    parent: &str,
+    rel_path: &str,
+    mechanism: VisibilityMechanism,
+    items: &mut Vec<ApiItem>,
+) {
+    let name = if parent.is_empty() {
+        sym.name.clone()
+    } else {
+        format!("{}/{}", parent, sym.name)
+    };
+    items.push(ApiItem {
+        file: rel_path.to_string(),
+        name: name.clone(),
+        kind: sym.kind.as_str().to_string(),
+        signature: normalize_signature(&sym.signature),
+    });
+    // Trait/interface implementations are public wherever their type is
+    for child in &sym.children {
+        if child.is_interface_impl || is_public(child, mechanism) {
+            push_public(child, &name, rel_path, mechanism, items);
+        }
+    }
+}
+
+fn is_public(sym: &SkeletonSymbol, mechanism: VisibilityMechanism) -> bool {
+    match mechanism {
+        VisibilityMechanism::AllPublic => true,
+        _ => sym.visibility == Visibility::Public,
+    }
+}
+
+fn is_header(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("h" | "hh" | "hpp" | "hxx" | "h++")
+    )
+}
+
+fn normalize_signature(signature: &str) -> String {
+    signature.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn format_item(item: &ApiItem) -> String {
+    format!("{}: {}", item.path(), item.signature)
+}
+
+/// A symbol path and one of its signatures, as compared against a baseline.
+#[derive(Debug, PartialEq)]
+struct ApiSignature {
+    path: String,
+    signature: String,
+}
+
+/// Parse a baseline written by `api-surface` (text or `--json`).
+fn parse_baseline(content: &str) -> Result<Vec<ApiSignature>, String> {
+    if content.trim_start().starts_with('[') {
+        let items: Vec<ApiItem> = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        return Ok(items
+            .into_iter()
+            .map(|item| ApiSignature {
+                path: item.path(),
+                signature: item.signature,
+            })
+            .collect());
+    }
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            line.split_once(": ")
+                .map(|(path, signature)| ApiSignature {
+                    path: path.to_string(),
+                    signature: signature.to_string(),
+                })
+                .ok_or_else(|| format!("line {}: expected `path: signature`", i + 1))
+        })
+        .collect()
+}
+
+/// Compare signature listings.
+fn diff_surfaces(old: &[ApiSignature], new: &[ApiSignature]) -> ApiDiff {
+    fn group(items: &[ApiSignature]) -> BTreeMap<&str, Vec<&str>> {
+        let mut map: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for item in items {
+            map.entry(&item.path).or_default().push(&item.signature);
+        }
+        for signatures in map.values_mut() {
+            signatures.sort();
+        }
+        map
+    }
+    let to_owned = |sigs: &[&str]| sigs.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    let old = group(old);
+    let new = group(new);
+    let mut diff = ApiDiff::default();
+    for (path, old_sigs) in &old {
+        match new.get(path) {
+            None => diff.removed.push(ApiChange {
+                path: path.to_string(),
+                old: to_owned(old_sigs),
+                new: Vec::new(),
+            }),
+            Some(new_sigs) if new_sigs != old_sigs => diff.changed.push(ApiChange {
+                path: path.to_string(),
+                old: to_owned(old_sigs),
+                new: to_owned(new_sigs),
+            }),
+            Some(_) => {}
+        }
+    }
+    for (path, new_sigs) in &new {
+        if !old.contains_key(path) {
+            diff.added.push(ApiChange {
+                path: path.to_string(),
+                old: Vec::new(),
+                new: to_owned(new_sigs),
+            });
+        }
+    }
+    diff
+}
+
+fn print_diff(diff: &ApiDiff, total: usize) {
+    if diff.is_breaking() {
+        println!(
+            "API surface changed: {} removed, {} changed, {} added",
+            diff.removed.len(),
+            diff.changed.len(),
+            diff.added.len()
+        );
+    } else {
+        println!(
+            "API surface matches baseline ({} symbols, {} added)",
+            total,
+            diff.added.len()
+        );
+    }
+    for change in &diff.removed {
+        for sig in &change.old {
+            println!("- {}: {}", change.path, sig);
+        }
+    }
+    for change in &diff.changed {
+        for sig in &change.old {
+            println!("~ {}: {}", change.path, sig);
+        }
+        for sig in &change.new {
+            println!("  -> {}", sig);
+        }
+    }
+    for change in &diff.added {
+        for sig in &change.new {
+            println!("+ {}: {}", change.path, sig);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signatures(items: &[(&str, &str)]) -> Vec<ApiSignature> {
+        items
+            .iter()
+            .map(|(path, signature)| ApiSignature {
+                path: path.to_string(),
+                signature: signature.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_surfaces() {
+        let old = signatures(&[
+            ("a.rs/foo", "pub fn foo()"),
+            ("a.rs/bar", "pub fn bar()"),
+            ("a.rs/baz", "pub fn baz()"),
+        ]);
+        let new = signatures(&[
+            ("a.rs/baz", "pub fn baz()"),
+            ("a.rs/bar", "pub fn bar(x: i32)"),
+            ("a.rs/qux", "pub fn qux()"),
+        ]);
+        let diff = diff_surfaces(&old, &new);
+        assert!(diff.is_breaking());
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].path, "a.rs/foo");
+        assert_eq!(diff.changed[0].new, vec!["pub fn bar(x: i32)"]);
+        assert_eq!(diff.added[0].path, "a.rs/qux");
+
+        let only_added = diff_surfaces(&new[..1], &new);
+        assert!(!only_added.is_breaking());
+    }
+
+    #[test]
+    fn test_parse_baseline() {
+        let item = ApiItem {
+            file: "src/lib.rs".to_string(),
+            name: "Client/send".to_string(),
+            kind: "method".to_string(),
+            signature: "pub fn send(&self, body: &str) -> Result<(), Error>".to_string(),
+        };
+        let expected = vec![ApiSignature {
+            path: item.path(),
+            signature: item.signature.clone(),
+        }];
+
+        let text = format!("{}\n\n", format_item(&item));
+        assert_eq!(parse_baseline(&text).unwrap(), expected);
+        let json = serde_json::to_string(&vec![item]).unwrap();
+        assert_eq!(parse_baseline(&json).unwrap(), expected);
+        assert!(parse_baseline("no separator").is_err());
+    }
+}
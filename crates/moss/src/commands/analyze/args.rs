@@ -1,5 +1,6 @@
 //! Analyze command arguments with subcommands
 
+use super::duplicates::NormalizationLevel;
 use clap::{Args, Subcommand};
 use std::path::PathBuf;
 
@@ -21,10 +22,29 @@ pub struct AnalyzeArgs {
     #[arg(long, value_name = "PATTERN", value_delimiter = ',', global = true)]
     pub only: Vec<String>,
 
+    /// Leave out test functions and everything in test modules/classes
+    /// (complexity, length, duplicate-functions)
+    #[arg(long, global = true, conflicts_with = "only_tests")]
+    pub exclude_tests: bool,
+
+    /// Analyze only test functions and code in test modules/classes
+    /// (complexity, length, duplicate-functions)
+    #[arg(long, global = true)]
+    pub only_tests: bool,
+
     /// Analyze only files changed since base ref (e.g., main, HEAD~1)
     /// If no BASE given, defaults to origin's default branch
     #[arg(long, value_name = "BASE", global = true, num_args = 0..=1, default_missing_value = "")]
     pub diff: Option<String>,
+
+    /// Output format: text, json, csv (one row per item; complexity, hotspots, duplicate-functions)
+    /// or json-lines (one record per item; complexity, security, duplicate-functions)
+    #[arg(long, value_name = "FORMAT", global = true, value_enum)]
+    pub format: Option<crate::output::FormatArg>,
+
+    /// Stream newline-delimited JSON (same as --format json-lines)
+    #[arg(long, global = true, conflicts_with = "format")]
+    pub json_lines: bool,
 }
 
 #[derive(Subcommand, Debug)]
@@ -44,13 +64,13 @@ pub enum AnalyzeCommand {
         #[arg(short, long)]
         threshold: Option<usize>,
 
-        /// Maximum number of functions to show (0 = no limit)
-        #[arg(short = 'l', long, default_value = "10")]
-        limit: usize,
+        /// Maximum number of functions to show (0 = no limit) [default: 10, none with --json-lines]
+        #[arg(short = 'l', long)]
+        limit: Option<usize>,
 
-        /// Filter by symbol kind: function, method
+        /// Filter by symbol kind, comma-separated: function, method
         #[arg(long)]
-        kind: Option<String>,
+        kind: Vec<String>,
 
         /// Output in SARIF format for IDE integration
         #[arg(long)]
@@ -63,6 +83,14 @@ pub enum AnalyzeCommand {
         /// Reason for allowing
         #[arg(long)]
         reason: Option<String>,
+
+        /// Show how the N most complex functions changed over git history (max 20)
+        #[arg(long, value_name = "N", conflicts_with_all = ["sarif", "allow"])]
+        trend: Option<usize>,
+
+        /// Past commits to sample per function with --trend (max 10)
+        #[arg(long, default_value = "5", requires = "trend")]
+        points: usize,
     },
 
     /// Run function length analysis
@@ -87,6 +115,14 @@ pub enum AnalyzeCommand {
     Security {
         /// Target file or directory
         target: Option<String>,
+
+        /// Allow a finding by fingerprint (add to .moss/security-allow)
+        #[arg(long, alias = "allow-finding", value_name = "FINGERPRINT")]
+        allow: Option<String>,
+
+        /// Reason for allowing
+        #[arg(long)]
+        reason: Option<String>,
     },
 
     /// Analyze documentation coverage
@@ -96,6 +132,30 @@ pub enum AnalyzeCommand {
         limit: usize,
     },
 
+    /// List public symbols with their signatures
+    ApiSurface {
+        /// Compare against a saved listing; exits 1 if symbols were removed or changed
+        #[arg(long, value_name = "FILE")]
+        baseline: Option<PathBuf>,
+    },
+
+    /// Summarize findings and grade by code owner (from CODEOWNERS)
+    Ownership,
+
+    /// Module dependencies: coupling, instability and cycles
+    Coupling {
+        /// Number of modules to show per ranking
+        #[arg(short = 'l', long, default_value = "10")]
+        limit: usize,
+
+        /// Output the dependency graph in Graphviz DOT format
+        #[arg(long)]
+        dot: bool,
+    },
+
+    /// List HTTP routes (method, path, handler) found by framework queries
+    Routes,
+
     /// Show longest files in codebase
     Files {
         /// Number of files to show
@@ -120,7 +180,7 @@ pub enum AnalyzeCommand {
         #[arg(long)]
         target: Option<String>,
 
-        /// Maximum trace depth
+        /// Maximum number of calls to follow a value through (0 = this function only)
         #[arg(long, default_value = "10")]
         max_depth: usize,
 
@@ -175,18 +235,27 @@ pub enum AnalyzeCommand {
 
     /// Detect duplicate functions (code clones)
     DuplicateFunctions {
-        /// Elide identifier names when comparing (default: true)
-        #[arg(long, default_value = "true")]
-        elide_identifiers: bool,
+        /// How much to normalize before comparing: exact, structural, aggressive
+        #[arg(long, value_enum, default_value = "structural")]
+        normalize: NormalizationLevel,
+
+        /// Elide identifier names when comparing (overrides --normalize)
+        #[arg(long)]
+        elide_identifiers: Option<bool>,
 
-        /// Elide literal values when comparing
+        /// Elide literal values when comparing (overrides --normalize)
         #[arg(long)]
-        elide_literals: bool,
+        elide_literals: Option<bool>,
 
         /// Show source code for detected duplicates
         #[arg(long)]
         show_source: bool,
 
+        /// Suggest which instance of each group to keep and show how the others
+        /// could delegate to it (advisory; nothing is changed)
+        #[arg(long, alias = "fix")]
+        suggest: bool,
+
         /// Minimum lines for a function to be considered
         #[arg(long, default_value = "1")]
         min_lines: usize,
@@ -219,6 +288,20 @@ pub enum AnalyzeCommand {
         reason: Option<String>,
     },
 
+    /// Detect string literals repeated across files
+    DuplicateStrings {
+        /// Target directory to scan
+        target: Option<String>,
+
+        /// Minimum occurrences for a literal to be reported
+        #[arg(long, default_value = "3")]
+        min_occurrences: usize,
+
+        /// Minimum literal length in characters (shorter literals are ignored)
+        #[arg(long, default_value = "8")]
+        min_length: usize,
+    },
+
     /// Run all analysis passes
     All {
         /// Target file or directory
@@ -1,8 +1,64 @@
 //! Check documentation references for broken links
+//!
+//! References are code spans naming a capitalized symbol (`Config`,
+//! `Config::load`) or a module path (`index::FileIndex`). Qualified references
+//! must match the index's qualified names: `Parent::name` of a nested symbol, or a
+//! symbol under a known module or parent. `.moss/doc-refs-ignore` lists
+//! references never to flag, and `!`-prefixed ones to check even where the
+//! built-in filters would skip them.
 
+use super::allowlist;
 use crate::index;
+use glob::Pattern;
+use regex::Regex;
+use rhizome_moss_core::Merge;
+use rhizome_moss_derive::Merge;
+use rhizome_moss_languages::support_for_path;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::path::Path;
 
+/// Documentation reference check configuration (`[analyze.check-refs]`).
+#[derive(Debug, Clone, Deserialize, Serialize, Default, Merge, schemars::JsonSchema)]
+#[serde(default)]
+pub struct CheckRefsConfig {
+    /// Skip ALL_CAPS references, taken to be constants or acronyms (default: true)
+    pub skip_all_caps: Option<bool>,
+    /// Where references are read from (default: inline)
+    pub scope: Option<RefScope>,
+}
+
+impl CheckRefsConfig {
+    pub fn skip_all_caps(&self) -> bool {
+        self.skip_all_caps.unwrap_or(true)
+    }
+
+    pub fn scope(&self) -> RefScope {
+        self.scope.unwrap_or_default()
+    }
+}
+
+/// Which code in a markdown file references are read from.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum RefScope {
+    /// Inline code spans outside fenced blocks
+    #[default]
+    Inline,
+    /// Paths in ```rust fenced blocks only
+    Rust,
+    /// Both
+    All,
+}
+
+impl Merge for RefScope {
+    fn merge(self, other: Self) -> Self {
+        other
+    }
+}
+
 /// A broken reference found in documentation
 #[derive(Debug)]
 struct BrokenRef {
@@ -13,14 +69,12 @@ struct BrokenRef {
 }
 
 /// Check documentation references for broken links
-pub fn cmd_check_refs(root: &Path, json: bool) -> i32 {
+pub fn cmd_check_refs(root: &Path, json: bool, config: &CheckRefsConfig) -> i32 {
     let rt = tokio::runtime::Runtime::new().unwrap();
-    rt.block_on(cmd_check_refs_async(root, json))
+    rt.block_on(cmd_check_refs_async(root, json, config))
 }
 
-async fn cmd_check_refs_async(root: &Path, json: bool) -> i32 {
-    use regex::Regex;
-
+async fn cmd_check_refs_async(root: &Path, json: bool, config: &CheckRefsConfig) -> i32 {
     // Open index to get known symbols
     let idx = match index::FileIndex::open_if_enabled(root).await {
         Some(i) => i,
@@ -32,6 +86,24 @@ async fn cmd_check_refs_async(root: &Path, json: bool) -> i32 {
 
     // Get all symbol names from index
     let all_symbols = idx.all_symbol_names().await.unwrap_or_default();
+    let known = KnownSymbols {
+        qualified: idx.qualified_symbol_names().await.unwrap_or_default(),
+        modules: idx
+            .all_files()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|f| !f.is_dir)
+            .filter_map(|f| {
+                let path = Path::new(&f.path);
+                support_for_path(path)?.file_path_to_module_name(path)
+            })
+            .map(|module| module.replace('.', "::"))
+            .collect(),
+        names: all_symbols,
+    };
+    let all_symbols = &known.names;
+    let filter = RefFilter::load(root, config);
 
     if all_symbols.is_empty() {
         eprintln!("No symbols indexed. Run: moss index rebuild --call-graph");
@@ -64,10 +136,7 @@ async fn cmd_check_refs_async(root: &Path, json: bool) -> i32 {
         return 0;
     }
 
-    // Regex for code references: `identifier` or `Module::method` or `Module.method`
-    let code_ref_re =
-        Regex::new(r"`([A-Z][a-zA-Z0-9_]*(?:[:\.][a-zA-Z_][a-zA-Z0-9_]*)*)`").unwrap();
-
+    let extractor = RefExtractor::new(config.scope());
     let mut broken_refs: Vec<BrokenRef> = Vec::new();
 
     for md_file in &md_files {
@@ -82,34 +151,14 @@ async fn cmd_check_refs_async(root: &Path, json: bool) -> i32 {
             .display()
             .to_string();
 
-        for (line_num, line) in content.lines().enumerate() {
-            for cap in code_ref_re.captures_iter(line) {
-                let reference = &cap[1];
-
-                // Extract symbol name (last part after :: or .)
-                let symbol_name = reference
-                    .rsplit(|c| c == ':' || c == '.')
-                    .next()
-                    .unwrap_or(reference);
-
-                // Skip common non-symbol patterns
-                if is_common_non_symbol(symbol_name) {
-                    continue;
-                }
-
-                // Check if symbol exists
-                if !all_symbols.contains(symbol_name) {
-                    // Also check the full reference
-                    let full_name = reference.replace("::", ".").replace(".", "::");
-                    if !all_symbols.contains(&full_name) && !all_symbols.contains(reference) {
-                        broken_refs.push(BrokenRef {
-                            file: rel_path.clone(),
-                            line: line_num + 1,
-                            reference: reference.to_string(),
-                            context: line.trim().to_string(),
-                        });
-                    }
-                }
+        for doc_ref in extractor.references(&content) {
+            if filter.should_check(&doc_ref.reference) && !known.contains(&doc_ref.reference) {
+                broken_refs.push(BrokenRef {
+                    file: rel_path.clone(),
+                    line: doc_ref.line,
+                    reference: doc_ref.reference,
+                    context: doc_ref.context.trim().to_string(),
+                });
             }
         }
     }
@@ -152,6 +201,168 @@ async fn cmd_check_refs_async(root: &Path, json: bool) -> i32 {
     if broken_refs.is_empty() { 0 } else { 1 }
 }
 
+/// A code reference found in markdown.
+struct DocRef<'a> {
+    /// 1-based line number
+    line: usize,
+    reference: String,
+    /// The whole line the reference is on
+    context: &'a str,
+}
+
+/// Reads code references out of markdown.
+struct RefExtractor {
+    scope: RefScope,
+    /// A code span holding a capitalized path (`Config`, `Config::load`,
+    /// `Config.load`) or a module path (`index::FileIndex`)
+    inline: Regex,
+    /// The same paths in code, minus the lowercase dotted ones (`self.field`)
+    code: Regex,
+}
+
+impl RefExtractor {
+    fn new(scope: RefScope) -> Self {
+        const PATH: &str = r"[A-Z][a-zA-Z0-9_]*(?:(?:::|\.)[a-zA-Z_][a-zA-Z0-9_]*)*|[a-z_][a-zA-Z0-9_]*(?:::[a-zA-Z_][a-zA-Z0-9_]*)+";
+        Self {
+            scope,
+            inline: Regex::new(&format!("`({})`", PATH)).unwrap(),
+            code: Regex::new(
+                r"\b([A-Z][a-zA-Z0-9_]*(?:::[a-zA-Z_][a-zA-Z0-9_]*)*|[a-z_][a-zA-Z0-9_]*(?:::[a-zA-Z_][a-zA-Z0-9_]*)+)",
+            )
+            .unwrap(),
+        }
+    }
+
+    /// Each reference in scope, in document order.
+    fn references<'a>(&self, content: &'a str) -> Vec<DocRef<'a>> {
+        let mut refs = Vec::new();
+        // Language of the fenced block being read, "" when it has none
+        let mut fence: Option<&str> = None;
+        for (i, line) in content.lines().enumerate() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                fence = match fence {
+                    Some(_) => None,
+                    None => Some(
+                        trimmed[3..]
+                            .trim_start_matches(['`', '~'])
+                            .split([' ', ','])
+                            .next()
+                            .unwrap_or(""),
+                    ),
+                };
+                continue;
+            }
+            let (re, code) = match fence {
+                None if self.scope != RefScope::Rust => (&self.inline, line),
+                Some("rust" | "rs") if self.scope != RefScope::Inline => {
+                    (&self.code, line.split("//").next().unwrap_or(line))
+                }
+                _ => continue,
+            };
+            for cap in re.captures_iter(code) {
+                refs.push(DocRef {
+                    line: i + 1,
+                    reference: cap[1].to_string(),
+                    context: line,
+                });
+            }
+        }
+        refs
+    }
+}
+
+/// Last segment of a reference (`load` in `Config::load`).
+fn last_segment(reference: &str) -> &str {
+    reference.rsplit([':', '.']).next().unwrap_or(reference)
+}
+
+/// Which references are looked up at all.
+struct RefFilter {
+    /// Never flagged (`.moss/doc-refs-ignore`)
+    ignore: Vec<Pattern>,
+    /// Always checked (`!` lines of `.moss/doc-refs-ignore`)
+    check: Vec<Pattern>,
+    skip_all_caps: bool,
+}
+
+impl RefFilter {
+    fn load(root: &Path, config: &CheckRefsConfig) -> Self {
+        let mut ignore = Vec::new();
+        let mut check = Vec::new();
+        for line in allowlist::load(root, "doc-refs-ignore") {
+            let (patterns, glob) = match line.strip_prefix('!') {
+                Some(glob) => (&mut check, glob.trim()),
+                None => (&mut ignore, line.as_str()),
+            };
+            match Pattern::new(glob) {
+                Ok(pattern) => patterns.push(pattern),
+                Err(e) => eprintln!("warning: doc-refs-ignore: invalid glob {}: {}", glob, e),
+            }
+        }
+        Self {
+            ignore,
+            check,
+            skip_all_caps: config.skip_all_caps(),
+        }
+    }
+
+    /// Patterns match the whole reference or its last segment.
+    fn should_check(&self, reference: &str) -> bool {
+        let name = last_segment(reference);
+        let matches = |patterns: &[Pattern]| {
+            patterns
+                .iter()
+                .any(|p| p.matches(reference) || p.matches(name))
+        };
+        if matches(&self.check) {
+            return true;
+        }
+        let skipped = matches(&self.ignore)
+            || is_common_non_symbol(name)
+            || (self.skip_all_caps && is_all_caps(name));
+        !skipped
+    }
+}
+
+/// What a reference can resolve to, from the index.
+struct KnownSymbols {
+    names: HashSet<String>,
+    /// `Parent::name` of nested symbols
+    qualified: HashSet<String>,
+    /// Module paths of indexed files (`commands::analyze`, `pkg::util`)
+    modules: Vec<String>,
+}
+
+impl KnownSymbols {
+    fn contains(&self, reference: &str) -> bool {
+        let segments: Vec<&str> = reference
+            .split("::")
+            .flat_map(|s| s.split('.'))
+            .filter(|s| !matches!(*s, "crate" | "self" | "super" | ""))
+            .collect();
+        match segments.as_slice() {
+            [] => true,
+            [name] => self.names.contains(*name) || self.is_module(name),
+            [.., parent, name] => {
+                let qualifier = segments[..segments.len() - 1].join("::");
+                self.qualified.contains(&format!("{}::{}", parent, name))
+                    || (self.names.contains(*name)
+                        && (self.names.contains(*parent) || self.is_module(&qualifier)))
+                    || self.is_module(&segments.join("::"))
+            }
+        }
+    }
+
+    /// Whether `path` is a module or the tail of one (`analyze` for
+    /// `commands::analyze`).
+    fn is_module(&self, path: &str) -> bool {
+        self.modules
+            .iter()
+            .any(|m| m == path || m.ends_with(&format!("::{}", path)))
+    }
+}
+
 /// Check if a string is a common non-symbol pattern (command, path, etc.)
 fn is_common_non_symbol(s: &str) -> bool {
     // Skip common patterns that aren't symbols
@@ -195,5 +406,82 @@ fn is_common_non_symbol(s: &str) -> bool {
             | "Serialize"
             | "Deserialize"
     ) || s.len() < 2
-        || s.chars().all(|c| c.is_uppercase() || c == '_') // ALL_CAPS constants
+}
+
+/// ALL_CAPS names, usually constants or acronyms.
+fn is_all_caps(s: &str) -> bool {
+    s.chars()
+        .all(|c| c.is_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "Use `Config::load` or `index::FileIndex`, not `config.toml`.\n\
+        ```rust\n\
+        let idx = FileIndex::open(root); // `Ignored`\n\
+        ```\n\
+        ```toml\n\
+        Key = 1\n\
+        ```\n";
+
+    fn refs(scope: RefScope) -> Vec<(usize, String)> {
+        RefExtractor::new(scope)
+            .references(DOC)
+            .into_iter()
+            .map(|doc_ref| (doc_ref.line, doc_ref.reference))
+            .collect()
+    }
+
+    #[test]
+    fn test_reference_scope() {
+        let inline = vec![
+            (1, "Config::load".to_string()),
+            (1, "index::FileIndex".to_string()),
+        ];
+        assert_eq!(refs(RefScope::Inline), inline);
+        assert_eq!(
+            refs(RefScope::Rust),
+            vec![(3, "FileIndex::open".to_string())]
+        );
+        assert_eq!(refs(RefScope::All).len(), 3);
+    }
+
+    #[test]
+    fn test_ref_filter() {
+        let mut filter = RefFilter {
+            ignore: vec![Pattern::new("Legacy*").unwrap()],
+            check: vec![Pattern::new("Option").unwrap()],
+            skip_all_caps: true,
+        };
+        assert!(!filter.should_check("LegacyParser"));
+        assert!(!filter.should_check("Parser::LegacyMode"));
+        assert!(filter.should_check("Option"));
+        assert!(!filter.should_check("Vec"));
+        assert!(!filter.should_check("API"));
+        filter.skip_all_caps = false;
+        assert!(filter.should_check("API"));
+    }
+
+    #[test]
+    fn test_known_symbols_qualified() {
+        let known = KnownSymbols {
+            names: ["Config", "load", "FileIndex", "open", "save"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            qualified: ["Config::load".to_string()].into_iter().collect(),
+            modules: vec!["index".to_string(), "commands::analyze".to_string()],
+        };
+        assert!(known.contains("Config"));
+        assert!(known.contains("Config::load"));
+        assert!(known.contains("Config.load"));
+        assert!(known.contains("crate::index::FileIndex"));
+        assert!(known.contains("commands::analyze"));
+        assert!(known.contains("analyze"));
+        // `save` exists, but neither under `Missing` nor a module of that name
+        assert!(!known.contains("Missing::save"));
+        assert!(!known.contains("Missing"));
+    }
 }
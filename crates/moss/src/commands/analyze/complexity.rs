@@ -1,10 +1,15 @@
 //! Complexity analysis - find complex functions in codebase
 
-use crate::analyze::complexity::{ComplexityAnalyzer, ComplexityReport};
-use crate::filter::Filter;
+use crate::analyze::FullStats;
+use crate::analyze::complexity::{ComplexityAnalyzer, ComplexityReport, FunctionComplexity};
+use crate::filter::{Filter, TestLines, TestScope};
 use crate::path_resolve;
 use rayon::prelude::*;
+use rhizome_moss_languages::SymbolKind;
+use std::collections::HashSet;
+use std::io;
 use std::path::Path;
+use std::sync::Mutex;
 
 /// Analyze complexity of a single file
 pub fn analyze_file_complexity(file_path: &Path) -> Option<ComplexityReport> {
@@ -13,17 +18,96 @@ pub fn analyze_file_complexity(file_path: &Path) -> Option<ComplexityReport> {
     Some(analyzer.analyze(file_path, &content))
 }
 
-/// Analyze complexity across a codebase, returning top complex functions
+/// Analyze complexity across a codebase, returning top complex functions.
+/// An empty `kinds` set includes both functions and methods.
 pub fn analyze_codebase_complexity(
     root: &Path,
     limit: usize,
     threshold: Option<usize>,
+    kinds: &HashSet<SymbolKind>,
     filter: Option<&Filter>,
     allowlist: &[String],
 ) -> ComplexityReport {
-    let all_files = path_resolve::all_files(root);
-    let code_files: Vec<_> = all_files
-        .iter()
+    let selection = Selection {
+        threshold,
+        kinds,
+        allowlist,
+        tests: filter.map_or(TestScope::All, |f| f.tests()),
+    };
+    let mut filtered: Vec<_> = code_files(root, filter)
+        .par_iter()
+        .flat_map_iter(|file| selection.functions(root, file))
+        .collect();
+
+    filtered.sort_by_key(|f| std::cmp::Reverse(f.complexity));
+
+    // Compute full stats before truncation
+    let complexities: Vec<usize> = filtered.iter().map(|f| f.complexity).collect();
+    let full_stats = full_stats(&complexities);
+
+    filtered.truncate(limit);
+
+    ComplexityReport {
+        functions: filtered,
+        file_path: root.to_string_lossy().to_string(),
+        full_stats,
+    }
+}
+
+/// Analyze complexity across a codebase, passing each function that passes the
+/// filters to `on_function` as soon as its file has been analyzed, rather than
+/// collecting and sorting them. Stops after `limit` functions, or at the first
+/// error from `on_function`, which is returned. Returns stats of the functions
+/// passed.
+///
+/// Files are analyzed in parallel, so functions arrive grouped by file but in
+/// no particular file order.
+pub fn for_each_function_complexity(
+    root: &Path,
+    limit: usize,
+    threshold: Option<usize>,
+    kinds: &HashSet<SymbolKind>,
+    filter: Option<&Filter>,
+    allowlist: &[String],
+    on_function: &mut (dyn FnMut(&FunctionComplexity) -> io::Result<()> + Send),
+) -> io::Result<Option<FullStats>> {
+    let selection = Selection {
+        threshold,
+        kinds,
+        allowlist,
+        tests: filter.map_or(TestScope::All, |f| f.tests()),
+    };
+
+    // The sink also serializes output, so functions of one file stay together
+    let sink = Mutex::new((on_function, Vec::new(), None::<io::Error>));
+    let _ = code_files(root, filter).par_iter().try_for_each(|file| {
+        let functions = selection.functions(root, file);
+        let mut guard = sink.lock().unwrap();
+        let (on_function, complexities, error) = &mut *guard;
+        for f in &functions {
+            if error.is_some() || complexities.len() >= limit {
+                return Err(());
+            }
+            if let Err(e) = on_function(f) {
+                *error = Some(e);
+                return Err(());
+            }
+            complexities.push(f.complexity);
+        }
+        Ok(())
+    });
+
+    let (_, complexities, error) = sink.into_inner().unwrap();
+    match error {
+        Some(e) => Err(e),
+        None => Ok(full_stats(&complexities)),
+    }
+}
+
+/// Relative paths of the files whose complexity is analyzed.
+fn code_files(root: &Path, filter: Option<&Filter>) -> Vec<String> {
+    path_resolve::all_files(root)
+        .into_iter()
         .filter(|f| {
             f.kind == "file" && {
                 let ext = std::path::Path::new(&f.path)
@@ -38,77 +122,130 @@ pub fn analyze_codebase_complexity(
                 .map(|flt| flt.matches(Path::new(&f.path)))
                 .unwrap_or(true)
         })
-        .collect();
+        .map(|f| f.path)
+        .collect()
+}
 
-    let all_functions: Vec<_> = code_files
-        .par_iter()
-        .filter_map(|file| {
-            let path = root.join(&file.path);
-            let content = std::fs::read_to_string(&path).ok()?;
-            let analyzer = ComplexityAnalyzer::new();
-            let report = analyzer.analyze(&path, &content);
-            Some(
-                report
-                    .functions
-                    .into_iter()
-                    .map(|mut f| {
-                        f.file_path = Some(file.path.clone());
-                        f
-                    })
-                    .collect::<Vec<_>>(),
-            )
-        })
-        .flatten()
-        .collect();
+/// Which functions a complexity analysis reports.
+struct Selection<'a> {
+    threshold: Option<usize>,
+    /// Empty includes both functions and methods
+    kinds: &'a HashSet<SymbolKind>,
+    allowlist: &'a [String],
+    tests: TestScope,
+}
 
-    // Filter by threshold
-    let mut filtered: Vec<_> = if let Some(t) = threshold {
-        all_functions
+impl Selection<'_> {
+    /// The selected functions of the file at `rel_path`, in file order.
+    fn functions(&self, root: &Path, rel_path: &str) -> Vec<FunctionComplexity> {
+        let path = root.join(rel_path);
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return Vec::new();
+        };
+        let report = ComplexityAnalyzer::new().analyze(&path, &content);
+        let tests = TestLines::new(self.tests, &path, &content);
+        report
+            .functions
             .into_iter()
-            .filter(|f| f.complexity >= t)
+            .filter(|f| tests.keeps(f.start_line))
+            .filter(|f| self.threshold.is_none_or(|t| f.complexity >= t))
+            .filter(|f| self.kinds.is_empty() || self.kinds.contains(&f.kind()))
+            .filter(|f| {
+                let key = f.qualified_name();
+                !self.allowlist.iter().any(|a| key.contains(a))
+            })
+            .map(|mut f| {
+                f.file_path = Some(rel_path.to_string());
+                f
+            })
             .collect()
-    } else {
-        all_functions
-    };
+    }
+}
 
-    // Filter by allowlist
-    if !allowlist.is_empty() {
-        filtered.retain(|f| {
-            let key = f.qualified_name();
-            !allowlist.iter().any(|a| key.contains(a))
-        });
+/// Stats over function complexities, or None if there are none.
+fn full_stats(complexities: &[usize]) -> Option<FullStats> {
+    if complexities.is_empty() {
+        return None;
     }
+    let total_count = complexities.len();
+    let total_sum: usize = complexities.iter().sum();
+    Some(FullStats {
+        total_count,
+        total_avg: total_sum as f64 / total_count as f64,
+        total_max: complexities.iter().copied().max().unwrap_or(0),
+        critical_count: complexities.iter().filter(|&&c| c > 20).count(),
+        high_count: complexities
+            .iter()
+            .filter(|&&c| (11..=20).contains(&c))
+            .count(),
+    })
+}
 
-    filtered.sort_by(|a, b| b.complexity.cmp(&a.complexity));
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // Compute full stats before truncation
-    let full_stats = if !filtered.is_empty() {
-        let total_count = filtered.len();
-        let total_sum: usize = filtered.iter().map(|f| f.complexity).sum();
-        let total_avg = total_sum as f64 / total_count as f64;
-        let total_max = filtered.first().map(|f| f.complexity).unwrap_or(0);
-        let critical_count = filtered.iter().filter(|f| f.complexity > 20).count();
-        let high_count = filtered
-            .iter()
-            .filter(|f| f.complexity >= 11 && f.complexity <= 20)
-            .count();
-
-        Some(crate::analyze::FullStats {
-            total_count,
-            total_avg,
-            total_max,
-            critical_count,
-            high_count,
-        })
-    } else {
-        None
-    };
+    #[test]
+    fn test_for_each_function_complexity() {
+        if crate::parsers::parser_for("python").is_none() {
+            return;
+        }
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tmp.path().join("a.py"),
+            "def simple():\n    return 1\n\ndef branchy(x):\n    if x:\n        return 1\n    if x > 2:\n        return 2\n    return 3\n",
+        )
+        .unwrap();
+        std::fs::write(tmp.path().join("b.py"), "def other():\n    pass\n").unwrap();
 
-    filtered.truncate(limit);
+        let mut seen = Vec::new();
+        let stats = for_each_function_complexity(
+            tmp.path(),
+            usize::MAX,
+            Some(2),
+            &HashSet::new(),
+            None,
+            &[],
+            &mut |f| {
+                seen.push(f.name.clone());
+                Ok(())
+            },
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(seen, ["branchy"]);
+        assert_eq!(stats.total_count, 1);
+        assert_eq!(stats.total_max, 3);
 
-    ComplexityReport {
-        functions: filtered,
-        file_path: root.to_string_lossy().to_string(),
-        full_stats,
+        // The limit stops the walk, and stats cover only what was passed
+        let mut count = 0;
+        let stats = for_each_function_complexity(
+            tmp.path(),
+            2,
+            None,
+            &HashSet::new(),
+            None,
+            &[],
+            &mut |_| {
+                count += 1;
+                Ok(())
+            },
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(stats.total_count, 2);
+
+        // Errors from the callback are returned
+        let result = for_each_function_complexity(
+            tmp.path(),
+            usize::MAX,
+            None,
+            &HashSet::new(),
+            None,
+            &[],
+            &mut |_| Err(io::Error::other("stop")),
+        );
+        assert!(result.is_err());
     }
 }
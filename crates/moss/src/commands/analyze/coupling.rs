@@ -0,0 +1,428 @@
+//! Module coupling (`analyze coupling`).
+//!
+//! A dependency graph between the project's source files is built from their
+//! imports. Each import is resolved with its language's local import resolution
+//! (`crate::`, `super::`, relative paths), then by module name when exactly one
+//! file has that name; imports of external packages don't resolve and are left
+//! out. Per module (file) the report gives:
+//!
+//! - Ca, afferent coupling: modules that import it
+//! - Ce, efferent coupling: modules it imports
+//! - I = Ce / (Ca + Ce), instability: 0 for modules only depended upon, 1 for
+//!   modules that only depend on others
+//!
+//! Dependency cycles are the strongly connected components of the graph.
+
+use super::is_source_file;
+use crate::deps::LocalImportResolver;
+use crate::filter::Filter;
+use crate::output::{Table, terminal_width};
+use crate::path_resolve;
+use crate::symbols::SymbolParser;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::Path;
+
+/// Coupling metrics of one module.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleCoupling {
+    pub module: String,
+    /// Modules importing this one (Ca)
+    pub afferent: usize,
+    /// Modules this one imports (Ce)
+    pub efferent: usize,
+    /// Ce / (Ca + Ce), or None for a module with no dependencies either way
+    pub instability: Option<f64>,
+    /// Part of a dependency cycle
+    pub in_cycle: bool,
+}
+
+/// One module importing another.
+#[derive(Debug, Serialize)]
+pub struct ModuleDependency {
+    pub importer: String,
+    pub imported: String,
+}
+
+/// Module dependency graph with coupling metrics.
+#[derive(Debug, Serialize)]
+pub struct CouplingReport {
+    /// All modules, by path
+    pub modules: Vec<ModuleCoupling>,
+    pub dependencies: Vec<ModuleDependency>,
+    /// Groups of modules that depend on each other, each listed in the order of
+    /// a cycle through its first module
+    pub cycles: Vec<Vec<String>>,
+}
+
+impl CouplingReport {
+    /// Compute metrics from `deps[i]`, the indices of the modules `modules[i]`
+    /// imports.
+    pub fn new(modules: Vec<String>, deps: Vec<BTreeSet<usize>>) -> Self {
+        let mut afferent = vec![0; modules.len()];
+        for targets in &deps {
+            for &t in targets {
+                afferent[t] += 1;
+            }
+        }
+        let components = strongly_connected(&deps);
+        let mut in_cycle = vec![false; modules.len()];
+        for component in &components {
+            for &m in component {
+                in_cycle[m] = true;
+            }
+        }
+
+        CouplingReport {
+            modules: modules
+                .iter()
+                .enumerate()
+                .map(|(i, module)| {
+                    let (ca, ce) = (afferent[i], deps[i].len());
+                    ModuleCoupling {
+                        module: module.clone(),
+                        afferent: ca,
+                        efferent: ce,
+                        instability: (ca + ce > 0).then(|| ce as f64 / (ca + ce) as f64),
+                        in_cycle: in_cycle[i],
+                    }
+                })
+                .collect(),
+            dependencies: deps
+                .iter()
+                .enumerate()
+                .flat_map(|(i, targets)| {
+                    targets
+                        .iter()
+                        .map(|&t| ModuleDependency {
+                            importer: modules[i].clone(),
+                            imported: modules[t].clone(),
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect(),
+            cycles: components
+                .iter()
+                .map(|component| {
+                    cycle_through(component, &deps)
+                        .into_iter()
+                        .map(|m| modules[m].clone())
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    /// Modules most imported by others, most first.
+    pub fn most_depended_upon(&self) -> Vec<&ModuleCoupling> {
+        let mut ranked: Vec<_> = self.modules.iter().filter(|m| m.afferent > 0).collect();
+        ranked.sort_by(|a, b| b.afferent.cmp(&a.afferent).then(a.module.cmp(&b.module)));
+        ranked
+    }
+
+    /// Modules that depend on others, most unstable first (ties: most imports).
+    pub fn most_unstable(&self) -> Vec<&ModuleCoupling> {
+        let mut ranked: Vec<_> = self.modules.iter().filter(|m| m.efferent > 0).collect();
+        ranked.sort_by(|a, b| {
+            b.instability
+                .partial_cmp(&a.instability)
+                .unwrap()
+                .then(b.efferent.cmp(&a.efferent))
+                .then(a.module.cmp(&b.module))
+        });
+        ranked
+    }
+
+    /// Graphviz DOT graph of the dependencies; those within a cycle are red.
+    pub fn to_dot(&self) -> String {
+        let cycle_of: HashMap<&str, usize> = self
+            .cycles
+            .iter()
+            .enumerate()
+            .flat_map(|(i, cycle)| cycle.iter().map(move |m| (m.as_str(), i)))
+            .collect();
+        let mut out = String::from("digraph coupling {\n    rankdir=LR;\n    node [shape=box];\n");
+        for dep in &self.dependencies {
+            let cyclic = cycle_of
+                .get(dep.importer.as_str())
+                .is_some_and(|c| cycle_of.get(dep.imported.as_str()) == Some(c));
+            out.push_str(&format!(
+                "    \"{}\" -> \"{}\"{};\n",
+                dot_escape(&dep.importer),
+                dot_escape(&dep.imported),
+                if cyclic { " [color=red]" } else { "" }
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    pub fn format(&self, limit: usize) -> String {
+        let mut lines = vec!["# Module Coupling".to_string(), String::new()];
+        lines.push(format!(
+            "{} modules, {} dependencies, {} cycle{}",
+            self.modules.len(),
+            self.dependencies.len(),
+            self.cycles.len(),
+            if self.cycles.len() == 1 { "" } else { "s" }
+        ));
+
+        let sections = [
+            ("Most Depended-Upon", self.most_depended_upon()),
+            ("Most Unstable", self.most_unstable()),
+        ];
+        for (title, ranked) in sections {
+            if ranked.is_empty() {
+                continue;
+            }
+            lines.push(String::new());
+            lines.push(format!("## {}", title));
+            let mut table = Table::new(&["Module", "Ca", "Ce", "I", ""])
+                .align_right(1)
+                .align_right(2)
+                .align_right(3)
+                .shrink_column(0);
+            for m in ranked.iter().take(limit) {
+                table.row(vec![
+                    m.module.clone(),
+                    m.afferent.to_string(),
+                    m.efferent.to_string(),
+                    m.instability
+                        .map_or("-".to_string(), |i| format!("{:.2}", i)),
+                    if m.in_cycle { "cycle" } else { "" }.to_string(),
+                ]);
+            }
+            lines.push(table.render(terminal_width()).trim_end().to_string());
+        }
+
+        if !self.cycles.is_empty() {
+            lines.push(String::new());
+            lines.push("## Cycles".to_string());
+            for cycle in &self.cycles {
+                let mut path = cycle.clone();
+                path.push(cycle[0].clone());
+                lines.push(format!("  {}", path.join(" -> ")));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Strongly connected components with more than one node (the dependency
+/// cycles), by Tarjan's algorithm. Each is sorted; components are ordered by
+/// their first node.
+pub fn strongly_connected(deps: &[BTreeSet<usize>]) -> Vec<Vec<usize>> {
+    struct State<'a> {
+        deps: &'a [BTreeSet<usize>],
+        next: usize,
+        index: Vec<Option<usize>>,
+        low: Vec<usize>,
+        stack: Vec<usize>,
+        on_stack: Vec<bool>,
+        components: Vec<Vec<usize>>,
+    }
+
+    fn visit(s: &mut State, v: usize) {
+        s.index[v] = Some(s.next);
+        s.low[v] = s.next;
+        s.next += 1;
+        s.stack.push(v);
+        s.on_stack[v] = true;
+        for &w in s.deps[v].iter() {
+            match s.index[w] {
+                None => {
+                    visit(s, w);
+                    s.low[v] = s.low[v].min(s.low[w]);
+                }
+                Some(i) if s.on_stack[w] => s.low[v] = s.low[v].min(i),
+                Some(_) => {}
+            }
+        }
+        if Some(s.low[v]) == s.index[v] {
+            let mut component = Vec::new();
+            while let Some(w) = s.stack.pop() {
+                s.on_stack[w] = false;
+                component.push(w);
+                if w == v {
+                    break;
+                }
+            }
+            if component.len() > 1 {
+                component.sort_unstable();
+                s.components.push(component);
+            }
+        }
+    }
+
+    let n = deps.len();
+    let mut state = State {
+        deps,
+        next: 0,
+        index: vec![None; n],
+        low: vec![0; n],
+        stack: Vec::new(),
+        on_stack: vec![false; n],
+        components: Vec::new(),
+    };
+    for v in 0..n {
+        if state.index[v].is_none() {
+            visit(&mut state, v);
+        }
+    }
+    let mut components = state.components;
+    components.sort();
+    components
+}
+
+/// Shortest cycle from the first node of `component` back to it, staying in
+/// the component (which is strongly connected, so one exists).
+fn cycle_through(component: &[usize], deps: &[BTreeSet<usize>]) -> Vec<usize> {
+    let start = component[0];
+    let members: HashSet<usize> = component.iter().copied().collect();
+    let mut previous: HashMap<usize, usize> = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some(v) = queue.pop_front() {
+        for &w in &deps[v] {
+            if w == start {
+                let mut path = vec![v];
+                while let Some(&p) = previous.get(path.last().unwrap()) {
+                    path.push(p);
+                }
+                path.reverse();
+                return path;
+            }
+            if members.contains(&w) && !previous.contains_key(&w) {
+                previous.insert(w, v);
+                queue.push_back(w);
+            }
+        }
+    }
+    component.to_vec()
+}
+
+/// Build the module graph of the source files under `root`.
+pub fn analyze_coupling(root: &Path, filter: Option<&Filter>) -> CouplingReport {
+    let modules: Vec<String> = path_resolve::all_files(root)
+        .into_iter()
+        .filter(|f| f.kind == "file" && is_source_file(Path::new(&f.path)))
+        .filter(|f| filter.is_none_or(|flt| flt.matches(Path::new(&f.path))))
+        .map(|f| f.path)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let resolver = LocalImportResolver::new(root, &modules);
+    let position: HashMap<&str, usize> = modules
+        .iter()
+        .enumerate()
+        .map(|(i, m)| (m.as_str(), i))
+        .collect();
+
+    let deps: Vec<BTreeSet<usize>> = modules
+        .par_iter()
+        .map(|file| {
+            let Ok(content) = std::fs::read_to_string(root.join(file)) else {
+                return BTreeSet::new();
+            };
+            SymbolParser::new()
+                .parse_imports(Path::new(file), &content)
+                .iter()
+                .filter_map(|import| resolver.resolve(file, import.module.as_deref(), &import.name))
+                .filter(|target| target != file)
+                .filter_map(|target| position.get(target).copied())
+                .collect()
+        })
+        .collect();
+
+    CouplingReport::new(modules, deps)
+}
+
+/// Report module coupling as a table, JSON or DOT.
+pub fn cmd_coupling(
+    root: &Path,
+    limit: usize,
+    dot: bool,
+    json: bool,
+    filter: Option<&Filter>,
+) -> i32 {
+    let report = analyze_coupling(root, filter);
+    if dot {
+        print!("{}", report.to_dot());
+    } else if json {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+    } else {
+        println!("{}", report.format(limit));
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(n: usize, edges: &[(usize, usize)]) -> Vec<BTreeSet<usize>> {
+        let mut deps = vec![BTreeSet::new(); n];
+        for &(from, to) in edges {
+            deps[from].insert(to);
+        }
+        deps
+    }
+
+    #[test]
+    fn test_strongly_connected() {
+        // 0 -> 1 -> 2 -> 0 is a cycle, 3 <-> 4 another; 5 only depends on them
+        let deps = graph(6, &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 3), (5, 0), (5, 3)]);
+        assert_eq!(strongly_connected(&deps), vec![vec![0, 1, 2], vec![3, 4]]);
+        assert!(strongly_connected(&graph(3, &[(0, 1), (1, 2), (0, 2)])).is_empty());
+    }
+
+    #[test]
+    fn test_cycle_through() {
+        // Shortest way back to 0 is 0 -> 2 -> 0, not 0 -> 1 -> 2 -> 0
+        let deps = graph(3, &[(0, 1), (1, 2), (0, 2), (2, 0)]);
+        assert_eq!(cycle_through(&[0, 1, 2], &deps), vec![0, 2]);
+    }
+
+    #[test]
+    fn test_coupling_report() {
+        let modules: Vec<String> = ["a.py", "b.py", "c.py", "d.py"]
+            .iter()
+            .map(|m| m.to_string())
+            .collect();
+        // a -> b, a -> c, b -> c, c -> b; d is isolated
+        let report = CouplingReport::new(modules, graph(4, &[(0, 1), (0, 2), (1, 2), (2, 1)]));
+
+        let a = &report.modules[0];
+        assert_eq!((a.afferent, a.efferent, a.instability), (0, 2, Some(1.0)));
+        let b = &report.modules[1];
+        assert_eq!(
+            (b.afferent, b.efferent, b.instability),
+            (2, 1, Some(1.0 / 3.0))
+        );
+        assert!(b.in_cycle && !a.in_cycle);
+        assert_eq!(report.modules[3].instability, None);
+        assert_eq!(report.cycles, vec![vec!["b.py", "c.py"]]);
+
+        let depended: Vec<_> = report
+            .most_depended_upon()
+            .iter()
+            .map(|m| m.module.as_str())
+            .collect();
+        assert_eq!(depended, vec!["b.py", "c.py"]);
+        let unstable: Vec<_> = report
+            .most_unstable()
+            .iter()
+            .map(|m| m.module.as_str())
+            .collect();
+        assert_eq!(unstable, vec!["a.py", "b.py", "c.py"]);
+
+        let dot = report.to_dot();
+        assert!(dot.contains("\"a.py\" -> \"b.py\";"));
+        assert!(dot.contains("\"b.py\" -> \"c.py\" [color=red];"));
+    }
+}
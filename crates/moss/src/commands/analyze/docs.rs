@@ -293,7 +293,7 @@ fn process_file(
 }
 
 /// Check if a file is a test file (should be excluded from doc coverage)
-fn is_test_file(path: &str) -> bool {
+pub(crate) fn is_test_file(path: &str) -> bool {
     let path_lower = path.to_lowercase();
 
     // Check path components for test directories
@@ -1,11 +1,19 @@
-//! Duplicate function and type detection.
+//! Duplicate function, type and string literal detection.
 
+use super::allowlist;
+use super::extraction::{self, ExtractionSuggestion};
+use crate::codeowners::CodeOwners;
 use crate::extract::Extractor;
-use crate::filter::Filter;
+use crate::filter::{Filter, TestLines, TestScope};
+use crate::output::{CsvWriter, JsonLinesWriter, OutputFormat};
 use crate::parsers;
-use rhizome_moss_languages::support_for_path;
+use crate::text_search::{SyntaxRegions, line_offsets};
+use glob::Pattern;
+use rhizome_moss_languages::{Language, support_for_path};
+use serde::Serialize;
 use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 
 /// A group of duplicate functions
@@ -18,11 +26,11 @@ struct DuplicateFunctionGroup {
 
 /// Location of a duplicate function instance
 #[derive(Debug)]
-struct DuplicateFunctionLocation {
-    file: String,
-    symbol: String,
-    start_line: usize,
-    end_line: usize,
+pub(super) struct DuplicateFunctionLocation {
+    pub file: String,
+    pub symbol: String,
+    pub start_line: usize,
+    pub end_line: usize,
 }
 
 /// Result from duplicate function detection.
@@ -35,28 +43,151 @@ pub struct DuplicateFunctionResult {
 
 /// Load allowed duplicate function locations from .moss/duplicate-functions-allow file
 fn load_duplicate_functions_allowlist(root: &Path) -> HashSet<String> {
-    let path = root.join(".moss/duplicate-functions-allow");
-    let mut allowed = HashSet::new();
-    if let Ok(content) = std::fs::read_to_string(&path) {
-        for line in content.lines() {
-            let line = line.trim();
-            if line.is_empty() || line.starts_with('#') {
-                continue;
+    allowlist::load(root, "duplicate-functions-allow")
+        .into_iter()
+        .collect()
+}
+
+/// Two type names, in sorted order so that a pair matches either way round.
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct TypePair {
+    first: String,
+    second: String,
+}
+
+impl TypePair {
+    fn new(a: &str, b: &str) -> Self {
+        let (first, second) = if a < b { (a, b) } else { (b, a) };
+        Self {
+            first: first.to_string(),
+            second: second.to_string(),
+        }
+    }
+}
+
+/// Load allowed type pairs from .moss/duplicate-types-allow
+fn load_duplicate_types_allowlist(root: &Path) -> HashSet<TypePair> {
+    allowlist::load(root, "duplicate-types-allow")
+        .iter()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            match parts[..] {
+                [a, b] => Some(TypePair::new(a, b)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Comment text marking code as generated (matched case-insensitively).
+const GENERATED_MARKERS: &[&str] = &[
+    "@generated",
+    "generated by",
+    "auto-generated",
+    "autogenerated",
+    "code generated",
+    "do not edit",
+];
+
+/// Load file globs excluded from duplicate-function hashing, from
+/// .moss/duplicate-functions-ignore (one per line, `#` comments).
+fn load_duplicate_functions_ignore(root: &Path) -> Vec<Pattern> {
+    allowlist::load(root, "duplicate-functions-ignore")
+        .iter()
+        .filter_map(|p| match Pattern::new(p) {
+            Ok(pattern) => Some(pattern),
+            Err(e) => {
+                eprintln!(
+                    "warning: duplicate-functions-ignore: invalid glob {}: {}",
+                    p, e
+                );
+                None
+            }
+        })
+        .collect()
+}
+
+/// Whether a comment carries a generated-code marker.
+fn is_generated_marker(comment: &str) -> bool {
+    let lower = comment.to_lowercase();
+    GENERATED_MARKERS.iter().any(|m| lower.contains(m))
+}
+
+/// Whether the file starts with a generated-code header: a marker in the
+/// comments before the first code.
+fn is_generated_file(content: &str, regions: &SyntaxRegions) -> bool {
+    let mut pos = 0;
+    for comment in &regions.comments {
+        if !content[pos..comment.start].trim().is_empty() {
+            break;
+        }
+        if is_generated_marker(&content[comment.clone()]) {
+            return true;
+        }
+        pos = comment.end;
+    }
+    false
+}
+
+/// Whether the function on 1-based lines `start_line..=end_line` begins with a marker:
+/// in the comments directly above it (attributes in between are skipped), or in
+/// comments on its first lines (inside the body or after the signature, where
+/// macro-expanded and templated code puts it).
+fn is_generated_function(
+    content: &str,
+    regions: &SyntaxRegions,
+    offsets: &[usize],
+    start_line: usize,
+    end_line: usize,
+) -> bool {
+    let line_start = |line: usize| offsets.get(line).copied().unwrap_or(content.len());
+    let start = line_start(start_line.saturating_sub(1));
+    let head_end = line_start(end_line.min(start_line + 2));
+
+    // Walk backwards over the comments and attributes preceding the function
+    let mut pos = start;
+    loop {
+        let end = content[..pos].trim_end().len();
+        if let Some(comment) = region_ending_at(&regions.comments, content, end) {
+            if is_generated_marker(&content[comment.clone()]) {
+                return true;
             }
-            allowed.insert(line.to_string());
+            pos = comment.start;
+        } else if let Some(attribute) = region_ending_at(&regions.attributes, content, end) {
+            pos = attribute.start;
+        } else {
+            break;
         }
     }
-    allowed
+
+    regions
+        .comments
+        .iter()
+        .filter(|c| c.start >= start && c.start < head_end)
+        .any(|c| is_generated_marker(&content[c.clone()]))
+}
+
+/// The region of the sorted, non-overlapping `ranges` whose text ends at
+/// `end`, ignoring trailing whitespace (some grammars include the newline).
+fn region_ending_at<'a>(
+    ranges: &'a [Range<usize>],
+    content: &str,
+    end: usize,
+) -> Option<&'a Range<usize>> {
+    let idx = ranges.partition_point(|r| r.start < end);
+    let range = ranges.get(idx.checked_sub(1)?)?;
+    (range.end >= end && content[end..range.end].trim().is_empty()).then_some(range)
 }
 
 /// Detect all duplicate function groups in the codebase (before filtering by allowlist)
 fn detect_duplicate_function_groups(
     root: &Path,
-    elide_identifiers: bool,
-    elide_literals: bool,
+    options: &NormalizeOptions,
     min_lines: usize,
+    tests: TestScope,
 ) -> Vec<DuplicateFunctionGroup> {
     let extractor = Extractor::new();
+    let ignore = load_duplicate_functions_ignore(root);
 
     let mut hash_groups: HashMap<u64, Vec<DuplicateFunctionLocation>> = HashMap::new();
 
@@ -72,6 +203,10 @@ fn detect_duplicate_function_groups(
         path.is_file() && super::is_source_file(path)
     }) {
         let path = entry.path();
+        let rel_path = path.strip_prefix(root).unwrap_or(path);
+        if ignore.iter().any(|pat| pat.matches_path(rel_path)) {
+            continue;
+        }
         let content = match std::fs::read_to_string(path) {
             Ok(c) => c,
             Err(_) => continue,
@@ -82,18 +217,30 @@ fn detect_duplicate_function_groups(
             None => continue,
         };
 
-        let tree = match parsers::parse_with_grammar(support.grammar_name(), &content) {
+        let tree = match parsers::parse_cached(support.grammar_name(), &content) {
             Some(t) => t,
             None => continue,
         };
+        let regions = SyntaxRegions::from_tree(&tree);
+        if is_generated_file(&content, &regions) {
+            continue;
+        }
+        let offsets = line_offsets(&content);
 
         let result = extractor.extract(path, &content);
+        let test_lines = TestLines::from_symbols(tests, path, &result.symbols);
 
-        for sym in result.symbols.iter().flat_map(|s| flatten_symbols(s)) {
+        for sym in result.symbols.iter().flat_map(|s| s.iter_flat()) {
             let kind = sym.kind.as_str();
             if kind != "function" && kind != "method" {
                 continue;
             }
+            if !test_lines.keeps(sym.start_line) {
+                continue;
+            }
+            if is_generated_function(&content, &regions, &offsets, sym.start_line, sym.end_line) {
+                continue;
+            }
 
             if let Some(node) = find_function_node(&tree, sym.start_line) {
                 let line_count = sym.end_line.saturating_sub(sym.start_line) + 1;
@@ -101,12 +248,7 @@ fn detect_duplicate_function_groups(
                     continue;
                 }
 
-                let hash = compute_function_hash(
-                    &node,
-                    content.as_bytes(),
-                    elide_identifiers,
-                    elide_literals,
-                );
+                let hash = compute_function_hash(&node, content.as_bytes(), support, options);
 
                 let rel_path = path
                     .strip_prefix(root)
@@ -154,18 +296,46 @@ fn detect_duplicate_function_groups(
     groups
 }
 
+/// Files of each duplicate function group (one entry per instance), with the
+/// allowlist and filter applied as by `analyze duplicate-functions`.
+pub(super) fn duplicate_function_files(
+    root: &Path,
+    options: &NormalizeOptions,
+    min_lines: usize,
+    filter: Option<&Filter>,
+) -> Vec<Vec<String>> {
+    let allowlist = load_duplicate_functions_allowlist(root);
+    let tests = filter.map_or(TestScope::All, |f| f.tests());
+    detect_duplicate_function_groups(root, options, min_lines, tests)
+        .into_iter()
+        .filter(|group| {
+            group
+                .locations
+                .iter()
+                .any(|loc| !allowlist.contains(&format!("{}:{}", loc.file, loc.symbol)))
+        })
+        .filter_map(|group| {
+            let files: Vec<String> = group
+                .locations
+                .into_iter()
+                .filter(|loc| filter.is_none_or(|f| f.matches(Path::new(&loc.file))))
+                .map(|loc| loc.file)
+                .collect();
+            (files.len() >= 2).then_some(files)
+        })
+        .collect()
+}
+
 /// Allow a specific duplicate function group by adding it to .moss/duplicate-functions-allow
 pub fn cmd_allow_duplicate_function(
     root: &Path,
     location: &str,
     reason: Option<&str>,
-    elide_identifiers: bool,
-    elide_literals: bool,
+    options: &NormalizeOptions,
     min_lines: usize,
 ) -> i32 {
     // Detect all duplicate function groups
-    let all_groups =
-        detect_duplicate_function_groups(root, elide_identifiers, elide_literals, min_lines);
+    let all_groups = detect_duplicate_function_groups(root, options, min_lines, TestScope::All);
 
     // Find the group containing this location
     // Support both formats:
@@ -193,103 +363,53 @@ pub fn cmd_allow_duplicate_function(
         }
     };
 
-    // Load existing allowlist to check for overlap
-    let allowlist_path = root.join(".moss/duplicate-functions-allow");
-    let existing_content = std::fs::read_to_string(&allowlist_path).unwrap_or_default();
-    let existing_lines: Vec<&str> = existing_content.lines().collect();
-
-    // Check if any entries from this group already exist
-    let mut has_existing = false;
-    let mut insert_after: Option<usize> = None;
-
-    for loc in &group.locations {
-        let entry = format!("{}:{}", loc.file, loc.symbol);
-        for (i, line) in existing_lines.iter().enumerate() {
-            if line.trim() == entry {
-                has_existing = true;
-                insert_after = Some(insert_after.map_or(i, |prev| prev.max(i)));
-            }
-        }
-    }
-
-    // Require reason for new groups
-    if !has_existing && reason.is_none() {
-        eprintln!("Reason required for new groups. Use --reason \"...\"");
-        return 1;
-    }
-
-    // Build entries to add
-    let mut to_add: Vec<String> = Vec::new();
-    for loc in &group.locations {
-        let entry = format!("{}:{}", loc.file, loc.symbol);
-        if !existing_lines.iter().any(|l| l.trim() == entry) {
-            to_add.push(entry);
-        }
-    }
-
-    if to_add.is_empty() {
-        println!("All entries from this group are already allowed.");
-        return 0;
-    }
-
-    // Build new content with smart insertion
-    let mut new_lines: Vec<String> = existing_lines.iter().map(|s| s.to_string()).collect();
-
-    if let Some(idx) = insert_after {
-        // Insert near existing entries from this group
-        let insert_pos = idx + 1;
-        for (i, entry) in to_add.iter().enumerate() {
-            new_lines.insert(insert_pos + i, entry.clone());
-        }
-    } else {
-        // Append at end with reason as comment
-        if !new_lines.is_empty() && !new_lines.last().map_or(true, |l| l.is_empty()) {
-            new_lines.push(String::new());
-        }
-        if let Some(r) = reason {
-            new_lines.push(format!("# {}", r));
-        }
-        for entry in &to_add {
-            new_lines.push(entry.clone());
-        }
-    }
-
-    // Write back
-    let new_content = new_lines.join("\n") + "\n";
-    if let Err(e) = std::fs::write(&allowlist_path, new_content) {
-        eprintln!("Failed to write .moss/duplicate-functions-allow: {}", e);
-        return 1;
-    }
-
-    println!(
-        "Added {} entries to .moss/duplicate-functions-allow:",
-        to_add.len()
-    );
-    for entry in &to_add {
-        println!("  {}", entry);
-    }
+    let entries: Vec<String> = group
+        .locations
+        .iter()
+        .map(|loc| format!("{}:{}", loc.file, loc.symbol))
+        .collect();
+    allowlist::add_entries(
+        root,
+        "duplicate-functions-allow",
+        &entries,
+        &entries,
+        reason,
+    )
+}
 
-    0
+/// Totals of a duplicate function detection run.
+#[derive(Debug, Default, Serialize)]
+struct DuplicateFunctionSummary {
+    files_scanned: usize,
+    functions_hashed: usize,
+    /// Skipped via duplicate-functions-ignore or a generated-code header
+    files_ignored: usize,
+    functions_generated: usize,
+    duplicate_groups: usize,
+    total_duplicates: usize,
+    duplicated_lines: usize,
 }
 
-/// Detect duplicate functions.
-pub fn cmd_duplicate_functions_with_count(
+/// Hash the functions under `root` and pass each duplicate group that isn't
+/// fully allowed to `on_group`, in no particular order. Groups are only known
+/// once every file has been hashed, so they arrive after the walk. Stops at the
+/// first error from `on_group`, which is returned.
+fn for_each_duplicate_function_group(
     root: &Path,
-    elide_identifiers: bool,
-    elide_literals: bool,
-    show_source: bool,
+    options: &NormalizeOptions,
     min_lines: usize,
-    json: bool,
     filter: Option<&Filter>,
-) -> DuplicateFunctionResult {
+    on_group: &mut dyn FnMut(DuplicateFunctionGroup) -> std::io::Result<()>,
+) -> std::io::Result<DuplicateFunctionSummary> {
     let extractor = Extractor::new();
 
     let allowlist = load_duplicate_functions_allowlist(root);
+    let ignore = load_duplicate_functions_ignore(root);
+    let tests = filter.map_or(TestScope::All, |f| f.tests());
 
     // Collect function hashes: hash -> [(file, symbol, start, end)]
     let mut hash_groups: HashMap<u64, Vec<DuplicateFunctionLocation>> = HashMap::new();
-    let mut files_scanned = 0;
-    let mut functions_hashed = 0;
+    let mut summary = DuplicateFunctionSummary::default();
 
     // Walk source files, respecting .gitignore
     let walker = ignore::WalkBuilder::new(root)
@@ -306,11 +426,15 @@ pub fn cmd_duplicate_functions_with_count(
         let path = entry.path();
 
         // Apply filter if specified
-        if let Some(f) = filter {
-            let rel_path = path.strip_prefix(root).unwrap_or(path);
-            if !f.matches(rel_path) {
-                continue;
-            }
+        let rel_path = path.strip_prefix(root).unwrap_or(path);
+        if let Some(f) = filter
+            && !f.matches(rel_path)
+        {
+            continue;
+        }
+        if ignore.iter().any(|pat| pat.matches_path(rel_path)) {
+            summary.files_ignored += 1;
+            continue;
         }
 
         let content = match std::fs::read_to_string(path) {
@@ -324,22 +448,36 @@ pub fn cmd_duplicate_functions_with_count(
         };
 
         // Parse the file
-        let tree = match parsers::parse_with_grammar(support.grammar_name(), &content) {
+        let tree = match parsers::parse_cached(support.grammar_name(), &content) {
             Some(t) => t,
             None => continue,
         };
+        let regions = SyntaxRegions::from_tree(&tree);
+        if is_generated_file(&content, &regions) {
+            summary.files_ignored += 1;
+            continue;
+        }
+        let offsets = line_offsets(&content);
 
-        files_scanned += 1;
+        summary.files_scanned += 1;
 
         // Extract symbols to find functions/methods
         let result = extractor.extract(path, &content);
+        let test_lines = TestLines::from_symbols(tests, path, &result.symbols);
 
         // Find and hash each function/method
-        for sym in result.symbols.iter().flat_map(|s| flatten_symbols(s)) {
+        for sym in result.symbols.iter().flat_map(|s| s.iter_flat()) {
             let kind = sym.kind.as_str();
             if kind != "function" && kind != "method" {
                 continue;
             }
+            if !test_lines.keeps(sym.start_line) {
+                continue;
+            }
+            if is_generated_function(&content, &regions, &offsets, sym.start_line, sym.end_line) {
+                summary.functions_generated += 1;
+                continue;
+            }
 
             // Find the function node
             if let Some(node) = find_function_node(&tree, sym.start_line) {
@@ -348,13 +486,8 @@ pub fn cmd_duplicate_functions_with_count(
                     continue;
                 }
 
-                let hash = compute_function_hash(
-                    &node,
-                    content.as_bytes(),
-                    elide_identifiers,
-                    elide_literals,
-                );
-                functions_hashed += 1;
+                let hash = compute_function_hash(&node, content.as_bytes(), support, options);
+                summary.functions_hashed += 1;
 
                 let rel_path = path
                     .strip_prefix(root)
@@ -375,28 +508,76 @@ pub fn cmd_duplicate_functions_with_count(
         }
     }
 
-    // Filter to groups with 2+ instances (actual duplicates)
-    // Skip groups where ALL locations are in the allowlist
-    let mut groups: Vec<DuplicateFunctionGroup> = hash_groups
-        .into_iter()
-        .filter(|(_, locs)| locs.len() >= 2)
-        .filter(|(_, locs)| {
-            // Keep if any location is NOT allowed
-            locs.iter()
-                .any(|loc| !allowlist.contains(&format!("{}:{}", loc.file, loc.symbol)))
-        })
-        .map(|(hash, locations)| {
-            let line_count = locations
-                .first()
-                .map(|l| l.end_line - l.start_line + 1)
-                .unwrap_or(0);
-            DuplicateFunctionGroup {
-                hash,
-                locations,
-                line_count,
+    // Groups with 2+ instances (actual duplicates), unless ALL locations are allowed
+    for (hash, locations) in hash_groups {
+        if locations.len() < 2
+            || locations
+                .iter()
+                .all(|loc| allowlist.contains(&format!("{}:{}", loc.file, loc.symbol)))
+        {
+            continue;
+        }
+        let line_count = locations
+            .first()
+            .map(|l| l.end_line - l.start_line + 1)
+            .unwrap_or(0);
+        summary.duplicate_groups += 1;
+        summary.total_duplicates += locations.len();
+        summary.duplicated_lines += line_count * locations.len();
+        on_group(DuplicateFunctionGroup {
+            hash,
+            locations,
+            line_count,
+        })?;
+    }
+    Ok(summary)
+}
+
+/// Detect duplicate functions. With `suggest`, each group also gets an
+/// extraction suggestion (which instance to keep, stubs for the others).
+pub fn cmd_duplicate_functions_with_count(
+    root: &Path,
+    options: &NormalizeOptions,
+    show_source: bool,
+    suggest: bool,
+    min_lines: usize,
+    format: &OutputFormat,
+    filter: Option<&Filter>,
+) -> DuplicateFunctionResult {
+    let owners = CodeOwners::load(root);
+
+    if format.is_json_lines() {
+        let group_count = match stream_duplicate_function_groups(
+            root,
+            options,
+            suggest,
+            min_lines,
+            filter,
+            owners.as_ref(),
+        ) {
+            Ok(summary) => summary.duplicate_groups,
+            // The reader stopped early (e.g. `| head`)
+            Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => 0,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                return DuplicateFunctionResult {
+                    exit_code: 1,
+                    group_count: 0,
+                };
             }
-        })
-        .collect();
+        };
+        return DuplicateFunctionResult {
+            exit_code: if group_count == 0 { 0 } else { 1 },
+            group_count,
+        };
+    }
+
+    let mut groups: Vec<DuplicateFunctionGroup> = Vec::new();
+    let summary = for_each_duplicate_function_group(root, options, min_lines, filter, &mut |g| {
+        groups.push(g);
+        Ok(())
+    })
+    .unwrap_or_default();
 
     // Sort by line count (larger duplicates first), then by number of instances
     groups.sort_by(|a, b| {
@@ -405,46 +586,70 @@ pub fn cmd_duplicate_functions_with_count(
             .then_with(|| b.locations.len().cmp(&a.locations.len()))
     });
 
-    let total_duplicates: usize = groups.iter().map(|g| g.locations.len()).sum();
-    let duplicated_lines: usize = groups
-        .iter()
-        .map(|g| g.line_count * g.locations.len())
-        .sum();
+    let suggestions: Vec<ExtractionSuggestion> = if suggest {
+        let importers = extraction::importer_counts(
+            root,
+            groups
+                .iter()
+                .flat_map(|g| g.locations.iter().map(|l| l.file.as_str())),
+        );
+        groups
+            .iter()
+            .map(|g| extraction::suggest(root, &g.locations, importers.as_ref()))
+            .collect()
+    } else {
+        Vec::new()
+    };
 
-    if json {
+    if format.is_csv() {
+        let mut csv =
+            CsvWriter::new(&["group", "file", "symbol", "start_line", "end_line", "lines"]);
+        for (i, group) in groups.iter().enumerate() {
+            for loc in &group.locations {
+                csv.row(&[
+                    &(i + 1),
+                    &loc.file,
+                    &loc.symbol,
+                    &loc.start_line,
+                    &loc.end_line,
+                    &group.line_count,
+                ]);
+            }
+        }
+        print!("{}", csv.finish());
+    } else if format.is_json() {
         let output = serde_json::json!({
-            "files_scanned": files_scanned,
-            "functions_hashed": functions_hashed,
-            "duplicate_groups": groups.len(),
-            "total_duplicates": total_duplicates,
-            "duplicated_lines": duplicated_lines,
-            "elide_identifiers": elide_identifiers,
-            "elide_literals": elide_literals,
-            "groups": groups.iter().map(|g| {
-                serde_json::json!({
-                    "hash": format!("{:016x}", g.hash),
-                    "line_count": g.line_count,
-                    "instances": g.locations.len(),
-                    "locations": g.locations.iter().map(|l| {
-                        serde_json::json!({
-                            "file": l.file,
-                            "symbol": l.symbol,
-                            "start_line": l.start_line,
-                            "end_line": l.end_line,
-                        })
-                    }).collect::<Vec<_>>(),
-                })
+            "files_scanned": summary.files_scanned,
+            "functions_hashed": summary.functions_hashed,
+            "files_ignored": summary.files_ignored,
+            "functions_generated": summary.functions_generated,
+            "duplicate_groups": summary.duplicate_groups,
+            "total_duplicates": summary.total_duplicates,
+            "duplicated_lines": summary.duplicated_lines,
+            "elide_identifiers": options.elide_identifiers,
+            "elide_literals": options.elide_literals,
+            "elide_types": options.elide_types,
+            "ignore_comments": options.ignore_comments,
+            "ignore_statement_order": options.ignore_statement_order,
+            "groups": groups.iter().enumerate().map(|(i, g)| {
+                group_json(g, suggestions.get(i), root, owners.as_ref())
             }).collect::<Vec<_>>(),
         });
         println!("{}", serde_json::to_string_pretty(&output).unwrap());
     } else {
         println!("Duplicate Function Detection");
         println!();
-        println!("Files scanned: {}", files_scanned);
-        println!("Functions hashed: {}", functions_hashed);
-        println!("Duplicate groups: {}", groups.len());
-        println!("Total duplicates: {}", total_duplicates);
-        println!("Duplicated lines: ~{}", duplicated_lines);
+        println!("Files scanned: {}", summary.files_scanned);
+        println!("Functions hashed: {}", summary.functions_hashed);
+        if summary.files_ignored > 0 || summary.functions_generated > 0 {
+            println!(
+                "Skipped as generated or ignored: {} files, {} functions",
+                summary.files_ignored, summary.functions_generated
+            );
+        }
+        println!("Duplicate groups: {}", summary.duplicate_groups);
+        println!("Total duplicates: {}", summary.total_duplicates);
+        println!("Duplicated lines: ~{}", summary.duplicated_lines);
         println!();
 
         if groups.is_empty() {
@@ -481,6 +686,9 @@ pub fn cmd_duplicate_functions_with_count(
                         }
                     }
                 }
+                if let Some(suggestion) = suggestions.get(i) {
+                    print_suggestion(suggestion, &group.locations);
+                }
                 println!();
             }
 
@@ -498,6 +706,137 @@ pub fn cmd_duplicate_functions_with_count(
     }
 }
 
+/// Stream duplicate function groups as JSON lines: a header, one record per
+/// group as detection yields it (unsorted), then a summary record with totals.
+fn stream_duplicate_function_groups(
+    root: &Path,
+    options: &NormalizeOptions,
+    suggest: bool,
+    min_lines: usize,
+    filter: Option<&Filter>,
+    owners: Option<&CodeOwners>,
+) -> std::io::Result<DuplicateFunctionSummary> {
+    let mut out = JsonLinesWriter::stdout();
+    out.header(
+        "duplicate-functions",
+        serde_json::json!({
+            "root": root.display().to_string(),
+            "min_lines": min_lines,
+            "elide_identifiers": options.elide_identifiers,
+            "elide_literals": options.elide_literals,
+            "elide_types": options.elide_types,
+            "ignore_comments": options.ignore_comments,
+            "ignore_statement_order": options.ignore_statement_order,
+        }),
+    )?;
+    let summary =
+        for_each_duplicate_function_group(root, options, min_lines, filter, &mut |group| {
+            let suggestion = suggest.then(|| {
+                let importers = extraction::importer_counts(
+                    root,
+                    group.locations.iter().map(|l| l.file.as_str()),
+                );
+                extraction::suggest(root, &group.locations, importers.as_ref())
+            });
+            out.record(
+                "duplicate_group",
+                &group_json(&group, suggestion.as_ref(), root, owners),
+            )
+        })?;
+    out.record("summary", &summary)?;
+    Ok(summary)
+}
+
+/// JSON for one duplicate group, with its extraction suggestion if requested.
+fn group_json(
+    group: &DuplicateFunctionGroup,
+    suggestion: Option<&ExtractionSuggestion>,
+    root: &Path,
+    owners: Option<&CodeOwners>,
+) -> serde_json::Value {
+    let mut value = serde_json::json!({
+        "hash": format!("{:016x}", group.hash),
+        "line_count": group.line_count,
+        "instances": group.locations.len(),
+        "locations": group.locations.iter().map(|l| location_json(l, root, owners)).collect::<Vec<_>>(),
+    });
+    if let Some(suggestion) = suggestion {
+        let kept = &group.locations[suggestion.keep];
+        value["suggestion"] = serde_json::json!({
+            "keep": {
+                "file": kept.file,
+                "symbol": kept.symbol,
+                "start_line": kept.start_line,
+                "end_line": kept.end_line,
+                "importers": suggestion.importers.as_ref().map(|counts| counts[suggestion.keep]),
+            },
+            "shared_dir": suggestion.shared_dir,
+            "stubs": suggestion.stubs.iter().map(|stub| {
+                let loc = &group.locations[stub.location];
+                serde_json::json!({
+                    "file": loc.file,
+                    "symbol": loc.symbol,
+                    "start_line": loc.start_line,
+                    "end_line": loc.end_line,
+                    "diff": stub.diff.join("\n"),
+                })
+            }).collect::<Vec<_>>(),
+        });
+    }
+    value
+}
+
+/// Print which instance to keep and how the others could delegate to it.
+fn print_suggestion(suggestion: &ExtractionSuggestion, locations: &[DuplicateFunctionLocation]) {
+    let kept = &locations[suggestion.keep];
+    let reason = match &suggestion.importers {
+        Some(counts) => match counts[suggestion.keep] {
+            1 => " (module imported by 1 file)".to_string(),
+            n => format!(" (module imported by {} files)", n),
+        },
+        None => " (no index: chosen by path depth)".to_string(),
+    };
+    println!(
+        "   Suggestion: keep {}:{} ({}){}",
+        kept.file, kept.start_line, kept.symbol, reason
+    );
+    if let Some(dir) = &suggestion.shared_dir {
+        println!(
+            "   Instances span modules; a shared module could live in {}/",
+            dir
+        );
+    }
+    for stub in &suggestion.stubs {
+        let loc = &locations[stub.location];
+        println!(
+            "   --- {}:{}-{} ({})",
+            loc.file, loc.start_line, loc.end_line, loc.symbol
+        );
+        println!("   +++ delegate to {}", kept.symbol);
+        for line in &stub.diff {
+            println!("   {}", line);
+        }
+    }
+}
+
+/// JSON for one duplicate location, with its `owner` if there is a CODEOWNERS file.
+fn location_json(
+    loc: &DuplicateFunctionLocation,
+    root: &Path,
+    owners: Option<&CodeOwners>,
+) -> serde_json::Value {
+    let mut value = serde_json::json!({
+        "file": loc.file,
+        "symbol": loc.symbol,
+        "start_line": loc.start_line,
+        "end_line": loc.end_line,
+    });
+    if let Some(owners) = owners {
+        super::ownership::set_owner(&mut value, &loc.file, root, owners);
+    }
+    value
+}
+
 /// Detect duplicate type definitions (structs with similar fields)
 pub fn cmd_duplicate_types(
     root: &Path,
@@ -510,26 +849,7 @@ pub fn cmd_duplicate_types(
     let extractor = Extractor::new();
 
     // Load allowlist
-    let allowlist_path = config_root.join(".moss/duplicate-types-allow");
-    let allowed_pairs: HashSet<(String, String)> = std::fs::read_to_string(&allowlist_path)
-        .unwrap_or_default()
-        .lines()
-        .filter(|l| !l.trim().is_empty() && !l.trim().starts_with('#'))
-        .filter_map(|l| {
-            let parts: Vec<&str> = l.trim().split_whitespace().collect();
-            if parts.len() == 2 {
-                // Store in sorted order for consistent matching
-                let (a, b) = if parts[0] < parts[1] {
-                    (parts[0].to_string(), parts[1].to_string())
-                } else {
-                    (parts[1].to_string(), parts[0].to_string())
-                };
-                Some((a, b))
-            } else {
-                None
-            }
-        })
-        .collect();
+    let allowed_pairs = load_duplicate_types_allowlist(config_root);
 
     // Type location info
     #[derive(Debug, Clone)]
@@ -581,7 +901,7 @@ pub fn cmd_duplicate_types(
         let lines: Vec<&str> = content.lines().collect();
 
         // Find type symbols (struct, class, interface, etc.)
-        for sym in result.symbols.iter().flat_map(|s| flatten_symbols(s)) {
+        for sym in result.symbols.iter().flat_map(|s| s.iter_flat()) {
             let kind = sym.kind.as_str();
             if !matches!(kind, "struct" | "class" | "interface" | "type") {
                 continue;
@@ -644,12 +964,7 @@ pub fn cmd_duplicate_types(
             }
 
             // Skip if pair is in allowlist
-            let pair_key = if t1.name < t2.name {
-                (t1.name.clone(), t2.name.clone())
-            } else {
-                (t2.name.clone(), t1.name.clone())
-            };
-            if allowed_pairs.contains(&pair_key) {
+            if allowed_pairs.contains(&TypePair::new(&t1.name, &t2.name)) {
                 continue;
             }
 
@@ -763,78 +1078,20 @@ pub fn cmd_allow_duplicate_type(
     type2: &str,
     reason: Option<&str>,
 ) -> i32 {
-    // Normalize to sorted order
-    let (type1, type2) = if type1 < type2 {
-        (type1, type2)
-    } else {
-        (type2, type1)
-    };
-    let entry = format!("{} {}", type1, type2);
-
-    // Load existing allowlist
-    let allowlist_path = root.join(".moss/duplicate-types-allow");
-    let existing_content = std::fs::read_to_string(&allowlist_path).unwrap_or_default();
-    let existing_lines: Vec<&str> = existing_content.lines().collect();
-
-    // Check if already exists
-    for line in &existing_lines {
-        let parts: Vec<&str> = line.trim().split_whitespace().collect();
-        if parts.len() == 2 {
-            let (a, b) = if parts[0] < parts[1] {
-                (parts[0], parts[1])
-            } else {
-                (parts[1], parts[0])
-            };
-            if a == type1 && b == type2 {
-                println!("Pair already allowed: {}", entry);
-                return 0;
-            }
-        }
-    }
-
-    // Require reason for new entries
-    if reason.is_none() {
-        eprintln!("Reason required for new type pairs. Use --reason \"...\"");
-        return 1;
-    }
-
-    // Build new content
-    let mut new_lines: Vec<String> = existing_lines.iter().map(|s| s.to_string()).collect();
-    if !new_lines.is_empty() && !new_lines.last().map_or(true, |l| l.is_empty()) {
-        new_lines.push(String::new());
-    }
-    if let Some(r) = reason {
-        new_lines.push(format!("# {}", r));
-    }
-    new_lines.push(entry.clone());
-
-    // Ensure .moss directory exists
-    let moss_dir = root.join(".moss");
-    if !moss_dir.exists() {
-        if let Err(e) = std::fs::create_dir_all(&moss_dir) {
-            eprintln!("Failed to create .moss directory: {}", e);
-            return 1;
-        }
-    }
-
-    // Write back
-    let new_content = new_lines.join("\n") + "\n";
-    if let Err(e) = std::fs::write(&allowlist_path, new_content) {
-        eprintln!("Failed to write .moss/duplicate-types-allow: {}", e);
-        return 1;
+    let pair = TypePair::new(type1, type2);
+    if load_duplicate_types_allowlist(root).contains(&pair) {
+        println!("Pair already allowed: {} {}", pair.first, pair.second);
+        return 0;
     }
 
-    println!("Added to .moss/duplicate-types-allow: {}", entry);
-    0
-}
-
-/// Flatten nested symbols into a flat list
-fn flatten_symbols(sym: &rhizome_moss_languages::Symbol) -> Vec<&rhizome_moss_languages::Symbol> {
-    let mut result = vec![sym];
-    for child in &sym.children {
-        result.extend(flatten_symbols(child));
-    }
-    result
+    let entry = format!("{} {}", pair.first, pair.second);
+    allowlist::add_entries(
+        root,
+        "duplicate-types-allow",
+        std::slice::from_ref(&entry),
+        std::slice::from_ref(&entry),
+        reason,
+    )
 }
 
 /// Find a function node at a given line
@@ -884,22 +1141,81 @@ fn find_node_at_line_recursive<'a>(
     None
 }
 
-/// Compute a normalized AST hash for duplicate function detection.
+/// How much of a function's AST is folded away before hashing.
+///
+/// Higher levels find more clones (renamed, retyped, reordered copies) at the
+/// cost of more false positives.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum NormalizationLevel {
+    /// Token-for-token: identifiers, literals, types and comment text all count
+    Exact,
+    /// Ignore identifier names and comments; literals and types still count
+    #[default]
+    Structural,
+    /// Also ignore literal values, type annotations and the order of simple statements
+    Aggressive,
+}
+
+/// What [`compute_function_hash`] ignores when hashing a function.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NormalizeOptions {
+    /// Identifier leaves (`foo`, `field_identifier`, `type_identifier`) hash by kind only.
+    pub elide_identifiers: bool,
+    /// String, number, boolean and null leaves hash by kind only.
+    pub elide_literals: bool,
+    /// Drop type annotations: `type`/`return_type` fields with their `:`/`->`
+    /// separator and `type_annotation` nodes. `typed_*` wrappers (Python's
+    /// `typed_parameter`) hash as their remaining children.
+    pub elide_types: bool,
+    /// Drop comments (the grammar's extra nodes) and whitespace-only nodes.
+    /// Without this, comment text is hashed.
+    pub ignore_comments: bool,
+    /// Within a block, hash runs of simple statements (assignments, declarations,
+    /// calls) as an unordered set. Control flow, as declared by
+    /// `Language::control_flow_kinds`, is a barrier that statements never move across. Statements
+    /// that share an identifier keep their relative order.
+    pub ignore_statement_order: bool,
+}
+
+impl NormalizationLevel {
+    pub fn options(self) -> NormalizeOptions {
+        match self {
+            NormalizationLevel::Exact => NormalizeOptions {
+                elide_identifiers: false,
+                elide_literals: false,
+                elide_types: false,
+                ignore_comments: false,
+                ignore_statement_order: false,
+            },
+            NormalizationLevel::Structural => NormalizeOptions {
+                elide_identifiers: true,
+                elide_literals: false,
+                elide_types: false,
+                ignore_comments: true,
+                ignore_statement_order: false,
+            },
+            NormalizationLevel::Aggressive => NormalizeOptions {
+                elide_identifiers: true,
+                elide_literals: true,
+                elide_types: true,
+                ignore_comments: true,
+                ignore_statement_order: true,
+            },
+        }
+    }
+}
+
+/// Compute a normalized AST hash for duplicate function detection. `lang`
+/// declares the control flow that statements are never reordered across.
 fn compute_function_hash(
     node: &tree_sitter::Node,
     content: &[u8],
-    elide_identifiers: bool,
-    elide_literals: bool,
+    lang: &dyn Language,
+    options: &NormalizeOptions,
 ) -> u64 {
     use std::collections::hash_map::DefaultHasher;
     let mut hasher = DefaultHasher::new();
-    hash_node_recursive(
-        node,
-        content,
-        &mut hasher,
-        elide_identifiers,
-        elide_literals,
-    );
+    hash_node_recursive(node, content, lang, &mut hasher, options);
     hasher.finish()
 }
 
@@ -907,24 +1223,31 @@ fn compute_function_hash(
 fn hash_node_recursive(
     node: &tree_sitter::Node,
     content: &[u8],
+    lang: &dyn Language,
     hasher: &mut impl Hasher,
-    elide_identifiers: bool,
-    elide_literals: bool,
+    options: &NormalizeOptions,
 ) {
     let kind = node.kind();
+    let children = normalized_children(node, content, options);
+
+    // Type wrappers whose annotation was dropped hash as their remaining children,
+    // so `x: int` and `x` agree
+    let transparent = options.elide_types && kind.starts_with("typed_");
 
     // Hash the node kind (structure)
-    kind.hash(hasher);
+    if !transparent {
+        kind.hash(hasher);
+    }
 
     // For leaf nodes, decide whether to hash content
     if node.child_count() == 0 {
         let should_hash = if is_identifier_kind(kind) {
-            !elide_identifiers
+            !options.elide_identifiers
         } else if is_literal_kind(kind) {
-            !elide_literals
+            !options.elide_literals
         } else {
-            // Operators, keywords - their kind is sufficient
-            false
+            // Operators, keywords - their kind is sufficient; comments hash their text
+            node.is_extra()
         };
 
         if should_hash {
@@ -933,13 +1256,364 @@ fn hash_node_recursive(
         }
     }
 
-    // Recurse into children
+    if !(options.ignore_statement_order && is_block_kind(kind)) {
+        for child in &children {
+            hash_node_recursive(child, content, lang, hasher, options);
+        }
+        return;
+    }
+
+    // Hash each statement separately; canonicalize order within runs between barriers
+    let mut run: Vec<RunStatement> = Vec::new();
+    for child in &children {
+        if is_order_barrier(child, lang) {
+            hash_statement_run(std::mem::take(&mut run), hasher);
+            hash_node_recursive(child, content, lang, hasher, options);
+        } else {
+            use std::collections::hash_map::DefaultHasher;
+            let mut child_hasher = DefaultHasher::new();
+            hash_node_recursive(child, content, lang, &mut child_hasher, options);
+            let mut names = HashSet::new();
+            collect_identifiers(child, content, &mut names);
+            run.push(RunStatement {
+                hash: child_hasher.finish(),
+                names,
+            });
+        }
+    }
+    hash_statement_run(run, hasher);
+}
+
+/// A simple statement within a reorderable run.
+struct RunStatement<'a> {
+    hash: u64,
+    /// Identifier names the statement mentions, unnormalized.
+    names: HashSet<&'a [u8]>,
+}
+
+/// Hash a run of statements in a canonical order.
+///
+/// Statements sharing an identifier may depend on each other, so the earlier
+/// one always comes first. Among statements whose predecessors have all been
+/// emitted, the smallest hash goes next. Any reordering that only swaps
+/// independent statements yields the same sequence.
+fn hash_statement_run(run: Vec<RunStatement>, hasher: &mut impl Hasher) {
+    let mut emitted = vec![false; run.len()];
+    for _ in 0..run.len() {
+        let next = (0..run.len())
+            .filter(|&i| !emitted[i])
+            .filter(|&i| (0..i).all(|j| emitted[j] || run[j].names.is_disjoint(&run[i].names)))
+            .min_by_key(|&i| run[i].hash)
+            .expect("the first unemitted statement is always ready");
+        emitted[next] = true;
+        run[next].hash.hash(hasher);
+    }
+}
+
+/// Collect the text of every identifier leaf under `node`.
+fn collect_identifiers<'a>(
+    node: &tree_sitter::Node,
+    content: &'a [u8],
+    names: &mut HashSet<&'a [u8]>,
+) {
+    if node.child_count() == 0 {
+        if is_identifier_kind(node.kind()) {
+            names.insert(&content[node.start_byte()..node.end_byte()]);
+        }
+        return;
+    }
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
-        hash_node_recursive(&child, content, hasher, elide_identifiers, elide_literals);
+        collect_identifiers(&child, content, names);
     }
 }
 
+/// Children of `node` that survive normalization.
+fn normalized_children<'a>(
+    node: &tree_sitter::Node<'a>,
+    content: &[u8],
+    options: &NormalizeOptions,
+) -> Vec<tree_sitter::Node<'a>> {
+    let mut children: Vec<tree_sitter::Node<'a>> = Vec::new();
+    let mut cursor = node.walk();
+    if !cursor.goto_first_child() {
+        return children;
+    }
+    loop {
+        let child = cursor.node();
+        let is_type_field = matches!(cursor.field_name(), Some("type" | "return_type"));
+
+        if options.elide_types && (is_type_field || child.kind() == "type_annotation") {
+            // Drop the `:` / `->` separating the annotation from what it annotates
+            if is_type_field && children.last().is_some_and(|prev| !prev.is_named()) {
+                children.pop();
+            }
+        } else if options.ignore_comments
+            && (child.is_extra() || is_whitespace_node(&child, content))
+        {
+            // Trivia
+        } else {
+            children.push(child);
+        }
+
+        if !cursor.goto_next_sibling() {
+            break;
+        }
+    }
+    children
+}
+
+/// Check if a node spans only whitespace (e.g. explicit newline tokens).
+fn is_whitespace_node(node: &tree_sitter::Node, content: &[u8]) -> bool {
+    content[node.start_byte()..node.end_byte()]
+        .iter()
+        .all(|b| b.is_ascii_whitespace())
+}
+
+/// Check if a node kind is a statement list whose order may be normalized.
+fn is_block_kind(kind: &str) -> bool {
+    kind == "block" || kind == "statement_block" || kind == "compound_statement"
+}
+
+/// Statements that can't be reordered: the language's control flow, and
+/// punctuation (`{`, `}`). Expression statements are checked through their
+/// expression, since Rust wraps `if`/`for` in `expression_statement`.
+fn is_order_barrier(node: &tree_sitter::Node, lang: &dyn Language) -> bool {
+    let is_control = |kind: &str| lang.control_flow_kinds().contains(&kind);
+
+    !node.is_named()
+        || is_control(node.kind())
+        || node.named_child(0).is_some_and(|c| is_control(c.kind()))
+}
+
+/// A string literal occurrence
+#[derive(Debug)]
+struct StringLocation {
+    file: String,
+    line: usize,
+}
+
+/// Detect string literals repeated across the codebase.
+pub fn cmd_duplicate_strings(
+    scan_root: &Path,
+    root: &Path,
+    min_occurrences: usize,
+    min_length: usize,
+    json: bool,
+    filter: Option<&Filter>,
+) -> i32 {
+    let mut occurrences: HashMap<String, Vec<StringLocation>> = HashMap::new();
+    let mut files_scanned = 0;
+
+    let walker = ignore::WalkBuilder::new(scan_root)
+        .hidden(true)
+        .git_ignore(true)
+        .git_global(true)
+        .git_exclude(true)
+        .build();
+
+    for entry in walker.filter_map(|e| e.ok()).filter(|e| {
+        let path = e.path();
+        path.is_file() && super::is_source_file(path)
+    }) {
+        let path = entry.path();
+        let rel_path = path.strip_prefix(root).unwrap_or(path);
+
+        if let Some(f) = filter
+            && !f.matches(rel_path)
+        {
+            continue;
+        }
+
+        let content = match std::fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+
+        let support = match support_for_path(path) {
+            Some(s) => s,
+            None => continue,
+        };
+
+        let tree = match parsers::parse_cached(support.grammar_name(), &content) {
+            Some(t) => t,
+            None => continue,
+        };
+
+        files_scanned += 1;
+
+        let mut literals = Vec::new();
+        collect_string_literals(
+            &tree.root_node(),
+            content.as_bytes(),
+            support.import_kinds(),
+            &mut literals,
+        );
+
+        let file = rel_path.display().to_string();
+        for (value, line) in literals {
+            if is_trivial_string(&value, min_length) {
+                continue;
+            }
+            occurrences.entry(value).or_default().push(StringLocation {
+                file: file.clone(),
+                line,
+            });
+        }
+    }
+
+    let mut repeated: Vec<(String, Vec<StringLocation>)> = occurrences
+        .into_iter()
+        .filter(|(_, locs)| locs.len() >= min_occurrences)
+        .collect();
+
+    // Most repeated first, then longest (more likely to be meaningful)
+    repeated.sort_by(|a, b| {
+        b.1.len()
+            .cmp(&a.1.len())
+            .then_with(|| b.0.len().cmp(&a.0.len()))
+            .then_with(|| a.0.cmp(&b.0))
+    });
+
+    if json {
+        let output = serde_json::json!({
+            "files_scanned": files_scanned,
+            "min_occurrences": min_occurrences,
+            "min_length": min_length,
+            "repeated_literals": repeated.len(),
+            "literals": repeated.iter().map(|(value, locs)| {
+                serde_json::json!({
+                    "value": value,
+                    "count": locs.len(),
+                    "locations": locs.iter().map(|l| {
+                        serde_json::json!({
+                            "file": l.file,
+                            "line": l.line,
+                        })
+                    }).collect::<Vec<_>>(),
+                })
+            }).collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&output).unwrap());
+    } else {
+        println!("Duplicate String Literals");
+        println!();
+        println!("Files scanned: {}", files_scanned);
+        println!(
+            "Repeated literals: {} (>= {} occurrences, >= {} chars)",
+            repeated.len(),
+            min_occurrences,
+            min_length
+        );
+        println!();
+
+        if repeated.is_empty() {
+            println!("No repeated string literals detected.");
+        } else {
+            for (i, (value, locs)) in repeated.iter().take(20).enumerate() {
+                let files: HashSet<&str> = locs.iter().map(|l| l.file.as_str()).collect();
+                println!(
+                    "{}. {:?} ({} occurrences in {} files)",
+                    i + 1,
+                    value,
+                    locs.len(),
+                    files.len()
+                );
+                for loc in locs {
+                    println!("   {}:{}", loc.file, loc.line);
+                }
+                println!();
+            }
+
+            if repeated.len() > 20 {
+                println!("... and {} more literals", repeated.len() - 20);
+            }
+            println!("Consider extracting repeated literals into named constants.");
+        }
+    }
+
+    if repeated.is_empty() { 0 } else { 1 }
+}
+
+/// Collect string literal values with their 1-based line numbers.
+/// Import statements are skipped: module paths repeat by design.
+fn collect_string_literals(
+    node: &tree_sitter::Node,
+    content: &[u8],
+    import_kinds: &[&str],
+    literals: &mut Vec<(String, usize)>,
+) {
+    let kind = node.kind();
+    if import_kinds.contains(&kind) {
+        return;
+    }
+
+    // Outermost string node: its children are quotes and content fragments
+    if is_literal_kind(kind) && kind.contains("string") {
+        if let Ok(text) = std::str::from_utf8(&content[node.start_byte()..node.end_byte()]) {
+            literals.push((
+                string_literal_value(text).to_string(),
+                node.start_position().row + 1,
+            ));
+        }
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_string_literals(&child, content, import_kinds, literals);
+    }
+}
+
+/// Strip prefixes (`r`, `b`, `f`, `#`) and quotes from a string literal's source text.
+fn string_literal_value(text: &str) -> &str {
+    let text = text
+        .trim_start_matches(|c: char| c.is_ascii_alphabetic() || c == '#' || c == '@')
+        .trim_end_matches('#');
+    for quote in ["\"\"\"", "'''", "\"", "'", "`"] {
+        if let Some(inner) = text.strip_prefix(quote).and_then(|t| t.strip_suffix(quote)) {
+            return inner;
+        }
+    }
+    text
+}
+
+/// Literals too short, or made only of format placeholders, whitespace and
+/// punctuation (`""`, `", "`, `"{}: {}"`, `"%s"`), aren't worth a constant.
+fn is_trivial_string(value: &str, min_length: usize) -> bool {
+    if value.chars().count() < min_length {
+        return true;
+    }
+
+    let mut rest = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            // {}, {0}, {name}, {:?}, ${expr}
+            '{' => {
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        break;
+                    }
+                }
+                if rest.ends_with('$') {
+                    rest.pop();
+                }
+            }
+            // %s, %d, %5.2f
+            '%' => {
+                while chars
+                    .next_if(|c| c.is_ascii_digit() || *c == '.' || *c == '-')
+                    .is_some()
+                {}
+                chars.next_if(|c| c.is_ascii_alphabetic());
+            }
+            _ => rest.push(c),
+        }
+    }
+    !rest.chars().any(|c| c.is_alphanumeric())
+}
+
 /// Check if a node kind represents an identifier.
 fn is_identifier_kind(kind: &str) -> bool {
     kind == "identifier"
@@ -968,10 +1642,195 @@ mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    fn hash_python(code: &str, level: NormalizationLevel) -> u64 {
+        let tree = parsers::parse_with_grammar("python", code).expect("python grammar");
+        let func = tree.root_node().named_child(0).unwrap();
+        compute_function_hash(
+            &func,
+            code.as_bytes(),
+            &rhizome_moss_languages::Python,
+            &level.options(),
+        )
+    }
+
+    #[test]
+    fn test_normalization_levels() {
+        let original = r#"def total(items: list) -> int:
+    # sum prices of unique items
+    result = 0
+    seen = set()
+    for item in items:
+        result += item.price
+    return result
+"#;
+        // Renamed, untyped, different initial value, reordered setup
+        let clone = r#"def sum_prices(xs):
+    seen = set()
+    result = 1
+    for x in xs:
+        result += x.price
+    return result
+"#;
+        assert_ne!(
+            hash_python(original, NormalizationLevel::Exact),
+            hash_python(clone, NormalizationLevel::Exact)
+        );
+        assert_ne!(
+            hash_python(original, NormalizationLevel::Structural),
+            hash_python(clone, NormalizationLevel::Structural)
+        );
+        assert_eq!(
+            hash_python(original, NormalizationLevel::Aggressive),
+            hash_python(clone, NormalizationLevel::Aggressive)
+        );
+    }
+
+    #[test]
+    fn test_normalization_comments_and_barriers() {
+        let plain = "def f(a):\n    x = a\n    return x\n";
+        let commented = "def f(a):\n    # copy\n    x = a\n    return x\n";
+        assert_ne!(
+            hash_python(plain, NormalizationLevel::Exact),
+            hash_python(commented, NormalizationLevel::Exact)
+        );
+        assert_eq!(
+            hash_python(plain, NormalizationLevel::Structural),
+            hash_python(commented, NormalizationLevel::Structural)
+        );
+
+        // Statements never move across control flow
+        let before = "def f(a):\n    x = g(a)\n    if a:\n        return 1\n    return x\n";
+        let after = "def f(a):\n    if a:\n        return 1\n    x = g(a)\n    return x\n";
+        assert_ne!(
+            hash_python(before, NormalizationLevel::Aggressive),
+            hash_python(after, NormalizationLevel::Aggressive)
+        );
+        // Barriers are the language's control flow kinds, such as Python's `assert`
+        let checked = "def f(a):\n    assert a\n    y = h()\n    return y\n";
+        let unchecked = "def f(a):\n    y = h()\n    assert a\n    return y\n";
+        assert_ne!(
+            hash_python(checked, NormalizationLevel::Aggressive),
+            hash_python(unchecked, NormalizationLevel::Aggressive)
+        );
+
+        // Dependent statements keep their order; independent ones may swap
+        let ordered = "def f(a):\n    b = 1\n    c = g(a)\n    d = c\n    return d\n";
+        let swapped = "def f(a):\n    b = 1\n    d = c\n    c = g(a)\n    return d\n";
+        let moved = "def f(a):\n    c = g(a)\n    d = c\n    b = 1\n    return d\n";
+        assert_ne!(
+            hash_python(ordered, NormalizationLevel::Aggressive),
+            hash_python(swapped, NormalizationLevel::Aggressive)
+        );
+        assert_eq!(
+            hash_python(ordered, NormalizationLevel::Aggressive),
+            hash_python(moved, NormalizationLevel::Aggressive)
+        );
+    }
+
+    #[test]
+    fn test_string_literal_value() {
+        assert_eq!(string_literal_value("\"hello\""), "hello");
+        assert_eq!(string_literal_value("r#\"raw\"#"), "raw");
+        assert_eq!(string_literal_value("f'name {x}'"), "name {x}");
+        assert_eq!(string_literal_value("\"\"\"doc\"\"\""), "doc");
+        assert_eq!(string_literal_value("`tmpl`"), "tmpl");
+    }
+
+    #[test]
+    fn test_is_trivial_string() {
+        assert!(is_trivial_string("", 1));
+        assert!(is_trivial_string("short", 8));
+        assert!(is_trivial_string("{}: {} -> {:?}", 4));
+        assert!(is_trivial_string("%s, %5.2f, %d", 4));
+        assert!(is_trivial_string("${name} / ${other}", 4));
+        assert!(!is_trivial_string("application/json", 8));
+        assert!(!is_trivial_string("Failed to read {}", 8));
+    }
+
     #[test]
     fn test_load_duplicate_functions_allowlist_empty() {
         let tmp = tempdir().unwrap();
         let allowlist = load_duplicate_functions_allowlist(tmp.path());
         assert!(allowlist.is_empty());
     }
+
+    /// Whether the function on `start..=end` of a source file is generated
+    /// (`None` without the grammar).
+    fn generated_function(file: &str, source: &str, start: usize, end: usize) -> Option<bool> {
+        let regions = SyntaxRegions::from_tree(&parse(file, source)?);
+        let offsets = line_offsets(source);
+        Some(is_generated_function(
+            source, &regions, &offsets, start, end,
+        ))
+    }
+
+    fn parse(file: &str, source: &str) -> Option<tree_sitter::Tree> {
+        let support = support_for_path(Path::new(file))?;
+        parsers::parse_with_grammar(support.grammar_name(), source)
+    }
+
+    #[test]
+    fn test_generated_file() {
+        if parse("a.rs", "").is_none() {
+            return;
+        }
+        let generated = |source: &str| {
+            let regions = SyntaxRegions::from_tree(&parse("a.rs", source).unwrap());
+            is_generated_file(source, &regions)
+        };
+        assert!(generated(
+            "// Code generated by protoc-gen-go. DO NOT EDIT.\n\nmod pb {}\n"
+        ));
+        assert!(generated(
+            "//! Parser module.\n\n/* @generated */\nfn f() {}\n"
+        ));
+        assert!(!generated(
+            "//! Parser module.\n\nfn f() {}\n// generated by hand, not really\n"
+        ));
+    }
+
+    #[test]
+    fn test_generated_function() {
+        let source = "fn a() {}\n\n/// @generated\n#[inline]\nfn b() {}\nfn c() {\n    // auto-generated from schema.json\n}\nfn d() {}\n";
+        let Some(a) = generated_function("a.rs", source, 1, 1) else {
+            return;
+        };
+        assert!(!a);
+        // The attribute between the marker and the function is skipped
+        assert_eq!(generated_function("a.rs", source, 5, 5), Some(true));
+        assert_eq!(generated_function("a.rs", source, 6, 8), Some(true));
+        assert_eq!(generated_function("a.rs", source, 9, 9), Some(false));
+
+        // An attribute is not a comment: the marker must still be attached
+        let source = "// @generated\nfn a() {}\n#[inline]\nfn b() {}\n";
+        assert_eq!(generated_function("a.rs", source, 4, 4), Some(false));
+
+        // String literals mentioning a marker are not comments
+        let source = "def f():\n    return \"generated by moss\"\n";
+        if parse("a.py", source).is_some() {
+            assert_eq!(generated_function("a.py", source, 1, 2), Some(false));
+            let source = "# generated by moss\ndef f():\n    pass\n";
+            assert_eq!(generated_function("a.py", source, 2, 3), Some(true));
+        }
+    }
+
+    #[test]
+    fn test_load_duplicate_functions_ignore() {
+        let tmp = tempdir().unwrap();
+        std::fs::create_dir(tmp.path().join(".moss")).unwrap();
+        std::fs::write(
+            tmp.path().join(".moss/duplicate-functions-ignore"),
+            "# protobuf output\nsrc/pb/**\n*.generated.ts\n",
+        )
+        .unwrap();
+        let ignore = load_duplicate_functions_ignore(tmp.path());
+        assert_eq!(ignore.len(), 2);
+        assert!(ignore[0].matches_path(Path::new("src/pb/api.rs")));
+        assert!(ignore[1].matches_path(Path::new("web/client.generated.ts")));
+        assert!(
+            !ignore
+                .iter()
+                .any(|p| p.matches_path(Path::new("src/lib.rs")))
+        );
+    }
 }
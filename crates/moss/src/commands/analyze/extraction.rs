@@ -0,0 +1,388 @@
+//! Refactoring suggestions for duplicate function groups
+//! (`analyze duplicate-functions --suggest`).
+//!
+//! One instance of each group is proposed as the implementation to keep: the
+//! one whose module the most files import (from the index), then the one
+//! closest to the root. The other instances get a stub delegating to it, shown
+//! as a diff. Suggestions are advisory; nothing is written.
+
+use super::duplicates::DuplicateFunctionLocation;
+use crate::index::FileIndex;
+use rhizome_moss_languages::support_for_path;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Removed lines shown per stub before the rest are elided.
+const MAX_REMOVED_LINES: usize = 8;
+
+/// Suggested refactoring of one duplicate group.
+pub(super) struct ExtractionSuggestion {
+    /// Index of the instance to keep
+    pub keep: usize,
+    /// Files importing each instance's module, if the index is available
+    pub importers: Option<Vec<usize>>,
+    /// Common directory of instances in different directories, where a shared
+    /// module could live
+    pub shared_dir: Option<String>,
+    /// Delegating stubs for the other instances
+    pub stubs: Vec<DelegateStub>,
+}
+
+/// Replacement of a duplicate instance by a call to the kept one.
+pub(super) struct DelegateStub {
+    /// Index of the replaced instance
+    pub location: usize,
+    /// Diff lines, each starting with `-`, `+` or ` `
+    pub diff: Vec<String>,
+}
+
+/// Files importing each file's module, or None without an index.
+pub(super) fn importer_counts<'a>(
+    root: &Path,
+    files: impl IntoIterator<Item = &'a str>,
+) -> Option<HashMap<String, usize>> {
+    let rt = tokio::runtime::Runtime::new().ok()?;
+    let index = rt.block_on(FileIndex::open_if_enabled(root))?;
+    let mut counts = HashMap::new();
+    for file in files {
+        if !counts.contains_key(file) {
+            let count = rt.block_on(index.count_importers(file)).unwrap_or(0);
+            counts.insert(file.to_string(), count);
+        }
+    }
+    Some(counts)
+}
+
+/// Suggest how to deduplicate a group. `importers` comes from [`importer_counts`].
+pub(super) fn suggest(
+    root: &Path,
+    locations: &[DuplicateFunctionLocation],
+    importers: Option<&HashMap<String, usize>>,
+) -> ExtractionSuggestion {
+    let importers: Option<Vec<usize>> = importers.map(|counts| {
+        locations
+            .iter()
+            .map(|l| counts.get(&l.file).copied().unwrap_or(0))
+            .collect()
+    });
+    let keep = choose_keep(locations, importers.as_deref());
+    let kept = &locations[keep];
+
+    let mut stubs = Vec::new();
+    for (i, loc) in locations.iter().enumerate() {
+        if i == keep {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(root.join(&loc.file)) else {
+            continue;
+        };
+        let lines: Vec<&str> = content.lines().collect();
+        let call_target = qualified_name(kept, &loc.file);
+        stubs.push(DelegateStub {
+            location: i,
+            diff: delegate_diff(&lines, loc, &call_target),
+        });
+    }
+
+    ExtractionSuggestion {
+        keep,
+        importers,
+        shared_dir: shared_dir(locations.iter().map(|l| l.file.as_str())),
+        stubs,
+    }
+}
+
+/// The most imported instance, then the shallowest path, then the first by
+/// file and line.
+fn choose_keep(locations: &[DuplicateFunctionLocation], importers: Option<&[usize]>) -> usize {
+    (0..locations.len())
+        .min_by_key(|&i| {
+            let loc = &locations[i];
+            (
+                std::cmp::Reverse(importers.map_or(0, |counts| counts[i])),
+                Path::new(&loc.file).components().count(),
+                loc.file.as_str(),
+                loc.start_line,
+            )
+        })
+        .unwrap_or(0)
+}
+
+/// Deepest directory containing all `files`, when they are not all in the same
+/// directory ("." for the root).
+fn shared_dir<'a>(files: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    let mut dirs = files
+        .into_iter()
+        .map(|f| Path::new(f).parent().unwrap_or(Path::new("")));
+    let first = dirs.next()?;
+    let mut common = first.to_path_buf();
+    let mut differ = false;
+    for dir in dirs {
+        if dir == first {
+            continue;
+        }
+        differ = true;
+        while !dir.starts_with(&common) {
+            if !common.pop() {
+                break;
+            }
+        }
+    }
+    if !differ {
+        return None;
+    }
+    let common = common.display().to_string();
+    Some(if common.is_empty() {
+        ".".to_string()
+    } else {
+        common
+    })
+}
+
+/// How a caller in `from_file` refers to the kept function: module-qualified
+/// in Python, and in Rust within a root `src/` crate; otherwise by name.
+fn qualified_name(kept: &DuplicateFunctionLocation, from_file: &str) -> String {
+    if kept.file == from_file {
+        return kept.symbol.clone();
+    }
+    let path = Path::new(&kept.file);
+    let module = support_for_path(path).and_then(|lang| lang.file_path_to_module_name(path));
+    match (extension(&kept.file), module) {
+        ("rs", Some(module)) if kept.file.starts_with("src/") => match module.as_str() {
+            "lib" | "main" => format!("crate::{}", kept.symbol),
+            _ => format!("crate::{}::{}", module, kept.symbol),
+        },
+        ("py", Some(module)) if !module.is_empty() => format!("{}.{}", module, kept.symbol),
+        _ => kept.symbol.clone(),
+    }
+}
+
+fn extension(file: &str) -> &str {
+    Path::new(file)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+}
+
+/// Diff replacing the body of the function at `loc` with a call to `target`,
+/// forwarding its parameters.
+fn delegate_diff(lines: &[&str], loc: &DuplicateFunctionLocation, target: &str) -> Vec<String> {
+    let start = loc.start_line.saturating_sub(1).min(lines.len());
+    let end = loc.end_line.clamp(start, lines.len());
+    let function = &lines[start..end];
+    let Some(first) = function.first() else {
+        return Vec::new();
+    };
+    let ext = extension(&loc.file);
+
+    // Signature: up to the line opening the body (`{`, or `:` in Python)
+    let sig_end = function
+        .iter()
+        .position(|l| l.contains('{') || (ext == "py" && l.trim_end().ends_with(':')))
+        .unwrap_or(0);
+    let mut signature: Vec<String> = function[..=sig_end].iter().map(|l| l.to_string()).collect();
+    if let Some(last) = signature.last_mut()
+        && let Some(brace) = last.find('{')
+    {
+        last.truncate(brace + 1);
+    }
+
+    let indent: String = first.chars().take_while(|c| c.is_whitespace()).collect();
+    let call = format!(
+        "{}({})",
+        target,
+        parameter_names(&signature.join(" "), ext).join(", ")
+    );
+    let body = match ext {
+        "py" => format!("return {}", call),
+        "rs" | "rb" | "ex" | "exs" | "lua" | "scala" | "kt" => call,
+        _ => format!("return {};", call),
+    };
+
+    let mut diff: Vec<String> = function
+        .iter()
+        .take(MAX_REMOVED_LINES)
+        .map(|l| format!("-{}", l))
+        .collect();
+    if function.len() > MAX_REMOVED_LINES {
+        diff.push(format!(
+            "-{}    ... ({} more lines)",
+            indent,
+            function.len() - MAX_REMOVED_LINES
+        ));
+    }
+    diff.extend(signature.iter().map(|l| format!("+{}", l)));
+    diff.push(format!("+{}    {}", indent, body));
+    if ext != "py"
+        && function.len() > sig_end + 1
+        && let Some(last) = function.last()
+    {
+        diff.push(format!("+{}", last));
+    }
+    diff
+}
+
+/// Names of the parameters in a function signature, without receivers (`self`,
+/// `this`, `cls`).
+fn parameter_names(signature: &str, ext: &str) -> Vec<String> {
+    let Some(open) = signature.find('(') else {
+        return Vec::new();
+    };
+    // Split the parameter list at top-level commas
+    let mut params = Vec::new();
+    let mut depth = 0usize;
+    let mut current = String::new();
+    for c in signature[open + 1..].chars() {
+        match c {
+            '(' | '[' | '{' | '<' => depth += 1,
+            ')' if depth == 0 => break,
+            ')' | ']' | '}' | '>' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                params.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    params.push(current);
+
+    params
+        .iter()
+        .filter_map(|param| {
+            let param = param.trim();
+            let param = param.strip_prefix("mut ").unwrap_or(param);
+            let declared = match param.find(':') {
+                Some(colon) => &param[..colon],
+                None => param.split('=').next().unwrap_or(param),
+            };
+            let declared = declared.trim();
+            // Go declares `name type`; elsewhere the name comes last (`int x`)
+            let name = if ext == "go" {
+                declared.split_whitespace().next()
+            } else {
+                declared.split_whitespace().last()
+            }?;
+            let name = name.trim_end_matches('?').trim_start_matches('&');
+            let name = if ext == "py" {
+                name
+            } else {
+                name.trim_start_matches('*')
+            };
+            let receiver = matches!(name, "self" | "this" | "cls");
+            (!name.is_empty() && !receiver).then(|| name.to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(
+        file: &str,
+        symbol: &str,
+        start_line: usize,
+        end_line: usize,
+    ) -> DuplicateFunctionLocation {
+        DuplicateFunctionLocation {
+            file: file.to_string(),
+            symbol: symbol.to_string(),
+            start_line,
+            end_line,
+        }
+    }
+
+    #[test]
+    fn test_choose_keep() {
+        let locations = vec![
+            location("src/a/deep/x.rs", "f", 1, 3),
+            location("src/b/y.rs", "f", 1, 3),
+            location("src/z.rs", "g", 1, 3),
+        ];
+        // Without import data the shallowest path wins
+        assert_eq!(choose_keep(&locations, None), 2);
+        // The most imported module wins over depth
+        assert_eq!(choose_keep(&locations, Some(&[1, 4, 2])), 1);
+    }
+
+    #[test]
+    fn test_shared_dir() {
+        assert_eq!(shared_dir(["src/a.rs", "src/b.rs"]), None);
+        assert_eq!(
+            shared_dir([
+                "src/api/users.rs",
+                "src/api/v2/users.rs",
+                "src/api/posts.rs"
+            ]),
+            Some("src/api".to_string())
+        );
+        assert_eq!(
+            shared_dir(["lib/a.py", "tools/b.py"]),
+            Some(".".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parameter_names() {
+        assert_eq!(
+            parameter_names(
+                "pub fn parse(&self, input: &str, mut n: usize) -> Vec<(u8, u8)> {",
+                "rs"
+            ),
+            vec!["input", "n"]
+        );
+        assert_eq!(
+            parameter_names("def load(self, path, *args, retries=3, **kwargs):", "py"),
+            vec!["path", "*args", "retries", "**kwargs"]
+        );
+        assert_eq!(
+            parameter_names("function f(a: Map<string, number>, b?: string) {", "ts"),
+            vec!["a", "b"]
+        );
+        assert_eq!(
+            parameter_names("int sum(int *xs, size_t n) {", "c"),
+            vec!["xs", "n"]
+        );
+        assert_eq!(
+            parameter_names("func Sum(xs []int, n int) int {", "go"),
+            vec!["xs", "n"]
+        );
+        assert!(parameter_names("fn now() -> u64 {", "rs").is_empty());
+    }
+
+    #[test]
+    fn test_delegate_diff() {
+        let source = "impl X {\n    fn total(&self, items: &[Item]) -> u64 {\n        items.iter().map(|i| i.price).sum()\n    }\n}\n";
+        let lines: Vec<&str> = source.lines().collect();
+        let diff = delegate_diff(
+            &lines,
+            &location("src/x.rs", "total", 2, 4),
+            "crate::util::total",
+        );
+        assert_eq!(
+            diff,
+            vec![
+                "-    fn total(&self, items: &[Item]) -> u64 {",
+                "-        items.iter().map(|i| i.price).sum()",
+                "-    }",
+                "+    fn total(&self, items: &[Item]) -> u64 {",
+                "+        crate::util::total(items)",
+                "+    }",
+            ]
+        );
+
+        let source = "def total(items):\n    return sum(i.price for i in items)\n";
+        let lines: Vec<&str> = source.lines().collect();
+        let diff = delegate_diff(&lines, &location("app/x.py", "total", 1, 2), "util.total");
+        assert_eq!(
+            diff,
+            vec![
+                "-def total(items):",
+                "-    return sum(i.price for i in items)",
+                "+def total(items):",
+                "+    return util.total(items)",
+            ]
+        );
+    }
+}
@@ -2,6 +2,7 @@
 
 use super::is_source_file;
 use crate::index;
+use crate::output::{CsvWriter, OutputFormat, Table, terminal_width};
 use glob::Pattern;
 use std::path::Path;
 
@@ -16,7 +17,7 @@ struct FileHotspot {
 }
 
 /// Analyze git history hotspots
-pub fn cmd_hotspots(root: &Path, exclude_patterns: &[String], json: bool) -> i32 {
+pub fn cmd_hotspots(root: &Path, exclude_patterns: &[String], format: &OutputFormat) -> i32 {
     // Compile exclusion patterns
     let excludes: Vec<Pattern> = exclude_patterns
         .iter()
@@ -99,7 +100,7 @@ pub fn cmd_hotspots(root: &Path, exclude_patterns: &[String], json: bool) -> i32
             hotspots.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
             hotspots.truncate(20);
 
-            return print_hotspots(&hotspots, json);
+            return print_hotspots(&hotspots, format);
         }
     };
 
@@ -133,11 +134,36 @@ pub fn cmd_hotspots(root: &Path, exclude_patterns: &[String], json: bool) -> i32
     hotspots.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
     hotspots.truncate(20);
 
-    print_hotspots(&hotspots, json)
+    print_hotspots(&hotspots, format)
 }
 
 /// Print hotspots report
-fn print_hotspots(hotspots: &[FileHotspot], json: bool) -> i32 {
+fn print_hotspots(hotspots: &[FileHotspot], format: &OutputFormat) -> i32 {
+    let json = format.is_json();
+    if format.is_csv() {
+        let mut csv = CsvWriter::new(&[
+            "path",
+            "commits",
+            "lines_added",
+            "lines_deleted",
+            "churn",
+            "score",
+        ]);
+        for h in hotspots {
+            let score = format!("{:.1}", h.score);
+            csv.row(&[
+                &h.path,
+                &h.commits,
+                &h.lines_added,
+                &h.lines_deleted,
+                &(h.lines_added + h.lines_deleted),
+                &score,
+            ]);
+        }
+        print!("{}", csv.finish());
+        return 0;
+    }
+
     if hotspots.is_empty() {
         if json {
             println!("[]");
@@ -165,24 +191,21 @@ fn print_hotspots(hotspots: &[FileHotspot], json: bool) -> i32 {
     } else {
         println!("Git Hotspots (high churn)");
         println!();
-        println!(
-            "{:<50} {:>8} {:>8} {:>8}",
-            "File", "Commits", "Churn", "Score"
-        );
-        println!("{}", "-".repeat(80));
-
+        let mut table = Table::new(&["File", "Commits", "Churn", "Score"])
+            .align_right(1)
+            .align_right(2)
+            .align_right(3)
+            .shrink_column(0);
         for h in hotspots {
             let churn = h.lines_added + h.lines_deleted;
-            let display_path = if h.path.len() > 48 {
-                format!("...{}", &h.path[h.path.len() - 45..])
-            } else {
-                h.path.clone()
-            };
-            println!(
-                "{:<50} {:>8} {:>8} {:>8.0}",
-                display_path, h.commits, churn, h.score
-            );
+            table.row(vec![
+                h.path.clone(),
+                h.commits.to_string(),
+                churn.to_string(),
+                format!("{:.0}", h.score),
+            ]);
         }
+        print!("{}", table.render(terminal_width()));
 
         println!();
         println!("Score = commits × √churn");
@@ -1,7 +1,7 @@
 //! Function length analysis - find long functions in codebase
 
 use crate::analyze::function_length::{FunctionLength, LengthAnalyzer, LengthReport};
-use crate::filter::Filter;
+use crate::filter::{Filter, TestLines, TestScope};
 use crate::path_resolve;
 use rayon::prelude::*;
 use std::path::Path;
@@ -39,6 +39,7 @@ pub fn analyze_codebase_length(
         })
         .collect();
 
+    let tests = filter.map_or(TestScope::All, |f| f.tests());
     let all_functions: Vec<FunctionLength> = code_files
         .par_iter()
         .filter_map(|file| {
@@ -46,10 +47,12 @@ pub fn analyze_codebase_length(
             let content = std::fs::read_to_string(&path).ok()?;
             let analyzer = LengthAnalyzer::new();
             let report = analyzer.analyze(&path, &content);
+            let tests = TestLines::new(tests, &path, &content);
             Some(
                 report
                     .functions
                     .into_iter()
+                    .filter(|f| tests.keeps(f.start_line))
                     .map(|mut f| {
                         f.file_path = Some(file.path.clone());
                         f
@@ -1,34 +1,45 @@
 //! Analyze command - run analysis on target.
 
+mod allowlist;
+pub mod api_surface;
 mod args;
 pub mod ast;
 pub mod call_graph;
 pub mod check_examples;
 pub mod check_refs;
 pub mod complexity;
+pub mod coupling;
 pub mod docs;
 pub mod duplicates;
+mod extraction;
 pub mod files;
 pub mod hotspots;
 pub mod length;
+pub mod ownership;
 pub mod query;
 pub mod report;
+pub mod routes;
 pub mod rules_cmd;
 mod sarif;
 pub mod security;
 pub mod stale_docs;
 pub mod trace;
+pub mod trend;
 
 use crate::analyze::complexity::{ComplexityReport, RiskLevel};
 use crate::analyze::function_length::LengthReport;
+use crate::codeowners::CodeOwners;
 use crate::commands::aliases::detect_project_languages;
 use crate::config::MossConfig;
 use crate::daemon;
-use crate::filter::Filter;
+use crate::filter::{Filter, TestScope, parse_kinds};
+use crate::output::{CsvWriter, JsonLinesWriter, OutputFormat};
 pub use args::{AnalyzeArgs, AnalyzeCommand};
+pub use check_refs::CheckRefsConfig;
 use rhizome_moss_derive::Merge;
 pub use rhizome_moss_rules::{RuleOverride, RulesConfig};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
@@ -53,8 +64,10 @@ pub struct AnalyzeConfig {
     /// Exclude interface implementations from doc coverage (default: true)
     /// This excludes trait impl methods in Rust, @Override methods in Java, etc.
     pub exclude_interface_impls: Option<bool>,
-    /// Patterns to exclude from hotspots analysis (e.g., generated code, lock files)
+    /// Patterns to exclude from hotspots analysis (e.g., generated code, lock files).
+    /// Project patterns are added to global ones.
     #[serde(default)]
+    #[merge(strategy = "append")]
     pub hotspots_exclude: Vec<String>,
     /// Syntax rules configuration
     #[serde(default)]
@@ -62,6 +75,12 @@ pub struct AnalyzeConfig {
     /// Default lines of context to show in query preview
     #[serde(rename = "query-context-lines")]
     pub query_context_lines: Option<usize>,
+    /// Documentation reference check configuration
+    #[serde(rename = "check-refs")]
+    pub check_refs: CheckRefsConfig,
+    /// Route query configuration (enable/disable, allow patterns per query ID)
+    #[serde(default)]
+    pub routes: RulesConfig,
 }
 
 /// Weights for each analysis pass (higher = more impact on grade).
@@ -123,29 +142,6 @@ impl AnalyzeConfig {
     }
 }
 
-/// Load patterns from a .moss allow file (e.g., hotspots-allow, large-files-allow)
-fn load_allow_file(root: &Path, filename: &str) -> Vec<String> {
-    let path = root.join(".moss").join(filename);
-    let content = match std::fs::read_to_string(&path) {
-        Ok(c) => c,
-        Err(_) => return Vec::new(),
-    };
-
-    content
-        .lines()
-        .filter_map(|line| {
-            // Strip trailing comments
-            let without_comment = line.split('#').next().unwrap_or(line);
-            let trimmed = without_comment.trim();
-            if trimmed.is_empty() {
-                None
-            } else {
-                Some(trimmed.to_string())
-            }
-        })
-        .collect()
-}
-
 /// Append a pattern to a .moss allow file
 fn append_to_allow_file(root: &Path, filename: &str, pattern: &str, reason: Option<&str>) -> i32 {
     // Validate filename to prevent path traversal
@@ -204,7 +200,12 @@ fn append_to_allow_file(root: &Path, filename: &str, pattern: &str, reason: Opti
 }
 
 /// Run analyze command with args.
-pub fn run(args: AnalyzeArgs, format: crate::output::OutputFormat) -> i32 {
+pub fn run(args: AnalyzeArgs, format: OutputFormat) -> i32 {
+    let format = if args.json_lines {
+        OutputFormat::JsonLines
+    } else {
+        format.with_format_arg(args.format)
+    };
     let effective_root = args
         .root
         .clone()
@@ -262,10 +263,12 @@ pub fn run(args: AnalyzeArgs, format: crate::output::OutputFormat) -> i32 {
         only_patterns.push(format!("/{}", file));
     }
 
-    // Build filter for --exclude and --only (returns None on error after printing message)
-    let filter = if !args.exclude.is_empty() || !only_patterns.is_empty() {
+    // Build filter for --exclude, --only and test scope (returns None on error after printing message)
+    let tests = TestScope::from_flags(args.exclude_tests, args.only_tests);
+    let filter = if !args.exclude.is_empty() || !only_patterns.is_empty() || tests != TestScope::All
+    {
         match build_filter(&effective_root, &args.exclude, &only_patterns) {
-            Some(f) => Some(f),
+            Some(f) => Some(f.with_tests(tests)),
             None => return 1, // Error already printed
         }
     } else {
@@ -283,7 +286,7 @@ pub fn run(args: AnalyzeArgs, format: crate::output::OutputFormat) -> i32 {
                 false, // length
                 false, // security
                 None,
-                None,
+                &HashSet::new(),
                 filter.as_ref(),
             );
             print_report(&report, json, pretty)
@@ -297,6 +300,8 @@ pub fn run(args: AnalyzeArgs, format: crate::output::OutputFormat) -> i32 {
             sarif,
             allow,
             reason,
+            trend,
+            points,
         }) => {
             // Handle --allow: append to allowlist and exit
             if let Some(pattern) = &allow {
@@ -308,24 +313,69 @@ pub fn run(args: AnalyzeArgs, format: crate::output::OutputFormat) -> i32 {
                 );
             }
 
-            // Load allowlist for filtering
-            let allowlist = load_allow_file(&effective_root, "complexity-allow");
+            let kinds = match parse_kinds(&kind) {
+                Ok(kinds) => kinds,
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    return 1;
+                }
+            };
 
-            // Use 0 to mean "no limit"
-            let effective_limit = if limit == 0 { usize::MAX } else { limit };
+            // Load allowlist for filtering
+            let allowlist = allowlist::load(&effective_root, "complexity-allow");
+
+            // Use 0 to mean "no limit"; streamed functions are unsorted, so
+            // a default limit would keep arbitrary ones
+            let effective_limit = match limit {
+                Some(0) => usize::MAX,
+                Some(limit) => limit,
+                None if format.is_json_lines() => usize::MAX,
+                None => 10,
+            };
             let effective_threshold = threshold.or(config.analyze.threshold());
 
-            if sarif {
+            if let Some(functions) = trend {
+                let analysis_root = target
+                    .as_ref()
+                    .map(|t| effective_root.join(t))
+                    .unwrap_or_else(|| effective_root.clone());
+                trend::cmd_complexity_trend(
+                    &analysis_root,
+                    functions,
+                    points,
+                    effective_threshold,
+                    &kinds,
+                    filter.as_ref(),
+                    &allowlist,
+                    json,
+                )
+            } else if sarif {
                 // Run complexity analysis and output in SARIF format
                 let report = complexity::analyze_codebase_complexity(
                     &effective_root,
                     effective_limit,
                     effective_threshold,
+                    &kinds,
                     filter.as_ref(),
                     &allowlist,
                 );
                 sarif::print_complexity_sarif(&report.functions, &effective_root);
                 0
+            } else if format.is_json_lines() {
+                let analysis_root = target
+                    .as_ref()
+                    .map(|t| effective_root.join(t))
+                    .unwrap_or_else(|| effective_root.clone());
+                let owners = CodeOwners::load(&effective_root);
+                stream_complexity_report(
+                    &analysis_root,
+                    effective_limit,
+                    effective_threshold,
+                    &kinds,
+                    filter.as_ref(),
+                    &allowlist,
+                    owners.as_ref(),
+                )
             } else {
                 // For custom limit, call complexity directly to avoid hardcoded limit in report
                 let analysis_root = target
@@ -337,15 +387,25 @@ pub fn run(args: AnalyzeArgs, format: crate::output::OutputFormat) -> i32 {
                     &analysis_root,
                     effective_limit,
                     effective_threshold,
+                    &kinds,
                     filter.as_ref(),
                     &allowlist,
                 );
 
-                // Note: kind filter not applicable to complexity (no kind field)
-                let _ = kind;
-
+                let owners = CodeOwners::load(&effective_root);
                 if json {
-                    println!("{}", serde_json::to_string(&report).unwrap_or_default());
+                    let mut value = serde_json::to_value(&report).unwrap_or_default();
+                    if let Some(owners) = &owners {
+                        ownership::set_owners(
+                            value.get_mut("functions"),
+                            "file_path",
+                            &analysis_root,
+                            owners,
+                        );
+                    }
+                    println!("{}", value);
+                } else if format.is_csv() {
+                    print_complexity_report_csv(&report);
                 } else if pretty {
                     print_complexity_report_pretty(&report);
                 } else {
@@ -372,7 +432,7 @@ pub fn run(args: AnalyzeArgs, format: crate::output::OutputFormat) -> i32 {
             }
 
             // Load allowlist for filtering
-            let allowlist = load_allow_file(&effective_root, "length-allow");
+            let allowlist = allowlist::load(&effective_root, "length-allow");
 
             if sarif {
                 // Run length analysis and output in SARIF format
@@ -409,7 +469,27 @@ pub fn run(args: AnalyzeArgs, format: crate::output::OutputFormat) -> i32 {
             }
         }
 
-        Some(AnalyzeCommand::Security { target }) => {
+        Some(AnalyzeCommand::Security {
+            target,
+            allow,
+            reason,
+        }) => {
+            if let Some(fingerprint) = allow {
+                return security::cmd_allow_finding(
+                    &effective_root,
+                    &fingerprint,
+                    reason.as_deref(),
+                );
+            }
+
+            let owners = CodeOwners::load(&effective_root);
+            if format.is_json_lines() {
+                let analysis_root = target
+                    .as_ref()
+                    .map(|t| effective_root.join(t))
+                    .unwrap_or_else(|| effective_root.clone());
+                return stream_security_report(&analysis_root, &effective_root, owners.as_ref());
+            }
             let report = report::analyze(
                 target.as_deref(),
                 &effective_root,
@@ -418,9 +498,22 @@ pub fn run(args: AnalyzeArgs, format: crate::output::OutputFormat) -> i32 {
                 false, // length
                 true,  // security
                 None,
-                None,
+                &HashSet::new(),
                 filter.as_ref(),
             );
+            if json && let Some(owners) = &owners {
+                let mut value = report.to_json();
+                ownership::set_owners(
+                    value
+                        .get_mut("security")
+                        .and_then(|s| s.get_mut("findings")),
+                    "file",
+                    &effective_root,
+                    owners,
+                );
+                println!("{}", value);
+                return 0;
+            }
             print_report(&report, json, pretty)
         }
 
@@ -428,6 +521,31 @@ pub fn run(args: AnalyzeArgs, format: crate::output::OutputFormat) -> i32 {
             docs::cmd_docs(&effective_root, limit, json, filter.as_ref())
         }
 
+        Some(AnalyzeCommand::ApiSurface { baseline }) => api_surface::cmd_api_surface(
+            &effective_root,
+            baseline.as_deref(),
+            json,
+            filter.as_ref(),
+        ),
+
+        Some(AnalyzeCommand::Ownership) => ownership::cmd_ownership(
+            &effective_root,
+            &config.analyze.weights(),
+            filter.as_ref(),
+            json,
+        ),
+
+        Some(AnalyzeCommand::Coupling { limit, dot }) => {
+            coupling::cmd_coupling(&effective_root, limit, dot, json, filter.as_ref())
+        }
+
+        Some(AnalyzeCommand::Routes) => routes::cmd_routes(
+            &effective_root,
+            json,
+            filter.as_ref(),
+            &config.analyze.routes,
+        ),
+
         Some(AnalyzeCommand::Files {
             limit,
             allow,
@@ -441,7 +559,7 @@ pub fn run(args: AnalyzeArgs, format: crate::output::OutputFormat) -> i32 {
                     reason.as_deref(),
                 )
             } else {
-                let excludes = load_allow_file(&effective_root, "large-files-allow");
+                let excludes = allowlist::load(&effective_root, "large-files-allow");
                 files::cmd_files(&effective_root, limit, &excludes, json)
             }
         }
@@ -497,12 +615,14 @@ pub fn run(args: AnalyzeArgs, format: crate::output::OutputFormat) -> i32 {
                 )
             } else {
                 let mut excludes = config.analyze.hotspots_exclude.clone();
-                excludes.extend(load_allow_file(&effective_root, "hotspots-allow"));
-                hotspots::cmd_hotspots(&effective_root, &excludes, json)
+                excludes.extend(allowlist::load(&effective_root, "hotspots-allow"));
+                hotspots::cmd_hotspots(&effective_root, &excludes, &format)
             }
         }
 
-        Some(AnalyzeCommand::CheckRefs) => check_refs::cmd_check_refs(&effective_root, json),
+        Some(AnalyzeCommand::CheckRefs) => {
+            check_refs::cmd_check_refs(&effective_root, json, &config.analyze.check_refs)
+        }
 
         Some(AnalyzeCommand::StaleDocs) => stale_docs::cmd_stale_docs(&effective_root, json),
 
@@ -511,30 +631,39 @@ pub fn run(args: AnalyzeArgs, format: crate::output::OutputFormat) -> i32 {
         }
 
         Some(AnalyzeCommand::DuplicateFunctions {
+            normalize,
             elide_identifiers,
             elide_literals,
             show_source,
+            suggest,
             min_lines,
             allow,
             reason,
         }) => {
+            let mut options = normalize.options();
+            if let Some(elide) = elide_identifiers {
+                options.elide_identifiers = elide;
+            }
+            if let Some(elide) = elide_literals {
+                options.elide_literals = elide;
+            }
+
             if let Some(location) = allow {
                 duplicates::cmd_allow_duplicate_function(
                     &effective_root,
                     &location,
                     reason.as_deref(),
-                    elide_identifiers,
-                    elide_literals,
+                    &options,
                     min_lines,
                 )
             } else {
                 let result = duplicates::cmd_duplicate_functions_with_count(
                     &effective_root,
-                    elide_identifiers,
-                    elide_literals,
+                    &options,
                     show_source,
+                    suggest,
                     min_lines,
-                    json,
+                    &format,
                     filter.as_ref(),
                 );
                 result.exit_code
@@ -567,6 +696,24 @@ pub fn run(args: AnalyzeArgs, format: crate::output::OutputFormat) -> i32 {
             }
         }
 
+        Some(AnalyzeCommand::DuplicateStrings {
+            target,
+            min_occurrences,
+            min_length,
+        }) => {
+            let scan_root = target
+                .map(PathBuf::from)
+                .unwrap_or_else(|| effective_root.clone());
+            duplicates::cmd_duplicate_strings(
+                &scan_root,
+                &effective_root,
+                min_occurrences,
+                min_length,
+                json,
+                filter.as_ref(),
+            )
+        }
+
         Some(AnalyzeCommand::All { target }) => {
             let weights = config.analyze.weights();
             run_all_passes(
@@ -633,7 +780,7 @@ pub fn run(args: AnalyzeArgs, format: crate::output::OutputFormat) -> i32 {
                 false, // length
                 false, // security
                 None,
-                None,
+                &HashSet::new(),
                 filter.as_ref(),
             );
             print_report(&report, json, pretty)
@@ -779,10 +926,6 @@ fn get_diff_files(root: &Path, base: &str) -> Result<Vec<String>, String> {
 
 /// Build filter from exclude/only patterns
 fn build_filter(root: &Path, exclude: &[String], only: &[String]) -> Option<Filter> {
-    if exclude.is_empty() && only.is_empty() {
-        return None;
-    }
-
     let config = MossConfig::load(root);
     let languages = detect_project_languages(root);
     let lang_refs: Vec<&str> = languages.iter().map(|s| s.as_str()).collect();
@@ -813,6 +956,11 @@ fn print_report(report: &report::AnalyzeReport, json: bool, pretty: bool) -> i32
     0
 }
 
+/// Duplicate-function score (0-100): 5 points off per duplicate group.
+fn duplicate_score(group_count: usize) -> f64 {
+    (100.0 - (group_count as f64 * 5.0)).max(0.0)
+}
+
 /// Run all analysis passes
 fn run_all_passes(
     target: Option<&str>,
@@ -824,17 +972,27 @@ fn run_all_passes(
 ) -> i32 {
     let mut exit_code = 0;
     let mut scores: Vec<(f64, f64)> = Vec::new();
+    // Passes that take an output format print JSON or compact text here
+    let format = if json {
+        OutputFormat::Json
+    } else {
+        OutputFormat::Compact
+    };
 
     // 1. Main analysis (health, complexity, length, security)
     if !json {
         eprintln!("Running: health, complexity, length, security...");
     }
     let report = report::analyze(
-        target, root, true, // health
+        target,
+        root,
+        true, // health
         true, // complexity
         true, // length
         true, // security
-        None, None, filter,
+        None,
+        &HashSet::new(),
+        filter,
     );
 
     if let Some(ref complexity_report) = report.complexity {
@@ -857,23 +1015,23 @@ fn run_all_passes(
         eprintln!("Running: duplicate-functions...");
     }
     let dup_result = duplicates::cmd_duplicate_functions_with_count(
-        root, true,  // elide_identifiers
-        false, // elide_literals
+        root,
+        &duplicates::NormalizationLevel::Structural.options(),
         false, // show_source
+        false, // suggest
         1,     // min_lines
-        json, filter,
+        &format,
+        filter,
     );
 
     if dup_result.exit_code != 0 {
         exit_code = dup_result.exit_code;
     }
 
-    let dup_score = if dup_result.group_count == 0 {
-        100.0
-    } else {
-        (100.0 - (dup_result.group_count as f64 * 5.0)).max(0.0)
-    };
-    scores.push((dup_score, weights.duplicate_functions()));
+    scores.push((
+        duplicate_score(dup_result.group_count),
+        weights.duplicate_functions(),
+    ));
 
     // 3. Duplicate types
     if !json {
@@ -897,7 +1055,7 @@ fn run_all_passes(
     if !json {
         eprintln!("Running: files...");
     }
-    let excludes = load_allow_file(root, "large-files-allow");
+    let excludes = allowlist::load(root, "large-files-allow");
     let files_result = files::cmd_files(root, 20, &excludes, json);
     if files_result != 0 {
         exit_code = files_result;
@@ -909,8 +1067,8 @@ fn run_all_passes(
     }
     let config = MossConfig::load(root);
     let mut hotspot_excludes = config.analyze.hotspots_exclude.clone();
-    hotspot_excludes.extend(load_allow_file(root, "hotspots-allow"));
-    let hotspots_result = hotspots::cmd_hotspots(root, &hotspot_excludes, json);
+    hotspot_excludes.extend(allowlist::load(root, "hotspots-allow"));
+    let hotspots_result = hotspots::cmd_hotspots(root, &hotspot_excludes, &format);
     if hotspots_result != 0 {
         exit_code = hotspots_result;
     }
@@ -919,7 +1077,7 @@ fn run_all_passes(
     if !json {
         eprintln!("Running: check-refs...");
     }
-    let refs_result = check_refs::cmd_check_refs(root, json);
+    let refs_result = check_refs::cmd_check_refs(root, json, &config.analyze.check_refs);
     if refs_result != 0 {
         exit_code = refs_result;
     }
@@ -1015,6 +1173,136 @@ fn print_complexity_report(report: &ComplexityReport) {
     }
 }
 
+/// Print complexity report as CSV, one row per function
+fn print_complexity_report_csv(report: &ComplexityReport) {
+    let mut csv = CsvWriter::new(&["file", "symbol", "line", "complexity", "risk"]);
+    for func in &report.functions {
+        csv.row(&[
+            &func.file_path.as_deref().unwrap_or(""),
+            &func.short_name(),
+            &func.start_line,
+            &func.complexity,
+            &func.risk_level().as_str(),
+        ]);
+    }
+    print!("{}", csv.finish());
+}
+
+/// Stream complexity as JSON lines: a header, one record per function as its
+/// file is analyzed (unsorted), then a summary record with totals.
+fn stream_complexity_report(
+    root: &Path,
+    limit: usize,
+    threshold: Option<usize>,
+    kinds: &HashSet<rhizome_moss_languages::SymbolKind>,
+    filter: Option<&Filter>,
+    allowlist: &[String],
+    owners: Option<&CodeOwners>,
+) -> i32 {
+    // Unlocked, as the analysis calls back from its worker threads
+    let mut out = JsonLinesWriter::new(std::io::stdout());
+    let header = out.header(
+        "complexity",
+        serde_json::json!({
+            "root": root.display().to_string(),
+            "threshold": threshold,
+            "limit": (limit != usize::MAX).then_some(limit),
+        }),
+    );
+    let result = header.and_then(|()| {
+        complexity::for_each_function_complexity(
+            root,
+            limit,
+            threshold,
+            kinds,
+            filter,
+            allowlist,
+            &mut |func| {
+                let mut record = serde_json::json!({
+                    "file": func.file_path,
+                    "symbol": func.short_name(),
+                    "start_line": func.start_line,
+                    "end_line": func.end_line,
+                    "complexity": func.complexity,
+                    "risk": func.risk_level().as_str(),
+                });
+                if let Some(owners) = owners {
+                    let file = func.file_path.as_deref().unwrap_or_default();
+                    ownership::set_owner(&mut record, file, root, owners);
+                }
+                out.record("function", &record)
+            },
+        )
+    });
+    let result = result.and_then(|stats| {
+        out.record(
+            "summary",
+            &serde_json::json!({
+                "functions": stats.as_ref().map_or(0, |s| s.total_count),
+                "stats": stats,
+            }),
+        )
+    });
+    json_lines_exit_code(result)
+}
+
+/// Stream security findings as JSON lines: a header, one record per finding as
+/// each tool finishes, then a summary record with totals.
+fn stream_security_report(target: &Path, root: &Path, owners: Option<&CodeOwners>) -> i32 {
+    let mut out = JsonLinesWriter::stdout();
+    let mut counts = report::SecurityReport::default().count_by_severity();
+    let header = out.header(
+        "security",
+        serde_json::json!({
+            "root": root.display().to_string(),
+        }),
+    );
+    let result = header.and_then(|()| {
+        security::for_each_finding(target, root, &mut |f| {
+            *counts.entry(f.severity.as_str()).or_default() += 1;
+            let mut record = serde_json::json!({
+                "file": f.file,
+                "line": f.line,
+                "severity": f.severity.as_str(),
+                "rule_id": f.rule_id,
+                "message": f.message,
+                "tool": f.tool,
+                "fingerprint": f.fingerprint,
+            });
+            if let Some(owners) = owners {
+                ownership::set_owner(&mut record, &f.file, root, owners);
+            }
+            out.record("finding", &record)
+        })
+    });
+    let result = result.and_then(|security| {
+        out.record(
+            "summary",
+            &serde_json::json!({
+                "findings": counts.values().sum::<usize>(),
+                "counts": counts,
+                "tools_run": security.tools_run,
+                "tools_skipped": security.tools_skipped,
+                "suppressed": security.suppressed,
+            }),
+        )
+    });
+    json_lines_exit_code(result)
+}
+
+/// Exit code of a JSON-lines stream: 0 unless writing failed for another
+/// reason than the reader stopping early (e.g. `| head`).
+fn json_lines_exit_code(result: std::io::Result<()>) -> i32 {
+    match result {
+        Ok(()) => 0,
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => 0,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            1
+        }
+    }
+}
+
 /// Print complexity report in pretty format with colors
 fn print_complexity_report_pretty(report: &ComplexityReport) {
     use nu_ansi_term::{Color, Style};
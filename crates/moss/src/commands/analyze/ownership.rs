@@ -0,0 +1,255 @@
+//! Findings by code owner (`analyze ownership`), and the `owner` field that JSON
+//! findings get when the project has a CODEOWNERS file.
+
+use super::duplicates::{self, NormalizationLevel};
+use super::report::{self, SecurityFinding, SecurityReport};
+use super::{AnalyzeWeights, allowlist, complexity, duplicate_score, security};
+use crate::analyze::complexity::{ComplexityReport, FunctionComplexity};
+use crate::codeowners::CodeOwners;
+use crate::filter::Filter;
+use crate::output::{Table, terminal_width};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/// Group for files no CODEOWNERS rule assigns.
+const UNOWNED: &str = "(unowned)";
+
+/// Findings and grade of one owner.
+#[derive(Debug, Serialize)]
+pub struct OwnerSummary {
+    /// Owners as written in CODEOWNERS, or `(unowned)`
+    pub owner: String,
+    pub grade: &'static str,
+    pub percentage: f64,
+    pub functions: usize,
+    pub high_risk_functions: usize,
+    /// Duplicate groups with at least one instance owned by this owner
+    pub duplicate_groups: usize,
+    pub security_findings: usize,
+}
+
+/// Collected findings of one owner.
+#[derive(Default)]
+struct OwnerFindings {
+    functions: Vec<FunctionComplexity>,
+    duplicate_groups: usize,
+    security: Vec<SecurityFinding>,
+}
+
+/// Add `"owner"` to a JSON finding: the owners of `file` (relative to `base`, or
+/// absolute), or null if unowned.
+pub(super) fn set_owner(
+    finding: &mut serde_json::Value,
+    file: &str,
+    base: &Path,
+    owners: &CodeOwners,
+) {
+    if let Some(obj) = finding.as_object_mut() {
+        obj.insert("owner".to_string(), owner_of(owners, base, file).into());
+    }
+}
+
+/// Add `"owner"` to every object in `findings` (a JSON array), using the path in
+/// each object's `file_key` field.
+pub(super) fn set_owners(
+    findings: Option<&mut serde_json::Value>,
+    file_key: &str,
+    base: &Path,
+    owners: &CodeOwners,
+) {
+    let Some(findings) = findings.and_then(|f| f.as_array_mut()) else {
+        return;
+    };
+    for finding in findings {
+        let file = finding
+            .get(file_key)
+            .and_then(|f| f.as_str())
+            .unwrap_or_default()
+            .to_string();
+        set_owner(finding, &file, base, owners);
+    }
+}
+
+/// Owners of `file`, which is relative to `base` (the CODEOWNERS root or a
+/// directory under it) or absolute.
+pub(super) fn owner_of(owners: &CodeOwners, base: &Path, file: &str) -> Option<String> {
+    let path = Path::new(file);
+    if path.is_absolute() {
+        return owners.owner_of(path);
+    }
+    match base.strip_prefix(owners.root()) {
+        Ok(dir) => owners.owner_of(&dir.join(path)),
+        Err(_) => owners.owner_of(path),
+    }
+}
+
+/// Summarize complexity, duplicate-function and security findings by owner.
+pub fn cmd_ownership(
+    root: &Path,
+    weights: &AnalyzeWeights,
+    filter: Option<&Filter>,
+    json: bool,
+) -> i32 {
+    let Some(owners) = CodeOwners::load(root) else {
+        eprintln!("error: no CODEOWNERS file found (looked in .github/, the root and docs/)");
+        return 1;
+    };
+
+    let mut by_owner: BTreeMap<String, OwnerFindings> = BTreeMap::new();
+    let key = |file: &str| owner_of(&owners, root, file).unwrap_or_else(|| UNOWNED.to_string());
+
+    let allowlist = allowlist::load(root, "complexity-allow");
+    let complexity = complexity::analyze_codebase_complexity(
+        root,
+        usize::MAX,
+        None,
+        &HashSet::new(),
+        filter,
+        &allowlist,
+    );
+    for func in complexity.functions {
+        let file = func.file_path.clone().unwrap_or_default();
+        by_owner.entry(key(&file)).or_default().functions.push(func);
+    }
+
+    let groups = duplicates::duplicate_function_files(
+        root,
+        &NormalizationLevel::Structural.options(),
+        1,
+        filter,
+    );
+    for files in groups {
+        let group_owners: HashSet<String> = files.iter().map(|f| key(f)).collect();
+        for owner in group_owners {
+            by_owner.entry(owner).or_default().duplicate_groups += 1;
+        }
+    }
+
+    for finding in security::analyze_security(root, root).findings {
+        if filter.is_none_or(|f| f.matches(Path::new(&finding.file))) {
+            by_owner
+                .entry(key(&finding.file))
+                .or_default()
+                .security
+                .push(finding);
+        }
+    }
+
+    let summaries: Vec<OwnerSummary> = by_owner
+        .into_iter()
+        .map(|(owner, findings)| summarize(owner, findings, weights))
+        .collect();
+
+    if json {
+        let output = serde_json::json!({
+            "codeowners": owners.path().strip_prefix(root).unwrap_or(owners.path()),
+            "owners": summaries,
+        });
+        println!("{}", output);
+    } else {
+        println!(
+            "Findings by owner ({})",
+            owners
+                .path()
+                .strip_prefix(root)
+                .unwrap_or(owners.path())
+                .display()
+        );
+        println!();
+        let mut table = Table::new(&[
+            "Owner",
+            "Grade",
+            "Functions",
+            "High risk",
+            "Duplicates",
+            "Security",
+        ])
+        .align_right(2)
+        .align_right(3)
+        .align_right(4)
+        .align_right(5)
+        .shrink_column(0);
+        for s in &summaries {
+            table.row(vec![
+                s.owner.clone(),
+                format!("{} ({:.0}%)", s.grade, s.percentage),
+                s.functions.to_string(),
+                s.high_risk_functions.to_string(),
+                s.duplicate_groups.to_string(),
+                s.security_findings.to_string(),
+            ]);
+        }
+        print!("{}", table.render(terminal_width()));
+    }
+    0
+}
+
+/// Grade an owner's findings with the weights `analyze all` uses.
+fn summarize(owner: String, findings: OwnerFindings, weights: &AnalyzeWeights) -> OwnerSummary {
+    let functions = ComplexityReport {
+        functions: findings.functions,
+        file_path: String::new(),
+        full_stats: None,
+    };
+    let security = SecurityReport {
+        findings: findings.security,
+        ..Default::default()
+    };
+    let grade = report::calculate_grade(&[
+        (functions.score(), weights.complexity()),
+        (security.score(), weights.security()),
+        (
+            duplicate_score(findings.duplicate_groups),
+            weights.duplicate_functions(),
+        ),
+    ]);
+    OwnerSummary {
+        owner,
+        grade: grade.letter,
+        percentage: grade.percentage,
+        functions: functions.functions.len(),
+        high_risk_functions: functions.high_risk_count(),
+        duplicate_groups: findings.duplicate_groups,
+        security_findings: security.findings.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_set_owners() {
+        let owners = CodeOwners::parse(
+            Path::new("/repo"),
+            PathBuf::from("CODEOWNERS"),
+            "src/ @org/core\n",
+        );
+        let mut findings = serde_json::json!([
+            {"file": "src/lib.rs", "line": 1},
+            {"file": "/repo/src/main.rs", "line": 2},
+            {"file": "README.md", "line": 3},
+        ]);
+        set_owners(Some(&mut findings), "file", Path::new("/repo"), &owners);
+        assert_eq!(
+            owner_of(&owners, Path::new("/repo/src"), "util.rs").as_deref(),
+            Some("@org/core")
+        );
+        let tagged: Vec<_> = findings
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["owner"].clone())
+            .collect();
+        assert_eq!(
+            tagged,
+            vec![
+                serde_json::json!("@org/core"),
+                serde_json::json!("@org/core"),
+                serde_json::Value::Null,
+            ]
+        );
+    }
+}
@@ -284,13 +284,7 @@ fn run_sexp_query(
     grammar: &tree_sitter::Language,
     grammar_name: &str,
 ) -> Result<Vec<MatchResult>, String> {
-    let mut parser = tree_sitter::Parser::new();
-    parser
-        .set_language(grammar)
-        .map_err(|e| format!("Failed to set language: {}", e))?;
-
-    let tree = parser
-        .parse(content, None)
+    let tree = crate::parsers::parse_with_grammar(grammar_name, content)
         .ok_or_else(|| "Failed to parse file".to_string())?;
 
     let query =
@@ -3,7 +3,7 @@
 //! Provides report structs for each analysis type and the `analyze()` function
 //! that orchestrates running multiple analyses based on flags.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 use crate::analyze::complexity::{ComplexityReport, RiskLevel};
@@ -11,6 +11,7 @@ use crate::analyze::function_length::{LengthCategory, LengthReport};
 use crate::filter::Filter;
 use crate::health::{HealthReport, analyze_health};
 use crate::path_resolve;
+use rhizome_moss_languages::SymbolKind;
 use std::path::PathBuf;
 
 use super::{complexity, length, security};
@@ -61,6 +62,8 @@ pub struct SecurityFinding {
     pub rule_id: String,
     pub message: String,
     pub tool: String,
+    /// Stable identifier for allowlisting (rule + file + normalized source line)
+    pub fingerprint: String,
 }
 
 /// Security analysis results
@@ -69,6 +72,8 @@ pub struct SecurityReport {
     pub findings: Vec<SecurityFinding>,
     pub tools_run: Vec<String>,
     pub tools_skipped: Vec<String>,
+    /// Findings suppressed by .moss/security-allow (not counted in score)
+    pub suppressed: usize,
 }
 
 impl SecurityReport {
@@ -116,6 +121,12 @@ impl SecurityReport {
                 self.tools_skipped.join(", ")
             ));
         }
+        if self.suppressed > 0 {
+            lines.push(format!(
+                "Suppressed: {} (see .moss/security-allow)",
+                self.suppressed
+            ));
+        }
 
         if !self.findings.is_empty() {
             lines.push(String::new());
@@ -134,6 +145,7 @@ impl SecurityReport {
                     finding.message,
                     finding.rule_id
                 ));
+                lines.push(format!("             {}", finding.fingerprint));
             }
 
             if self.findings.len() > 20 {
@@ -373,6 +385,7 @@ impl AnalyzeReport {
                         "rule_id": f.rule_id,
                         "message": f.message,
                         "tool": f.tool,
+                        "fingerprint": f.fingerprint,
                     })
                 })
                 .collect();
@@ -384,6 +397,7 @@ impl AnalyzeReport {
                     "counts": security.count_by_severity(),
                     "tools_run": security.tools_run,
                     "tools_skipped": security.tools_skipped,
+                    "suppressed": security.suppressed,
                 }),
             );
         }
@@ -526,7 +540,7 @@ impl AnalyzeReport {
 }
 
 /// Check if a string contains glob characters
-fn is_glob_pattern(s: &str) -> bool {
+pub(crate) fn is_glob_pattern(s: &str) -> bool {
     s.contains('*') || s.contains('?') || s.contains('[') || s.contains('{')
 }
 
@@ -552,7 +566,7 @@ fn analyze_glob(
     run_length: bool,
     run_security: bool,
     complexity_threshold: Option<usize>,
-    _kind_filter: Option<&str>, // Not currently used - FunctionComplexity doesn't have kind
+    kinds: &HashSet<SymbolKind>,
     filter: Option<&Filter>,
 ) -> AnalyzeReport {
     use rayon::prelude::*;
@@ -589,10 +603,10 @@ fn analyze_glob(
                         .functions
                         .into_iter()
                         .filter(|f| {
-                            // Apply threshold filter
                             complexity_threshold
                                 .map(|t| f.complexity >= t)
                                 .unwrap_or(true)
+                                && (kinds.is_empty() || kinds.contains(&f.kind()))
                         })
                         .map(|mut f| {
                             f.file_path = Some(rel_path.to_string_lossy().to_string());
@@ -658,7 +672,7 @@ fn analyze_glob(
 
     // Security analysis works at directory level, so we run on root and filter
     let security = if run_security {
-        let full_report = security::analyze_security(root);
+        let full_report = security::analyze_security(root, root);
         // Filter findings to only files matching our glob pattern
         let matching_findings: Vec<_> = full_report
             .findings
@@ -675,6 +689,7 @@ fn analyze_glob(
                 findings: matching_findings,
                 tools_run: full_report.tools_run,
                 tools_skipped: full_report.tools_skipped,
+                suppressed: full_report.suppressed,
             })
         } else {
             None
@@ -702,18 +717,11 @@ pub fn analyze(
     run_length: bool,
     run_security: bool,
     complexity_threshold: Option<usize>,
-    kind_filter: Option<&str>,
+    kinds: &HashSet<SymbolKind>,
     filter: Option<&Filter>,
 ) -> AnalyzeReport {
     let target_path = target.unwrap_or(".");
 
-    // Normalize kind filter
-    let kind = kind_filter.map(|k| match k.to_lowercase().as_str() {
-        "function" | "functions" | "func" | "fn" => "function",
-        "method" | "methods" => "method",
-        _ => k,
-    });
-
     // Check for glob patterns in target
     if let Some(t) = target {
         if is_glob_pattern(t) {
@@ -724,7 +732,7 @@ pub fn analyze(
                 run_length,
                 run_security,
                 complexity_threshold,
-                kind.as_deref(),
+                kinds,
                 filter,
             );
         }
@@ -783,6 +791,7 @@ pub fn analyze(
                     &analysis_root,
                     10,
                     complexity_threshold,
+                    kinds,
                     filter,
                     &[], // No allowlist for report mode
                 ))
@@ -823,13 +832,11 @@ pub fn analyze(
                 r.functions.retain(|f| f.complexity >= threshold);
             }
 
-            // Apply kind filter (function = no parent, method = has parent)
-            if let (Some(r), Some(k)) = (&mut report, &kind) {
-                match *k {
-                    "function" => r.functions.retain(|f| f.parent.is_none()),
-                    "method" => r.functions.retain(|f| f.parent.is_some()),
-                    _ => {} // Unknown kind, don't filter
-                }
+            // Apply kind filter
+            if let Some(r) = &mut report
+                && !kinds.is_empty()
+            {
+                r.functions.retain(|f| kinds.contains(&f.kind()));
             }
 
             report
@@ -875,7 +882,7 @@ pub fn analyze(
         } else {
             root.to_path_buf()
         };
-        Some(security::analyze_security(&analysis_root))
+        Some(security::analyze_security(&analysis_root, root))
     } else {
         None
     };
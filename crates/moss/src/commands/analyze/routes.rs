@@ -0,0 +1,243 @@
+//! HTTP route table (`analyze routes`).
+//!
+//! Routes are found by tree-sitter queries, one per framework, loaded like
+//! syntax rules: builtins, then `~/.config/moss/routes/*.scm`, then
+//! `.moss/routes/*.scm`. A query captures `@method` (the decorator, function
+//! or annotation naming the HTTP method), `@path`, and the `@handler`
+//! symbol when it has one; `@match` gives the reported line.
+
+use crate::filter::Filter;
+use crate::parsers::grammar_loader;
+use rhizome_moss_rules::{DebugFlags, Finding, RulesConfig, load_route_queries, run_rules};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// One route definition.
+#[derive(Debug, Serialize)]
+pub struct Route {
+    /// HTTP method (GET, POST, ...), ANY for catch-all registrations
+    pub method: String,
+    pub path: String,
+    /// Handler function; None for inline closures
+    pub handler: Option<String>,
+    pub file: String,
+    pub line: usize,
+    /// Framework, from the query's message
+    pub framework: String,
+    /// ID of the query that found the route
+    pub query: String,
+}
+
+/// Routes found under a root, ordered by file and line.
+#[derive(Debug, Serialize)]
+pub struct RoutesReport {
+    pub routes: Vec<Route>,
+}
+
+impl RoutesReport {
+    fn from_findings(root: &Path, findings: &[Finding], filter: Option<&Filter>) -> Self {
+        let mut seen = HashSet::new();
+        let mut routes: Vec<Route> = findings
+            .iter()
+            .filter_map(|f| {
+                let rel = f.file.strip_prefix(root).unwrap_or(&f.file);
+                if filter.is_some_and(|flt| !flt.matches(rel)) {
+                    return None;
+                }
+                Some(Route {
+                    method: http_method(f.captures.get("method")?),
+                    path: f
+                        .captures
+                        .get("path")
+                        .map(|p| unquote(p))
+                        .unwrap_or_default(),
+                    handler: f.captures.get("handler").and_then(|h| handler_name(h)),
+                    file: rel.to_string_lossy().to_string(),
+                    line: f.start_line,
+                    framework: f.message.clone(),
+                    query: f.rule_id.clone(),
+                })
+            })
+            // Overlapping patterns of one query can match the same route
+            .filter(|r| seen.insert((r.file.clone(), r.line, r.method.clone(), r.path.clone())))
+            .collect();
+        routes.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+        Self { routes }
+    }
+
+    pub fn format(&self) -> String {
+        let mut lines = vec!["# Routes".to_string(), String::new()];
+        let files: HashSet<&str> = self.routes.iter().map(|r| r.file.as_str()).collect();
+        lines.push(format!(
+            "{} route{} in {} file{}",
+            self.routes.len(),
+            if self.routes.len() == 1 { "" } else { "s" },
+            files.len(),
+            if files.len() == 1 { "" } else { "s" }
+        ));
+        if self.routes.is_empty() {
+            return lines.join("\n");
+        }
+        lines.push(String::new());
+
+        let handlers: Vec<&str> = self
+            .routes
+            .iter()
+            .map(|r| r.handler.as_deref().unwrap_or("(inline)"))
+            .collect();
+        let method_width = self
+            .routes
+            .iter()
+            .map(|r| r.method.len())
+            .max()
+            .unwrap_or(0);
+        let path_width = self.routes.iter().map(|r| r.path.len()).max().unwrap_or(0);
+        let handler_width = handlers.iter().map(|h| h.len()).max().unwrap_or(0);
+        for (route, handler) in self.routes.iter().zip(&handlers) {
+            lines.push(format!(
+                "{:<mw$}  {:<pw$}  {:<hw$}  {}:{}  [{}]",
+                route.method,
+                route.path,
+                handler,
+                route.file,
+                route.line,
+                route.framework,
+                mw = method_width,
+                pw = path_width,
+                hw = handler_width,
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Normalize the captured method name: `get` and `GetMapping` are GET,
+/// catch-all registrations (`route`, `RequestMapping`, `all`) are ANY.
+fn http_method(name: &str) -> String {
+    let name = name.strip_suffix("Mapping").unwrap_or(name);
+    match name.to_ascii_uppercase().as_str() {
+        "ROUTE" | "API_ROUTE" | "REQUEST" | "ALL" | "ANY" => "ANY".to_string(),
+        "WEBSOCKET" => "WS".to_string(),
+        method => method.to_string(),
+    }
+}
+
+/// Strip string prefixes (`r`, `f`, ...) and quotes from a path literal.
+fn unquote(literal: &str) -> String {
+    literal
+        .trim_start_matches(|c: char| c.is_ascii_alphabetic())
+        .trim_matches(|c| matches!(c, '"' | '\'' | '`' | '#'))
+        .to_string()
+}
+
+/// The handler's name, or None when it isn't a (possibly qualified) name.
+fn handler_name(text: &str) -> Option<String> {
+    let is_name = !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '$' | '.' | ':'));
+    is_name.then(|| text.to_string())
+}
+
+/// List the HTTP routes defined under `root`.
+pub fn cmd_routes(root: &Path, json: bool, filter: Option<&Filter>, config: &RulesConfig) -> i32 {
+    let queries = load_route_queries(root, config);
+    let findings = run_rules(
+        &queries,
+        root,
+        &grammar_loader(),
+        None,
+        &DebugFlags::default(),
+    );
+    let report = RoutesReport::from_findings(root, &findings, filter);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+    } else {
+        println!("{}", report.format());
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhizome_moss_rules::Severity;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn finding(file: &str, line: usize, captures: &[(&str, &str)]) -> Finding {
+        Finding {
+            rule_id: "js/express".to_string(),
+            file: PathBuf::from("/repo").join(file),
+            start_line: line,
+            start_col: 1,
+            end_line: line,
+            end_col: 1,
+            start_byte: 0,
+            end_byte: 0,
+            message: "Express".to_string(),
+            severity: Severity::Warning,
+            matched_text: String::new(),
+            fix: None,
+            captures: captures
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>(),
+        }
+    }
+
+    #[test]
+    fn test_http_method() {
+        assert_eq!(http_method("get"), "GET");
+        assert_eq!(http_method("PostMapping"), "POST");
+        assert_eq!(http_method("RequestMapping"), "ANY");
+        assert_eq!(http_method("api_route"), "ANY");
+    }
+
+    #[test]
+    fn test_unquote() {
+        assert_eq!(unquote("\"/users/{id}\""), "/users/{id}");
+        assert_eq!(unquote("'/x'"), "/x");
+        assert_eq!(unquote("`/items`"), "/items");
+        assert_eq!(unquote("r#\"/raw\"#"), "/raw");
+        assert_eq!(unquote("f'/fmt'"), "/fmt");
+    }
+
+    #[test]
+    fn test_routes_from_findings() {
+        let findings = [
+            finding(
+                "src/b.js",
+                3,
+                &[
+                    ("method", "post"),
+                    ("path", "'/b'"),
+                    ("handler", "(req, res) => {}"),
+                ],
+            ),
+            finding(
+                "src/a.js",
+                7,
+                &[
+                    ("method", "get"),
+                    ("path", "'/a'"),
+                    ("handler", "users.list"),
+                ],
+            ),
+            // Same route matched by a second pattern
+            finding("src/a.js", 7, &[("method", "get"), ("path", "'/a'")]),
+        ];
+        let report = RoutesReport::from_findings(Path::new("/repo"), &findings, None);
+        let summary: Vec<_> = report
+            .routes
+            .iter()
+            .map(|r| (r.method.as_str(), r.path.as_str(), r.handler.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("GET", "/a", Some("users.list")), ("POST", "/b", None)]
+        );
+        assert_eq!(report.routes[0].file, "src/a.js");
+    }
+}
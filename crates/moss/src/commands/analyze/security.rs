@@ -1,9 +1,14 @@
 //! Security analysis - run security scanning tools
 
+use super::allowlist;
 use super::report::{SecurityFinding, SecurityReport, Severity};
+use std::collections::HashSet;
+use std::io;
 use std::path::Path;
 use std::process::Command;
 
+const ALLOWLIST_FILE: &str = "security-allow";
+
 /// Check if a command is available on the system
 fn command_available(cmd: &str) -> bool {
     Command::new("which")
@@ -62,6 +67,7 @@ fn run_bandit(root: &Path) -> Result<Vec<SecurityFinding>, String> {
                 rule_id,
                 message,
                 tool: "bandit".to_string(),
+                fingerprint: String::new(),
             });
         }
     }
@@ -69,15 +75,81 @@ fn run_bandit(root: &Path) -> Result<Vec<SecurityFinding>, String> {
     Ok(findings)
 }
 
-/// Run security analysis on a codebase
-pub fn analyze_security(root: &Path) -> SecurityReport {
+/// Compute a stable fingerprint for a finding.
+///
+/// Hashes the whitespace-normalized source line rather than the line number, so
+/// fingerprints survive unrelated edits that shift the finding up or down.
+fn fingerprint(rule_id: &str, file: &str, context: &str) -> String {
+    let normalized = context.split_whitespace().collect::<Vec<_>>().join(" ");
+    // FNV-1a: stable across Rust versions, unlike DefaultHasher
+    let mut hash = 0xcbf29ce484222325u64;
+    for byte in normalized.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{}:{}:{:016x}", rule_id, file, hash)
+}
+
+/// Make finding paths root-relative and assign fingerprints
+fn normalize_findings(findings: &mut [SecurityFinding], root: &Path) {
+    let root = std::path::absolute(root).unwrap_or_else(|_| root.to_path_buf());
+    for finding in findings {
+        let path = Path::new(&finding.file);
+        let full_path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+        if let Ok(rel) = full_path.strip_prefix(&root) {
+            finding.file = rel.to_string_lossy().to_string();
+        }
+
+        let context = std::fs::read_to_string(&full_path)
+            .ok()
+            .and_then(|content| {
+                content
+                    .lines()
+                    .nth(finding.line.saturating_sub(1))
+                    .map(str::to_string)
+            })
+            .unwrap_or_default();
+        finding.fingerprint = fingerprint(&finding.rule_id, &finding.file, &context);
+    }
+}
+
+/// Run security analysis on `target`, suppressing findings listed in
+/// `root`/.moss/security-allow.
+pub fn analyze_security(target: &Path, root: &Path) -> SecurityReport {
+    let mut findings = Vec::new();
+    let mut report = for_each_finding(target, root, &mut |f| {
+        findings.push(f.clone());
+        Ok(())
+    })
+    .unwrap_or_default();
+    report.findings = findings;
+    report
+}
+
+/// Run security analysis on `target`, passing each finding not listed in
+/// `root`/.moss/security-allow to `on_finding` as soon as its tool has finished.
+/// Stops at the first error from `on_finding`, which is returned. The returned
+/// report has the tools and suppressed count, but no findings.
+pub fn for_each_finding(
+    target: &Path,
+    root: &Path,
+    on_finding: &mut dyn FnMut(&SecurityFinding) -> io::Result<()>,
+) -> io::Result<SecurityReport> {
     let mut report = SecurityReport::default();
+    let allowed: HashSet<String> = allowlist::load(root, ALLOWLIST_FILE).into_iter().collect();
 
     if command_available("bandit") {
-        match run_bandit(root) {
-            Ok(findings) => {
-                report.findings.extend(findings);
+        match run_bandit(target) {
+            Ok(mut findings) => {
                 report.tools_run.push("bandit".to_string());
+                normalize_findings(&mut findings, root);
+                for f in &findings {
+                    if allowed.contains(&f.fingerprint) {
+                        report.suppressed += 1;
+                    } else {
+                        on_finding(f)?;
+                    }
+                }
             }
             Err(_) => {
                 report.tools_skipped.push("bandit (error)".to_string());
@@ -87,5 +159,58 @@ pub fn analyze_security(root: &Path) -> SecurityReport {
         report.tools_skipped.push("bandit".to_string());
     }
 
-    report
+    Ok(report)
+}
+
+/// Allow a security finding by adding its fingerprint to .moss/security-allow
+pub fn cmd_allow_finding(root: &Path, fingerprint: &str, reason: Option<&str>) -> i32 {
+    let mut findings = Vec::new();
+    if command_available("bandit") {
+        match run_bandit(root) {
+            Ok(f) => findings = f,
+            Err(e) => {
+                eprintln!("Failed to run bandit: {}", e);
+                return 1;
+            }
+        }
+    }
+    normalize_findings(&mut findings, root);
+
+    let Some(target) = findings.iter().find(|f| f.fingerprint == fingerprint) else {
+        eprintln!("No security finding with fingerprint: {}", fingerprint);
+        eprintln!("Run `moss analyze security` to see current findings.");
+        return 1;
+    };
+
+    // Findings for the same rule in the same file form a group
+    let related: Vec<String> = findings
+        .iter()
+        .filter(|f| f.rule_id == target.rule_id && f.file == target.file)
+        .map(|f| f.fingerprint.clone())
+        .collect();
+
+    allowlist::add_entries(
+        root,
+        ALLOWLIST_FILE,
+        &related,
+        &[fingerprint.to_string()],
+        reason,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_ignores_whitespace() {
+        let a = fingerprint("B602", "app.py", "  subprocess.call(cmd,  shell=True)");
+        let b = fingerprint("B602", "app.py", "subprocess.call(cmd, shell=True)");
+        assert_eq!(a, b);
+        assert!(a.starts_with("B602:app.py:"));
+        assert_ne!(
+            a,
+            fingerprint("B602", "other.py", "subprocess.call(cmd, shell=True)")
+        );
+    }
 }
@@ -3,7 +3,7 @@
 use crate::index;
 use crate::parsers;
 use crate::path_resolve::resolve_unified;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 /// Trace value provenance for a symbol.
@@ -58,7 +58,8 @@ async fn cmd_trace_async(
     };
 
     // Find the symbol - try index first, fall back to file parsing
-    let symbol_matches = if let Some(mut idx) = index::FileIndex::open_if_enabled(root).await {
+    let mut idx = index::FileIndex::open_if_enabled(root).await;
+    let symbol_matches = if let Some(idx) = idx.as_mut() {
         let _ = idx.incremental_refresh().await;
         match idx.find_symbols(&symbol_name, None, false, 10).await {
             Ok(matches) if !matches.is_empty() => matches,
@@ -105,30 +106,7 @@ async fn cmd_trace_async(
     let end_line = sym.end_line;
 
     // Build signature map for same-file function lookups
-    let extractor = crate::extract::Extractor::new();
-    let extract_result = extractor.extract(&full_path, &content);
-    let mut signature_map: HashMap<String, FunctionInfo> = HashMap::new();
-    fn collect_signatures(
-        sym: &rhizome_moss_languages::Symbol,
-        map: &mut HashMap<String, FunctionInfo>,
-    ) {
-        if !sym.signature.is_empty() {
-            map.insert(
-                sym.name.clone(),
-                FunctionInfo {
-                    signature: sym.signature.clone(),
-                    start_line: sym.start_line,
-                    end_line: sym.end_line,
-                },
-            );
-        }
-        for child in &sym.children {
-            collect_signatures(child, map);
-        }
-    }
-    for sym in &extract_result.symbols {
-        collect_signatures(sym, &mut signature_map);
-    }
+    let signature_map = function_map(&full_path, &content);
 
     // Trace assignments within the function
     let trace_results = trace_assignments(
@@ -136,35 +114,36 @@ async fn cmd_trace_async(
         source_bytes,
         start_line,
         end_line,
-        max_depth,
         &signature_map,
     );
 
+    // Follow assigned values into the functions they're passed to
+    let tracked: HashSet<String> = trace_results.iter().map(|t| t.variable.clone()).collect();
+    let caller = FunctionScope {
+        file: sym.file.clone(),
+        name: sym.name.clone(),
+        start_line,
+        end_line,
+    };
+    let mut visited = HashSet::new();
+    let flows = trace_call_flows(
+        &caller,
+        &tree.root_node(),
+        source_bytes,
+        &signature_map,
+        &tracked,
+        root,
+        idx.as_ref(),
+        1,
+        max_depth,
+        &mut visited,
+    )
+    .await;
+
     if json {
         let trace_json: Vec<serde_json::Value> = trace_results
             .iter()
-            .map(|t| {
-                let calls_json: Vec<serde_json::Value> = t
-                    .calls
-                    .iter()
-                    .map(|c| {
-                        serde_json::json!({
-                            "name": c.name,
-                            "signature": c.signature,
-                            "defined_at": c.defined_at
-                        })
-                    })
-                    .collect();
-                serde_json::json!({
-                    "variable": t.variable,
-                    "line": t.line,
-                    "source": t.source,
-                    "flows_from": t.flows_from,
-                    "is_terminal": t.is_terminal,
-                    "calls": calls_json,
-                    "branch": t.branch_context
-                })
-            })
+            .map(|t| trace_entry_json(t, &sym.file))
             .collect();
         println!(
             "{}",
@@ -173,7 +152,8 @@ async fn cmd_trace_async(
                 "file": sym.file,
                 "start_line": start_line,
                 "end_line": end_line,
-                "trace": trace_json
+                "trace": trace_json,
+                "flows_into": flows.iter().map(call_flow_json).collect::<Vec<_>>()
             })
         );
         return 0;
@@ -313,9 +293,105 @@ async fn cmd_trace_async(
         }
     }
 
+    print_call_flows(&flows, 1, pretty);
+
     0
 }
 
+/// Print values followed into callees, indented by call depth.
+fn print_call_flows(flows: &[CallFlow], depth: usize, pretty: bool) {
+    let indent = "  ".repeat(depth);
+    for flow in flows {
+        let location = format!("({}:{})", flow.file, flow.start_line);
+        if pretty {
+            println!(
+                "{}L{}: {} → {}({}) {}",
+                indent,
+                nu_ansi_term::Color::Yellow.paint(flow.line.to_string()),
+                nu_ansi_term::Color::Cyan.paint(&flow.variable),
+                nu_ansi_term::Color::Magenta.paint(&flow.callee),
+                flow.parameter,
+                nu_ansi_term::Color::DarkGray.paint(&location)
+            );
+        } else {
+            println!(
+                "{}L{}: {} → {}({}) {}",
+                indent, flow.line, flow.variable, flow.callee, flow.parameter, location
+            );
+        }
+        for t in &flow.entries {
+            let flows_from = if t.flows_from.is_empty() {
+                String::new()
+            } else {
+                format!(" ← {}", t.flows_from.join(", "))
+            };
+            println!(
+                "{}  L{}: {} = {}{}",
+                indent, t.line, t.variable, t.source, flows_from
+            );
+        }
+        print_call_flows(&flow.flows_into, depth + 1, pretty);
+    }
+}
+
+fn trace_entry_json(t: &TraceEntry, file: &str) -> serde_json::Value {
+    let calls_json: Vec<serde_json::Value> = t
+        .calls
+        .iter()
+        .map(|c| {
+            serde_json::json!({
+                "name": c.name,
+                "signature": c.signature,
+                "defined_at": c.defined_at
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "file": file,
+        "variable": t.variable,
+        "line": t.line,
+        "source": t.source,
+        "flows_from": t.flows_from,
+        "is_terminal": t.is_terminal,
+        "calls": calls_json,
+        "branch": t.branch_context
+    })
+}
+
+fn call_flow_json(flow: &CallFlow) -> serde_json::Value {
+    serde_json::json!({
+        "variable": flow.variable,
+        "line": flow.line,
+        "callee": flow.callee,
+        "parameter": flow.parameter,
+        "file": flow.file,
+        "start_line": flow.start_line,
+        "end_line": flow.end_line,
+        "trace": flow.entries.iter().map(|t| trace_entry_json(t, &flow.file)).collect::<Vec<_>>(),
+        "flows_into": flow.flows_into.iter().map(call_flow_json).collect::<Vec<_>>()
+    })
+}
+
+/// Functions defined in a file, by name (for same-file call resolution).
+fn function_map(path: &Path, content: &str) -> HashMap<String, FunctionInfo> {
+    let extractor = crate::extract::Extractor::new();
+    let extract_result = extractor.extract(path, content);
+    let mut signature_map: HashMap<String, FunctionInfo> = HashMap::new();
+    for sym in extract_result.symbols.iter().flat_map(|s| s.iter_flat()) {
+        if !sym.signature.is_empty() {
+            signature_map.insert(
+                sym.name.clone(),
+                FunctionInfo {
+                    signature: sym.signature.clone(),
+                    start_line: sym.start_line,
+                    end_line: sym.end_line,
+                },
+            );
+        }
+    }
+    signature_map
+}
+
 /// Parse a file to find a symbol (fallback when index unavailable/empty).
 fn fallback_parse_symbol(
     symbol: &str,
@@ -403,13 +479,368 @@ struct CrossFileReturns {
     file: String,
 }
 
+/// A function being traced: where it lives and its line range.
+#[derive(Debug, Clone)]
+struct FunctionScope {
+    file: String,
+    name: String,
+    start_line: usize,
+    end_line: usize,
+}
+
+/// A traced value passed as a call argument, followed into the callee.
+#[derive(Debug)]
+struct CallFlow {
+    /// Caller variable passed as the argument
+    variable: String,
+    /// Line of the call in the caller
+    line: usize,
+    callee: String,
+    /// Callee parameter receiving the value
+    parameter: String,
+    file: String,
+    start_line: usize,
+    end_line: usize,
+    /// Assignments in the callee derived from the parameter
+    entries: Vec<TraceEntry>,
+    /// Calls the parameter (or values derived from it) is passed on to
+    flows_into: Vec<CallFlow>,
+}
+
+/// Which parameter an argument binds to.
+#[derive(Debug, Clone, PartialEq)]
+enum ArgumentPosition {
+    Index(usize),
+    Keyword(String),
+}
+
+/// A call site passing a traced variable as an argument.
+#[derive(Debug)]
+struct ArgumentUse {
+    variable: String,
+    call: String,
+    line: usize,
+    position: ArgumentPosition,
+}
+
+/// Follow traced variables of `caller` into the functions they're passed to.
+///
+/// Each hop resolves the callee (same file first, then the index via the
+/// caller's imports), binds the argument to the callee's parameter, and traces
+/// assignments derived from that parameter. `visited` holds
+/// (file, function, parameter) triples so recursive call chains terminate.
+#[allow(clippy::too_many_arguments)]
+async fn trace_call_flows(
+    caller: &FunctionScope,
+    tree_root: &tree_sitter::Node<'_>,
+    source: &[u8],
+    functions: &HashMap<String, FunctionInfo>,
+    tracked: &HashSet<String>,
+    root: &Path,
+    idx: Option<&index::FileIndex>,
+    depth: usize,
+    max_depth: usize,
+    visited: &mut HashSet<(String, String, String)>,
+) -> Vec<CallFlow> {
+    if depth > max_depth || tracked.is_empty() {
+        return Vec::new();
+    }
+
+    let uses = find_argument_uses(
+        tree_root,
+        source,
+        caller.start_line,
+        caller.end_line,
+        tracked,
+    );
+
+    let mut flows = Vec::new();
+    for arg in uses {
+        let Some(callee) = resolve_callee(&arg.call, caller, functions, idx).await else {
+            continue;
+        };
+
+        let full_path = root.join(&callee.file);
+        let Ok(content) = std::fs::read_to_string(&full_path) else {
+            continue;
+        };
+        let Some(lang) = rhizome_moss_languages::support_for_path(&full_path) else {
+            continue;
+        };
+        let Some(tree) = parsers::parse_with_grammar(lang.grammar_name(), &content) else {
+            continue;
+        };
+        let callee_source = content.as_bytes();
+
+        let parameters = function_parameters(&tree.root_node(), callee_source, callee.start_line);
+        let parameter = match &arg.position {
+            ArgumentPosition::Index(i) => parameters.get(*i).cloned(),
+            ArgumentPosition::Keyword(name) => parameters.iter().find(|p| *p == name).cloned(),
+        };
+        let Some(parameter) = parameter else {
+            continue;
+        };
+
+        if !visited.insert((callee.file.clone(), callee.name.clone(), parameter.clone())) {
+            continue;
+        }
+
+        // Keep assignments derived from the parameter, growing the tracked set as we go
+        let callee_functions = function_map(&full_path, &content);
+        let mut callee_tracked = HashSet::from([parameter.clone()]);
+        let entries: Vec<TraceEntry> = trace_assignments(
+            &tree.root_node(),
+            callee_source,
+            callee.start_line,
+            callee.end_line,
+            &callee_functions,
+        )
+        .into_iter()
+        .filter(|t| {
+            let derived = t.flows_from.iter().any(|f| callee_tracked.contains(f));
+            if derived {
+                callee_tracked.insert(t.variable.clone());
+            }
+            derived
+        })
+        .collect();
+
+        let flows_into = Box::pin(trace_call_flows(
+            &callee,
+            &tree.root_node(),
+            callee_source,
+            &callee_functions,
+            &callee_tracked,
+            root,
+            idx,
+            depth + 1,
+            max_depth,
+            visited,
+        ))
+        .await;
+
+        flows.push(CallFlow {
+            variable: arg.variable,
+            line: arg.line,
+            callee: callee.name,
+            parameter,
+            file: callee.file,
+            start_line: callee.start_line,
+            end_line: callee.end_line,
+            entries,
+            flows_into,
+        });
+    }
+    flows
+}
+
+/// Find the definition of a called function: same file first, then the index,
+/// preferring the module the caller imports the name from.
+async fn resolve_callee(
+    call: &str,
+    caller: &FunctionScope,
+    functions: &HashMap<String, FunctionInfo>,
+    idx: Option<&index::FileIndex>,
+) -> Option<FunctionScope> {
+    let simple_name = call.split(&['.', ':'][..]).next_back().unwrap_or(call);
+
+    if let Some(info) = functions.get(simple_name) {
+        return Some(FunctionScope {
+            file: caller.file.clone(),
+            name: simple_name.to_string(),
+            start_line: info.start_line,
+            end_line: info.end_line,
+        });
+    }
+
+    let idx = idx?;
+    let import = idx
+        .resolve_import(&caller.file, simple_name)
+        .await
+        .ok()
+        .flatten();
+    let name = import
+        .as_ref()
+        .map(|(_, original)| original.as_str())
+        .unwrap_or(simple_name);
+    let module_stem = import
+        .as_ref()
+        .and_then(|(module, _)| module.rsplit(['.', ':', '/']).next())
+        .filter(|stem| !stem.is_empty());
+
+    let matches: Vec<index::SymbolMatch> = idx
+        .find_symbols(name, None, false, 20)
+        .await
+        .ok()?
+        .into_iter()
+        .filter(|m| m.name == name && (m.kind == "function" || m.kind == "method"))
+        .collect();
+    let sym = module_stem
+        .and_then(|stem| matches.iter().find(|m| m.file.contains(stem)))
+        .or_else(|| matches.first())?;
+
+    Some(FunctionScope {
+        file: sym.file.clone(),
+        name: sym.name.clone(),
+        start_line: sym.start_line,
+        end_line: sym.end_line,
+    })
+}
+
+/// Find calls within a line range that pass a tracked variable as an argument.
+fn find_argument_uses(
+    root: &tree_sitter::Node,
+    source: &[u8],
+    start_line: usize,
+    end_line: usize,
+    tracked: &HashSet<String>,
+) -> Vec<ArgumentUse> {
+    let mut uses = Vec::new();
+    let mut stack = vec![*root];
+    while let Some(node) = stack.pop() {
+        let first = node.start_position().row + 1;
+        let last = node.end_position().row + 1;
+        if last < start_line || first > end_line {
+            continue;
+        }
+
+        if is_call_kind(node.kind()) && first >= start_line {
+            collect_argument_uses(&node, source, tracked, &mut uses);
+        }
+
+        let mut cursor = node.walk();
+        let children: Vec<_> = node.children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+    uses.sort_by_key(|u| u.line);
+    uses
+}
+
+fn collect_argument_uses(
+    call: &tree_sitter::Node,
+    source: &[u8],
+    tracked: &HashSet<String>,
+    uses: &mut Vec<ArgumentUse>,
+) {
+    let Some(name) = call
+        .child_by_field_name("function")
+        .or_else(|| call.child(0))
+        .and_then(|n| n.utf8_text(source).ok())
+    else {
+        return;
+    };
+    let Some(arguments) = call.child_by_field_name("arguments") else {
+        return;
+    };
+
+    let mut index = 0;
+    let mut cursor = arguments.walk();
+    for arg in arguments.named_children(&mut cursor) {
+        if arg.kind().contains("comment") {
+            continue;
+        }
+        let (position, value) = match arg.kind() {
+            "keyword_argument" => {
+                let Some(keyword) = arg
+                    .child_by_field_name("name")
+                    .and_then(|n| n.utf8_text(source).ok())
+                else {
+                    continue;
+                };
+                let value = arg.child_by_field_name("value").unwrap_or(arg);
+                (ArgumentPosition::Keyword(keyword.to_string()), value)
+            }
+            _ => {
+                index += 1;
+                (ArgumentPosition::Index(index - 1), arg)
+            }
+        };
+
+        for variable in extract_identifiers_from_node(&value, source) {
+            if tracked.contains(&variable) {
+                uses.push(ArgumentUse {
+                    variable,
+                    call: name.to_string(),
+                    line: call.start_position().row + 1,
+                    position: position.clone(),
+                });
+            }
+        }
+    }
+}
+
+/// Parameter names of the function starting at `start_line`, excluding receivers
+/// (`self`, `this`, `cls`) so positions line up with call arguments.
+fn function_parameters(root: &tree_sitter::Node, source: &[u8], start_line: usize) -> Vec<String> {
+    let mut stack = vec![*root];
+    let mut parameters_node = None;
+    while let Some(node) = stack.pop() {
+        let first = node.start_position().row + 1;
+        let last = node.end_position().row + 1;
+        if start_line < first || start_line > last {
+            continue;
+        }
+        if first == start_line
+            && let Some(params) = node.child_by_field_name("parameters")
+        {
+            parameters_node = Some(params);
+            break;
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+    let Some(params) = parameters_node else {
+        return Vec::new();
+    };
+
+    let mut names = Vec::new();
+    let mut cursor = params.walk();
+    for param in params.named_children(&mut cursor) {
+        let kind = param.kind();
+        if kind.contains("comment") || kind == "self_parameter" {
+            continue;
+        }
+        let name_node = if kind == "identifier" {
+            Some(param)
+        } else {
+            param
+                .child_by_field_name("pattern")
+                .or_else(|| param.child_by_field_name("name"))
+                .filter(|n| n.kind() == "identifier")
+                .or_else(|| first_identifier(&param))
+        };
+        if let Some(name) = name_node.and_then(|n| n.utf8_text(source).ok())
+            && !["self", "this", "cls"].contains(&name)
+        {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+fn first_identifier<'a>(node: &tree_sitter::Node<'a>) -> Option<tree_sitter::Node<'a>> {
+    if node.kind() == "identifier" {
+        return Some(*node);
+    }
+    let mut cursor = node.walk();
+    let children: Vec<_> = node.named_children(&mut cursor).collect();
+    children.iter().find_map(first_identifier)
+}
+
+/// Call expression kinds across languages.
+fn is_call_kind(kind: &str) -> bool {
+    kind == "call_expression"      // Rust, JS, Go
+        || kind == "call"          // Python, Lua
+        || kind == "method_call"   // Java
+        || kind == "invocation_expression" // C#
+}
+
 /// Trace assignments within a function.
 fn trace_assignments(
     root: &tree_sitter::Node,
     source: &[u8],
     start_line: usize,
     end_line: usize,
-    max_depth: usize,
     signature_map: &HashMap<String, FunctionInfo>,
 ) -> Vec<TraceEntry> {
     let mut entries = Vec::new();
@@ -426,11 +857,6 @@ fn trace_assignments(
         None, // no initial branch context
     );
 
-    // Limit results (max_depth acts as max items until cross-function tracing is added)
-    if entries.len() > max_depth {
-        entries.truncate(max_depth);
-    }
-
     entries
 }
 
@@ -625,12 +1051,7 @@ fn extract_calls_from_node(
             let kind = node.kind();
 
             // Detect call expressions across languages
-            if kind == "call_expression"      // Rust, JS, Go
-                || kind == "call"             // Python, Lua
-                || kind == "method_call"      // Java
-                || kind == "invocation_expression"
-            // C#
-            {
+            if is_call_kind(kind) {
                 // Get the function name (usually first child or 'function' field)
                 let func_name = node
                     .child_by_field_name("function")
@@ -840,3 +1261,147 @@ async fn trace_cross_file_returns_async(
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHAIN: &str = "\
+def entry():
+    x = 1
+    step1(x)
+
+def step1(a):
+    b = a + 1
+    step2(0, value=b)
+
+def step2(unused, value):
+    c = value * 2
+    step3(c)
+
+def step3(d):
+    e = d
+    step1(e)
+";
+
+    fn python_tree(source: &str) -> Option<tree_sitter::Tree> {
+        parsers::parse_with_grammar("python", source)
+    }
+
+    fn tracked(names: &[&str]) -> HashSet<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_argument_positions() {
+        let source = "def f(a):\n    g(1, a, key=a)\n    h(other)\n";
+        // Skip if grammar not available
+        let Some(tree) = python_tree(source) else {
+            return;
+        };
+        let uses = find_argument_uses(&tree.root_node(), source.as_bytes(), 1, 3, &tracked(&["a"]));
+        let positions: Vec<_> = uses
+            .iter()
+            .map(|u| (u.call.as_str(), u.position.clone()))
+            .collect();
+        assert_eq!(
+            positions,
+            vec![
+                ("g", ArgumentPosition::Index(1)),
+                ("g", ArgumentPosition::Keyword("key".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_function_parameters_skip_receiver() {
+        let source = "class C:\n    def m(self, x, y=1):\n        pass\n";
+        // Skip if grammar not available
+        let Some(tree) = python_tree(source) else {
+            return;
+        };
+        assert_eq!(
+            function_parameters(&tree.root_node(), source.as_bytes(), 2),
+            vec!["x", "y"]
+        );
+    }
+
+    /// Follow `x` out of `entry` in [`CHAIN`], at most `max_depth` hops.
+    async fn chain_flows(dir: &Path, max_depth: usize) -> Vec<CallFlow> {
+        let tree = python_tree(CHAIN).unwrap();
+        let path = dir.join("chain.py");
+        let caller = FunctionScope {
+            file: "chain.py".to_string(),
+            name: "entry".to_string(),
+            start_line: 1,
+            end_line: 3,
+        };
+        trace_call_flows(
+            &caller,
+            &tree.root_node(),
+            CHAIN.as_bytes(),
+            &function_map(&path, CHAIN),
+            &tracked(&["x"]),
+            dir,
+            None,
+            1,
+            max_depth,
+            &mut HashSet::new(),
+        )
+        .await
+    }
+
+    /// (callee, parameter) of each hop along the first flow.
+    fn hops(flows: &[CallFlow]) -> Vec<(String, String)> {
+        let mut hops = Vec::new();
+        let mut flows = flows;
+        while let Some(flow) = flows.first() {
+            hops.push((flow.callee.clone(), flow.parameter.clone()));
+            flows = &flow.flows_into;
+        }
+        hops
+    }
+
+    #[tokio::test]
+    async fn test_call_flow_binds_parameters() {
+        // Skip if grammar not available
+        if python_tree("").is_none() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("chain.py"), CHAIN).unwrap();
+
+        let flows = chain_flows(dir.path(), 10).await;
+        let pair = |callee: &str, parameter: &str| (callee.to_string(), parameter.to_string());
+        // step1(x) binds by position, step2(0, value=b) by keyword; the call
+        // back into step1 is not followed again
+        assert_eq!(
+            hops(&flows),
+            vec![
+                pair("step1", "a"),
+                pair("step2", "value"),
+                pair("step3", "d")
+            ]
+        );
+        let derived: Vec<_> = flows[0]
+            .entries
+            .iter()
+            .map(|t| t.variable.as_str())
+            .collect();
+        assert_eq!(derived, vec!["b"]);
+    }
+
+    #[tokio::test]
+    async fn test_call_flow_max_depth() {
+        // Skip if grammar not available
+        if python_tree("").is_none() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("chain.py"), CHAIN).unwrap();
+
+        assert!(chain_flows(dir.path(), 0).await.is_empty());
+        assert_eq!(hops(&chain_flows(dir.path(), 1).await).len(), 1);
+        assert_eq!(hops(&chain_flows(dir.path(), 2).await).len(), 2);
+    }
+}
@@ -0,0 +1,360 @@
+//! Complexity trend of the most complex functions over git history
+//! (`analyze complexity --trend`).
+//!
+//! Each function's file is read at a few commits that touched it (`git show
+//! <rev>:<path>`), reparsed, and the function with the same name and parent is
+//! looked up again. Parsed historical files are cached, since the top functions
+//! often share files.
+
+use super::complexity;
+use crate::analyze::complexity::{ComplexityAnalyzer, FunctionComplexity};
+use crate::filter::Filter;
+use crate::output::{Table, terminal_width};
+use rhizome_moss_languages::SymbolKind;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::process::Command;
+
+/// Most functions `--trend` follows.
+pub const MAX_FUNCTIONS: usize = 20;
+
+/// Most historical commits sampled per function.
+pub const MAX_POINTS: usize = 10;
+
+/// Commits of a file's history the samples are spread over.
+const HISTORY_WINDOW: usize = 200;
+
+/// Direction of a function's complexity, from its oldest sampled version to now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Trend {
+    Improving,
+    Worsening,
+    Stable,
+    /// Not found at any sampled commit
+    New,
+}
+
+impl Trend {
+    fn as_str(self) -> &'static str {
+        match self {
+            Trend::Improving => "improving",
+            Trend::Worsening => "worsening",
+            Trend::Stable => "stable",
+            Trend::New => "new",
+        }
+    }
+}
+
+/// A function's complexity at one point in history.
+#[derive(Debug, Serialize)]
+pub struct TrendPoint {
+    /// Commit hash, or None for the working tree
+    pub rev: Option<String>,
+    /// Commit date (YYYY-MM-DD), or None for the working tree
+    pub date: Option<String>,
+    /// None if the function didn't exist at that commit
+    pub complexity: Option<usize>,
+}
+
+/// Complexity history of one function.
+#[derive(Debug, Serialize)]
+pub struct FunctionTrend {
+    pub name: String,
+    pub file: String,
+    pub start_line: usize,
+    pub complexity: usize,
+    /// Oldest first, ending with the working tree
+    pub history: Vec<TrendPoint>,
+    pub trend: Trend,
+}
+
+/// Complexity reports of files at past commits, by (commit, path).
+#[derive(Default)]
+struct HistoryCache {
+    files: HashMap<(String, String), Vec<FunctionComplexity>>,
+}
+
+impl HistoryCache {
+    /// Functions of `file` (relative to `root`) at commit `rev`; empty if the
+    /// file didn't exist.
+    fn functions(&mut self, root: &Path, rev: &str, file: &str) -> &[FunctionComplexity] {
+        self.files
+            .entry((rev.to_string(), file.to_string()))
+            .or_insert_with(|| {
+                let spec = format!("{}:./{}", rev, file);
+                match git_output(root, &["show", &spec]) {
+                    Some(content) => {
+                        ComplexityAnalyzer::new()
+                            .analyze(&root.join(file), &content)
+                            .functions
+                    }
+                    None => Vec::new(),
+                }
+            })
+    }
+}
+
+/// Show how the complexity of the `functions` most complex functions under
+/// `root` changed over up to `points` past commits each.
+#[allow(clippy::too_many_arguments)]
+pub fn cmd_complexity_trend(
+    root: &Path,
+    functions: usize,
+    points: usize,
+    threshold: Option<usize>,
+    kinds: &HashSet<SymbolKind>,
+    filter: Option<&Filter>,
+    allowlist: &[String],
+    json: bool,
+) -> i32 {
+    if git_output(root, &["rev-parse", "--is-inside-work-tree"]).is_none() {
+        eprintln!("error: --trend needs a git repository");
+        return 1;
+    }
+    let functions = functions.clamp(1, MAX_FUNCTIONS);
+    let points = points.clamp(1, MAX_POINTS);
+
+    let report = complexity::analyze_codebase_complexity(
+        root, functions, threshold, kinds, filter, allowlist,
+    );
+
+    let mut cache = HistoryCache::default();
+    let mut commits_by_file: HashMap<String, Vec<Commit>> = HashMap::new();
+    let trends: Vec<FunctionTrend> = report
+        .functions
+        .iter()
+        .map(|func| {
+            let file = func.file_path.clone().unwrap_or_default();
+            let commits = commits_by_file
+                .entry(file.clone())
+                .or_insert_with(|| file_commits(root, &file, points));
+
+            let mut history: Vec<TrendPoint> = commits
+                .iter()
+                .map(|commit| TrendPoint {
+                    rev: Some(commit.rev.clone()),
+                    date: Some(commit.date.clone()),
+                    complexity: match_function(cache.functions(root, &commit.rev, &file), func)
+                        .map(|f| f.complexity),
+                })
+                .collect();
+            history.push(TrendPoint {
+                rev: None,
+                date: None,
+                complexity: Some(func.complexity),
+            });
+
+            let values: Vec<Option<usize>> = history.iter().map(|p| p.complexity).collect();
+            FunctionTrend {
+                name: func.short_name(),
+                file,
+                start_line: func.start_line,
+                complexity: func.complexity,
+                trend: classify(&values),
+                history,
+            }
+        })
+        .collect();
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "points": points,
+                "functions": trends,
+            })
+        );
+        return 0;
+    }
+
+    if trends.is_empty() {
+        println!("No functions found.");
+        return 0;
+    }
+    println!(
+        "Complexity trend of the {} most complex functions (up to {} commits each, oldest to now)",
+        trends.len(),
+        points
+    );
+    println!();
+    let mut table =
+        Table::new(&["Function", "Location", "History", "Then → Now", "Trend"]).shrink_column(1);
+    for t in &trends {
+        let values: Vec<Option<usize>> = t.history.iter().map(|p| p.complexity).collect();
+        let then = values
+            .iter()
+            .flatten()
+            .next()
+            .copied()
+            .unwrap_or(t.complexity);
+        table.row(vec![
+            t.name.clone(),
+            format!("{}:{}", t.file, t.start_line),
+            sparkline(&values),
+            format!("{} → {}", then, t.complexity),
+            t.trend.as_str().to_string(),
+        ]);
+    }
+    print!("{}", table.render(terminal_width()));
+    0
+}
+
+/// A commit in a file's history.
+#[derive(Debug, Clone)]
+struct Commit {
+    /// Commit hash
+    rev: String,
+    /// Commit date (YYYY-MM-DD)
+    date: String,
+}
+
+/// Up to `points` commits that touched `file`, oldest first, spread evenly over
+/// its recent history.
+fn file_commits(root: &Path, file: &str, points: usize) -> Vec<Commit> {
+    let window = format!("-n{}", HISTORY_WINDOW);
+    let Some(log) = git_output(root, &["log", "--format=%H %as", &window, "--", file]) else {
+        return Vec::new();
+    };
+    let commits: Vec<Commit> = log
+        .lines()
+        .filter_map(|line| {
+            let (rev, date) = line.split_once(' ')?;
+            Some(Commit {
+                rev: rev.to_string(),
+                date: date.to_string(),
+            })
+        })
+        .collect();
+    // `git log` lists newest first
+    sample_evenly(commits.len(), points)
+        .into_iter()
+        .rev()
+        .map(|i| commits[i].clone())
+        .collect()
+}
+
+/// Up to `count` distinct indices spread evenly over `0..len`, ascending and
+/// including both ends.
+fn sample_evenly(len: usize, count: usize) -> Vec<usize> {
+    if len == 0 || count == 0 {
+        return Vec::new();
+    }
+    if count == 1 {
+        return vec![0];
+    }
+    let mut indices: Vec<usize> = (0..count).map(|i| i * (len - 1) / (count - 1)).collect();
+    indices.dedup();
+    indices
+}
+
+/// The function in `candidates` with the same name and parent as `target`,
+/// nearest its line if there are several.
+fn match_function<'a>(
+    candidates: &'a [FunctionComplexity],
+    target: &FunctionComplexity,
+) -> Option<&'a FunctionComplexity> {
+    candidates
+        .iter()
+        .filter(|f| f.name == target.name && f.parent == target.parent)
+        .min_by_key(|f| f.start_line.abs_diff(target.start_line))
+}
+
+/// Trend from the oldest known complexity to the last one (now).
+fn classify(values: &[Option<usize>]) -> Trend {
+    let Some((now, past)) = values.split_last() else {
+        return Trend::New;
+    };
+    match (past.iter().flatten().next(), now) {
+        (Some(then), Some(now)) if now < then => Trend::Improving,
+        (Some(then), Some(now)) if now > then => Trend::Worsening,
+        (Some(_), _) => Trend::Stable,
+        (None, _) => Trend::New,
+    }
+}
+
+/// One block character per value, scaled between the smallest and largest;
+/// `·` where the function didn't exist.
+fn sparkline(values: &[Option<usize>]) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let present = values.iter().flatten();
+    let (Some(min), Some(max)) = (present.clone().min(), present.max()) else {
+        return String::new();
+    };
+    values
+        .iter()
+        .map(|v| match v {
+            Some(v) if max > min => BLOCKS[(v - min) * (BLOCKS.len() - 1) / (max - min)],
+            Some(_) => BLOCKS[0],
+            None => '·',
+        })
+        .collect()
+}
+
+fn git_output(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn function(
+        name: &str,
+        parent: Option<&str>,
+        line: usize,
+        complexity: usize,
+    ) -> FunctionComplexity {
+        FunctionComplexity {
+            name: name.to_string(),
+            complexity,
+            start_line: line,
+            end_line: line + 10,
+            parent: parent.map(String::from),
+            file_path: None,
+        }
+    }
+
+    #[test]
+    fn test_sample_evenly() {
+        assert_eq!(sample_evenly(0, 5), Vec::<usize>::new());
+        assert_eq!(sample_evenly(3, 5), vec![0, 1, 2]);
+        assert_eq!(sample_evenly(100, 5), vec![0, 24, 49, 74, 99]);
+        assert_eq!(sample_evenly(10, 1), vec![0]);
+    }
+
+    #[test]
+    fn test_match_function() {
+        let candidates = vec![
+            function("run", Some("Server"), 10, 4),
+            function("run", None, 50, 7),
+            function("run", None, 200, 9),
+        ];
+        let target = function("run", None, 180, 12);
+        assert_eq!(match_function(&candidates, &target).unwrap().complexity, 9);
+        let method = function("run", Some("Server"), 90, 5);
+        assert_eq!(match_function(&candidates, &method).unwrap().complexity, 4);
+        assert!(match_function(&candidates, &function("stop", None, 1, 1)).is_none());
+    }
+
+    #[test]
+    fn test_classify_and_sparkline() {
+        assert_eq!(classify(&[Some(20), Some(15), Some(12)]), Trend::Improving);
+        assert_eq!(classify(&[None, Some(8), Some(12)]), Trend::Worsening);
+        assert_eq!(classify(&[Some(12), Some(30), Some(12)]), Trend::Stable);
+        assert_eq!(classify(&[None, None, Some(12)]), Trend::New);
+        assert_eq!(classify(&[Some(12)]), Trend::New);
+
+        assert_eq!(sparkline(&[None, Some(8), Some(12), Some(15)]), "·▁▅█");
+        assert_eq!(sparkline(&[Some(3), Some(3)]), "▁▁");
+    }
+}
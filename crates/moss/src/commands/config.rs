@@ -0,0 +1,428 @@
+//! Config command - emit the config JSON Schema and validate config files.
+
+use crate::config::MossConfig;
+use clap::Subcommand;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Print the JSON Schema for .moss/config.toml
+    Schema,
+
+    /// Validate config files against the schema
+    Validate {
+        /// Config file to validate (defaults to project and global config)
+        file: Option<PathBuf>,
+    },
+}
+
+/// A problem found while validating a config file.
+#[derive(Debug, serde::Serialize)]
+pub struct ConfigIssue {
+    pub file: String,
+    /// 1-based line of the offending key, when it can be located
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// Dotted key path (e.g. "analyze.threshold")
+    pub key: String,
+    pub message: String,
+}
+
+/// Run the config command
+pub fn cmd_config(action: ConfigAction, root: Option<&Path>, json: bool) -> i32 {
+    let root = root
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(|| std::env::current_dir().unwrap());
+
+    match action {
+        ConfigAction::Schema => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&MossConfig::json_schema()).unwrap()
+            );
+            0
+        }
+        ConfigAction::Validate { file } => {
+            let files = match file {
+                Some(f) => vec![f],
+                None => {
+                    let mut files = Vec::new();
+                    if let Some(global) = MossConfig::global_config_path()
+                        && global.exists()
+                    {
+                        files.push(global);
+                    }
+                    let project = root.join(".moss").join("config.toml");
+                    if project.exists() {
+                        files.push(project);
+                    }
+                    files
+                }
+            };
+            cmd_validate(&files, json)
+        }
+    }
+}
+
+/// Validate each file and report issues.
+fn cmd_validate(files: &[PathBuf], json: bool) -> i32 {
+    let schema = MossConfig::json_schema();
+    let mut issues = Vec::new();
+    for file in files {
+        match std::fs::read_to_string(file) {
+            Ok(content) => issues.extend(validate_config(
+                &file.display().to_string(),
+                &content,
+                &schema,
+            )),
+            Err(e) => issues.push(ConfigIssue {
+                file: file.display().to_string(),
+                line: None,
+                key: String::new(),
+                message: format!("failed to read: {}", e),
+            }),
+        }
+    }
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "files": files.iter().map(|f| f.display().to_string()).collect::<Vec<_>>(),
+                "issues": issues,
+            })
+        );
+    } else if files.is_empty() {
+        println!("No config files found");
+    } else {
+        for issue in &issues {
+            let location = match issue.line {
+                Some(line) => format!("{}:{}", issue.file, line),
+                None => issue.file.clone(),
+            };
+            if issue.key.is_empty() {
+                println!("{}: error: {}", location, issue.message);
+            } else {
+                println!("{}: error: {}: {}", location, issue.key, issue.message);
+            }
+        }
+        if issues.is_empty() {
+            for file in files {
+                println!("{}: ok", file.display());
+            }
+        }
+    }
+
+    if issues.is_empty() { 0 } else { 1 }
+}
+
+/// Validate TOML config content against the config JSON Schema.
+pub fn validate_config(file: &str, content: &str, schema: &Value) -> Vec<ConfigIssue> {
+    let parsed: toml::Value = match toml::from_str(content) {
+        Ok(v) => v,
+        Err(e) => {
+            let line = e
+                .span()
+                .map(|span| content[..span.start].matches('\n').count() + 1);
+            return vec![ConfigIssue {
+                file: file.to_string(),
+                line,
+                key: String::new(),
+                message: e.message().to_string(),
+            }];
+        }
+    };
+    let value = serde_json::to_value(parsed).unwrap_or(Value::Null);
+
+    let mut errors = Vec::new();
+    check_value(&value, schema, schema, &mut Vec::new(), &mut errors);
+
+    let mut issues: Vec<ConfigIssue> = errors
+        .into_iter()
+        .map(|error| ConfigIssue {
+            file: file.to_string(),
+            line: find_key_line(content, &error.path),
+            key: error.path.join("."),
+            message: error.message,
+        })
+        .collect();
+    issues.sort_by_key(|i| i.line.unwrap_or(usize::MAX));
+    issues
+}
+
+/// Resolve a local `$ref` (e.g. "#/$defs/ViewConfig") against the root schema.
+fn resolve<'a>(schema: &'a Value, root: &'a Value) -> &'a Value {
+    match schema.get("$ref").and_then(|r| r.as_str()) {
+        Some(reference) => reference
+            .strip_prefix('#')
+            .and_then(|pointer| root.pointer(pointer))
+            .map(|target| resolve(target, root))
+            .unwrap_or(schema),
+        None => schema,
+    }
+}
+
+/// Name of a JSON value's type, using JSON Schema vocabulary.
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Check whether a value satisfies a JSON Schema `type` keyword entry.
+fn matches_type(value: &Value, ty: &str) -> bool {
+    match ty {
+        "number" => value.is_number(),
+        other => type_name(value) == other,
+    }
+}
+
+/// A value that doesn't match the schema.
+struct SchemaError {
+    /// Key path to the value
+    path: Vec<String>,
+    message: String,
+}
+
+impl SchemaError {
+    fn new(path: &[String], message: String) -> Self {
+        Self {
+            path: path.to_vec(),
+            message,
+        }
+    }
+}
+
+/// Recursively check `value` against `schema`, collecting errors.
+fn check_value(
+    value: &Value,
+    schema: &Value,
+    root: &Value,
+    path: &mut Vec<String>,
+    errors: &mut Vec<SchemaError>,
+) {
+    let schema = resolve(schema, root);
+
+    // Unions (Option<Struct>, enums): accept the first branch without errors
+    if let Some(branches) = schema
+        .get("anyOf")
+        .or_else(|| schema.get("oneOf"))
+        .and_then(|b| b.as_array())
+    {
+        let consts: Vec<&Value> = branches
+            .iter()
+            .filter_map(|b| resolve(b, root).get("const"))
+            .collect();
+        if consts.len() == branches.len() {
+            if !consts.contains(&value) {
+                errors.push(SchemaError::new(path, expected_one_of(value, &consts)));
+            }
+            return;
+        }
+
+        let mut best: Option<Vec<SchemaError>> = None;
+        for branch in branches {
+            let mut branch_errors = Vec::new();
+            check_value(value, branch, root, path, &mut branch_errors);
+            if branch_errors.is_empty() {
+                return;
+            }
+            if best.as_ref().is_none_or(|b| branch_errors.len() < b.len()) {
+                best = Some(branch_errors);
+            }
+        }
+        errors.extend(best.unwrap_or_default());
+        return;
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(|e| e.as_array()) {
+        let allowed: Vec<&Value> = allowed.iter().collect();
+        if !allowed.contains(&value) {
+            errors.push(SchemaError::new(path, expected_one_of(value, &allowed)));
+        }
+        return;
+    }
+
+    if let Some(ty) = schema.get("type") {
+        let types: Vec<&str> = match ty {
+            Value::String(s) => vec![s.as_str()],
+            Value::Array(a) => a.iter().filter_map(|t| t.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|t| matches_type(value, t)) {
+            errors.push(SchemaError::new(
+                path,
+                format!(
+                    "expected {}, found {}",
+                    types.join(" or "),
+                    type_name(value)
+                ),
+            ));
+            return;
+        }
+    }
+
+    if let Some(n) = value.as_f64() {
+        if let Some(min) = schema.get("minimum").and_then(|m| m.as_f64())
+            && n < min
+        {
+            errors.push(SchemaError::new(
+                path,
+                format!("{} is below minimum {}", value, min),
+            ));
+        }
+        if let Some(max) = schema.get("maximum").and_then(|m| m.as_f64())
+            && n > max
+        {
+            errors.push(SchemaError::new(
+                path,
+                format!("{} is above maximum {}", value, max),
+            ));
+        }
+    }
+
+    match value {
+        Value::Object(map) => {
+            let properties = schema.get("properties").and_then(|p| p.as_object());
+            let additional = schema.get("additionalProperties");
+            for (key, child) in map {
+                path.push(key.clone());
+                if let Some(prop) = properties.and_then(|p| p.get(key)) {
+                    check_value(child, prop, root, path, errors);
+                } else {
+                    match additional {
+                        Some(Value::Bool(true)) => {}
+                        Some(extra @ Value::Object(_)) => {
+                            check_value(child, extra, root, path, errors)
+                        }
+                        _ => errors.push(SchemaError::new(path, "unknown key".to_string())),
+                    }
+                }
+                path.pop();
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    path.push(i.to_string());
+                    check_value(item, item_schema, root, path, errors);
+                    path.pop();
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn expected_one_of(value: &Value, allowed: &[&Value]) -> String {
+    let allowed: Vec<String> = allowed.iter().map(|v| v.to_string()).collect();
+    format!(
+        "invalid value {}, expected one of {}",
+        value,
+        allowed.join(", ")
+    )
+}
+
+/// Locate the line defining a key path in TOML source.
+///
+/// Tracks `[table]` headers and matches `key = ...` within the parent table, or a
+/// `[table.key]` header for table-valued keys. Array indices are ignored.
+fn find_key_line(content: &str, path: &[String]) -> Option<usize> {
+    let path: Vec<&str> = path
+        .iter()
+        .map(|s| s.as_str())
+        .filter(|s| s.parse::<usize>().is_err())
+        .collect();
+    let (key, parents) = path.split_last()?;
+    let parent_table = parents.join(".");
+    let full_table = path.join(".");
+
+    let mut current_table = String::new();
+    for (idx, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if let Some(header) = trimmed.strip_prefix('[') {
+            let header = header.trim_start_matches('[');
+            let header = header.split(']').next().unwrap_or("").trim();
+            current_table = header.replace(['"', '\''], "");
+            if current_table == full_table {
+                return Some(idx + 1);
+            }
+            continue;
+        }
+        if current_table == parent_table
+            && let Some((lhs, _)) = trimmed.split_once('=')
+            && lhs.trim().trim_matches(['"', '\'']) == *key
+        {
+            return Some(idx + 1);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(content: &str) -> Vec<ConfigIssue> {
+        validate_config("config.toml", content, &MossConfig::json_schema())
+    }
+
+    #[test]
+    fn test_valid_config() {
+        let issues = validate(
+            r#"
+[daemon]
+enabled = false
+
+[aliases]
+vendor = ["vendor/**"]
+
+[pretty]
+colors = "always"
+"#,
+        );
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn test_unknown_key_and_type_mismatch() {
+        let issues = validate(
+            r#"
+[view]
+depht = 2
+
+[text-search]
+limit = "fifty"
+"#,
+        );
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].key, "view.depht");
+        assert_eq!(issues[0].message, "unknown key");
+        assert_eq!(issues[0].line, Some(3));
+        assert_eq!(issues[1].key, "text-search.limit");
+        assert_eq!(issues[1].line, Some(6));
+    }
+
+    #[test]
+    fn test_out_of_range_and_enum() {
+        let issues = validate(
+            r#"
+[analyze]
+threshold = -1
+
+[pretty]
+colors = "sometimes"
+"#,
+        );
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].message.contains("below minimum"));
+        assert!(issues[1].message.contains("expected one of"));
+    }
+}
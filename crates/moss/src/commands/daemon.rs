@@ -1,8 +1,13 @@
 //! Daemon management commands for moss CLI.
 
-use crate::daemon::{self, DaemonClient, Response, global_socket_path};
+use crate::daemon::{
+    self, DaemonClient, Response, global_log_path, global_socket_path, parse_log_line,
+    rotated_log_path,
+};
 use clap::Subcommand;
-use std::path::PathBuf;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Handle a daemon response, calling success_fn for Ok(resp.ok) case.
 /// Returns exit code: 0 for success, 1 for error.
@@ -56,6 +61,17 @@ pub enum DaemonAction {
 
     /// List all watched roots
     List,
+
+    /// Show recent daemon activity (requests, reindexing, errors)
+    Logs {
+        /// Keep printing new entries as they are logged
+        #[arg(short, long)]
+        follow: bool,
+
+        /// Number of recent entries to show
+        #[arg(short = 'n', long, default_value = "50")]
+        lines: usize,
+    },
 }
 
 /// Run a daemon management action
@@ -76,6 +92,7 @@ pub fn cmd_daemon(action: DaemonAction, json: bool) -> i32 {
                 } else {
                     eprintln!("Daemon is not running");
                     eprintln!("Socket: {}", global_socket_path().display());
+                    eprintln!("Log: {}", global_log_path().display());
                 }
                 return 1;
             }
@@ -96,6 +113,7 @@ pub fn cmd_daemon(action: DaemonAction, json: bool) -> i32 {
                         if let Some(roots) = data.get("roots_watched") {
                             println!("  Roots watched: {}", roots);
                         }
+                        println!("  Log: {}", global_log_path().display());
                     }
                     0
                 }
@@ -264,5 +282,85 @@ pub fn cmd_daemon(action: DaemonAction, json: bool) -> i32 {
                 }
             })
         }
+
+        DaemonAction::Logs { follow, lines } => cmd_logs(follow, lines, json),
+    }
+}
+
+/// Print the last `lines` log entries, then optionally follow the log.
+fn cmd_logs(follow: bool, lines: usize, json: bool) -> i32 {
+    let path = global_log_path();
+    if !follow && !path.exists() {
+        eprintln!("No daemon log at {}", path.display());
+        return 1;
+    }
+
+    // The rotated log holds the entries before the current one
+    let previous = std::fs::read_to_string(rotated_log_path(&path)).unwrap_or_default();
+    let current = std::fs::read_to_string(&path).unwrap_or_default();
+    let all: Vec<&str> = previous.lines().chain(current.lines()).collect();
+
+    let mut out = std::io::stdout().lock();
+    for line in &all[all.len().saturating_sub(lines)..] {
+        if print_log_line(&mut out, line, json).is_err() {
+            return 0;
+        }
+    }
+    if follow {
+        let _ = out.flush();
+        drop(out);
+        follow_log(&path, current.len() as u64, json);
+    }
+    0
+}
+
+fn print_log_line(out: &mut impl Write, line: &str, json: bool) -> std::io::Result<()> {
+    if !json {
+        return writeln!(out, "{}", line);
+    }
+    match parse_log_line(line) {
+        Some(entry) => writeln!(out, "{}", serde_json::to_string(&entry).unwrap()),
+        None => Ok(()),
+    }
+}
+
+/// Print entries appended to the log after byte `pos` until stdout closes.
+/// A log that shrinks has been rotated, and is read again from the start.
+fn follow_log(path: &Path, mut pos: u64, json: bool) {
+    let mut pending = String::new();
+    loop {
+        std::thread::sleep(Duration::from_millis(500));
+        let len = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        if len < pos {
+            pos = 0;
+            pending.clear();
+        }
+        if len == pos {
+            continue;
+        }
+        let Ok(mut file) = std::fs::File::open(path) else {
+            continue;
+        };
+        let mut chunk = Vec::new();
+        if file.seek(SeekFrom::Start(pos)).is_err() || file.read_to_end(&mut chunk).is_err() {
+            continue;
+        }
+        pos += chunk.len() as u64;
+        pending.push_str(&String::from_utf8_lossy(&chunk));
+
+        // Only complete lines; a partial write is finished on the next poll
+        let Some(end) = pending.rfind('\n') else {
+            continue;
+        };
+        let mut out = std::io::stdout().lock();
+        for line in pending[..end].lines() {
+            if print_log_line(&mut out, line, json).is_err() {
+                return;
+            }
+        }
+        if out.flush().is_err() {
+            return;
+        }
+        pending.drain(..=end);
     }
 }
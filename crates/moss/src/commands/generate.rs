@@ -1,7 +1,26 @@
 //! Generate command - code generation from API specs and schemas.
+//!
+//! External generators are configured in `.moss/generators.toml`:
+//!
+//! ```toml
+//! [generator.elm]
+//! command = ["elm-openapi-gen", "--stdin"]
+//! languages = ["elm"]
+//! targets = ["client"]
+//! ```
+//!
+//! The spec (or schema) is passed to the command as JSON on stdin, from an
+//! anonymous temporary file; its stdout is the generated code.
 
 use clap::{Args, Subcommand};
-use std::path::PathBuf;
+use rhizome_moss_core::load_spec_file;
+use rhizome_moss_jsonschema::JsonSchemaGenerator;
+use rhizome_moss_openapi::{GenerateError, GenerateOptions, GeneratorPreamble, Issue, Severity};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::{Seek, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 
 /// Generate command arguments
 #[derive(Args)]
@@ -14,7 +33,7 @@ pub struct GenerateArgs {
 pub enum GenerateTarget {
     /// Generate API client from OpenAPI spec
     Client {
-        /// OpenAPI spec JSON file
+        /// OpenAPI spec file (JSON or YAML)
         spec: PathBuf,
 
         /// Target language: typescript, python, rust
@@ -24,11 +43,37 @@ pub enum GenerateTarget {
         /// Output file (stdout if not specified)
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Return declared response headers alongside the body
+        #[arg(long)]
+        with_headers: bool,
+
+        /// Also emit `MockApiClient`, returning example responses, for tests
+        #[arg(long)]
+        mock: bool,
+
+        /// File whose contents go at the top of the output (e.g. a license header)
+        #[arg(long, value_name = "PATH")]
+        header_file: Option<PathBuf>,
+
+        /// Leave out the `Auto-generated from ...` banner
+        #[arg(long)]
+        no_banner: bool,
+    },
+    /// Check an OpenAPI spec for structural problems
+    Validate {
+        /// OpenAPI spec file (JSON or YAML)
+        spec: PathBuf,
     },
     /// Generate types from JSON Schema
     Types {
-        /// JSON Schema file
-        schema: PathBuf,
+        /// JSON Schema file (JSON or YAML)
+        #[arg(required_unless_present = "from_sample")]
+        schema: Option<PathBuf>,
+
+        /// Infer the schema from a sample JSON (or YAML) document instead
+        #[arg(long, value_name = "FILE", conflicts_with = "schema")]
+        from_sample: Option<PathBuf>,
 
         /// Root type name
         #[arg(short, long, default_value = "Root")]
@@ -41,13 +86,96 @@ pub enum GenerateTarget {
         /// Output file (stdout if not specified)
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Expand `$ref`s in place instead of referencing named types
+        /// (recursive references stay named)
+        #[arg(long)]
+        inline_refs: bool,
+
+        /// File whose contents go at the top of the output (e.g. a license header)
+        #[arg(long, value_name = "PATH")]
+        header_file: Option<PathBuf>,
+
+        /// Leave out the `Auto-generated from ...` banner
+        #[arg(long)]
+        no_banner: bool,
+    },
+    /// Generate sample JSON for each type in a JSON Schema
+    Example {
+        /// JSON Schema file (JSON or YAML)
+        schema: PathBuf,
+
+        /// Root type name
+        #[arg(short, long, default_value = "Root")]
+        name: String,
+
+        /// Directory to write one `<Type>.json` per type (stdout if not specified)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
     },
 }
 
+/// Read and parse a JSON or YAML spec file, reporting failures to stderr
+fn read_spec(path: &Path) -> Option<serde_json::Value> {
+    match load_spec_file(path) {
+        Ok(spec) => Some(spec),
+        Err(e) => {
+            eprintln!("{}", e);
+            None
+        }
+    }
+}
+
+/// Preamble from `--header-file` and `--no-banner`, reporting an unreadable
+/// header file to stderr
+fn read_preamble(header_file: Option<&Path>, no_banner: bool) -> Option<GeneratorPreamble> {
+    let header = match header_file {
+        Some(path) => match std::fs::read_to_string(path) {
+            Ok(header) => Some(header),
+            Err(e) => {
+                eprintln!("Failed to read {}: {}", path.display(), e);
+                return None;
+            }
+        },
+        None => None,
+    };
+    Some(GeneratorPreamble { header, no_banner })
+}
+
+fn format_issue(spec: &Path, issue: &Issue) -> String {
+    let pointer = if issue.pointer.is_empty() {
+        "/"
+    } else {
+        &issue.pointer
+    };
+    format!(
+        "{}: {}: {}: {}",
+        spec.display(),
+        issue.severity.as_str(),
+        pointer,
+        issue.message
+    )
+}
+
+fn has_errors(issues: &[Issue]) -> bool {
+    issues.iter().any(|i| i.severity == Severity::Error)
+}
+
 /// Run the generate command
-pub fn run(args: GenerateArgs) -> i32 {
+pub fn run(args: GenerateArgs, json: bool) -> i32 {
+    let root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    register_external_generators(&root);
+
     match args.target {
-        GenerateTarget::Client { spec, lang, output } => {
+        GenerateTarget::Client {
+            spec,
+            lang,
+            output,
+            with_headers,
+            mock,
+            header_file,
+            no_banner,
+        } => {
             let Some(generator) = rhizome_moss_openapi::find_generator(&lang) else {
                 eprintln!("Unknown language: {}. Available:", lang);
                 for (lang, variant) in rhizome_moss_openapi::list_generators() {
@@ -56,23 +184,36 @@ pub fn run(args: GenerateArgs) -> i32 {
                 return 1;
             };
 
-            let content = match std::fs::read_to_string(&spec) {
-                Ok(c) => c,
-                Err(e) => {
-                    eprintln!("Failed to read {}: {}", spec.display(), e);
-                    return 1;
-                }
+            let Some(spec_json) = read_spec(&spec) else {
+                return 1;
+            };
+            let Some(preamble) = read_preamble(header_file.as_deref(), no_banner) else {
+                return 1;
             };
-            let spec_json: serde_json::Value = match serde_json::from_str(&content) {
-                Ok(j) => j,
+
+            let issues = rhizome_moss_openapi::validate(&spec_json);
+            for issue in &issues {
+                eprintln!("{}", format_issue(&spec, issue));
+            }
+            if has_errors(&issues) {
+                eprintln!("Spec has errors; run `moss generate validate` for details");
+                return 1;
+            }
+
+            let options = GenerateOptions { with_headers, mock };
+            let code = match rhizome_moss_openapi::apply_preamble(
+                generator.as_ref(),
+                &spec_json,
+                &options,
+                &preamble,
+            ) {
+                Ok(code) => code,
                 Err(e) => {
-                    eprintln!("Failed to parse JSON: {}", e);
+                    eprintln!("{}", e);
                     return 1;
                 }
             };
 
-            let code = generator.generate(&spec_json);
-
             if let Some(path) = output {
                 if let Err(e) = std::fs::write(&path, &code) {
                     eprintln!("Failed to write {}: {}", path.display(), e);
@@ -84,11 +225,43 @@ pub fn run(args: GenerateArgs) -> i32 {
             }
             0
         }
+        GenerateTarget::Validate { spec } => {
+            let Some(spec_json) = read_spec(&spec) else {
+                return 1;
+            };
+            let issues = rhizome_moss_openapi::validate(&spec_json);
+
+            if json {
+                let issues: Vec<_> = issues
+                    .iter()
+                    .map(|i| {
+                        serde_json::json!({
+                            "severity": i.severity.as_str(),
+                            "pointer": i.pointer,
+                            "message": i.message,
+                        })
+                    })
+                    .collect();
+                println!("{}", serde_json::json!({ "issues": issues }));
+            } else if issues.is_empty() {
+                println!("{}: ok", spec.display());
+            } else {
+                for issue in &issues {
+                    println!("{}", format_issue(&spec, issue));
+                }
+            }
+
+            if has_errors(&issues) { 1 } else { 0 }
+        }
         GenerateTarget::Types {
             schema,
+            from_sample,
             name,
             lang,
             output,
+            inline_refs,
+            header_file,
+            no_banner,
         } => {
             let Some(generator) = rhizome_moss_jsonschema::find_generator(&lang) else {
                 eprintln!("Unknown language: {}. Available:", lang);
@@ -98,23 +271,36 @@ pub fn run(args: GenerateArgs) -> i32 {
                 return 1;
             };
 
-            let content = match std::fs::read_to_string(&schema) {
-                Ok(c) => c,
-                Err(e) => {
-                    eprintln!("Failed to read {}: {}", schema.display(), e);
-                    return 1;
-                }
+            // clap requires one of SCHEMA and --from-sample
+            let Some(input) = from_sample.as_ref().or(schema.as_ref()) else {
+                return 1;
+            };
+            let Some(mut schema_json) = read_spec(input) else {
+                return 1;
+            };
+            let Some(preamble) = read_preamble(header_file.as_deref(), no_banner) else {
+                return 1;
             };
-            let schema_json: serde_json::Value = match serde_json::from_str(&content) {
-                Ok(j) => j,
+            if from_sample.is_some() {
+                schema_json = rhizome_moss_jsonschema::infer_schema(&schema_json, &name);
+            }
+            if inline_refs {
+                schema_json = rhizome_moss_jsonschema::inline_refs(&schema_json);
+            }
+
+            let code = match rhizome_moss_jsonschema::apply_preamble(
+                generator.as_ref(),
+                &schema_json,
+                &name,
+                &preamble,
+            ) {
+                Ok(code) => code,
                 Err(e) => {
-                    eprintln!("Failed to parse JSON: {}", e);
+                    eprintln!("{}", e);
                     return 1;
                 }
             };
 
-            let code = generator.generate(&schema_json, &name);
-
             if let Some(path) = output {
                 if let Err(e) = std::fs::write(&path, &code) {
                     eprintln!("Failed to write {}: {}", path.display(), e);
@@ -126,5 +312,349 @@ pub fn run(args: GenerateArgs) -> i32 {
             }
             0
         }
+        GenerateTarget::Example {
+            schema,
+            name,
+            output,
+        } => {
+            let Some(schema_json) = read_spec(&schema) else {
+                return 1;
+            };
+            let samples = rhizome_moss_jsonschema::samples(&schema_json, &name);
+
+            let Some(dir) = output else {
+                let map: serde_json::Map<String, serde_json::Value> = samples
+                    .into_iter()
+                    .map(|s| (s.type_name, s.value))
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&map).unwrap());
+                return 0;
+            };
+            if let Err(e) = std::fs::create_dir_all(&dir) {
+                eprintln!("Failed to create {}: {}", dir.display(), e);
+                return 1;
+            }
+            for sample in &samples {
+                let path = dir.join(format!("{}.json", sample.type_name));
+                let content = serde_json::to_string_pretty(&sample.value).unwrap() + "\n";
+                if let Err(e) = std::fs::write(&path, content) {
+                    eprintln!("Failed to write {}: {}", path.display(), e);
+                    return 1;
+                }
+                eprintln!("Generated {}", path.display());
+            }
+            0
+        }
+    }
+}
+
+// =============================================================================
+// External generators
+// =============================================================================
+
+/// Contents of `.moss/generators.toml`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GeneratorsConfig {
+    #[serde(default)]
+    pub generator: HashMap<String, ExternalGeneratorConfig>,
+}
+
+/// An external generator command.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExternalGeneratorConfig {
+    /// Command to run (first element is executable, rest are args).
+    pub command: Vec<String>,
+
+    /// Values of `--lang` routed to this command (defaults to the table name).
+    #[serde(default)]
+    pub languages: Vec<String>,
+
+    /// Which subcommands use it: "client", "types" (defaults to both).
+    #[serde(default = "default_targets")]
+    pub targets: Vec<ExternalTarget>,
+
+    /// Variant shown when listing client generators.
+    #[serde(default = "default_variant")]
+    pub variant: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExternalTarget {
+    Client,
+    Types,
+}
+
+fn default_targets() -> Vec<ExternalTarget> {
+    vec![ExternalTarget::Client, ExternalTarget::Types]
+}
+
+fn default_variant() -> String {
+    "external".to_string()
+}
+
+/// A generator backed by an external command, registered under one language.
+struct ExternalGenerator {
+    name: String,
+    language: &'static str,
+    variant: &'static str,
+    command: Vec<String>,
+    root: PathBuf,
+}
+
+impl ExternalGenerator {
+    /// Run the command with `input` as JSON on stdin and return its stdout.
+    ///
+    /// `env` describes the request (target, root type name, options) so one command
+    /// can serve both subcommands.
+    fn run(&self, input: &serde_json::Value, env: &[EnvVar]) -> Result<String, String> {
+        let Some((program, args)) = self.command.split_first() else {
+            return Err("empty command".to_string());
+        };
+        // Input goes through a file rather than a pipe: moss runs with the default
+        // SIGPIPE action, so writing to a command that exits without reading its
+        // stdin would kill the process. The file is created exclusively and already
+        // unlinked, so nothing else can open or replace it.
+        let mut stdin =
+            tempfile::tempfile().map_err(|e| format!("failed to create input file: {}", e))?;
+        stdin
+            .write_all(input.to_string().as_bytes())
+            .and_then(|()| stdin.rewind())
+            .map_err(|e| format!("failed to write input file: {}", e))?;
+
+        let output = Command::new(program)
+            .args(args)
+            .current_dir(&self.root)
+            .env("MOSS_GENERATOR_LANGUAGE", self.language)
+            .envs(env.iter().map(|var| (var.name, var.value.as_str())))
+            .stdin(Stdio::from(stdin))
+            .output()
+            .map_err(|e| format!("failed to run {}: {}", program, e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let mut message = format!("{} exited with {}", program, output.status);
+            if !stderr.trim().is_empty() {
+                message.push_str(": ");
+                message.push_str(stderr.trim());
+            }
+            return Err(message);
+        }
+        String::from_utf8(output.stdout).map_err(|_| format!("{} wrote non-UTF-8 output", program))
+    }
+
+    fn generate(&self, input: &serde_json::Value, env: &[EnvVar]) -> Result<String, GenerateError> {
+        self.run(input, env).map_err(|message| GenerateError {
+            generator: self.name.clone(),
+            message,
+        })
+    }
+}
+
+/// An environment variable passed to an external generator.
+struct EnvVar {
+    name: &'static str,
+    value: String,
+}
+
+impl EnvVar {
+    fn new(name: &'static str, value: String) -> Self {
+        Self { name, value }
+    }
+}
+
+/// Environment describing client generation. Destructured so that a new
+/// option can't be added without deciding how it reaches external commands.
+fn client_env(options: &GenerateOptions) -> Vec<EnvVar> {
+    let GenerateOptions { with_headers, mock } = options;
+    vec![
+        EnvVar::new("MOSS_GENERATOR_TARGET", "client".to_string()),
+        EnvVar::new("MOSS_WITH_HEADERS", with_headers.to_string()),
+        EnvVar::new("MOSS_MOCK", mock.to_string()),
+    ]
+}
+
+impl rhizome_moss_openapi::OpenApiClientGenerator for ExternalGenerator {
+    fn language(&self) -> &'static str {
+        self.language
+    }
+
+    fn variant(&self) -> &'static str {
+        self.variant
+    }
+
+    fn generate(
+        &self,
+        spec: &serde_json::Value,
+        options: &GenerateOptions,
+    ) -> Result<String, GenerateError> {
+        ExternalGenerator::generate(self, spec, &client_env(options))
+    }
+}
+
+impl JsonSchemaGenerator for ExternalGenerator {
+    fn language(&self) -> &'static str {
+        self.language
+    }
+
+    fn generate(
+        &self,
+        schema: &serde_json::Value,
+        root_name: &str,
+    ) -> Result<String, GenerateError> {
+        let env = [
+            EnvVar::new("MOSS_GENERATOR_TARGET", "types".to_string()),
+            EnvVar::new("MOSS_TYPE_NAME", root_name.to_string()),
+        ];
+        ExternalGenerator::generate(self, schema, &env)
+    }
+}
+
+/// Load external generators from `.moss/generators.toml`.
+fn load_generators_config(root: &Path) -> Option<GeneratorsConfig> {
+    let config_path = root.join(".moss").join("generators.toml");
+    let content = std::fs::read_to_string(&config_path).ok()?;
+    match toml::from_str(&content) {
+        Ok(config) => Some(config),
+        Err(e) => {
+            eprintln!("Warning: failed to parse {}: {}", config_path.display(), e);
+            None
+        }
+    }
+}
+
+/// Register external generators ahead of the built-ins, so a configured
+/// language takes precedence over a built-in one of the same name.
+fn register_external_generators(root: &Path) {
+    let Some(config) = load_generators_config(root) else {
+        return;
+    };
+
+    // Registries hold &'static references; generators are registered once per process
+    let leak = |s: String| -> &'static str { Box::leak(s.into_boxed_str()) };
+
+    let mut names: Vec<_> = config.generator.into_iter().collect();
+    names.sort_by(|a, b| a.0.cmp(&b.0));
+    for (name, entry) in names {
+        let languages = if entry.languages.is_empty() {
+            vec![name.clone()]
+        } else {
+            entry.languages.clone()
+        };
+        let variant = leak(entry.variant.clone());
+        for language in languages {
+            let generator: &'static ExternalGenerator = Box::leak(Box::new(ExternalGenerator {
+                name: name.clone(),
+                language: leak(language.to_lowercase()),
+                variant,
+                command: entry.command.clone(),
+                root: root.to_path_buf(),
+            }));
+            if entry.targets.contains(&ExternalTarget::Client) {
+                rhizome_moss_openapi::register(generator);
+            }
+            if entry.targets.contains(&ExternalTarget::Types) {
+                rhizome_moss_jsonschema::register(generator);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generators_config_defaults() {
+        let config: GeneratorsConfig = toml::from_str(
+            r#"
+            [generator.elm]
+            command = ["elm-gen"]
+
+            [generator.kotlin]
+            command = ["kgen", "--stdin"]
+            languages = ["kotlin", "kt"]
+            targets = ["types"]
+            "#,
+        )
+        .unwrap();
+        let elm = &config.generator["elm"];
+        assert!(elm.languages.is_empty());
+        assert_eq!(elm.targets, default_targets());
+        assert_eq!(elm.variant, "external");
+        let kotlin = &config.generator["kotlin"];
+        assert_eq!(kotlin.languages, vec!["kotlin", "kt"]);
+        assert_eq!(kotlin.targets, vec![ExternalTarget::Types]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_external_generator_pipes_spec() {
+        let generator = ExternalGenerator {
+            name: "cat".to_string(),
+            language: "cat",
+            variant: "external",
+            command: vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                "cat; echo \" $MOSS_TYPE_NAME\"".to_string(),
+            ],
+            root: std::env::temp_dir(),
+        };
+        let code = generator
+            .run(
+                &serde_json::json!({"a": 1}),
+                &[EnvVar::new("MOSS_TYPE_NAME", "Root".to_string())],
+            )
+            .unwrap();
+        assert_eq!(code, "{\"a\":1} Root\n");
+
+        let failing = ExternalGenerator {
+            command: vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                "echo bad >&2; exit 3".to_string(),
+            ],
+            ..generator
+        };
+        let err = failing.run(&serde_json::json!({}), &[]).unwrap_err();
+        assert!(err.contains("bad"), "{}", err);
+
+        // Through the generator trait the failure is returned, not fatal
+        let err = rhizome_moss_openapi::OpenApiClientGenerator::generate(
+            &failing,
+            &serde_json::json!({}),
+            &GenerateOptions::default(),
+        )
+        .unwrap_err();
+        assert_eq!(err.generator, "cat");
+        assert!(err.message.contains("exited"), "{}", err.message);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_external_generator_receives_options() {
+        let generator = ExternalGenerator {
+            name: "env".to_string(),
+            language: "env",
+            variant: "external",
+            command: vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                "echo \"$MOSS_GENERATOR_TARGET $MOSS_WITH_HEADERS $MOSS_MOCK\"".to_string(),
+            ],
+            root: std::env::temp_dir(),
+        };
+        let options = GenerateOptions {
+            with_headers: false,
+            mock: true,
+        };
+        let code = rhizome_moss_openapi::OpenApiClientGenerator::generate(
+            &generator,
+            &serde_json::json!({}),
+            &options,
+        )
+        .unwrap();
+        assert_eq!(code, "client false true\n");
     }
 }
@@ -57,6 +57,30 @@ pub enum IndexAction {
         limit: usize,
     },
 
+    /// Look up raw symbol and call graph data (always JSON)
+    #[command(group(
+        clap::ArgGroup::new("query")
+            .required(true)
+            .args(["symbol", "callers", "callees", "references"])
+    ))]
+    Query {
+        /// Declarations of a symbol: file, kind, line range
+        #[arg(long)]
+        symbol: Option<String>,
+
+        /// Call sites of a symbol (Class.method for methods)
+        #[arg(long)]
+        callers: Option<String>,
+
+        /// Calls made by a symbol, as <file>:<name>
+        #[arg(long)]
+        callees: Option<String>,
+
+        /// Call sites and imports of a symbol
+        #[arg(long)]
+        references: Option<String>,
+    },
+
     /// Index external packages (stdlib, site-packages) into global cache
     Packages {
         /// Ecosystems to index (python, go, js, deno, java, cpp, rust). Defaults to all available.
@@ -81,6 +105,29 @@ pub fn cmd_index(action: IndexAction, root: Option<&Path>, json: bool) -> i32 {
         IndexAction::Packages { only, clear } => {
             rt.block_on(cmd_packages(&only, clear, root, json))
         }
+        IndexAction::Query {
+            symbol,
+            callers,
+            callees,
+            references,
+        } => {
+            let query = if let Some(name) = symbol {
+                IndexQuery::Symbol(name)
+            } else if let Some(name) = callers {
+                IndexQuery::Callers(name)
+            } else if let Some(target) = callees {
+                let Some(query) = parse_callees(&target) else {
+                    eprintln!("--callees expects <file>:<name>, got: {}", target);
+                    return 1;
+                };
+                query
+            } else if let Some(name) = references {
+                IndexQuery::References(name)
+            } else {
+                unreachable!("clap requires one query argument")
+            };
+            rt.block_on(cmd_query(root, &query))
+        }
     }
 }
 
@@ -340,6 +387,170 @@ async fn cmd_list_files(
     0
 }
 
+// =============================================================================
+// Query
+// =============================================================================
+
+/// A raw index lookup.
+#[derive(Debug, PartialEq)]
+enum IndexQuery {
+    Symbol(String),
+    Callers(String),
+    Callees { file: String, name: String },
+    References(String),
+}
+
+/// Parse a `--callees` target. The name follows the last `:`, so file paths may
+/// contain colons.
+fn parse_callees(target: &str) -> Option<IndexQuery> {
+    match target.rsplit_once(':') {
+        Some((file, name)) if !file.is_empty() && !name.is_empty() => Some(IndexQuery::Callees {
+            file: file.to_string(),
+            name: name.to_string(),
+        }),
+        _ => None,
+    }
+}
+
+#[derive(serde::Serialize)]
+struct SymbolRow {
+    file: String,
+    kind: String,
+    line: usize,
+    end_line: usize,
+}
+
+#[derive(serde::Serialize)]
+struct CallerRow {
+    file: String,
+    symbol: String,
+    line: usize,
+}
+
+#[derive(serde::Serialize)]
+struct CalleeRow {
+    name: String,
+    line: usize,
+}
+
+#[derive(serde::Serialize)]
+struct ReferenceRow {
+    /// "call" or "import"
+    kind: &'static str,
+    file: String,
+    line: usize,
+    /// Calling symbol, for calls
+    #[serde(skip_serializing_if = "Option::is_none")]
+    symbol: Option<String>,
+    /// Source module, for imports
+    #[serde(skip_serializing_if = "Option::is_none")]
+    module: Option<String>,
+}
+
+/// Print the result of a raw index lookup as a JSON array.
+///
+/// Output is JSON regardless of `--json`: this is the scripting interface to the
+/// index, so its shape doesn't depend on presentation flags.
+async fn cmd_query(root: Option<&Path>, query: &IndexQuery) -> i32 {
+    let root = root
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(|| std::env::current_dir().unwrap());
+
+    let Some(idx) = index::FileIndex::open_if_enabled(&root).await else {
+        eprintln!("Indexing disabled or failed. Run: moss index rebuild");
+        return 1;
+    };
+    if idx.count().await.unwrap_or(0) == 0 {
+        eprintln!("Index not built. Run: moss index rebuild");
+        return 1;
+    }
+    let stats = idx.call_graph_stats().await.unwrap_or_default();
+    let (built, what) = match query {
+        IndexQuery::Symbol(_) => (stats.symbols > 0, "Symbols"),
+        _ => (stats.calls > 0, "Call graph"),
+    };
+    if !built {
+        eprintln!("{} not indexed. Run: moss index rebuild", what);
+        return 1;
+    }
+
+    let output = match query {
+        IndexQuery::Symbol(name) => idx.find_symbol(name).await.map(|rows| {
+            let rows: Vec<SymbolRow> = rows
+                .into_iter()
+                .map(|(file, kind, line, end_line)| SymbolRow {
+                    file,
+                    kind,
+                    line,
+                    end_line,
+                })
+                .collect();
+            serde_json::to_string(&rows)
+        }),
+        IndexQuery::Callers(name) => idx.find_callers(name).await.map(|rows| {
+            let rows: Vec<CallerRow> = rows
+                .into_iter()
+                .map(|(file, symbol, line)| CallerRow { file, symbol, line })
+                .collect();
+            serde_json::to_string(&rows)
+        }),
+        IndexQuery::Callees { file, name } => idx.find_callees(file, name).await.map(|rows| {
+            let rows: Vec<CalleeRow> = rows
+                .into_iter()
+                .map(|(name, line)| CalleeRow { name, line })
+                .collect();
+            serde_json::to_string(&rows)
+        }),
+        IndexQuery::References(name) => find_references(&idx, name)
+            .await
+            .map(|rows| serde_json::to_string(&rows)),
+    };
+
+    match output {
+        Ok(json) => {
+            println!("{}", json.unwrap());
+            0
+        }
+        Err(e) => {
+            eprintln!("Index query failed: {}", e);
+            1
+        }
+    }
+}
+
+/// Call sites and imports of `name`, ordered by file and line.
+async fn find_references(
+    idx: &index::FileIndex,
+    name: &str,
+) -> Result<Vec<ReferenceRow>, libsql::Error> {
+    let mut rows: Vec<ReferenceRow> = idx
+        .find_callers(name)
+        .await?
+        .into_iter()
+        .map(|(file, symbol, line)| ReferenceRow {
+            kind: "call",
+            file,
+            line,
+            symbol: Some(symbol),
+            module: None,
+        })
+        .collect();
+    rows.extend(
+        idx.find_imports_of(name)
+            .await?
+            .into_iter()
+            .map(|import| ReferenceRow {
+                kind: "import",
+                file: import.file,
+                line: import.line,
+                symbol: None,
+                module: import.module,
+            }),
+    );
+    rows.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+    Ok(rows)
+}
+
 // =============================================================================
 // Packages
 // =============================================================================
@@ -685,3 +896,67 @@ fn format_size(bytes: u64) -> String {
         format!("{} B", bytes)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_callees() {
+        assert_eq!(
+            parse_callees("src/a.py:run"),
+            Some(IndexQuery::Callees {
+                file: "src/a.py".to_string(),
+                name: "run".to_string(),
+            })
+        );
+        // Only the last colon separates the name
+        assert_eq!(
+            parse_callees("C:/src/a.py:run"),
+            Some(IndexQuery::Callees {
+                file: "C:/src/a.py".to_string(),
+                name: "run".to_string(),
+            })
+        );
+        assert_eq!(parse_callees("run"), None);
+        assert_eq!(parse_callees(":run"), None);
+        assert_eq!(parse_callees("src/a.py:"), None);
+    }
+
+    #[tokio::test]
+    async fn test_find_references() {
+        if crate::parsers::parser_for("python").is_none() {
+            return;
+        }
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("a.py"), "def helper():\n    pass\n").unwrap();
+        fs::write(
+            dir.path().join("b.py"),
+            "from a import helper\n\ndef run():\n    helper()\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("c.py"), "def other():\n    helper()\n").unwrap();
+
+        let mut idx = index::FileIndex::open(dir.path()).await.unwrap();
+        idx.refresh().await.unwrap();
+        idx.refresh_call_graph().await.unwrap();
+
+        // Calls and imports are merged, ordered by file then line
+        let rows: Vec<(&str, String, usize)> = find_references(&idx, "helper")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|r| (r.kind, r.file, r.line))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("import", "b.py".to_string(), 1),
+                ("call", "b.py".to_string(), 4),
+                ("call", "c.py".to_string(), 2),
+            ]
+        );
+    }
+}
@@ -140,11 +140,14 @@ fn detect_todo_files(root: &Path) -> Vec<String> {
 const GITIGNORE_ENTRIES: &[&str] = &[
     ".moss/*",
     "!.moss/config.toml",
+    "!.moss/doc-refs-ignore",
     "!.moss/duplicate-functions-allow",
+    "!.moss/duplicate-functions-ignore",
     "!.moss/duplicate-types-allow",
     "!.moss/hotspots-allow",
     "!.moss/large-files-allow",
     "!.moss/memory/",
+    "!.moss/security-allow",
 ];
 
 /// Update .gitignore with moss entries. Returns list of changes made.
@@ -302,6 +305,13 @@ mod tests {
                 .iter()
                 .any(|l| l.trim() == "!.moss/duplicate-types-allow")
         );
+        assert!(lines.iter().any(|l| l.trim() == "!.moss/security-allow"));
+        assert!(
+            lines
+                .iter()
+                .any(|l| l.trim() == "!.moss/duplicate-functions-ignore")
+        );
+        assert!(lines.iter().any(|l| l.trim() == "!.moss/doc-refs-ignore"));
     }
 
     #[test]
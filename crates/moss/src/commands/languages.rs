@@ -0,0 +1,70 @@
+//! Languages command - show which analyses each supported language provides.
+
+use crate::output::{Table, terminal_width};
+use clap::Args;
+use rhizome_moss_languages::{Language, support_for_path, supported_languages};
+use std::path::PathBuf;
+
+/// Languages command arguments
+#[derive(Args)]
+pub struct LanguagesArgs {
+    /// Show only the language handling this file
+    pub path: Option<PathBuf>,
+}
+
+/// Run the languages command
+pub fn run(args: LanguagesArgs, json: bool) -> i32 {
+    let mut languages: Vec<&'static dyn Language> = match &args.path {
+        Some(path) => match support_for_path(path) {
+            Some(lang) => vec![lang],
+            None => {
+                eprintln!("No language support for: {}", path.display());
+                return 1;
+            }
+        },
+        None => supported_languages(),
+    };
+    languages.sort_by_key(|l| l.name().to_lowercase());
+
+    if json {
+        let output: Vec<_> = languages
+            .iter()
+            .map(|lang| {
+                let caps = lang.capabilities();
+                serde_json::json!({
+                    "name": lang.name(),
+                    "extensions": lang.extensions(),
+                    "symbols": caps.symbols,
+                    "call_graph": caps.call_graph,
+                    "docs": caps.docs,
+                    "complexity": caps.complexity,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&output).unwrap());
+        return 0;
+    }
+
+    let mark = |supported: bool| if supported { "yes" } else { "-" }.to_string();
+    let mut table = Table::new(&[
+        "Language",
+        "Symbols",
+        "Call graph",
+        "Docs",
+        "Complexity",
+        "Extensions",
+    ]);
+    for lang in &languages {
+        let caps = lang.capabilities();
+        table.row(vec![
+            lang.name().to_string(),
+            mark(caps.symbols),
+            mark(caps.call_graph),
+            mark(caps.docs),
+            mark(caps.complexity),
+            lang.extensions().join(", "),
+        ]);
+    }
+    print!("{}", table.render(terminal_width()));
+    0
+}
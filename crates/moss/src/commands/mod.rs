@@ -2,6 +2,7 @@
 
 pub mod aliases;
 pub mod analyze;
+pub mod config;
 pub mod context;
 pub mod daemon;
 pub mod edit;
@@ -10,6 +11,7 @@ pub mod grammars;
 pub mod history;
 pub mod index;
 pub mod init;
+pub mod languages;
 pub mod package;
 pub mod rules;
 pub mod sessions;
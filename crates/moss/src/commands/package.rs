@@ -4,8 +4,10 @@ use crate::output::OutputFormat;
 use clap::Subcommand;
 use nu_ansi_term::Color::Yellow;
 use rhizome_moss_packages::{
-    AuditResult, PackageError, PackageInfo, VulnerabilitySeverity, all_ecosystems,
-    detect_all_ecosystems,
+    AuditResult, Dependency, DependencyKind, Ecosystem, LockCheck, LockIssueKind, PackageError,
+    PackageInfo, PackageQuery, ReleaseNote, SearchResult, TreeOptions, Vulnerability,
+    VulnerabilitySeverity, all_ecosystems, cached_release_notes, cached_search,
+    detect_all_ecosystems, lock_check,
 };
 use std::path::Path;
 
@@ -15,11 +17,39 @@ pub enum PackageAction {
     Info {
         /// Package name to query (optionally with @version)
         package: String,
+
+        /// Show release notes between the installed (or given) version and the latest
+        #[arg(long)]
+        changelog: bool,
     },
     /// List declared dependencies from manifest
-    List,
+    List {
+        /// Only development dependencies
+        #[arg(long, conflicts_with_all = ["prod", "all"])]
+        dev: bool,
+
+        /// Only dependencies needed outside development (everything but dev)
+        #[arg(long, conflicts_with = "all")]
+        prod: bool,
+
+        /// All dependencies (default)
+        #[arg(long)]
+        all: bool,
+    },
     /// Show dependency tree from lockfile
-    Tree,
+    Tree {
+        /// Only expand this many levels of dependencies (1 = direct only)
+        #[arg(long)]
+        depth: Option<usize>,
+
+        /// Expand each package only at its first occurrence
+        #[arg(long)]
+        dedup: bool,
+
+        /// Stop expanding after this many seconds and show the partial tree
+        #[arg(long, value_name = "SECS")]
+        timeout: Option<u64>,
+    },
     /// Show why a dependency is in the tree
     Why {
         /// Package name to trace
@@ -27,8 +57,25 @@ pub enum PackageAction {
     },
     /// Show outdated packages (installed vs latest)
     Outdated,
-    /// Check for security vulnerabilities
-    Audit,
+    /// Check for security vulnerabilities (all detected ecosystems)
+    Audit {
+        /// Only report vulnerabilities at or above this severity (critical, high, medium, low)
+        #[arg(long, value_name = "LEVEL")]
+        severity: Option<VulnerabilitySeverity>,
+    },
+    /// Check that the lockfile agrees with the manifest (all detected ecosystems)
+    LockCheck,
+    /// Search the registry for packages by (partial) name
+    Search {
+        /// Search query
+        query: String,
+
+        /// Maximum number of results
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+    },
+    /// Print the JSON Schema of the package types used in JSON output
+    Schema,
 }
 
 pub fn cmd_package(
@@ -40,6 +87,11 @@ pub fn cmd_package(
     let project_root = root.unwrap_or(Path::new("."));
     let use_colors = format.use_colors();
 
+    // The schema is the same for every ecosystem
+    if let PackageAction::Schema = action {
+        return cmd_schema();
+    }
+
     // Get ecosystem either by name or by detection
     if let Some(name) = ecosystem {
         // Explicit ecosystem specified
@@ -61,10 +113,14 @@ pub fn cmd_package(
             return 1;
         }
 
-        // For list/tree, run for all detected ecosystems
+        // For list/tree/audit, run for all detected ecosystems
         // For info/outdated, use first ecosystem only
         match &action {
-            PackageAction::List | PackageAction::Tree => {
+            PackageAction::Audit { severity } => {
+                cmd_audit(&ecosystems, *severity, project_root, &format)
+            }
+            PackageAction::LockCheck => cmd_lock_check(&ecosystems, project_root, &format),
+            PackageAction::List { .. } | PackageAction::Tree { .. } => {
                 if format.is_json() && ecosystems.len() > 1 {
                     // Collect all results into a JSON array
                     run_all_ecosystems_json(&ecosystems, &action, project_root, &format)
@@ -105,16 +161,17 @@ fn run_all_ecosystems_json(
 
     for eco in ecosystems {
         match action {
-            PackageAction::List => match eco.list_dependencies(project_root) {
+            PackageAction::List { dev, prod, .. } => match eco.list_dependencies(project_root) {
                 Ok(deps) => {
+                    let filter = KindFilter::from_flags(*dev, *prod);
                     results.insert(
                         eco.name().to_string(),
                         serde_json::json!({
-                            "dependencies": deps.iter().map(|d| serde_json::json!({
-                                "name": d.name,
-                                "version_req": d.version_req,
-                                "optional": d.optional,
-                            })).collect::<Vec<_>>()
+                            "dependencies": deps
+                                .iter()
+                                .filter(|d| filter.matches(d.kind))
+                                .map(dependency_json)
+                                .collect::<Vec<_>>()
                         }),
                     );
                 }
@@ -127,7 +184,11 @@ fn run_all_ecosystems_json(
                     );
                 }
             },
-            PackageAction::Tree => match eco.dependency_tree(project_root) {
+            PackageAction::Tree {
+                depth,
+                dedup,
+                timeout,
+            } => match eco.dependency_tree(project_root, &tree_options(*depth, *dedup, *timeout)) {
                 Ok(tree) => {
                     results.insert(
                         eco.name().to_string(),
@@ -162,12 +223,105 @@ fn run_for_ecosystem(
     use_colors: bool,
 ) -> i32 {
     match action {
-        PackageAction::Info { package } => cmd_info(eco, package, project_root, format),
-        PackageAction::List => cmd_list(eco, project_root, format, use_colors),
-        PackageAction::Tree => cmd_tree(eco, project_root, format, use_colors),
+        PackageAction::Info {
+            package,
+            changelog: false,
+        } => cmd_info(eco, package, project_root, format),
+        PackageAction::Info {
+            package,
+            changelog: true,
+        } => cmd_changelog(eco, package, project_root, format),
+        PackageAction::List { dev, prod, .. } => cmd_list(
+            eco,
+            KindFilter::from_flags(*dev, *prod),
+            project_root,
+            format,
+            use_colors,
+        ),
+        PackageAction::Tree {
+            depth,
+            dedup,
+            timeout,
+        } => cmd_tree(
+            eco,
+            &tree_options(*depth, *dedup, *timeout),
+            project_root,
+            format,
+            use_colors,
+        ),
         PackageAction::Why { package } => cmd_why(eco, package, project_root, format, use_colors),
         PackageAction::Outdated => cmd_outdated(eco, project_root, format, use_colors),
-        PackageAction::Audit => cmd_audit(eco, project_root, format),
+        PackageAction::Audit { severity } => cmd_audit(&[eco], *severity, project_root, format),
+        PackageAction::LockCheck => cmd_lock_check(&[eco], project_root, format),
+        PackageAction::Search { query, limit } => cmd_search(eco, query, *limit, format),
+        PackageAction::Schema => cmd_schema(),
+    }
+}
+
+fn cmd_schema() -> i32 {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&rhizome_moss_packages::json_schema()).unwrap()
+    );
+    0
+}
+
+fn cmd_search(
+    eco: &dyn rhizome_moss_packages::Ecosystem,
+    query: &str,
+    limit: usize,
+    format: &OutputFormat,
+) -> i32 {
+    match cached_search(eco, query, limit) {
+        Ok(results) => {
+            if format.is_json() {
+                let value = serde_json::json!({
+                    "ecosystem": eco.name(),
+                    "query": query,
+                    "results": results,
+                });
+                print_json_value(&value, format);
+            } else if results.is_empty() {
+                println!("No {} packages matching '{}'", eco.name(), query);
+            } else {
+                print_search_results(&results, format.use_colors());
+            }
+            0
+        }
+        Err(PackageError::NotFound(_)) => {
+            eprintln!("error: {} does not support registry search", eco.name());
+            1
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            1
+        }
+    }
+}
+
+fn print_search_results(results: &[SearchResult], use_colors: bool) {
+    let name_width = results.iter().map(|r| r.name.len()).max().unwrap_or(0);
+    for result in results {
+        let version = result.version.as_deref().unwrap_or("");
+        let version_display = if use_colors {
+            Yellow.paint(version).to_string()
+        } else {
+            version.to_string()
+        };
+        let mut line = format!(
+            "{:<width$} {}",
+            result.name,
+            version_display,
+            width = name_width
+        );
+        if let Some(downloads) = result.downloads {
+            line.push_str(&format!(" ({} downloads)", downloads));
+        }
+        println!("{}", line.trim_end());
+        if let Some(desc) = result.description.as_deref().filter(|d| !d.is_empty()) {
+            let first_line = desc.lines().next().unwrap_or(desc);
+            println!("  {}", first_line);
+        }
     }
 }
 
@@ -204,36 +358,194 @@ fn cmd_info(
     }
 }
 
+fn cmd_changelog(
+    eco: &dyn rhizome_moss_packages::Ecosystem,
+    package: &str,
+    project_root: &Path,
+    format: &OutputFormat,
+) -> i32 {
+    let query = PackageQuery::parse(package);
+    let current = query
+        .version
+        .clone()
+        .or_else(|| eco.installed_version(&query.name, project_root));
+
+    let Some(tool) = eco.detect_tool(project_root) else {
+        eprintln!("error: no {} tools found in PATH", eco.name());
+        eprintln!("hint: install one of: {:?}", eco.tools());
+        return 1;
+    };
+    let latest_query = PackageQuery {
+        name: query.name.clone(),
+        version: None,
+    };
+    let latest = match eco.fetch_info(&latest_query, tool) {
+        Ok(info) => info,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return 1;
+        }
+    };
+
+    if current.as_deref() == Some(latest.version.as_str()) {
+        if format.is_json() {
+            let value = serde_json::json!({
+                "package": latest.name,
+                "ecosystem": eco.name(),
+                "from": current,
+                "to": latest.version,
+                "notes": [],
+            });
+            print_json_value(&value, format);
+        } else {
+            println!("{} {} is the latest version", latest.name, latest.version);
+        }
+        return 0;
+    }
+
+    let notes = match cached_release_notes(eco, &latest, current.as_deref(), &latest.version) {
+        Ok(notes) => notes,
+        Err(PackageError::NotFound(_)) => {
+            eprintln!(
+                "error: no release notes found for {} (repository: {})",
+                latest.name,
+                latest.repository.as_deref().unwrap_or("none")
+            );
+            return 1;
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return 1;
+        }
+    };
+
+    if format.is_json() {
+        let value = serde_json::json!({
+            "package": latest.name,
+            "ecosystem": eco.name(),
+            "from": current,
+            "to": latest.version,
+            "notes": notes,
+        });
+        print_json_value(&value, format);
+    } else {
+        print_release_notes(&latest, current.as_deref(), &notes, format.use_colors());
+    }
+    0
+}
+
+fn print_release_notes(
+    info: &PackageInfo,
+    from: Option<&str>,
+    notes: &[ReleaseNote],
+    use_colors: bool,
+) {
+    match from {
+        Some(from) => println!("{} {} -> {}", info.name, from, info.version),
+        None => println!("{} {}", info.name, info.version),
+    }
+    if notes.is_empty() {
+        println!();
+        println!("No release notes found between these versions.");
+        return;
+    }
+    for note in notes {
+        println!();
+        let heading = match &note.published {
+            Some(date) => format!(
+                "## {} ({})",
+                note.version,
+                date.split('T').next().unwrap_or(date)
+            ),
+            None => format!("## {}", note.version),
+        };
+        if use_colors {
+            println!("{}", Yellow.paint(heading));
+        } else {
+            println!("{}", heading);
+        }
+        if !note.body.is_empty() {
+            println!();
+            println!("{}", note.body);
+        }
+    }
+}
+
+/// Which dependencies `list` shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KindFilter {
+    All,
+    Dev,
+    Prod,
+}
+
+impl KindFilter {
+    fn from_flags(dev: bool, prod: bool) -> Self {
+        match (dev, prod) {
+            (true, _) => KindFilter::Dev,
+            (_, true) => KindFilter::Prod,
+            _ => KindFilter::All,
+        }
+    }
+
+    fn matches(self, kind: DependencyKind) -> bool {
+        match self {
+            KindFilter::All => true,
+            KindFilter::Dev => kind == DependencyKind::Dev,
+            KindFilter::Prod => kind != DependencyKind::Dev,
+        }
+    }
+}
+
+fn dependency_json(dep: &Dependency) -> serde_json::Value {
+    serde_json::json!({
+        "name": dep.name,
+        "version_req": dep.version_req,
+        "optional": dep.optional,
+        "kind": dep.kind.as_str(),
+    })
+}
+
 fn cmd_list(
     eco: &dyn rhizome_moss_packages::Ecosystem,
+    filter: KindFilter,
     project_root: &Path,
     format: &OutputFormat,
     use_colors: bool,
 ) -> i32 {
     match eco.list_dependencies(project_root) {
         Ok(deps) => {
+            let mut deps: Vec<Dependency> = deps
+                .into_iter()
+                .filter(|d| filter.matches(d.kind))
+                .collect();
             if format.is_json() {
                 let value = serde_json::json!({
                     "ecosystem": eco.name(),
-                    "dependencies": deps.iter().map(|d| serde_json::json!({
-                        "name": d.name,
-                        "version_req": d.version_req,
-                        "optional": d.optional,
-                    })).collect::<Vec<_>>()
+                    "dependencies": deps.iter().map(dependency_json).collect::<Vec<_>>()
                 });
                 print_json_value(&value, format);
             } else {
                 println!("{} dependencies ({})", deps.len(), eco.name());
-                println!();
-                for dep in &deps {
-                    let version = dep.version_req.as_deref().unwrap_or("*");
-                    let version_display = if use_colors {
-                        Yellow.paint(version).to_string()
-                    } else {
-                        version.to_string()
-                    };
-                    let optional = if dep.optional { " (optional)" } else { "" };
-                    println!("  {} {}{}", dep.name, version_display, optional);
+                // Grouped by kind, in manifest order within a group
+                deps.sort_by_key(|d| d.kind);
+                for group in deps.chunk_by(|a, b| a.kind == b.kind) {
+                    println!();
+                    println!("{} ({}):", group[0].kind.as_str(), group.len());
+                    for dep in group {
+                        let version = dep.version_req.as_deref().unwrap_or("*");
+                        let version_display = if use_colors {
+                            Yellow.paint(version).to_string()
+                        } else {
+                            version.to_string()
+                        };
+                        let optional = if dep.optional && dep.kind != DependencyKind::Optional {
+                            " (optional)"
+                        } else {
+                            ""
+                        };
+                        println!("  {} {}{}", dep.name, version_display, optional);
+                    }
                 }
             }
             0
@@ -245,13 +557,22 @@ fn cmd_list(
     }
 }
 
+fn tree_options(depth: Option<usize>, dedup: bool, timeout: Option<u64>) -> TreeOptions {
+    TreeOptions {
+        max_depth: depth,
+        dedup,
+        timeout: timeout.map(std::time::Duration::from_secs),
+    }
+}
+
 fn cmd_tree(
     eco: &dyn rhizome_moss_packages::Ecosystem,
+    options: &TreeOptions,
     project_root: &Path,
     format: &OutputFormat,
     use_colors: bool,
 ) -> i32 {
-    match eco.dependency_tree(project_root) {
+    match eco.dependency_tree(project_root, options) {
         Ok(tree) => {
             if format.is_json() {
                 let value = serde_json::json!({
@@ -261,6 +582,9 @@ fn cmd_tree(
                 print_json_value(&value, format);
             } else {
                 print_tree(&tree, use_colors);
+                if tree.truncated {
+                    eprintln!("note: timeout reached, some dependencies were not expanded");
+                }
             }
             0
         }
@@ -301,7 +625,7 @@ fn cmd_why(
     format: &OutputFormat,
     use_colors: bool,
 ) -> i32 {
-    match eco.dependency_tree(project_root) {
+    match eco.dependency_tree(project_root, &TreeOptions::default()) {
         Ok(tree) => {
             let paths = find_dependency_paths(&tree, package);
 
@@ -492,76 +816,262 @@ fn cmd_outdated(
     0
 }
 
-fn cmd_audit(
-    eco: &dyn rhizome_moss_packages::Ecosystem,
+fn cmd_lock_check(
+    ecosystems: &[&dyn Ecosystem],
     project_root: &Path,
     format: &OutputFormat,
 ) -> i32 {
-    match eco.audit(project_root) {
-        Ok(result) => {
-            if format.is_json() {
-                let value = serde_json::to_value(&result).unwrap_or_default();
-                print_json_value(&value, format);
-            } else {
-                print_audit_human(&result, eco.name());
+    let mut results = Vec::new();
+    let mut failed = false;
+    for eco in ecosystems {
+        match lock_check(*eco, project_root) {
+            Ok(check) => {
+                failed |= check.lockfile.is_none() || !check.issues.is_empty();
+                results.push((eco, Ok(check)));
             }
-            if result.vulnerabilities.iter().any(|v| {
-                matches!(
-                    v.severity,
-                    VulnerabilitySeverity::Critical | VulnerabilitySeverity::High
-                )
-            }) {
-                1 // Exit with error if high/critical vulnerabilities found
-            } else {
-                0
+            Err(e) => {
+                failed = true;
+                results.push((eco, Err(e.to_string())));
             }
         }
-        Err(e) => {
-            eprintln!("error: {}", e);
-            1
+    }
+
+    if format.is_json() {
+        let value: Vec<_> = results
+            .iter()
+            .map(|(eco, result)| match result {
+                Ok(check) => {
+                    let mut value = serde_json::to_value(check).unwrap_or_default();
+                    value["ecosystem"] = eco.name().into();
+                    value
+                }
+                Err(e) => serde_json::json!({"ecosystem": eco.name(), "error": e}),
+            })
+            .collect();
+        print_json_value(&serde_json::json!({ "ecosystems": value }), format);
+    } else {
+        for (eco, result) in &results {
+            match result {
+                Ok(check) => print_lock_check(**eco, check, format.use_colors()),
+                Err(e) => eprintln!("error: {}: {}", eco.name(), e),
+            }
         }
     }
+
+    if failed { 1 } else { 0 }
 }
 
-fn print_audit_human(result: &AuditResult, ecosystem: &str) {
-    if result.vulnerabilities.is_empty() {
-        println!("No vulnerabilities found ({}).", ecosystem);
+fn print_lock_check(eco: &dyn Ecosystem, check: &LockCheck, use_colors: bool) {
+    let Some(lockfile) = &check.lockfile else {
+        let expected: Vec<_> = eco.lockfiles().iter().map(|l| l.filename).collect();
+        println!(
+            "{}: no lockfile (expected {})",
+            eco.name(),
+            expected.join(" or ")
+        );
+        return;
+    };
+    if check.issues.is_empty() {
+        println!(
+            "{} ({}): {} dependencies match",
+            eco.name(),
+            lockfile,
+            check.checked
+        );
         return;
     }
+    println!(
+        "{} ({}): {} of {} dependencies disagree with the lockfile",
+        eco.name(),
+        lockfile,
+        check.issues.len(),
+        check.checked
+    );
+    for issue in &check.issues {
+        let req = issue.version_req.as_deref().unwrap_or("*");
+        let (label, detail) = match issue.kind {
+            LockIssueKind::Missing => ("missing", format!("{} {}", issue.name, req)),
+            LockIssueKind::Unsatisfied => (
+                "unsatisfied",
+                format!(
+                    "{} {} (locked {})",
+                    issue.name,
+                    req,
+                    issue.locked.as_deref().unwrap_or("?")
+                ),
+            ),
+        };
+        let label = format!("{:<11}", label);
+        if use_colors {
+            println!("  {} {}", Yellow.paint(label), detail);
+        } else {
+            println!("  {} {}", label, detail);
+        }
+    }
+}
 
-    let critical = result
-        .vulnerabilities
-        .iter()
-        .filter(|v| v.severity == VulnerabilitySeverity::Critical)
-        .count();
-    let high = result
-        .vulnerabilities
-        .iter()
-        .filter(|v| v.severity == VulnerabilitySeverity::High)
-        .count();
-    let medium = result
-        .vulnerabilities
-        .iter()
-        .filter(|v| v.severity == VulnerabilitySeverity::Medium)
-        .count();
-    let low = result
-        .vulnerabilities
+/// A vulnerability and the ecosystems that reported it.
+struct AuditFinding {
+    vulnerability: Vulnerability,
+    ecosystems: Vec<&'static str>,
+}
+
+fn cmd_audit(
+    ecosystems: &[&dyn Ecosystem],
+    min_severity: Option<VulnerabilitySeverity>,
+    project_root: &Path,
+    format: &OutputFormat,
+) -> i32 {
+    let mut results = Vec::new();
+    let mut errors = Vec::new();
+    for eco in ecosystems {
+        match eco.audit(project_root) {
+            Ok(result) => results.push((eco.name(), result)),
+            Err(e) => {
+                eprintln!("error: {} audit failed: {}", eco.name(), e);
+                errors.push((eco.name(), e.to_string()));
+            }
+        }
+    }
+
+    let mut findings = merge_audits(&results);
+    if let Some(min) = min_severity {
+        findings.retain(|f| f.vulnerability.severity <= min);
+    }
+
+    if format.is_json() {
+        print_json_value(&audit_json(&findings, &results, &errors), format);
+    } else {
+        let names: Vec<_> = ecosystems.iter().map(|e| e.name()).collect();
+        print_audit_human(&findings, &names);
+    }
+
+    // Fail on high/critical findings, and when an ecosystem couldn't be audited at all
+    let blocking = findings
         .iter()
-        .filter(|v| v.severity == VulnerabilitySeverity::Low)
-        .count();
+        .any(|f| f.vulnerability.severity <= VulnerabilitySeverity::High);
+    if blocking || !errors.is_empty() { 1 } else { 0 }
+}
+
+/// Merge audit results, most severe first. A vulnerability reported by several
+/// ecosystems (same CVE, package and version) is listed once.
+fn merge_audits(results: &[(&'static str, AuditResult)]) -> Vec<AuditFinding> {
+    let mut findings: Vec<AuditFinding> = Vec::new();
+    for (ecosystem, result) in results {
+        for vuln in &result.vulnerabilities {
+            let existing = vuln.cve.as_ref().and_then(|cve| {
+                findings.iter_mut().find(|f| {
+                    f.vulnerability.cve.as_ref() == Some(cve)
+                        && f.vulnerability.package == vuln.package
+                        && f.vulnerability.version == vuln.version
+                })
+            });
+            match existing {
+                Some(finding) => {
+                    let merged = &mut finding.vulnerability;
+                    merged.severity = merged.severity.min(vuln.severity);
+                    if merged.fixed_in.is_none() {
+                        merged.fixed_in = vuln.fixed_in.clone();
+                    }
+                    if merged.url.is_none() {
+                        merged.url = vuln.url.clone();
+                    }
+                    if !finding.ecosystems.contains(ecosystem) {
+                        finding.ecosystems.push(ecosystem);
+                    }
+                }
+                None => findings.push(AuditFinding {
+                    vulnerability: vuln.clone(),
+                    ecosystems: vec![ecosystem],
+                }),
+            }
+        }
+    }
+    findings.sort_by(|a, b| {
+        let (a, b) = (&a.vulnerability, &b.vulnerability);
+        a.severity
+            .cmp(&b.severity)
+            .then_with(|| a.package.cmp(&b.package))
+            .then_with(|| a.version.cmp(&b.version))
+    });
+    findings
+}
+
+const SEVERITIES: [VulnerabilitySeverity; 5] = [
+    VulnerabilitySeverity::Critical,
+    VulnerabilitySeverity::High,
+    VulnerabilitySeverity::Medium,
+    VulnerabilitySeverity::Low,
+    VulnerabilitySeverity::Unknown,
+];
+
+/// Summary counts, plus findings grouped by ecosystem and then severity.
+fn audit_json(
+    findings: &[AuditFinding],
+    results: &[(&'static str, AuditResult)],
+    errors: &[(&'static str, String)],
+) -> serde_json::Value {
+    let mut summary = serde_json::Map::new();
+    summary.insert("total".to_string(), findings.len().into());
+    for severity in SEVERITIES {
+        let count = findings
+            .iter()
+            .filter(|f| f.vulnerability.severity == severity)
+            .count();
+        summary.insert(severity.as_str().to_string(), count.into());
+    }
+
+    let mut by_ecosystem = serde_json::Map::new();
+    for (ecosystem, _) in results {
+        let mut by_severity = serde_json::Map::new();
+        for finding in findings.iter().filter(|f| f.ecosystems.contains(ecosystem)) {
+            let mut value = serde_json::to_value(&finding.vulnerability).unwrap_or_default();
+            value["ecosystems"] = serde_json::json!(finding.ecosystems);
+            by_severity
+                .entry(finding.vulnerability.severity.as_str())
+                .or_insert_with(|| serde_json::json!([]))
+                .as_array_mut()
+                .unwrap()
+                .push(value);
+        }
+        by_ecosystem.insert(ecosystem.to_string(), by_severity.into());
+    }
+    for (ecosystem, error) in errors {
+        by_ecosystem.insert(ecosystem.to_string(), serde_json::json!({ "error": error }));
+    }
 
+    serde_json::json!({
+        "summary": summary,
+        "ecosystems": by_ecosystem,
+    })
+}
+
+fn print_audit_human(findings: &[AuditFinding], ecosystems: &[&str]) {
+    let ecosystems_label = ecosystems.join(", ");
+    if findings.is_empty() {
+        println!("No vulnerabilities found ({}).", ecosystems_label);
+        return;
+    }
+
+    let count = |severity: VulnerabilitySeverity| {
+        findings
+            .iter()
+            .filter(|f| f.vulnerability.severity == severity)
+            .count()
+    };
     println!(
         "Found {} vulnerabilities ({}) - {} critical, {} high, {} medium, {} low",
-        result.vulnerabilities.len(),
-        ecosystem,
-        critical,
-        high,
-        medium,
-        low
+        findings.len(),
+        ecosystems_label,
+        count(VulnerabilitySeverity::Critical),
+        count(VulnerabilitySeverity::High),
+        count(VulnerabilitySeverity::Medium),
+        count(VulnerabilitySeverity::Low)
     );
     println!();
 
-    for vuln in &result.vulnerabilities {
+    for finding in findings {
+        let vuln = &finding.vulnerability;
         let severity = vuln.severity.as_str();
         println!(
             "[{}] {} {} - {}",
@@ -571,6 +1081,9 @@ fn print_audit_human(result: &AuditResult, ecosystem: &str) {
             vuln.title
         );
 
+        if ecosystems.len() > 1 {
+            println!("  Ecosystem: {}", finding.ecosystems.join(", "));
+        }
         if let Some(cve) = &vuln.cve {
             println!("  CVE: {}", cve);
         }
@@ -595,7 +1108,10 @@ fn available_ecosystems() -> Vec<&'static str> {
 /// Print a JSON value, applying jq filter if specified.
 fn print_json_value(value: &serde_json::Value, format: &OutputFormat) {
     match format {
-        OutputFormat::Compact | OutputFormat::Pretty { .. } => {
+        OutputFormat::Compact
+        | OutputFormat::Pretty { .. }
+        | OutputFormat::Csv
+        | OutputFormat::JsonLines => {
             unreachable!("print_json_value called with non-JSON format")
         }
         OutputFormat::Json => println!("{}", value),
@@ -615,7 +1131,10 @@ fn print_json_value(value: &serde_json::Value, format: &OutputFormat) {
 /// Print package info in the specified format.
 fn print_package_info(info: &PackageInfo, ecosystem: &str, format: &OutputFormat) {
     match format {
-        OutputFormat::Compact | OutputFormat::Pretty { .. } => print_human(info, ecosystem),
+        OutputFormat::Compact
+        | OutputFormat::Pretty { .. }
+        | OutputFormat::Csv
+        | OutputFormat::JsonLines => print_human(info, ecosystem),
         OutputFormat::Json | OutputFormat::Jq(_) => {
             let value = serde_json::to_value(info).unwrap_or_default();
             print_json_value(&value, format);
@@ -644,6 +1163,15 @@ fn print_human(info: &PackageInfo, ecosystem: &str) {
         println!("repository: {}", repo);
     }
 
+    if let Some(published) = &info.published {
+        // Date only; JSON output keeps the full timestamp
+        println!("published: {}", published.get(..10).unwrap_or(published));
+    }
+
+    if let Some(downloads) = info.downloads {
+        println!("downloads: {}", downloads);
+    }
+
     if !info.features.is_empty() {
         println!();
         println!("features:");
@@ -666,3 +1194,74 @@ fn print_human(info: &PackageInfo, ecosystem: &str) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vuln(package: &str, severity: VulnerabilitySeverity, cve: Option<&str>) -> Vulnerability {
+        Vulnerability {
+            package: package.to_string(),
+            version: "1.0.0".to_string(),
+            severity,
+            title: format!("{} issue", package),
+            url: None,
+            cve: cve.map(String::from),
+            fixed_in: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_audits() {
+        let npm = AuditResult {
+            vulnerabilities: vec![
+                vuln(
+                    "lodash",
+                    VulnerabilitySeverity::Medium,
+                    Some("CVE-2021-23337"),
+                ),
+                vuln("minimist", VulnerabilitySeverity::Low, None),
+            ],
+        };
+        let mut duplicate = vuln(
+            "lodash",
+            VulnerabilitySeverity::High,
+            Some("CVE-2021-23337"),
+        );
+        duplicate.fixed_in = Some("4.17.21".to_string());
+        let deno = AuditResult {
+            vulnerabilities: vec![
+                duplicate,
+                vuln("minimist", VulnerabilitySeverity::Low, None),
+            ],
+        };
+
+        let findings = merge_audits(&[("npm", npm), ("deno", deno)]);
+        let summary: Vec<_> = findings
+            .iter()
+            .map(|f| (f.vulnerability.package.as_str(), f.ecosystems.clone()))
+            .collect();
+        // Without a CVE there's nothing to match on, so both minimist reports stay
+        assert_eq!(
+            summary,
+            vec![
+                ("lodash", vec!["npm", "deno"]),
+                ("minimist", vec!["npm"]),
+                ("minimist", vec!["deno"]),
+            ]
+        );
+        let lodash = &findings[0].vulnerability;
+        assert_eq!(lodash.severity, VulnerabilitySeverity::High);
+        assert_eq!(lodash.fixed_in.as_deref(), Some("4.17.21"));
+    }
+
+    #[test]
+    fn test_severity_order() {
+        assert!(VulnerabilitySeverity::Critical < VulnerabilitySeverity::High);
+        assert_eq!(
+            "moderate".parse::<VulnerabilitySeverity>(),
+            Ok(VulnerabilitySeverity::Medium)
+        );
+        assert!("severe".parse::<VulnerabilitySeverity>().is_err());
+    }
+}
@@ -73,6 +73,7 @@ pub fn cmd_sessions_analyze_multi(
                         .or_insert_with(|| ToolStats::new(&k));
                     stat.calls += v.calls;
                     stat.errors += v.errors;
+                    stat.merge_failures(v.failures);
                 }
 
                 // Aggregate token stats
@@ -125,8 +126,18 @@ pub fn cmd_sessions_analyze_multi(
     0
 }
 
-/// Apply jq filter to each line of a JSONL file.
-pub fn cmd_sessions_jq(path: &Path, filter: &str) -> i32 {
+/// How [`cmd_sessions_jq`] feeds JSONL records to the filter.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JqInput<'a> {
+    /// Run the filter once, on an array of every record (like `jq --slurp`)
+    pub slurp: bool,
+    /// Skip lines whose raw text doesn't contain this, without parsing them
+    pub raw_contains: Option<&'a str>,
+}
+
+/// Apply a jq filter to each line of the JSONL files, or once to all of their
+/// records with [`JqInput::slurp`].
+pub fn cmd_sessions_jq(paths: &[PathBuf], filter: &str, input: JqInput) -> i32 {
     use jaq_core::load::{Arena, File as JaqFile, Loader};
     use jaq_core::{Compiler, Ctx, RcIter};
     use jaq_json::Val;
@@ -165,37 +176,9 @@ pub fn cmd_sessions_jq(path: &Path, filter: &str) -> i32 {
         }
     };
 
-    // Process each line
-    let file = match File::open(path) {
-        Ok(f) => f,
-        Err(e) => {
-            eprintln!("Failed to open {}: {}", path.display(), e);
-            return 1;
-        }
-    };
-
-    let reader = BufReader::new(file);
     let stdout = std::io::stdout();
     let mut stdout = stdout.lock();
-
-    for line in reader.lines() {
-        let line = match line {
-            Ok(l) => l,
-            Err(e) => {
-                eprintln!("Read error: {}", e);
-                return 1;
-            }
-        };
-
-        if line.trim().is_empty() {
-            continue;
-        }
-
-        let json_val: serde_json::Value = match serde_json::from_str(&line) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-
+    let run = |json_val: serde_json::Value| {
         let val = Val::from(json_val);
         let inputs = RcIter::new(core::iter::empty());
         let out = filter_compiled.run((Ctx::new([], &inputs), val));
@@ -210,7 +193,120 @@ pub fn cmd_sessions_jq(path: &Path, filter: &str) -> i32 {
                 }
             }
         }
+    };
+
+    for_each_jq_input(paths, input, run)
+}
+
+/// Pass each record of the JSONL files to `run`, or all of them at once as an
+/// array with [`JqInput::slurp`]. Unreadable files are reported to stderr and
+/// make the exit code 1.
+fn for_each_jq_input(
+    paths: &[PathBuf],
+    input: JqInput,
+    mut run: impl FnMut(serde_json::Value),
+) -> i32 {
+    let mut exit_code = 0;
+    let mut slurped = Vec::new();
+    for path in paths {
+        let result = for_each_record(path, input.raw_contains, |record| {
+            if input.slurp {
+                slurped.push(record);
+            } else {
+                run(record);
+            }
+        });
+        if let Err(e) = result {
+            eprintln!("{}", e);
+            exit_code = 1;
+        }
+    }
+    if input.slurp {
+        run(serde_json::Value::Array(slurped));
     }
 
-    0
+    exit_code
+}
+
+/// Parse each JSONL line of `path` and pass it to `f`. Blank and malformed lines
+/// are skipped, as are lines not containing `raw_contains`.
+fn for_each_record(
+    path: &Path,
+    raw_contains: Option<&str>,
+    mut f: impl FnMut(serde_json::Value),
+) -> Result<(), String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| format!("Read error: {}", e))?;
+
+        if line.trim().is_empty() || raw_contains.is_some_and(|text| !line.contains(text)) {
+            continue;
+        }
+
+        if let Ok(json_val) = serde_json::from_str(&line) {
+            f(json_val);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_for_each_record_raw_contains() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("a.jsonl");
+        std::fs::write(
+            &path,
+            "{\"type\":\"user\"}\n\n{\"type\":\"assistant\"}\nnot json user\n{\"type\":\"user\",\"n\":2}\n",
+        )
+        .unwrap();
+
+        let mut records = Vec::new();
+        for_each_record(&path, Some("user"), |r| records.push(r)).unwrap();
+        assert_eq!(
+            records,
+            vec![json!({"type": "user"}), json!({"type": "user", "n": 2})]
+        );
+
+        let mut all = Vec::new();
+        for_each_record(&path, None, |r| all.push(r)).unwrap();
+        assert_eq!(all.len(), 3);
+    }
+
+    #[test]
+    fn test_for_each_jq_input_slurp() {
+        let dir = tempdir().unwrap();
+        let a = dir.path().join("a.jsonl");
+        let b = dir.path().join("b.jsonl");
+        std::fs::write(&a, "{\"n\":1}\n{\"n\":2}\n").unwrap();
+        std::fs::write(&b, "{\"n\":3}\n").unwrap();
+        let paths = [a, b];
+
+        let mut inputs = Vec::new();
+        let input = JqInput {
+            slurp: true,
+            raw_contains: None,
+        };
+        assert_eq!(for_each_jq_input(&paths, input, |v| inputs.push(v)), 0);
+        // One array holding the records of every file
+        assert_eq!(inputs, vec![json!([{"n": 1}, {"n": 2}, {"n": 3}])]);
+
+        let mut inputs = Vec::new();
+        let input = JqInput {
+            slurp: false,
+            raw_contains: Some("3"),
+        };
+        assert_eq!(for_each_jq_input(&paths, input, |v| inputs.push(v)), 0);
+        assert_eq!(inputs, vec![json!({"n": 3})]);
+
+        let missing = [dir.path().join("missing.jsonl")];
+        assert_eq!(for_each_jq_input(&missing, JqInput::default(), |_| {}), 1);
+    }
 }
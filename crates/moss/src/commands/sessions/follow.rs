@@ -0,0 +1,257 @@
+//! Live-follow a session log (`sessions show --follow`).
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::time::Duration;
+
+/// How often the session file is checked for appended lines.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Longest text excerpt in a summary line.
+const SUMMARY_WIDTH: usize = 100;
+
+/// What to print for each record.
+#[derive(Debug, Clone, Copy)]
+pub enum FollowOutput<'a> {
+    /// The JSONL line as-is
+    Raw,
+    /// Results of a jq filter applied to the record
+    Jq(&'a str),
+    /// One `role: text` line per message record
+    Summary,
+}
+
+/// Print the session's existing lines, then keep printing lines as they are
+/// appended, like `tail -f`. Runs until interrupted (Ctrl-C).
+///
+/// If the file is truncated or replaced (e.g. rotated), it is reopened and
+/// printed from the start.
+pub fn cmd_sessions_follow(path: &Path, output: FollowOutput) -> i32 {
+    if let FollowOutput::Jq(filter) = output
+        && let Err(e) = crate::output::apply_jq(&serde_json::Value::Null, filter)
+    {
+        eprintln!("jq error: {}", e);
+        return 1;
+    }
+
+    let mut file = match FollowedFile::open(path) {
+        Ok(f) => f,
+        Err(e) => {
+            eprintln!("Failed to open {}: {}", path.display(), e);
+            return 1;
+        }
+    };
+    eprintln!("Following {} (Ctrl+C to stop)", path.display());
+
+    let stdout = std::io::stdout();
+    let mut line = String::new();
+    loop {
+        match file.reader.read_line(&mut line) {
+            // A line without its newline is still being written
+            Ok(n) if n > 0 && line.ends_with('\n') => {
+                file.pos += n as u64;
+                let mut stdout = stdout.lock();
+                print_record(&mut stdout, line.trim_end_matches(['\n', '\r']), output);
+                let _ = stdout.flush();
+                line.clear();
+                continue;
+            }
+            Ok(n) => file.pos += n as u64,
+            Err(e) => {
+                eprintln!("Read error: {}", e);
+                return 1;
+            }
+        }
+
+        std::thread::sleep(POLL_INTERVAL);
+        if file.was_replaced(path) {
+            match FollowedFile::open(path) {
+                Ok(reopened) => {
+                    eprintln!("{} was replaced; following the new file", path.display());
+                    file = reopened;
+                    line.clear();
+                }
+                // Mid-rotation: the new file may not exist yet
+                Err(_) => continue,
+            }
+        }
+    }
+}
+
+/// An open session file and how far it has been read.
+struct FollowedFile {
+    reader: BufReader<File>,
+    pos: u64,
+    id: Option<u64>,
+}
+
+impl FollowedFile {
+    fn open(path: &Path) -> std::io::Result<Self> {
+        let file = File::open(path)?;
+        let id = file_id(&file.metadata()?);
+        Ok(Self {
+            reader: BufReader::new(file),
+            pos: 0,
+            id,
+        })
+    }
+
+    /// Whether the path now names another file, or the file was truncated.
+    fn was_replaced(&self, path: &Path) -> bool {
+        let Ok(meta) = std::fs::metadata(path) else {
+            return false;
+        };
+        meta.len() < self.pos || file_id(&meta) != self.id
+    }
+}
+
+#[cfg(unix)]
+fn file_id(meta: &std::fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(meta.ino())
+}
+
+/// Without inode numbers only truncation is detected.
+#[cfg(not(unix))]
+fn file_id(_meta: &std::fs::Metadata) -> Option<u64> {
+    None
+}
+
+fn print_record(out: &mut impl Write, line: &str, output: FollowOutput) {
+    if line.trim().is_empty() {
+        return;
+    }
+    match output {
+        FollowOutput::Raw => {
+            let _ = writeln!(out, "{}", line);
+        }
+        FollowOutput::Jq(filter) => {
+            let Ok(value) = serde_json::from_str::<serde_json::Value>(line) else {
+                return;
+            };
+            match crate::output::apply_jq(&value, filter) {
+                Ok(results) => {
+                    for result in results {
+                        let _ = writeln!(out, "{}", result);
+                    }
+                }
+                Err(e) => eprintln!("jq error: {}", e),
+            }
+        }
+        FollowOutput::Summary => {
+            if let Some(summary) = serde_json::from_str(line)
+                .ok()
+                .and_then(|v| summarize_record(&v))
+            {
+                let _ = writeln!(out, "{}", summary);
+            }
+        }
+    }
+}
+
+/// One-line summary of a message record: its role, then its text and tool calls.
+///
+/// Works on the raw JSON rather than a parsed session, so it only knows the
+/// common shapes: a `message`/`payload` wrapper (Claude, Codex) or a bare
+/// message, with string content or a list of content blocks. Other records
+/// (metadata, snapshots) give None.
+pub fn summarize_record(record: &serde_json::Value) -> Option<String> {
+    let message = record
+        .get("message")
+        .or_else(|| record.get("payload"))
+        .filter(|m| m.is_object())
+        .unwrap_or(record);
+    let role = message
+        .get("role")
+        .or_else(|| record.get("type"))
+        .and_then(|r| r.as_str())?;
+
+    let mut parts: Vec<String> = Vec::new();
+    match message.get("content") {
+        Some(serde_json::Value::String(text)) => parts.push(text.clone()),
+        Some(serde_json::Value::Array(blocks)) => {
+            for block in blocks {
+                let kind = block.get("type").and_then(|t| t.as_str()).unwrap_or("");
+                if let Some(text) = block.get("text").and_then(|t| t.as_str()) {
+                    parts.push(text.to_string());
+                } else if let Some(name) = block.get("name").and_then(|n| n.as_str()) {
+                    parts.push(format!("[{}]", name));
+                } else if !kind.is_empty() {
+                    parts.push(format!("[{}]", kind));
+                }
+            }
+        }
+        _ => {}
+    }
+    // Codex records tool calls as their own payloads
+    if parts.is_empty()
+        && let Some(name) = message.get("name").and_then(|n| n.as_str())
+    {
+        parts.push(format!("[{}]", name));
+    }
+    if parts.is_empty() {
+        return None;
+    }
+
+    let text = parts
+        .join(" ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let excerpt = match text.char_indices().nth(SUMMARY_WIDTH) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => text,
+    };
+    Some(format!("{}: {}", role, excerpt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_record() {
+        let claude = serde_json::json!({
+            "type": "assistant",
+            "message": {"role": "assistant", "content": [
+                {"type": "text", "text": "Reading the\nconfig."},
+                {"type": "tool_use", "name": "Read", "input": {}}
+            ]}
+        });
+        assert_eq!(
+            summarize_record(&claude).as_deref(),
+            Some("assistant: Reading the config. [Read]")
+        );
+
+        let codex_call = serde_json::json!({
+            "type": "response_item",
+            "payload": {"type": "function_call", "name": "shell", "arguments": "{}"}
+        });
+        assert_eq!(
+            summarize_record(&codex_call).as_deref(),
+            Some("response_item: [shell]")
+        );
+
+        let long = serde_json::json!({"role": "user", "content": "x".repeat(150)});
+        let summary = summarize_record(&long).unwrap();
+        assert_eq!(summary, format!("user: {}...", "x".repeat(SUMMARY_WIDTH)));
+
+        let meta = serde_json::json!({"type": "summary", "leafUuid": "abc"});
+        assert_eq!(summarize_record(&meta), None);
+    }
+
+    #[test]
+    fn test_print_record() {
+        let line = r#"{"role": "user", "content": "hi"}"#;
+        let mut out = Vec::new();
+        print_record(&mut out, line, FollowOutput::Raw);
+        print_record(&mut out, line, FollowOutput::Jq(".content"));
+        print_record(&mut out, line, FollowOutput::Summary);
+        print_record(&mut out, "", FollowOutput::Raw);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!("{}\n\"hi\"\nuser: hi\n", line)
+        );
+    }
+}
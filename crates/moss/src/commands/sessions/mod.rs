@@ -1,6 +1,7 @@
 //! Sessions command - analyze Claude Code and other agent session logs.
 
 pub mod analyze;
+pub mod follow;
 pub mod list;
 pub mod plans;
 #[cfg(feature = "sessions-web")]
@@ -14,6 +15,7 @@ pub use serve::cmd_sessions_serve;
 pub use show::cmd_sessions_show;
 pub use stats::cmd_sessions_stats;
 
+use analyze::JqInput;
 use clap::{Args, Subcommand};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
@@ -143,9 +145,27 @@ pub enum SessionsCommand {
         #[arg(long)]
         jq: Option<String>,
 
+        /// Run the jq filter once on an array of all records (across all matched
+        /// sessions), for totals and grouping
+        #[arg(long, requires = "jq", conflicts_with = "follow")]
+        slurp: bool,
+
+        /// Only parse lines whose raw text contains TEXT before applying --jq
+        /// (cheap pre-filter for large logs)
+        #[arg(long, value_name = "TEXT", requires = "jq", conflicts_with = "follow")]
+        jq_raw: Option<String>,
+
         /// Run full analysis instead of dumping raw log
         #[arg(short, long)]
         analyze: bool,
+
+        /// Keep printing lines as they are appended (like `tail -f`)
+        #[arg(short, long, conflicts_with = "analyze")]
+        follow: bool,
+
+        /// Print a one-line summary per message instead of raw JSONL
+        #[arg(long, conflicts_with_all = ["analyze", "jq"])]
+        summary: bool,
     },
 
     /// Show aggregate statistics across sessions
@@ -216,13 +236,23 @@ pub fn run(args: SessionsArgs, json: bool, pretty: bool) -> i32 {
         Some(SessionsCommand::Show {
             session,
             jq,
+            slurp,
+            jq_raw,
             analyze,
+            follow,
+            summary,
         }) => cmd_sessions_show(
             &session,
             args.root.as_deref(),
             jq.as_deref(),
+            JqInput {
+                slurp,
+                raw_contains: jq_raw.as_deref(),
+            },
             args.format.as_deref(),
             analyze,
+            follow,
+            summary,
             json,
             pretty,
         ),
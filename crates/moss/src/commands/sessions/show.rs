@@ -1,18 +1,23 @@
 //! Show/analyze a specific session.
 
-use super::analyze::{cmd_sessions_analyze, cmd_sessions_analyze_multi, cmd_sessions_jq};
+use super::analyze::{JqInput, cmd_sessions_analyze, cmd_sessions_analyze_multi, cmd_sessions_jq};
+use super::follow::{FollowOutput, cmd_sessions_follow, summarize_record};
 use super::resolve_session_paths;
 use std::fs::File;
 use std::io::{BufRead, BufReader, Write};
 use std::path::Path;
 
 /// Show/analyze a specific session or sessions matching a pattern.
+#[allow(clippy::too_many_arguments)]
 pub fn cmd_sessions_show(
     session_id: &str,
     project: Option<&Path>,
     jq_filter: Option<&str>,
+    jq_input: JqInput,
     format: Option<&str>,
     analyze: bool,
+    follow: bool,
+    summary: bool,
     json: bool,
     pretty: bool,
 ) -> i32 {
@@ -34,16 +39,18 @@ pub fn cmd_sessions_show(
         return cmd_sessions_analyze(&paths[0], format, json, pretty);
     }
 
+    if follow {
+        let output = match (jq_filter, summary) {
+            (Some(filter), _) => FollowOutput::Jq(filter),
+            (None, true) => FollowOutput::Summary,
+            (None, false) => FollowOutput::Raw,
+        };
+        return cmd_sessions_follow(&paths[0], output);
+    }
+
     // If --jq with multiple sessions, apply to all
     if let Some(filter) = jq_filter {
-        let mut exit_code = 0;
-        for path in &paths {
-            let code = cmd_sessions_jq(path, filter);
-            if code != 0 {
-                exit_code = code;
-            }
-        }
-        return exit_code;
+        return cmd_sessions_jq(&paths, filter, jq_input);
     }
 
     // Default: dump the raw JSONL (only first match for non-glob)
@@ -62,6 +69,14 @@ pub fn cmd_sessions_show(
 
     for line in reader.lines() {
         match line {
+            Ok(l) if summary => {
+                if let Some(s) = serde_json::from_str(&l)
+                    .ok()
+                    .and_then(|v| summarize_record(&v))
+                {
+                    let _ = writeln!(stdout, "{}", s);
+                }
+            }
             Ok(l) => {
                 let _ = writeln!(stdout, "{}", l);
             }
@@ -6,8 +6,8 @@
 use crate::commands::aliases::detect_project_languages;
 use crate::config::MossConfig;
 use crate::filter::Filter;
-use crate::output::{OutputFormat, OutputFormatter};
-use crate::text_search;
+use crate::output::{JsonLinesWriter, OutputFormat, OutputFormatter};
+use crate::text_search::{self, MatchScope};
 use clap::Args;
 use rhizome_moss_derive::Merge;
 use serde::Deserialize;
@@ -58,16 +58,42 @@ pub struct TextSearchArgs {
     /// Only include files matching patterns or aliases
     #[arg(long, value_delimiter = ',')]
     pub only: Vec<String>,
+
+    /// Skip matches inside comments and string literals (files with grammar support)
+    #[arg(long, conflicts_with = "comments_only")]
+    pub code_only: bool,
+
+    /// Only report matches inside comments (files with grammar support)
+    #[arg(long)]
+    pub comments_only: bool,
+
+    /// Stream newline-delimited JSON: a header record, then one record per match
+    /// as it is found
+    #[arg(long)]
+    pub json_lines: bool,
 }
 
 /// Run text-search command with args.
 pub fn run(args: TextSearchArgs, format: crate::output::OutputFormat) -> i32 {
+    let format = if args.json_lines {
+        OutputFormat::JsonLines
+    } else {
+        format
+    };
     let effective_root = args
         .root
         .clone()
         .unwrap_or_else(|| std::env::current_dir().unwrap());
     let config = MossConfig::load(&effective_root);
 
+    let scope = if args.code_only {
+        MatchScope::CodeOnly
+    } else if args.comments_only {
+        MatchScope::CommentsOnly
+    } else {
+        MatchScope::All
+    };
+
     cmd_text_search(
         &args.pattern,
         args.root.as_deref(),
@@ -76,10 +102,12 @@ pub fn run(args: TextSearchArgs, format: crate::output::OutputFormat) -> i32 {
         &format,
         &args.exclude,
         &args.only,
+        scope,
     )
 }
 
 /// Search file contents for a pattern
+#[allow(clippy::too_many_arguments)]
 pub fn cmd_text_search(
     pattern: &str,
     root: Option<&Path>,
@@ -88,6 +116,7 @@ pub fn cmd_text_search(
     format: &OutputFormat,
     exclude: &[String],
     only: &[String],
+    scope: MatchScope,
 ) -> i32 {
     let root = root
         .map(|p| p.to_path_buf())
@@ -115,8 +144,18 @@ pub fn cmd_text_search(
         None
     };
 
-    match text_search::grep(pattern, &root, filter.as_ref(), limit, ignore_case) {
+    if format.is_json_lines() {
+        return stream_matches(pattern, &root, filter.as_ref(), limit, ignore_case, scope);
+    }
+
+    match text_search::grep(pattern, &root, filter.as_ref(), limit, ignore_case, scope) {
         Ok(result) => {
+            if !result.unclassified_files.is_empty() && !format.is_json() {
+                eprintln!(
+                    "note: {} file(s) without grammar support matched as plain text",
+                    result.unclassified_files.len()
+                );
+            }
             if result.matches.is_empty() && !format.is_json() {
                 eprintln!("No matches found for: {}", pattern);
                 return 1;
@@ -130,3 +169,73 @@ pub fn cmd_text_search(
         }
     }
 }
+
+/// A match as streamed by `--json-lines`.
+#[derive(serde::Serialize)]
+struct MatchRecord<'a> {
+    file: &'a str,
+    line: usize,
+    column: usize,
+    text: &'a str,
+    /// Containing symbol, when the file has grammar support
+    #[serde(skip_serializing_if = "Option::is_none")]
+    symbol: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    symbol_start: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    symbol_end: Option<usize>,
+}
+
+/// One JSON-lines record per match, written as each file is searched.
+fn stream_matches(
+    pattern: &str,
+    root: &Path,
+    filter: Option<&Filter>,
+    limit: usize,
+    ignore_case: bool,
+    scope: MatchScope,
+) -> i32 {
+    // Unlocked, as the search calls back from its worker threads
+    let mut out = JsonLinesWriter::new(std::io::stdout());
+    let header = out.header(
+        "text-search",
+        serde_json::json!({
+            "pattern": pattern,
+            "root": root.display().to_string(),
+            "limit": limit,
+        }),
+    );
+    let result = header.and_then(|()| {
+        text_search::grep_each(pattern, root, filter, limit, ignore_case, scope, &mut |m| {
+            out.record(
+                "match",
+                &MatchRecord {
+                    file: &m.file,
+                    line: m.line,
+                    column: m.column,
+                    text: &m.content,
+                    symbol: m.symbol.as_deref(),
+                    symbol_start: m.symbol_start,
+                    symbol_end: m.symbol_end,
+                },
+            )
+        })
+    });
+    match result {
+        Ok(summary) => {
+            if !summary.unclassified_files.is_empty() {
+                eprintln!(
+                    "note: {} file(s) without grammar support matched as plain text",
+                    summary.unclassified_files.len()
+                );
+            }
+            if summary.matches == 0 { 1 } else { 0 }
+        }
+        // The reader stopped early (e.g. `| head`)
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => 0,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            1
+        }
+    }
+}
@@ -1,11 +1,14 @@
 //! Lint command - run linters, formatters, and type checkers.
 
+use crate::git_diff::{self, DiffHunks};
 use crate::output::{OutputFormat, OutputFormatter};
 use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
 use nu_ansi_term::Color::{Blue, Red, Yellow};
 use nu_ansi_term::Style;
 use rayon::prelude::*;
-use rhizome_moss_tools::{SarifReport, ToolCategory, ToolRegistry, registry_with_custom};
+use rhizome_moss_tools::{
+    Diagnostic, SarifReport, ToolCategory, ToolRegistry, registry_with_custom,
+};
 use serde::Serialize;
 use std::fmt::Write;
 use std::path::Path;
@@ -50,6 +53,7 @@ impl OutputFormatter for LintListResult {
 }
 
 /// Run linting tools on the codebase.
+#[allow(clippy::too_many_arguments)]
 pub fn cmd_lint_run(
     target: Option<&str>,
     root: Option<&Path>,
@@ -57,11 +61,21 @@ pub fn cmd_lint_run(
     tools: Option<&str>,
     category: Option<&str>,
     sarif: bool,
+    diff: Option<&str>,
     format: crate::output::OutputFormat,
 ) -> i32 {
     let root = root.unwrap_or_else(|| Path::new("."));
     let use_colors = format.use_colors();
     let json = format.is_json();
+
+    let diff_hunks = match diff.map(|base| git_diff::changed_lines(root, base)) {
+        None => None,
+        Some(Ok(hunks)) => Some(hunks),
+        Some(Err(e)) => {
+            eprintln!("error: {}", e);
+            return 1;
+        }
+    };
     // Load built-in tools + custom tools from .moss/tools.toml
     let registry = registry_with_custom(root);
 
@@ -115,6 +129,7 @@ pub fn cmd_lint_run(
     // Run tools
     let mut all_results = Vec::new();
     let mut had_errors = false;
+    let mut total_diagnostics = 0;
 
     for tool in &tools_to_run {
         let info = tool.info();
@@ -142,7 +157,14 @@ pub fn cmd_lint_run(
         };
 
         match result {
-            Ok(result) => {
+            Ok(mut result) => {
+                registry.remap_severity(&mut result);
+                total_diagnostics += result.diagnostics.len();
+                if let Some(hunks) = &diff_hunks {
+                    result
+                        .diagnostics
+                        .retain(|d| on_changed_line(d, root, hunks));
+                }
                 if !result.success {
                     had_errors = true;
                     if let Some(err) = &result.error {
@@ -164,6 +186,13 @@ pub fn cmd_lint_run(
         }
     }
 
+    let on_diff = diff_hunks.as_ref().map(|_| {
+        all_results
+            .iter()
+            .map(|r| r.diagnostics.len())
+            .sum::<usize>()
+    });
+
     // Output results
     if sarif {
         let diagnostics = ToolRegistry::collect_diagnostics(&all_results);
@@ -171,7 +200,7 @@ pub fn cmd_lint_run(
         println!("{}", serde_json::to_string_pretty(&report).unwrap());
     } else if json {
         let diagnostics = ToolRegistry::collect_diagnostics(&all_results);
-        let output = serde_json::json!({
+        let mut output = serde_json::json!({
             "tools": tools_to_run.iter().map(|t| {
                 let info = t.info();
                 serde_json::json!({
@@ -192,6 +221,13 @@ pub fn cmd_lint_run(
             }).collect::<Vec<_>>(),
             "diagnostics": diagnostics,
         });
+        if let Some(on_diff) = on_diff {
+            output["diff"] = serde_json::json!({
+                "base": diff,
+                "total_diagnostics": total_diagnostics,
+                "on_diff": on_diff,
+            });
+        }
         println!("{}", serde_json::to_string_pretty(&output).unwrap());
     } else {
         // Print diagnostics
@@ -250,11 +286,33 @@ pub fn cmd_lint_run(
                 total_errors, total_warnings
             );
         }
+        if let (Some(base), Some(on_diff)) = (diff, on_diff) {
+            eprintln!(
+                "{} of {} diagnostic(s) on lines changed since {}",
+                on_diff, total_diagnostics, base
+            );
+        }
     }
 
     if had_errors { 1 } else { 0 }
 }
 
+/// Whether a diagnostic is on a line in `hunks` (keyed by path relative to `root`).
+fn on_changed_line(diag: &Diagnostic, root: &Path, hunks: &DiffHunks) -> bool {
+    let file = &diag.location.file;
+    let relative = if file.is_absolute() {
+        root.canonicalize()
+            .ok()
+            .and_then(|r| file.strip_prefix(r).ok())
+            .or_else(|| file.strip_prefix(root).ok())
+    } else {
+        Some(file.strip_prefix(".").unwrap_or(file))
+    };
+    relative
+        .and_then(|path| hunks.get(path))
+        .is_some_and(|ranges| git_diff::contains_line(ranges, diag.location.line))
+}
+
 /// List available linting tools.
 pub fn cmd_lint_list(root: Option<&Path>, format: &OutputFormat) -> i32 {
     let root = root.unwrap_or_else(|| Path::new("."));
@@ -447,7 +505,8 @@ fn run_lint_once(
         };
 
         match result {
-            Ok(result) => {
+            Ok(mut result) => {
+                registry.remap_severity(&mut result);
                 if !result.success {
                     had_errors = true;
                     if let Some(err) = &result.error {
@@ -38,6 +38,10 @@ pub enum ToolsAction {
         /// Watch for file changes and re-run on save
         #[arg(short, long, global = true)]
         watch: bool,
+
+        /// Only report diagnostics on lines changed since REF (git diff REF)
+        #[arg(long, value_name = "REF", global = true, conflicts_with = "watch")]
+        diff: Option<String>,
     },
 
     /// Run native test runners (cargo test, go test, bun test, etc.)
@@ -81,6 +85,7 @@ pub fn run(action: ToolsAction, root: Option<&Path>, format: OutputFormat, json:
             category,
             sarif,
             watch,
+            diff,
         } => {
             let is_list = matches!(sub_action, Some(LintSubAction::List));
             if is_list {
@@ -102,6 +107,7 @@ pub fn run(action: ToolsAction, root: Option<&Path>, format: OutputFormat, json:
                     tools.as_deref(),
                     category.as_deref(),
                     sarif,
+                    diff.as_deref(),
                     format,
                 )
             }
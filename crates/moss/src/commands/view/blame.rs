@@ -0,0 +1,187 @@
+//! Last-change annotations for `view --blame`.
+
+use crate::tree::{LastChange, ViewNode, ViewNodeKind};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// `git blame` of one file.
+#[derive(Debug, Default)]
+struct FileBlame {
+    commits: Vec<LastChange>,
+    /// Index into `commits` for each line (line 1 at index 0)
+    lines: Vec<usize>,
+}
+
+impl FileBlame {
+    fn at(&self, line: usize) -> Option<&LastChange> {
+        let index = *self.lines.get(line.checked_sub(1)?)?;
+        self.commits.get(index)
+    }
+}
+
+/// Blame of the files in the repository containing a path.
+///
+/// Each file is blamed the first time one of its symbols is annotated, and the
+/// result is cached for the rest of the run.
+pub struct GitBlame {
+    toplevel: PathBuf,
+    /// Blame by absolute path; empty for files git can't blame (e.g. untracked)
+    files: RefCell<HashMap<PathBuf, FileBlame>>,
+}
+
+impl GitBlame {
+    /// Blame files of the repository containing `path`.
+    ///
+    /// Returns None outside a git repository or if git can't be run.
+    pub fn load(path: &Path) -> Option<Self> {
+        let dir = if path.is_dir() { path } else { path.parent()? };
+        let dir = if dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            dir
+        };
+        let toplevel = git_output(dir, &["rev-parse", "--show-toplevel"])?;
+        let toplevel = PathBuf::from(toplevel.trim()).canonicalize().ok()?;
+        Some(Self {
+            toplevel,
+            files: RefCell::new(HashMap::new()),
+        })
+    }
+
+    /// Annotate `symbols` of `file`, and their children, with the last change to
+    /// their first line.
+    pub fn annotate_symbols(&self, file: &Path, symbols: &mut [ViewNode]) {
+        let Ok(path) = file.canonicalize() else {
+            return;
+        };
+        let mut files = self.files.borrow_mut();
+        let blame = files.entry(path).or_insert_with_key(|path| {
+            let path_arg = path.to_string_lossy();
+            git_output(
+                &self.toplevel,
+                &["blame", "--porcelain", "--", path_arg.as_ref()],
+            )
+            .map(|output| parse_blame(&output))
+            .unwrap_or_default()
+        });
+        set_last_changes(symbols, blame);
+    }
+
+    /// Annotate the symbols of every file in a directory tree from
+    /// [`crate::tree::generate_view_tree`] on `dir`.
+    pub fn annotate_tree(&self, root: &mut ViewNode, dir: &Path) {
+        // Node paths start with the root's name; map them onto the filesystem
+        let root_name = root.path.split('/').next().unwrap_or("").to_string();
+        self.annotate_tree_node(root, dir, &root_name);
+    }
+
+    fn annotate_tree_node(&self, node: &mut ViewNode, dir: &Path, root_name: &str) {
+        match node.kind {
+            ViewNodeKind::Directory => {
+                for child in &mut node.children {
+                    self.annotate_tree_node(child, dir, root_name);
+                }
+            }
+            ViewNodeKind::File if !node.children.is_empty() => {
+                let rel = node
+                    .path
+                    .strip_prefix(root_name)
+                    .unwrap_or(&node.path)
+                    .trim_start_matches('/');
+                self.annotate_symbols(&dir.join(rel), &mut node.children);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn set_last_changes(symbols: &mut [ViewNode], blame: &FileBlame) {
+    for symbol in symbols {
+        if let Some((start, _)) = symbol.line_range {
+            symbol.blame = blame.at(start).cloned();
+        }
+        set_last_changes(&mut symbol.children, blame);
+    }
+}
+
+/// Parse `git blame --porcelain` output.
+///
+/// Each line's entry starts with `<hash> <orig line> <final line> [<count>]`;
+/// the first entry of a commit is followed by its headers (`author`,
+/// `author-time`, ...), and every entry ends with the line's content after a tab.
+fn parse_blame(output: &str) -> FileBlame {
+    let mut blame = FileBlame::default();
+    let mut by_hash: HashMap<&str, usize> = HashMap::new();
+    let mut current = None;
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            continue;
+        }
+        let mut fields = line.split(' ');
+        let first = fields.next().unwrap_or_default();
+        if first.len() >= 40 && first.bytes().all(|b| b.is_ascii_hexdigit()) {
+            let Some(final_line) = fields.nth(1).and_then(|n| n.parse::<usize>().ok()) else {
+                continue;
+            };
+            let index = *by_hash.entry(first).or_insert_with(|| {
+                let mut change = LastChange::default();
+                change.last_commit = first.to_string();
+                blame.commits.push(change);
+                blame.commits.len() - 1
+            });
+            if blame.lines.len() < final_line {
+                blame.lines.resize(final_line, index);
+            }
+            blame.lines[final_line - 1] = index;
+            current = Some(index);
+        } else if let Some(commit) = current.and_then(|i| blame.commits.get_mut(i)) {
+            if let Some(author) = line.strip_prefix("author ") {
+                commit.last_author = author.to_string();
+            } else if let Some(time) = line.strip_prefix("author-time ") {
+                commit.set_time(time.parse().unwrap_or_default());
+            }
+        }
+    }
+    blame
+}
+
+fn git_output(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_blame() {
+        let a = "a".repeat(40);
+        let b = "b".repeat(40);
+        let output = format!(
+            "{a} 1 1 2\nauthor alice\nauthor-mail <a@x>\nauthor-time 1700000000\nsummary init\nfilename f.rs\n\tfn main() {{\n\
+             {a} 2 2\n\t}}\n\
+             {b} 5 3 1\nauthor bob\nauthor-time 1710000000\nfilename f.rs\n\tfn helper() {{}}\n"
+        );
+        let blame = parse_blame(&output);
+        assert_eq!(blame.commits.len(), 2);
+        assert_eq!(blame.lines, vec![0, 0, 1]);
+
+        let first = blame.at(2).unwrap();
+        assert_eq!(first.last_author, "alice");
+        assert_eq!(first.last_commit, a);
+        assert_eq!(first.last_modified, "2023-11-14T22:13:20+00:00");
+        assert_eq!(blame.at(3).unwrap().last_author, "bob");
+        assert!(blame.at(4).is_none());
+        assert!(blame.at(0).is_none());
+    }
+}
@@ -1,5 +1,7 @@
 //! File skeleton viewing for view command.
 
+use super::blame::GitBlame;
+use super::git_status::GitChanges;
 use super::symbol::find_symbol_signature;
 use crate::tree::{DocstringDisplay, FormatOptions};
 use crate::{deps, skeleton, tree};
@@ -155,6 +157,8 @@ pub fn cmd_view_file(
     json: bool,
     pretty: bool,
     use_colors: bool,
+    git_status: bool,
+    blame: bool,
 ) -> i32 {
     let full_path = root.join(file_path);
     let content = match std::fs::read_to_string(&full_path) {
@@ -212,11 +216,29 @@ pub fn cmd_view_file(
         None
     };
 
+    let mut view_node = skeleton_result.to_view_node(grammar.as_deref());
+    let changes = if git_status {
+        GitChanges::load(&full_path)
+    } else {
+        None
+    };
+    if let Some(changes) = &changes {
+        view_node.git_status = changes.status(&full_path).map(|s| s.marker().to_string());
+        if view_node.git_status.is_some() {
+            changes.annotate_symbols(&full_path, &mut view_node.children);
+        }
+    }
+    if blame && let Some(blame) = GitBlame::load(&full_path) {
+        blame.annotate_symbols(&full_path, &mut view_node.children);
+    }
+
     if json {
-        let view_node = skeleton_result.to_view_node(grammar.as_deref());
         println!("{}", serde_json::to_string(&view_node).unwrap());
     } else {
-        println!("# {}", file_path);
+        match &view_node.git_status {
+            Some(marker) => println!("# {} [{}]", file_path, marker),
+            None => println!("# {}", file_path),
+        }
         println!("Lines: {}", content.lines().count());
 
         if let Some(ref deps) = deps_result {
@@ -256,7 +278,6 @@ pub fn cmd_view_file(
         }
 
         if depth >= 1 && (!show_deps || context) {
-            let view_node = skeleton_result.to_view_node(grammar.as_deref());
             let format_options = FormatOptions {
                 docstrings: if context {
                     DocstringDisplay::None
@@ -335,3 +356,84 @@ pub fn cmd_view_file(
     }
     0
 }
+
+/// View a file's outline: top-level declarations only, one per line, with
+/// imports collapsed into a single `imports (N)` entry.
+pub fn cmd_view_outline(
+    file_path: &str,
+    root: &Path,
+    show_tests: bool,
+    json: bool,
+    pretty: bool,
+    use_colors: bool,
+) -> i32 {
+    let full_path = root.join(file_path);
+    let content = match std::fs::read_to_string(&full_path) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Error reading {}: {}", file_path, e);
+            return 1;
+        }
+    };
+
+    let extractor = skeleton::SkeletonExtractor::top_level();
+    let skeleton_result = extractor.extract(&full_path, &content);
+    let skeleton_result = if show_tests {
+        skeleton_result
+    } else {
+        skeleton_result.filter_tests()
+    };
+    let imports = deps::DepsExtractor::new()
+        .extract(&full_path, &content)
+        .imports;
+    let import_lines = imports.iter().map(|i| i.line);
+    let import_range = import_lines.clone().min().zip(import_lines.max());
+
+    if json {
+        let symbols: Vec<_> = skeleton_result
+            .symbols
+            .iter()
+            .map(|sym| {
+                serde_json::json!({
+                    "name": sym.name,
+                    "kind": sym.kind.as_str(),
+                    "signature": sym.signature,
+                    "start_line": sym.start_line,
+                    "end_line": sym.end_line,
+                })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "path": file_path,
+                "lines": content.lines().count(),
+                "imports": imports.len(),
+                "symbols": symbols,
+            })
+        );
+        return 0;
+    }
+
+    println!("# {}", file_path);
+    println!("Lines: {}", content.lines().count());
+    println!();
+    if let Some((start, end)) = import_range {
+        println!("imports ({}) L{}-{}", imports.len(), start, end);
+    }
+
+    let grammar = support_for_path(&full_path).map(|s| s.grammar_name().to_string());
+    let view_node = skeleton_result.to_view_node(grammar.as_deref());
+    let format_options = FormatOptions {
+        docstrings: DocstringDisplay::None,
+        line_numbers: true,
+        skip_root: true,
+        max_depth: None,
+        minimal: !pretty,
+        use_colors,
+    };
+    for line in tree::format_view_node(&view_node, &format_options) {
+        println!("{}", line);
+    }
+    0
+}
@@ -0,0 +1,329 @@
+//! Uncommitted-change annotations for `view --git-status`.
+
+use crate::git_diff::{parse_hunks, whole_file};
+use crate::tree::{ViewNode, ViewNodeKind};
+use rhizome_moss_languages::LineRange;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Marker for directories and symbols that contain changes.
+const CONTAINS_CHANGES: &str = "*";
+
+/// State of a file with uncommitted changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    Modified,
+    Added,
+    Deleted,
+    Untracked,
+}
+
+impl FileStatus {
+    /// One-letter marker, as shown by `git status --short`.
+    pub fn marker(self) -> &'static str {
+        match self {
+            FileStatus::Modified => "M",
+            FileStatus::Added => "A",
+            FileStatus::Deleted => "D",
+            FileStatus::Untracked => "?",
+        }
+    }
+
+    /// Status from a porcelain `XY` code. Renames, copies and conflicts count
+    /// as modifications.
+    fn from_porcelain(xy: &str) -> Option<Self> {
+        let mut chars = xy.chars();
+        let (x, y) = (chars.next()?, chars.next()?);
+        Some(match (x, y) {
+            ('?', '?') => FileStatus::Untracked,
+            ('!', '!') => return None,
+            ('D', _) | (_, 'D') => FileStatus::Deleted,
+            ('A', _) => FileStatus::Added,
+            _ => FileStatus::Modified,
+        })
+    }
+}
+
+/// Uncommitted changes in the repository containing a path.
+///
+/// `git status` is read once when loading; `git diff` is read the first time a
+/// file's changed lines are needed and cached for the rest of the run.
+pub struct GitChanges {
+    toplevel: PathBuf,
+    /// Changed files by absolute path
+    files: HashMap<PathBuf, FileStatus>,
+    /// Changed line ranges (1-based, inclusive) by absolute path
+    hunks: RefCell<HashMap<PathBuf, Vec<LineRange>>>,
+}
+
+impl GitChanges {
+    /// Load the changes of the repository containing `path`.
+    ///
+    /// Returns None outside a git repository or if git can't be run.
+    pub fn load(path: &Path) -> Option<Self> {
+        let dir = if path.is_dir() { path } else { path.parent()? };
+        let dir = if dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            dir
+        };
+
+        let toplevel = git_output(dir, &["rev-parse", "--show-toplevel"])?;
+        let toplevel = PathBuf::from(toplevel.trim()).canonicalize().ok()?;
+        let status = git_output(
+            &toplevel,
+            &["status", "--porcelain=v1", "-z", "--untracked-files=all"],
+        )?;
+
+        let files = parse_porcelain(&status)
+            .into_iter()
+            .map(|entry| (toplevel.join(entry.path), entry.status))
+            .collect();
+        Some(Self {
+            toplevel,
+            files,
+            hunks: RefCell::new(HashMap::new()),
+        })
+    }
+
+    /// Status of a file, if it has uncommitted changes.
+    pub fn status(&self, path: &Path) -> Option<FileStatus> {
+        let path = path.canonicalize().ok()?;
+        self.files.get(&path).copied()
+    }
+
+    fn contains_changes(&self, dir: &Path) -> bool {
+        let Ok(dir) = dir.canonicalize() else {
+            return false;
+        };
+        self.files.keys().any(|f| f.starts_with(&dir))
+    }
+
+    /// Line ranges of `path` changed relative to HEAD, staged or not.
+    fn changed_lines(&self, path: &Path) -> Vec<LineRange> {
+        let Ok(path) = path.canonicalize() else {
+            return Vec::new();
+        };
+        if let Some(hunks) = self.hunks.borrow().get(&path) {
+            return hunks.clone();
+        }
+
+        let hunks = match self.files.get(&path) {
+            None => Vec::new(),
+            // New files are changed in their entirety
+            Some(FileStatus::Added | FileStatus::Untracked) => vec![whole_file()],
+            Some(_) => {
+                let path_arg = path.to_string_lossy();
+                let args = [
+                    "diff",
+                    "--no-color",
+                    "--no-ext-diff",
+                    "-U0",
+                    "HEAD",
+                    "--",
+                    path_arg.as_ref(),
+                ];
+                match git_output(&self.toplevel, &args) {
+                    Some(diff) => parse_hunks(&diff),
+                    // No HEAD yet: everything is uncommitted
+                    None => vec![whole_file()],
+                }
+            }
+        };
+        self.hunks.borrow_mut().insert(path, hunks.clone());
+        hunks
+    }
+
+    /// Annotate a directory tree from [`crate::tree::generate_view_tree`] on `dir`.
+    ///
+    /// Files get their status marker, directories and symbols containing changes
+    /// get `*`, and deleted files are added back under their (still visible)
+    /// directory unless `dirs_only` is set.
+    pub fn annotate_tree(&self, root: &mut ViewNode, dir: &Path, dirs_only: bool) {
+        let Ok(dir) = dir.canonicalize() else {
+            return;
+        };
+        // Node paths start with the root's name; map them onto the filesystem
+        let root_name = root.path.split('/').next().unwrap_or("").to_string();
+        self.annotate_tree_node(root, &dir, &root_name, dirs_only);
+    }
+
+    fn annotate_tree_node(
+        &self,
+        node: &mut ViewNode,
+        dir: &Path,
+        root_name: &str,
+        dirs_only: bool,
+    ) {
+        let rel = node
+            .path
+            .strip_prefix(root_name)
+            .unwrap_or(&node.path)
+            .trim_start_matches('/');
+        let path = dir.join(rel);
+
+        match node.kind {
+            ViewNodeKind::Directory => {
+                if !dirs_only && !node.truncated {
+                    self.add_deleted_files(node, &path);
+                }
+                for child in &mut node.children {
+                    self.annotate_tree_node(child, dir, root_name, dirs_only);
+                }
+                if self.contains_changes(&path) {
+                    node.git_status = Some(CONTAINS_CHANGES.to_string());
+                }
+            }
+            ViewNodeKind::File => {
+                if node.git_status.is_none() {
+                    node.git_status = self.status(&path).map(|s| s.marker().to_string());
+                }
+                if node.git_status.is_some() && !node.children.is_empty() {
+                    self.annotate_symbols(&path, &mut node.children);
+                }
+            }
+            ViewNodeKind::Symbol(_) => {}
+        }
+    }
+
+    fn add_deleted_files(&self, node: &mut ViewNode, path: &Path) {
+        let Ok(path) = path.canonicalize() else {
+            return;
+        };
+        let mut deleted: Vec<&PathBuf> = self
+            .files
+            .iter()
+            .filter(|(f, status)| **status == FileStatus::Deleted && f.parent() == Some(&path))
+            .map(|(f, _)| f)
+            .collect();
+        deleted.sort();
+        for file in deleted {
+            let Some(name) = file.file_name().map(|n| n.to_string_lossy().to_string()) else {
+                continue;
+            };
+            if node.children.iter().any(|c| c.name == name) {
+                continue;
+            }
+            let mut child = ViewNode::file(&name, format!("{}/{}", node.path, name));
+            child.git_status = Some(FileStatus::Deleted.marker().to_string());
+            node.children.push(child);
+        }
+    }
+
+    /// Mark symbols of `file` whose line ranges overlap changed lines.
+    pub fn annotate_symbols(&self, file: &Path, symbols: &mut [ViewNode]) {
+        let hunks = self.changed_lines(file);
+        if !hunks.is_empty() {
+            mark_symbols(symbols, &hunks);
+        }
+    }
+}
+
+fn mark_symbols(symbols: &mut [ViewNode], hunks: &[LineRange]) {
+    for symbol in symbols {
+        let Some((start, end)) = symbol.line_range else {
+            continue;
+        };
+        if hunks
+            .iter()
+            .any(|hunk| hunk.start <= end && start <= hunk.end)
+        {
+            symbol.git_status = Some(CONTAINS_CHANGES.to_string());
+            mark_symbols(&mut symbol.children, hunks);
+        }
+    }
+}
+
+fn git_output(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// A changed file in `git status` output.
+#[derive(Debug, PartialEq)]
+struct StatusEntry {
+    /// Repo-relative path
+    path: String,
+    status: FileStatus,
+}
+
+/// Parse `git status --porcelain=v1 -z` output.
+fn parse_porcelain(output: &str) -> Vec<StatusEntry> {
+    let mut entries = Vec::new();
+    let mut fields = output.split('\0');
+    while let Some(entry) = fields.next() {
+        if entry.len() < 4 {
+            continue;
+        }
+        let (xy, path) = (&entry[..2], &entry[3..]);
+        // Renames and copies are followed by the original path
+        if xy.starts_with(['R', 'C']) {
+            fields.next();
+        }
+        if let Some(status) = FileStatus::from_porcelain(xy) {
+            entries.push(StatusEntry {
+                path: path.to_string(),
+                status,
+            });
+        }
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_porcelain() {
+        let output = " M src/lib.rs\0A  new.rs\0 D gone.rs\0R  to.rs\0from.rs\0?? notes.md\0";
+        assert_eq!(
+            parse_porcelain(output),
+            vec![
+                StatusEntry {
+                    path: "src/lib.rs".to_string(),
+                    status: FileStatus::Modified,
+                },
+                StatusEntry {
+                    path: "new.rs".to_string(),
+                    status: FileStatus::Added,
+                },
+                StatusEntry {
+                    path: "gone.rs".to_string(),
+                    status: FileStatus::Deleted,
+                },
+                StatusEntry {
+                    path: "to.rs".to_string(),
+                    status: FileStatus::Modified,
+                },
+                StatusEntry {
+                    path: "notes.md".to_string(),
+                    status: FileStatus::Untracked,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mark_symbols() {
+        let mut symbol = ViewNode::file("a", "a");
+        symbol.kind = ViewNodeKind::Symbol("function".to_string());
+        symbol.line_range = Some((5, 9));
+        let mut untouched = symbol.clone();
+        untouched.line_range = Some((12, 20));
+        let mut symbols = vec![symbol, untouched];
+
+        mark_symbols(&mut symbols, &[LineRange { start: 9, end: 10 }]);
+        assert_eq!(symbols[0].git_status.as_deref(), Some("*"));
+        assert_eq!(symbols[1].git_status, None);
+    }
+}
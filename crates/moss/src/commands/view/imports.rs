@@ -0,0 +1,286 @@
+//! A file's import relationships (`view --imports-graph`).
+//!
+//! Imports are resolved to project files where possible; the others are
+//! reported as standard library imports or, when they name a dependency
+//! declared in a manifest, as package imports. Files importing this one come
+//! from the index, which only records imports after
+//! `moss index rebuild --call-graph`.
+
+use crate::commands::analyze::is_source_file;
+use crate::deps::LocalImportResolver;
+use crate::index::{FileIndex, Importer};
+use crate::path_resolve;
+use crate::symbols::{FlatImport, SymbolParser};
+use rhizome_moss_languages::support_for_path;
+use rhizome_moss_packages::{Dependency, Ecosystem, detect_all_ecosystems};
+use serde::Serialize;
+use std::path::Path;
+
+/// What an import refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportKind {
+    /// A file in the project
+    Local,
+    /// The language's standard library
+    Stdlib,
+    /// Anything else: a dependency, annotated when its manifest declares it
+    External,
+    /// A relative or crate-local import that matches no project file
+    Unresolved,
+}
+
+/// The declared dependency an external import belongs to.
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageRef {
+    pub name: String,
+    pub ecosystem: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+/// One import statement (or one module of it), with what it resolved to.
+#[derive(Debug, Serialize)]
+pub struct ImportEdge {
+    pub module: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub names: Vec<String>,
+    pub line: usize,
+    pub kind: ImportKind,
+    /// Project files the import resolved to
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package: Option<PackageRef>,
+}
+
+/// Imports of a file and the files importing it.
+#[derive(Debug, Serialize)]
+pub struct ImportsGraph {
+    pub file: String,
+    pub imports: Vec<ImportEdge>,
+    /// None when the index has no imports recorded
+    pub imported_by: Option<Vec<Importer>>,
+}
+
+/// Show the import relationships of a file.
+pub fn cmd_view_imports_graph(target: &str, root: &Path, json: bool) -> i32 {
+    let matches = path_resolve::resolve_unified_all(target, root);
+    let resolved = match matches.as_slice() {
+        [m] if !m.is_directory && m.symbol_path.is_empty() => m,
+        [] => {
+            eprintln!("error: file not found: {}", target);
+            return 1;
+        }
+        [_] => {
+            eprintln!("error: --imports-graph needs a file target: {}", target);
+            return 1;
+        }
+        _ => {
+            eprintln!("Multiple matches for '{}' - be more specific:", target);
+            for m in &matches {
+                println!("  {}", m.file_path);
+            }
+            return 1;
+        }
+    };
+
+    let file = resolved.file_path.as_str();
+    let content = match std::fs::read_to_string(root.join(file)) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: reading {}: {}", file, e);
+            return 1;
+        }
+    };
+
+    let files: Vec<String> = path_resolve::all_files(root)
+        .into_iter()
+        .filter(|f| f.kind == "file" && is_source_file(Path::new(&f.path)))
+        .map(|f| f.path)
+        .collect();
+    let resolver = LocalImportResolver::new(root, &files);
+    let dependencies: Vec<(&dyn Ecosystem, Vec<Dependency>)> = detect_all_ecosystems(root)
+        .into_iter()
+        .filter_map(|eco| Some((eco, eco.list_dependencies(root).ok()?)))
+        .collect();
+
+    let imports = SymbolParser::new().parse_imports(Path::new(file), &content);
+    let graph = ImportsGraph {
+        file: file.to_string(),
+        imports: import_edges(root, file, &imports, &resolver, &dependencies),
+        imported_by: importers(root, file),
+    };
+
+    if json {
+        println!("{}", serde_json::to_string(&graph).unwrap());
+    } else {
+        print_imports_graph(&graph);
+    }
+    0
+}
+
+/// Group flattened imports back into statements (by module and line) and
+/// classify each.
+fn import_edges(
+    root: &Path,
+    file: &str,
+    imports: &[FlatImport],
+    resolver: &LocalImportResolver,
+    dependencies: &[(&dyn Ecosystem, Vec<Dependency>)],
+) -> Vec<ImportEdge> {
+    let lang = support_for_path(Path::new(file));
+    let mut edges: Vec<ImportEdge> = Vec::new();
+    for import in imports {
+        let module = import.module.as_deref().unwrap_or(&import.name);
+        let index = match edges
+            .iter()
+            .position(|e| e.module == module && e.line == import.line)
+        {
+            Some(index) => index,
+            None => {
+                edges.push(ImportEdge {
+                    module: module.to_string(),
+                    names: Vec::new(),
+                    line: import.line,
+                    kind: ImportKind::External,
+                    paths: Vec::new(),
+                    package: None,
+                });
+                edges.len() - 1
+            }
+        };
+        let edge = &mut edges[index];
+        if import.module.is_some() {
+            edge.names.push(import.name.clone());
+        }
+        if let Some(path) = resolver.resolve(file, import.module.as_deref(), &import.name)
+            && path != file
+            && !edge.paths.iter().any(|p| p == path)
+        {
+            edge.paths.push(path.to_string());
+        }
+    }
+
+    for edge in &mut edges {
+        edge.kind = if !edge.paths.is_empty() {
+            ImportKind::Local
+        } else if lang.is_some_and(|lang| lang.is_stdlib_import(&edge.module, root)) {
+            ImportKind::Stdlib
+        } else if is_project_relative(&edge.module) {
+            ImportKind::Unresolved
+        } else {
+            edge.package = dependencies.iter().find_map(|(eco, deps)| {
+                let dep = eco.package_for_import(&edge.module, deps)?;
+                Some(PackageRef {
+                    name: dep.name.clone(),
+                    ecosystem: eco.name(),
+                    version: dep.version_req.clone(),
+                })
+            });
+            ImportKind::External
+        };
+    }
+    edges.sort_by_key(|e| e.line);
+    edges
+}
+
+/// Whether an import path can only name something inside the project.
+fn is_project_relative(module: &str) -> bool {
+    module.starts_with('.')
+        || module.starts_with('/')
+        || ["crate", "self", "super"]
+            .iter()
+            .any(|k| module == *k || module.starts_with(&format!("{}::", k)))
+}
+
+/// Files importing `file`, from the index. None without indexed imports.
+fn importers(root: &Path, file: &str) -> Option<Vec<Importer>> {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let index = rt.block_on(FileIndex::open_if_enabled(root))?;
+    let stats = rt.block_on(index.call_graph_stats()).unwrap_or_default();
+    if stats.imports == 0 {
+        return None;
+    }
+    rt.block_on(index.importers_of_file(file)).ok()
+}
+
+fn print_imports_graph(graph: &ImportsGraph) {
+    println!("{}", graph.file);
+    println!("  imports ({})", graph.imports.len());
+    for edge in &graph.imports {
+        let mut line = format!("    {}", edge.module);
+        if !edge.names.is_empty() {
+            line.push_str(&format!(" ({})", edge.names.join(", ")));
+        }
+        match edge.kind {
+            ImportKind::Local => line.push_str(&format!(" -> {}", edge.paths.join(", "))),
+            ImportKind::Stdlib => line.push_str(" [stdlib]"),
+            ImportKind::Unresolved => line.push_str(" [unresolved]"),
+            ImportKind::External => match &edge.package {
+                Some(pkg) => match &pkg.version {
+                    Some(version) => {
+                        line.push_str(&format!(" [{} {} {}]", pkg.ecosystem, pkg.name, version))
+                    }
+                    None => line.push_str(&format!(" [{} {}]", pkg.ecosystem, pkg.name)),
+                },
+                None => line.push_str(" [external]"),
+            },
+        }
+        println!("{}  L{}", line, edge.line);
+    }
+    match &graph.imported_by {
+        Some(importers) => {
+            println!("  imported by ({})", importers.len());
+            for importer in importers {
+                println!("    {}:{}", importer.file, importer.line);
+            }
+        }
+        None => {
+            println!("  imported by: unknown (run: moss index rebuild --call-graph)");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_project_relative() {
+        assert!(is_project_relative("crate::index"));
+        assert!(is_project_relative("super"));
+        assert!(is_project_relative("..util"));
+        assert!(is_project_relative("./lib"));
+        assert!(!is_project_relative("serde::Serialize"));
+        assert!(!is_project_relative("crates_io"));
+        assert!(!is_project_relative("lodash/fp"));
+    }
+
+    #[test]
+    fn test_import_edges_groups_names() {
+        let root = Path::new(".");
+        let files = vec!["src/main.rs".to_string()];
+        let resolver = LocalImportResolver::new(root, &files);
+        let flat = |module: Option<&str>, name: &str, line| FlatImport {
+            module: module.map(String::from),
+            name: name.to_string(),
+            alias: None,
+            line,
+        };
+        let imports = [
+            flat(Some("crate::missing"), "a", 1),
+            flat(Some("crate::missing"), "b", 1),
+            flat(None, "serde", 2),
+        ];
+        let edges = import_edges(root, "src/main.rs", &imports, &resolver, &[]);
+        assert_eq!(edges.len(), 2);
+        assert_eq!(edges[0].module, "crate::missing");
+        assert_eq!(edges[0].names, vec!["a", "b"]);
+        assert_eq!(edges[0].kind, ImportKind::Unresolved);
+        assert_eq!(edges[1].module, "serde");
+        assert!(edges[1].names.is_empty());
+        assert_eq!(edges[1].kind, ImportKind::External);
+        assert!(edges[1].package.is_none());
+    }
+}
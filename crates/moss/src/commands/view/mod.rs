@@ -1,19 +1,26 @@
 //! View command - unified view of files, directories, and symbols.
 
+pub mod blame;
 pub mod file;
+pub mod git_status;
 pub mod history;
+pub mod imports;
 pub mod lines;
+pub mod position;
 pub mod search;
 pub mod symbol;
+pub mod symbols_json;
 pub mod tree;
 
 use crate::commands::aliases::detect_project_languages;
 use crate::config::MossConfig;
-use crate::filter::Filter;
+use crate::filter::{Filter, parse_kinds};
 use crate::{daemon, path_resolve};
 use clap::Args;
 use rhizome_moss_derive::Merge;
+use rhizome_moss_languages::SymbolKind;
 use serde::Deserialize;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 pub use search::search_symbols;
@@ -58,6 +65,7 @@ impl ViewConfig {
 /// | `Parent/method` | Symbol search (when Parent isn't a path) |
 /// | `file:123` | Symbol containing line 123 |
 /// | `file:10-20` | Lines 10-20 (raw) |
+/// | `file --symbol-at 12:5` | Identifier or symbol at line 12, column 5 |
 /// | `SymbolName` | Symbol search across codebase |
 #[derive(Args, Debug)]
 pub struct ViewArgs {
@@ -80,9 +88,9 @@ pub struct ViewArgs {
     #[arg(long)]
     pub deps: bool,
 
-    /// Filter by symbol kind: class, function, method
+    /// Filter by symbol kind, comma-separated (e.g. function,method)
     #[arg(short = 'k', long = "kind")]
-    pub kind: Option<String>,
+    pub kind: Vec<String>,
 
     /// Show only type definitions (class, struct, enum, interface, type alias)
     #[arg(long = "types-only")]
@@ -96,6 +104,14 @@ pub struct ViewArgs {
     #[arg(long)]
     pub raw: bool,
 
+    /// Directory levels to show, independent of --depth symbol expansion
+    #[arg(short = 'L', long, value_name = "N")]
+    pub tree_depth: Option<usize>,
+
+    /// Show only directories in the tree
+    #[arg(long)]
+    pub dirs_only: bool,
+
     /// Focus view: show target at high detail, imports at signature level
     #[arg(long, value_name = "MODULE", num_args = 0..=1, default_missing_value = "*", require_equals = true)]
     pub focus: Option<String>,
@@ -108,6 +124,10 @@ pub struct ViewArgs {
     #[arg(long)]
     pub full: bool,
 
+    /// Outline: top-level declarations only, one per line (overrides --depth, --full, --focus)
+    #[arg(long)]
+    pub outline: bool,
+
     /// Show full docstrings
     #[arg(long)]
     pub docs: bool,
@@ -120,6 +140,10 @@ pub struct ViewArgs {
     #[arg(long)]
     pub context: bool,
 
+    /// Show signatures of N sibling symbols before and after a viewed symbol
+    #[arg(long, value_name = "N", visible_alias = "siblings")]
+    pub context_lines: Option<usize>,
+
     /// Prepend directory context (.context.md files from hierarchy)
     #[arg(long)]
     pub dir_context: bool,
@@ -136,9 +160,30 @@ pub struct ViewArgs {
     #[arg(short = 'i', long)]
     pub case_insensitive: bool,
 
+    /// Mark uncommitted changes: file status (M/A/D/?) and changed symbols (*)
+    #[arg(long)]
+    pub git_status: bool,
+
+    /// Annotate symbols with the last commit to change their first line
+    #[arg(long)]
+    pub blame: bool,
+
     /// Show git history for symbol (last N changes)
     #[arg(long, value_name = "N", default_missing_value = "5", num_args = 0..=1)]
     pub history: Option<usize>,
+
+    /// Flat JSON array of every symbol in the file or directory, for external indexers
+    #[arg(long)]
+    pub symbols_json: bool,
+
+    /// Identifier or symbol at an exact position in the file, with the definition
+    /// of a referenced identifier
+    #[arg(long, value_name = "LINE:COL")]
+    pub symbol_at: Option<String>,
+
+    /// Resolved imports of a file (local paths, packages) and the files importing it
+    #[arg(long)]
+    pub imports_graph: bool,
 }
 
 /// Run view command with args.
@@ -149,6 +194,14 @@ pub fn run(args: ViewArgs, format: crate::output::OutputFormat) -> i32 {
         .unwrap_or_else(|| std::env::current_dir().unwrap());
     let config = MossConfig::load(&effective_root);
 
+    let kinds = match parse_kinds(&args.kind) {
+        Ok(kinds) => kinds,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return 1;
+        }
+    };
+
     // Handle --history mode
     if let Some(limit) = args.history {
         return history::cmd_history(
@@ -160,6 +213,46 @@ pub fn run(args: ViewArgs, format: crate::output::OutputFormat) -> i32 {
         );
     }
 
+    if let Some(position) = &args.symbol_at {
+        let position = match position::parse_position(position) {
+            Ok(position) => position,
+            Err(e) => {
+                eprintln!("error: {}", e);
+                return 1;
+            }
+        };
+        let Some(target) = args.target.as_deref() else {
+            eprintln!("error: --symbol-at requires a file target");
+            return 1;
+        };
+        return position::cmd_view_symbol_at(target, position, &effective_root, format.is_json());
+    }
+
+    if args.imports_graph {
+        let Some(target) = args.target.as_deref() else {
+            eprintln!("error: --imports-graph requires a file target");
+            return 1;
+        };
+        return imports::cmd_view_imports_graph(target, &effective_root, format.is_json());
+    }
+
+    if args.symbols_json {
+        let filter = match build_filter(&effective_root, &args.exclude, &args.only) {
+            Ok(f) => f,
+            Err(e) => {
+                eprintln!("error: {}", e);
+                return 1;
+            }
+        };
+        return symbols_json::cmd_symbols_json(
+            args.target.as_deref(),
+            &effective_root,
+            args.tests,
+            filter.as_ref(),
+            &format,
+        );
+    }
+
     // Handle --dir-context: prepend directory context before main output
     if args.dir_context && !format.is_json() {
         let target_path = args
@@ -181,15 +274,19 @@ pub fn run(args: ViewArgs, format: crate::output::OutputFormat) -> i32 {
         args.depth.unwrap_or_else(|| config.view.depth()),
         args.line_numbers || config.view.line_numbers(),
         args.deps,
-        args.kind.as_deref(),
+        &kinds,
         args.types_only,
         args.tests,
         args.raw,
+        args.tree_depth,
+        args.dirs_only,
         args.focus.as_deref(),
         args.resolve_imports,
         args.full,
+        args.outline,
         args.docs || config.view.show_docs(),
         args.context,
+        args.context_lines.unwrap_or(0),
         !args.no_parent,
         format.is_json(),
         format.is_pretty(),
@@ -197,6 +294,8 @@ pub fn run(args: ViewArgs, format: crate::output::OutputFormat) -> i32 {
         &args.exclude,
         &args.only,
         args.case_insensitive,
+        args.git_status,
+        args.blame,
     )
 }
 
@@ -208,15 +307,19 @@ pub fn cmd_view(
     depth: i32,
     line_numbers: bool,
     show_deps: bool,
-    kind_filter: Option<&str>,
+    kind_filter: &HashSet<SymbolKind>,
     types_only: bool,
     show_tests: bool,
     raw: bool,
+    tree_depth: Option<usize>,
+    dirs_only: bool,
     focus: Option<&str>,
     resolve_imports: bool,
     full: bool,
+    outline: bool,
     show_docs: bool,
     context: bool,
+    siblings: usize,
     show_parent: bool,
     json: bool,
     pretty: bool,
@@ -224,6 +327,8 @@ pub fn cmd_view(
     exclude: &[String],
     only: &[String],
     case_insensitive: bool,
+    git_status: bool,
+    blame: bool,
 ) -> i32 {
     let root = root
         .map(|p| p.to_path_buf())
@@ -232,35 +337,25 @@ pub fn cmd_view(
     // Ensure daemon is running if configured
     daemon::maybe_start_daemon(&root);
 
-    // Build filter if exclude/only patterns are specified
-    let filter = if !exclude.is_empty() || !only.is_empty() {
-        let config = MossConfig::load(&root);
-        let languages = detect_project_languages(&root);
-        let lang_refs: Vec<&str> = languages.iter().map(|s| s.as_str()).collect();
-
-        match Filter::new(exclude, only, &config.aliases, &lang_refs) {
-            Ok(f) => {
-                for warning in f.warnings() {
-                    eprintln!("warning: {}", warning);
-                }
-                Some(f)
-            }
-            Err(e) => {
-                eprintln!("error: {}", e);
-                return 1;
-            }
+    let filter = match build_filter(&root, exclude, only) {
+        Ok(f) => f,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return 1;
         }
-    } else {
-        None
     };
 
     // If kind filter is specified without target (or with "."), list matching symbols
-    if let Some(kind) = kind_filter {
+    if !kind_filter.is_empty() {
         let scope = target.unwrap_or(".");
-        return tree::cmd_view_filtered(&root, scope, kind, json);
+        return tree::cmd_view_filtered(&root, scope, kind_filter, json);
     }
 
-    // --focus requires a file target
+    // --focus and --outline require a file target
+    if outline && target.is_none_or(|t| t == ".") {
+        eprintln!("--outline requires a file target");
+        return 1;
+    }
     if focus.is_some() && target.is_none() {
         eprintln!("--focus requires a file target");
         return 1;
@@ -274,11 +369,15 @@ pub fn cmd_view(
             &root,
             &root,
             depth,
+            tree_depth,
+            dirs_only,
             raw,
             json,
             pretty,
             use_colors,
             filter.as_ref(),
+            git_status,
+            blame,
         );
     }
 
@@ -355,6 +454,7 @@ pub fn cmd_view(
                 show_docs,
                 show_parent,
                 context,
+                siblings,
                 json,
                 pretty,
                 use_colors,
@@ -413,16 +513,32 @@ pub fn cmd_view(
         }
     };
 
-    if unified.is_directory {
+    if outline && (unified.is_directory || !unified.symbol_path.is_empty()) {
+        eprintln!("--outline requires a file target");
+        1
+    } else if outline {
+        file::cmd_view_outline(
+            &unified.file_path,
+            &root,
+            show_tests,
+            json,
+            pretty,
+            use_colors,
+        )
+    } else if unified.is_directory {
         tree::cmd_view_directory(
             &root.join(&unified.file_path),
             &root,
             depth,
+            tree_depth,
+            dirs_only,
             raw,
             json,
             pretty,
             use_colors,
             filter.as_ref(),
+            git_status,
+            blame,
         )
     } else if unified.symbol_path.is_empty() {
         let effective_depth = if full { -1 } else { depth };
@@ -441,6 +557,8 @@ pub fn cmd_view(
             json,
             pretty,
             use_colors,
+            git_status,
+            blame,
         )
     } else {
         // Check if symbol path contains glob patterns
@@ -469,6 +587,7 @@ pub fn cmd_view(
             show_docs,
             show_parent,
             context,
+            siblings,
             json,
             pretty,
             use_colors,
@@ -476,3 +595,24 @@ pub fn cmd_view(
         )
     }
 }
+
+/// Build a filter if exclude/only patterns are specified. Warnings are
+/// printed; errors are returned for the caller to report.
+pub(crate) fn build_filter(
+    root: &Path,
+    exclude: &[String],
+    only: &[String],
+) -> Result<Option<Filter>, String> {
+    if exclude.is_empty() && only.is_empty() {
+        return Ok(None);
+    }
+    let config = MossConfig::load(root);
+    let languages = detect_project_languages(root);
+    let lang_refs: Vec<&str> = languages.iter().map(|s| s.as_str()).collect();
+
+    let filter = Filter::new(exclude, only, &config.aliases, &lang_refs)?;
+    for warning in filter.warnings() {
+        eprintln!("warning: {}", warning);
+    }
+    Ok(Some(filter))
+}
@@ -0,0 +1,328 @@
+//! Symbol or identifier at an exact position (`view --symbol-at LINE:COL`).
+//!
+//! The innermost named syntax node at the position is looked up. When it is an
+//! identifier, it is reported as a declaration if it is the `name` of its parent
+//! node, and otherwise as a reference, whose definitions are looked up in the
+//! same file and then in the index. Positions in whitespace, comments or other
+//! non-identifier nodes report the enclosing symbol.
+
+use crate::index::FileIndex;
+use crate::{parsers, path_resolve, skeleton};
+use rhizome_moss_languages::{Symbol, support_for_path};
+use serde::Serialize;
+use std::path::Path;
+
+/// How the position relates to the reported symbol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PositionRole {
+    /// An identifier naming the declaration it is in
+    Declaration,
+    /// An identifier referring to a symbol declared elsewhere
+    Reference,
+    /// Not an identifier; only the enclosing symbol is known
+    Enclosing,
+}
+
+/// A symbol's location.
+#[derive(Debug, Clone, Serialize)]
+pub struct SymbolLocation {
+    pub name: String,
+    pub kind: String,
+    pub file: String,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+/// What is at a position in a file.
+#[derive(Debug, Serialize)]
+pub struct SymbolAt {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub role: PositionRole,
+    /// Syntax node kind at the position (None without grammar support)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_kind: Option<String>,
+    /// Identifier at the position
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
+    /// Innermost symbol containing the position
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enclosing: Option<SymbolLocation>,
+    /// Where a referenced identifier is defined: in the same file if it is
+    /// declared there, otherwise anywhere in the index
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub definitions: Vec<SymbolLocation>,
+}
+
+/// A 1-based line and character column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Parse a `LINE:COL` position (both 1-based).
+pub fn parse_position(s: &str) -> Result<Position, String> {
+    let (line, col) = s
+        .split_once(':')
+        .ok_or_else(|| format!("invalid position `{}` (expected LINE:COL)", s))?;
+    let parse = |n: &str| n.parse::<usize>().ok().filter(|&n| n > 0);
+    match (parse(line), parse(col)) {
+        (Some(line), Some(column)) => Ok(Position { line, column }),
+        _ => Err(format!(
+            "invalid position `{}` (line and column start at 1)",
+            s
+        )),
+    }
+}
+
+/// Byte offset of 1-based `line` and character column `col` in `content`. A
+/// column past the end of the line is clamped to the line's end.
+fn byte_offset(content: &str, line: usize, col: usize) -> Option<usize> {
+    let line_start = if line == 1 {
+        0
+    } else {
+        content.match_indices('\n').nth(line - 2)?.0 + 1
+    };
+    let text = content[line_start..].split('\n').next().unwrap_or("");
+    let within = text
+        .char_indices()
+        .nth(col - 1)
+        .map_or(text.len(), |(i, _)| i);
+    Some(line_start + within)
+}
+
+/// Whether a syntax node kind names something (`identifier`, `type_identifier`,
+/// `property_identifier`, Ruby's `constant`, PHP's `name`, ...).
+fn is_identifier_kind(kind: &str) -> bool {
+    kind.ends_with("identifier") || matches!(kind, "name" | "constant" | "word")
+}
+
+/// Show the symbol or identifier at `position` in `file_path`.
+pub fn cmd_view_symbol_at(file_path: &str, position: Position, root: &Path, json: bool) -> i32 {
+    let Position { line, column: col } = position;
+    let matches = path_resolve::resolve_unified_all(file_path, root);
+    let resolved = match matches.as_slice() {
+        [m] if !m.is_directory && m.symbol_path.is_empty() => m,
+        [] => {
+            eprintln!("error: file not found: {}", file_path);
+            return 1;
+        }
+        [_] => {
+            eprintln!("error: --symbol-at needs a file target: {}", file_path);
+            return 1;
+        }
+        _ => {
+            eprintln!("Multiple matches for '{}' - be more specific:", file_path);
+            for m in &matches {
+                println!("  {}", m.file_path);
+            }
+            return 1;
+        }
+    };
+
+    let full_path = root.join(&resolved.file_path);
+    let content = match std::fs::read_to_string(&full_path) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: reading {}: {}", resolved.file_path, e);
+            return 1;
+        }
+    };
+    let Some(offset) = byte_offset(&content, line, col) else {
+        eprintln!("error: {} has no line {}", resolved.file_path, line);
+        return 1;
+    };
+
+    let result = symbol_at(root, &resolved.file_path, &content, line, col, offset);
+    if json {
+        println!("{}", serde_json::to_string(&result).unwrap());
+    } else {
+        print_symbol_at(&result);
+    }
+    0
+}
+
+fn symbol_at(
+    root: &Path,
+    file: &str,
+    content: &str,
+    line: usize,
+    column: usize,
+    offset: usize,
+) -> SymbolAt {
+    let full_path = root.join(file);
+    let symbols = skeleton::SkeletonExtractor::new()
+        .extract(&full_path, content)
+        .symbols;
+    let location = |sym: &Symbol| SymbolLocation {
+        name: sym.name.clone(),
+        kind: sym.kind.as_str().to_string(),
+        file: file.to_string(),
+        start_line: sym.start_line,
+        end_line: sym.end_line,
+    };
+
+    let mut result = SymbolAt {
+        file: file.to_string(),
+        line,
+        column,
+        role: PositionRole::Enclosing,
+        node_kind: None,
+        identifier: None,
+        enclosing: symbols
+            .iter()
+            .find_map(|s| s.find_by_line(line))
+            .map(location),
+        definitions: Vec::new(),
+    };
+
+    let tree = support_for_path(&full_path)
+        .and_then(|support| parsers::parse_with_grammar(support.grammar_name(), content));
+    let Some(tree) = tree else {
+        return result;
+    };
+    let Some(node) = tree
+        .root_node()
+        .named_descendant_for_byte_range(offset, offset)
+    else {
+        return result;
+    };
+    result.node_kind = Some(node.kind().to_string());
+    if !is_identifier_kind(node.kind()) {
+        return result;
+    }
+    let Ok(name) = node.utf8_text(content.as_bytes()) else {
+        return result;
+    };
+    result.identifier = Some(name.to_string());
+
+    let declared = node
+        .parent()
+        .and_then(|p| p.child_by_field_name("name"))
+        .is_some_and(|n| n.id() == node.id());
+    if declared {
+        result.role = PositionRole::Declaration;
+        // The declared symbol, rather than the one around its declaration
+        if let Some(sym) = symbols
+            .iter()
+            .flat_map(|s| s.iter_flat())
+            .filter(|s| s.name == name && s.start_line <= line && line <= s.end_line)
+            .last()
+        {
+            result.enclosing = Some(location(sym));
+        }
+        return result;
+    }
+
+    result.role = PositionRole::Reference;
+    result.definitions = symbols
+        .iter()
+        .flat_map(|s| s.iter_flat())
+        .filter(|s| s.name == name)
+        .map(location)
+        .collect();
+    if result.definitions.is_empty() {
+        result.definitions = index_definitions(root, name);
+    }
+    result
+}
+
+/// Definitions of `name` elsewhere in the project, from the index.
+fn index_definitions(root: &Path, name: &str) -> Vec<SymbolLocation> {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let Some(index) = rt.block_on(FileIndex::open_if_enabled(root)) else {
+        return Vec::new();
+    };
+    rt.block_on(index.find_symbol(name))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(file, kind, start_line, end_line)| SymbolLocation {
+            name: name.to_string(),
+            kind,
+            file,
+            start_line,
+            end_line,
+        })
+        .collect()
+}
+
+fn print_symbol_at(result: &SymbolAt) {
+    let what = match (&result.identifier, &result.node_kind) {
+        (Some(name), _) => format!("`{}`", name),
+        (None, Some(kind)) => kind.clone(),
+        (None, None) => "position".to_string(),
+    };
+    let role = match result.role {
+        PositionRole::Declaration => "declaration",
+        PositionRole::Reference => "reference",
+        PositionRole::Enclosing => "no identifier",
+    };
+    println!(
+        "{}:{}:{} {} ({})",
+        result.file, result.line, result.column, what, role
+    );
+    if let Some(sym) = &result.enclosing {
+        let label = if result.role == PositionRole::Declaration {
+            "declares"
+        } else {
+            "in"
+        };
+        println!(
+            "  {} {} {} (L{}-{})",
+            label, sym.kind, sym.name, sym.start_line, sym.end_line
+        );
+    }
+    if result.role == PositionRole::Reference && result.definitions.is_empty() {
+        println!("  definition not found");
+    }
+    for def in &result.definitions {
+        println!(
+            "  defined at {}:{}-{} ({} {})",
+            def.file, def.start_line, def.end_line, def.kind, def.name
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_position() {
+        assert_eq!(
+            parse_position("12:5"),
+            Ok(Position {
+                line: 12,
+                column: 5
+            })
+        );
+        assert!(parse_position("12").is_err());
+        assert!(parse_position("0:5").is_err());
+        assert!(parse_position("3:x").is_err());
+    }
+
+    #[test]
+    fn test_byte_offset() {
+        let content = "fn a() {}\n  let é = b;\n";
+        assert_eq!(byte_offset(content, 1, 1), Some(0));
+        assert_eq!(byte_offset(content, 2, 3), Some(12));
+        // Columns count characters, so `=` after the two-byte `é` is at byte 19
+        assert_eq!(&content[byte_offset(content, 2, 9).unwrap()..][..1], "=");
+        // Past the end of the line: clamped
+        assert_eq!(byte_offset(content, 1, 99), Some(9));
+        assert_eq!(byte_offset(content, 3, 1), Some(content.len()));
+        assert_eq!(byte_offset(content, 4, 1), None);
+    }
+
+    #[test]
+    fn test_is_identifier_kind() {
+        assert!(is_identifier_kind("identifier"));
+        assert!(is_identifier_kind("type_identifier"));
+        assert!(is_identifier_kind("constant"));
+        assert!(!is_identifier_kind("line_comment"));
+        assert!(!is_identifier_kind("string_literal"));
+    }
+}
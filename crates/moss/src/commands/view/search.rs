@@ -1,9 +1,13 @@
 //! Symbol search functionality for view command.
 
-use crate::{index, skeleton};
+use crate::{index, skeleton, symbol_rank};
 use rhizome_moss_languages::support_for_path;
 use std::path::Path;
 
+/// Symbols fetched from the index for ranking; more than are shown, since the
+/// index's own order only roughly agrees with the ranking.
+const CANDIDATE_LIMIT: usize = 500;
+
 /// Parsed symbol query with optional file and parent hints.
 struct SymbolQuery {
     file_hint: Option<String>,
@@ -18,7 +22,7 @@ pub fn has_language_support(path: &str) -> bool {
         .unwrap_or(false)
 }
 
-/// Search for symbols in the index by name.
+/// Search for symbols in the index by name, best match first (see `symbol_rank`).
 /// Supports qualified names like "ClassName/method" or "file.rs/ClassName/method"
 pub fn search_symbols(query: &str, root: &Path) -> Vec<index::SymbolMatch> {
     let rt = tokio::runtime::Runtime::new().unwrap();
@@ -38,7 +42,11 @@ async fn search_symbols_async(query: &str, root: &Path) -> Vec<index::SymbolMatc
                 return search_symbols_unindexed(query, root);
             }
         }
-        if let Ok(mut symbols) = idx.find_symbols(&parsed.symbol_name, None, true, 50).await {
+        if let Ok(symbols) = idx
+            .find_symbols(&parsed.symbol_name, None, true, CANDIDATE_LIMIT)
+            .await
+        {
+            let mut symbols = symbol_rank::rank(&parsed.symbol_name, symbols);
             // Filter by parent hint if provided
             if let Some(ref parent) = parsed.parent_hint {
                 let parent_lower = parent.to_lowercase();
@@ -110,10 +118,7 @@ fn parse_symbol_query(query: &str) -> SymbolQuery {
 /// Search for symbols by walking filesystem and parsing files
 fn search_symbols_unindexed(query: &str, root: &Path) -> Vec<index::SymbolMatch> {
     use ignore::WalkBuilder;
-    use nucleo_matcher::{Config, Matcher};
 
-    let query_lower = query.to_lowercase();
-    let mut matcher = Matcher::new(Config::DEFAULT);
     let mut matches = Vec::new();
 
     let walker = WalkBuilder::new(root).hidden(true).git_ignore(true).build();
@@ -141,8 +146,7 @@ fn search_symbols_unindexed(query: &str, root: &Path) -> Vec<index::SymbolMatch>
 
         collect_matching_symbols(
             &result.symbols,
-            &query_lower,
-            &mut matcher,
+            query,
             &rel_path.to_string_lossy(),
             None,
             &mut matches,
@@ -153,54 +157,30 @@ fn search_symbols_unindexed(query: &str, root: &Path) -> Vec<index::SymbolMatch>
         }
     }
 
-    matches.sort_by(|a, b| b.1.cmp(&a.1));
-    matches.into_iter().take(10).map(|(m, _)| m).collect()
+    let mut ranked = symbol_rank::rank(query, matches);
+    ranked.truncate(10);
+    ranked
 }
 
 fn collect_matching_symbols(
     symbols: &[skeleton::SkeletonSymbol],
     query: &str,
-    matcher: &mut nucleo_matcher::Matcher,
     file: &str,
     parent: Option<&str>,
-    matches: &mut Vec<(index::SymbolMatch, u32)>,
+    matches: &mut Vec<index::SymbolMatch>,
 ) {
-    use nucleo_matcher::Utf32Str;
-    use nucleo_matcher::pattern::{AtomKind, CaseMatching, Normalization, Pattern};
-
-    let pattern = Pattern::new(
-        query,
-        CaseMatching::Ignore,
-        Normalization::Smart,
-        AtomKind::Fuzzy,
-    );
-
     for sym in symbols {
-        let name_lower = sym.name.to_lowercase();
-        let mut buf = Vec::new();
-        let haystack = Utf32Str::new(&name_lower, &mut buf);
-
-        if let Some(score) = pattern.score(haystack, matcher) {
-            matches.push((
-                index::SymbolMatch {
-                    name: sym.name.clone(),
-                    kind: sym.kind.as_str().to_string(),
-                    file: file.to_string(),
-                    start_line: sym.start_line,
-                    end_line: sym.end_line,
-                    parent: parent.map(|s| s.to_string()),
-                },
-                score,
-            ));
+        if symbol_rank::match_tier(query, &sym.name).is_some() {
+            matches.push(index::SymbolMatch {
+                name: sym.name.clone(),
+                kind: sym.kind.as_str().to_string(),
+                file: file.to_string(),
+                start_line: sym.start_line,
+                end_line: sym.end_line,
+                parent: parent.map(|s| s.to_string()),
+            });
         }
 
-        collect_matching_symbols(
-            &sym.children,
-            query,
-            matcher,
-            file,
-            Some(&sym.name),
-            matches,
-        );
+        collect_matching_symbols(&sym.children, query, file, Some(&sym.name), matches);
     }
 }
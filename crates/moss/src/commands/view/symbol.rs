@@ -19,6 +19,7 @@ pub fn cmd_view_symbol_direct(
     show_docs: bool,
     show_parent: bool,
     context: bool,
+    siblings: usize,
     json: bool,
     pretty: bool,
     use_colors: bool,
@@ -37,6 +38,7 @@ pub fn cmd_view_symbol_direct(
         show_docs,
         show_parent,
         context,
+        siblings,
         json,
         pretty,
         use_colors,
@@ -255,6 +257,158 @@ fn find_symbol_by_path<'a>(
     None
 }
 
+/// The symbols around a symbol in its container (or among the top-level
+/// items): up to N before and after it.
+struct SiblingWindow<'a> {
+    before: &'a [skeleton::SkeletonSymbol],
+    after: &'a [skeleton::SkeletonSymbol],
+    /// Siblings outside the window, before and after it
+    hidden_before: usize,
+    hidden_after: usize,
+}
+
+/// The `n` siblings on each side of the symbol at `path`, found like
+/// [`find_symbol_by_path`].
+fn sibling_window<'a>(
+    symbols: &'a [skeleton::SkeletonSymbol],
+    path: &[String],
+    n: usize,
+    case_insensitive: bool,
+) -> Option<SiblingWindow<'a>> {
+    let SiblingPosition { siblings, index } = match path {
+        [] => return None,
+        [name] => find_in_siblings(symbols, name, case_insensitive)?,
+        [parents @ .., name] => {
+            let mut current = symbols;
+            for parent in parents {
+                current = &current
+                    .iter()
+                    .find(|s| names_match(&s.name, parent, case_insensitive))?
+                    .children;
+            }
+            let index = current
+                .iter()
+                .position(|s| names_match(&s.name, name, case_insensitive))?;
+            SiblingPosition {
+                siblings: current,
+                index,
+            }
+        }
+    };
+    let start = index.saturating_sub(n);
+    let end = (index + 1 + n).min(siblings.len());
+    Some(SiblingWindow {
+        before: &siblings[start..index],
+        after: &siblings[index + 1..end],
+        hidden_before: start,
+        hidden_after: siblings.len() - end,
+    })
+}
+
+/// A symbol's place in its sibling list.
+struct SiblingPosition<'a> {
+    siblings: &'a [skeleton::SkeletonSymbol],
+    index: usize,
+}
+
+/// Where the first symbol named `name` (in the order of [`find_symbol_ci`]) is
+/// among its siblings.
+fn find_in_siblings<'a>(
+    symbols: &'a [skeleton::SkeletonSymbol],
+    name: &str,
+    case_insensitive: bool,
+) -> Option<SiblingPosition<'a>> {
+    for (i, sym) in symbols.iter().enumerate() {
+        if names_match(&sym.name, name, case_insensitive) {
+            return Some(SiblingPosition {
+                siblings: symbols,
+                index: i,
+            });
+        }
+        if let Some(found) = find_in_siblings(&sym.children, name, case_insensitive) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+fn siblings_json(window: &SiblingWindow) -> serde_json::Value {
+    let entries = |symbols: &[skeleton::SkeletonSymbol]| -> Vec<serde_json::Value> {
+        symbols
+            .iter()
+            .map(|s| {
+                serde_json::json!({
+                    "name": s.name,
+                    "kind": s.kind.as_str(),
+                    "signature": s.signature,
+                    "start_line": s.start_line,
+                    "end_line": s.end_line,
+                })
+            })
+            .collect()
+    };
+    serde_json::json!({
+        "before": entries(window.before),
+        "after": entries(window.after),
+        "hidden_before": window.hidden_before,
+        "hidden_after": window.hidden_after,
+    })
+}
+
+/// Print symbol signatures at `indent`.
+fn print_signatures(
+    symbols: &[skeleton::SkeletonSymbol],
+    indent: &str,
+    grammar: Option<&str>,
+    use_colors: bool,
+) {
+    for sym in symbols {
+        let signature = sym
+            .signature
+            .lines()
+            .map(|l| format!("{}{}", indent, l.trim_start()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        match grammar {
+            Some(g) => println!("{}", tree::highlight_source(&signature, g, use_colors)),
+            None => println!("{}", signature),
+        }
+    }
+}
+
+/// Print a symbol's (highlighted) source between the signatures of its
+/// siblings, indented like its first line.
+fn print_with_siblings(
+    source: &str,
+    highlighted: &str,
+    window: &SiblingWindow,
+    grammar: Option<&str>,
+    use_colors: bool,
+) {
+    let indent: String = source
+        .lines()
+        .next()
+        .unwrap_or("")
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect();
+    if window.hidden_before > 0 {
+        println!("{}/* {} more above */", indent, window.hidden_before);
+    }
+    print_signatures(window.before, &indent, grammar, use_colors);
+    if window.hidden_before > 0 || !window.before.is_empty() {
+        println!();
+    }
+    println!("{}", highlighted);
+    if window.hidden_after > 0 || !window.after.is_empty() {
+        println!();
+    }
+    print_signatures(window.after, &indent, grammar, use_colors);
+    if window.hidden_after > 0 {
+        println!("{}/* {} more below */", indent, window.hidden_after);
+    }
+}
+
 /// Info about one ancestor in the chain
 struct AncestorInfo<'a> {
     symbol: &'a skeleton::SkeletonSymbol,
@@ -392,6 +546,7 @@ pub fn cmd_view_symbol(
     show_docs: bool,
     show_parent: bool,
     context: bool,
+    siblings: usize,
     json: bool,
     pretty: bool,
     use_colors: bool,
@@ -424,23 +579,36 @@ pub fn cmd_view_symbol(
     if let Some(source) = source_opt {
         let full_symbol_path = format!("{}/{}", file_path, symbol_path.join("/"));
 
+        // Extract skeleton if needed for parent, context or siblings
+        let skeleton_result = if show_parent || context || siblings > 0 {
+            let extractor = skeleton::SkeletonExtractor::new();
+            Some(extractor.extract(&full_path, &content))
+        } else {
+            None
+        };
+        let window = skeleton_result
+            .as_ref()
+            .filter(|_| siblings > 0)
+            .and_then(|sr| sibling_window(&sr.symbols, symbol_path, siblings, case_insensitive));
+
         if json {
             let imports: Vec<String> = deps_result
                 .imports
                 .iter()
                 .map(|i| i.format_summary())
                 .collect();
-            println!(
-                "{}",
-                serde_json::json!({
-                    "type": "symbol",
-                    "path": full_symbol_path,
-                    "file": file_path,
-                    "symbol": symbol_name,
-                    "imports": imports,
-                    "source": source
-                })
-            );
+            let mut output = serde_json::json!({
+                "type": "symbol",
+                "path": full_symbol_path,
+                "file": file_path,
+                "symbol": symbol_name,
+                "imports": imports,
+                "source": source
+            });
+            if let Some(window) = &window {
+                output["siblings"] = siblings_json(window);
+            }
+            println!("{}", output);
         } else {
             if depth >= 0 {
                 if let Some(sym) = parser.find_symbol(&full_path, &content, symbol_name) {
@@ -460,14 +628,6 @@ pub fn cmd_view_symbol(
                 }
             }
 
-            // Show ancestor context (extract skeleton if needed for parent or context)
-            let skeleton_result = if show_parent || context {
-                let extractor = skeleton::SkeletonExtractor::new();
-                Some(extractor.extract(&full_path, &content))
-            } else {
-                None
-            };
-
             let ancestors: Vec<(String, usize)> = if show_parent {
                 if let Some(ref sr) = skeleton_result {
                     let result =
@@ -496,13 +656,25 @@ pub fn cmd_view_symbol(
             } else {
                 source.clone()
             };
-            println!("{}", highlighted);
+            if let Some(window) = &window {
+                print_with_siblings(
+                    &source,
+                    &highlighted,
+                    window,
+                    grammar.as_deref(),
+                    use_colors,
+                );
+            } else {
+                println!("{}", highlighted);
+            }
 
-            if let Some((_, sibling_count)) = ancestors.last() {
-                if *sibling_count > 0 {
-                    println!();
-                    println!("    /* {} other members */", sibling_count);
-                }
+            // Siblings shown individually replace the member count
+            if let Some((_, sibling_count)) = ancestors.last()
+                && *sibling_count > 0
+                && window.is_none()
+            {
+                println!();
+                println!("    /* {} other members */", sibling_count);
             }
 
             // Show referenced type definitions when --context is used
@@ -540,20 +712,31 @@ pub fn cmd_view_symbol(
                 let start = sym.start_line - 1;
                 let end = std::cmp::min(sym.end_line, lines.len());
                 let source: String = lines[start..end].join("\n");
+                let window = (siblings > 0)
+                    .then(|| {
+                        sibling_window(
+                            &skeleton_result.symbols,
+                            symbol_path,
+                            siblings,
+                            case_insensitive,
+                        )
+                    })
+                    .flatten();
 
                 if json {
-                    println!(
-                        "{}",
-                        serde_json::json!({
-                            "type": "symbol",
-                            "path": full_symbol_path,
-                            "file": file_path,
-                            "symbol": symbol_name,
-                            "source": source,
-                            "start_line": sym.start_line,
-                            "end_line": sym.end_line
-                        })
-                    );
+                    let mut output = serde_json::json!({
+                        "type": "symbol",
+                        "path": full_symbol_path,
+                        "file": file_path,
+                        "symbol": symbol_name,
+                        "source": source,
+                        "start_line": sym.start_line,
+                        "end_line": sym.end_line
+                    });
+                    if let Some(window) = &window {
+                        output["siblings"] = siblings_json(window);
+                    }
+                    println!("{}", output);
                 } else {
                     if depth >= 0 {
                         println!(
@@ -577,7 +760,17 @@ pub fn cmd_view_symbol(
                     } else {
                         source.clone()
                     };
-                    println!("{}", highlighted);
+                    if let Some(window) = &window {
+                        print_with_siblings(
+                            &source,
+                            &highlighted,
+                            window,
+                            grammar.as_deref(),
+                            use_colors,
+                        );
+                    } else {
+                        println!("{}", highlighted);
+                    }
 
                     // Show referenced type definitions when --context is used
                     if context {
@@ -970,3 +1163,60 @@ pub fn cmd_view_symbol_glob(
 
     0
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhizome_moss_languages::{SymbolKind, Visibility};
+
+    fn symbol(name: &str, children: Vec<skeleton::SkeletonSymbol>) -> skeleton::SkeletonSymbol {
+        skeleton::SkeletonSymbol {
+            name: name.to_string(),
+            kind: SymbolKind::Function,
+            signature: format!("fn {}()", name),
+            docstring: None,
+            attributes: Vec::new(),
+            start_line: 1,
+            end_line: 1,
+            visibility: Visibility::default(),
+            children,
+            is_interface_impl: false,
+            implements: Vec::new(),
+        }
+    }
+
+    fn names(symbols: &[skeleton::SkeletonSymbol]) -> Vec<&str> {
+        symbols.iter().map(|s| s.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sibling_window() {
+        let methods = ["new", "get", "set", "len", "clear"]
+            .iter()
+            .map(|name| symbol(name, Vec::new()))
+            .collect();
+        let symbols = vec![
+            symbol("helper", Vec::new()),
+            symbol("Store", methods),
+            symbol("main", Vec::new()),
+        ];
+        let path = |p: &[&str]| p.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        let window = sibling_window(&symbols, &path(&["Store", "set"]), 1, false).unwrap();
+        assert_eq!(names(window.before), vec!["get"]);
+        assert_eq!(names(window.after), vec!["len"]);
+        assert_eq!((window.hidden_before, window.hidden_after), (1, 1));
+
+        // Single names are searched depth-first; windows clamp at the ends
+        let window = sibling_window(&symbols, &path(&["NEW"]), 2, true).unwrap();
+        assert!(window.before.is_empty());
+        assert_eq!(names(window.after), vec!["get", "set"]);
+        assert_eq!((window.hidden_before, window.hidden_after), (0, 2));
+
+        let window = sibling_window(&symbols, &path(&["main"]), 5, false).unwrap();
+        assert_eq!(names(window.before), vec!["helper", "Store"]);
+        assert!(window.after.is_empty());
+
+        assert!(sibling_window(&symbols, &path(&["Store", "main"]), 1, false).is_none());
+    }
+}
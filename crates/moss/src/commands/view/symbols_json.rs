@@ -0,0 +1,201 @@
+//! Flat symbol export for `view --symbols-json`.
+//!
+//! One entry per symbol with its parent path instead of nesting, so external
+//! indexers (editor plugins, ctags-style tools) can consume moss's extraction
+//! without following the tree view's output format.
+
+use crate::filter::Filter;
+use crate::output::OutputFormat;
+use crate::skeleton::{SkeletonExtractor, SkeletonSymbol};
+use serde::Serialize;
+use std::path::Path;
+
+/// A symbol in the flat export.
+#[derive(Debug, Serialize)]
+pub struct SymbolEntry {
+    /// File path relative to the root
+    pub file: String,
+    pub name: String,
+    pub kind: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    /// Path of the enclosing symbol (`Outer/Inner`), None at top level
+    pub parent: Option<String>,
+    pub signature: String,
+    /// Attributes, decorators or annotations, as written in the source
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<String>,
+}
+
+/// Print the symbols of a file, or of every supported file under a directory.
+pub fn cmd_symbols_json(
+    target: Option<&str>,
+    root: &Path,
+    show_tests: bool,
+    filter: Option<&Filter>,
+    format: &OutputFormat,
+) -> i32 {
+    let target = target.unwrap_or(".");
+    let target_path = root.join(target);
+
+    let files: Vec<String> = if target_path.is_file() {
+        vec![target.to_string()]
+    } else if target_path.is_dir() {
+        let prefix = target.trim_start_matches("./").trim_end_matches('/');
+        let prefix = if prefix == "." { "" } else { prefix };
+        let mut files: Vec<String> = crate::path_resolve::all_files(root)
+            .into_iter()
+            .filter(|f| f.kind == "file")
+            .map(|f| f.path)
+            .filter(|path| {
+                prefix.is_empty()
+                    || path
+                        .strip_prefix(prefix)
+                        .is_some_and(|rest| rest.starts_with('/'))
+            })
+            .filter(|path| filter.is_none_or(|f| f.matches(Path::new(path))))
+            .collect();
+        files.sort();
+        files
+    } else {
+        eprintln!(
+            "error: --symbols-json requires a file or directory target: {}",
+            target
+        );
+        return 1;
+    };
+
+    let extractor = SkeletonExtractor::new();
+    let mut entries = Vec::new();
+    for file in &files {
+        let path = root.join(file);
+        if rhizome_moss_languages::support_for_path(&path).is_none() {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let result = extractor.extract(&path, &content);
+        let result = if show_tests {
+            result
+        } else {
+            result.filter_tests()
+        };
+        flatten_symbols(file, &result.symbols, None, &mut entries);
+    }
+
+    let value = serde_json::to_value(&entries).unwrap_or_default();
+    match format {
+        OutputFormat::Jq(filter) => match crate::output::apply_jq(&value, filter) {
+            Ok(results) => {
+                for result in results {
+                    println!("{}", result);
+                }
+            }
+            Err(e) => {
+                eprintln!("jq error: {}", e);
+                return 1;
+            }
+        },
+        _ => println!("{}", value),
+    }
+    0
+}
+
+/// Append `symbols` and all their descendants, depth-first in source order.
+fn flatten_symbols(
+    file: &str,
+    symbols: &[SkeletonSymbol],
+    parent: Option<&str>,
+    entries: &mut Vec<SymbolEntry>,
+) {
+    for sym in symbols {
+        entries.push(SymbolEntry {
+            file: file.to_string(),
+            name: sym.name.clone(),
+            kind: sym.kind.as_str().to_string(),
+            start_line: sym.start_line,
+            end_line: sym.end_line,
+            parent: parent.map(String::from),
+            signature: sym.signature.clone(),
+            attributes: sym.attributes.clone(),
+        });
+        let path = match parent {
+            Some(parent) => format!("{}/{}", parent, sym.name),
+            None => sym.name.clone(),
+        };
+        flatten_symbols(file, &sym.children, Some(&path), entries);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhizome_moss_languages::{SymbolKind, Visibility};
+
+    fn symbol(name: &str, kind: SymbolKind, children: Vec<SkeletonSymbol>) -> SkeletonSymbol {
+        SkeletonSymbol {
+            name: name.to_string(),
+            kind,
+            signature: format!("{} {}", kind.as_str(), name),
+            docstring: None,
+            attributes: Vec::new(),
+            start_line: 1,
+            end_line: 10,
+            visibility: Visibility::Public,
+            children,
+            is_interface_impl: false,
+            implements: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_flatten_symbols() {
+        let symbols = vec![
+            symbol(
+                "Outer",
+                SymbolKind::Class,
+                vec![symbol(
+                    "Inner",
+                    SymbolKind::Class,
+                    vec![symbol("run", SymbolKind::Method, Vec::new())],
+                )],
+            ),
+            symbol("main", SymbolKind::Function, Vec::new()),
+        ];
+        let mut entries = Vec::new();
+        flatten_symbols("src/app.py", &symbols, None, &mut entries);
+
+        let summary: Vec<_> = entries
+            .iter()
+            .map(|e| (e.name.as_str(), e.parent.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Outer", None),
+                ("Inner", Some("Outer")),
+                ("run", Some("Outer/Inner")),
+                ("main", None),
+            ]
+        );
+        assert_eq!(entries[2].kind, "method");
+        assert!(entries.iter().all(|e| e.file == "src/app.py"));
+    }
+
+    #[test]
+    fn test_attributes_serialized_when_present() {
+        let mut route = symbol("index", SymbolKind::Function, Vec::new());
+        route.attributes = vec!["@app.get(\"/\")".to_string()];
+        let symbols = vec![route, symbol("main", SymbolKind::Function, Vec::new())];
+        let mut entries = Vec::new();
+        flatten_symbols("app.py", &symbols, None, &mut entries);
+
+        let value = serde_json::to_value(&entries).unwrap();
+        assert_eq!(
+            value[0]["attributes"],
+            serde_json::json!(["@app.get(\"/\")"])
+        );
+        assert!(value[1].get("attributes").is_none());
+    }
+}
@@ -1,9 +1,13 @@
 //! Directory tree viewing for view command.
 
+use super::blame::GitBlame;
+use super::git_status::GitChanges;
 use super::search::has_language_support;
-use crate::filter::Filter;
+use crate::filter::{Filter, format_kinds};
 use crate::tree::{FormatOptions, ViewNode, ViewNodeKind};
 use crate::{path_resolve, symbols, tree};
+use rhizome_moss_languages::SymbolKind;
+use std::collections::HashSet;
 use std::path::Path;
 
 /// Counts of files and directories in a tree.
@@ -12,41 +16,59 @@ struct NodeCounts {
     dirs: usize,
 }
 
-/// View a directory as a tree
+/// View a directory as a tree.
+///
+/// `depth` controls symbol expansion and, unless `tree_depth` is given, also
+/// how many directory levels are shown.
 #[allow(clippy::too_many_arguments)]
 pub fn cmd_view_directory(
     dir: &Path,
     _root: &Path,
     depth: i32,
+    tree_depth: Option<usize>,
+    dirs_only: bool,
     raw: bool,
     json: bool,
     pretty: bool,
     use_colors: bool,
     filter: Option<&Filter>,
+    git_status: bool,
+    blame: bool,
 ) -> i32 {
-    let effective_depth = if depth < 0 {
-        None
-    } else {
-        Some(depth as usize)
+    let effective_depth = match tree_depth {
+        Some(levels) => Some(levels),
+        None if depth < 0 => None,
+        None => Some(depth as usize),
     };
 
     let include_symbols = depth > 1 || depth < 0;
 
-    let view_node = tree::generate_view_tree(
+    if dirs_only && filter.is_some() {
+        eprintln!("warning: --exclude/--only match files and are ignored with --dirs-only");
+    }
+
+    let mut view_node = tree::generate_view_tree(
         dir,
         &tree::TreeOptions {
             max_depth: effective_depth,
             collapse_single: !raw,
             include_symbols,
+            dirs_only,
             ..Default::default()
         },
     );
 
-    let view_node = if let Some(f) = filter {
-        filter_view_node(view_node, f)
-    } else {
-        view_node
+    if git_status && let Some(changes) = GitChanges::load(dir) {
+        changes.annotate_tree(&mut view_node, dir, dirs_only);
+    }
+
+    let mut view_node = match filter {
+        Some(f) if !dirs_only => filter_view_node(view_node, f),
+        _ => view_node,
     };
+    if blame && let Some(blame) = GitBlame::load(dir) {
+        blame.annotate_tree(&mut view_node, dir);
+    }
 
     fn count_nodes(node: &ViewNode) -> NodeCounts {
         let mut counts = NodeCounts { files: 0, dirs: 0 };
@@ -80,7 +102,11 @@ pub fn cmd_view_directory(
             println!("{}", line);
         }
         println!();
-        println!("{} directories, {} files", dir_count, file_count);
+        if dirs_only {
+            println!("{} directories", dir_count);
+        } else {
+            println!("{} directories, {} files", dir_count, file_count);
+        }
     }
     0
 }
@@ -93,6 +119,8 @@ fn filter_view_node(mut node: ViewNode, filter: &Filter) -> ViewNode {
         .filter_map(|child| {
             let path = std::path::Path::new(&child.path);
             match child.kind {
+                // Contents cut off by the depth limit can't be judged; keep them
+                ViewNodeKind::Directory if child.truncated => Some(child),
                 ViewNodeKind::Directory => {
                     let filtered = filter_view_node(child, filter);
                     if filtered.children.is_empty() {
@@ -115,23 +143,8 @@ fn filter_view_node(mut node: ViewNode, filter: &Filter) -> ViewNode {
     node
 }
 
-/// List symbols matching a kind filter within a scope
-pub fn cmd_view_filtered(root: &Path, scope: &str, kind: &str, json: bool) -> i32 {
-    let kind_lower = kind.to_lowercase();
-    let kind_filter = match kind_lower.as_str() {
-        "class" | "classes" => Some("class"),
-        "function" | "functions" | "func" | "fn" => Some("function"),
-        "method" | "methods" => Some("method"),
-        "all" | "*" => None,
-        _ => {
-            eprintln!(
-                "Unknown type: {}. Valid types: class, function, method",
-                kind
-            );
-            return 1;
-        }
-    };
-
+/// List symbols whose kind is in `kinds` within a scope
+pub fn cmd_view_filtered(root: &Path, scope: &str, kinds: &HashSet<SymbolKind>, json: bool) -> i32 {
     let files_to_search: Vec<std::path::PathBuf> = if scope == "." {
         path_resolve::all_files(root)
             .into_iter()
@@ -164,16 +177,13 @@ pub fn cmd_view_filtered(root: &Path, scope: &str, kind: &str, json: bool) -> i3
 
         let syms = parser.parse_file(&file_path, &content);
         for sym in syms {
-            let sym_kind = sym.kind.as_str();
-            if let Some(filter) = kind_filter {
-                if sym_kind != filter {
-                    continue;
-                }
+            if !kinds.contains(&sym.kind) {
+                continue;
             }
             all_symbols.push((
                 rel_path.clone(),
                 sym.name,
-                sym_kind.to_string(),
+                sym.kind.as_str().to_string(),
                 sym.start_line,
                 sym.parent,
             ));
@@ -184,7 +194,7 @@ pub fn cmd_view_filtered(root: &Path, scope: &str, kind: &str, json: bool) -> i3
         if json {
             println!("[]");
         } else {
-            eprintln!("No symbols found matching type: {}", kind);
+            eprintln!("No symbols found matching kind: {}", format_kinds(kinds));
         }
         return 1;
     }
@@ -83,7 +83,9 @@ pub struct IndexConfig {
 #[serde(default)]
 pub struct AliasConfig {
     /// Map alias names to paths/patterns. Empty array disables the alias.
+    /// Merges key-wise: a project alias replaces the global one of the same name.
     #[serde(flatten)]
+    #[merge(strategy = "extend")]
     pub entries: HashMap<String, Vec<String>>,
 }
 
@@ -239,8 +241,13 @@ impl MossConfig {
         Self::default()
     }
 
+    /// JSON Schema for the full config file.
+    pub fn json_schema() -> serde_json::Value {
+        serde_json::to_value(schemars::schema_for!(MossConfig)).unwrap_or_default()
+    }
+
     /// Get the global config path.
-    fn global_config_path() -> Option<std::path::PathBuf> {
+    pub fn global_config_path() -> Option<std::path::PathBuf> {
         let config_home = std::env::var("XDG_CONFIG_HOME")
             .map(std::path::PathBuf::from)
             .ok()
@@ -379,6 +386,71 @@ config = []
         assert!(merged.daemon.auto_start());
     }
 
+    #[test]
+    fn test_merge_aliases_keywise() {
+        let global = MossConfig {
+            aliases: AliasConfig {
+                entries: [
+                    ("vendor".to_string(), vec!["vendor/**".to_string()]),
+                    ("tests".to_string(), vec!["t/**".to_string()]),
+                ]
+                .into_iter()
+                .collect(),
+            },
+            ..Default::default()
+        };
+        let project = MossConfig {
+            aliases: AliasConfig {
+                entries: [("tests".to_string(), vec![])].into_iter().collect(),
+            },
+            ..Default::default()
+        };
+
+        let merged = global.merge(project);
+        assert_eq!(
+            merged.aliases.entries.get("vendor"),
+            Some(&vec!["vendor/**".to_string()])
+        );
+        // Project alias replaces (here: disables) the global one rather than appending
+        assert_eq!(merged.aliases.entries.get("tests"), Some(&vec![]));
+    }
+
+    #[test]
+    fn test_merge_vec_fields() {
+        let global: MossConfig = toml::from_str(
+            r#"
+[analyze]
+hotspots_exclude = ["*.lock"]
+
+[analyze.rules."rust/unwrap"]
+allow = ["tests/**"]
+"#,
+        )
+        .unwrap();
+        let project: MossConfig = toml::from_str(
+            r#"
+[analyze]
+hotspots_exclude = ["generated/**"]
+
+[analyze.rules."rust/unwrap"]
+allow = ["benches/**"]
+"#,
+        )
+        .unwrap();
+
+        let merged = global.merge(project);
+        // hotspots_exclude opts into append
+        assert_eq!(
+            merged.analyze.hotspots_exclude,
+            vec!["*.lock".to_string(), "generated/**".to_string()]
+        );
+        // Other Vec fields keep replace semantics
+        assert_eq!(
+            merged.analyze.rules.0["rust/unwrap"].allow,
+            vec!["benches/**".to_string()]
+        );
+    }
+
     #[test]
     fn test_pretty_config() {
         use crate::output::ColorMode;
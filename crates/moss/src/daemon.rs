@@ -2,13 +2,15 @@
 //!
 //! The daemon watches file changes across registered roots and incrementally
 //! refreshes their indexes. Index queries go directly to SQLite files.
+//! Activity is logged to `daemon.log` next to the socket (see `moss daemon logs`).
 
 use crate::config::MossConfig;
 use crate::index::FileIndex;
 use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
 use rhizome_moss_derive::Merge;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{File, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
 use std::os::unix::net::UnixStream;
 use std::path::{Path, PathBuf};
@@ -26,6 +28,18 @@ pub fn global_socket_path() -> PathBuf {
         .join("daemon.sock")
 }
 
+/// Get global daemon log path (~/.config/moss/daemon.log)
+pub fn global_log_path() -> PathBuf {
+    global_socket_path().with_file_name("daemon.log")
+}
+
+/// Path a log is rotated to (`daemon.log.1`).
+pub fn rotated_log_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".1");
+    PathBuf::from(name)
+}
+
 /// Daemon configuration.
 #[derive(Debug, Clone, Deserialize, serde::Serialize, Merge, Default, schemars::JsonSchema)]
 #[serde(default)]
@@ -206,6 +220,214 @@ impl Default for DaemonClient {
     }
 }
 
+// ============================================================================
+// Daemon Log
+// ============================================================================
+
+/// Size at which the log is rotated, replacing the previous rotated log.
+const LOG_MAX_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    fn as_str(self) -> &'static str {
+        match self {
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+}
+
+/// A parsed daemon log line.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct LogEntry {
+    pub ts: String,
+    pub level: String,
+    pub event: String,
+    #[serde(flatten)]
+    pub fields: BTreeMap<String, String>,
+}
+
+/// Format a log line: `<rfc3339> <LEVEL> <event> key=value ...`. Values that
+/// are empty or contain spaces, quotes or `=` are quoted.
+fn format_log_line(ts: &str, level: LogLevel, event: &str, fields: &[LogField]) -> String {
+    let mut line = format!("{} {:<5} {}", ts, level.as_str(), event);
+    for LogField { key, value } in fields {
+        let quote = value.is_empty()
+            || value
+                .chars()
+                .any(|c| c.is_whitespace() || c == '"' || c == '=' || c.is_control());
+        if quote {
+            line.push_str(&format!(" {}={:?}", key, value));
+        } else {
+            line.push_str(&format!(" {}={}", key, value));
+        }
+    }
+    line
+}
+
+/// Parse a line written by `format_log_line`.
+pub fn parse_log_line(line: &str) -> Option<LogEntry> {
+    let mut rest = line.trim_end();
+    let mut next_word = || {
+        let (word, tail) = rest.split_once(' ').unwrap_or((rest, ""));
+        rest = tail.trim_start();
+        word
+    };
+    let ts = next_word().to_string();
+    let level = next_word().to_string();
+    let event = next_word().to_string();
+    if event.is_empty() {
+        return None;
+    }
+
+    let mut fields = BTreeMap::new();
+    while !rest.is_empty() {
+        let (key, tail) = rest.split_once('=')?;
+        let Quoted { value, rest: tail } = match tail.strip_prefix('"') {
+            Some(quoted) => parse_quoted(quoted)?,
+            None => {
+                let (value, tail) = tail.split_once(' ').unwrap_or((tail, ""));
+                Quoted {
+                    value: value.to_string(),
+                    rest: tail,
+                }
+            }
+        };
+        fields.insert(key.to_string(), value);
+        rest = tail.trim_start();
+    }
+    Some(LogEntry {
+        ts,
+        level,
+        event,
+        fields,
+    })
+}
+
+/// A `{:?}`-quoted value, unescaped, and the text after its closing quote.
+struct Quoted<'a> {
+    value: String,
+    rest: &'a str,
+}
+
+/// Unescape a `{:?}`-quoted value (after its opening quote).
+fn parse_quoted(s: &str) -> Option<Quoted<'_>> {
+    let mut value = String::new();
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => {
+                return Some(Quoted {
+                    value,
+                    rest: &s[i + 1..],
+                });
+            }
+            '\\' => match chars.next()?.1 {
+                'n' => value.push('\n'),
+                'r' => value.push('\r'),
+                't' => value.push('\t'),
+                '0' => value.push('\0'),
+                // `\u{1b}`: other control characters
+                'u' => {
+                    let (start, _) = chars.next().filter(|&(_, c)| c == '{')?;
+                    let (end, _) = chars.find(|&(_, c)| c == '}')?;
+                    let code = u32::from_str_radix(&s[start + 1..end], 16).ok()?;
+                    value.push(char::from_u32(code)?);
+                }
+                other => value.push(other),
+            },
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+/// Daemon activity log, appended one line per event and echoed to stderr.
+struct DaemonLog {
+    path: PathBuf,
+    max_bytes: u64,
+    file: Mutex<Option<File>>,
+}
+
+impl DaemonLog {
+    fn new(path: PathBuf, max_bytes: u64) -> Self {
+        Self {
+            path,
+            max_bytes,
+            file: Mutex::new(None),
+        }
+    }
+
+    fn open(&self) -> Option<File> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .ok()
+    }
+
+    fn write(&self, level: LogLevel, event: &str, fields: &[LogField]) {
+        let ts = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        let line = format_log_line(&ts, level, event, fields);
+        eprintln!("{}", line);
+
+        let mut file = self.file.lock().unwrap();
+        if file
+            .as_ref()
+            .and_then(|f| f.metadata().ok())
+            .is_some_and(|m| m.len() >= self.max_bytes)
+        {
+            *file = None;
+            let _ = std::fs::rename(&self.path, rotated_log_path(&self.path));
+        }
+        if file.is_none() {
+            *file = self.open();
+        }
+        if let Some(f) = file.as_mut() {
+            let _ = writeln!(f, "{}", line);
+        }
+    }
+
+    fn info(&self, event: &str, fields: &[LogField]) {
+        self.write(LogLevel::Info, event, fields);
+    }
+
+    fn warn(&self, event: &str, fields: &[LogField]) {
+        self.write(LogLevel::Warn, event, fields);
+    }
+
+    fn error(&self, event: &str, fields: &[LogField]) {
+        self.write(LogLevel::Error, event, fields);
+    }
+}
+
+/// One `key=value` field of a log line.
+struct LogField {
+    key: &'static str,
+    value: String,
+}
+
+impl LogField {
+    fn new(key: &'static str, value: String) -> Self {
+        Self { key, value }
+    }
+}
+
+fn root_field(root: &Path) -> LogField {
+    LogField::new("root", root.display().to_string())
+}
+
+fn ms_field(start: Instant) -> LogField {
+    LogField::new("ms", start.elapsed().as_millis().to_string())
+}
+
 // ============================================================================
 // Daemon Server
 // ============================================================================
@@ -222,14 +444,16 @@ struct DaemonServer {
     roots: Mutex<HashMap<PathBuf, WatchedRoot>>,
     refresh_tx: Sender<PathBuf>,
     start_time: Instant,
+    log: DaemonLog,
 }
 
 impl DaemonServer {
-    fn new(refresh_tx: Sender<PathBuf>) -> Self {
+    fn new(refresh_tx: Sender<PathBuf>, log: DaemonLog) -> Self {
         Self {
             roots: Mutex::new(HashMap::new()),
             refresh_tx,
             start_time: Instant::now(),
+            log,
         }
     }
 
@@ -247,6 +471,7 @@ impl DaemonServer {
         }
 
         // Initial index refresh
+        let start = Instant::now();
         let rt = tokio::runtime::Runtime::new().unwrap();
         match rt.block_on(FileIndex::open(&root)) {
             Ok(mut idx) => {
@@ -254,7 +479,10 @@ impl DaemonServer {
                     return Response::err(&format!("Failed to index: {}", e));
                 }
                 if let Err(e) = rt.block_on(idx.incremental_call_graph_refresh()) {
-                    eprintln!("Warning: call graph refresh failed: {}", e);
+                    self.log.warn(
+                        "call_graph_failed",
+                        &[root_field(&root), LogField::new("error", e.to_string())],
+                    );
                 }
             }
             Err(e) => return Response::err(&format!("Failed to open index: {}", e)),
@@ -305,6 +533,8 @@ impl DaemonServer {
                 last_refresh: Instant::now(),
             },
         );
+        self.log
+            .info("watch", &[root_field(&root), ms_field(start)]);
 
         Response::ok(serde_json::json!({"added": true, "root": root}))
     }
@@ -312,6 +542,7 @@ impl DaemonServer {
     fn remove_root(&self, root: &Path) -> Response {
         let mut roots = self.roots.lock().unwrap();
         if roots.remove(root).is_some() {
+            self.log.info("unwatch", &[root_field(root)]);
             Response::ok(serde_json::json!({"removed": true}))
         } else {
             Response::ok(serde_json::json!({"removed": false, "reason": "not watching"}))
@@ -334,27 +565,66 @@ impl DaemonServer {
     }
 
     fn handle_request(&self, req: Request) -> Response {
-        match req {
+        let start = Instant::now();
+        let (cmd, root) = match &req {
+            Request::Add { root } => ("add", Some(root.clone())),
+            Request::Remove { root } => ("remove", Some(root.clone())),
+            Request::List => ("list", None),
+            Request::Status => ("status", None),
+            Request::Shutdown => ("shutdown", None),
+        };
+        let response = match req {
             Request::Add { root } => self.add_root(root),
             Request::Remove { root } => self.remove_root(&root),
             Request::List => self.list_roots(),
             Request::Status => self.status(),
             Request::Shutdown => Response::ok(serde_json::json!({"message": "shutting down"})),
+        };
+
+        // Every client probes liveness with `status`, so only failures are logged
+        if response.ok && cmd == "status" {
+            return response;
         }
+        let mut fields = vec![LogField::new("cmd", cmd.to_string())];
+        fields.extend(root.as_deref().map(root_field));
+        fields.push(ms_field(start));
+        match &response.error {
+            None => self.log.info("request", &fields),
+            Some(error) => {
+                fields.push(LogField::new("error", error.clone()));
+                self.log.error("request", &fields);
+            }
+        }
+        response
     }
 
     fn refresh_root(&self, root: &Path) {
         let mut roots = self.roots.lock().unwrap();
         if let Some(watched) = roots.get_mut(root) {
+            let start = Instant::now();
             let rt = tokio::runtime::Runtime::new().unwrap();
             match rt.block_on(FileIndex::open(root)) {
                 Ok(mut idx) => {
-                    if let Err(e) = rt.block_on(idx.incremental_refresh()) {
-                        eprintln!("Refresh error for {:?}: {}", root, e);
+                    match rt.block_on(idx.incremental_refresh()) {
+                        Ok(changed) => self.log.info(
+                            "reindex",
+                            &[
+                                root_field(root),
+                                LogField::new("files", changed.to_string()),
+                                ms_field(start),
+                            ],
+                        ),
+                        Err(e) => self.log.error(
+                            "reindex_failed",
+                            &[root_field(root), LogField::new("error", e.to_string())],
+                        ),
                     }
                     watched.last_refresh = Instant::now();
                 }
-                Err(e) => eprintln!("Failed to open index for {:?}: {}", root, e),
+                Err(e) => self.log.error(
+                    "index_open_failed",
+                    &[root_field(root), LogField::new("error", e.to_string())],
+                ),
             }
         }
     }
@@ -376,7 +646,8 @@ pub async fn run_daemon() -> Result<i32, Box<dyn std::error::Error>> {
     // Channel for refresh requests from watchers
     let (refresh_tx, refresh_rx) = channel::<PathBuf>();
 
-    let server = Arc::new(DaemonServer::new(refresh_tx));
+    let log = DaemonLog::new(global_log_path(), LOG_MAX_BYTES);
+    let server = Arc::new(DaemonServer::new(refresh_tx, log));
 
     // Spawn refresh handler
     let server_refresh = server.clone();
@@ -388,7 +659,13 @@ pub async fn run_daemon() -> Result<i32, Box<dyn std::error::Error>> {
 
     // Start socket server
     let listener = UnixListener::bind(&socket_path)?;
-    eprintln!("Daemon listening on {}", socket_path.display());
+    server.log.info(
+        "start",
+        &[
+            LogField::new("pid", std::process::id().to_string()),
+            LogField::new("socket", socket_path.display().to_string()),
+        ],
+    );
 
     loop {
         let (stream, _) = listener.accept().await?;
@@ -406,10 +683,28 @@ pub async fn run_daemon() -> Result<i32, Box<dyn std::error::Error>> {
                         let resp_str = serde_json::to_string(&resp).unwrap();
                         let _ = writer.write_all(resp_str.as_bytes()).await;
                         let _ = writer.write_all(b"\n").await;
+                        server.log.info(
+                            "stop",
+                            &[LogField::new(
+                                "uptime_secs",
+                                server.start_time.elapsed().as_secs().to_string(),
+                            )],
+                        );
                         std::process::exit(0);
                     }
-                    Ok(req) => server.handle_request(req),
-                    Err(e) => Response::err(&format!("Invalid request: {}", e)),
+                    // Handlers block on their own runtimes, which can't run on a worker thread
+                    Ok(req) => {
+                        let server = server.clone();
+                        tokio::task::spawn_blocking(move || server.handle_request(req))
+                            .await
+                            .unwrap_or_else(|e| Response::err(&format!("Request failed: {}", e)))
+                    }
+                    Err(e) => {
+                        server
+                            .log
+                            .warn("invalid_request", &[LogField::new("error", e.to_string())]);
+                        Response::err(&format!("Invalid request: {}", e))
+                    }
                 };
 
                 let resp_str = serde_json::to_string(&response).unwrap();
@@ -438,3 +733,65 @@ pub fn maybe_start_daemon(root: &Path) {
         let _ = client.add_root(root);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_line_roundtrip() {
+        let line = format_log_line(
+            "2026-01-02T03:04:05.678Z",
+            LogLevel::Error,
+            "reindex_failed",
+            &[
+                LogField::new("root", "/home/me/my project".to_string()),
+                LogField::new("error", "bad \"thing\"\nhappened".to_string()),
+                LogField::new("ms", "12".to_string()),
+                LogField::new("note", String::new()),
+                LogField::new("color", "\u{1b}[31mred\u{1b}[0m\u{7f}".to_string()),
+            ],
+        );
+        assert!(
+            line.starts_with(
+                "2026-01-02T03:04:05.678Z ERROR reindex_failed root=\"/home/me/my project\""
+            ),
+            "{}",
+            line
+        );
+        assert!(!line.contains('\n'));
+
+        let entry = parse_log_line(&line).unwrap();
+        assert_eq!(entry.level, "ERROR");
+        assert_eq!(entry.event, "reindex_failed");
+        assert_eq!(entry.fields["root"], "/home/me/my project");
+        assert_eq!(entry.fields["error"], "bad \"thing\"\nhappened");
+        assert_eq!(entry.fields["ms"], "12");
+        assert_eq!(entry.fields["note"], "");
+        assert_eq!(entry.fields["color"], "\u{1b}[31mred\u{1b}[0m\u{7f}");
+
+        let info = parse_log_line("2026-01-02T03:04:05.678Z INFO  start pid=42").unwrap();
+        assert_eq!(info.level, "INFO");
+        assert_eq!(info.event, "start");
+        assert_eq!(info.fields["pid"], "42");
+        assert!(parse_log_line("garbage").is_none());
+    }
+
+    #[test]
+    fn test_log_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("daemon.log");
+        let log = DaemonLog::new(path.clone(), 100);
+        for i in 0..10 {
+            log.info("request", &[LogField::new("n", i.to_string())]);
+        }
+
+        let current = std::fs::read_to_string(&path).unwrap();
+        let rotated = std::fs::read_to_string(rotated_log_path(&path)).unwrap();
+        assert!(current.ends_with("n=9\n"), "{}", current);
+        assert!(!rotated.is_empty());
+        assert!((rotated.len() as u64) < 100 + 60);
+        // Only one rotated file is kept, so the oldest entries are gone
+        assert!(!rotated.contains("n=0\n"));
+    }
+}
@@ -6,6 +6,7 @@ use crate::parsers;
 use rhizome_moss_languages::{
     Export, Import, Language, SymbolKind, support_for_grammar, support_for_path,
 };
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use tree_sitter;
 
@@ -572,6 +573,80 @@ impl DepsExtractor {
     }
 }
 
+/// Resolves imports to project files: with the language's local import
+/// resolution (`crate::`, `super::`, relative paths), then by module name when
+/// exactly one project file has that name.
+pub struct LocalImportResolver<'a> {
+    root: &'a Path,
+    files: HashSet<&'a str>,
+    /// Module name to the one file with that name, or None when several share it
+    by_module: HashMap<String, Option<&'a str>>,
+}
+
+impl<'a> LocalImportResolver<'a> {
+    /// Resolver over `files`, relative to `root`.
+    pub fn new(root: &'a Path, files: &'a [String]) -> Self {
+        let mut by_module: HashMap<String, Option<&str>> = HashMap::new();
+        for file in files {
+            let path = Path::new(file);
+            if let Some(name) =
+                support_for_path(path).and_then(|lang| lang.file_path_to_module_name(path))
+            {
+                by_module
+                    .entry(name)
+                    .and_modify(|f| *f = None)
+                    .or_insert(Some(file));
+            }
+        }
+        Self {
+            root,
+            files: files.iter().map(|f| f.as_str()).collect(),
+            by_module,
+        }
+    }
+
+    /// The project file that `file` imports `name` from: from `module`, or the
+    /// module `name` itself when `module` is None (`import name`).
+    pub fn resolve(&self, file: &str, module: Option<&str>, name: &str) -> Option<&'a str> {
+        let lang = support_for_path(Path::new(file))?;
+        // `from pkg import mod` and `use crate::a::b` may import a module by
+        // name, so the most specific path comes first
+        let candidates = match module {
+            Some(module) if name != "*" => vec![join_module(module, name), module.to_string()],
+            Some(module) => vec![module.to_string()],
+            None => vec![name.to_string()],
+        };
+        for candidate in candidates {
+            if let Some(path) =
+                lang.resolve_local_import(&candidate, &self.root.join(file), self.root)
+                && let Ok(rel) = path.strip_prefix(self.root)
+                && let Some(rel) = rel.to_str()
+                && let Some(&known) = self.files.get(rel)
+            {
+                return Some(known);
+            }
+            if let Some(Some(target)) = self.by_module.get(&candidate) {
+                return Some(target);
+            }
+        }
+        None
+    }
+}
+
+/// Import path of `name` inside `module`: `a::b` + `c` is `a::b::c`, `pkg` +
+/// `mod` is `pkg.mod`, and Python's relative `..` + `mod` is `..mod`.
+fn join_module(module: &str, name: &str) -> String {
+    if module.contains("::") || matches!(module, "crate" | "self" | "super") {
+        format!("{}::{}", module, name)
+    } else if module.contains('/') {
+        format!("{}/{}", module, name)
+    } else if module.ends_with('.') {
+        format!("{}{}", module, name)
+    } else {
+        format!("{}.{}", module, name)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -813,4 +888,13 @@ const message = ref('Hello World');
             "Should have app.js import"
         );
     }
+
+    #[test]
+    fn test_join_module() {
+        assert_eq!(join_module("crate::util", "fs"), "crate::util::fs");
+        assert_eq!(join_module("super", "parser"), "super::parser");
+        assert_eq!(join_module("pkg.sub", "mod"), "pkg.sub.mod");
+        assert_eq!(join_module("..", "mod"), "..mod");
+        assert_eq!(join_module("./lib", "x"), "./lib/x");
+    }
 }
@@ -60,41 +60,27 @@ impl Editor {
         let extractor = SkeletonExtractor::new();
         let result = extractor.extract(path, content);
 
-        fn search_symbols(
-            symbols: &[crate::skeleton::SkeletonSymbol],
-            name: &str,
-            content: &str,
-            case_insensitive: bool,
-        ) -> Option<SymbolLocation> {
-            for sym in symbols {
-                let matches = if case_insensitive {
-                    sym.name.eq_ignore_ascii_case(name)
+        let sym = result
+            .symbols
+            .iter()
+            .flat_map(|s| s.iter_flat())
+            .find(|s| {
+                if case_insensitive {
+                    s.name.eq_ignore_ascii_case(name)
                 } else {
-                    sym.name == name
-                };
-                if matches {
-                    let start_byte = line_to_byte(content, sym.start_line);
-                    let end_byte = line_to_byte(content, sym.end_line + 1);
-
-                    return Some(SymbolLocation {
-                        name: sym.name.clone(),
-                        kind: sym.kind.as_str().to_string(),
-                        start_byte,
-                        end_byte,
-                        start_line: sym.start_line,
-                        end_line: sym.end_line,
-                        indent: String::new(),
-                    });
-                }
-                // Search children
-                if let Some(loc) = search_symbols(&sym.children, name, content, case_insensitive) {
-                    return Some(loc);
+                    s.name == name
                 }
-            }
-            None
-        }
-
-        search_symbols(&result.symbols, name, content, case_insensitive)
+            })?;
+
+        Some(SymbolLocation {
+            name: sym.name.clone(),
+            kind: sym.kind.as_str().to_string(),
+            start_byte: line_to_byte(content, sym.start_line),
+            end_byte: line_to_byte(content, sym.end_line + 1),
+            start_line: sym.start_line,
+            end_line: sym.end_line,
+            indent: String::new(),
+        })
     }
 
     /// Check if a pattern contains glob characters (delegates to path_resolve)
@@ -343,33 +329,21 @@ impl Editor {
         let extractor = SkeletonExtractor::new();
         let result = extractor.extract(path, content);
 
-        fn search_symbols(
-            symbols: &[crate::skeleton::SkeletonSymbol],
-            name: &str,
-            content: &str,
-        ) -> Option<ContainerBody> {
-            for sym in symbols {
-                if sym.name == name && sym.kind == SymbolKind::Heading {
-                    // For markdown: body starts after heading line, ends at section end
-                    let content_start = line_to_byte(content, sym.start_line + 1);
-                    let content_end = line_to_byte(content, sym.end_line + 1);
-
-                    return Some(ContainerBody {
-                        content_start,
-                        content_end,
-                        inner_indent: String::new(),
-                        is_empty: content_start >= content_end,
-                    });
-                }
-                // Search children
-                if let Some(body) = search_symbols(&sym.children, name, content) {
-                    return Some(body);
-                }
-            }
-            None
-        }
+        // For markdown: body starts after heading line, ends at section end
+        let sym = result
+            .symbols
+            .iter()
+            .flat_map(|s| s.iter_flat())
+            .find(|s| s.name == name && s.kind == SymbolKind::Heading)?;
+        let content_start = line_to_byte(content, sym.start_line + 1);
+        let content_end = line_to_byte(content, sym.end_line + 1);
 
-        search_symbols(&result.symbols, name, content)
+        Some(ContainerBody {
+            content_start,
+            content_end,
+            inner_indent: String::new(),
+            is_empty: content_start >= content_end,
+        })
     }
 
     fn find_container_body_with_trait(
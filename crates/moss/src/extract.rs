@@ -21,6 +21,9 @@ pub struct ExtractResult {
 pub struct ExtractOptions {
     /// Include private/non-public symbols (default: true for code exploration)
     pub include_private: bool,
+    /// Only extract top-level declarations: container bodies and nested
+    /// definitions are not visited (default: false)
+    pub top_level_only: bool,
 }
 
 impl Default for ExtractOptions {
@@ -29,6 +32,7 @@ impl Default for ExtractOptions {
             // Default to including all symbols - moss is for code exploration,
             // not API documentation. This ensures trait impl methods are visible.
             include_private: true,
+            top_level_only: false,
         }
     }
 }
@@ -290,13 +294,22 @@ impl Extractor {
                         symbols.push(sym);
                     }
                 }
+                if self.options.top_level_only {
+                    if cursor.goto_next_sibling() {
+                        continue;
+                    }
+                    break;
+                }
             }
             // Check if this is a container (class, impl, module)
             else if support.container_kinds().contains(&kind) {
                 if let Some(mut sym) = support.extract_container(&node, content) {
                     if self.should_include(&sym) {
                         // Recurse into container body
-                        if let Some(body) = support.container_body(&node) {
+                        if let Some(body) = support
+                            .container_body(&node)
+                            .filter(|_| !self.options.top_level_only)
+                        {
                             let mut body_cursor = body.walk();
                             if body_cursor.goto_first_child() {
                                 self.collect_symbols(
@@ -332,6 +345,12 @@ impl Extractor {
                         symbols.push(sym);
                     }
                 }
+                if self.options.top_level_only {
+                    if cursor.goto_next_sibling() {
+                        continue;
+                    }
+                    break;
+                }
             }
 
             // Descend into children for other nodes
@@ -594,38 +613,6 @@ fn adjust_lines(sym: &mut Symbol, offset: usize) {
     }
 }
 
-/// Compute cyclomatic complexity for a function node.
-pub fn compute_complexity(node: &tree_sitter::Node, support: &dyn Language) -> usize {
-    let mut complexity = 1; // Base complexity
-    let complexity_nodes = support.complexity_nodes();
-    let mut cursor = node.walk();
-
-    if !cursor.goto_first_child() {
-        return complexity;
-    }
-
-    loop {
-        if complexity_nodes.contains(&cursor.node().kind()) {
-            complexity += 1;
-        }
-
-        if cursor.goto_first_child() {
-            continue;
-        }
-        if cursor.goto_next_sibling() {
-            continue;
-        }
-        loop {
-            if !cursor.goto_parent() {
-                return complexity;
-            }
-            if cursor.goto_next_sibling() {
-                break;
-            }
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -687,6 +674,7 @@ impl Foo {
     fn test_include_private() {
         let extractor = Extractor::with_options(ExtractOptions {
             include_private: true,
+            top_level_only: false,
         });
         let content = r#"
 fn private_fn() {}
@@ -698,6 +686,27 @@ pub fn public_fn() {}
         assert!(names.contains(&"public_fn"));
     }
 
+    #[test]
+    fn test_top_level_only() {
+        let extractor = Extractor::with_options(ExtractOptions {
+            include_private: true,
+            top_level_only: true,
+        });
+        let content = r#"
+def outer():
+    def inner():
+        pass
+
+class Bar:
+    def method(self):
+        pass
+"#;
+        let result = extractor.extract(&PathBuf::from("test.py"), content);
+        let names: Vec<_> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["outer", "Bar"]);
+        assert!(result.symbols.iter().all(|s| s.children.is_empty()));
+    }
+
     #[test]
     fn test_typescript_interface_impl_detection() {
         let extractor = Extractor::new();
@@ -746,6 +755,102 @@ class Foo implements IFoo {
         );
     }
 
+    /// Attributes of the symbol at `path` (top-level name, then child names).
+    fn attributes(symbols: &[Symbol], path: &[&str]) -> Vec<String> {
+        let (first, rest) = path.split_first().unwrap();
+        let symbol = symbols.iter().find(|s| s.name == *first).unwrap();
+        if rest.is_empty() {
+            symbol.attributes.clone()
+        } else {
+            attributes(&symbol.children, rest)
+        }
+    }
+
+    #[test]
+    fn test_extract_java_annotations() {
+        if crate::parsers::parser_for("java").is_none() {
+            return;
+        }
+        let content = r#"
+@Service
+@Deprecated(since = "2")
+public class Users {
+    @Inject
+    private Repo repo;
+
+    @Test
+    public void loads() {}
+}
+"#;
+        let result = Extractor::new().extract(&PathBuf::from("Users.java"), content);
+        assert_eq!(
+            attributes(&result.symbols, &["Users"]),
+            vec!["@Service", "@Deprecated(since = \"2\")"]
+        );
+        assert_eq!(
+            attributes(&result.symbols, &["Users", "loads"]),
+            vec!["@Test"]
+        );
+    }
+
+    #[test]
+    fn test_extract_kotlin_annotations() {
+        if crate::parsers::parser_for("kotlin").is_none() {
+            return;
+        }
+        let content = r#"
+@Serializable
+data class User(val name: String)
+
+class UserTest {
+    @Test
+    fun loads() {}
+}
+"#;
+        let result = Extractor::new().extract(&PathBuf::from("User.kt"), content);
+        assert_eq!(
+            attributes(&result.symbols, &["User"]),
+            vec!["@Serializable"]
+        );
+        assert_eq!(
+            attributes(&result.symbols, &["UserTest", "loads"]),
+            vec!["@Test"]
+        );
+    }
+
+    #[test]
+    fn test_extract_typescript_decorators() {
+        if crate::parsers::parser_for("typescript").is_none() {
+            return;
+        }
+        let content = r#"
+@Component({ selector: "app" })
+export class App {
+    @Input() name: string;
+
+    @HostListener("click")
+    @Debounce(10)
+    onClick() {}
+}
+
+export @Injectable() class Service {}
+"#;
+        let result = Extractor::new().extract(&PathBuf::from("app.ts"), content);
+        // Each decorator once, in source order
+        assert_eq!(
+            attributes(&result.symbols, &["App"]),
+            vec!["@Component({ selector: \"app\" })"]
+        );
+        assert_eq!(
+            attributes(&result.symbols, &["App", "onClick"]),
+            vec!["@HostListener(\"click\")", "@Debounce(10)"]
+        );
+        assert_eq!(
+            attributes(&result.symbols, &["Service"]),
+            vec!["@Injectable()"]
+        );
+    }
+
     #[test]
     fn test_cross_file_interface_impl_with_mock_resolver() {
         // Mock resolver that returns methods for IRemote interface
@@ -1,14 +1,18 @@
-//! Filter system for --exclude and --only flags.
+//! Filter system for --exclude and --only flags, --exclude-tests/--only-tests,
+//! and --kind symbol filters.
 //!
 //! Supports:
 //! - Glob patterns: `--exclude="*_test.go"`, `--only="*.rs"`
 //! - Aliases: `--exclude=@tests`, `--only=@docs`
+//! - Test code: `--exclude-tests`, `--only-tests` (by symbol, see [`TestScope`])
 //!
 //! Built-in aliases are language-aware (e.g., @tests includes `*_test.go` for Go,
 //! `test_*.py` for Python). Config can override or add new aliases via `[aliases]`.
 
 use crate::config::AliasConfig;
 use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use rhizome_moss_languages::{LineRange, Symbol, SymbolKind, support_for_path, test_line_ranges};
+use std::collections::HashSet;
 use std::path::Path;
 
 /// Status of an alias (for display purposes).
@@ -52,6 +56,8 @@ pub struct Filter {
     only_matcher: Option<Gitignore>,
     /// Warnings accumulated during construction
     warnings: Vec<String>,
+    /// Which functions to keep by whether they are tests
+    tests: TestScope,
 }
 
 impl Filter {
@@ -95,9 +101,21 @@ impl Filter {
             exclude_matcher,
             only_matcher,
             warnings,
+            tests: TestScope::All,
         })
     }
 
+    /// Also filter functions by whether they are tests.
+    pub fn with_tests(mut self, tests: TestScope) -> Self {
+        self.tests = tests;
+        self
+    }
+
+    /// Which functions to keep by whether they are tests.
+    pub fn tests(&self) -> TestScope {
+        self.tests
+    }
+
     /// Get warnings from filter construction.
     pub fn warnings(&self) -> &[String] {
         &self.warnings
@@ -127,7 +145,84 @@ impl Filter {
     /// Check if any filters are active.
     #[allow(dead_code)]
     pub fn is_active(&self) -> bool {
-        self.exclude_matcher.is_some() || self.only_matcher.is_some()
+        self.exclude_matcher.is_some()
+            || self.only_matcher.is_some()
+            || self.tests != TestScope::All
+    }
+}
+
+/// Which functions to keep by whether they are test code.
+///
+/// A function is test code if it, or a symbol it is nested in, is a test by its
+/// language's [`Language::is_test_symbol`](rhizome_moss_languages::Language::is_test_symbol)
+/// (`#[test]` or a `#[cfg(test)]` module in Rust, `test_*`/`Test*` or pytest
+/// decorators in Python, `TestXxx` in Go, ...).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TestScope {
+    /// Tests and everything else
+    #[default]
+    All,
+    /// Everything but tests (`--exclude-tests`)
+    Exclude,
+    /// Only tests (`--only-tests`)
+    Only,
+}
+
+impl TestScope {
+    pub fn from_flags(exclude_tests: bool, only_tests: bool) -> Self {
+        if only_tests {
+            TestScope::Only
+        } else if exclude_tests {
+            TestScope::Exclude
+        } else {
+            TestScope::All
+        }
+    }
+}
+
+/// Test code of one file, for applying a [`TestScope`] to its functions.
+pub struct TestLines {
+    scope: TestScope,
+    ranges: Vec<LineRange>,
+}
+
+impl TestLines {
+    /// Find the test code of `path` by extracting its symbols; nothing is
+    /// extracted if `scope` keeps everything.
+    pub fn new(scope: TestScope, path: &Path, content: &str) -> Self {
+        if scope == TestScope::All {
+            return Self::from_ranges(scope, Vec::new());
+        }
+        let symbols = crate::extract::Extractor::new()
+            .extract(path, content)
+            .symbols;
+        Self::from_symbols(scope, path, &symbols)
+    }
+
+    /// Test code among symbols already extracted from `path`.
+    pub fn from_symbols(scope: TestScope, path: &Path, symbols: &[Symbol]) -> Self {
+        let ranges = match (scope, support_for_path(path)) {
+            (TestScope::All, _) | (_, None) => Vec::new(),
+            (_, Some(lang)) if lang.is_test_file(path) => vec![LineRange {
+                start: 1,
+                end: usize::MAX,
+            }],
+            (_, Some(lang)) => test_line_ranges(lang, symbols),
+        };
+        Self::from_ranges(scope, ranges)
+    }
+
+    fn from_ranges(scope: TestScope, ranges: Vec<LineRange>) -> Self {
+        Self { scope, ranges }
+    }
+
+    /// Whether the scope keeps a function starting at `line`.
+    pub fn keeps(&self, line: usize) -> bool {
+        match self.scope {
+            TestScope::All => true,
+            TestScope::Exclude => !self.ranges.iter().any(|r| r.contains(line)),
+            TestScope::Only => self.ranges.iter().any(|r| r.contains(line)),
+        }
     }
 }
 
@@ -254,6 +349,49 @@ pub fn list_aliases(config: &AliasConfig, languages: &[&str]) -> Vec<ResolvedAli
     aliases
 }
 
+/// Parse `--kind` values into a set of symbol kinds.
+///
+/// Values may be repeated or comma-separated (`--kind function,method`);
+/// `all` or `*` selects every kind. No values yields an empty set, meaning
+/// no kind filter.
+pub fn parse_kinds(values: &[String]) -> Result<HashSet<SymbolKind>, String> {
+    let mut kinds = HashSet::new();
+    for value in values.iter().flat_map(|v| v.split(',')) {
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+        if value == "all" || value == "*" {
+            kinds.extend(SymbolKind::ALL.iter().copied());
+            continue;
+        }
+        match SymbolKind::from_name(value) {
+            Some(kind) => {
+                kinds.insert(kind);
+            }
+            None => {
+                let valid: Vec<&str> = SymbolKind::ALL.iter().map(|k| k.as_str()).collect();
+                return Err(format!(
+                    "unknown kind: {}. Valid kinds: {}",
+                    value,
+                    valid.join(", ")
+                ));
+            }
+        }
+    }
+    Ok(kinds)
+}
+
+/// Format a kind set for messages, in canonical kind order.
+pub fn format_kinds(kinds: &HashSet<SymbolKind>) -> String {
+    SymbolKind::ALL
+        .iter()
+        .filter(|k| kinds.contains(k))
+        .map(|k| k.as_str())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -343,4 +481,51 @@ mod tests {
         let docs = aliases.iter().find(|a| a.name == "docs").unwrap();
         assert_eq!(docs.status, AliasStatus::Builtin);
     }
+
+    #[test]
+    fn test_parse_kinds() {
+        let kinds = parse_kinds(&["function,methods".to_string()]).unwrap();
+        assert_eq!(
+            kinds,
+            HashSet::from([SymbolKind::Function, SymbolKind::Method])
+        );
+
+        let kinds = parse_kinds(&["struct".to_string(), "enum, trait".to_string()]).unwrap();
+        assert_eq!(format_kinds(&kinds), "struct,enum,trait");
+
+        assert!(parse_kinds(&[]).unwrap().is_empty());
+        assert_eq!(
+            parse_kinds(&["all".to_string()]).unwrap().len(),
+            SymbolKind::ALL.len()
+        );
+        assert!(parse_kinds(&["function,bogus".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_test_lines() {
+        let ranges = vec![
+            LineRange { start: 10, end: 20 },
+            LineRange { start: 30, end: 31 },
+        ];
+        let keeps = |scope| {
+            let lines = TestLines::from_ranges(scope, ranges.clone());
+            [1, 10, 15, 25, 31].map(|line| lines.keeps(line))
+        };
+        assert_eq!(keeps(TestScope::All), [true; 5]);
+        assert_eq!(keeps(TestScope::Exclude), [true, false, false, true, false]);
+        assert_eq!(keeps(TestScope::Only), [false, true, true, false, true]);
+        assert_eq!(TestScope::from_flags(true, false), TestScope::Exclude);
+        assert_eq!(TestScope::from_flags(false, true), TestScope::Only);
+    }
+
+    #[test]
+    fn test_test_files() {
+        let lines = TestLines::from_symbols(TestScope::Exclude, Path::new("add.test.ts"), &[]);
+        assert!(!lines.keeps(1));
+        assert!(!lines.keeps(500));
+        let lines = TestLines::from_symbols(TestScope::Only, Path::new("add.ts"), &[]);
+        assert!(!lines.keeps(1));
+        let lines = TestLines::from_symbols(TestScope::Only, Path::new("client_test.go"), &[]);
+        assert!(lines.keeps(1));
+    }
 }
@@ -0,0 +1,215 @@
+//! Changed line ranges from `git diff`.
+//!
+//! Line ranges are 1-based and inclusive, in the new version of each file.
+
+use rhizome_moss_languages::LineRange;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Changed line ranges by file.
+pub type DiffHunks = HashMap<PathBuf, Vec<LineRange>>;
+
+/// Lines changed in the working tree relative to `base`, by path relative to
+/// `root`. Only files under `root` are included. Untracked files (other than
+/// ignored ones) are changed in their entirety.
+pub fn changed_lines(root: &Path, base: &str) -> Result<DiffHunks, String> {
+    let output = Command::new("git")
+        .args([
+            "diff",
+            "--no-color",
+            "--no-ext-diff",
+            "--relative",
+            "-U0",
+            base,
+            "--",
+        ])
+        .current_dir(root)
+        .output()
+        .map_err(|e| format!("failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git diff {} failed: {}",
+            base,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let mut files = parse_diff(&String::from_utf8_lossy(&output.stdout));
+
+    let output = Command::new("git")
+        .args(["ls-files", "--others", "--exclude-standard", "-z"])
+        .current_dir(root)
+        .output()
+        .map_err(|e| format!("failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git ls-files failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    for path in String::from_utf8_lossy(&output.stdout).split('\0') {
+        if !path.is_empty() {
+            files.insert(PathBuf::from(path), vec![whole_file()]);
+        }
+    }
+    Ok(files)
+}
+
+/// The range covering every line of a file.
+pub fn whole_file() -> LineRange {
+    LineRange {
+        start: 1,
+        end: usize::MAX,
+    }
+}
+
+/// Split a unified diff into each new file's changed line ranges. Deleted files
+/// are left out.
+pub fn parse_diff(diff: &str) -> DiffHunks {
+    let mut files = DiffHunks::new();
+    let mut current: Option<PathBuf> = None;
+    let mut prev = "";
+    for line in diff.lines() {
+        // An added line can start with "++ " too; the header follows "--- "
+        let header = prev.starts_with("--- ");
+        prev = line;
+        if header && let Some(path) = line.strip_prefix("+++ ") {
+            current = match path {
+                "/dev/null" => None,
+                _ => Some(PathBuf::from(path.strip_prefix("b/").unwrap_or(path))),
+            };
+        } else if line.starts_with("@@ ")
+            && let Some(path) = &current
+        {
+            files
+                .entry(path.clone())
+                .or_default()
+                .extend(parse_hunks(line));
+        }
+    }
+    files
+}
+
+/// New-file line ranges from the `@@ -a,b +c,d @@` headers of a unified diff.
+///
+/// A pure deletion (`d == 0`) is recorded as the line it follows, so the symbol
+/// it was removed from still counts as changed.
+pub fn parse_hunks(diff: &str) -> Vec<LineRange> {
+    diff.lines()
+        .filter_map(|line| {
+            let new_range = line
+                .strip_prefix("@@ ")?
+                .split_whitespace()
+                .find(|part| part.starts_with('+'))?
+                .trim_start_matches('+');
+            let (start, count) = match new_range.split_once(',') {
+                Some((start, count)) => (start.parse::<usize>().ok()?, count.parse().ok()?),
+                None => (new_range.parse::<usize>().ok()?, 1),
+            };
+            Some(if count == 0 {
+                LineRange {
+                    start: start.max(1),
+                    end: start.max(1),
+                }
+            } else {
+                LineRange {
+                    start,
+                    end: start + count - 1,
+                }
+            })
+        })
+        .collect()
+}
+
+/// Whether `line` falls in one of the ranges.
+pub fn contains_line(hunks: &[LineRange], line: usize) -> bool {
+    hunks.iter().any(|range| range.contains(line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: usize, end: usize) -> LineRange {
+        LineRange { start, end }
+    }
+
+    #[test]
+    fn test_parse_hunks() {
+        let diff = "diff --git a/x b/x\n@@ -3 +3 @@ fn a()\n@@ -10,2 +10,4 @@\n@@ -20,3 +21,0 @@\n";
+        assert_eq!(
+            parse_hunks(diff),
+            vec![range(3, 3), range(10, 13), range(21, 21)]
+        );
+    }
+
+    #[test]
+    fn test_parse_diff() {
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -3 +3 @@ fn a()
+-old
++new
+@@ -10,0 +11,2 @@
++added
++added
+diff --git a/gone.rs b/gone.rs
+deleted file mode 100644
+--- a/gone.rs
++++ /dev/null
+@@ -1,2 +0,0 @@
+-x
+-y
+diff --git a/new.rs b/new.rs
+new file mode 100644
+--- /dev/null
++++ b/new.rs
+@@ -0,0 +1,3 @@
++a
++b
++c
+";
+        let files = parse_diff(diff);
+        assert_eq!(files.len(), 2);
+        assert_eq!(
+            files[Path::new("src/lib.rs")],
+            vec![range(3, 3), range(11, 12)]
+        );
+        assert_eq!(files[Path::new("new.rs")], vec![range(1, 3)]);
+
+        assert!(contains_line(&files[Path::new("src/lib.rs")], 12));
+        assert!(!contains_line(&files[Path::new("src/lib.rs")], 4));
+    }
+
+    #[test]
+    fn test_changed_lines_untracked() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?}", args);
+        };
+        git(&["init", "-q"]);
+        std::fs::write(dir.path().join(".gitignore"), "ignored.rs\n").unwrap();
+        std::fs::write(dir.path().join("tracked.rs"), "a\nb\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-qm", "init"]);
+
+        std::fs::write(dir.path().join("tracked.rs"), "a\nc\n").unwrap();
+        std::fs::write(dir.path().join("new.rs"), "x\n").unwrap();
+        std::fs::write(dir.path().join("ignored.rs"), "y\n").unwrap();
+
+        let files = changed_lines(dir.path(), "HEAD").unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[Path::new("tracked.rs")], vec![range(2, 2)]);
+        assert_eq!(files[Path::new("new.rs")], vec![whole_file()]);
+    }
+}
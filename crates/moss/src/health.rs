@@ -4,7 +4,7 @@
 //! complexity summary, and structural metrics.
 
 use glob::Pattern;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 use crate::commands::analyze::complexity::analyze_codebase_complexity;
@@ -277,7 +277,8 @@ struct ComplexityStats {
 }
 
 fn compute_complexity_stats(root: &Path, allowlist: &[String]) -> ComplexityStats {
-    let report = analyze_codebase_complexity(root, usize::MAX, None, None, allowlist);
+    let report =
+        analyze_codebase_complexity(root, usize::MAX, None, &HashSet::new(), None, allowlist);
     ComplexityStats {
         total_functions: report.functions.len(),
         avg_complexity: report.avg_complexity(),
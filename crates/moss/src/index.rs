@@ -73,6 +73,24 @@ pub struct ChangedFiles {
     pub deleted: Vec<String>,
 }
 
+/// An import of a symbol by name
+#[derive(Debug, Clone)]
+pub struct SymbolImport {
+    /// Importing file
+    pub file: String,
+    /// Source module, when the import names one
+    pub module: Option<String>,
+    pub line: usize,
+}
+
+/// A file that imports another file's module
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Importer {
+    pub file: String,
+    /// Line of its first import of the module
+    pub line: usize,
+}
+
 /// Call graph statistics
 #[derive(Debug, Clone, Copy, Default)]
 pub struct CallGraphStats {
@@ -888,6 +906,28 @@ impl FileIndex {
         Ok(names)
     }
 
+    /// `Parent::name` of every symbol nested in another
+    pub async fn qualified_symbol_names(
+        &self,
+    ) -> Result<std::collections::HashSet<String>, libsql::Error> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT DISTINCT parent, name FROM symbols WHERE parent IS NOT NULL",
+                (),
+            )
+            .await?;
+        let mut names = std::collections::HashSet::new();
+        while let Some(row) = rows.next().await? {
+            names.insert(format!(
+                "{}::{}",
+                row.get::<String>(0)?,
+                row.get::<String>(1)?
+            ));
+        }
+        Ok(names)
+    }
+
     /// Find symbols by name with fuzzy matching, optional kind filter, and limit
     pub async fn find_symbols(
         &self,
@@ -903,7 +943,16 @@ impl FileIndex {
         let mut symbols = Vec::new();
 
         if fuzzy {
-            let pattern = format!("%{}%", query_lower);
+            // Names containing the query's characters in order, so camelCase
+            // abbreviations are candidates too; see `symbol_rank` for ordering
+            let pattern = format!(
+                "%{}%",
+                query_lower
+                    .chars()
+                    .map(String::from)
+                    .collect::<Vec<_>>()
+                    .join("%")
+            );
             let mut rows = if let Some(k) = kind {
                 self.conn
                     .query(
@@ -1143,6 +1192,65 @@ impl FileIndex {
         Ok(importers)
     }
 
+    /// Other files importing the module defined in `file`, with the line of
+    /// their first such import. Imports match by module name, also behind a
+    /// prefix (`crate::util`, `pkg.util`).
+    pub async fn importers_of_file(&self, file: &str) -> Result<Vec<Importer>, libsql::Error> {
+        let path = Path::new(file);
+        let Some(module) =
+            support_for_path(path).and_then(|lang| lang.file_path_to_module_name(path))
+        else {
+            return Ok(Vec::new());
+        };
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT file, MIN(line) FROM imports
+                 WHERE file != ?1 AND (module = ?2 OR module LIKE ?3 OR module LIKE ?4)
+                 GROUP BY file ORDER BY file",
+                params![
+                    file,
+                    module.clone(),
+                    format!("%::{}", module),
+                    format!("%.{}", module)
+                ],
+            )
+            .await?;
+        let mut importers = Vec::new();
+        while let Some(row) = rows.next().await? {
+            importers.push(Importer {
+                file: row.get(0)?,
+                line: row.get::<i64>(1)? as usize,
+            });
+        }
+        Ok(importers)
+    }
+
+    /// Number of other files importing the module defined in `file`.
+    pub async fn count_importers(&self, file: &str) -> Result<usize, libsql::Error> {
+        Ok(self.importers_of_file(file).await?.len())
+    }
+
+    /// Find imports of a symbol by name
+    pub async fn find_imports_of(&self, name: &str) -> Result<Vec<SymbolImport>, libsql::Error> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT file, module, line FROM imports WHERE name = ?1",
+                params![name],
+            )
+            .await?;
+        let mut imports = Vec::new();
+        while let Some(row) = rows.next().await? {
+            imports.push(SymbolImport {
+                file: row.get(0)?,
+                module: row.get(1)?,
+                line: row.get::<i64>(2)? as usize,
+            });
+        }
+        Ok(imports)
+    }
+
     /// Get method names for a type (interface/class) in a specific file.
     /// Used for cross-file interface implementation detection.
     pub async fn get_type_methods(
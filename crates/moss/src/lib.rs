@@ -18,6 +18,7 @@
 //! ```
 
 pub mod analyze;
+pub mod codeowners;
 pub mod commands;
 pub mod config;
 pub mod daemon;
@@ -25,6 +26,7 @@ pub mod deps;
 pub mod edit;
 pub mod extract;
 pub mod filter;
+pub mod git_diff;
 pub mod health;
 pub mod index;
 pub mod output;
@@ -35,6 +37,7 @@ pub mod serve;
 pub mod sessions;
 pub mod shadow;
 pub mod skeleton;
+pub mod symbol_rank;
 pub mod symbols;
 pub mod text_search;
 pub mod tree;
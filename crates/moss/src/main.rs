@@ -9,6 +9,7 @@ use rhizome_moss::commands::context::ContextArgs;
 use rhizome_moss::commands::edit::EditArgs;
 use rhizome_moss::commands::generate::GenerateArgs;
 use rhizome_moss::commands::history::HistoryArgs;
+use rhizome_moss::commands::languages::LanguagesArgs;
 use rhizome_moss::commands::rules::RulesAction;
 use rhizome_moss::commands::sessions::SessionsArgs;
 use rhizome_moss::commands::text_search::TextSearchArgs;
@@ -38,6 +39,10 @@ struct Cli {
     /// Compact output without colors (overrides TTY detection)
     #[arg(long, global = true, conflicts_with = "pretty")]
     compact: bool,
+
+    /// Width of text tables (default: terminal width, else $COLUMNS, else 80)
+    #[arg(long, global = true, value_name = "N")]
+    width: Option<usize>,
 }
 
 #[derive(Subcommand)]
@@ -89,6 +94,9 @@ enum Commands {
     /// List filter aliases (used by --exclude/--only)
     Aliases(AliasesArgs),
 
+    /// Show supported languages and which analyses each provides
+    Languages(LanguagesArgs),
+
     /// Show directory context (hierarchical .context.md files)
     Context(ContextArgs),
 
@@ -99,7 +107,7 @@ enum Commands {
     /// Analyze Claude Code and other agent session logs
     Sessions(SessionsArgs),
 
-    /// Package management: info, list, tree, outdated
+    /// Package management: info, list, tree, outdated, search
     Package {
         #[command(subcommand)]
         action: commands::package::PackageAction,
@@ -134,6 +142,16 @@ enum Commands {
         #[command(subcommand)]
         action: RulesAction,
     },
+
+    /// Inspect and validate configuration (.moss/config.toml)
+    Config {
+        #[command(subcommand)]
+        action: commands::config::ConfigAction,
+
+        /// Root directory (defaults to current directory)
+        #[arg(short, long, global = true)]
+        root: Option<PathBuf>,
+    },
 }
 
 /// Help output styling.
@@ -195,7 +213,7 @@ fn handle_schema_flag() -> bool {
         let response = serde_json::json!({
             "config_path": ".moss/config.toml",
             "format": "toml",
-            "schema": schemars::schema_for!(rhizome_moss::config::MossConfig)
+            "schema": rhizome_moss::config::MossConfig::json_schema()
         });
         println!("{}", serde_json::to_string_pretty(&response).unwrap());
         true
@@ -229,6 +247,10 @@ fn main() {
         &config.pretty,
     );
 
+    if let Some(width) = cli.width {
+        rhizome_moss::output::set_width(width);
+    }
+
     let exit_code = match cli.command {
         Commands::View(args) => commands::view::run(args, format),
         Commands::Edit(args) => commands::edit::run(args, cli.json),
@@ -242,6 +264,7 @@ fn main() {
         Commands::Grammars { action } => commands::grammars::cmd_grammars(action, cli.json),
         Commands::Analyze(args) => commands::analyze::run(args, format),
         Commands::Aliases(args) => commands::aliases::run(args, cli.json),
+        Commands::Languages(args) => commands::languages::run(args, cli.json),
         Commands::Context(args) => commands::context::run(args, format),
         Commands::TextSearch(args) => commands::text_search::run(args, format),
         Commands::Sessions(args) => commands::sessions::run(args, cli.json, cli.pretty),
@@ -254,8 +277,11 @@ fn main() {
             commands::tools::run(action, root.as_deref(), format, cli.json)
         }
         Commands::Serve(args) => serve::run(args, cli.json),
-        Commands::Generate(args) => commands::generate::run(args),
+        Commands::Generate(args) => commands::generate::run(args, cli.json),
         Commands::Rules { action } => commands::rules::cmd_rules(action, cli.json),
+        Commands::Config { action, root } => {
+            commands::config::cmd_config(action, root.as_deref(), cli.json)
+        }
     };
 
     std::process::exit(exit_code);
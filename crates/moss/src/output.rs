@@ -5,7 +5,14 @@
 use rhizome_moss_core::Merge;
 use rhizome_moss_derive::Merge;
 use serde::{Deserialize, Serialize};
-use std::io::IsTerminal;
+use std::io::{IsTerminal, Write};
+use std::sync::OnceLock;
+
+/// Width set with `--width`, overriding terminal detection.
+static WIDTH_OVERRIDE: OnceLock<usize> = OnceLock::new();
+
+/// Width used when stdout is not a terminal and `COLUMNS` is unset.
+const DEFAULT_WIDTH: usize = 80;
 
 /// Color output mode.
 #[derive(
@@ -89,6 +96,24 @@ pub enum OutputFormat {
     Json,
     /// JSON filtered through jq expression.
     Jq(String),
+    /// CSV rows, for commands with tabular output (others print compact text).
+    Csv,
+    /// One JSON record per line, for reports that stream items (others print
+    /// compact text).
+    JsonLines,
+}
+
+/// Value of the `--format` flag of commands with tabular output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum FormatArg {
+    /// Text (compact or pretty, as selected by the global flags)
+    Text,
+    /// JSON (same as --json)
+    Json,
+    /// Comma-separated values with a header row
+    Csv,
+    /// Newline-delimited JSON: a header record, then one record per item
+    JsonLines,
 }
 
 impl OutputFormat {
@@ -135,11 +160,33 @@ impl OutputFormat {
         }
     }
 
+    /// Apply a command's `--format` flag over the format from the global flags.
+    pub fn with_format_arg(self, arg: Option<FormatArg>) -> Self {
+        match arg {
+            None => self,
+            Some(FormatArg::Csv) => OutputFormat::Csv,
+            Some(FormatArg::JsonLines) => OutputFormat::JsonLines,
+            Some(FormatArg::Json) => OutputFormat::Json,
+            Some(FormatArg::Text) if self.is_pretty() => self,
+            Some(FormatArg::Text) => OutputFormat::Compact,
+        }
+    }
+
     /// Is this a JSON-based format?
     pub fn is_json(&self) -> bool {
         matches!(self, OutputFormat::Json | OutputFormat::Jq(_))
     }
 
+    /// Is this CSV output?
+    pub fn is_csv(&self) -> bool {
+        matches!(self, OutputFormat::Csv)
+    }
+
+    /// Is this newline-delimited JSON?
+    pub fn is_json_lines(&self) -> bool {
+        matches!(self, OutputFormat::JsonLines)
+    }
+
     /// Is this pretty mode?
     pub fn is_pretty(&self) -> bool {
         matches!(self, OutputFormat::Pretty { .. })
@@ -168,9 +215,9 @@ pub trait OutputFormatter: Serialize {
     /// Print to stdout in the specified format.
     fn print(&self, format: &OutputFormat) {
         match format {
-            OutputFormat::Compact => println!("{}", self.format_text()),
+            OutputFormat::Compact | OutputFormat::Csv => println!("{}", self.format_text()),
             OutputFormat::Pretty { .. } => println!("{}", self.format_pretty()),
-            OutputFormat::Json => {
+            OutputFormat::Json | OutputFormat::JsonLines => {
                 println!("{}", serde_json::to_string(self).unwrap_or_default())
             }
             OutputFormat::Jq(filter) => {
@@ -190,6 +237,233 @@ pub trait OutputFormatter: Serialize {
     }
 }
 
+/// Builds CSV output (RFC 4180 quoting) for reports with one row per item.
+pub struct CsvWriter {
+    buf: String,
+}
+
+impl CsvWriter {
+    /// Start a CSV document with a header row.
+    pub fn new(headers: &[&str]) -> Self {
+        let mut writer = Self { buf: String::new() };
+        writer.push_row(headers.iter().map(|h| h.to_string()));
+        writer
+    }
+
+    /// Append a row. Fields containing commas, quotes or line breaks are quoted.
+    pub fn row(&mut self, fields: &[&dyn std::fmt::Display]) {
+        self.push_row(fields.iter().map(|f| f.to_string()));
+    }
+
+    fn push_row(&mut self, fields: impl Iterator<Item = String>) {
+        for (i, field) in fields.enumerate() {
+            if i > 0 {
+                self.buf.push(',');
+            }
+            if field.contains([',', '"', '\n', '\r']) {
+                self.buf.push('"');
+                self.buf.push_str(&field.replace('"', "\"\""));
+                self.buf.push('"');
+            } else {
+                self.buf.push_str(&field);
+            }
+        }
+        self.buf.push('\n');
+    }
+
+    /// The CSV text, one line per row.
+    pub fn finish(self) -> String {
+        self.buf
+    }
+}
+
+/// Writes newline-delimited JSON records as they are produced.
+///
+/// The first record describes the run (`"type": "header"`); every later record
+/// is one item of the report, tagged with its own `type`. Each line is flushed
+/// immediately so consumers can process results while analysis continues.
+pub struct JsonLinesWriter<W: Write> {
+    out: W,
+}
+
+impl JsonLinesWriter<std::io::StdoutLock<'static>> {
+    /// Write to stdout.
+    pub fn stdout() -> Self {
+        Self::new(std::io::stdout().lock())
+    }
+}
+
+impl<W: Write> JsonLinesWriter<W> {
+    pub fn new(out: W) -> Self {
+        Self { out }
+    }
+
+    /// Write the header record for `report`, with run details from `fields`.
+    pub fn header(&mut self, report: &str, fields: serde_json::Value) -> std::io::Result<()> {
+        let mut header = serde_json::json!({
+            "report": report,
+            "version": env!("CARGO_PKG_VERSION"),
+        });
+        if let (Some(header), serde_json::Value::Object(fields)) = (header.as_object_mut(), fields)
+        {
+            header.extend(fields);
+        }
+        self.record("header", &header)
+    }
+
+    /// Write one record of type `kind`. Objects get a `type` field; other values
+    /// are wrapped as `{"type": kind, "value": ...}`.
+    pub fn record(&mut self, kind: &str, item: &impl Serialize) -> std::io::Result<()> {
+        let value = serde_json::to_value(item).map_err(std::io::Error::other)?;
+        let mut record = serde_json::Map::new();
+        record.insert("type".to_string(), kind.into());
+        match value {
+            serde_json::Value::Object(fields) => record.extend(fields),
+            other => {
+                record.insert("value".to_string(), other);
+            }
+        }
+        serde_json::to_writer(&mut self.out, &record).map_err(std::io::Error::other)?;
+        self.out.write_all(b"\n")?;
+        self.out.flush()
+    }
+}
+
+/// Set the output width for the rest of the process (`--width`).
+pub fn set_width(width: usize) {
+    let _ = WIDTH_OVERRIDE.set(width);
+}
+
+/// Width available for text output: `--width`, else the terminal's width, else
+/// `COLUMNS`, else 80.
+pub fn terminal_width() -> usize {
+    if let Some(width) = WIDTH_OVERRIDE.get() {
+        return *width;
+    }
+    tty_width()
+        .or_else(|| {
+            std::env::var("COLUMNS")
+                .ok()
+                .and_then(|c| c.trim().parse().ok())
+                .filter(|&c| c > 0)
+        })
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+#[cfg(unix)]
+fn tty_width() -> Option<usize> {
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    // SAFETY: TIOCGWINSZ only writes a winsize struct, which we own
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    let ok = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } == 0;
+    (ok && size.ws_col > 0).then_some(size.ws_col as usize)
+}
+
+#[cfg(not(unix))]
+fn tty_width() -> Option<usize> {
+    None
+}
+
+/// A text table whose column widths come from its contents.
+///
+/// When the table is wider than the available width, the shrinkable column (if
+/// any) is narrowed, and its values are truncated from the start so that the
+/// end of a path stays visible.
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    right_aligned: Vec<bool>,
+    shrink: Option<usize>,
+}
+
+impl Table {
+    /// Narrowest a shrinkable column gets, so its values stay recognizable.
+    const MIN_SHRINK_WIDTH: usize = 12;
+
+    pub fn new(headers: &[&str]) -> Self {
+        Self {
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+            right_aligned: vec![false; headers.len()],
+            shrink: None,
+        }
+    }
+
+    /// Right-align a column (numbers).
+    pub fn align_right(mut self, column: usize) -> Self {
+        self.right_aligned[column] = true;
+        self
+    }
+
+    /// Let a column be narrowed to fit the width.
+    pub fn shrink_column(mut self, column: usize) -> Self {
+        self.shrink = Some(column);
+        self
+    }
+
+    pub fn row(&mut self, cells: Vec<String>) {
+        self.rows.push(cells);
+    }
+
+    /// Render the header, a rule, and the rows, fitting `width` where possible.
+    pub fn render(&self, width: usize) -> String {
+        let char_len = |s: &str| s.chars().count();
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| char_len(h)).collect();
+        for row in &self.rows {
+            for (w, cell) in widths.iter_mut().zip(row) {
+                *w = (*w).max(char_len(cell));
+            }
+        }
+        let total = |widths: &[usize]| widths.iter().sum::<usize>() + widths.len() - 1;
+        if let Some(col) = self.shrink {
+            let excess = total(&widths).saturating_sub(width);
+            let min = Self::MIN_SHRINK_WIDTH.min(widths[col]);
+            widths[col] = widths[col].saturating_sub(excess).max(min);
+        }
+
+        let mut out = String::new();
+        self.push_line(&mut out, &self.headers, &widths);
+        out.push_str(&"-".repeat(total(&widths)));
+        out.push('\n');
+        for row in &self.rows {
+            self.push_line(&mut out, row, &widths);
+        }
+        out
+    }
+
+    fn push_line(&self, out: &mut String, cells: &[String], widths: &[usize]) {
+        let mut line = String::new();
+        for (i, (cell, &width)) in cells.iter().zip(widths).enumerate() {
+            if i > 0 {
+                line.push(' ');
+            }
+            let cell = truncate_start(cell, width);
+            if self.right_aligned[i] {
+                line.push_str(&format!("{:>width$}", cell));
+            } else {
+                line.push_str(&format!("{:<width$}", cell));
+            }
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+}
+
+/// Keep the last `width` characters of `s`, marking a cut with a leading `...`.
+fn truncate_start(s: &str, width: usize) -> std::borrow::Cow<'_, str> {
+    let len = s.chars().count();
+    if len <= width {
+        return s.into();
+    }
+    if width <= 3 {
+        return s.chars().skip(len - width).collect::<String>().into();
+    }
+    let tail: String = s.chars().skip(len - (width - 3)).collect();
+    format!("...{}", tail).into()
+}
+
 /// Apply a jq filter to a JSON value.
 pub fn apply_jq(value: &serde_json::Value, filter: &str) -> Result<Vec<String>, String> {
     use jaq_core::load::{Arena, File as JaqFile, Loader};
@@ -265,6 +539,51 @@ mod tests {
             OutputFormat::from_cli(true, Some(".name"), false, false, &config),
             OutputFormat::Jq(".name".to_string())
         );
+        assert_eq!(
+            OutputFormat::Compact.with_format_arg(Some(FormatArg::Csv)),
+            OutputFormat::Csv
+        );
+        assert_eq!(
+            OutputFormat::Json.with_format_arg(Some(FormatArg::Text)),
+            OutputFormat::Compact
+        );
+    }
+
+    #[test]
+    fn test_csv_writer() {
+        let mut csv = CsvWriter::new(&["file", "symbol", "line"]);
+        csv.row(&[&"src/a.rs", &"Foo.bar", &12]);
+        csv.row(&[&"odd, \"name\".rs", &"x\ny", &1]);
+        assert_eq!(
+            csv.finish(),
+            "file,symbol,line\nsrc/a.rs,Foo.bar,12\n\"odd, \"\"name\"\".rs\",\"x\ny\",1\n"
+        );
+    }
+
+    #[test]
+    fn test_json_lines_writer() {
+        let mut buf = Vec::new();
+        let mut out = JsonLinesWriter::new(&mut buf);
+        out.header("complexity", serde_json::json!({"root": "."}))
+            .unwrap();
+        out.record("function", &serde_json::json!({"name": "f"}))
+            .unwrap();
+        out.record("count", &3).unwrap();
+
+        let lines: Vec<serde_json::Value> = std::str::from_utf8(&buf)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["type"], "header");
+        assert_eq!(lines[0]["report"], "complexity");
+        assert_eq!(lines[0]["root"], ".");
+        assert_eq!(
+            lines[1],
+            serde_json::json!({"type": "function", "name": "f"})
+        );
+        assert_eq!(lines[2], serde_json::json!({"type": "count", "value": 3}));
     }
 
     #[test]
@@ -315,4 +634,38 @@ mod tests {
         };
         assert!(!config.highlight());
     }
+
+    #[test]
+    fn test_table_render() {
+        let mut table = Table::new(&["File", "Commits"])
+            .align_right(1)
+            .shrink_column(0);
+        table.row(vec![
+            "src/commands/analyze/hotspots.rs".to_string(),
+            "12".to_string(),
+        ]);
+        table.row(vec!["lib.rs".to_string(), "3".to_string()]);
+
+        // Wide enough: columns sized to the data
+        assert_eq!(
+            table.render(80),
+            "File                             Commits\n\
+             ----------------------------------------\n\
+             src/commands/analyze/hotspots.rs      12\n\
+             lib.rs                                 3\n"
+        );
+
+        // Narrow: the path column gives up width, keeping the end of paths
+        assert_eq!(
+            table.render(26),
+            "File               Commits\n\
+             --------------------------\n\
+             ...yze/hotspots.rs      12\n\
+             lib.rs                   3\n"
+        );
+
+        // Never narrower than the minimum, even if the table overflows
+        let narrow = table.render(10);
+        assert!(narrow.lines().nth(2).unwrap().starts_with("...tspots.rs "));
+    }
 }
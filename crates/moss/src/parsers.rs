@@ -1,14 +1,27 @@
 //! Tree-sitter parser initialization and management.
 //!
 //! Provides free functions for parsing using a global singleton GrammarLoader.
+//! [`parse_cached`] keeps recent trees in a small per-process LRU cache, for
+//! commands that parse the same unchanged file more than once (e.g. the
+//! complexity and length passes of `analyze`). [`reparse`] updates a tree
+//! incrementally from edits.
 
+use lru::LruCache;
 use rhizome_moss_languages::GrammarLoader;
-use std::sync::{Arc, OnceLock};
-use tree_sitter::Parser;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex, OnceLock};
+use tree_sitter::{InputEdit, Parser, Tree};
 
 /// Global grammar loader singleton - avoids reloading grammars for each parse.
 static GRAMMAR_LOADER: OnceLock<Arc<GrammarLoader>> = OnceLock::new();
 
+/// Global parse tree cache used by [`parse_cached`].
+static TREE_CACHE: OnceLock<Mutex<TreeCache<Tree>>> = OnceLock::new();
+
+/// Number of trees kept by [`parse_cached`].
+const TREE_CACHE_ENTRIES: NonZeroUsize = NonZeroUsize::new(64).unwrap();
+
 /// Get the global grammar loader singleton.
 pub fn grammar_loader() -> Arc<GrammarLoader> {
     GRAMMAR_LOADER
@@ -29,12 +42,184 @@ pub fn parser_for(grammar: &str) -> Option<Parser> {
 /// Parse source code with a specific grammar.
 ///
 /// The grammar name should match tree-sitter grammar names (e.g., "python", "rust", "typescript").
-pub fn parse_with_grammar(grammar: &str, source: &str) -> Option<tree_sitter::Tree> {
+pub fn parse_with_grammar(grammar: &str, source: &str) -> Option<Tree> {
     let mut parser = parser_for(grammar)?;
     parser.parse(source, None)
 }
 
+/// Parse like [`parse_with_grammar`], reusing the tree when the same source was
+/// recently parsed with the same grammar. Only worth it where one command
+/// parses a file several times; one-off parses should not fill the cache.
+pub fn parse_cached(grammar: &str, source: &str) -> Option<Tree> {
+    if let Some(tree) = tree_cache().lock().unwrap().get(grammar, source) {
+        return Some(tree);
+    }
+    let tree = parse_with_grammar(grammar, source)?;
+    tree_cache()
+        .lock()
+        .unwrap()
+        .insert(grammar, source, tree.clone());
+    Some(tree)
+}
+
+/// Incrementally reparse `new_source` from the tree of the previous source.
+///
+/// `edits` describe how the previous source became `new_source`, in the order
+/// they were made; they are applied to a copy of `old_tree`, and tree-sitter
+/// reuses the unchanged parts of it. For watchers and editors, which see
+/// small edits to files they already parsed.
+pub fn reparse(
+    grammar: &str,
+    old_tree: &Tree,
+    edits: &[InputEdit],
+    new_source: &str,
+) -> Option<Tree> {
+    let mut edited = old_tree.clone();
+    for edit in edits {
+        edited.edit(edit);
+    }
+    parser_for(grammar)?.parse(new_source, Some(&edited))
+}
+
 /// List grammars available in external search paths.
 pub fn available_external_grammars() -> Vec<String> {
     grammar_loader().available_external()
 }
+
+fn tree_cache() -> &'static Mutex<TreeCache<Tree>> {
+    TREE_CACHE.get_or_init(|| Mutex::new(TreeCache::new(TREE_CACHE_ENTRIES)))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    grammar: String,
+    source_hash: u64,
+}
+
+impl CacheKey {
+    fn new(grammar: &str, source: &str) -> Self {
+        let mut hasher = DefaultHasher::new();
+        source.hash(&mut hasher);
+        Self {
+            grammar: grammar.to_string(),
+            source_hash: hasher.finish(),
+        }
+    }
+}
+
+/// A cached tree with the source it was parsed from.
+struct CachedTree<T> {
+    source: String,
+    tree: T,
+}
+
+/// Least-recently-used cache of parse trees, bounded by entry count.
+struct TreeCache<T> {
+    entries: LruCache<CacheKey, CachedTree<T>>,
+}
+
+impl<T: Clone> TreeCache<T> {
+    fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            entries: LruCache::new(capacity),
+        }
+    }
+
+    fn get(&mut self, grammar: &str, source: &str) -> Option<T> {
+        let cached = self.entries.get(&CacheKey::new(grammar, source))?;
+        // Compare sources so a hash collision can't return another file's tree.
+        // Cloning a tree is cheap: the syntax nodes are shared.
+        (cached.source == source).then(|| cached.tree.clone())
+    }
+
+    fn insert(&mut self, grammar: &str, source: &str, tree: T) {
+        self.entries.put(
+            CacheKey::new(grammar, source),
+            CachedTree {
+                source: source.to_string(),
+                tree,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tree_cache_evicts_least_recently_used() {
+        let mut cache = TreeCache::new(NonZeroUsize::new(2).unwrap());
+        cache.insert("rust", "aaaa", "a");
+        cache.insert("rust", "bbbb", "b");
+        assert_eq!(cache.get("rust", "aaaa"), Some("a"));
+
+        // b is the least recently used
+        cache.insert("rust", "cccc", "c");
+        assert_eq!(cache.get("rust", "bbbb"), None);
+        assert_eq!(cache.get("rust", "aaaa"), Some("a"));
+        assert_eq!(cache.get("rust", "cccc"), Some("c"));
+
+        // Same source under another grammar is a different entry
+        assert_eq!(cache.get("python", "aaaa"), None);
+    }
+
+    #[test]
+    fn test_tree_cache_compares_source() {
+        let mut cache = TreeCache::new(NonZeroUsize::new(2).unwrap());
+        // Simulate a hash collision: "bbbb" stored under the key of "aaaa"
+        cache.entries.put(
+            CacheKey::new("rust", "aaaa"),
+            CachedTree {
+                source: "bbbb".to_string(),
+                tree: "b",
+            },
+        );
+        assert_eq!(cache.get("rust", "aaaa"), None);
+    }
+
+    #[test]
+    fn test_reparse() {
+        // Skip if grammar not available
+        let old_source = "fn a() {}\nfn b() { 1 }\n";
+        let Some(old_tree) = parse_with_grammar("rust", old_source) else {
+            return;
+        };
+        // Rename `a` to `ab`, then change `1` to `22`
+        let new_source = "fn ab() {}\nfn b() { 22 }\n";
+        let point = tree_sitter::Point::new;
+        let edits = [
+            InputEdit {
+                start_byte: 4,
+                old_end_byte: 4,
+                new_end_byte: 5,
+                start_position: point(0, 4),
+                old_end_position: point(0, 4),
+                new_end_position: point(0, 5),
+            },
+            InputEdit {
+                start_byte: 20,
+                old_end_byte: 21,
+                new_end_byte: 22,
+                start_position: point(1, 9),
+                old_end_position: point(1, 10),
+                new_end_position: point(1, 11),
+            },
+        ];
+        let tree = reparse("rust", &old_tree, &edits, new_source).unwrap();
+        let fresh = parse_with_grammar("rust", new_source).unwrap();
+        assert_eq!(spans(&tree), spans(&fresh));
+    }
+
+    /// Kind and byte range of every node, depth-first.
+    fn spans(tree: &Tree) -> Vec<(&'static str, std::ops::Range<usize>)> {
+        let mut spans = Vec::new();
+        let mut stack = vec![tree.root_node()];
+        while let Some(node) = stack.pop() {
+            spans.push((node.kind(), node.byte_range()));
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
+        }
+        spans
+    }
+}
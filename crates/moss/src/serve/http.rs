@@ -2,16 +2,22 @@
 //!
 //! Exposes moss functionality over HTTP for integration with other tools.
 
+use crate::commands::analyze::report;
+use crate::commands::view::build_filter;
+use crate::filter::parse_kinds;
 use crate::index::FileIndex;
-use crate::skeleton::SkeletonExtractor;
+use crate::skeleton::{SkeletonExtractor, SymbolExt};
+use crate::text_search::{self, MatchScope};
+use crate::{path_resolve, tree};
 use axum::{
     Json, Router,
     extract::{Path, Query, State},
     http::StatusCode,
-    routing::get,
+    response::{IntoResponse, Response},
+    routing::{get, post},
 };
+use rhizome_moss_languages::support_for_path;
 use serde::{Deserialize, Serialize};
-use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use utoipa::{OpenApi, ToSchema};
@@ -24,8 +30,20 @@ use utoipa::{OpenApi, ToSchema};
         version = "0.1.0",
         description = "Code intelligence API for moss"
     ),
-    paths(health, list_files, get_file, list_symbols, get_symbol, search),
+    paths(
+        health,
+        list_files,
+        get_file,
+        list_symbols,
+        get_symbol,
+        search,
+        analyze,
+        view,
+        grep
+    ),
     components(schemas(
+        ErrorResponse,
+        AnalyzeRequest,
         HealthResponse,
         FileListResponse,
         FileInfoResponse,
@@ -45,8 +63,11 @@ struct AppState {
     index: Mutex<FileIndex>,
 }
 
-/// Start the HTTP server.
-pub async fn run_http_server(root: &std::path::Path, port: u16) -> i32 {
+/// Start the HTTP server on `host:port`.
+///
+/// The API has no authentication; callers bind to loopback unless the
+/// project config asks for another host.
+pub async fn run_http_server(root: &std::path::Path, host: &str, port: u16) -> i32 {
     // Initialize index
     let index = match FileIndex::open_if_enabled(root).await {
         Some(idx) => idx,
@@ -66,20 +87,23 @@ pub async fn run_http_server(root: &std::path::Path, port: u16) -> i32 {
         .route("/openapi.json", get(openapi_spec))
         .route("/health", get(health))
         .route("/files", get(list_files))
-        .route("/files/*path", get(get_file))
+        .route("/files/{*path}", get(get_file))
         .route("/symbols", get(list_symbols))
-        .route("/symbols/:name", get(get_symbol))
+        .route("/symbols/{name}", get(get_symbol))
         .route("/search", get(search))
+        .route("/analyze", post(analyze))
+        .route("/view", get(view))
+        .route("/grep", get(grep))
         .with_state(state);
 
-    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+    let addr = format!("{}:{}", host, port);
     eprintln!("HTTP server listening on http://{}", addr);
     eprintln!("OpenAPI spec available at http://{}/openapi.json", addr);
 
-    let listener = match tokio::net::TcpListener::bind(addr).await {
+    let listener = match tokio::net::TcpListener::bind(&addr).await {
         Ok(l) => l,
         Err(e) => {
-            eprintln!("Failed to bind to port {}: {}", port, e);
+            eprintln!("Failed to bind to {}: {}", addr, e);
             return 1;
         }
     };
@@ -200,6 +224,7 @@ pub struct SymbolInfo {
     ),
     responses(
         (status = 200, description = "File info with symbols", body = FileInfoResponse),
+        (status = 403, description = "Path is outside the project root"),
         (status = 404, description = "File not found")
     ),
     tag = "files"
@@ -212,6 +237,9 @@ async fn get_file(
     if !file_path.exists() {
         return Err(StatusCode::NOT_FOUND);
     }
+    if !is_within_root(&state.root, &file_path) {
+        return Err(StatusCode::FORBIDDEN);
+    }
 
     let content =
         std::fs::read_to_string(&file_path).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
@@ -448,3 +476,481 @@ async fn search(
 
     Json(SearchResponse { results })
 }
+
+/// Error body returned with non-2xx responses.
+#[derive(Serialize, ToSchema)]
+pub struct ErrorResponse {
+    /// What went wrong
+    error: String,
+}
+
+/// An error response: status code plus an [`ErrorResponse`] body.
+#[derive(Debug)]
+struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            message: message.into(),
+        }
+    }
+
+    fn forbidden(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::FORBIDDEN,
+            message: message.into(),
+        }
+    }
+
+    fn not_found(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::NOT_FOUND,
+            message: message.into(),
+        }
+    }
+
+    fn internal(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            message: message.into(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (
+            self.status,
+            Json(ErrorResponse {
+                error: self.message,
+            }),
+        )
+            .into_response()
+    }
+}
+
+/// Whether `path` (after resolving `..` and symlinks) lies inside `root`.
+fn is_within_root(root: &std::path::Path, path: &std::path::Path) -> bool {
+    match (root.canonicalize(), path.canonicalize()) {
+        (Ok(root), Ok(path)) => path.starts_with(root),
+        _ => false,
+    }
+}
+
+/// Resolve a file/symbol target, refusing anything outside the project root.
+fn resolve_target(
+    root: &std::path::Path,
+    target: &str,
+) -> Result<path_resolve::UnifiedPath, ApiError> {
+    let unified = path_resolve::resolve_unified(target, root)
+        .ok_or_else(|| ApiError::not_found(format!("no match for: {}", target)))?;
+    if !is_within_root(root, &root.join(&unified.file_path)) {
+        return Err(ApiError::forbidden(format!(
+            "outside project root: {}",
+            target
+        )));
+    }
+    Ok(unified)
+}
+
+/// Check an analyze target: globs may not be absolute or climb out of the
+/// root, and paths must resolve inside it.
+fn check_analyze_target(root: &std::path::Path, target: &str) -> Result<(), ApiError> {
+    if report::is_glob_pattern(target) {
+        let escapes = std::path::Path::new(target).components().any(|c| {
+            matches!(
+                c,
+                std::path::Component::RootDir
+                    | std::path::Component::Prefix(_)
+                    | std::path::Component::ParentDir
+            )
+        });
+        if escapes {
+            return Err(ApiError::forbidden(format!(
+                "outside project root: {}",
+                target
+            )));
+        }
+        return Ok(());
+    }
+    resolve_target(root, target).map(|_| ())
+}
+
+/// Run blocking work (parsing, walking the tree) off the async runtime.
+async fn blocking<T: Send + 'static>(
+    f: impl FnOnce() -> Result<T, ApiError> + Send + 'static,
+) -> Result<T, ApiError> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?
+}
+
+/// Analysis request body.
+#[derive(Deserialize, ToSchema)]
+pub struct AnalyzeRequest {
+    /// File, directory, symbol path or glob (default: whole project)
+    target: Option<String>,
+    /// Passes to run: health, complexity, length, security (default: all)
+    #[serde(default)]
+    passes: Vec<String>,
+    /// Only report functions at or above this complexity
+    threshold: Option<usize>,
+    /// Symbol kinds for complexity, e.g. "function,method"
+    #[serde(default)]
+    kind: Vec<String>,
+    /// Exclude paths matching these patterns or @aliases
+    #[serde(default)]
+    exclude: Vec<String>,
+    /// Include only paths matching these patterns or @aliases
+    #[serde(default)]
+    only: Vec<String>,
+}
+
+/// Run analysis passes
+#[utoipa::path(
+    post,
+    path = "/analyze",
+    request_body = AnalyzeRequest,
+    responses(
+        (status = 200, description = "Analysis report (same shape as `moss analyze --json`)", body = Object),
+        (status = 400, description = "Unknown pass, kind or filter pattern", body = ErrorResponse),
+        (status = 403, description = "Target is outside the project root", body = ErrorResponse),
+        (status = 404, description = "Target not found", body = ErrorResponse)
+    ),
+    tag = "analyze"
+)]
+async fn analyze(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<AnalyzeRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    const PASSES: [&str; 4] = ["health", "complexity", "length", "security"];
+    if let Some(unknown) = request
+        .passes
+        .iter()
+        .find(|p| !PASSES.contains(&p.as_str()))
+    {
+        return Err(ApiError::bad_request(format!(
+            "unknown pass '{}' (expected one of: {})",
+            unknown,
+            PASSES.join(", ")
+        )));
+    }
+    let run = |pass: &str| request.passes.is_empty() || request.passes.iter().any(|p| p == pass);
+    let (health, complexity, length, security) = (
+        run("health"),
+        run("complexity"),
+        run("length"),
+        run("security"),
+    );
+
+    let kinds = parse_kinds(&request.kind).map_err(ApiError::bad_request)?;
+    let root = state.root.clone();
+    blocking(move || {
+        if let Some(target) = &request.target {
+            check_analyze_target(&root, target)?;
+        }
+        let filter =
+            build_filter(&root, &request.exclude, &request.only).map_err(ApiError::bad_request)?;
+        let report = report::analyze(
+            request.target.as_deref(),
+            &root,
+            health,
+            complexity,
+            length,
+            security,
+            request.threshold,
+            &kinds,
+            filter.as_ref(),
+        );
+        Ok(Json(report.to_json()))
+    })
+    .await
+}
+
+/// View query parameters.
+#[derive(Deserialize, utoipa::IntoParams)]
+struct ViewQuery {
+    /// Directory, file, or file/Symbol path (default: project root)
+    target: Option<String>,
+    /// Directory levels to include for directory targets
+    depth: Option<usize>,
+    /// Include test functions and modules
+    #[serde(default)]
+    tests: bool,
+}
+
+/// View a directory tree, file skeleton or symbol
+#[utoipa::path(
+    get,
+    path = "/view",
+    params(ViewQuery),
+    responses(
+        (status = 200, description = "View tree (same shape as `moss view --json`)", body = Object),
+        (status = 403, description = "Target is outside the project root", body = ErrorResponse),
+        (status = 404, description = "Target not found", body = ErrorResponse)
+    ),
+    tag = "view"
+)]
+async fn view(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ViewQuery>,
+) -> Result<Json<tree::ViewNode>, ApiError> {
+    let root = state.root.clone();
+    blocking(move || {
+        let target = query.target.as_deref().unwrap_or(".");
+        if target == "." {
+            return Ok(Json(tree::generate_view_tree(
+                &root,
+                &tree::TreeOptions {
+                    max_depth: query.depth,
+                    ..Default::default()
+                },
+            )));
+        }
+
+        let unified = resolve_target(&root, target)?;
+        let full_path = root.join(&unified.file_path);
+        if unified.is_directory {
+            return Ok(Json(tree::generate_view_tree(
+                &full_path,
+                &tree::TreeOptions {
+                    max_depth: query.depth,
+                    ..Default::default()
+                },
+            )));
+        }
+
+        let content = std::fs::read_to_string(&full_path)
+            .map_err(|e| ApiError::internal(format!("{}: {}", unified.file_path, e)))?;
+        let grammar = support_for_path(&full_path).map(|s| s.grammar_name());
+        let skeleton = SkeletonExtractor::new().extract(&full_path, &content);
+        let skeleton = if query.tests {
+            skeleton
+        } else {
+            skeleton.filter_tests()
+        };
+
+        let Some((first, rest)) = unified.symbol_path.split_first() else {
+            return Ok(Json(skeleton.to_view_node(grammar)));
+        };
+        let mut symbol = skeleton.symbols.iter().find(|s| &s.name == first);
+        for name in rest {
+            symbol = symbol.and_then(|s| s.children.iter().find(|c| &c.name == name));
+        }
+        let symbol = symbol.ok_or_else(|| {
+            ApiError::not_found(format!(
+                "symbol not found: {} in {}",
+                unified.symbol_path.join("/"),
+                unified.file_path
+            ))
+        })?;
+        let parent_path = match unified.symbol_path.len() {
+            1 => unified.file_path.clone(),
+            n => format!(
+                "{}/{}",
+                unified.file_path,
+                unified.symbol_path[..n - 1].join("/")
+            ),
+        };
+        Ok(Json(symbol.to_view_node(&parent_path, grammar)))
+    })
+    .await
+}
+
+/// Grep query parameters.
+#[derive(Deserialize, utoipa::IntoParams)]
+struct GrepQuery {
+    /// Regex pattern to search for
+    pattern: String,
+    /// Maximum number of matches (default: 100)
+    limit: Option<usize>,
+    /// Case-insensitive search
+    #[serde(default)]
+    ignore_case: bool,
+    /// Comma-separated patterns or @aliases to exclude
+    exclude: Option<String>,
+    /// Comma-separated patterns or @aliases to include exclusively
+    only: Option<String>,
+}
+
+/// Search file contents
+#[utoipa::path(
+    get,
+    path = "/grep",
+    params(GrepQuery),
+    responses(
+        (status = 200, description = "Matches (same shape as `moss text-search --json`)", body = Object),
+        (status = 400, description = "Invalid regex or filter pattern", body = ErrorResponse)
+    ),
+    tag = "search"
+)]
+async fn grep(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<GrepQuery>,
+) -> Result<Json<text_search::GrepResult>, ApiError> {
+    let split = |list: &Option<String>| -> Vec<String> {
+        list.iter()
+            .flat_map(|s| s.split(','))
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    };
+    let (exclude, only) = (split(&query.exclude), split(&query.only));
+    let root = state.root.clone();
+    blocking(move || {
+        let filter = build_filter(&root, &exclude, &only).map_err(ApiError::bad_request)?;
+        text_search::grep(
+            &query.pattern,
+            &root,
+            filter.as_ref(),
+            query.limit.unwrap_or(100),
+            query.ignore_case,
+            MatchScope::All,
+        )
+        .map(Json)
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::InvalidInput => ApiError::bad_request(e.to_string()),
+            _ => ApiError::internal(e.to_string()),
+        })
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::{TempDir, tempdir};
+
+    /// A project under `<tmp>/project` with `secret.txt` next to it.
+    fn project() -> TempDir {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("project/src")).unwrap();
+        fs::write(
+            dir.path().join("project/src/main.py"),
+            "def hello():\n    return 'hello'\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("secret.txt"), "hello from outside\n").unwrap();
+        dir
+    }
+
+    async fn state(dir: &TempDir) -> State<Arc<AppState>> {
+        let root = dir.path().join("project");
+        let index = FileIndex::open(&root).await.unwrap();
+        State(Arc::new(AppState {
+            root,
+            index: Mutex::new(index),
+        }))
+    }
+
+    fn view_query(target: &str) -> Query<ViewQuery> {
+        Query(ViewQuery {
+            target: Some(target.to_string()),
+            depth: None,
+            tests: false,
+        })
+    }
+
+    fn analyze_request(target: &str, passes: &[&str]) -> Json<AnalyzeRequest> {
+        Json(AnalyzeRequest {
+            target: Some(target.to_string()),
+            passes: passes.iter().map(|p| p.to_string()).collect(),
+            threshold: None,
+            kind: Vec::new(),
+            exclude: Vec::new(),
+            only: Vec::new(),
+        })
+    }
+
+    fn grep_query(pattern: &str, only: Option<&str>) -> Query<GrepQuery> {
+        Query(GrepQuery {
+            pattern: pattern.to_string(),
+            limit: None,
+            ignore_case: false,
+            exclude: None,
+            only: only.map(str::to_string),
+        })
+    }
+
+    fn status<T>(result: Result<T, ApiError>) -> StatusCode {
+        match result {
+            Ok(_) => StatusCode::OK,
+            Err(e) => e.status,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_view() {
+        let dir = project();
+        let state = state(&dir).await;
+
+        let Json(node) = view(state.clone(), view_query("src")).await.unwrap();
+        assert!(node.children.iter().any(|c| c.name == "main.py"));
+
+        let result = view(state.clone(), view_query("nope/missing.py")).await;
+        assert_eq!(status(result), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_view_outside_root() {
+        let dir = project();
+        let state = state(&dir).await;
+        let secret = dir.path().join("secret.txt");
+
+        let result = view(state.clone(), view_query("../secret.txt")).await;
+        assert_eq!(status(result), StatusCode::FORBIDDEN);
+        let result = view(state.clone(), view_query(&secret.to_string_lossy())).await;
+        assert_eq!(status(result), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_analyze() {
+        let dir = project();
+        let state = state(&dir).await;
+
+        let result = analyze(state.clone(), analyze_request("src/main.py", &["length"])).await;
+        assert_eq!(status(result), StatusCode::OK);
+
+        let result = analyze(state.clone(), analyze_request("src/main.py", &["lint"])).await;
+        assert_eq!(status(result), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_analyze_outside_root() {
+        let dir = project();
+        let state = state(&dir).await;
+        let secret = dir.path().join("secret.txt");
+
+        for target in [
+            "../secret.txt".to_string(),
+            secret.to_string_lossy().to_string(),
+            "../*.txt".to_string(),
+        ] {
+            let result = analyze(state.clone(), analyze_request(&target, &["length"])).await;
+            assert_eq!(status(result), StatusCode::FORBIDDEN, "{}", target);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_grep() {
+        let dir = project();
+        let state = state(&dir).await;
+
+        let Json(result) = grep(state.clone(), grep_query("hello", None))
+            .await
+            .unwrap();
+        // Only files under the root are searched
+        assert!(!result.matches.is_empty());
+        assert!(result.matches.iter().all(|m| m.file == "src/main.py"));
+
+        let result = grep(state.clone(), grep_query("(", None)).await;
+        assert_eq!(status(result), StatusCode::BAD_REQUEST);
+        let result = grep(state.clone(), grep_query("hello", Some("@no-such-alias"))).await;
+        assert_eq!(status(result), StatusCode::BAD_REQUEST);
+    }
+}
@@ -1,9 +1,15 @@
 //! LSP (Language Server Protocol) server for moss.
 //!
 //! Provides IDE integration with document symbols, workspace symbols, and hover.
+//! Open documents are checked with moss rules; findings are published as
+//! diagnostics, and rule fixes are offered as quick-fix code actions.
 
+use crate::config::MossConfig;
 use crate::index::FileIndex;
+use crate::parsers::grammar_loader;
 use crate::skeleton::SkeletonExtractor;
+use rhizome_moss_rules::{Finding, Rule, load_all_rules, run_rules_on_source};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use tokio::sync::Mutex;
 use tower_lsp::jsonrpc::Result;
@@ -15,6 +21,10 @@ struct MossBackend {
     client: Client,
     root: Mutex<Option<PathBuf>>,
     index: Mutex<Option<FileIndex>>,
+    /// Rules of the workspace root, loaded on initialize
+    rules: Mutex<Vec<Rule>>,
+    /// Content of open documents
+    documents: Mutex<HashMap<Url, String>>,
 }
 
 impl MossBackend {
@@ -23,17 +33,49 @@ impl MossBackend {
             client,
             root: Mutex::new(None),
             index: Mutex::new(None),
+            rules: Mutex::new(Vec::new()),
+            documents: Mutex::new(HashMap::new()),
         }
     }
 
-    /// Initialize index for the workspace root.
+    /// Initialize index and rules for the workspace root.
     async fn init_index(&self, root: PathBuf) {
         if let Some(idx) = FileIndex::open_if_enabled(&root).await {
             *self.index.lock().await = Some(idx);
         }
+        let config = MossConfig::load(&root);
+        *self.rules.lock().await = load_all_rules(&root, &config.analyze.rules);
         *self.root.lock().await = Some(root);
     }
 
+    /// Rule findings in a document: its open content, or the file on disk.
+    async fn findings(&self, uri: &Url) -> Option<DocumentFindings> {
+        let file_path = uri.to_file_path().ok()?;
+        let content = match self.documents.lock().await.get(uri) {
+            Some(content) => content.clone(),
+            None => std::fs::read_to_string(&file_path).ok()?,
+        };
+        let root = self.root.lock().await.clone();
+        let root = root.as_deref().or(file_path.parent())?;
+        let rules = self.rules.lock().await;
+        let findings = run_rules_on_source(&rules, root, &file_path, &content, &grammar_loader());
+        Some(DocumentFindings { content, findings })
+    }
+
+    /// Check a document with moss rules and publish the findings as diagnostics.
+    async fn publish_findings(&self, uri: Url) {
+        let Some(DocumentFindings { content, findings }) = self.findings(&uri).await else {
+            return;
+        };
+        let diagnostics = findings
+            .iter()
+            .map(|f| finding_diagnostic(&content, f))
+            .collect();
+        self.client
+            .publish_diagnostics(uri, diagnostics, None)
+            .await;
+    }
+
     /// Convert moss symbol kind to LSP SymbolKind.
     fn to_lsp_symbol_kind(kind: &str) -> SymbolKind {
         match kind {
@@ -71,7 +113,7 @@ impl LanguageServer for MossBackend {
         Ok(InitializeResult {
             capabilities: ServerCapabilities {
                 text_document_sync: Some(TextDocumentSyncCapability::Kind(
-                    TextDocumentSyncKind::INCREMENTAL,
+                    TextDocumentSyncKind::FULL,
                 )),
                 document_symbol_provider: Some(OneOf::Left(true)),
                 workspace_symbol_provider: Some(OneOf::Left(true)),
@@ -82,6 +124,12 @@ impl LanguageServer for MossBackend {
                     prepare_provider: Some(true),
                     work_done_progress_options: Default::default(),
                 })),
+                code_action_provider: Some(CodeActionProviderCapability::Options(
+                    CodeActionOptions {
+                        code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+                        ..Default::default()
+                    },
+                )),
                 ..Default::default()
             },
             server_info: Some(ServerInfo {
@@ -101,6 +149,76 @@ impl LanguageServer for MossBackend {
         Ok(())
     }
 
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        let uri = params.text_document.uri;
+        self.documents
+            .lock()
+            .await
+            .insert(uri.clone(), params.text_document.text);
+        self.publish_findings(uri).await;
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        // Full sync: the last change holds the whole document
+        let Some(change) = params.content_changes.into_iter().last() else {
+            return;
+        };
+        let uri = params.text_document.uri;
+        self.documents.lock().await.insert(uri.clone(), change.text);
+        self.publish_findings(uri).await;
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let uri = params.text_document.uri;
+        self.documents.lock().await.remove(&uri);
+        self.client.publish_diagnostics(uri, Vec::new(), None).await;
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let uri = params.text_document.uri;
+        let Some(DocumentFindings { content, findings }) = self.findings(&uri).await else {
+            return Ok(None);
+        };
+
+        let actions: CodeActionResponse = findings
+            .iter()
+            .filter_map(|finding| {
+                let new_text = finding.fix_text()?;
+                let diagnostic = finding_diagnostic(&content, finding);
+                if !ranges_overlap(&diagnostic.range, &params.range) {
+                    return None;
+                }
+                let title = if new_text.is_empty() {
+                    format!("Remove {} match", finding.rule_id)
+                } else {
+                    format!("Apply {} fix", finding.rule_id)
+                };
+                let edit = TextEdit {
+                    range: diagnostic.range,
+                    new_text,
+                };
+                Some(CodeActionOrCommand::CodeAction(CodeAction {
+                    title,
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic]),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(HashMap::from([(uri.clone(), vec![edit])])),
+                        document_changes: None,
+                        change_annotations: None,
+                    }),
+                    is_preferred: Some(true),
+                    ..Default::default()
+                }))
+            })
+            .collect();
+
+        if actions.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(actions))
+        }
+    }
+
     async fn document_symbol(
         &self,
         params: DocumentSymbolParams,
@@ -177,11 +295,13 @@ impl LanguageServer for MossBackend {
             _ => return Ok(None),
         };
 
-        // Search symbols in index
-        let matches = match index.find_symbols(query, None, false, 50).await {
+        // Search symbols in index, ranked the same way as `moss view`
+        let matches = match index.find_symbols(query, None, true, 500).await {
             Ok(m) => m,
             Err(_) => return Ok(None),
         };
+        let mut matches = crate::symbol_rank::rank(query, matches);
+        matches.truncate(50);
 
         #[allow(deprecated)]
         let symbols: Vec<SymbolInformation> = matches
@@ -239,23 +359,7 @@ impl LanguageServer for MossBackend {
         // Find symbol at position (1-indexed line)
         let line = position.line as usize + 1;
 
-        fn find_symbol_at_line<'a>(
-            symbols: &'a [crate::skeleton::SkeletonSymbol],
-            line: usize,
-        ) -> Option<&'a crate::skeleton::SkeletonSymbol> {
-            for sym in symbols {
-                if line >= sym.start_line && line <= sym.end_line {
-                    // Check children first for more specific match
-                    if let Some(child) = find_symbol_at_line(&sym.children, line) {
-                        return Some(child);
-                    }
-                    return Some(sym);
-                }
-            }
-            None
-        }
-
-        let symbol = find_symbol_at_line(&result.symbols, line);
+        let symbol = result.symbols.iter().find_map(|s| s.find_by_line(line));
 
         match symbol {
             Some(sym) => {
@@ -606,6 +710,49 @@ impl LanguageServer for MossBackend {
     }
 }
 
+/// LSP diagnostic for a rule finding, spanning the matched node.
+fn finding_diagnostic(content: &str, finding: &Finding) -> Diagnostic {
+    let severity = match finding.severity {
+        rhizome_moss_rules::Severity::Error => DiagnosticSeverity::ERROR,
+        rhizome_moss_rules::Severity::Warning => DiagnosticSeverity::WARNING,
+        rhizome_moss_rules::Severity::Info => DiagnosticSeverity::INFORMATION,
+    };
+    Diagnostic {
+        range: Range {
+            start: byte_to_position(content, finding.start_byte),
+            end: byte_to_position(content, finding.end_byte),
+        },
+        severity: Some(severity),
+        code: Some(NumberOrString::String(finding.rule_id.clone())),
+        source: Some("moss".to_string()),
+        message: finding.message.clone(),
+        ..Default::default()
+    }
+}
+
+/// LSP position (line and UTF-16 column, both 0-based) of a byte offset.
+fn byte_to_position(content: &str, byte: usize) -> Position {
+    let byte = byte.min(content.len());
+    let before = &content[..byte];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Position {
+        line: before.matches('\n').count() as u32,
+        character: before[line_start..].encode_utf16().count() as u32,
+    }
+}
+
+/// Whether two ranges overlap or touch; an empty range (a cursor) touching a
+/// finding counts.
+fn ranges_overlap(a: &Range, b: &Range) -> bool {
+    a.start <= b.end && b.start <= a.end
+}
+
+/// Rule findings in a document, with the text they were found in.
+struct DocumentFindings {
+    content: String,
+    findings: Vec<Finding>,
+}
+
 /// Word at a position with its range.
 struct WordAtPosition {
     word: String,
@@ -731,3 +878,33 @@ pub async fn run_lsp_server(root: Option<&std::path::Path>) -> i32 {
     Server::new(stdin, stdout, socket).serve(service).await;
     0
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_to_position() {
+        let content = "fn a() {}\nlet é = x.unwrap();\n";
+        assert_eq!(byte_to_position(content, 0), Position::new(0, 0));
+        assert_eq!(byte_to_position(content, 10), Position::new(1, 0));
+        // `é` is two bytes but one UTF-16 unit
+        let x = content.find("x.").unwrap();
+        assert_eq!(byte_to_position(content, x), Position::new(1, 8));
+        assert_eq!(byte_to_position(content, 1000), Position::new(2, 0));
+
+        let finding = Range::new(Position::new(1, 8), Position::new(1, 18));
+        let cursor = |line, character| {
+            let p = Position::new(line, character);
+            Range::new(p, p)
+        };
+        assert!(ranges_overlap(&finding, &cursor(1, 8)));
+        assert!(ranges_overlap(&finding, &cursor(1, 18)));
+        assert!(!ranges_overlap(&finding, &cursor(1, 19)));
+        assert!(ranges_overlap(
+            &finding,
+            &Range::new(Position::new(0, 0), Position::new(3, 0))
+        ));
+        assert!(!ranges_overlap(&finding, &cursor(0, 3)));
+    }
+}
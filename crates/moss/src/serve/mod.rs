@@ -18,7 +18,8 @@ pub mod mcp;
 pub struct ServeConfig {
     /// Default HTTP port (overridden by --port).
     pub http_port: Option<u16>,
-    /// HTTP host to bind to.
+    /// HTTP host to bind to (default: 127.0.0.1). The HTTP API has no
+    /// authentication, so only widen this on a trusted network.
     pub http_host: Option<String>,
 }
 
@@ -90,7 +91,11 @@ pub fn run(args: ServeArgs, json: bool) -> i32 {
                     config.serve.http_port()
                 };
                 let rt = tokio::runtime::Runtime::new().unwrap();
-                rt.block_on(http::run_http_server(&root, effective_port))
+                rt.block_on(http::run_http_server(
+                    &root,
+                    config.serve.http_host(),
+                    effective_port,
+                ))
             }
         }
         ServeProtocol::Lsp => {
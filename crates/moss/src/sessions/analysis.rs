@@ -9,12 +9,29 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+/// Failing invocations kept per tool.
+const MAX_FAILURE_SAMPLES: usize = 3;
+
+/// Characters of error output kept per failure sample.
+const FAILURE_MESSAGE_CHARS: usize = 200;
+
+/// A failed tool invocation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolFailure {
+    pub tool_use_id: String,
+    /// Error output, truncated
+    pub message: String,
+}
+
 /// Statistics for a single tool.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ToolStats {
     pub name: String,
     pub calls: usize,
     pub errors: usize,
+    /// Sample of failing invocations (first few)
+    #[serde(default)]
+    pub failures: Vec<ToolFailure>,
 }
 
 impl ToolStats {
@@ -23,6 +40,7 @@ impl ToolStats {
             name: name.into(),
             calls: 0,
             errors: 0,
+            failures: Vec::new(),
         }
     }
 
@@ -33,6 +51,29 @@ impl ToolStats {
             (self.calls - self.errors) as f64 / self.calls as f64
         }
     }
+
+    /// Fraction of calls that failed. Zero for formats that don't flag errors.
+    pub fn error_rate(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.calls as f64
+        }
+    }
+
+    /// Record a failing invocation, keeping a bounded sample of messages.
+    pub fn record_failure(&mut self, failure: ToolFailure) {
+        self.errors += 1;
+        if self.failures.len() < MAX_FAILURE_SAMPLES {
+            self.failures.push(failure);
+        }
+    }
+
+    /// Merge failure samples from another analysis (counts are merged separately).
+    pub fn merge_failures(&mut self, failures: Vec<ToolFailure>) {
+        let room = MAX_FAILURE_SAMPLES.saturating_sub(self.failures.len());
+        self.failures.extend(failures.into_iter().take(room));
+    }
 }
 
 /// Token usage statistics.
@@ -181,6 +222,32 @@ impl SessionAnalysis {
             lines.push(String::new());
         }
 
+        // Most error-prone tools
+        let mut failing: Vec<_> = self.tool_stats.values().filter(|t| t.errors > 0).collect();
+        if !failing.is_empty() {
+            failing.sort_by(|a, b| {
+                b.error_rate()
+                    .total_cmp(&a.error_rate())
+                    .then(b.errors.cmp(&a.errors))
+                    .then(a.name.cmp(&b.name))
+            });
+            lines.push("## Most Error-Prone Tools".to_string());
+            lines.push(String::new());
+            for tool in failing {
+                lines.push(format!(
+                    "### {} ({}/{} failed, {:.0}%)",
+                    tool.name,
+                    tool.errors,
+                    tool.calls,
+                    tool.error_rate() * 100.0
+                ));
+                for failure in &tool.failures {
+                    lines.push(format!("- {}", failure.message.replace('\n', " ")));
+                }
+                lines.push(String::new());
+            }
+        }
+
         // Token usage
         if self.token_stats.api_calls > 0 {
             let ts = &self.token_stats;
@@ -427,7 +494,20 @@ pub fn analyze_session(session: &Session) -> SessionAnalysis {
         }
     }
 
+    // Map tool_use ids to tool names so results can be attributed
+    let mut tool_names: HashMap<&str, &str> = HashMap::new();
+    for turn in &session.turns {
+        for msg in &turn.messages {
+            for block in &msg.content {
+                if let ContentBlock::ToolUse { id, name, .. } = block {
+                    tool_names.insert(id, name);
+                }
+            }
+        }
+    }
+
     // Analyze tool usage
+    let mut last_tool: Option<&str> = None;
     for turn in &session.turns {
         let mut tool_uses_in_turn = 0;
 
@@ -441,13 +521,23 @@ pub fn analyze_session(session: &Session) -> SessionAnalysis {
                             .or_insert_with(|| ToolStats::new(name));
                         stat.calls += 1;
                         tool_uses_in_turn += 1;
+                        last_tool = Some(name);
                     }
                     ContentBlock::ToolResult {
-                        is_error, content, ..
+                        tool_use_id,
+                        is_error,
+                        content,
                     } => {
                         if *is_error {
-                            // Try to attribute error to most recent tool
-                            // For now, just track in error patterns
+                            // Attribute by id, falling back to the most recent tool
+                            let tool = tool_names.get(tool_use_id.as_str()).copied().or(last_tool);
+                            if let Some(stat) = tool.and_then(|t| analysis.tool_stats.get_mut(t)) {
+                                stat.record_failure(ToolFailure {
+                                    tool_use_id: tool_use_id.clone(),
+                                    message: content.chars().take(FAILURE_MESSAGE_CHARS).collect(),
+                                });
+                            }
+
                             let category = categorize_error(content);
                             let pattern = analysis
                                 .error_patterns
@@ -504,3 +594,66 @@ pub fn analyze_session(session: &Session) -> SessionAnalysis {
 
     analysis
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhizome_moss_sessions::{Message, Role, Turn};
+
+    fn message(role: Role, content: Vec<ContentBlock>) -> Message {
+        Message {
+            role,
+            content,
+            timestamp: None,
+        }
+    }
+
+    fn tool_use(id: &str, name: &str) -> ContentBlock {
+        ContentBlock::ToolUse {
+            id: id.to_string(),
+            name: name.to_string(),
+            input: serde_json::Value::Null,
+        }
+    }
+
+    fn tool_result(id: &str, content: &str, is_error: bool) -> ContentBlock {
+        ContentBlock::ToolResult {
+            tool_use_id: id.to_string(),
+            content: content.to_string(),
+            is_error,
+        }
+    }
+
+    #[test]
+    fn test_failures_attributed_by_tool_use_id() {
+        let mut session = Session::new(PathBuf::from("s.jsonl"), "claude");
+        session.turns.push(Turn {
+            messages: vec![
+                message(
+                    Role::Assistant,
+                    vec![tool_use("a", "Bash"), tool_use("b", "Read")],
+                ),
+                message(
+                    Role::User,
+                    vec![
+                        tool_result("a", "Exit code: 1\ncargo: not found", true),
+                        tool_result("b", "fn main() {}", false),
+                    ],
+                ),
+            ],
+            token_usage: None,
+        });
+
+        let analysis = analyze_session(&session);
+        let bash = &analysis.tool_stats["Bash"];
+        assert_eq!(bash.errors, 1);
+        assert_eq!(bash.error_rate(), 1.0);
+        assert_eq!(bash.failures[0].tool_use_id, "a");
+        assert!(bash.failures[0].message.starts_with("Exit code: 1"));
+        assert_eq!(analysis.tool_stats["Read"].error_rate(), 0.0);
+
+        let markdown = analysis.to_markdown();
+        assert!(markdown.contains("## Most Error-Prone Tools"));
+        assert!(markdown.contains("### Bash (1/1 failed, 100%)"));
+    }
+}
@@ -14,6 +14,6 @@ pub use rhizome_moss_sessions::{
 
 // Export analysis types from this crate
 pub use analysis::{
-    ErrorPattern, SessionAnalysis, TokenStats, ToolStats, analyze_session, categorize_error,
-    normalize_path,
+    ErrorPattern, SessionAnalysis, TokenStats, ToolFailure, ToolStats, analyze_session,
+    categorize_error, normalize_path,
 };
@@ -3,7 +3,7 @@
 //! Extracts function/class signatures with optional docstrings.
 //! Uses the shared Extractor from extract.rs for tree traversal.
 
-use crate::extract::Extractor;
+use crate::extract::{ExtractOptions, Extractor};
 use crate::tree::{ViewNode, ViewNodeKind};
 use rhizome_moss_languages::{Symbol, SymbolKind};
 use std::path::Path;
@@ -39,8 +39,12 @@ impl SymbolExt for Symbol {
             children,
             signature: Some(self.signature.clone()),
             docstring: self.docstring.clone(),
+            attributes: self.attributes.clone(),
             line_range: Some((self.start_line, self.end_line)),
             grammar: grammar.map(String::from),
+            truncated: false,
+            git_status: None,
+            blame: None,
         }
     }
 }
@@ -94,7 +98,7 @@ impl SkeletonResult {
                     kind: sym.kind,
                     signature: sym.signature.clone(),
                     docstring: sym.docstring.clone(),
-                    attributes: Vec::new(),
+                    attributes: sym.attributes.clone(),
                     start_line: sym.start_line,
                     end_line: sym.end_line,
                     visibility: sym.visibility,
@@ -119,13 +123,21 @@ impl SkeletonResult {
         }
     }
 
-    /// Filter out test functions and test modules.
-    /// Uses Language::is_test_symbol() for language-specific detection.
+    /// Filter out test functions and test modules; a test file loses all its symbols.
+    /// Uses Language::is_test_file() and Language::is_test_symbol() for
+    /// language-specific detection.
     pub fn filter_tests(&self) -> SkeletonResult {
         use rhizome_moss_languages::{Language, support_for_path};
         use std::path::Path;
 
-        let lang = support_for_path(Path::new(&self.file_path));
+        let path = Path::new(&self.file_path);
+        let lang = support_for_path(path);
+        if lang.is_some_and(|l| l.is_test_file(path)) {
+            return SkeletonResult {
+                symbols: Vec::new(),
+                file_path: self.file_path.clone(),
+            };
+        }
 
         fn filter_symbol(sym: &Symbol, lang: Option<&dyn Language>) -> Option<Symbol> {
             let is_test = match lang {
@@ -181,6 +193,17 @@ impl SkeletonExtractor {
         }
     }
 
+    /// Extractor for outlines: top-level declarations only, without members
+    /// or nested definitions.
+    pub fn top_level() -> Self {
+        Self {
+            extractor: Extractor::with_options(ExtractOptions {
+                include_private: true,
+                top_level_only: true,
+            }),
+        }
+    }
+
     pub fn extract(&self, path: &Path, content: &str) -> SkeletonResult {
         let result = self.extractor.extract(path, content);
         SkeletonResult {
@@ -0,0 +1,256 @@
+//! Ranking of symbols against a name query.
+//!
+//! Shared by `view` symbol search and the LSP's workspace symbols, so a query
+//! gives the same order everywhere. Symbols are ordered by [`SymbolScore`]:
+//!
+//! 1. How the name matches ([`MatchTier`]): exact, then prefix (case-sensitive
+//!    before case-insensitive), then camelCase subsequence, then substring
+//! 2. Kind: types, then functions, then everything else
+//! 3. Shallower file paths
+//! 4. Shorter names, then name and file, so the order is total
+
+use crate::index::SymbolMatch;
+
+/// How a symbol name matches the query, best first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchTier {
+    /// Equal, ignoring case
+    Exact,
+    /// Starts with the query
+    Prefix,
+    /// Starts with the query, ignoring case
+    PrefixIgnoreCase,
+    /// The query is made of prefixes of the name's words, in order
+    /// (`gUN` or `getun` for `getUserName`, `hs` for `HttpServer`)
+    CamelCase,
+    /// Contains the query, ignoring case
+    Substring,
+}
+
+/// Sort key of a matching symbol; smaller is better.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SymbolScore {
+    pub tier: MatchTier,
+    /// See [`kind_priority`]
+    pub kind: u8,
+    /// Number of directories in the symbol's file path
+    pub depth: usize,
+    pub name_len: usize,
+}
+
+/// How `name` matches `query`, or None if it doesn't. An empty query matches
+/// every name as a prefix.
+pub fn match_tier(query: &str, name: &str) -> Option<MatchTier> {
+    let query_lower = query.to_lowercase();
+    let name_lower = name.to_lowercase();
+    if name_lower == query_lower {
+        Some(MatchTier::Exact)
+    } else if name.starts_with(query) {
+        Some(MatchTier::Prefix)
+    } else if name_lower.starts_with(&query_lower) {
+        Some(MatchTier::PrefixIgnoreCase)
+    } else if camel_case_match(query, name) {
+        Some(MatchTier::CamelCase)
+    } else if name_lower.contains(&query_lower) {
+        Some(MatchTier::Substring)
+    } else {
+        None
+    }
+}
+
+/// Rank of a symbol kind: 0 for types, 1 for functions, 2 for the rest.
+pub fn kind_priority(kind: &str) -> u8 {
+    match kind {
+        "class" | "struct" | "enum" | "trait" | "interface" | "type" | "module" => 0,
+        "function" | "method" => 1,
+        _ => 2,
+    }
+}
+
+/// Score of `symbol` for `query`, or None if its name doesn't match.
+pub fn score(query: &str, symbol: &SymbolMatch) -> Option<SymbolScore> {
+    Some(SymbolScore {
+        tier: match_tier(query, &symbol.name)?,
+        kind: kind_priority(&symbol.kind),
+        depth: symbol.file.matches('/').count(),
+        name_len: symbol.name.chars().count(),
+    })
+}
+
+/// Keep the symbols matching `query`, best first.
+pub fn rank(query: &str, symbols: Vec<SymbolMatch>) -> Vec<SymbolMatch> {
+    let mut scored: Vec<(SymbolScore, SymbolMatch)> = symbols
+        .into_iter()
+        .filter_map(|s| Some((score(query, &s)?, s)))
+        .collect();
+    scored.sort_by(|(a_score, a), (b_score, b)| {
+        a_score
+            .cmp(b_score)
+            .then_with(|| a.name.cmp(&b.name))
+            .then_with(|| a.file.cmp(&b.file))
+            .then_with(|| a.start_line.cmp(&b.start_line))
+    });
+    scored.into_iter().map(|(_, s)| s).collect()
+}
+
+/// Whether `query` splits into prefixes of words of `name`, in order, skipping
+/// words as needed. Matching ignores case, so the query's capitals are optional.
+fn camel_case_match(query: &str, name: &str) -> bool {
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    let words: Vec<Vec<char>> = split_words(name)
+        .iter()
+        .map(|w| w.to_lowercase().chars().collect())
+        .collect();
+    !query.is_empty() && match_words(&query, &words)
+}
+
+fn match_words(query: &[char], words: &[Vec<char>]) -> bool {
+    if query.is_empty() {
+        return true;
+    }
+    for (i, word) in words.iter().enumerate() {
+        let common = word.iter().zip(query).take_while(|(w, q)| w == q).count();
+        // Longest chunk first: it leaves the least for later words
+        for len in (1..=common).rev() {
+            if match_words(&query[len..], &words[i + 1..]) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Split an identifier into words at `_`, `-`, `.`, digits-to-letters, and case
+/// changes (`HTTPServer` is `HTTP` + `Server`).
+fn split_words(name: &str) -> Vec<&str> {
+    let chars: Vec<(usize, char)> = name.char_indices().collect();
+    let mut words = Vec::new();
+    let mut start: Option<usize> = None;
+    for (i, &(pos, c)) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if let Some(s) = start.take() {
+                words.push(&name[s..pos]);
+            }
+            continue;
+        }
+        let boundary = match (i.checked_sub(1).map(|p| chars[p].1), chars.get(i + 1)) {
+            (Some(prev), _) if prev.is_lowercase() && c.is_uppercase() => true,
+            (Some(prev), _) if prev.is_numeric() != c.is_numeric() => true,
+            // Last capital of a run followed by lowercase starts a word
+            (Some(prev), Some(&(_, next)))
+                if prev.is_uppercase() && c.is_uppercase() && next.is_lowercase() =>
+            {
+                true
+            }
+            _ => false,
+        };
+        match start {
+            Some(s) if boundary => {
+                words.push(&name[s..pos]);
+                start = Some(pos);
+            }
+            None => start = Some(pos),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        words.push(&name[s..]);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(name: &str, kind: &str, file: &str) -> SymbolMatch {
+        SymbolMatch {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            file: file.to_string(),
+            start_line: 1,
+            end_line: 1,
+            parent: None,
+        }
+    }
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(split_words("getUserName"), vec!["get", "User", "Name"]);
+        assert_eq!(split_words("HTTPServer"), vec!["HTTP", "Server"]);
+        assert_eq!(
+            split_words("parse_http2_body"),
+            vec!["parse", "http", "2", "body"]
+        );
+        assert_eq!(split_words("__init__"), vec!["init"]);
+    }
+
+    #[test]
+    fn test_match_tier() {
+        assert_eq!(match_tier("parser", "Parser"), Some(MatchTier::Exact));
+        assert_eq!(match_tier("Par", "ParseError"), Some(MatchTier::Prefix));
+        assert_eq!(
+            match_tier("par", "ParseError"),
+            Some(MatchTier::PrefixIgnoreCase)
+        );
+        assert_eq!(match_tier("gUN", "getUserName"), Some(MatchTier::CamelCase));
+        assert_eq!(
+            match_tier("getun", "getUserName"),
+            Some(MatchTier::CamelCase)
+        );
+        assert_eq!(match_tier("pe", "ParseError"), Some(MatchTier::CamelCase));
+        assert_eq!(match_tier("rse", "ParseError"), Some(MatchTier::Substring));
+        assert_eq!(match_tier("xyz", "ParseError"), None);
+        assert_eq!(match_tier("", "anything"), Some(MatchTier::Prefix));
+    }
+
+    #[test]
+    fn test_rank_order() {
+        let candidates = vec![
+            symbol("reparse", "function", "src/a.rs"),
+            symbol("parse_config", "variable", "src/config.rs"),
+            symbol("ParseError", "struct", "src/deep/nested/error.rs"),
+            symbol("parse", "function", "src/deep/parser.rs"),
+            symbol("parse", "variable", "src/lib.rs"),
+            symbol("parseFile", "method", "src/lib.rs"),
+            symbol("Parse", "struct", "src/deep/parser.rs"),
+            symbol("ProcessEntry", "class", "src/process.rs"),
+            symbol("unrelated", "function", "src/lib.rs"),
+            symbol("parse_args", "function", "main.rs"),
+            symbol("parse_args", "function", "src/cli/args.rs"),
+        ];
+        let ranked = rank("parse", candidates);
+        let order: Vec<(&str, &str)> = ranked
+            .iter()
+            .map(|s| (s.name.as_str(), s.file.as_str()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                // Exact: struct before function before variable
+                ("Parse", "src/deep/parser.rs"),
+                ("parse", "src/deep/parser.rs"),
+                ("parse", "src/lib.rs"),
+                // Case-sensitive prefix: function/method before variable,
+                // then shallower paths, then shorter names
+                ("parse_args", "main.rs"),
+                ("parseFile", "src/lib.rs"),
+                ("parse_args", "src/cli/args.rs"),
+                ("parse_config", "src/config.rs"),
+                // Case-insensitive prefix
+                ("ParseError", "src/deep/nested/error.rs"),
+                // Substring
+                ("reparse", "src/a.rs"),
+            ]
+        );
+
+        // camelCase subsequence ranks between prefixes and substrings
+        let candidates = vec![
+            symbol("speedEntry", "function", "a.rs"),
+            symbol("ProcessEntry", "class", "a.rs"),
+            symbol("pe", "variable", "a.rs"),
+        ];
+        let order: Vec<String> = rank("pE", candidates).into_iter().map(|s| s.name).collect();
+        assert_eq!(order, vec!["pe", "ProcessEntry", "speedEntry"]);
+    }
+}
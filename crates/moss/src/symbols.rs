@@ -39,6 +39,7 @@ impl SymbolParser {
         Self {
             extractor: Extractor::with_options(ExtractOptions {
                 include_private: true, // symbols.rs includes all symbols for indexing
+                top_level_only: false,
             }),
         }
     }
@@ -308,18 +309,18 @@ impl SymbolParser {
         let end = symbol.end_line.min(lines.len());
         let source = lines[start..end].join("\n");
 
-        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-        match ext {
-            "py" => self.find_python_calls_with_lines(&source, symbol.start_line),
-            "rs" => self.find_rust_calls_with_lines(&source, symbol.start_line),
-            "ts" | "tsx" => {
-                self.find_typescript_calls_with_lines(&source, symbol.start_line, ext == "tsx")
-            }
-            "js" | "mjs" | "cjs" => {
-                self.find_javascript_calls_with_lines(&source, symbol.start_line)
+        // Keep in sync with Language::supports_call_graph
+        let grammar = support_for_path(path).map(|l| l.grammar_name());
+        match grammar {
+            Some("python") => self.find_python_calls_with_lines(&source, symbol.start_line),
+            Some("rust") => self.find_rust_calls_with_lines(&source, symbol.start_line),
+            Some("typescript") => {
+                self.find_typescript_calls_with_lines(&source, symbol.start_line, false)
             }
-            "java" => self.find_java_calls_with_lines(&source, symbol.start_line),
-            "go" => self.find_go_calls_with_lines(&source, symbol.start_line),
+            Some("tsx") => self.find_typescript_calls_with_lines(&source, symbol.start_line, true),
+            Some("javascript") => self.find_javascript_calls_with_lines(&source, symbol.start_line),
+            Some("java") => self.find_java_calls_with_lines(&source, symbol.start_line),
+            Some("go") => self.find_go_calls_with_lines(&source, symbol.start_line),
             _ => Vec::new(),
         }
     }
@@ -3,16 +3,17 @@
 use crate::filter::Filter;
 use crate::output::OutputFormatter;
 use crate::symbols::SymbolParser;
+use crate::tree::{HighlightKind, classify_node_kind};
 use grep_matcher::Matcher;
 use grep_regex::RegexMatcher;
 use grep_searcher::Searcher;
 use grep_searcher::sinks::UTF8;
 use ignore::WalkBuilder;
 use nu_ansi_term::Color::{Cyan, Green, Red, Yellow};
-use std::collections::HashMap;
 use std::fmt::Write;
 use std::fs;
 use std::io;
+use std::ops::Range;
 use std::path::Path;
 use std::sync::Mutex;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -22,6 +23,8 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 pub struct GrepMatch {
     pub file: String,
     pub line: usize,
+    /// 1-based byte column of the match in the untrimmed line
+    pub column: usize,
     pub content: String,
     pub start: usize,
     pub end: usize,
@@ -42,113 +45,249 @@ pub struct GrepResult {
     pub matches: Vec<GrepMatch>,
     pub total_matches: usize,
     pub files_searched: usize,
+    /// Files searched without comment/string classification (no grammar support)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unclassified_files: Vec<String>,
 }
 
-/// Search for a pattern in files
-pub fn grep(
-    pattern: &str,
-    root: &Path,
-    filter: Option<&Filter>,
-    limit: usize,
-    ignore_case: bool,
-) -> io::Result<GrepResult> {
-    // Build the regex matcher
+/// Totals of a streamed search ([`grep_each`]).
+#[derive(Debug, Default)]
+pub struct GrepSummary {
+    /// Matches passed on, at most the limit
+    pub matches: usize,
+    pub files_searched: usize,
+    pub unclassified_files: Vec<String>,
+}
+
+/// Which syntactic regions a match is allowed to fall in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchScope {
+    /// Plain text matching, no parsing
+    #[default]
+    All,
+    /// Suppress matches entirely inside comments or string literals
+    CodeOnly,
+    /// Only keep matches entirely inside comments
+    CommentsOnly,
+}
+
+/// Byte ranges of comment, string and attribute nodes in a parsed file.
+///
+/// Ranges are collected in document order without recursing into comments
+/// and strings, so each list is sorted and non-overlapping.
+pub(crate) struct SyntaxRegions {
+    pub(crate) comments: Vec<Range<usize>>,
+    pub(crate) strings: Vec<Range<usize>>,
+    /// Attributes and decorators (`#[inline]`, `@Override`)
+    pub(crate) attributes: Vec<Range<usize>>,
+}
+
+impl SyntaxRegions {
+    /// Parse `content` with the grammar for `path`, if one is available.
+    fn for_file(path: &Path, content: &str) -> Option<Self> {
+        let support = rhizome_moss_languages::support_for_path(path)?;
+        let tree = crate::parsers::parse_with_grammar(support.grammar_name(), content)?;
+        Some(Self::from_tree(&tree))
+    }
+
+    /// Collect the regions of an already parsed file.
+    pub(crate) fn from_tree(tree: &tree_sitter::Tree) -> Self {
+        let mut regions = SyntaxRegions {
+            comments: Vec::new(),
+            strings: Vec::new(),
+            attributes: Vec::new(),
+        };
+        let mut cursor = tree.walk();
+        loop {
+            let node = cursor.node();
+            let range = node.start_byte()..node.end_byte();
+            let descend = match classify_node_kind(node.kind()) {
+                HighlightKind::Comment => {
+                    regions.comments.push(range);
+                    false
+                }
+                HighlightKind::String => {
+                    regions.strings.push(range);
+                    false
+                }
+                HighlightKind::Attribute => {
+                    regions.attributes.push(range);
+                    true
+                }
+                _ => true,
+            };
+
+            if descend && cursor.goto_first_child() {
+                continue;
+            }
+            while !cursor.goto_next_sibling() {
+                if !cursor.goto_parent() {
+                    return regions;
+                }
+            }
+        }
+    }
+
+    /// Whether a match spanning `span` is permitted under `scope`.
+    fn allows(&self, scope: MatchScope, span: &Range<usize>) -> bool {
+        match scope {
+            MatchScope::All => true,
+            MatchScope::CodeOnly => {
+                !contains_span(&self.comments, span) && !contains_span(&self.strings, span)
+            }
+            MatchScope::CommentsOnly => contains_span(&self.comments, span),
+        }
+    }
+}
+
+/// Check whether `span` lies entirely within one of the sorted `ranges`.
+fn contains_span(ranges: &[Range<usize>], span: &Range<usize>) -> bool {
+    let idx = ranges.partition_point(|r| r.start <= span.start);
+    idx > 0 && span.end <= ranges[idx - 1].end
+}
+
+/// Byte offset of the start of each line (0-indexed by line).
+pub(crate) fn line_offsets(content: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+        .collect()
+}
+
+fn build_matcher(pattern: &str, ignore_case: bool) -> io::Result<RegexMatcher> {
     let pattern_str = if ignore_case {
         format!("(?i){}", pattern)
     } else {
         pattern.to_string()
     };
-    let matcher = RegexMatcher::new_line_matcher(&pattern_str)
-        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
-
-    let matches: Mutex<Vec<GrepMatch>> = Mutex::new(Vec::new());
-    let total_matches = AtomicUsize::new(0);
-    let files_searched = AtomicUsize::new(0);
+    RegexMatcher::new_line_matcher(&pattern_str)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
 
-    // Build the file walker
+fn build_walker(root: &Path) -> ignore::WalkParallel {
     let mut builder = WalkBuilder::new(root);
     builder.hidden(true); // skip hidden files
     builder.git_ignore(true);
     builder.git_global(true);
     builder.git_exclude(true);
+    builder.build_parallel()
+}
+
+/// A searched file's matches.
+struct FileMatches {
+    matches: Vec<GrepMatch>,
+    /// Scope was requested but the file has no grammar support
+    unclassified: bool,
+}
+
+/// Search one file. `rel_path` is the path reported in matches.
+fn search_file(
+    matcher: &RegexMatcher,
+    path: &Path,
+    rel_path: &str,
+    scope: MatchScope,
+) -> FileMatches {
+    let mut searcher = Searcher::new();
+    let mut file_matches: Vec<GrepMatch> = Vec::new();
+
+    // Classify comment/string regions once per file when scoped
+    let mut content = None;
+    let mut regions = None;
+    if scope != MatchScope::All {
+        let text = fs::read_to_string(path).unwrap_or_default();
+        regions = SyntaxRegions::for_file(path, &text);
+        content = Some(text);
+    }
+    let unclassified = content.is_some() && regions.is_none();
+    let offsets = content.as_deref().map(line_offsets);
+
+    let _ = searcher.search_path(
+        matcher,
+        path,
+        UTF8(|line_num, line| {
+            // Find the first match position permitted by the scope
+            let line_start = offsets
+                .as_ref()
+                .and_then(|o| o.get(line_num as usize - 1).copied())
+                .unwrap_or(0);
+            let mut found: Option<(usize, usize)> = None;
+            let _ = matcher.find_iter(line.as_bytes(), |m| {
+                let span = line_start + m.start()..line_start + m.end();
+                if regions.as_ref().is_none_or(|r| r.allows(scope, &span)) {
+                    found = Some((m.start(), m.end()));
+                    return false;
+                }
+                true
+            });
+            let Some((start, end)) = found else {
+                return Ok(true);
+            };
+
+            let trimmed = line.trim();
+            // Adjust match positions for trimmed content
+            let leading_ws = line.len() - line.trim_start().len();
+            let adj_start = start.saturating_sub(leading_ws);
+            let adj_end = end.saturating_sub(leading_ws).min(trimmed.len());
+
+            file_matches.push(GrepMatch {
+                file: rel_path.to_string(),
+                line: line_num as usize,
+                column: start + 1,
+                content: trimmed.to_string(),
+                start: adj_start,
+                end: adj_end,
+                symbol: None,
+                symbol_start: None,
+                symbol_end: None,
+            });
+            Ok(true)
+        }),
+    );
+
+    FileMatches {
+        matches: file_matches,
+        unclassified,
+    }
+}
+
+/// Search for a pattern in files
+pub fn grep(
+    pattern: &str,
+    root: &Path,
+    filter: Option<&Filter>,
+    limit: usize,
+    ignore_case: bool,
+    scope: MatchScope,
+) -> io::Result<GrepResult> {
+    let matcher = build_matcher(pattern, ignore_case)?;
 
-    let walker = builder.build_parallel();
+    let matches: Mutex<Vec<GrepMatch>> = Mutex::new(Vec::new());
+    let total_matches = AtomicUsize::new(0);
+    let files_searched = AtomicUsize::new(0);
+    let unclassified_files: Mutex<Vec<String>> = Mutex::new(Vec::new());
 
-    walker.run(|| {
+    build_walker(root).run(|| {
         let matcher = &matcher;
         let matches = &matches;
         let total_matches = &total_matches;
         let files_searched = &files_searched;
+        let unclassified_files = &unclassified_files;
 
         Box::new(move |entry| {
-            let entry = match entry {
-                Ok(e) => e,
-                Err(_) => return ignore::WalkState::Continue,
-            };
-
-            // Skip directories
-            if !entry.file_type().map(|t| t.is_file()).unwrap_or(false) {
+            let Some(rel_path) = searchable_path(entry, root, filter) else {
                 return ignore::WalkState::Continue;
-            }
-
-            let path = entry.path();
-
-            // Apply filter if provided
-            let rel_path = path.strip_prefix(root).unwrap_or(path);
-            if let Some(f) = filter {
-                if !f.matches(rel_path) {
-                    return ignore::WalkState::Continue;
-                }
-            }
-
+            };
             files_searched.fetch_add(1, Ordering::Relaxed);
 
-            let mut searcher = Searcher::new();
-            let mut file_matches: Vec<GrepMatch> = Vec::new();
-
-            let rel_path = path
-                .strip_prefix(root)
-                .unwrap_or(path)
-                .to_string_lossy()
-                .to_string();
-
-            let _ = searcher.search_path(
-                matcher,
-                path,
-                UTF8(|line_num, line| {
-                    // Find match positions
-                    let mut start = 0;
-                    let mut end = 0;
-                    if let Ok(Some(m)) = matcher.find(line.as_bytes()) {
-                        start = m.start();
-                        end = m.end();
-                    }
+            let found = search_file(matcher, &root.join(&rel_path), &rel_path, scope);
+            if found.unclassified {
+                unclassified_files.lock().unwrap().push(rel_path);
+            }
 
-                    let trimmed = line.trim();
-                    // Adjust match positions for trimmed content
-                    let leading_ws = line.len() - line.trim_start().len();
-                    let adj_start = start.saturating_sub(leading_ws);
-                    let adj_end = end.saturating_sub(leading_ws).min(trimmed.len());
-
-                    file_matches.push(GrepMatch {
-                        file: rel_path.clone(),
-                        line: line_num as usize,
-                        content: trimmed.to_string(),
-                        start: adj_start,
-                        end: adj_end,
-                        symbol: None,
-                        symbol_start: None,
-                        symbol_end: None,
-                    });
-                    Ok(true)
-                }),
-            );
-
-            if !file_matches.is_empty() {
-                total_matches.fetch_add(file_matches.len(), Ordering::Relaxed);
+            if !found.matches.is_empty() {
+                total_matches.fetch_add(found.matches.len(), Ordering::Relaxed);
 
                 let mut guard = matches.lock().unwrap();
-                for m in file_matches {
+                for m in found.matches {
                     if guard.len() < limit {
                         guard.push(m);
                     }
@@ -165,6 +304,8 @@ pub fn grep(
     });
 
     let mut matches = matches.into_inner().unwrap();
+    let mut unclassified_files = unclassified_files.into_inner().unwrap();
+    unclassified_files.sort();
 
     // Enrich matches with containing symbol info
     add_symbol_context(&mut matches, root);
@@ -173,65 +314,162 @@ pub fn grep(
         matches,
         total_matches: total_matches.load(Ordering::Relaxed),
         files_searched: files_searched.load(Ordering::Relaxed),
+        unclassified_files,
     })
 }
 
+/// Search for a pattern in files, passing each match to `on_match` (with its
+/// containing symbol) as soon as its file has been searched, rather than
+/// collecting them. Stops after `limit` matches, or at the first error from
+/// `on_match`, which is returned.
+///
+/// Files are searched in parallel, so matches arrive grouped by file but in no
+/// particular file order.
+pub fn grep_each(
+    pattern: &str,
+    root: &Path,
+    filter: Option<&Filter>,
+    limit: usize,
+    ignore_case: bool,
+    scope: MatchScope,
+    on_match: &mut (dyn FnMut(&GrepMatch) -> io::Result<()> + Send),
+) -> io::Result<GrepSummary> {
+    let matcher = build_matcher(pattern, ignore_case)?;
+
+    // The sink also serializes output, so matches of one file stay together
+    let sink = Mutex::new((on_match, GrepSummary::default(), None::<io::Error>));
+    let files_searched = AtomicUsize::new(0);
+
+    build_walker(root).run(|| {
+        let matcher = &matcher;
+        let sink = &sink;
+        let files_searched = &files_searched;
+        let parser = SymbolParser::new();
+
+        Box::new(move |entry| {
+            let Some(rel_path) = searchable_path(entry, root, filter) else {
+                return ignore::WalkState::Continue;
+            };
+            files_searched.fetch_add(1, Ordering::Relaxed);
+
+            let path = root.join(&rel_path);
+            let mut found = search_file(matcher, &path, &rel_path, scope);
+            if !found.matches.is_empty()
+                && let Ok(content) = fs::read_to_string(&path)
+            {
+                annotate_symbols(&mut found.matches, &path, &content, &parser);
+            }
+
+            let mut guard = sink.lock().unwrap();
+            let (on_match, summary, error) = &mut *guard;
+            if error.is_some() || summary.matches >= limit {
+                return ignore::WalkState::Quit;
+            }
+            if found.unclassified {
+                summary.unclassified_files.push(rel_path);
+            }
+            for m in &found.matches {
+                if summary.matches >= limit {
+                    break;
+                }
+                if let Err(e) = on_match(m) {
+                    *error = Some(e);
+                    return ignore::WalkState::Quit;
+                }
+                summary.matches += 1;
+            }
+            if summary.matches >= limit {
+                return ignore::WalkState::Quit;
+            }
+            ignore::WalkState::Continue
+        })
+    });
+
+    let (_, mut summary, error) = sink.into_inner().unwrap();
+    if let Some(e) = error {
+        return Err(e);
+    }
+    summary.files_searched = files_searched.load(Ordering::Relaxed);
+    summary.unclassified_files.sort();
+    Ok(summary)
+}
+
+/// Path relative to `root` of a walked entry that should be searched: a file
+/// accepted by `filter`.
+fn searchable_path(
+    entry: Result<ignore::DirEntry, ignore::Error>,
+    root: &Path,
+    filter: Option<&Filter>,
+) -> Option<String> {
+    let entry = entry.ok()?;
+    // Skip directories
+    if !entry.file_type().is_some_and(|t| t.is_file()) {
+        return None;
+    }
+    let path = entry.path();
+    let rel_path = path.strip_prefix(root).unwrap_or(path);
+    if filter.is_some_and(|f| !f.matches(rel_path)) {
+        return None;
+    }
+    Some(rel_path.to_string_lossy().to_string())
+}
+
 /// Enrich grep matches with containing symbol information.
+///
+/// Each file's matches are contiguous, as [`grep`] adds them per file.
 fn add_symbol_context(matches: &mut [GrepMatch], root: &Path) {
     if matches.is_empty() {
         return;
     }
 
-    // Group match indices by file (clone file strings to avoid borrow issues)
-    let mut by_file: HashMap<String, Vec<usize>> = HashMap::new();
-    for (idx, m) in matches.iter().enumerate() {
-        by_file.entry(m.file.clone()).or_default().push(idx);
-    }
-
     let parser = SymbolParser::new();
 
     // For each file with matches, parse symbols and find containing symbol
-    for (file, indices) in by_file {
-        let path = root.join(&file);
+    for file_matches in matches.chunk_by_mut(|a, b| a.file == b.file) {
+        let path = root.join(&file_matches[0].file);
         let content = match fs::read_to_string(&path) {
             Ok(c) => c,
             Err(_) => continue,
         };
+        annotate_symbols(file_matches, &path, &content, &parser);
+    }
+}
 
-        let symbols = parser.parse_file(&path, &content);
-        if symbols.is_empty() {
-            continue;
-        }
+/// Set the containing symbol of each of `matches`, all in the file at `path`.
+fn annotate_symbols(matches: &mut [GrepMatch], path: &Path, content: &str, parser: &SymbolParser) {
+    let symbols = parser.parse_file(path, content);
+    if symbols.is_empty() {
+        return;
+    }
 
-        // For each match in this file, find the smallest containing symbol
-        for idx in indices {
-            let line = matches[idx].line;
-
-            // Find smallest symbol that contains this line
-            let mut best: Option<&crate::symbols::FlatSymbol> = None;
-            for sym in &symbols {
-                if sym.start_line <= line && line <= sym.end_line {
-                    // Prefer smaller (more specific) symbols
-                    if best.is_none()
-                        || (sym.end_line - sym.start_line)
-                            < (best.unwrap().end_line - best.unwrap().start_line)
-                    {
-                        best = Some(sym);
-                    }
+    // For each match in this file, find the smallest containing symbol
+    for m in matches {
+        let line = m.line;
+
+        // Find smallest symbol that contains this line
+        let mut best: Option<&crate::symbols::FlatSymbol> = None;
+        for sym in &symbols {
+            if sym.start_line <= line && line <= sym.end_line {
+                // Prefer smaller (more specific) symbols
+                if best.is_none()
+                    || (sym.end_line - sym.start_line)
+                        < (best.unwrap().end_line - best.unwrap().start_line)
+                {
+                    best = Some(sym);
                 }
             }
+        }
 
-            if let Some(sym) = best {
-                // Format name with parent if present (use / for consistency with moss paths)
-                let name = if let Some(parent) = &sym.parent {
-                    format!("{}/{}", parent, sym.name)
-                } else {
-                    sym.name.clone()
-                };
-                matches[idx].symbol = Some(name);
-                matches[idx].symbol_start = Some(sym.start_line);
-                matches[idx].symbol_end = Some(sym.end_line);
-            }
+        if let Some(sym) = best {
+            // Format name with parent if present (use / for consistency with moss paths)
+            let name = if let Some(parent) = &sym.parent {
+                format!("{}/{}", parent, sym.name)
+            } else {
+                sym.name.clone()
+            };
+            m.symbol = Some(name);
+            m.symbol_start = Some(sym.start_line);
+            m.symbol_end = Some(sym.end_line);
         }
     }
 }
@@ -334,7 +572,7 @@ mod tests {
         let file = dir.path().join("test.txt");
         fs::write(&file, "hello world\nfoo bar\nhello again").unwrap();
 
-        let result = grep("hello", dir.path(), None, 100, false).unwrap();
+        let result = grep("hello", dir.path(), None, 100, false, MatchScope::All).unwrap();
         assert_eq!(result.total_matches, 2);
         assert_eq!(result.matches.len(), 2);
         assert_eq!(result.matches[0].line, 1);
@@ -347,7 +585,7 @@ mod tests {
         let file = dir.path().join("test.txt");
         fs::write(&file, "Hello World\nHELLO AGAIN").unwrap();
 
-        let result = grep("hello", dir.path(), None, 100, true).unwrap();
+        let result = grep("hello", dir.path(), None, 100, true, MatchScope::All).unwrap();
         assert_eq!(result.total_matches, 2);
     }
 
@@ -357,8 +595,118 @@ mod tests {
         let file = dir.path().join("test.txt");
         fs::write(&file, "a\na\na\na\na").unwrap();
 
-        let result = grep("a", dir.path(), None, 2, false).unwrap();
+        let result = grep("a", dir.path(), None, 2, false, MatchScope::All).unwrap();
         assert_eq!(result.matches.len(), 2);
         assert!(result.total_matches >= 2);
     }
+
+    #[test]
+    fn test_grep_each() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.txt"), "x\n  a b a\n").unwrap();
+        fs::write(dir.path().join("b.txt"), "a\na\n").unwrap();
+
+        let mut seen = Vec::new();
+        let summary = grep_each(
+            "a",
+            dir.path(),
+            None,
+            100,
+            false,
+            MatchScope::All,
+            &mut |m| {
+                seen.push(m.clone());
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert_eq!(summary.matches, 3);
+        assert_eq!(seen.len(), 3);
+        let m = seen
+            .iter()
+            .find(|m| m.file == "a.txt")
+            .expect("match in a.txt");
+        assert_eq!((m.line, m.column, m.content.as_str()), (2, 3, "a b a"));
+
+        // Files arrive in no particular order, so only the count is known
+        let mut seen = Vec::new();
+        let summary = grep_each("a", dir.path(), None, 2, false, MatchScope::All, &mut |m| {
+            seen.push(m.clone());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(summary.matches, 2);
+        assert_eq!(seen.len(), 2);
+
+        // An error from the callback stops the search
+        let result = grep_each(
+            "a",
+            dir.path(),
+            None,
+            100,
+            false,
+            MatchScope::All,
+            &mut |_| Err(io::Error::from(io::ErrorKind::BrokenPipe)),
+        );
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+    }
+
+    #[test]
+    fn test_grep_code_only() {
+        // Skip if grammar not available
+        if crate::parsers::parser_for("rust").is_none() {
+            return;
+        }
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("lib.rs");
+        fs::write(
+            &file,
+            "// needle in comment\nfn needle() {}\nconst S: &str = \"needle\";\n",
+        )
+        .unwrap();
+
+        let result = grep("needle", dir.path(), None, 100, false, MatchScope::CodeOnly).unwrap();
+        assert_eq!(result.matches.len(), 1);
+        assert_eq!(result.matches[0].line, 2);
+    }
+
+    #[test]
+    fn test_grep_comments_only() {
+        // Skip if grammar not available
+        if crate::parsers::parser_for("rust").is_none() {
+            return;
+        }
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("lib.rs");
+        fs::write(
+            &file,
+            "// TODO: fix\nfn todo() {} // TODO later\nfn f() {}\n",
+        )
+        .unwrap();
+
+        let result = grep(
+            "TODO",
+            dir.path(),
+            None,
+            100,
+            false,
+            MatchScope::CommentsOnly,
+        )
+        .unwrap();
+        assert_eq!(result.matches.len(), 2);
+        assert_eq!(result.matches[1].line, 2);
+        assert_eq!(result.matches[1].content, "fn todo() {} // TODO later");
+        assert_eq!(result.matches[1].start, 16);
+    }
+
+    #[test]
+    fn test_grep_scope_falls_back_without_grammar() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("notes.unknownext");
+        fs::write(&file, "// needle\n").unwrap();
+
+        let result = grep("needle", dir.path(), None, 100, false, MatchScope::CodeOnly).unwrap();
+        assert_eq!(result.matches.len(), 1);
+        assert_eq!(result.unclassified_files, vec!["notes.unknownext"]);
+    }
 }
@@ -83,12 +83,70 @@ pub struct ViewNode {
     /// Docstring (for symbols)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub docstring: Option<String>,
+    /// Attributes, decorators or annotations (for symbols: "#[derive(Debug)]")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<String>,
     /// Line range in file (start, end)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub line_range: Option<(usize, usize)>,
     /// Grammar name for syntax highlighting (e.g., "rust", "python")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub grammar: Option<String>,
+    /// Directory has contents hidden by the depth limit
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+    /// Uncommitted-change marker: M/A/D/? for files, * for directories and
+    /// symbols containing changes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_status: Option<String>,
+    /// Last change to a symbol's first line (`view --blame`)
+    #[serde(flatten)]
+    pub blame: Option<LastChange>,
+}
+
+/// The commit that last changed a line, from `git blame`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LastChange {
+    pub last_author: String,
+    /// Full commit hash; all zeros for uncommitted changes
+    pub last_commit: String,
+    /// Author time (RFC 3339, UTC)
+    pub last_modified: String,
+    #[serde(skip)]
+    timestamp: i64,
+}
+
+impl LastChange {
+    /// Set the author time from a Unix timestamp.
+    pub fn set_time(&mut self, timestamp: i64) {
+        self.timestamp = timestamp;
+        self.last_modified = chrono::DateTime::from_timestamp(timestamp, 0)
+            .map(|t| t.to_rfc3339())
+            .unwrap_or_default();
+    }
+
+    /// `alice, 3mo ago, a1b2c3d`, or `uncommitted`.
+    pub fn summary(&self) -> String {
+        if self.last_commit.bytes().all(|b| b == b'0') {
+            return "uncommitted".to_string();
+        }
+        let age = chrono::Utc::now().timestamp() - self.timestamp;
+        let short = &self.last_commit[..self.last_commit.len().min(7)];
+        format!("{}, {}, {}", self.last_author, format_age(age), short)
+    }
+}
+
+/// Coarse age: `5m ago`, `3h ago`, `12d ago`, `3mo ago`, `2y ago`.
+fn format_age(secs: i64) -> String {
+    let secs = secs.max(0);
+    match secs {
+        0..60 => "just now".to_string(),
+        60..3600 => format!("{}m ago", secs / 60),
+        3600..86400 => format!("{}h ago", secs / 3600),
+        86400..2_592_000 => format!("{}d ago", secs / 86400),
+        2_592_000..31_536_000 => format!("{}mo ago", secs / 2_592_000),
+        _ => format!("{}y ago", secs / 31_536_000),
+    }
 }
 
 /// Type of node in the view tree.
@@ -112,8 +170,12 @@ impl ViewNode {
             children: Vec::new(),
             signature: None,
             docstring: None,
+            attributes: Vec::new(),
             line_range: None,
             grammar: None,
+            truncated: false,
+            git_status: None,
+            blame: None,
         }
     }
 
@@ -179,6 +241,8 @@ pub fn format_view_node(node: &ViewNode, options: &FormatOptions) -> Vec<String>
     let mut lines = Vec::new();
 
     if !options.skip_root {
+        format_attributes(node, "", options, &mut lines);
+
         // Root line: name with optional signature and line numbers
         let root_line = format_node_line(node, options);
         lines.push(root_line);
@@ -309,6 +373,26 @@ fn format_docstring_lines(
     }
 }
 
+/// Push a symbol's attributes/decorators, one line each, above its signature.
+fn format_attributes(
+    node: &ViewNode,
+    prefix: &str,
+    options: &FormatOptions,
+    lines: &mut Vec<String>,
+) {
+    for attr in &node.attributes {
+        let attr = match &node.grammar {
+            Some(grammar) if !options.minimal => {
+                highlight_source(attr, grammar, options.use_colors)
+            }
+            _ => attr.clone(),
+        };
+        for line in attr.lines() {
+            lines.push(format!("{}{}", prefix, line.trim()));
+        }
+    }
+}
+
 /// Format a single node line with optional line numbers.
 fn format_node_line(node: &ViewNode, options: &FormatOptions) -> String {
     let base = match &node.kind {
@@ -348,15 +432,21 @@ fn format_node_line(node: &ViewNode, options: &FormatOptions) -> String {
         }
         _ => node.name.clone(),
     };
+    let base = match &node.git_status {
+        Some(marker) => format!("{} {}", marker, base),
+        None => base,
+    };
 
     // Add line info for symbols if requested
-    if options.line_numbers {
-        if let Some((start, end)) = node.line_range {
-            return format!("{} L{}-{}", base, start, end);
-        }
-    }
+    let base = match node.line_range {
+        Some((start, end)) if options.line_numbers => format!("{} L{}-{}", base, start, end),
+        _ => base,
+    };
 
-    base
+    match &node.blame {
+        Some(change) => format!("{}  # {}", base, change.summary()),
+        None => base,
+    }
 }
 
 /// Elide visibility and declaration keywords for minimal output.
@@ -472,12 +562,7 @@ pub fn highlight_source(source: &str, grammar: &str, use_colors: bool) -> String
         None => return source.to_string(),
     };
 
-    let mut parser = tree_sitter::Parser::new();
-    if parser.set_language(&language).is_err() {
-        return source.to_string();
-    }
-
-    let tree = match parser.parse(source, None) {
+    let tree = match crate::parsers::parse_with_grammar(grammar, source) {
         Some(t) => t,
         None => return source.to_string(),
     };
@@ -579,12 +664,7 @@ fn collect_inner_spans(content: &str, grammar: &str, offset: usize) -> Vec<Highl
         None => return Vec::new(),
     };
 
-    let mut parser = tree_sitter::Parser::new();
-    if parser.set_language(&language).is_err() {
-        return Vec::new();
-    }
-
-    let tree = match parser.parse(content, None) {
+    let tree = match crate::parsers::parse_with_grammar(grammar, content) {
         Some(t) => t,
         None => return Vec::new(),
     };
@@ -991,7 +1071,7 @@ pub fn collect_highlight_spans(node: tree_sitter::Node, spans: &mut Vec<Highligh
 }
 
 /// Classify a node kind into a highlight category.
-fn classify_node_kind(kind: &str) -> HighlightKind {
+pub fn classify_node_kind(kind: &str) -> HighlightKind {
     match kind {
         // Comments (including doc comments)
         "comment"
@@ -1136,6 +1216,8 @@ fn format_children(
         // Always use plain indentation (no box-drawing chars)
         let child_prefix = format!("{}  ", prefix);
 
+        format_attributes(child, prefix, options, lines);
+
         // Format child line using shared formatter
         let child_line = format_node_line(child, options);
         lines.push(format!("{}{}", prefix, child_line));
@@ -1186,6 +1268,8 @@ pub struct TreeOptions {
     pub boilerplate_dirs: HashSet<String>,
     /// Include symbols inside files (requires depth > 1)
     pub include_symbols: bool,
+    /// Omit files, showing only directories
+    pub dirs_only: bool,
 }
 
 impl Default for TreeOptions {
@@ -1198,6 +1282,7 @@ impl Default for TreeOptions {
                 .map(|s| s.to_string())
                 .collect(),
             include_symbols: false,
+            dirs_only: false,
         }
     }
 }
@@ -1207,6 +1292,8 @@ impl Default for TreeOptions {
 struct InternalTreeNode {
     children: BTreeMap<String, InternalTreeNode>,
     is_dir: bool,
+    /// Some children were cut off by the depth limit
+    truncated: bool,
 }
 
 impl InternalTreeNode {
@@ -1230,6 +1317,7 @@ impl InternalTreeNode {
             let is_boilerplate = boilerplate_dirs.contains(name);
             // Block if we're at max depth, unless this is a boilerplate dir (which gets a pass)
             if effective_depth >= max && !is_boilerplate {
+                self.truncated = true;
                 return;
             }
         }
@@ -1284,6 +1372,9 @@ pub fn generate_view_tree(root: &Path, options: &TreeOptions) -> ViewNode {
             }
 
             let is_dir = path.is_dir();
+            if options.dirs_only && !is_dir {
+                continue;
+            }
             let parts: Vec<&str> = rel_str.split('/').filter(|s| !s.is_empty()).collect();
             if !parts.is_empty() {
                 tree.add_path(
@@ -1332,6 +1423,7 @@ fn tree_node_to_view_node(
     });
 
     // Handle single-child chain collapsing
+    let mut truncated = node.truncated;
     let (final_name, final_path, mut children) = if options.collapse_single && node.is_dir {
         let chain = collect_single_chain_internal(node, name);
         let collapsed_path = if parent_path.is_empty() {
@@ -1339,6 +1431,7 @@ fn tree_node_to_view_node(
         } else {
             format!("{}/{}", parent_path, chain.path)
         };
+        truncated = chain.end_node.truncated;
 
         let child_nodes: Vec<ViewNode> = chain
             .end_node
@@ -1377,8 +1470,12 @@ fn tree_node_to_view_node(
         children,
         signature: None,
         docstring: None,
+        attributes: Vec::new(),
         line_range: None,
         grammar: None,
+        truncated,
+        git_status: None,
+        blame: None,
     }
 }
 
@@ -1426,8 +1523,12 @@ fn skeleton_to_view_node(sym: &SkeletonSymbol, parent_path: &str, grammar: &str)
         children,
         signature: Some(sym.signature.clone()),
         docstring: sym.docstring.clone(),
+        attributes: sym.attributes.clone(),
         line_range: Some((sym.start_line, sym.end_line)),
         grammar: Some(grammar.to_string()),
+        truncated: false,
+        git_status: None,
+        blame: None,
     }
 }
 
@@ -1472,6 +1573,35 @@ mod tests {
     use std::fs;
     use tempfile::tempdir;
 
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(-5), "just now");
+        assert_eq!(format_age(125), "2m ago");
+        assert_eq!(format_age(3 * 86400), "3d ago");
+        assert_eq!(format_age(95 * 86400), "3mo ago");
+        assert_eq!(format_age(800 * 86400), "2y ago");
+    }
+
+    #[test]
+    fn test_format_attributes_above_signature() {
+        let mut symbol = ViewNode::file("get", "app.py/get");
+        symbol.kind = ViewNodeKind::Symbol("function".to_string());
+        symbol.signature = Some("def get(id)".to_string());
+        symbol.attributes = vec!["@app.route(\"/items\")".to_string(), "@cached".to_string()];
+        let file = ViewNode::file("app.py", "app.py").with_children(vec![symbol]);
+
+        let lines = format_view_node(&file, &FormatOptions::default());
+        assert_eq!(
+            lines,
+            vec![
+                "app.py",
+                "@app.route(\"/items\")",
+                "@cached",
+                "def get(id):"
+            ]
+        );
+    }
+
     #[test]
     fn test_view_tree() {
         let dir = tempdir().unwrap();
@@ -1500,10 +1630,39 @@ mod tests {
                 collapse_single: false,
                 boilerplate_dirs: HashSet::new(),
                 include_symbols: false,
+                dirs_only: false,
             },
         );
 
         // Should return a ViewNode structure
         assert_eq!(result.kind, ViewNodeKind::Directory);
+        let beta = &result.children[0].children[0];
+        assert_eq!(beta.name, "beta");
+        assert!(beta.children.is_empty());
+        assert!(beta.truncated);
+    }
+
+    #[test]
+    fn test_view_tree_dirs_only() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("docs")).unwrap();
+        fs::create_dir_all(dir.path().join("src/foo")).unwrap();
+        fs::write(dir.path().join("src/main.rs"), "").unwrap();
+        fs::write(dir.path().join("README.md"), "").unwrap();
+
+        let options = TreeOptions {
+            dirs_only: true,
+            ..Default::default()
+        };
+        let result = generate_view_tree(dir.path(), &options);
+        let names: Vec<&str> = result.children.iter().map(|c| c.name.as_str()).collect();
+        // With main.rs hidden, src/foo collapses into one node
+        assert_eq!(names, vec!["docs", "src/foo"]);
+        assert!(
+            result
+                .children
+                .iter()
+                .all(|c| c.kind == ViewNodeKind::Directory)
+        );
     }
 }